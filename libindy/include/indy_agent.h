@@ -16,6 +16,14 @@ extern "C" {
 ///
 /// Note that messages encryption/decryption will be performed automatically.
 ///
+/// Transport is selected by scheme of receiver endpoint: tcp:// or endpoint without scheme
+/// is served by ZeroMQ with CurveCP, http:// and https:// endpoints are served by HTTP POST
+/// with messages encrypted by libindy. Over HTTP incoming messages are polled periodically.
+///
+/// Established connection is checked by periodic heartbeats. If remote agent doesn't respond
+/// or restarts, connection is re-established automatically with growing delay between attempts.
+/// While connection is lost outgoing messages are queued and sent after reconnect.
+///
 /// #Params
 /// command_handle: Command handle to map callback to caller context.
 /// pool_handle: Pool handle (created by open_pool_ledger).
@@ -26,6 +34,11 @@ extern "C" {
 ///     Will be called exactly once with result of connect operation.
 /// message_cb: Callback that will be called on receiving of an incoming message.
 ///     Can be called multiply times: once for each incoming message.
+///     Messages sent with id are passed to callback set by indy_agent_set_message_id_cb if any.
+///     Also notifies about connection loss without message by AgentConnectionLostError.
+///     Reconnect isn't notified: see state and stats.reconnects in indy_agent_get_connection_info.
+///     AgentMessageTooLargeError is passed if remote listener rejected sent message by size.
+///     Binary messages are passed to callback set by indy_agent_set_binary_message_cb.
 ///
 /// #Returns
 /// Error code
@@ -53,6 +66,46 @@ extern indy_error_t indy_agent_connect(indy_handle_t command_handle,
                                                               const char *  message)
                                        );
 
+/// Establishes agent to agent connection for pairwise stored in the wallet.
+///
+/// Works as indy_agent_connect with my DID of pairwise (created by indy_create_pairwise)
+/// as sender and their DID as receiver.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// pool_handle: Pool handle (created by open_pool_ledger).
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// their_did: Id of receiver Identity that identifies pairwise.
+/// connection_cb: Callback that will be called after establishing of connection or on error.
+///     Will be called exactly once with result of connect operation.
+/// message_cb: Callback that will be called on receiving of an incoming message
+///     or on connection state change as described for indy_agent_connect.
+///
+/// #Returns
+/// Error code
+/// connection_cb:
+/// - xcommand_handle: command handle to map callback to caller context.
+/// - err: Error code.
+/// - connection_handle: Connection handle to use for messages sending and mapping of incomming messages to this connection.
+/// message_cb:
+/// - xconnection_handle: Connection handle. Indetnifies connection.
+/// - err: Error code.
+/// - message: Received message.
+
+extern indy_error_t indy_agent_connect_pairwise(indy_handle_t command_handle,
+                                                indy_handle_t pool_handle,
+                                                indy_handle_t wallet_handle,
+                                                const char *  their_did,
+
+                                                void  (*connection_cb)(indy_handle_t xcommand_handle,
+                                                                       indy_error_t  err,
+                                                                       indy_handle_t connection_handle),
+
+                                                void     (*message_cb)(indy_handle_t xconnection_handle,
+                                                                       indy_error_t  err,
+                                                                       const char *  message)
+                                                );
+
 /// Starts listening of agent connections.
 ///
 /// Listener will accept only connections to registered DIDs by indy_agent_add_identity call.
@@ -64,6 +117,12 @@ extern indy_error_t indy_agent_connect(indy_handle_t command_handle,
 ///
 /// Note that messages encryption/decryption will be performed automatically.
 ///
+/// Transport is selected by endpoint scheme: tcp:// or endpoint without scheme (host:port)
+/// starts ZeroMQ CurveCP listener, http:// starts HTTP listener. https:// isn't supported for
+/// listening, use TLS-terminating proxy in front of http listener instead.
+///
+/// Listener uses default config, see indy_agent_listen_with_config.
+///
 /// #Params
 /// command_handle: command handle to map callback to caller context.
 /// endpoint: endpoint to use in starting listener.
@@ -73,6 +132,8 @@ extern indy_error_t indy_agent_connect(indy_handle_t command_handle,
 ///     Can be called multiply times: once for each incoming connection.
 /// message_cb: Callback that will be called on receiving of an incoming message.
 ///     Can be called multiply times: once for each incoming message.
+///     Messages sent with id are passed to callback set by indy_agent_set_message_id_cb if any.
+///     Binary messages are passed to callback set by indy_agent_set_binary_message_cb.
 ///
 /// #Returns
 /// Error code
@@ -109,6 +170,68 @@ extern indy_error_t indy_agent_listen(indy_handle_t command_handle,
                                                               const char *  message)
                                       );
 
+/// Starts listening of agent connections with listener config.
+///
+/// Works as indy_agent_listen, config limits incoming messages and senders allowed to connect.
+///
+/// Incoming messages larger than maximum message size are rejected: sender receives
+/// AgentMessageTooLargeError in message_cb and message isn't passed to the listener.
+///
+/// Sender is checked against listener policy before connection is confirmed. Deny lists take
+/// precedence over allow lists. Role rules require sender NYM lookup on the ledger even if sender
+/// keys are stored in the wallet. Application can add own check with indy_agent_set_authorization_cb.
+/// Connection attempts over rate limit are rejected before any wallet or ledger lookup.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// endpoint: endpoint to use in starting listener.
+/// config: (optional) listener config json.
+///     {
+///         "max_message_size": <int> Optional maximum size of incoming message in bytes, 1048576 by default.
+///             Larger message is answered with msg_too_large, zmq frame above 4 times the limit
+///             is dropped and its connection is closed.
+///         "allow_dids": <[string]> Optional list of sender DIDs allowed to connect, any DID by default.
+///         "deny_dids": <[string]> Optional list of sender DIDs rejected by listener.
+///         "allow_roles": <[string]> Optional list of sender ledger roles allowed to connect:
+///             TRUSTEE, STEWARD, TRUST_ANCHOR, TGB or USER for NYM without role.
+///         "deny_roles": <[string]> Optional list of sender ledger roles rejected by listener.
+///         "rate_limit": Optional limit of connection attempts per sender {
+///             "max_attempts": <int> attempts of one sender key with one DID and accepted attempts
+///                 of one DID allowed during interval,
+///             "interval_secs": <int> interval in seconds,
+///             "max_total_attempts": <int> Optional attempts of all senders allowed during interval, 1024 by default
+///         }
+///     }
+/// listener_cb: Callback that will be called after listening started or on error.
+///     Will be called exactly once with result of start listen operation.
+/// connection_cb: Callback that will be called after establishing of incoming connection.
+///     Can be called multiply times: once for each incoming connection.
+/// message_cb: Callback that will be called on receiving of an incoming message.
+///     Can be called multiply times: once for each incoming message.
+///
+/// #Returns
+/// Error code
+/// listener_cb, connection_cb and message_cb are called as for indy_agent_listen.
+
+extern indy_error_t indy_agent_listen_with_config(indy_handle_t command_handle,
+                                                  const char *  endpoint,
+                                                  const char *  config,
+
+                                                  void     (*listener_cb)(indy_handle_t xcommand_handle,
+                                                                          indy_error_t  err,
+                                                                          indy_handle_t listener_handle),
+
+                                                  void   (*connection_cb)(indy_handle_t xlistener_handle,
+                                                                          indy_error_t  err,
+                                                                          indy_handle_t connection_handle,
+                                                                          const char *  sender_did,
+                                                                          const char *  receiver_did),
+
+                                                  void      (*message_cb)(indy_handle_t xconnection_handle,
+                                                                          indy_error_t  err,
+                                                                          const char *  message)
+                                                  );
+
 /// Add identity to listener.
 ///
 /// Performs wallet lookup to find corresponded receiver Identity information.
@@ -177,10 +300,126 @@ extern indy_error_t indy_agent_remove_identity(indy_handle_t command_handle,
                                                                       indy_error_t  err)
                                                );
 
+/// Sets application callback consulted before incoming connection is confirmed.
+///
+/// Callback is called after sender key is verified and listener policy from indy_agent_listen
+/// config allowed the sender. Connection is accepted only if callback returns true.
+/// Callback is called on libindy thread, so it must return quickly and mustn't wait for
+/// other libindy calls.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// listener_handle: listener handle (created by indy_agent_listen).
+/// authorization_cb: (optional) authorization callback. Pass null to remove callback set before.
+/// cb: Callback that will be called after authorization callback set or on error.
+///     Will be called exactly once.
+///
+/// #Returns
+/// Error code
+/// authorization_cb:
+/// - xlistener_handle: Listener handle. Identifies listener.
+/// - sender_did: DID of connecting sender.
+/// - receiver_did: DID of listener identity sender connects to.
+/// - role: Sender role from the ledger. Null if sender keys were found in the wallet.
+/// - returns true to accept connection, false to reject it.
+/// cb:
+/// - xcommand_handle: command handle to map callback to caller context.
+/// - err: Error code
+
+extern indy_error_t indy_agent_set_authorization_cb(indy_handle_t command_handle,
+                                                    indy_handle_t listener_handle,
+
+                                                    indy_bool_t (*authorization_cb)(indy_handle_t xlistener_handle,
+                                                                                    const char *  sender_did,
+                                                                                    const char *  receiver_did,
+                                                                                    const char *  role),
+
+                                                    void (*cb)(indy_handle_t xcommand_handle,
+                                                               indy_error_t  err)
+                                                    );
+
+/// Sets callback for messages sent with id by indy_agent_send_with_ack, indy_agent_request
+/// and indy_agent_reply.
+///
+/// Callback set for listener is used for all its incoming connections, callback set for
+/// connection takes precedence. If callback isn't set, such messages are passed to message_cb
+/// without id, so requests can't be answered.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// handle: Connection handle returned by indy_agent_connect or indy_agent_listen calls
+///     or listener handle returned by indy_agent_listen call.
+/// message_id_cb: (optional) message id callback. Pass null to remove callback set before.
+/// cb: Callback that will be called after message id callback set or on error.
+///     Will be called exactly once.
+///
+/// #Returns
+/// Error code
+/// message_id_cb:
+/// - xconnection_handle: Connection handle. Identifies connection.
+/// - message: Received message.
+/// - message_id: Id of message. Pass it to indy_agent_reply to answer request.
+/// cb:
+/// - xcommand_handle: command handle to map callback to caller context.
+/// - err: Error code
+///
+/// #Errors
+/// CommonInvalidStructure - connection or listener with passed handle isn't found.
+
+extern indy_error_t indy_agent_set_message_id_cb(indy_handle_t command_handle,
+                                                 indy_handle_t handle,
+
+                                                 void (*message_id_cb)(indy_handle_t xconnection_handle,
+                                                                       const char *  message,
+                                                                       const char *  message_id),
+
+                                                 void (*cb)(indy_handle_t xcommand_handle,
+                                                            indy_error_t  err)
+                                                 );
+
+/// Sets callback for binary messages sent by indy_agent_send_bytes.
+///
+/// Callback set for listener is used for all its incoming connections, callback set for
+/// connection takes precedence. If callback isn't set, binary messages are dropped.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// handle: Connection handle returned by indy_agent_connect or indy_agent_listen calls
+///     or listener handle returned by indy_agent_listen call.
+/// binary_message_cb: (optional) binary message callback. Pass null to remove callback set before.
+/// cb: Callback that will be called after binary message callback set or on error.
+///     Will be called exactly once.
+///
+/// #Returns
+/// Error code
+/// binary_message_cb:
+/// - xconnection_handle: Connection handle. Identifies connection.
+/// - message_raw: a pointer to first byte of received message.
+/// - message_len: a message length.
+/// cb:
+/// - xcommand_handle: command handle to map callback to caller context.
+/// - err: Error code
+///
+/// #Errors
+/// CommonInvalidStructure - connection or listener with passed handle isn't found.
+
+extern indy_error_t indy_agent_set_binary_message_cb(indy_handle_t command_handle,
+                                                     indy_handle_t handle,
+
+                                                     void (*binary_message_cb)(indy_handle_t     xconnection_handle,
+                                                                               const indy_u8_t*  message_raw,
+                                                                               indy_u32_t        message_len),
+
+                                                     void (*cb)(indy_handle_t xcommand_handle,
+                                                                indy_error_t  err)
+                                                     );
+
 /// Sends message to connected agent.
 ///
 /// Note that this call works for both incoming and outgoing connections.
 /// Note that messages encryption/decryption will be performed automatically.
+/// Note that messages to outgoing connection that is reconnecting are queued, so success result
+/// means message is accepted for delivery.
 ///
 /// #Params
 /// command_handle: command handle to map callback to caller context.
@@ -195,6 +434,7 @@ extern indy_error_t indy_agent_remove_identity(indy_handle_t command_handle,
 /// - err: Error code
 ///
 /// #Errors
+/// AgentOutboundQueueFullError - connection is reconnecting and too many messages are queued already.
 
 extern indy_error_t indy_agent_send(indy_handle_t command_handle,
                                     indy_handle_t connection_handle,
@@ -204,6 +444,135 @@ extern indy_error_t indy_agent_send(indy_handle_t command_handle,
                                                    indy_error_t  err)
                                    );
 
+/// Sends binary message to connected agent.
+///
+/// Message is passed as is without text encoding and received by callback set with
+/// indy_agent_set_binary_message_cb on remote side. Message larger than maximum message size of remote listener is rejected
+/// by it with AgentMessageTooLargeError passed to message_cb of this connection.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// connection_handle: Connection handle returned by indy_agent_connect or indy_agent_listen calls.
+/// message_raw: a pointer to first byte of message to send.
+/// message_len: a message length.
+/// cb: Callback that will be called after message sent or on error. Will be called exactly once.
+///
+/// #Returns
+/// err: Error code
+/// cb:
+/// - xcommand_handle: Command handle to map callback to caller context.
+/// - err: Error code
+///
+/// #Errors
+/// AgentOutboundQueueFullError - connection is reconnecting and too many messages are queued already.
+
+extern indy_error_t indy_agent_send_bytes(indy_handle_t     command_handle,
+                                          indy_handle_t     connection_handle,
+                                          const indy_u8_t*  message_raw,
+                                          indy_u32_t        message_len,
+
+                                          void     (*cb)(indy_handle_t xcommand_handle,
+                                                         indy_error_t  err)
+                                         );
+
+/// Sends message to connected agent and waits for delivery acknowledgement.
+///
+/// Message is sent with id that is passed to receiver callback set by indy_agent_set_message_id_cb.
+/// Receiving libindy acknowledges delivery automatically before passing message to application.
+/// Note that this call works for both incoming and outgoing connections.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// connection_handle: Connection handle returned by indy_agent_connect or indy_agent_listen calls.
+/// message_id: (optional) Id of message. Must be unique for connection, random id is generated if null.
+/// message: Message to send.
+/// cb: Callback that will be called after delivery acknowledgement received or on error.
+///     Will be called exactly once.
+///
+/// #Returns
+/// err: Error code
+/// cb:
+/// - xcommand_handle: Command handle to map callback to caller context.
+/// - err: Error code
+/// - message_id: Id of delivered message.
+///
+/// #Errors
+/// AgentOutboundQueueFullError - connection is reconnecting and too many messages are queued already.
+/// AgentTimeoutError - delivery acknowledgement isn't received in time.
+
+extern indy_error_t indy_agent_send_with_ack(indy_handle_t command_handle,
+                                             indy_handle_t connection_handle,
+                                             const char *  message_id,
+                                             const char *  message,
+
+                                             void     (*cb)(indy_handle_t xcommand_handle,
+                                                            indy_error_t  err,
+                                                            const char *  message_id)
+                                            );
+
+/// Sends request to connected agent and waits for correlated response.
+///
+/// Other side receives request with message id in callback set by indy_agent_set_message_id_cb
+/// and answers with indy_agent_reply. Response is passed to cb of this call instead of message_cb.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// connection_handle: Connection handle returned by indy_agent_connect or indy_agent_listen calls.
+/// message: Request message to send.
+/// timeout_ms: Time to wait for response in milliseconds, must be greater than 0.
+/// cb: Callback that will be called after response received, on timeout or on error.
+///     Will be called exactly once.
+///
+/// #Returns
+/// err: Error code
+/// cb:
+/// - xcommand_handle: Command handle to map callback to caller context.
+/// - err: Error code
+/// - response: Received response message.
+///
+/// #Errors
+/// AgentOutboundQueueFullError - connection is reconnecting and too many messages are queued already.
+/// AgentTimeoutError - response isn't received in time.
+
+extern indy_error_t indy_agent_request(indy_handle_t command_handle,
+                                       indy_handle_t connection_handle,
+                                       const char *  message,
+                                       indy_u32_t    timeout_ms,
+
+                                       void     (*cb)(indy_handle_t xcommand_handle,
+                                                      indy_error_t  err,
+                                                      const char *  response)
+                                      );
+
+/// Sends response to request received by callback set by indy_agent_set_message_id_cb.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// connection_handle: Connection handle the request was received from.
+/// request_id: message_id passed to message id callback with request.
+/// message: Response message to send.
+/// cb: Callback that will be called after delivery acknowledgement received or on error.
+///     Will be called exactly once.
+///
+/// #Returns
+/// err: Error code
+/// cb:
+/// - xcommand_handle: Command handle to map callback to caller context.
+/// - err: Error code
+///
+/// #Errors
+/// AgentOutboundQueueFullError - connection is reconnecting and too many messages are queued already.
+/// AgentTimeoutError - delivery acknowledgement isn't received in time.
+
+extern indy_error_t indy_agent_reply(indy_handle_t command_handle,
+                                     indy_handle_t connection_handle,
+                                     const char *  request_id,
+                                     const char *  message,
+
+                                     void     (*cb)(indy_handle_t xcommand_handle,
+                                                    indy_error_t  err)
+                                    );
+
 /// Closes agent connection.
 ///
 /// Note that this call works for both incoming and outgoing connections.
@@ -253,6 +622,73 @@ extern indy_error_t indy_agent_close_listener(indy_handle_t command_handle,
                                                              indy_error_t  err)
                                               );
 
+/// Returns info of all opened agent connections: outgoing ones created by indy_agent_connect
+/// and incoming ones accepted by listeners.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// cb: Callback that will be called with connections info or on error. Will be called exactly once.
+///
+/// #Returns
+/// Error code
+/// cb:
+/// - xcommand_handle: command handle to map callback to caller context.
+/// - err: Error code
+/// - connections_json: list of connection info json described in indy_agent_get_connection_info.
+///     [<connection info json>, ...]
+
+extern indy_error_t indy_agent_list_connections(indy_handle_t command_handle,
+
+                                                void (*cb)(indy_handle_t xcommand_handle,
+                                                           indy_error_t  err,
+                                                           const char *  connections_json)
+                                                );
+
+/// Returns info of opened agent connection.
+///
+/// Message counters include application messages and delivery acknowledgements:
+/// heartbeats and handshake aren't counted.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// connection_handle: Connection handle returned by indy_agent_connect or indy_agent_listen calls.
+/// cb: Callback that will be called with connection info or on error. Will be called exactly once.
+///
+/// #Returns
+/// Error code
+/// cb:
+/// - xcommand_handle: command handle to map callback to caller context.
+/// - err: Error code
+/// - connection_info_json: connection info json.
+///     {
+///         "connection_handle": <int>,
+///         "sender_did": <string> DID of connection initiator,
+///         "receiver_did": <string> DID of listener identity,
+///         "endpoint": <string> remote endpoint for outgoing connection, listener endpoint for incoming one,
+///         "direction": "outgoing" or "incoming",
+///         "listener_handle": <int> handle of listener accepted incoming connection, null for outgoing one,
+///         "state": "connecting", "connected" or "disconnected",
+///         "opened_at": <int> unix timestamp of connection creation,
+///         "stats": {
+///             "messages_sent": <int>,
+///             "messages_received": <int>,
+///             "bytes_sent": <int>,
+///             "bytes_received": <int>,
+///             "reconnects": <int> count of restores of the connection after loss
+///         }
+///     }
+///
+/// #Errors
+/// CommonInvalidStructure - connection with passed handle isn't found.
+
+extern indy_error_t indy_agent_get_connection_info(indy_handle_t command_handle,
+                                                   indy_handle_t connection_handle,
+
+                                                   void (*cb)(indy_handle_t xcommand_handle,
+                                                              indy_error_t  err,
+                                                              const char *  connection_info_json)
+                                                   );
+
 #ifdef __cplusplus
 }
 #endif
@@ -53,6 +53,22 @@ extern "C" {
                                                                       const char*   revoc_reg_update_json)
                                                  );
     
+    extern indy_error_t indy_issuer_generate_safe_primes(indy_handle_t command_handle,
+                                                         indy_handle_t wallet_handle,
+                                                         indy_i32_t    count,
+
+                                                         void           (*cb)(indy_handle_t xcommand_handle,
+                                                                              indy_error_t  err)
+                                                         );
+
+    extern indy_error_t indy_issuer_import_safe_primes(indy_handle_t command_handle,
+                                                       indy_handle_t wallet_handle,
+                                                       const char *  safe_primes_json,
+
+                                                       void           (*cb)(indy_handle_t xcommand_handle,
+                                                                            indy_error_t  err)
+                                                       );
+    
     extern indy_error_t indy_prover_store_claim_offer(indy_handle_t command_handle,
                                                       indy_handle_t wallet_handle,
                                                       const char *  claim_offer_json,
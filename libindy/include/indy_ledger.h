@@ -102,6 +102,7 @@ extern "C" {
 
 
     /// Builds a request to get a DDO.
+    /// Note that GET_DDO transaction isn't supported by the Ledger yet, use indy_resolve_did to get a DID document.
     ///
     /// #Params
     /// command_handle: command handle to map callback to caller context.
@@ -379,7 +380,250 @@ extern "C" {
                                                                         indy_error_t  err,
                                                                         const char*   request_json)
                                                    );
-    
+
+    /// Builds a REVOC_REG_DEF request. Request to publish definition of revocation registry:
+    /// public key of accumulator and max number of claims.
+    /// Revocation registry is identified on the ledger by "<issuer_did>:<schema_seq_no>".
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// submitter_did: Id of Identity stored in secured Wallet.
+    /// data: revocation registry json returned by indy_issuer_create_and_store_revoc_reg.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Request result as json.
+    ///
+    /// #Errors
+    /// Common*
+
+    extern indy_error_t indy_build_revoc_reg_def_request(indy_handle_t command_handle,
+                                                         const char *  submitter_did,
+                                                         const char *  data,
+
+                                                         void           (*cb)(indy_handle_t xcommand_handle,
+                                                                              indy_error_t  err,
+                                                                              const char*   request_json)
+                                                        );
+
+    /// Builds a REVOC_REG_ENTRY request. Request to publish current accumulator of revocation registry.
+    /// Should be sent after each change of revocation registry to let verifiers check non-revocation.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// submitter_did: Id of Identity stored in secured Wallet.
+    /// data: revocation registry update json returned by indy_issuer_create_claim or indy_issuer_revoke_claim.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Request result as json.
+    ///
+    /// #Errors
+    /// Common*
+
+    extern indy_error_t indy_build_revoc_reg_entry_request(indy_handle_t command_handle,
+                                                           const char *  submitter_did,
+                                                           const char *  data,
+
+                                                           void           (*cb)(indy_handle_t xcommand_handle,
+                                                                                indy_error_t  err,
+                                                                                const char*   request_json)
+                                                          );
+
+    /// Builds a GET_REVOC_REG_DEF request. Request to get definition of revocation registry.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// submitter_did: Id of Identity stored in secured Wallet.
+    /// id: revocation registry id: "<issuer_did>:<schema_seq_no>".
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Request result as json.
+    ///
+    /// #Errors
+    /// Common*
+
+    extern indy_error_t indy_build_get_revoc_reg_def_request(indy_handle_t command_handle,
+                                                             const char *  submitter_did,
+                                                             const char *  id,
+
+                                                             void           (*cb)(indy_handle_t xcommand_handle,
+                                                                                  indy_error_t  err,
+                                                                                  const char*   request_json)
+                                                            );
+
+    /// Builds a GET_REVOC_REG request. Request to get accumulator of revocation registry
+    /// as it was at the given time.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// submitter_did: Id of Identity stored in secured Wallet.
+    /// revoc_reg_def_id: revocation registry id: "<issuer_did>:<schema_seq_no>".
+    /// timestamp: time (unix timestamp in seconds) the accumulator should be actual for.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Request result as json.
+    ///
+    /// #Errors
+    /// Common*
+
+    extern indy_error_t indy_build_get_revoc_reg_request(indy_handle_t command_handle,
+                                                         const char *  submitter_did,
+                                                         const char *  revoc_reg_def_id,
+                                                         indy_i64_t    timestamp,
+
+                                                         void           (*cb)(indy_handle_t xcommand_handle,
+                                                                              indy_error_t  err,
+                                                                              const char*   request_json)
+                                                        );
+
+    /// Parses a GET_SCHEMA response to get Schema in the format compatible with Anoncreds API.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// get_schema_response: response of GET_SCHEMA request.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Schema json:
+    /// {
+    ///     "seqNo": number, // sequence number of schema transaction in ledger
+    ///     "data": {
+    ///         "name": string,
+    ///         "version": string,
+    ///         "keys": [string] // attr_names of schema transaction
+    ///     }
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// LedgerNotFound if schema is not found on the ledger
+
+    extern indy_error_t indy_parse_get_schema_response(indy_handle_t command_handle,
+                                                       const char *  get_schema_response,
+
+                                                       void           (*cb)(indy_handle_t xcommand_handle,
+                                                                            indy_error_t  err,
+                                                                            const char*   schema_json)
+                                                      );
+
+    /// Parses a GET_CLAIM_DEF response to get Claim Definition in the format compatible with Anoncreds API.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// get_claim_def_response: response of GET_CLAIM_DEF request.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Claim definition json:
+    /// {
+    ///     "ref": number, // sequence number of schema
+    ///     "origin": string, // issuer did
+    ///     "signature_type": string,
+    ///     "data": {
+    ///         "primary": primary public key,
+    ///         "revocation": revocation public key or null
+    ///     }
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// LedgerNotFound if claim definition is not found on the ledger
+
+    extern indy_error_t indy_parse_get_claim_def_response(indy_handle_t command_handle,
+                                                          const char *  get_claim_def_response,
+
+                                                          void           (*cb)(indy_handle_t xcommand_handle,
+                                                                               indy_error_t  err,
+                                                                               const char*   claim_def_json)
+                                                         );
+
+    /// Parses a GET_NYM response to get NYM data.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// get_nym_response: response of GET_NYM request.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// NYM data json:
+    /// {
+    ///     "identifier": string or null, // did of NYM transaction author
+    ///     "dest": string, // target did
+    ///     "role": string or null, // role code
+    ///     "verkey": string or null // verkey, may be abbreviated
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// LedgerNotFound if NYM is not found on the ledger
+
+    extern indy_error_t indy_parse_get_nym_response(indy_handle_t command_handle,
+                                                    const char *  get_nym_response,
+
+                                                    void           (*cb)(indy_handle_t xcommand_handle,
+                                                                         indy_error_t  err,
+                                                                         const char*   nym_json)
+                                                   );
+
+    /// Parses a GET_REVOC_REG_DEF response to get definition of revocation registry.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// get_revoc_reg_def_response: response of GET_REVOC_REG_DEF request.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Revocation registry definition json:
+    /// {
+    ///     "id": string, // revocation registry id
+    ///     "ref": number, // sequence number of schema
+    ///     "value": {
+    ///         "issuer_did": string,
+    ///         "max_claim_num": number,
+    ///         "acc_pk": accumulator public key
+    ///     }
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// LedgerNotFound if revocation registry definition is not found on the ledger
+
+    extern indy_error_t indy_parse_get_revoc_reg_def_response(indy_handle_t command_handle,
+                                                              const char *  get_revoc_reg_def_response,
+
+                                                              void           (*cb)(indy_handle_t xcommand_handle,
+                                                                                   indy_error_t  err,
+                                                                                   const char*   revoc_reg_def_json)
+                                                             );
+
+    /// Parses a GET_REVOC_REG response to get revocation registry in the format compatible with Anoncreds API
+    /// (revoc_regs_json of indy_prover_create_proof and indy_verifier_verify_proof).
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// revoc_reg_def_json: revocation registry definition json returned by indy_parse_get_revoc_reg_def_response.
+    /// get_revoc_reg_response: response of GET_REVOC_REG request.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Revocation registry json with accumulator from the ledger.
+    ///
+    /// #Errors
+    /// Common*
+    /// LedgerNotFound if revocation registry entry is not found on the ledger
+
+    extern indy_error_t indy_parse_get_revoc_reg_response(indy_handle_t command_handle,
+                                                          const char *  revoc_reg_def_json,
+                                                          const char *  get_revoc_reg_response,
+
+                                                          void           (*cb)(indy_handle_t xcommand_handle,
+                                                                               indy_error_t  err,
+                                                                               const char*   revoc_reg_json)
+                                                         );
+
 #ifdef __cplusplus
 }
 #endif
@@ -0,0 +1,123 @@
+#ifndef __indy__pairwise__included__
+#define __indy__pairwise__included__
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+    /// Creates pairwise: persistent relationship between my DID and their DID in the wallet.
+    ///
+    /// Both DIDs must be already stored in the wallet: my DID with indy_create_and_store_my_did
+    /// and their DID with indy_store_their_did. Pairwise for the same their DID is replaced.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// their_did: DID of the other party.
+    /// my_did: my DID used in relationship with the other party.
+    /// metadata Optional: some extra information for pairwise (for example, label).
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_create_pairwise(indy_handle_t command_handle,
+                                             indy_handle_t wallet_handle,
+                                             const char *  their_did,
+                                             const char *  my_did,
+                                             const char *  metadata,
+
+                                             void           (*cb)(indy_handle_t xcommand_handle,
+                                                                  indy_error_t  err)
+                                            );
+
+    /// Get list of saved pairwise.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// list_pairwise: list of saved pairwise
+    /// [{
+    ///     "my_did": string,
+    ///     "their_did": string,
+    ///     "metadata": Optional<string>
+    /// }]
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_list_pairwise(indy_handle_t command_handle,
+                                           indy_handle_t wallet_handle,
+
+                                           void           (*cb)(indy_handle_t xcommand_handle,
+                                                                indy_error_t  err,
+                                                                const char *  list_pairwise)
+                                          );
+
+    /// Gets pairwise information for specific their_did.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// their_did: DID of the other party.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// pairwise_info_json: did info associated with their did
+    /// {
+    ///     "my_did": string,
+    ///     "metadata": Optional<string>
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_get_pairwise(indy_handle_t command_handle,
+                                          indy_handle_t wallet_handle,
+                                          const char *  their_did,
+
+                                          void           (*cb)(indy_handle_t xcommand_handle,
+                                                               indy_error_t  err,
+                                                               const char *  pairwise_info_json)
+                                         );
+
+    /// Save some data in the Wallet for pairwise associated with Their DID.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// their_did: DID of the other party.
+    /// metadata: some extra information for pairwise. Null clears previously saved metadata.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_set_pairwise_metadata(indy_handle_t command_handle,
+                                                   indy_handle_t wallet_handle,
+                                                   const char *  their_did,
+                                                   const char *  metadata,
+
+                                                   void           (*cb)(indy_handle_t xcommand_handle,
+                                                                        indy_error_t  err)
+                                                  );
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
+
@@ -5,6 +5,82 @@
 extern "C" {
 #endif
 
+    /// Registers custom crypto type implementation.
+    ///
+    /// It allows library user to provide custom signus implementation (for example, another curve
+    /// or keys held in HSM). DIDs and keys with registered crypto_type can be created after it.
+    /// Keys are passed to handlers exactly as they were returned by create_key handler.
+    /// Anonymous encryption isn't supported for custom crypto types.
+    ///
+    /// Handlers are called from libindy worker pool threads, possibly concurrently with each other
+    /// and with themselves, so they must be thread-safe and reentrant.
+    /// Buffers returned by create_key, sign, encrypt and decrypt handlers are copied and passed
+    /// to the free handler on the same worker thread right after the handler returns.
+    /// valid is written by verify handler as indy_bool_t (0 - invalid, non-zero - valid).
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// xtype: Crypto type name.
+    /// create_key: Key pair creation handler (seed_raw is null if seed isn't provided)
+    /// sign: Sign operation handler
+    /// verify: Signature verification handler
+    /// encrypt: Authenticated encryption handler
+    /// decrypt: Authenticated decryption handler
+    /// free: Handler that allows to de-allocate byte arrays allocated in caller code
+    ///
+    /// #Returns
+    /// Error code
+
+    extern indy_error_t indy_register_crypto_type(indy_handle_t  command_handle,
+                                                  const char*    xtype,
+                                                  indy_error_t (*createKeyFn)(const indy_u8_t*  seed_raw,
+                                                                              indy_u32_t        seed_len,
+                                                                              const indy_u8_t** verkey_raw,
+                                                                              indy_u32_t*       verkey_len,
+                                                                              const indy_u8_t** signkey_raw,
+                                                                              indy_u32_t*       signkey_len),
+
+                                                  indy_error_t (*signFn)(const indy_u8_t*  signkey_raw,
+                                                                         indy_u32_t        signkey_len,
+                                                                         const indy_u8_t*  message_raw,
+                                                                         indy_u32_t        message_len,
+                                                                         const indy_u8_t** signature_raw,
+                                                                         indy_u32_t*       signature_len),
+
+                                                  indy_error_t (*verifyFn)(const indy_u8_t*  verkey_raw,
+                                                                           indy_u32_t        verkey_len,
+                                                                           const indy_u8_t*  message_raw,
+                                                                           indy_u32_t        message_len,
+                                                                           const indy_u8_t*  signature_raw,
+                                                                           indy_u32_t        signature_len,
+                                                                           indy_bool_t*      valid),
+
+                                                  indy_error_t (*encryptFn)(const indy_u8_t*  signkey_raw,
+                                                                            indy_u32_t        signkey_len,
+                                                                            const indy_u8_t*  verkey_raw,
+                                                                            indy_u32_t        verkey_len,
+                                                                            const indy_u8_t*  nonce_raw,
+                                                                            indy_u32_t        nonce_len,
+                                                                            const indy_u8_t*  message_raw,
+                                                                            indy_u32_t        message_len,
+                                                                            const indy_u8_t** encrypted_msg_raw,
+                                                                            indy_u32_t*       encrypted_msg_len),
+
+                                                  indy_error_t (*decryptFn)(const indy_u8_t*  signkey_raw,
+                                                                            indy_u32_t        signkey_len,
+                                                                            const indy_u8_t*  verkey_raw,
+                                                                            indy_u32_t        verkey_len,
+                                                                            const indy_u8_t*  nonce_raw,
+                                                                            indy_u32_t        nonce_len,
+                                                                            const indy_u8_t*  encrypted_msg_raw,
+                                                                            indy_u32_t        encrypted_msg_len,
+                                                                            const indy_u8_t** decrypted_msg_raw,
+                                                                            indy_u32_t*       decrypted_msg_len),
+
+                                                  indy_error_t (*freeFn)(const indy_u8_t* data_raw, indy_u32_t data_len),
+                                                  void           (*fn)(indy_handle_t xcommand_handle, indy_error_t err)
+                                                  );
+
     /// Creates keys (signing and encryption keys) for a new
     /// DID (owned by the caller of the library).
     /// Identity's DID must be either explicitly provided, or taken as the first 16 bit of verkey.
@@ -21,6 +97,13 @@ extern "C" {
     ///     "seed": string, (optional; if not provide then a random one will be created)
     ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
     ///               currently only 'ed25519' value is supported for this field)
+    ///     "derivation_path": string, (optional; slash separated indexes, for example "0/1";
+    ///             if provided then keys are derived from the wallet master seed by this path
+    ///             and the same DID can be restored later from the master seed;
+    ///             a master seed is created in the wallet on first use; can't be used with seed)
+    ///     "method_name": string, (optional; DID method used to qualify a new DID, for example "sov"
+    ///             gives "did:sov:<did>"; if not set then the default method is used (see indy_set_default_did_method);
+    ///             provided "did" may be either bare or qualified)
     /// }
     /// cb: Callback that takes command result as parameter.
     ///
@@ -76,6 +159,67 @@ extern "C" {
                                                                const char*   pk)
                                          );
 
+    /// Generates new keys (signing and encryption keys) for an existing
+    /// DID (owned by the caller of the library) and stores them as pending.
+    /// Current keys are still used for signing and decryption until
+    /// indy_replace_keys_apply is called, so NYM transaction with new verkey
+    /// can be signed by current key and sent to the Ledger first.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// command_handle: command handle to map callback to user context.
+    /// did: DID to replace keys.
+    /// identity_json: Identity information as json. Example:
+    /// {
+    ///     "seed": string, (optional; if not provide then a random one will be created)
+    ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
+    ///               currently only 'ed25519' value is supported for this field)
+    /// }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// verkey (for verification of signature) and public_key (for decryption)
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+
+    extern indy_error_t indy_replace_keys_start(indy_handle_t command_handle,
+                                                indy_handle_t wallet_handle,
+                                                const char *  did,
+                                                const char *  identity_json,
+
+                                                void           (*cb)(indy_handle_t xcommand_handle,
+                                                                     indy_error_t  err,
+                                                                     const char*   verkey,
+                                                                     const char*   pk)
+                                               );
+
+    /// Applies keys generated by indy_replace_keys_start for the DID.
+    /// Should be called after the Ledger confirms NYM transaction with new verkey.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// command_handle: command handle to map callback to user context.
+    /// did: DID to apply pending keys.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_replace_keys_apply(indy_handle_t command_handle,
+                                                indy_handle_t wallet_handle,
+                                                const char *  did,
+
+                                                void           (*cb)(indy_handle_t xcommand_handle,
+                                                                     indy_error_t  err)
+                                               );
+
     /// Saves their DID for a pairwise connection in a secured Wallet,
     /// so that it can be used to verify transaction.
     ///
@@ -260,6 +404,722 @@ extern "C" {
                                                           indy_u32_t        decrypted_msg_len)
                                     );
 
+    /// Verifies a signature with the given verkey.
+    /// Neither wallet nor ledger is used, so the caller is responsible for verkey freshness.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// did(optional): DID the verkey belongs to. It is required only if verkey is abbreviated.
+    /// verkey: verkey of the signer. Both full and abbreviated (starts with '~') forms are supported.
+    /// message_raw: a pointer to first byte of message that was signed
+    /// message_len: a message length
+    /// signature_raw: a pointer to first byte of signature to be verified
+    /// signature_len: a signature length
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// valid: true - if signature is valid, false - otherwise
+    ///
+    /// #Errors
+    /// Common*
+    /// Crypto*
+
+    extern indy_error_t indy_verify_signature_with_verkey(indy_handle_t      command_handle,
+                                                          const char *       did,
+                                                          const char *       verkey,
+                                                          const indy_u8_t *  message_raw,
+                                                          indy_u32_t         message_len,
+                                                          const indy_u8_t *  signature_raw,
+                                                          indy_u32_t         signature_len,
+
+                                                          void           (*cb)(indy_handle_t xcommand_handle,
+                                                                               indy_error_t  err,
+                                                                               indy_bool_t   valid )
+                                                         );
+
+    /// Creates keys pair and stores in the wallet.
+    /// Keys are not tied to any DID and are referenced by verkey.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// key_json: Key information as json. Example:
+    /// {
+    ///     "seed": string, (optional; if not provide then a random one will be created)
+    ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
+    ///               currently only 'ed25519' value is supported for this field)
+    /// }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// verkey: verkey of generated key pair, also used as key identifier
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+
+    extern indy_error_t indy_create_key(indy_handle_t     command_handle,
+                                        indy_handle_t     wallet_handle,
+                                        const char *      key_json,
+
+                                        void              (*cb)(indy_handle_t     xcommand_handle,
+                                                                indy_error_t      err,
+                                                                const char *      verkey)
+                                       );
+
+    /// Signs a message with a key stored in the wallet (see indy_create_key).
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// my_vk: verkey of the key to sign with
+    /// message_raw: a pointer to first byte of message to be signed
+    /// message_len: a message length
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// a signature string
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+
+    extern indy_error_t indy_crypto_sign(indy_handle_t      command_handle,
+                                         indy_handle_t      wallet_handle,
+                                         const char *       my_vk,
+                                         const indy_u8_t *  message_raw,
+                                         indy_u32_t         message_len,
+
+                                         void           (*cb)(indy_handle_t     xcommand_handle,
+                                                              indy_error_t      err,
+                                                              const indy_u8_t*  signature_raw,
+                                                              indy_u32_t        signature_len)
+                                        );
+
+    /// Verifies a signature with the given verkey.
+    /// Neither wallet nor ledger is used.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// their_vk: verkey of the signer
+    /// message_raw: a pointer to first byte of message that was signed
+    /// message_len: a message length
+    /// signature_raw: a pointer to first byte of signature to be verified
+    /// signature_len: a signature length
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// valid: true - if signature is valid, false - otherwise
+    ///
+    /// #Errors
+    /// Common*
+    /// Crypto*
+
+    extern indy_error_t indy_crypto_verify(indy_handle_t      command_handle,
+                                           const char *       their_vk,
+                                           const indy_u8_t *  message_raw,
+                                           indy_u32_t         message_len,
+                                           const indy_u8_t *  signature_raw,
+                                           indy_u32_t         signature_len,
+
+                                           void           (*cb)(indy_handle_t xcommand_handle,
+                                                                indy_error_t  err,
+                                                                indy_bool_t   valid )
+                                          );
+
+    /// Encrypts a message with a key stored in the wallet (see indy_create_key)
+    /// for the owner of the given verkey.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// my_vk: verkey of the sender key
+    /// their_vk: verkey of the recipient
+    /// message_raw: a pointer to first byte of message that to be encrypted
+    /// message_len: a message length
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// an encrypted message and nonce
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+
+    extern indy_error_t indy_crypto_box(indy_handle_t      command_handle,
+                                        indy_handle_t      wallet_handle,
+                                        const char *       my_vk,
+                                        const char *       their_vk,
+                                        const indy_u8_t *  message_raw,
+                                        indy_u32_t         message_len,
+
+                                        void           (*cb)(indy_handle_t     xcommand_handle,
+                                                             indy_error_t      err,
+                                                             const indy_u8_t*  encrypted_msg_raw,
+                                                             indy_u32_t        encrypted_msg_len,
+                                                             const indy_u8_t*  nonce_raw,
+                                                             indy_u32_t        nonce_len)
+                                       );
+
+    /// Decrypts a message encrypted with indy_crypto_box.
+    /// The recipient key must be stored in the wallet (see indy_create_key).
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// my_vk: verkey of the recipient key
+    /// their_vk: verkey of the sender
+    /// encrypted_msg_raw: a pointer to first byte of message that to be decrypted
+    /// encrypted_msg_len: a message length
+    /// nonce_raw: a pointer to first byte of nonce that encrypted message
+    /// nonce_len: a nonce length
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// decrypted message
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+
+    extern indy_error_t indy_crypto_box_open(indy_handle_t      command_handle,
+                                             indy_handle_t      wallet_handle,
+                                             const char *       my_vk,
+                                             const char *       their_vk,
+                                             const indy_u8_t*   encrypted_msg_raw,
+                                             indy_u32_t         encrypted_msg_len,
+                                             const indy_u8_t*   nonce_raw,
+                                             indy_u32_t         nonce_len,
+
+                                             void           (*cb)(indy_handle_t     xcommand_handle,
+                                                                  indy_error_t      err,
+                                                                  const indy_u8_t*  decrypted_msg_raw,
+                                                                  indy_u32_t        decrypted_msg_len)
+                                            );
+
+    /// Encrypts a message by anonymous-encryption scheme.
+    /// Sealed boxes are designed to anonymously send messages to a recipient given its verkey.
+    /// Only the recipient can decrypt these messages, using its private key.
+    /// While the recipient can verify the integrity of the message, it cannot verify the identity of the sender.
+    /// An ephemeral key pair is generated for every message, so neither wallet nor ledger is used.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// their_vk: verkey of the recipient
+    /// message_raw: a pointer to first byte of message that to be encrypted
+    /// message_len: a message length
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// an encrypted message
+    ///
+    /// #Errors
+    /// Common*
+    /// Crypto*
+
+    extern indy_error_t indy_crypto_anon_crypt(indy_handle_t      command_handle,
+                                               const char *       their_vk,
+                                               const indy_u8_t *  message_raw,
+                                               indy_u32_t         message_len,
+
+                                               void           (*cb)(indy_handle_t     xcommand_handle,
+                                                                    indy_error_t      err,
+                                                                    const indy_u8_t*  encrypted_msg_raw,
+                                                                    indy_u32_t        encrypted_msg_len)
+                                              );
+
+    /// Decrypts a message encrypted by anonymous-encryption scheme (see indy_crypto_anon_crypt).
+    /// The recipient key must be stored in the wallet (see indy_create_key).
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// my_vk: verkey of the recipient key
+    /// encrypted_msg_raw: a pointer to first byte of message that to be decrypted
+    /// encrypted_msg_len: a message length
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// decrypted message
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+
+    extern indy_error_t indy_crypto_anon_decrypt(indy_handle_t      command_handle,
+                                                 indy_handle_t      wallet_handle,
+                                                 const char *       my_vk,
+                                                 const indy_u8_t*   encrypted_msg_raw,
+                                                 indy_u32_t         encrypted_msg_len,
+
+                                                 void           (*cb)(indy_handle_t     xcommand_handle,
+                                                                      indy_error_t      err,
+                                                                      const indy_u8_t*  decrypted_msg_raw,
+                                                                      indy_u32_t        decrypted_msg_len)
+                                                );
+
+    /// Packs a message into self-describing JSON envelope for one or more recipients.
+    /// The message is encrypted once with random content key, the content key is encrypted for each recipient.
+    /// If sender verkey is passed the content key is encrypted by authenticated-encryption scheme
+    /// (see indy_crypto_box) and sender verkey is sealed for each recipient, so only recipients can learn it.
+    /// Otherwise the content key is encrypted by anonymous-encryption scheme (see indy_crypto_anon_crypt).
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// message_raw: a pointer to first byte of message that to be packed
+    /// message_len: a message length
+    /// receiver_keys: json array of recipient verkeys, for example ["verkey1", "verkey2"]
+    /// sender_vk: verkey of the sender key stored in the wallet (see indy_create_key),
+    ///            NULL for anonymous packing
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// packed message json:
+    /// {
+    ///     "version": "1.0",
+    ///     "alg": "Authcrypt" or "Anoncrypt",
+    ///     "recipients": [{
+    ///         "verkey": string, recipient verkey
+    ///         "encrypted_key": string, base64url (unpadded) encoded encrypted content key
+    ///         "sender": string, (Authcrypt only) base64url (unpadded) encoded sealed sender verkey
+    ///         "nonce": string, (Authcrypt only) base64url (unpadded) encoded nonce of encrypted content key
+    ///     }],
+    ///     "nonce": string, base64url (unpadded) encoded nonce of ciphertext
+    ///     "ciphertext": string, base64url (unpadded) encoded encrypted message
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+
+    extern indy_error_t indy_pack_message(indy_handle_t      command_handle,
+                                          indy_handle_t      wallet_handle,
+                                          const indy_u8_t*   message_raw,
+                                          indy_u32_t         message_len,
+                                          const char *       receiver_keys,
+                                          const char *       sender_vk,
+
+                                          void           (*cb)(indy_handle_t     xcommand_handle,
+                                                               indy_error_t      err,
+                                                               const char *      packed_message)
+                                         );
+
+    /// Unpacks a message packed by indy_pack_message.
+    /// The wallet is searched for a key of one of the message recipients (see indy_create_key).
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// packed_message: packed message json
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// message_raw, message_len: unpacked message
+    /// sender_vk: verkey of the sender, NULL for anonymously packed message
+    /// recipient_vk: verkey of the recipient key used to unpack the message
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    ///
+    /// WalletNotFoundError is returned if no key of message recipients is stored in the wallet.
+
+    extern indy_error_t indy_unpack_message(indy_handle_t      command_handle,
+                                            indy_handle_t      wallet_handle,
+                                            const char *       packed_message,
+
+                                            void           (*cb)(indy_handle_t     xcommand_handle,
+                                                                 indy_error_t      err,
+                                                                 const indy_u8_t*  message_raw,
+                                                                 indy_u32_t        message_len,
+                                                                 const char *      sender_vk,
+                                                                 const char *      recipient_vk)
+                                           );
+
+    /// Saves/replaces the meta information for the giving DID in the wallet.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// did: the DID to store metadata (either my or their).
+    /// metadata: the meta information that will be store with the DID (for example, label).
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_set_did_metadata(indy_handle_t command_handle,
+                                              indy_handle_t wallet_handle,
+                                              const char *  did,
+                                              const char *  metadata,
+
+                                              void           (*cb)(indy_handle_t xcommand_handle,
+                                                                   indy_error_t  err)
+                                             );
+
+    /// Retrieves the meta information for the giving DID in the wallet.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// did: the DID to retrieve metadata.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// metadata: the meta information stored with the DID; Can be null if no metadata was saved for this DID.
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_get_did_metadata(indy_handle_t command_handle,
+                                              indy_handle_t wallet_handle,
+                                              const char *  did,
+
+                                              void           (*cb)(indy_handle_t xcommand_handle,
+                                                                   indy_error_t  err,
+                                                                   const char *  metadata)
+                                             );
+
+    /// Get info about My DID in format: DID, verkey, crypto type, metadata and pending rotation verkey.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// my_did: DID
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// DID with verkey and metadata:
+    /// {
+    ///     "did": string,
+    ///     "verkey": string,
+    ///     "crypto_type": string,
+    ///     "metadata": string, (optional; null if metadata wasn't set)
+    ///     "temp_verkey": string, (optional; new verkey created by indy_replace_keys_start
+    ///                             that isn't applied yet)
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_get_my_did_with_meta(indy_handle_t command_handle,
+                                                  indy_handle_t wallet_handle,
+                                                  const char *  my_did,
+
+                                                  void           (*cb)(indy_handle_t xcommand_handle,
+                                                                       indy_error_t  err,
+                                                                       const char *  did_with_meta)
+                                                 );
+
+    /// Retrieves the information about all DIDs stored in the wallet.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// dids: List of DIDs with verkeys and meta data in the same format as indy_get_my_did_with_meta returns:
+    /// [{
+    ///     "did": string,
+    ///     "verkey": string,
+    ///     "crypto_type": string,
+    ///     "metadata": string, (optional)
+    ///     "temp_verkey": string, (optional)
+    /// }]
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_list_my_dids_with_meta(indy_handle_t command_handle,
+                                                    indy_handle_t wallet_handle,
+
+                                                    void           (*cb)(indy_handle_t xcommand_handle,
+                                                                         indy_error_t  err,
+                                                                         const char *  dids)
+                                                   );
+
+    /// Returns the wallet master seed used to derive DIDs by derivation path (see indy_create_and_store_my_did).
+    /// Master seed is created in the wallet on the first derivation of DID (or stored by indy_import_master_seed),
+    /// WalletNotFoundError is returned if the wallet doesn't contain it yet.
+    /// Backup of the master seed is enough to restore all DIDs derived from it.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// master_seed: base58 encoded master seed
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_export_master_seed(indy_handle_t command_handle,
+                                                indy_handle_t wallet_handle,
+
+                                                void           (*cb)(indy_handle_t xcommand_handle,
+                                                                     indy_error_t  err,
+                                                                     const char *  master_seed)
+                                               );
+
+    /// Stores the master seed (previously returned by indy_export_master_seed) in the wallet,
+    /// so DIDs derived from it can be restored by indy_create_and_store_my_did with the same derivation paths.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// master_seed: base58 encoded master seed.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    ///
+    /// CommonInvalidState is returned if the wallet already contains another master seed.
+
+    extern indy_error_t indy_import_master_seed(indy_handle_t command_handle,
+                                                indy_handle_t wallet_handle,
+                                                const char *  master_seed,
+
+                                                void           (*cb)(indy_handle_t xcommand_handle,
+                                                                     indy_error_t  err)
+                                               );
+
+    /// Returns the wallet master seed (see indy_export_master_seed) as BIP39 mnemonic of 24 english words.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// mnemonic: space separated mnemonic words
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_export_master_seed_mnemonic(indy_handle_t command_handle,
+                                                         indy_handle_t wallet_handle,
+
+                                                         void           (*cb)(indy_handle_t xcommand_handle,
+                                                                              indy_error_t  err,
+                                                                              const char *  mnemonic)
+                                                        );
+
+    /// Stores the master seed given as BIP39 mnemonic (previously returned by indy_export_master_seed_mnemonic)
+    /// in the wallet the same way as indy_import_master_seed.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// mnemonic: space separated mnemonic words.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    ///
+    /// CommonInvalidStructure is returned for unknown word or invalid checksum of mnemonic.
+    /// CommonInvalidState is returned if the wallet already contains another master seed.
+
+    extern indy_error_t indy_import_master_seed_mnemonic(indy_handle_t command_handle,
+                                                         indy_handle_t wallet_handle,
+                                                         const char *  mnemonic,
+
+                                                         void           (*cb)(indy_handle_t xcommand_handle,
+                                                                              indy_error_t  err)
+                                                        );
+
+    /// Sets the DID method used to qualify new DIDs created by indy_create_and_store_my_did
+    /// when "method_name" isn't provided in did_json (for example "sov" gives "did:sov:<did>").
+    /// All functions accept both bare and method-qualified DIDs; wallet records are shared by both forms
+    /// and qualification is stripped when building ledger requests.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// method_name: DID method name (lowercase letters and digits); null to create bare DIDs (default).
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+
+    extern indy_error_t indy_set_default_did_method(indy_handle_t command_handle,
+                                                    const char *  method_name,
+
+                                                    void           (*cb)(indy_handle_t xcommand_handle,
+                                                                         indy_error_t  err)
+                                                   );
+
+    /// Resolves a DID to a DID document assembled from its NYM and "endpoint" ATTRIB transactions on the Ledger.
+    /// Ledger replies are accepted only after consensus of f+1 identical replies from pool nodes.
+    /// The Ledger doesn't serve GET_DDO transaction, so it isn't used for resolution.
+    /// Resolved NYM and ATTRIB data is cached in the wallet according to freshness settings set during initialization.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// pool_handle: pool handle.
+    /// did: DID to resolve (bare or method-qualified).
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// did_doc: DID document json:
+    /// {
+    ///     "@context": "https://w3id.org/did/v1",
+    ///     "id": method-qualified DID (method of the given DID, default DID method or "sov"),
+    ///     "publicKey": [{"id": "<id>#key-1", "type": "Ed25519VerificationKey2018",
+    ///                    "controller": "<id>", "publicKeyBase58": full verkey}],
+    ///     "authentication": [{"type": "Ed25519SignatureAuthentication2018", "publicKey": "<id>#key-1"}],
+    ///     "service": [{"id": "<id>#agent", "type": "IndyAgent",
+    ///                  "serviceEndpoint": endpoint host address, "recipientKeys": [endpoint verkey]}],
+    ///                 (empty if endpoint attribute isn't set)
+    ///     "role": role name from NYM (TRUSTEE, STEWARD, TRUST_ANCHOR, TGB) or null
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Ledger*
+    ///
+    /// LedgerNotFound is returned if NYM of the DID doesn't exist on the Ledger.
+
+    extern indy_error_t indy_resolve_did(indy_handle_t command_handle,
+                                         indy_handle_t wallet_handle,
+                                         indy_handle_t pool_handle,
+                                         const char *  did,
+
+                                         void           (*cb)(indy_handle_t xcommand_handle,
+                                                              indy_error_t  err,
+                                                              const char *  did_doc)
+                                        );
+
+    /// Saves/replaces the endpoint information for the given DID in the wallet.
+    /// Endpoint is used by agent connections and returned by indy_get_endpoint_for_did
+    /// without querying the Ledger (until it expires according to freshness settings).
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// did: DID the endpoint belongs to (bare or method-qualified).
+    /// address: endpoint address, for example "127.0.0.1:9700".
+    /// transport_key: base58 encoded key used to establish secure connections with the endpoint.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_set_endpoint_for_did(indy_handle_t command_handle,
+                                                  indy_handle_t wallet_handle,
+                                                  const char *  did,
+                                                  const char *  address,
+                                                  const char *  transport_key,
+
+                                                  void           (*cb)(indy_handle_t xcommand_handle,
+                                                                       indy_error_t  err)
+                                                 );
+
+    /// Returns endpoint information for the given DID.
+    /// Endpoint stored in the wallet (by indy_set_endpoint_for_did or cached from the Ledger) is used first.
+    /// If it is missed or expired according to freshness settings set during initialization,
+    /// then the "endpoint" ATTRIB is read from the Ledger and cached in the wallet.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// pool_handle: pool handle.
+    /// did: DID to get endpoint for (bare or method-qualified).
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// address: endpoint address.
+    /// transport_key: base58 encoded transport key of the endpoint.
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Ledger*
+    ///
+    /// LedgerNotFound is returned if the endpoint is found neither in the wallet nor on the Ledger.
+
+    extern indy_error_t indy_get_endpoint_for_did(indy_handle_t command_handle,
+                                                  indy_handle_t wallet_handle,
+                                                  indy_handle_t pool_handle,
+                                                  const char *  did,
+
+                                                  void           (*cb)(indy_handle_t xcommand_handle,
+                                                                       indy_error_t  err,
+                                                                       const char *  address,
+                                                                       const char *  transport_key)
+                                                 );
+
+    /// Publishes endpoint information for the given DID on the Ledger.
+    /// Builds ATTRIB transaction with "endpoint" raw attribute ({"endpoint":{"ha": address, "verkey": transport_key}}),
+    /// signs it with submitter's key and sends it to the pool.
+    /// Note that the endpoint isn't saved in the wallet (see indy_set_endpoint_for_did).
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// pool_handle: pool handle.
+    /// submitter_did: Id of Identity stored in secured Wallet.
+    /// did: DID the endpoint belongs to.
+    /// address: endpoint address, for example "127.0.0.1:9700".
+    /// transport_key: base58 encoded key used to establish secure connections with the endpoint.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Request result as json.
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Ledger*
+    /// Crypto*
+
+    extern indy_error_t indy_publish_endpoint(indy_handle_t command_handle,
+                                              indy_handle_t wallet_handle,
+                                              indy_handle_t pool_handle,
+                                              const char *  submitter_did,
+                                              const char *  did,
+                                              const char *  address,
+                                              const char *  transport_key,
+
+                                              void           (*cb)(indy_handle_t xcommand_handle,
+                                                                   indy_error_t  err,
+                                                                   const char *  request_result_json)
+                                             );
+
 #ifdef __cplusplus
 }
 #endif
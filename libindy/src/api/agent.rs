@@ -2,11 +2,13 @@ extern crate libc;
 
 use api::ErrorCode;
 use commands::{Command, CommandExecutor};
-use commands::agent::AgentCommand;
+use commands::agent::{AgentAuthorizationCB, AgentBinaryMessageCB, AgentCommand, AgentMessageCB, AgentMessageIdCB};
 use errors::ToErrorCode;
 use utils::cstring::CStringUtils;
 
 use self::libc::c_char;
+use std::ptr;
+use std::slice;
 
 /// Establishes agent to agent connection.
 ///
@@ -19,6 +21,14 @@ use self::libc::c_char;
 ///
 /// Note that messages encryption/decryption will be performed automatically.
 ///
+/// Transport is selected by scheme of receiver endpoint: tcp:// or endpoint without scheme
+/// is served by ZeroMQ with CurveCP, http:// and https:// endpoints are served by HTTP POST
+/// with messages encrypted by libindy. Over HTTP incoming messages are polled periodically.
+///
+/// Established connection is checked by periodic heartbeats. If remote agent doesn't respond
+/// or restarts, connection is re-established automatically with growing delay between attempts.
+/// While connection is lost outgoing messages are queued and sent after reconnect.
+///
 /// #Params
 /// command_handle: Command handle to map callback to caller context.
 /// pool_handle: Pool handle (created by open_pool_ledger).
@@ -29,6 +39,11 @@ use self::libc::c_char;
 ///     Will be called exactly once with result of connect operation.
 /// message_cb: Callback that will be called on receiving of an incoming message.
 ///     Can be called multiply times: once for each incoming message.
+///     Messages sent with id are passed to callback set by indy_agent_set_message_id_cb if any.
+///     Also notifies about connection loss without message by AgentConnectionLostError.
+///     Reconnect isn't notified: see state and stats.reconnects in indy_agent_get_connection_info.
+///     AgentMessageTooLargeError is passed if remote listener rejected sent message by size.
+///     Binary messages are passed to callback set by indy_agent_set_binary_message_cb.
 ///
 /// #Returns
 /// Error code
@@ -68,11 +83,65 @@ pub extern fn indy_agent_connect(command_handle: i32,
                     let (err, handle) = result_to_err_code_1!(result, 0);
                     connection_cb(command_handle, err, handle);
                 }),
+                _message_cb(message_cb)
+            )
+        )
+    );
+
+    result_to_err_code!(result)
+}
+
+/// Establishes agent to agent connection for pairwise stored in the wallet.
+///
+/// Works as indy_agent_connect with my DID of pairwise (created by indy_create_pairwise)
+/// as sender and their DID as receiver.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// pool_handle: Pool handle (created by open_pool_ledger).
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// their_did: Id of receiver Identity that identifies pairwise.
+/// connection_cb: Callback that will be called after establishing of connection or on error.
+///     Will be called exactly once with result of connect operation.
+/// message_cb: Callback that will be called on receiving of an incoming message
+///     or on connection state change as described for indy_agent_connect.
+///
+/// #Returns
+/// Error code
+/// connection_cb:
+/// - xcommand_handle: command handle to map callback to caller context.
+/// - err: Error code.
+/// - connection_handle: Connection handle to use for messages sending and mapping of incomming messages to this connection.
+/// message_cb:
+/// - xconnection_handle: Connection handle. Indetnifies connection.
+/// - err: Error code.
+/// - message: Received message.
+#[no_mangle]
+pub extern fn indy_agent_connect_pairwise(command_handle: i32,
+                                          pool_handle: i32,
+                                          wallet_handle: i32,
+                                          their_did: *const c_char,
+                                          connection_cb: Option<extern fn(xcommand_handle: i32,
+                                                                          err: ErrorCode,
+                                                                          connection_handle: i32)>,
+                                          message_cb: Option<extern fn(xconnection_handle: i32,
+                                                                       err: ErrorCode,
+                                                                       message: *const c_char)>) -> ErrorCode {
+    check_useful_c_str!(their_did, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(connection_cb, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(message_cb, ErrorCode::CommonInvalidParam6);
+
+    let result = CommandExecutor::instance().send(
+        Command::Agent(
+            AgentCommand::ConnectPairwise(
+                pool_handle,
+                wallet_handle,
+                their_did,
                 Box::new(move |result| {
-                    let (err, handle, msg) = result_to_err_code_2!(result, 0, String::new());
-                    let msg = CStringUtils::string_to_cstring(msg);
-                    message_cb(handle, err, msg.as_ptr());
-                })
+                    let (err, handle) = result_to_err_code_1!(result, 0);
+                    connection_cb(command_handle, err, handle);
+                }),
+                _message_cb(message_cb)
             )
         )
     );
@@ -91,6 +160,12 @@ pub extern fn indy_agent_connect(command_handle: i32,
 ///
 /// Note that messages encryption/decryption will be performed automatically.
 ///
+/// Transport is selected by endpoint scheme: tcp:// or endpoint without scheme (host:port)
+/// starts ZeroMQ CurveCP listener, http:// starts HTTP listener. https:// isn't supported for
+/// listening, use TLS-terminating proxy in front of http listener instead.
+///
+/// Listener uses default config, see indy_agent_listen_with_config.
+///
 /// #Params
 /// command_handle: command handle to map callback to caller context.
 /// endpoint: endpoint to use in starting listener.
@@ -100,6 +175,8 @@ pub extern fn indy_agent_connect(command_handle: i32,
 ///     Can be called multiply times: once for each incoming connection.
 /// message_cb: Callback that will be called on receiving of an incoming message.
 ///     Can be called multiply times: once for each incoming message.
+///     Messages sent with id are passed to callback set by indy_agent_set_message_id_cb if any.
+///     Binary messages are passed to callback set by indy_agent_set_binary_message_cb.
 ///
 /// #Returns
 /// Error code
@@ -136,29 +213,73 @@ pub extern fn indy_agent_listen(command_handle: i32,
     check_useful_c_callback!(connection_cb, ErrorCode::CommonInvalidParam4);
     check_useful_c_callback!(message_cb, ErrorCode::CommonInvalidParam5);
 
-    let cmd = Command::Agent(AgentCommand::Listen(
-        endpoint,
-        Box::new(move |result| {
-            let (err, handle) = result_to_err_code_1!(result, 0);
-            listener_cb(command_handle, err, handle);
-        }),
-        Box::new(move |result| {
-            let (err, listener_handle, conn_handle, sender_did, receiver_did) =
-                result_to_err_code_4!(result, 0, 0, String::new(), String::new());
-            connection_cb(listener_handle, err, conn_handle,
-                          CStringUtils::string_to_cstring(sender_did).as_ptr(),
-                          CStringUtils::string_to_cstring(receiver_did).as_ptr());
-        }),
-        Box::new(move |result| {
-            let (err, handle, msg) = result_to_err_code_2!(result, 0, String::new());
-            let msg = CStringUtils::string_to_cstring(msg);
-            message_cb(handle, err, msg.as_ptr());
-        })
-    ));
+    _listen(command_handle, endpoint, None, listener_cb, connection_cb, message_cb)
+}
 
-    let result = CommandExecutor::instance().send(cmd);
+/// Starts listening of agent connections with listener config.
+///
+/// Works as indy_agent_listen, config limits incoming messages and senders allowed to connect.
+///
+/// Incoming messages larger than maximum message size are rejected: sender receives
+/// AgentMessageTooLargeError in message_cb and message isn't passed to the listener.
+///
+/// Sender is checked against listener policy before connection is confirmed. Deny lists take
+/// precedence over allow lists. Role rules require sender NYM lookup on the ledger even if sender
+/// keys are stored in the wallet. Application can add own check with indy_agent_set_authorization_cb.
+/// Connection attempts over rate limit are rejected before any wallet or ledger lookup.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// endpoint: endpoint to use in starting listener.
+/// config: (optional) listener config json.
+///     {
+///         "max_message_size": <int> Optional maximum size of incoming message in bytes, 1048576 by default.
+///             Larger message is answered with msg_too_large, zmq frame above 4 times the limit
+///             is dropped and its connection is closed.
+///         "allow_dids": <[string]> Optional list of sender DIDs allowed to connect, any DID by default.
+///         "deny_dids": <[string]> Optional list of sender DIDs rejected by listener.
+///         "allow_roles": <[string]> Optional list of sender ledger roles allowed to connect:
+///             TRUSTEE, STEWARD, TRUST_ANCHOR, TGB or USER for NYM without role.
+///         "deny_roles": <[string]> Optional list of sender ledger roles rejected by listener.
+///         "rate_limit": Optional limit of connection attempts per sender {
+///             "max_attempts": <int> attempts of one sender key with one DID and accepted attempts
+///                 of one DID allowed during interval,
+///             "interval_secs": <int> interval in seconds,
+///             "max_total_attempts": <int> Optional attempts of all senders allowed during interval, 1024 by default
+///         }
+///     }
+/// listener_cb: Callback that will be called after listening started or on error.
+///     Will be called exactly once with result of start listen operation.
+/// connection_cb: Callback that will be called after establishing of incoming connection.
+///     Can be called multiply times: once for each incoming connection.
+/// message_cb: Callback that will be called on receiving of an incoming message.
+///     Can be called multiply times: once for each incoming message.
+///
+/// #Returns
+/// Error code
+/// listener_cb, connection_cb and message_cb are called as for indy_agent_listen.
+#[no_mangle]
+pub extern fn indy_agent_listen_with_config(command_handle: i32,
+                                            endpoint: *const c_char,
+                                            config: *const c_char,
+                                            listener_cb: Option<extern fn(xcommand_handle: i32,
+                                                                          err: ErrorCode,
+                                                                          listener_handle: i32)>,
+                                            connection_cb: Option<extern fn(xlistener_handle: i32,
+                                                                            err: ErrorCode,
+                                                                            connection_handle: i32,
+                                                                            sender_did: *const c_char,
+                                                                            receiver_did: *const c_char)>,
+                                            message_cb: Option<extern fn(xconnection_handle: i32,
+                                                                         err: ErrorCode,
+                                                                         message: *const c_char)>) -> ErrorCode {
+    check_useful_c_str!(endpoint, ErrorCode::CommonInvalidParam2);
+    check_useful_opt_c_str!(config, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(listener_cb, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(connection_cb, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(message_cb, ErrorCode::CommonInvalidParam6);
 
-    result_to_err_code!(result)
+    _listen(command_handle, endpoint, config, listener_cb, connection_cb, message_cb)
 }
 
 /// Add identity to listener.
@@ -260,10 +381,183 @@ pub extern fn indy_agent_remove_identity(command_handle: i32,
     result_to_err_code!(result)
 }
 
+/// Sets application callback consulted before incoming connection is confirmed.
+///
+/// Callback is called after sender key is verified and listener policy from indy_agent_listen
+/// config allowed the sender. Connection is accepted only if callback returns true.
+/// Callback is called on libindy thread, so it must return quickly and mustn't wait for
+/// other libindy calls.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// listener_handle: listener handle (created by indy_agent_listen).
+/// authorization_cb: (optional) authorization callback. Pass null to remove callback set before.
+/// cb: Callback that will be called after authorization callback set or on error.
+///     Will be called exactly once.
+///
+/// #Returns
+/// Error code
+/// authorization_cb:
+/// - xlistener_handle: Listener handle. Identifies listener.
+/// - sender_did: DID of connecting sender.
+/// - receiver_did: DID of listener identity sender connects to.
+/// - role: Sender role from the ledger. Null if sender keys were found in the wallet.
+/// - returns true to accept connection, false to reject it.
+/// cb:
+/// - xcommand_handle: command handle to map callback to caller context.
+/// - err: Error code
+#[no_mangle]
+pub extern fn indy_agent_set_authorization_cb(command_handle: i32,
+                                              listener_handle: i32,
+                                              authorization_cb: Option<extern fn(xlistener_handle: i32,
+                                                                                 sender_did: *const c_char,
+                                                                                 receiver_did: *const c_char,
+                                                                                 role: *const c_char) -> bool>,
+                                              cb: Option<extern fn(xcommand_handle: i32,
+                                                                   err: ErrorCode)>) -> ErrorCode {
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    let authorization_cb = authorization_cb.map(|authorization_cb| -> AgentAuthorizationCB {
+        Box::new(move |listener_handle: i32, sender_did: &str, receiver_did: &str, role: Option<&str>| {
+            let sender_did = CStringUtils::string_to_cstring(sender_did.to_string());
+            let receiver_did = CStringUtils::string_to_cstring(receiver_did.to_string());
+            let role = role.map(|role| CStringUtils::string_to_cstring(role.to_string()));
+            authorization_cb(listener_handle, sender_did.as_ptr(), receiver_did.as_ptr(),
+                             role.as_ref().map(|role| role.as_ptr()).unwrap_or(ptr::null()))
+        })
+    });
+
+    let cmd = Command::Agent(AgentCommand::ListenerSetAuthorizationCB(
+        listener_handle,
+        authorization_cb,
+        Box::new(move |result| {
+            cb(command_handle, result_to_err_code!(result))
+        }),
+    ));
+
+    let result = CommandExecutor::instance().send(cmd);
+
+    result_to_err_code!(result)
+}
+
+/// Sets callback for messages sent with id by indy_agent_send_with_ack, indy_agent_request
+/// and indy_agent_reply.
+///
+/// Callback set for listener is used for all its incoming connections, callback set for
+/// connection takes precedence. If callback isn't set, such messages are passed to message_cb
+/// without id, so requests can't be answered.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// handle: Connection handle returned by indy_agent_connect or indy_agent_listen calls
+///     or listener handle returned by indy_agent_listen call.
+/// message_id_cb: (optional) message id callback. Pass null to remove callback set before.
+/// cb: Callback that will be called after message id callback set or on error.
+///     Will be called exactly once.
+///
+/// #Returns
+/// Error code
+/// message_id_cb:
+/// - xconnection_handle: Connection handle. Identifies connection.
+/// - message: Received message.
+/// - message_id: Id of message. Pass it to indy_agent_reply to answer request.
+/// cb:
+/// - xcommand_handle: command handle to map callback to caller context.
+/// - err: Error code
+///
+/// #Errors
+/// CommonInvalidStructure - connection or listener with passed handle isn't found.
+#[no_mangle]
+pub extern fn indy_agent_set_message_id_cb(command_handle: i32,
+                                           handle: i32,
+                                           message_id_cb: Option<extern fn(xconnection_handle: i32,
+                                                                           message: *const c_char,
+                                                                           message_id: *const c_char)>,
+                                           cb: Option<extern fn(xcommand_handle: i32,
+                                                                err: ErrorCode)>) -> ErrorCode {
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    let message_id_cb = message_id_cb.map(|message_id_cb| -> AgentMessageIdCB {
+        Box::new(move |connection_handle: i32, message: &str, message_id: &str| {
+            let message = CStringUtils::string_to_cstring(message.to_string());
+            let message_id = CStringUtils::string_to_cstring(message_id.to_string());
+            message_id_cb(connection_handle, message.as_ptr(), message_id.as_ptr())
+        })
+    });
+
+    let cmd = Command::Agent(AgentCommand::SetMessageIdCB(
+        handle,
+        message_id_cb,
+        Box::new(move |result| {
+            cb(command_handle, result_to_err_code!(result))
+        }),
+    ));
+
+    let result = CommandExecutor::instance().send(cmd);
+
+    result_to_err_code!(result)
+}
+
+/// Sets callback for binary messages sent by indy_agent_send_bytes.
+///
+/// Callback set for listener is used for all its incoming connections, callback set for
+/// connection takes precedence. If callback isn't set, binary messages are dropped.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// handle: Connection handle returned by indy_agent_connect or indy_agent_listen calls
+///     or listener handle returned by indy_agent_listen call.
+/// binary_message_cb: (optional) binary message callback. Pass null to remove callback set before.
+/// cb: Callback that will be called after binary message callback set or on error.
+///     Will be called exactly once.
+///
+/// #Returns
+/// Error code
+/// binary_message_cb:
+/// - xconnection_handle: Connection handle. Identifies connection.
+/// - message_raw: a pointer to first byte of received message.
+/// - message_len: a message length.
+/// cb:
+/// - xcommand_handle: command handle to map callback to caller context.
+/// - err: Error code
+///
+/// #Errors
+/// CommonInvalidStructure - connection or listener with passed handle isn't found.
+#[no_mangle]
+pub extern fn indy_agent_set_binary_message_cb(command_handle: i32,
+                                               handle: i32,
+                                               binary_message_cb: Option<extern fn(xconnection_handle: i32,
+                                                                                   message_raw: *const u8,
+                                                                                   message_len: u32)>,
+                                               cb: Option<extern fn(xcommand_handle: i32,
+                                                                    err: ErrorCode)>) -> ErrorCode {
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    let binary_message_cb = binary_message_cb.map(|binary_message_cb| -> AgentBinaryMessageCB {
+        Box::new(move |connection_handle: i32, message: &[u8]| {
+            binary_message_cb(connection_handle, message.as_ptr(), message.len() as u32)
+        })
+    });
+
+    let cmd = Command::Agent(AgentCommand::SetBinaryMessageCB(
+        handle,
+        binary_message_cb,
+        Box::new(move |result| {
+            cb(command_handle, result_to_err_code!(result))
+        }),
+    ));
+
+    let result = CommandExecutor::instance().send(cmd);
+
+    result_to_err_code!(result)
+}
+
 /// Sends message to connected agent.
 ///
 /// Note that this call works for both incoming and outgoing connections.
 /// Note that messages encryption/decryption will be performed automatically.
+/// Note that messages to outgoing connection that is reconnecting are queued, so success result
+/// means message is accepted for delivery.
 ///
 /// #Params
 /// command_handle: command handle to map callback to caller context.
@@ -278,6 +572,7 @@ pub extern fn indy_agent_remove_identity(command_handle: i32,
 /// - err: Error code
 ///
 /// #Errors
+/// AgentOutboundQueueFullError - connection is reconnecting and too many messages are queued already.
 #[no_mangle]
 pub extern fn indy_agent_send(command_handle: i32,
                               connection_handle: i32,
@@ -299,6 +594,196 @@ pub extern fn indy_agent_send(command_handle: i32,
     result_to_err_code!(res)
 }
 
+/// Sends binary message to connected agent.
+///
+/// Message is passed as is without text encoding and received by callback set with
+/// indy_agent_set_binary_message_cb on remote side. Message larger than maximum message size of remote listener is rejected
+/// by it with AgentMessageTooLargeError passed to message_cb of this connection.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// connection_handle: Connection handle returned by indy_agent_connect or indy_agent_listen calls.
+/// message_raw: a pointer to first byte of message to send.
+/// message_len: a message length.
+/// cb: Callback that will be called after message sent or on error. Will be called exactly once.
+///
+/// #Returns
+/// err: Error code
+/// cb:
+/// - xcommand_handle: Command handle to map callback to caller context.
+/// - err: Error code
+///
+/// #Errors
+/// AgentOutboundQueueFullError - connection is reconnecting and too many messages are queued already.
+#[no_mangle]
+pub extern fn indy_agent_send_bytes(command_handle: i32,
+                                    connection_handle: i32,
+                                    message_raw: *const u8,
+                                    message_len: u32,
+                                    cb: Option<extern fn(xcommand_handle: i32,
+                                                         err: ErrorCode)>) -> ErrorCode {
+    get_byte_array!(message_raw, message_len, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    let cmd = Command::Agent(AgentCommand::SendBytes(
+        connection_handle,
+        message_raw,
+        Box::new(move |result| {
+            cb(command_handle, result_to_err_code!(result))
+        })
+    ));
+
+    let res = CommandExecutor::instance().send(cmd);
+    result_to_err_code!(res)
+}
+
+/// Sends message to connected agent and waits for delivery acknowledgement.
+///
+/// Message is sent with id that is passed to receiver callback set by indy_agent_set_message_id_cb.
+/// Receiving libindy acknowledges delivery automatically before passing message to application.
+/// Note that this call works for both incoming and outgoing connections.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// connection_handle: Connection handle returned by indy_agent_connect or indy_agent_listen calls.
+/// message_id: (optional) Id of message. Must be unique for connection, random id is generated if null.
+/// message: Message to send.
+/// cb: Callback that will be called after delivery acknowledgement received or on error.
+///     Will be called exactly once.
+///
+/// #Returns
+/// err: Error code
+/// cb:
+/// - xcommand_handle: Command handle to map callback to caller context.
+/// - err: Error code
+/// - message_id: Id of delivered message.
+///
+/// #Errors
+/// AgentOutboundQueueFullError - connection is reconnecting and too many messages are queued already.
+/// AgentTimeoutError - delivery acknowledgement isn't received in time.
+#[no_mangle]
+pub extern fn indy_agent_send_with_ack(command_handle: i32,
+                                       connection_handle: i32,
+                                       message_id: *const c_char,
+                                       message: *const c_char,
+                                       cb: Option<extern fn(xcommand_handle: i32,
+                                                            err: ErrorCode,
+                                                            message_id: *const c_char)>) -> ErrorCode {
+    check_useful_opt_c_str!(message_id, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(message, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    let cmd = Command::Agent(AgentCommand::SendWithAck(
+        connection_handle,
+        message_id,
+        message,
+        Box::new(move |result| {
+            let (err, message_id) = result_to_err_code_1!(result, String::new());
+            let message_id = CStringUtils::string_to_cstring(message_id);
+            cb(command_handle, err, message_id.as_ptr())
+        })
+    ));
+
+    let res = CommandExecutor::instance().send(cmd);
+    result_to_err_code!(res)
+}
+
+/// Sends request to connected agent and waits for correlated response.
+///
+/// Other side receives request with message id in callback set by indy_agent_set_message_id_cb
+/// and answers with indy_agent_reply. Response is passed to cb of this call instead of message_cb.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// connection_handle: Connection handle returned by indy_agent_connect or indy_agent_listen calls.
+/// message: Request message to send.
+/// timeout_ms: Time to wait for response in milliseconds, must be greater than 0.
+/// cb: Callback that will be called after response received, on timeout or on error.
+///     Will be called exactly once.
+///
+/// #Returns
+/// err: Error code
+/// cb:
+/// - xcommand_handle: Command handle to map callback to caller context.
+/// - err: Error code
+/// - response: Received response message.
+///
+/// #Errors
+/// AgentOutboundQueueFullError - connection is reconnecting and too many messages are queued already.
+/// AgentTimeoutError - response isn't received in time.
+#[no_mangle]
+pub extern fn indy_agent_request(command_handle: i32,
+                                 connection_handle: i32,
+                                 message: *const c_char,
+                                 timeout_ms: u32,
+                                 cb: Option<extern fn(xcommand_handle: i32,
+                                                      err: ErrorCode,
+                                                      response: *const c_char)>) -> ErrorCode {
+    check_useful_c_str!(message, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    if timeout_ms == 0 {
+        return ErrorCode::CommonInvalidParam4;
+    }
+
+    let cmd = Command::Agent(AgentCommand::SendRequest(
+        connection_handle,
+        message,
+        timeout_ms,
+        Box::new(move |result| {
+            let (err, response) = result_to_err_code_1!(result, String::new());
+            let response = CStringUtils::string_to_cstring(response);
+            cb(command_handle, err, response.as_ptr())
+        })
+    ));
+
+    let res = CommandExecutor::instance().send(cmd);
+    result_to_err_code!(res)
+}
+
+/// Sends response to request received by callback set by indy_agent_set_message_id_cb.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// connection_handle: Connection handle the request was received from.
+/// request_id: message_id passed to message id callback with request.
+/// message: Response message to send.
+/// cb: Callback that will be called after delivery acknowledgement received or on error.
+///     Will be called exactly once.
+///
+/// #Returns
+/// err: Error code
+/// cb:
+/// - xcommand_handle: Command handle to map callback to caller context.
+/// - err: Error code
+///
+/// #Errors
+/// AgentOutboundQueueFullError - connection is reconnecting and too many messages are queued already.
+/// AgentTimeoutError - delivery acknowledgement isn't received in time.
+#[no_mangle]
+pub extern fn indy_agent_reply(command_handle: i32,
+                               connection_handle: i32,
+                               request_id: *const c_char,
+                               message: *const c_char,
+                               cb: Option<extern fn(xcommand_handle: i32,
+                                                    err: ErrorCode)>) -> ErrorCode {
+    check_useful_c_str!(request_id, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(message, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    let cmd = Command::Agent(AgentCommand::Reply(
+        connection_handle,
+        request_id,
+        message,
+        Box::new(move |result| {
+            cb(command_handle, result_to_err_code!(result))
+        })
+    ));
+
+    let res = CommandExecutor::instance().send(cmd);
+    result_to_err_code!(res)
+}
+
 /// Closes agent connection.
 ///
 /// Note that this call works for both incoming and outgoing connections.
@@ -366,3 +851,139 @@ pub extern fn indy_agent_close_listener(command_handle: i32,
     let res = CommandExecutor::instance().send(cmd);
     result_to_err_code!(res)
 }
+
+/// Returns info of all opened agent connections: outgoing ones created by indy_agent_connect
+/// and incoming ones accepted by listeners.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// cb: Callback that will be called with connections info or on error. Will be called exactly once.
+///
+/// #Returns
+/// Error code
+/// cb:
+/// - xcommand_handle: command handle to map callback to caller context.
+/// - err: Error code
+/// - connections_json: list of connection info json described in indy_agent_get_connection_info.
+///     [<connection info json>, ...]
+#[no_mangle]
+pub extern fn indy_agent_list_connections(command_handle: i32,
+                                          cb: Option<extern fn(xcommand_handle: i32,
+                                                               err: ErrorCode,
+                                                               connections_json: *const c_char)>) -> ErrorCode {
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam2);
+
+    let cmd = Command::Agent(AgentCommand::ListConnections(
+        Box::new(move |result| {
+            let (err, connections_json) = result_to_err_code_1!(result, String::new());
+            let connections_json = CStringUtils::string_to_cstring(connections_json);
+            cb(command_handle, err, connections_json.as_ptr())
+        })
+    ));
+
+    let res = CommandExecutor::instance().send(cmd);
+    result_to_err_code!(res)
+}
+
+/// Returns info of opened agent connection.
+///
+/// Message counters include application messages and delivery acknowledgements:
+/// heartbeats and handshake aren't counted.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// connection_handle: Connection handle returned by indy_agent_connect or indy_agent_listen calls.
+/// cb: Callback that will be called with connection info or on error. Will be called exactly once.
+///
+/// #Returns
+/// Error code
+/// cb:
+/// - xcommand_handle: command handle to map callback to caller context.
+/// - err: Error code
+/// - connection_info_json: connection info json.
+///     {
+///         "connection_handle": <int>,
+///         "sender_did": <string> DID of connection initiator,
+///         "receiver_did": <string> DID of listener identity,
+///         "endpoint": <string> remote endpoint for outgoing connection, listener endpoint for incoming one,
+///         "direction": "outgoing" or "incoming",
+///         "listener_handle": <int> handle of listener accepted incoming connection, null for outgoing one,
+///         "state": "connecting", "connected" or "disconnected",
+///         "opened_at": <int> unix timestamp of connection creation,
+///         "stats": {
+///             "messages_sent": <int>,
+///             "messages_received": <int>,
+///             "bytes_sent": <int>,
+///             "bytes_received": <int>,
+///             "reconnects": <int> count of restores of the connection after loss
+///         }
+///     }
+///
+/// #Errors
+/// CommonInvalidStructure - connection with passed handle isn't found.
+#[no_mangle]
+pub extern fn indy_agent_get_connection_info(command_handle: i32,
+                                             connection_handle: i32,
+                                             cb: Option<extern fn(xcommand_handle: i32,
+                                                                  err: ErrorCode,
+                                                                  connection_info_json: *const c_char)>) -> ErrorCode {
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    let cmd = Command::Agent(AgentCommand::GetConnectionInfo(
+        connection_handle,
+        Box::new(move |result| {
+            let (err, connection_info_json) = result_to_err_code_1!(result, String::new());
+            let connection_info_json = CStringUtils::string_to_cstring(connection_info_json);
+            cb(command_handle, err, connection_info_json.as_ptr())
+        })
+    ));
+
+    let res = CommandExecutor::instance().send(cmd);
+    result_to_err_code!(res)
+}
+
+fn _listen(command_handle: i32,
+           endpoint: String,
+           config: Option<String>,
+           listener_cb: extern fn(xcommand_handle: i32,
+                                  err: ErrorCode,
+                                  listener_handle: i32),
+           connection_cb: extern fn(xlistener_handle: i32,
+                                    err: ErrorCode,
+                                    connection_handle: i32,
+                                    sender_did: *const c_char,
+                                    receiver_did: *const c_char),
+           message_cb: extern fn(xconnection_handle: i32,
+                                 err: ErrorCode,
+                                 message: *const c_char)) -> ErrorCode {
+    let cmd = Command::Agent(AgentCommand::Listen(
+        endpoint,
+        config,
+        Box::new(move |result| {
+            let (err, handle) = result_to_err_code_1!(result, 0);
+            listener_cb(command_handle, err, handle);
+        }),
+        Box::new(move |result| {
+            let (err, listener_handle, conn_handle, sender_did, receiver_did) =
+                result_to_err_code_4!(result, 0, 0, String::new(), String::new());
+            connection_cb(listener_handle, err, conn_handle,
+                          CStringUtils::string_to_cstring(sender_did).as_ptr(),
+                          CStringUtils::string_to_cstring(receiver_did).as_ptr());
+        }),
+        _message_cb(message_cb)
+    ));
+
+    let result = CommandExecutor::instance().send(cmd);
+
+    result_to_err_code!(result)
+}
+
+fn _message_cb(message_cb: extern fn(xconnection_handle: i32,
+                                     err: ErrorCode,
+                                     message: *const c_char)) -> AgentMessageCB {
+    Box::new(move |handle, result| {
+        let (err, msg) = result_to_err_code_1!(result, String::new());
+        let msg = CStringUtils::string_to_cstring(msg);
+        message_cb(handle, err, msg.as_ptr());
+    })
+}
@@ -200,7 +200,8 @@ pub extern fn indy_issuer_create_claim(command_handle: i32,
 /// cb: Callback that takes command result as parameter.
 ///
 /// #Returns
-/// Revocation registry update json with a revoked claim
+/// Revocation registry update json with a revoked claim.
+/// Publish it with indy_build_revoc_reg_entry_request to let verifiers get the new accumulator.
 ///
 /// #Errors
 /// Annoncreds*
@@ -236,6 +237,81 @@ pub extern fn indy_issuer_revoke_claim(command_handle: i32,
     result_to_err_code!(result)
 }
 
+/// Generates safe primes in background and stores them in a secure wallet as a pool.
+/// Creation of claim definition takes two safe primes from the pool if it contains enough of them,
+/// otherwise safe primes are generated during claim definition creation.
+///
+/// #Params
+/// wallet_handle: wallet handler (created by open_wallet).
+/// command_handle: command handle to map callback to user context.
+/// count: number of safe primes to generate.
+/// cb: Callback that takes command result as parameter. Called when all safe primes are stored in the pool.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_generate_safe_primes(command_handle: i32,
+                                               wallet_handle: i32,
+                                               count: i32,
+                                               cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode
+                                               )>) -> ErrorCode {
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Issuer(IssuerCommand::GenerateSafePrimes(
+            wallet_handle,
+            count,
+            Box::new(move |result| {
+                let err = result_to_err_code!(result);
+                cb(command_handle, err)
+            })
+        ))));
+
+    result_to_err_code!(result)
+}
+
+/// Imports externally generated safe primes to the pool stored in a secure wallet.
+/// Each prime must be a safe prime of the size used for claim definition keys.
+///
+/// #Params
+/// wallet_handle: wallet handler (created by open_wallet).
+/// command_handle: command handle to map callback to user context.
+/// safe_primes_json: list of safe primes as decimal strings:
+///     ["<safe_prime_1>", "<safe_prime_2>"]
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_import_safe_primes(command_handle: i32,
+                                             wallet_handle: i32,
+                                             safe_primes_json: *const c_char,
+                                             cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode
+                                             )>) -> ErrorCode {
+    check_useful_c_str!(safe_primes_json, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Issuer(IssuerCommand::ImportSafePrimes(
+            wallet_handle,
+            safe_primes_json,
+            Box::new(move |result| {
+                let err = result_to_err_code!(result);
+                cb(command_handle, err)
+            })
+        ))));
+
+    result_to_err_code!(result)
+}
+
 /// Stores a claim offer from the given issuer in a secure storage.
 ///
 /// #Params
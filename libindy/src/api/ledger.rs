@@ -145,6 +145,7 @@ pub extern fn indy_sign_request(command_handle: i32,
 
 
 /// Builds a request to get a DDO.
+/// Note that GET_DDO transaction isn't supported by the Ledger yet, use indy_resolve_did to get a DID document.
 ///
 /// #Params
 /// command_handle: command handle to map callback to caller context.
@@ -601,3 +602,365 @@ pub extern fn indy_build_get_txn_request(command_handle: i32,
 
     result_to_err_code!(result)
 }
+
+/// Builds a REVOC_REG_DEF request. Request to publish definition of revocation registry:
+/// public key of accumulator and max number of claims.
+/// Revocation registry is identified on the ledger by "<issuer_did>:<schema_seq_no>".
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// submitter_did: Id of Identity stored in secured Wallet.
+/// data: revocation registry json returned by indy_issuer_create_and_store_revoc_reg.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Request result as json.
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_build_revoc_reg_def_request(command_handle: i32,
+                                               submitter_did: *const c_char,
+                                               data: *const c_char,
+                                               cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                                    request_json: *const c_char)>) -> ErrorCode {
+    check_useful_c_str!(submitter_did, ErrorCode::CommonInvalidParam2);
+    check_useful_c_str!(data, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::BuildRevocRegDefRequest(
+            submitter_did,
+            data,
+            Box::new(move |result| {
+                let (err, request_json) = result_to_err_code_1!(result, String::new());
+                let request_json = CStringUtils::string_to_cstring(request_json);
+                cb(command_handle, err, request_json.as_ptr())
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Builds a REVOC_REG_ENTRY request. Request to publish current accumulator of revocation registry.
+/// Should be sent after each change of revocation registry to let verifiers check non-revocation.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// submitter_did: Id of Identity stored in secured Wallet.
+/// data: revocation registry update json returned by indy_issuer_create_claim or indy_issuer_revoke_claim.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Request result as json.
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_build_revoc_reg_entry_request(command_handle: i32,
+                                                 submitter_did: *const c_char,
+                                                 data: *const c_char,
+                                                 cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                                      request_json: *const c_char)>) -> ErrorCode {
+    check_useful_c_str!(submitter_did, ErrorCode::CommonInvalidParam2);
+    check_useful_c_str!(data, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::BuildRevocRegEntryRequest(
+            submitter_did,
+            data,
+            Box::new(move |result| {
+                let (err, request_json) = result_to_err_code_1!(result, String::new());
+                let request_json = CStringUtils::string_to_cstring(request_json);
+                cb(command_handle, err, request_json.as_ptr())
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Builds a GET_REVOC_REG_DEF request. Request to get definition of revocation registry.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// submitter_did: Id of Identity stored in secured Wallet.
+/// id: revocation registry id: "<issuer_did>:<schema_seq_no>".
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Request result as json.
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_build_get_revoc_reg_def_request(command_handle: i32,
+                                                   submitter_did: *const c_char,
+                                                   id: *const c_char,
+                                                   cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                                        request_json: *const c_char)>) -> ErrorCode {
+    check_useful_c_str!(submitter_did, ErrorCode::CommonInvalidParam2);
+    check_useful_c_str!(id, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::BuildGetRevocRegDefRequest(
+            submitter_did,
+            id,
+            Box::new(move |result| {
+                let (err, request_json) = result_to_err_code_1!(result, String::new());
+                let request_json = CStringUtils::string_to_cstring(request_json);
+                cb(command_handle, err, request_json.as_ptr())
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Builds a GET_REVOC_REG request. Request to get accumulator of revocation registry
+/// as it was at the given time.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// submitter_did: Id of Identity stored in secured Wallet.
+/// revoc_reg_def_id: revocation registry id: "<issuer_did>:<schema_seq_no>".
+/// timestamp: time (unix timestamp in seconds) the accumulator should be actual for.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Request result as json.
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_build_get_revoc_reg_request(command_handle: i32,
+                                               submitter_did: *const c_char,
+                                               revoc_reg_def_id: *const c_char,
+                                               timestamp: i64,
+                                               cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                                    request_json: *const c_char)>) -> ErrorCode {
+    check_useful_c_str!(submitter_did, ErrorCode::CommonInvalidParam2);
+    check_useful_c_str!(revoc_reg_def_id, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::BuildGetRevocRegRequest(
+            submitter_did,
+            revoc_reg_def_id,
+            timestamp,
+            Box::new(move |result| {
+                let (err, request_json) = result_to_err_code_1!(result, String::new());
+                let request_json = CStringUtils::string_to_cstring(request_json);
+                cb(command_handle, err, request_json.as_ptr())
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Parses a GET_SCHEMA response to get Schema in the format compatible with Anoncreds API.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// get_schema_response: response of GET_SCHEMA request.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Schema json:
+/// {
+///     "seqNo": number, // sequence number of schema transaction in ledger
+///     "data": {
+///         "name": string,
+///         "version": string,
+///         "keys": [string] // attr_names of schema transaction
+///     }
+/// }
+///
+/// #Errors
+/// Common*
+/// LedgerNotFound if schema is not found on the ledger
+#[no_mangle]
+pub extern fn indy_parse_get_schema_response(command_handle: i32,
+                                             get_schema_response: *const c_char,
+                                             cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                                  schema_json: *const c_char)>) -> ErrorCode {
+    check_useful_c_str!(get_schema_response, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::ParseGetSchemaResponse(
+            get_schema_response,
+            Box::new(move |result| {
+                let (err, schema_json) = result_to_err_code_1!(result, String::new());
+                let schema_json = CStringUtils::string_to_cstring(schema_json);
+                cb(command_handle, err, schema_json.as_ptr())
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Parses a GET_CLAIM_DEF response to get Claim Definition in the format compatible with Anoncreds API.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// get_claim_def_response: response of GET_CLAIM_DEF request.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Claim definition json:
+/// {
+///     "ref": number, // sequence number of schema
+///     "origin": string, // issuer did
+///     "signature_type": string,
+///     "data": {
+///         "primary": primary public key,
+///         "revocation": revocation public key or null
+///     }
+/// }
+///
+/// #Errors
+/// Common*
+/// LedgerNotFound if claim definition is not found on the ledger
+#[no_mangle]
+pub extern fn indy_parse_get_claim_def_response(command_handle: i32,
+                                                get_claim_def_response: *const c_char,
+                                                cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                                     claim_def_json: *const c_char)>) -> ErrorCode {
+    check_useful_c_str!(get_claim_def_response, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::ParseGetClaimDefResponse(
+            get_claim_def_response,
+            Box::new(move |result| {
+                let (err, claim_def_json) = result_to_err_code_1!(result, String::new());
+                let claim_def_json = CStringUtils::string_to_cstring(claim_def_json);
+                cb(command_handle, err, claim_def_json.as_ptr())
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Parses a GET_NYM response to get NYM data.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// get_nym_response: response of GET_NYM request.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// NYM data json:
+/// {
+///     "identifier": string or null, // did of NYM transaction author
+///     "dest": string, // target did
+///     "role": string or null, // role code
+///     "verkey": string or null // verkey, may be abbreviated
+/// }
+///
+/// #Errors
+/// Common*
+/// LedgerNotFound if NYM is not found on the ledger
+#[no_mangle]
+pub extern fn indy_parse_get_nym_response(command_handle: i32,
+                                          get_nym_response: *const c_char,
+                                          cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                               nym_json: *const c_char)>) -> ErrorCode {
+    check_useful_c_str!(get_nym_response, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::ParseGetNymResponse(
+            get_nym_response,
+            Box::new(move |result| {
+                let (err, nym_json) = result_to_err_code_1!(result, String::new());
+                let nym_json = CStringUtils::string_to_cstring(nym_json);
+                cb(command_handle, err, nym_json.as_ptr())
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Parses a GET_REVOC_REG_DEF response to get definition of revocation registry.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// get_revoc_reg_def_response: response of GET_REVOC_REG_DEF request.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Revocation registry definition json:
+/// {
+///     "id": string, // revocation registry id
+///     "ref": number, // sequence number of schema
+///     "value": {
+///         "issuer_did": string,
+///         "max_claim_num": number,
+///         "acc_pk": accumulator public key
+///     }
+/// }
+///
+/// #Errors
+/// Common*
+/// LedgerNotFound if revocation registry definition is not found on the ledger
+#[no_mangle]
+pub extern fn indy_parse_get_revoc_reg_def_response(command_handle: i32,
+                                                    get_revoc_reg_def_response: *const c_char,
+                                                    cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                                         revoc_reg_def_json: *const c_char)>) -> ErrorCode {
+    check_useful_c_str!(get_revoc_reg_def_response, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::ParseGetRevocRegDefResponse(
+            get_revoc_reg_def_response,
+            Box::new(move |result| {
+                let (err, revoc_reg_def_json) = result_to_err_code_1!(result, String::new());
+                let revoc_reg_def_json = CStringUtils::string_to_cstring(revoc_reg_def_json);
+                cb(command_handle, err, revoc_reg_def_json.as_ptr())
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Parses a GET_REVOC_REG response to get revocation registry in the format compatible with Anoncreds API
+/// (revoc_regs_json of indy_prover_create_proof and indy_verifier_verify_proof).
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// revoc_reg_def_json: revocation registry definition json returned by indy_parse_get_revoc_reg_def_response.
+/// get_revoc_reg_response: response of GET_REVOC_REG request.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Revocation registry json with accumulator from the ledger.
+///
+/// #Errors
+/// Common*
+/// LedgerNotFound if revocation registry entry is not found on the ledger
+#[no_mangle]
+pub extern fn indy_parse_get_revoc_reg_response(command_handle: i32,
+                                                revoc_reg_def_json: *const c_char,
+                                                get_revoc_reg_response: *const c_char,
+                                                cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                                     revoc_reg_json: *const c_char)>) -> ErrorCode {
+    check_useful_c_str!(revoc_reg_def_json, ErrorCode::CommonInvalidParam2);
+    check_useful_c_str!(get_revoc_reg_response, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::ParseGetRevocRegResponse(
+            revoc_reg_def_json,
+            get_revoc_reg_response,
+            Box::new(move |result| {
+                let (err, revoc_reg_json) = result_to_err_code_1!(result, String::new());
+                let revoc_reg_json = CStringUtils::string_to_cstring(revoc_reg_json);
+                cb(command_handle, err, revoc_reg_json.as_ptr())
+            })
+        )));
+
+    result_to_err_code!(result)
+}
@@ -6,6 +6,7 @@ pub mod agent;
 pub mod anoncreds;
 pub mod signus;
 pub mod ledger;
+pub mod pairwise;
 pub mod pool;
 pub mod wallet;
 
@@ -106,6 +107,9 @@ pub enum ErrorCode
     // Attempt to create pool ledger config with name used for another existing pool
     PoolLedgerConfigAlreadyExistsError = 306,
 
+    // Requested entity (for example NYM of the DID) doesn't exist on the ledger
+    LedgerNotFound = 307,
+
     // Revocation registry is full and creation of new registry is necessary
     AnoncredsRevocationRegistryFullError = 400,
 
@@ -124,5 +128,21 @@ pub enum ErrorCode
 
     // Signus errors
     // Unknown format of DID entity keys
-    SignusUnknownCryptoError = 500
+    SignusUnknownCryptoError = 500,
+
+    // Attempt to register already existing crypto type
+    SignusCryptoTypeAlreadyRegisteredError = 501,
+
+    // Agent errors
+    // Connection to remote agent is lost. Library reconnects automatically and queues outgoing messages
+    AgentConnectionLostError = 600,
+
+    // Outgoing messages queue of disconnected connection is full
+    AgentOutboundQueueFullError = 602,
+
+    // Delivery acknowledgement or response to request isn't received in time
+    AgentTimeoutError = 603,
+
+    // Message exceeds maximum message size accepted by remote agent listener
+    AgentMessageTooLargeError = 604
 }
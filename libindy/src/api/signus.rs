@@ -6,11 +6,91 @@ use commands::{Command, CommandExecutor};
 use commands::signus::SignusCommand;
 use utils::cstring::CStringUtils;
 use utils::byte_array::vec_to_pointer;
+use std::ptr;
 use std::slice;
 
 use self::libc::c_char;
 
 
+/// Registers custom crypto type implementation.
+///
+/// It allows library user to provide custom signus implementation (for example, another curve
+/// or keys held in HSM). DIDs and keys with registered crypto_type can be created after it.
+/// Keys are passed to handlers exactly as they were returned by create_key handler.
+/// Anonymous encryption isn't supported for custom crypto types.
+///
+/// Handlers are called from libindy worker pool threads, possibly concurrently with each other
+/// and with themselves, so they must be thread-safe and reentrant.
+/// Buffers returned by create_key, sign, encrypt and decrypt handlers are copied and passed
+/// to the free handler on the same worker thread right after the handler returns.
+/// valid is written by verify handler as indy_bool_t (0 - invalid, non-zero - valid).
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// xtype: Crypto type name.
+/// create_key: Key pair creation handler (seed_raw is null if seed isn't provided)
+/// sign: Sign operation handler
+/// verify: Signature verification handler
+/// encrypt: Authenticated encryption handler
+/// decrypt: Authenticated decryption handler
+/// free: Handler that allows to de-allocate byte arrays allocated in caller code
+///
+/// #Returns
+/// Error code
+#[no_mangle]
+pub extern fn indy_register_crypto_type(command_handle: i32,
+                                        xtype: *const c_char,
+                                        create_key: Option<extern fn(seed_raw: *const u8, seed_len: u32,
+                                                                     verkey_raw: *mut *const u8, verkey_len: *mut u32,
+                                                                     signkey_raw: *mut *const u8, signkey_len: *mut u32) -> ErrorCode>,
+                                        sign: Option<extern fn(signkey_raw: *const u8, signkey_len: u32,
+                                                               message_raw: *const u8, message_len: u32,
+                                                               signature_raw: *mut *const u8, signature_len: *mut u32) -> ErrorCode>,
+                                        verify: Option<extern fn(verkey_raw: *const u8, verkey_len: u32,
+                                                                 message_raw: *const u8, message_len: u32,
+                                                                 signature_raw: *const u8, signature_len: u32,
+                                                                 valid: *mut u32) -> ErrorCode>,
+                                        encrypt: Option<extern fn(signkey_raw: *const u8, signkey_len: u32,
+                                                                  verkey_raw: *const u8, verkey_len: u32,
+                                                                  nonce_raw: *const u8, nonce_len: u32,
+                                                                  message_raw: *const u8, message_len: u32,
+                                                                  encrypted_msg_raw: *mut *const u8, encrypted_msg_len: *mut u32) -> ErrorCode>,
+                                        decrypt: Option<extern fn(signkey_raw: *const u8, signkey_len: u32,
+                                                                  verkey_raw: *const u8, verkey_len: u32,
+                                                                  nonce_raw: *const u8, nonce_len: u32,
+                                                                  encrypted_msg_raw: *const u8, encrypted_msg_len: u32,
+                                                                  decrypted_msg_raw: *mut *const u8, decrypted_msg_len: *mut u32) -> ErrorCode>,
+                                        free: Option<extern fn(data_raw: *const u8, data_len: u32) -> ErrorCode>,
+                                        cb: Option<extern fn(xcommand_handle: i32,
+                                                             err: ErrorCode)>) -> ErrorCode {
+    check_useful_c_str!(xtype, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(create_key, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(sign, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(verify, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(encrypt, ErrorCode::CommonInvalidParam6);
+    check_useful_c_callback!(decrypt, ErrorCode::CommonInvalidParam7);
+    check_useful_c_callback!(free, ErrorCode::CommonInvalidParam8);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam9);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Signus(SignusCommand::RegisterCryptoType(
+            xtype,
+            create_key,
+            sign,
+            verify,
+            encrypt,
+            decrypt,
+            free,
+            Box::new(move |result| {
+                let err = result_to_err_code!(result);
+                cb(command_handle, err)
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+
 /// Creates keys (signing and encryption keys) for a new
 /// DID (owned by the caller of the library).
 /// Identity's DID must be either explicitly provided, or taken as the first 16 bit of verkey.
@@ -30,6 +110,13 @@ use self::libc::c_char;
 ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
 ///               currently only 'ed25519' value is supported for this field)
 ///     "cid": bool, (optional; if not set then false is used;)
+///     "derivation_path": string, (optional; slash separated indexes, for example "0/1";
+///             if provided then keys are derived from the wallet master seed by this path
+///             and the same DID can be restored later from the master seed;
+///             a master seed is created in the wallet on first use; can't be used with seed)
+///     "method_name": string, (optional; DID method used to qualify a new DID, for example "sov"
+///             gives "did:sov:<did>"; if not set then the default method is used (see indy_set_default_did_method);
+///             provided "did" may be either bare or qualified)
 /// }
 /// cb: Callback that takes command result as parameter.
 ///
@@ -116,6 +203,95 @@ pub  extern fn indy_replace_keys(command_handle: i32,
     result_to_err_code!(result)
 }
 
+/// Generates new keys (signing and encryption keys) for an existing
+/// DID (owned by the caller of the library) and stores them as pending.
+/// Current keys are still used for signing and decryption until
+/// indy_replace_keys_apply is called, so NYM transaction with new verkey
+/// can be signed by current key and sent to the Ledger first.
+///
+/// #Params
+/// wallet_handle: wallet handler (created by open_wallet).
+/// command_handle: command handle to map callback to user context.
+/// did: DID to replace keys.
+/// identity_json: Identity information as json. Example:
+/// {
+///     "seed": string, (optional; if not provide then a random one will be created)
+///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
+///               currently only 'ed25519' value is supported for this field)
+/// }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// verkey (for verification of signature) and public_key (for decryption)
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_replace_keys_start(command_handle: i32,
+                                       wallet_handle: i32,
+                                       did: *const c_char,
+                                       identity_json: *const c_char,
+                                       cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                            verkey: *const c_char,
+                                                            pk: *const c_char)>) -> ErrorCode {
+    check_useful_c_str!(did, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(identity_json, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Signus(SignusCommand::ReplaceKeysStart(
+            wallet_handle,
+            identity_json,
+            did,
+            Box::new(move |result| {
+                let (err, verkey, pk) = result_to_err_code_2!(result, String::new(), String::new());
+                let verkey = CStringUtils::string_to_cstring(verkey);
+                let pk = CStringUtils::string_to_cstring(pk);
+                cb(command_handle, err, verkey.as_ptr(), pk.as_ptr())
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Applies keys generated by indy_replace_keys_start for the DID.
+/// Should be called after the Ledger confirms NYM transaction with new verkey.
+///
+/// #Params
+/// wallet_handle: wallet handler (created by open_wallet).
+/// command_handle: command handle to map callback to user context.
+/// did: DID to apply pending keys.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub  extern fn indy_replace_keys_apply(command_handle: i32,
+                                       wallet_handle: i32,
+                                       did: *const c_char,
+                                       cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode)>) -> ErrorCode {
+    check_useful_c_str!(did, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Signus(SignusCommand::ReplaceKeysApply(
+            wallet_handle,
+            did,
+            Box::new(move |result| {
+                let err = result_to_err_code!(result);
+                cb(command_handle, err)
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
 /// Saves their DID for a pairwise connection in a secured Wallet,
 /// so that it can be used to verify transaction.
 ///
@@ -375,4 +551,1045 @@ pub  extern fn indy_decrypt(command_handle: i32,
         )));
 
     result_to_err_code!(result)
-}
\ No newline at end of file
+}
+/// Verifies a signature with the given verkey.
+/// Neither wallet nor ledger is used, so the caller is responsible for verkey freshness.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// did(optional): DID the verkey belongs to. It is required only if verkey is abbreviated.
+/// verkey: verkey of the signer. Both full and abbreviated (starts with '~') forms are supported.
+/// message_raw: a pointer to first byte of message that was signed
+/// message_len: a message length
+/// signature_raw: a pointer to first byte of signature to be verified
+/// signature_len: a signature length
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// valid: true - if signature is valid, false - otherwise
+///
+/// #Errors
+/// Common*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_verify_signature_with_verkey(command_handle: i32,
+                                                 did: *const c_char,
+                                                 verkey: *const c_char,
+                                                 message_raw: *const u8,
+                                                 message_len: u32,
+                                                 signature_raw: *const u8,
+                                                 signature_len: u32,
+                                                 cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                                      valid: bool)>) -> ErrorCode {
+    check_useful_opt_c_str!(did, ErrorCode::CommonInvalidParam2);
+    check_useful_c_str!(verkey, ErrorCode::CommonInvalidParam3);
+    get_byte_array!(message_raw, message_len, ErrorCode::CommonInvalidParam4);
+    get_byte_array!(signature_raw, signature_len, ErrorCode::CommonInvalidParam6);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Signus(SignusCommand::VerifySignatureWithVerkey(
+            did,
+            verkey,
+            message_raw,
+            signature_raw,
+            Box::new(move |result| {
+                let (err, valid) = result_to_err_code_1!(result, false);
+                cb(command_handle, err, valid)
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Creates keys pair and stores in the wallet.
+/// Keys are not tied to any DID and are referenced by verkey.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// key_json: Key information as json. Example:
+/// {
+///     "seed": string, (optional; if not provide then a random one will be created)
+///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
+///               currently only 'ed25519' value is supported for this field)
+/// }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// verkey: verkey of generated key pair, also used as key identifier
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_create_key(command_handle: i32,
+                               wallet_handle: i32,
+                               key_json: *const c_char,
+                               cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                    verkey: *const c_char)>) -> ErrorCode {
+    check_useful_c_str!(key_json, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Signus(SignusCommand::CreateKey(
+            wallet_handle,
+            key_json,
+            Box::new(move |result| {
+                let (err, verkey) = result_to_err_code_1!(result, String::new());
+                let verkey = CStringUtils::string_to_cstring(verkey);
+                cb(command_handle, err, verkey.as_ptr())
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Signs a message with a key stored in the wallet (see indy_create_key).
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// my_vk: verkey of the key to sign with
+/// message_raw: a pointer to first byte of message to be signed
+/// message_len: a message length
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// a signature string
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_crypto_sign(command_handle: i32,
+                                wallet_handle: i32,
+                                my_vk: *const c_char,
+                                message_raw: *const u8,
+                                message_len: u32,
+                                cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                     signature_raw: *const u8, signature_len: u32)>) -> ErrorCode {
+    check_useful_c_str!(my_vk, ErrorCode::CommonInvalidParam3);
+    get_byte_array!(message_raw, message_len, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Signus(SignusCommand::CryptoSign(
+            wallet_handle,
+            my_vk,
+            message_raw,
+            Box::new(move |result| {
+                let (err, signature) = result_to_err_code_1!(result, Vec::new());
+                let (signature_raw, signature_len) = vec_to_pointer(&signature);
+                cb(command_handle, err, signature_raw, signature_len)
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Verifies a signature with the given verkey.
+/// Neither wallet nor ledger is used.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// their_vk: verkey of the signer
+/// message_raw: a pointer to first byte of message that was signed
+/// message_len: a message length
+/// signature_raw: a pointer to first byte of signature to be verified
+/// signature_len: a signature length
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// valid: true - if signature is valid, false - otherwise
+///
+/// #Errors
+/// Common*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_crypto_verify(command_handle: i32,
+                                  their_vk: *const c_char,
+                                  message_raw: *const u8,
+                                  message_len: u32,
+                                  signature_raw: *const u8,
+                                  signature_len: u32,
+                                  cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                       valid: bool)>) -> ErrorCode {
+    check_useful_c_str!(their_vk, ErrorCode::CommonInvalidParam2);
+    get_byte_array!(message_raw, message_len, ErrorCode::CommonInvalidParam3);
+    get_byte_array!(signature_raw, signature_len, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Signus(SignusCommand::CryptoVerify(
+            their_vk,
+            message_raw,
+            signature_raw,
+            Box::new(move |result| {
+                let (err, valid) = result_to_err_code_1!(result, false);
+                cb(command_handle, err, valid)
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Encrypts a message with a key stored in the wallet (see indy_create_key)
+/// for the owner of the given verkey.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// my_vk: verkey of the sender key
+/// their_vk: verkey of the recipient
+/// message_raw: a pointer to first byte of message that to be encrypted
+/// message_len: a message length
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// an encrypted message and nonce
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_crypto_box(command_handle: i32,
+                               wallet_handle: i32,
+                               my_vk: *const c_char,
+                               their_vk: *const c_char,
+                               message_raw: *const u8,
+                               message_len: u32,
+                               cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                    encrypted_msg_raw: *const u8, encrypted_msg_len: u32,
+                                                    nonce_raw: *const u8, nonce_len: u32)>) -> ErrorCode {
+    check_useful_c_str!(my_vk, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(their_vk, ErrorCode::CommonInvalidParam4);
+    get_byte_array!(message_raw, message_len, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Signus(SignusCommand::CryptoBox(
+            wallet_handle,
+            my_vk,
+            their_vk,
+            message_raw,
+            Box::new(move |result| {
+                let (err, encrypted_msg, nonce) = result_to_err_code_2!(result, Vec::new(), Vec::new());
+                let (encrypted_msg_raw, encrypted_msg_len) = vec_to_pointer(&encrypted_msg);
+                let (nonce_raw, nonce_len) = vec_to_pointer(&nonce);
+                cb(command_handle, err, encrypted_msg_raw, encrypted_msg_len, nonce_raw, nonce_len)
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Decrypts a message encrypted with indy_crypto_box.
+/// The recipient key must be stored in the wallet (see indy_create_key).
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// my_vk: verkey of the recipient key
+/// their_vk: verkey of the sender
+/// encrypted_msg_raw: a pointer to first byte of message that to be decrypted
+/// encrypted_msg_len: a message length
+/// nonce_raw: a pointer to first byte of nonce that encrypted message
+/// nonce_len: a nonce length
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// decrypted message
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_crypto_box_open(command_handle: i32,
+                                    wallet_handle: i32,
+                                    my_vk: *const c_char,
+                                    their_vk: *const c_char,
+                                    encrypted_msg_raw: *const u8,
+                                    encrypted_msg_len: u32,
+                                    nonce_raw: *const u8,
+                                    nonce_len: u32,
+                                    cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                         decrypted_msg_raw: *const u8, decrypted_msg_len: u32)>) -> ErrorCode {
+    check_useful_c_str!(my_vk, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(their_vk, ErrorCode::CommonInvalidParam4);
+    get_byte_array!(encrypted_msg_raw, encrypted_msg_len, ErrorCode::CommonInvalidParam5);
+    get_byte_array!(nonce_raw, nonce_len, ErrorCode::CommonInvalidParam7);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam9);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Signus(SignusCommand::CryptoBoxOpen(
+            wallet_handle,
+            my_vk,
+            their_vk,
+            encrypted_msg_raw,
+            nonce_raw,
+            Box::new(move |result| {
+                let (err, decrypted_msg) = result_to_err_code_1!(result, Vec::new());
+                let (decrypted_msg_raw, decrypted_msg_len) = vec_to_pointer(&decrypted_msg);
+                cb(command_handle, err, decrypted_msg_raw, decrypted_msg_len)
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Encrypts a message by anonymous-encryption scheme.
+/// Sealed boxes are designed to anonymously send messages to a recipient given its verkey.
+/// Only the recipient can decrypt these messages, using its private key.
+/// While the recipient can verify the integrity of the message, it cannot verify the identity of the sender.
+/// An ephemeral key pair is generated for every message, so neither wallet nor ledger is used.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// their_vk: verkey of the recipient
+/// message_raw: a pointer to first byte of message that to be encrypted
+/// message_len: a message length
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// an encrypted message
+///
+/// #Errors
+/// Common*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_crypto_anon_crypt(command_handle: i32,
+                                      their_vk: *const c_char,
+                                      message_raw: *const u8,
+                                      message_len: u32,
+                                      cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                           encrypted_msg_raw: *const u8, encrypted_msg_len: u32)>) -> ErrorCode {
+    check_useful_c_str!(their_vk, ErrorCode::CommonInvalidParam2);
+    get_byte_array!(message_raw, message_len, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Signus(SignusCommand::AnonCrypt(
+            their_vk,
+            message_raw,
+            Box::new(move |result| {
+                let (err, encrypted_msg) = result_to_err_code_1!(result, Vec::new());
+                let (encrypted_msg_raw, encrypted_msg_len) = vec_to_pointer(&encrypted_msg);
+                cb(command_handle, err, encrypted_msg_raw, encrypted_msg_len)
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Decrypts a message encrypted by anonymous-encryption scheme (see indy_crypto_anon_crypt).
+/// The recipient key must be stored in the wallet (see indy_create_key).
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// my_vk: verkey of the recipient key
+/// encrypted_msg_raw: a pointer to first byte of message that to be decrypted
+/// encrypted_msg_len: a message length
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// decrypted message
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_crypto_anon_decrypt(command_handle: i32,
+                                        wallet_handle: i32,
+                                        my_vk: *const c_char,
+                                        encrypted_msg_raw: *const u8,
+                                        encrypted_msg_len: u32,
+                                        cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                             decrypted_msg_raw: *const u8, decrypted_msg_len: u32)>) -> ErrorCode {
+    check_useful_c_str!(my_vk, ErrorCode::CommonInvalidParam3);
+    get_byte_array!(encrypted_msg_raw, encrypted_msg_len, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Signus(SignusCommand::AnonDecrypt(
+            wallet_handle,
+            my_vk,
+            encrypted_msg_raw,
+            Box::new(move |result| {
+                let (err, decrypted_msg) = result_to_err_code_1!(result, Vec::new());
+                let (decrypted_msg_raw, decrypted_msg_len) = vec_to_pointer(&decrypted_msg);
+                cb(command_handle, err, decrypted_msg_raw, decrypted_msg_len)
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Packs a message into self-describing JSON envelope for one or more recipients.
+/// The message is encrypted once with random content key, the content key is encrypted for each recipient.
+/// If sender verkey is passed the content key is encrypted by authenticated-encryption scheme
+/// (see indy_crypto_box) and sender verkey is sealed for each recipient, so only recipients can learn it.
+/// Otherwise the content key is encrypted by anonymous-encryption scheme (see indy_crypto_anon_crypt).
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// message_raw: a pointer to first byte of message that to be packed
+/// message_len: a message length
+/// receiver_keys: json array of recipient verkeys, for example ["verkey1", "verkey2"]
+/// sender_vk: verkey of the sender key stored in the wallet (see indy_create_key),
+///            NULL for anonymous packing
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// packed message json:
+/// {
+///     "version": "1.0",
+///     "alg": "Authcrypt" or "Anoncrypt",
+///     "recipients": [{
+///         "verkey": string, recipient verkey
+///         "encrypted_key": string, base64url (unpadded) encoded encrypted content key
+///         "sender": string, (Authcrypt only) base64url (unpadded) encoded sealed sender verkey
+///         "nonce": string, (Authcrypt only) base64url (unpadded) encoded nonce of encrypted content key
+///     }],
+///     "nonce": string, base64url (unpadded) encoded nonce of ciphertext
+///     "ciphertext": string, base64url (unpadded) encoded encrypted message
+/// }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_pack_message(command_handle: i32,
+                                 wallet_handle: i32,
+                                 message_raw: *const u8,
+                                 message_len: u32,
+                                 receiver_keys: *const c_char,
+                                 sender_vk: *const c_char,
+                                 cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                      packed_message: *const c_char)>) -> ErrorCode {
+    get_byte_array!(message_raw, message_len, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(receiver_keys, ErrorCode::CommonInvalidParam5);
+    check_useful_opt_c_str!(sender_vk, ErrorCode::CommonInvalidParam6);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Signus(SignusCommand::PackMessage(
+            wallet_handle,
+            message_raw,
+            receiver_keys,
+            sender_vk,
+            Box::new(move |result| {
+                let (err, packed_message) = result_to_err_code_1!(result, String::new());
+                let packed_message = CStringUtils::string_to_cstring(packed_message);
+                cb(command_handle, err, packed_message.as_ptr())
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Unpacks a message packed by indy_pack_message.
+/// The wallet is searched for a key of one of the message recipients (see indy_create_key).
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// packed_message: packed message json
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// message_raw, message_len: unpacked message
+/// sender_vk: verkey of the sender, NULL for anonymously packed message
+/// recipient_vk: verkey of the recipient key used to unpack the message
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+///
+/// WalletNotFoundError is returned if no key of message recipients is stored in the wallet.
+#[no_mangle]
+pub  extern fn indy_unpack_message(command_handle: i32,
+                                   wallet_handle: i32,
+                                   packed_message: *const c_char,
+                                   cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                        message_raw: *const u8, message_len: u32,
+                                                        sender_vk: *const c_char,
+                                                        recipient_vk: *const c_char)>) -> ErrorCode {
+    check_useful_c_str!(packed_message, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Signus(SignusCommand::UnpackMessage(
+            wallet_handle,
+            packed_message,
+            Box::new(move |result| {
+                let (err, message, sender_vk, recipient_vk) = result_to_err_code_3!(result, Vec::new(), None, String::new());
+                let (message_raw, message_len) = vec_to_pointer(&message);
+                let sender_vk = sender_vk.map(CStringUtils::string_to_cstring);
+                let recipient_vk = CStringUtils::string_to_cstring(recipient_vk);
+                cb(command_handle, err, message_raw, message_len,
+                   sender_vk.as_ref().map(|vk| vk.as_ptr()).unwrap_or(ptr::null()),
+                   recipient_vk.as_ptr())
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Saves/replaces the meta information for the giving DID in the wallet.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// did: the DID to store metadata (either my or their).
+/// metadata: the meta information that will be store with the DID (for example, label).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub  extern fn indy_set_did_metadata(command_handle: i32,
+                                     wallet_handle: i32,
+                                     did: *const c_char,
+                                     metadata: *const c_char,
+                                     cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode)>) -> ErrorCode {
+    check_useful_c_str!(did, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(metadata, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Signus(SignusCommand::SetDidMetadata(
+            wallet_handle,
+            did,
+            metadata,
+            Box::new(move |result| {
+                let err = result_to_err_code!(result);
+                cb(command_handle, err)
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Retrieves the meta information for the giving DID in the wallet.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// did: the DID to retrieve metadata.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// metadata: the meta information stored with the DID; Can be null if no metadata was saved for this DID.
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub  extern fn indy_get_did_metadata(command_handle: i32,
+                                     wallet_handle: i32,
+                                     did: *const c_char,
+                                     cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                          metadata: *const c_char)>) -> ErrorCode {
+    check_useful_c_str!(did, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Signus(SignusCommand::GetDidMetadata(
+            wallet_handle,
+            did,
+            Box::new(move |result| {
+                let (err, metadata) = result_to_err_code_1!(result, None);
+                let metadata = metadata.map(CStringUtils::string_to_cstring);
+                cb(command_handle, err, metadata.as_ref().map(|m| m.as_ptr()).unwrap_or(ptr::null()))
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Get info about My DID in format: DID, verkey, crypto type, metadata and pending rotation verkey.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// my_did: DID
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// DID with verkey and metadata:
+/// {
+///     "did": string,
+///     "verkey": string,
+///     "crypto_type": string,
+///     "metadata": string, (optional; null if metadata wasn't set)
+///     "temp_verkey": string, (optional; new verkey created by indy_replace_keys_start
+///                             that isn't applied yet)
+/// }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub  extern fn indy_get_my_did_with_meta(command_handle: i32,
+                                         wallet_handle: i32,
+                                         my_did: *const c_char,
+                                         cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                              did_with_meta: *const c_char)>) -> ErrorCode {
+    check_useful_c_str!(my_did, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Signus(SignusCommand::GetMyDidWithMeta(
+            wallet_handle,
+            my_did,
+            Box::new(move |result| {
+                let (err, did_with_meta) = result_to_err_code_1!(result, String::new());
+                let did_with_meta = CStringUtils::string_to_cstring(did_with_meta);
+                cb(command_handle, err, did_with_meta.as_ptr())
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Retrieves the information about all DIDs stored in the wallet.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// dids: List of DIDs with verkeys and meta data in the same format as indy_get_my_did_with_meta returns:
+/// [{
+///     "did": string,
+///     "verkey": string,
+///     "crypto_type": string,
+///     "metadata": string, (optional)
+///     "temp_verkey": string, (optional)
+/// }]
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub  extern fn indy_list_my_dids_with_meta(command_handle: i32,
+                                           wallet_handle: i32,
+                                           cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                                dids: *const c_char)>) -> ErrorCode {
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Signus(SignusCommand::ListMyDidsWithMeta(
+            wallet_handle,
+            Box::new(move |result| {
+                let (err, dids) = result_to_err_code_1!(result, String::new());
+                let dids = CStringUtils::string_to_cstring(dids);
+                cb(command_handle, err, dids.as_ptr())
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Returns the wallet master seed used to derive DIDs by derivation path (see indy_create_and_store_my_did).
+/// Master seed is created in the wallet on the first derivation of DID (or stored by indy_import_master_seed),
+/// WalletNotFoundError is returned if the wallet doesn't contain it yet.
+/// Backup of the master seed is enough to restore all DIDs derived from it.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// master_seed: base58 encoded master seed
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub  extern fn indy_export_master_seed(command_handle: i32,
+                                       wallet_handle: i32,
+                                       cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                            master_seed: *const c_char)>) -> ErrorCode {
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Signus(SignusCommand::ExportMasterSeed(
+            wallet_handle,
+            Box::new(move |result| {
+                let (err, master_seed) = result_to_err_code_1!(result, String::new());
+                let master_seed = CStringUtils::string_to_cstring(master_seed);
+                cb(command_handle, err, master_seed.as_ptr())
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Stores the master seed (previously returned by indy_export_master_seed) in the wallet,
+/// so DIDs derived from it can be restored by indy_create_and_store_my_did with the same derivation paths.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// master_seed: base58 encoded master seed.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+///
+/// CommonInvalidState is returned if the wallet already contains another master seed.
+#[no_mangle]
+pub  extern fn indy_import_master_seed(command_handle: i32,
+                                       wallet_handle: i32,
+                                       master_seed: *const c_char,
+                                       cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode)>) -> ErrorCode {
+    check_useful_c_str!(master_seed, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Signus(SignusCommand::ImportMasterSeed(
+            wallet_handle,
+            master_seed,
+            Box::new(move |result| {
+                let err = result_to_err_code!(result);
+                cb(command_handle, err)
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Returns the wallet master seed (see indy_export_master_seed) as BIP39 mnemonic of 24 english words.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// mnemonic: space separated mnemonic words
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub  extern fn indy_export_master_seed_mnemonic(command_handle: i32,
+                                                wallet_handle: i32,
+                                                cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                                     mnemonic: *const c_char)>) -> ErrorCode {
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Signus(SignusCommand::ExportMasterSeedMnemonic(
+            wallet_handle,
+            Box::new(move |result| {
+                let (err, mnemonic) = result_to_err_code_1!(result, String::new());
+                let mnemonic = CStringUtils::string_to_cstring(mnemonic);
+                cb(command_handle, err, mnemonic.as_ptr())
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Stores the master seed given as BIP39 mnemonic (previously returned by indy_export_master_seed_mnemonic)
+/// in the wallet the same way as indy_import_master_seed.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// mnemonic: space separated mnemonic words.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+///
+/// CommonInvalidStructure is returned for unknown word or invalid checksum of mnemonic.
+/// CommonInvalidState is returned if the wallet already contains another master seed.
+#[no_mangle]
+pub  extern fn indy_import_master_seed_mnemonic(command_handle: i32,
+                                                wallet_handle: i32,
+                                                mnemonic: *const c_char,
+                                                cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode)>) -> ErrorCode {
+    check_useful_c_str!(mnemonic, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Signus(SignusCommand::ImportMasterSeedMnemonic(
+            wallet_handle,
+            mnemonic,
+            Box::new(move |result| {
+                let err = result_to_err_code!(result);
+                cb(command_handle, err)
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Sets the DID method used to qualify new DIDs created by indy_create_and_store_my_did
+/// when "method_name" isn't provided in did_json (for example "sov" gives "did:sov:<did>").
+/// All functions accept both bare and method-qualified DIDs; wallet records are shared by both forms
+/// and qualification is stripped when building ledger requests.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// method_name: DID method name (lowercase letters and digits); null to create bare DIDs (default).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub  extern fn indy_set_default_did_method(command_handle: i32,
+                                           method_name: *const c_char,
+                                           cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode)>) -> ErrorCode {
+    check_useful_opt_c_str!(method_name, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Signus(SignusCommand::SetDefaultDidMethod(
+            method_name,
+            Box::new(move |result| {
+                let err = result_to_err_code!(result);
+                cb(command_handle, err)
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Resolves a DID to a DID document assembled from its NYM and "endpoint" ATTRIB transactions on the Ledger.
+/// Ledger replies are accepted only after consensus of f+1 identical replies from pool nodes.
+/// The Ledger doesn't serve GET_DDO transaction, so it isn't used for resolution.
+/// Resolved NYM and ATTRIB data is cached in the wallet according to freshness settings set during initialization.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// pool_handle: pool handle.
+/// did: DID to resolve (bare or method-qualified).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// did_doc: DID document json:
+/// {
+///     "@context": "https://w3id.org/did/v1",
+///     "id": method-qualified DID (method of the given DID, default DID method or "sov"),
+///     "publicKey": [{"id": "<id>#key-1", "type": "Ed25519VerificationKey2018",
+///                    "controller": "<id>", "publicKeyBase58": full verkey}],
+///     "authentication": [{"type": "Ed25519SignatureAuthentication2018", "publicKey": "<id>#key-1"}],
+///     "service": [{"id": "<id>#agent", "type": "IndyAgent",
+///                  "serviceEndpoint": endpoint host address, "recipientKeys": [endpoint verkey]}],
+///                 (empty if endpoint attribute isn't set)
+///     "role": role name from NYM (TRUSTEE, STEWARD, TRUST_ANCHOR, TGB) or null
+/// }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Ledger*
+///
+/// LedgerNotFound is returned if NYM of the DID doesn't exist on the Ledger.
+#[no_mangle]
+pub  extern fn indy_resolve_did(command_handle: i32,
+                                wallet_handle: i32,
+                                pool_handle: i32,
+                                did: *const c_char,
+                                cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                     did_doc: *const c_char)>) -> ErrorCode {
+    check_useful_c_str!(did, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Signus(SignusCommand::ResolveDid(
+            wallet_handle,
+            pool_handle,
+            did,
+            Box::new(move |result| {
+                let (err, did_doc) = result_to_err_code_1!(result, String::new());
+                let did_doc = CStringUtils::string_to_cstring(did_doc);
+                cb(command_handle, err, did_doc.as_ptr())
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Saves/replaces the endpoint information for the given DID in the wallet.
+/// Endpoint is used by agent connections and returned by indy_get_endpoint_for_did
+/// without querying the Ledger (until it expires according to freshness settings).
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// did: DID the endpoint belongs to (bare or method-qualified).
+/// address: endpoint address, for example "127.0.0.1:9700".
+/// transport_key: base58 encoded key used to establish secure connections with the endpoint.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub  extern fn indy_set_endpoint_for_did(command_handle: i32,
+                                         wallet_handle: i32,
+                                         did: *const c_char,
+                                         address: *const c_char,
+                                         transport_key: *const c_char,
+                                         cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode)>) -> ErrorCode {
+    check_useful_c_str!(did, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(address, ErrorCode::CommonInvalidParam4);
+    check_useful_c_str!(transport_key, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Signus(SignusCommand::SetEndpointForDid(
+            wallet_handle,
+            did,
+            address,
+            transport_key,
+            Box::new(move |result| {
+                let err = result_to_err_code!(result);
+                cb(command_handle, err)
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Returns endpoint information for the given DID.
+/// Endpoint stored in the wallet (by indy_set_endpoint_for_did or cached from the Ledger) is used first.
+/// If it is missed or expired according to freshness settings set during initialization,
+/// then the "endpoint" ATTRIB is read from the Ledger and cached in the wallet.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// pool_handle: pool handle.
+/// did: DID to get endpoint for (bare or method-qualified).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// address: endpoint address.
+/// transport_key: base58 encoded transport key of the endpoint.
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Ledger*
+///
+/// LedgerNotFound is returned if the endpoint is found neither in the wallet nor on the Ledger.
+#[no_mangle]
+pub  extern fn indy_get_endpoint_for_did(command_handle: i32,
+                                         wallet_handle: i32,
+                                         pool_handle: i32,
+                                         did: *const c_char,
+                                         cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                              address: *const c_char,
+                                                              transport_key: *const c_char)>) -> ErrorCode {
+    check_useful_c_str!(did, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Signus(SignusCommand::GetEndpointForDid(
+            wallet_handle,
+            pool_handle,
+            did,
+            Box::new(move |result| {
+                let (err, address, transport_key) = result_to_err_code_2!(result, String::new(), String::new());
+                let address = CStringUtils::string_to_cstring(address);
+                let transport_key = CStringUtils::string_to_cstring(transport_key);
+                cb(command_handle, err, address.as_ptr(), transport_key.as_ptr())
+            })
+        )));
+
+    result_to_err_code!(result)
+}
+
+/// Publishes endpoint information for the given DID on the Ledger.
+/// Builds ATTRIB transaction with "endpoint" raw attribute ({"endpoint":{"ha": address, "verkey": transport_key}}),
+/// signs it with submitter's key and sends it to the pool.
+/// Note that the endpoint isn't saved in the wallet (see indy_set_endpoint_for_did).
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// pool_handle: pool handle.
+/// submitter_did: Id of Identity stored in secured Wallet.
+/// did: DID the endpoint belongs to.
+/// address: endpoint address, for example "127.0.0.1:9700".
+/// transport_key: base58 encoded key used to establish secure connections with the endpoint.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Request result as json.
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Ledger*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_publish_endpoint(command_handle: i32,
+                                     wallet_handle: i32,
+                                     pool_handle: i32,
+                                     submitter_did: *const c_char,
+                                     did: *const c_char,
+                                     address: *const c_char,
+                                     transport_key: *const c_char,
+                                     cb: Option<extern fn(xcommand_handle: i32, err: ErrorCode,
+                                                          request_result_json: *const c_char)>) -> ErrorCode {
+    check_useful_c_str!(submitter_did, ErrorCode::CommonInvalidParam4);
+    check_useful_c_str!(did, ErrorCode::CommonInvalidParam5);
+    check_useful_c_str!(address, ErrorCode::CommonInvalidParam6);
+    check_useful_c_str!(transport_key, ErrorCode::CommonInvalidParam7);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Signus(SignusCommand::PublishEndpoint(
+            wallet_handle,
+            pool_handle,
+            submitter_did,
+            did,
+            address,
+            transport_key,
+            Box::new(move |result| {
+                let (err, request_result_json) = result_to_err_code_1!(result, String::new());
+                let request_result_json = CStringUtils::string_to_cstring(request_result_json);
+                cb(command_handle, err, request_result_json.as_ptr())
+            })
+        )));
+
+    result_to_err_code!(result)
+}
@@ -13,22 +13,33 @@ use self::rust_base58::{FromBase58, ToBase58};
 
 use commands::{Command, CommandExecutor};
 use commands::ledger::LedgerCommand;
+use commands::pairwise::get_pairwise;
 use commands::utils::check_wallet_and_pool_handles_consistency;
+use errors::agent::AgentError;
 use errors::indy::IndyError;
 use errors::common::CommonError;
+use errors::ledger::LedgerError;
 use services::agent::AgentService;
-use services::ledger::LedgerService;
-use services::ledger::types::{Reply, GetNymResultData, GetNymReplyResult};
+use services::agent::policy::{self, ListenerPolicy};
+use services::ledger::{self, LedgerService};
+use services::ledger::types::{Reply, GetNymResultData, GetNymReplyResult, Endpoint};
 use services::pool::PoolService;
 use services::signus::types::{MyDid, TheirDid};
 use services::wallet::WalletService;
 use utils::crypto::ed25519::ED25519;
+use utils::did::DidUtils;
 use utils::json::JsonDecodable;
 use utils::sequence::SequenceUtils;
 use utils::crypto::verkey_builder::build_full_verkey;
 
 pub type AgentConnectCB = Box<Fn(Result<i32, IndyError>) + Send>;
-pub type AgentMessageCB = Box<Fn(Result<(i32, String), IndyError>) + Send>;
+pub type AgentMessageCB = Box<Fn(i32, Result<String, IndyError>) + Send>;
+/// (connection handle, message, message id)
+pub type AgentMessageIdCB = Box<Fn(i32, &str, &str) + Send>;
+/// (connection handle, message)
+pub type AgentBinaryMessageCB = Box<Fn(i32, &[u8]) + Send>;
+/// (listener handle, sender did, receiver did, sender role if known) -> is connection allowed
+pub type AgentAuthorizationCB = Box<Fn(i32, &str, &str, Option<&str>) -> bool + Send>;
 
 pub enum AgentCommand {
     Connect(
@@ -39,6 +50,13 @@ pub enum AgentCommand {
         AgentConnectCB, // connect cb
         AgentMessageCB, // message cb
     ),
+    ConnectPairwise(
+        i32, // pool handle
+        i32, // wallet handle
+        String, // their did
+        AgentConnectCB, // connect cb
+        AgentMessageCB, // message cb
+    ),
     ResumeConnectProcess(
         i32, // cmd handle
         Result<(MyConnectInfo, String /* get DDO result JSON */), IndyError>
@@ -57,6 +75,7 @@ pub enum AgentCommand {
     ),
     Listen(
         String, // endpoint
+        Option<String>, // config
         Box<Fn(Result<i32, IndyError>) + Send>, // listen cb
         Box<Fn(Result<(i32, i32, String, String), IndyError>) + Send>, // connect cb
         AgentMessageCB, // message cb
@@ -67,6 +86,7 @@ pub enum AgentCommand {
     ),
     ListenerCheckConnect(
         String, // did
+        String, // receiver did
         String, // pk
         i32, // listener handle
         i32, // pool handle
@@ -75,16 +95,41 @@ pub enum AgentCommand {
     ListenerResumeCheckConnect(
         i32, // listener handle
         String, // did
+        String, // receiver did
         String, // pk
         Result<String, IndyError> // get nym result
     ),
+    ListenerSetAuthorizationCB(
+        i32, // listener handle
+        Option<AgentAuthorizationCB>, // authorization cb
+        Box<Fn(Result<(), IndyError>) + Send>, // set cb
+    ),
     ListenerOnConnect(
         i32, // listener handle
         Result<(i32, i32, String, String), CommonError>, // (listener handle, new connection handle, sender and receiver did) or error
     ),
     MessageReceived(
         i32, // connection handle
-        Result<(i32, String), CommonError> // result for message
+        Result<(String, Option<String>), AgentError> // (message, message id) or connection state change
+    ),
+    BinaryMessageReceived(
+        i32, // connection handle
+        Vec<u8>, // message
+    ),
+    /* handled by agent worker, isn't passed to executor */
+    ProtocolMessageReceived(
+        i32, // connection handle
+        String, // protocol message json
+    ),
+    SetMessageIdCB(
+        i32, // connection or listener handle
+        Option<AgentMessageIdCB>, // message id cb
+        Box<Fn(Result<(), IndyError>) + Send>, // set cb
+    ),
+    SetBinaryMessageCB(
+        i32, // connection or listener handle
+        Option<AgentBinaryMessageCB>, // binary message cb
+        Box<Fn(Result<(), IndyError>) + Send>, // set cb
     ),
     CloseListener(
         i32, // listener handle
@@ -120,9 +165,47 @@ pub enum AgentCommand {
         Option<String>, // message
         Box<Fn(Result<(), IndyError>) + Send>, // send cb
     ),
+    SendBytes(
+        i32, // connection handle
+        Vec<u8>, // message
+        Box<Fn(Result<(), IndyError>) + Send>, // send cb
+    ),
     SendAck(
         i32, // send cmd handle
-        Result<(), CommonError>,
+        Result<(), AgentError>,
+    ),
+    SendWithAck(
+        i32, // connection handle
+        Option<String>, // message id
+        String, // message
+        Box<Fn(Result<String, IndyError>) + Send>, // send cb
+    ),
+    Reply(
+        i32, // connection handle
+        String, // request message id
+        String, // message
+        Box<Fn(Result<(), IndyError>) + Send>, // reply cb
+    ),
+    SendRequest(
+        i32, // connection handle
+        String, // message
+        u32, // timeout in ms
+        Box<Fn(Result<String, IndyError>) + Send>, // response cb
+    ),
+    SendRequestAck(
+        i32, // request cmd handle
+        Result<String, AgentError>, // response
+    ),
+    ListConnections(
+        Box<Fn(Result<String, IndyError>) + Send>, // connections info cb
+    ),
+    GetConnectionInfo(
+        i32, // connection handle
+        Box<Fn(Result<String, IndyError>) + Send>, // connection info cb
+    ),
+    ConnectionsInfoAck(
+        i32, // cmd handle
+        Result<String, CommonError>, // connections info json
     )
 }
 
@@ -134,6 +217,8 @@ pub struct AgentCommandExecutor {
 
     out_connections: RefCell<HashMap<i32, AgentMessageCB>>,
     listeners: RefCell<HashMap<i32, Listener>>,
+    message_id_callbacks: RefCell<HashMap<i32 /* connection or listener handle */, AgentMessageIdCB>>,
+    binary_message_callbacks: RefCell<HashMap<i32 /* connection or listener handle */, AgentBinaryMessageCB>>,
 
     listen_callbacks: RefCell<HashMap<i32, (
         Box<Fn(Result<i32, IndyError>) + Send>, // listen cb
@@ -142,13 +227,17 @@ pub struct AgentCommandExecutor {
     add_rm_identity_callbacks: RefCell<HashMap<i32, Box<Fn(Result<(), IndyError>)>>>,
     connect_callbacks: RefCell<HashMap<i32, (AgentConnectCB, AgentMessageCB)>>,
     send_callbacks: RefCell<HashMap<i32, Box<Fn(Result<(), IndyError>)>>>,
+    request_callbacks: RefCell<HashMap<i32, Box<Fn(Result<String, IndyError>)>>>,
     close_callbacks: RefCell<HashMap<i32, Box<Fn(Result<(), IndyError>)>>>,
+    info_callbacks: RefCell<HashMap<i32, Box<Fn(Result<String, IndyError>)>>>,
 }
 
 struct Listener {
     on_connect: Box<Fn(Result<(i32, i32, String, String), IndyError>) + Send>,
     on_msg: AgentMessageCB,
     conn_handles: HashSet<i32>,
+    policy: ListenerPolicy,
+    authorization_cb: Option<AgentAuthorizationCB>,
 }
 
 impl AgentCommandExecutor {
@@ -160,11 +249,15 @@ impl AgentCommandExecutor {
             wallet_service: wallet_service,
             out_connections: RefCell::new(HashMap::new()),
             listeners: RefCell::new(HashMap::new()),
+            message_id_callbacks: RefCell::new(HashMap::new()),
+            binary_message_callbacks: RefCell::new(HashMap::new()),
             listen_callbacks: RefCell::new(HashMap::new()),
             add_rm_identity_callbacks: RefCell::new(HashMap::new()),
             connect_callbacks: RefCell::new(HashMap::new()),
             send_callbacks: RefCell::new(HashMap::new()),
+            request_callbacks: RefCell::new(HashMap::new()),
             close_callbacks: RefCell::new(HashMap::new()),
+            info_callbacks: RefCell::new(HashMap::new()),
         }
     }
 
@@ -174,6 +267,10 @@ impl AgentCommandExecutor {
                 info!(target: "agent_command_executor", "Connect command received");
                 self.connect(pool_handle, wallet_handle, sender_did, receiver_did, connect_cb, message_cb)
             }
+            AgentCommand::ConnectPairwise(pool_handle, wallet_handle, their_did, connect_cb, message_cb) => {
+                info!(target: "agent_command_executor", "ConnectPairwise command received");
+                self.connect_pairwise(pool_handle, wallet_handle, their_did, connect_cb, message_cb)
+            }
             AgentCommand::ResumeConnectProcess(cmd_id, res) => {
                 info!(target: "agent_command_executor", "ResumeConnectProcess command received");
                 self.resume_connect_process(cmd_id, res);
@@ -182,30 +279,49 @@ impl AgentCommandExecutor {
                 info!(target: "agent_command_executor", "ConnectAck command received");
                 self.on_connect_ack(cmd_id, res);
             }
-            AgentCommand::Listen(endpoint, listen_cb, connect_cb, message_cb) => {
+            AgentCommand::Listen(endpoint, config, listen_cb, connect_cb, message_cb) => {
                 info!(target: "agent_command_executor", "Listen command received");
-                self.listen(endpoint, listen_cb, connect_cb, message_cb);
+                self.listen(endpoint, config, listen_cb, connect_cb, message_cb);
             }
             AgentCommand::ListenAck(cmd_id, res) => {
                 info!(target: "agent_command_executor", "ListenAck command received");
                 self.on_listen_ack(cmd_id, res);
             }
-            AgentCommand::ListenerCheckConnect(did, pk, listener_handle, pool_handle, wallet_handle) => {
+            AgentCommand::ListenerCheckConnect(did, receiver_did, pk, listener_handle, pool_handle, wallet_handle) => {
                 info!(target: "agent_command_executor", "ListenerCheckConnect command received");
-                self.check_connect(did, pk, listener_handle, pool_handle, wallet_handle);
+                self.check_connect(did, receiver_did, pk, listener_handle, pool_handle, wallet_handle);
             }
-            AgentCommand::ListenerResumeCheckConnect(listener_handle, did, pk, res) => {
+            AgentCommand::ListenerResumeCheckConnect(listener_handle, did, receiver_did, pk, res) => {
                 info!(target: "agent_command_executor", "ListenerResumeCheckConnect command received");
-                self.resume_check_connect(listener_handle, did, pk, res);
+                self.resume_check_connect(listener_handle, did, receiver_did, pk, res);
+            }
+            AgentCommand::ListenerSetAuthorizationCB(listener_handle, authorization_cb, cb) => {
+                info!(target: "agent_command_executor", "ListenerSetAuthorizationCB command received");
+                self.set_authorization_cb(listener_handle, authorization_cb, cb);
             }
             AgentCommand::ListenerOnConnect(listener_id, res) => {
                 info!(target: "agent_command_executor", "ListenerOnConnect command received");
                 self.on_client_connected(listener_id, res);
             }
             AgentCommand::MessageReceived(connection_id, res) => {
-                info!(target: "agent_command_executor", "ListenerOnConnect command received");
+                info!(target: "agent_command_executor", "MessageReceived command received");
                 self.on_message_received(connection_id, res);
             }
+            AgentCommand::BinaryMessageReceived(connection_id, msg) => {
+                info!(target: "agent_command_executor", "BinaryMessageReceived command received");
+                self.on_binary_message_received(connection_id, msg);
+            }
+            AgentCommand::ProtocolMessageReceived(connection_id, _) => {
+                error!("Can't handle ProtocolMessageReceived cmd for {} - must be handled by agent worker", connection_id);
+            }
+            AgentCommand::SetMessageIdCB(handle, message_id_cb, cb) => {
+                info!(target: "agent_command_executor", "SetMessageIdCB command received");
+                self.set_message_id_cb(handle, message_id_cb, cb);
+            }
+            AgentCommand::SetBinaryMessageCB(handle, binary_message_cb, cb) => {
+                info!(target: "agent_command_executor", "SetBinaryMessageCB command received");
+                self.set_binary_message_cb(handle, binary_message_cb, cb);
+            }
             AgentCommand::ListenerAddIdentity(listener_handle, pool_handle, wallet_handle, did, cb) => {
                 info!(target: "agent_command_executor", "ListenerAddIdentity command received");
                 self.add_identity(listener_handle, pool_handle, wallet_handle, did, cb);
@@ -226,10 +342,31 @@ impl AgentCommandExecutor {
                 info!(target: "agent_command_executor", "Send command received");
                 self.send(connection_id, msg, cb)
             }
+            AgentCommand::SendBytes(connection_id, msg, cb) => {
+                info!(target: "agent_command_executor", "SendBytes command received");
+                self.send_bytes(connection_id, msg, cb)
+            }
             AgentCommand::SendAck(cmd_id, res) => {
                 info!(target: "agent_command_executor", "SendAck command received");
                 self.on_send_ack(cmd_id, res);
             }
+            AgentCommand::SendWithAck(connection_id, msg_id, msg, cb) => {
+                info!(target: "agent_command_executor", "SendWithAck command received");
+                self.send_with_ack(connection_id, msg_id, None, msg, cb)
+            }
+            AgentCommand::Reply(connection_id, request_id, msg, cb) => {
+                info!(target: "agent_command_executor", "Reply command received");
+                self.send_with_ack(connection_id, None, Some(request_id), msg,
+                                   Box::new(move |res: Result<String, IndyError>| cb(res.map(|_| ()))))
+            }
+            AgentCommand::SendRequest(connection_id, msg, timeout_ms, cb) => {
+                info!(target: "agent_command_executor", "SendRequest command received");
+                self.send_request(connection_id, msg, timeout_ms, cb)
+            }
+            AgentCommand::SendRequestAck(cmd_id, res) => {
+                info!(target: "agent_command_executor", "SendRequestAck command received");
+                self.on_send_request_ack(cmd_id, res);
+            }
             AgentCommand::CloseConnection(connection_id, cb) => {
                 info!(target: "agent_command_executor", "CloseConnection command received");
                 self.close_connection_or_listener(connection_id, cb, false)
@@ -246,6 +383,18 @@ impl AgentCommandExecutor {
                 info!(target: "agent_command_executor", "CloseListenerAck command received");
                 self.on_close_listener_ack(cmd_id, res);
             }
+            AgentCommand::ListConnections(cb) => {
+                info!(target: "agent_command_executor", "ListConnections command received");
+                self.get_connections_info(None, cb);
+            }
+            AgentCommand::GetConnectionInfo(connection_id, cb) => {
+                info!(target: "agent_command_executor", "GetConnectionInfo command received");
+                self.get_connections_info(Some(connection_id), cb);
+            }
+            AgentCommand::ConnectionsInfoAck(cmd_id, res) => {
+                info!(target: "agent_command_executor", "ConnectionsInfoAck command received");
+                self.on_connections_info_ack(cmd_id, res);
+            }
         }
     }
 
@@ -264,6 +413,14 @@ impl AgentCommandExecutor {
         }
     }
 
+    fn connect_pairwise(&self, pool_handle: i32, wallet_handle: i32, their_did: String,
+                        connect_cb: AgentConnectCB, message_cb: AgentMessageCB) {
+        match get_pairwise(&self.wallet_service, wallet_handle, &their_did) {
+            Ok(pairwise) => self.connect(pool_handle, wallet_handle, pairwise.my_did, pairwise.their_did, connect_cb, message_cb),
+            Err(err) => connect_cb(Err(err)),
+        }
+    }
+
     fn do_connect(&self, my_info: MyConnectInfo, info: ConnectInfo,
                   connect_cb: AgentConnectCB, message_cb: AgentMessageCB) {
         debug!("AgentCommandExecutor::connect try to service.connect with {:?}", info);
@@ -288,19 +445,9 @@ impl AgentCommandExecutor {
         let cbs = self.connect_callbacks.borrow_mut().remove(&cmd_id);
         if let Some((connect_cb, on_msg)) = cbs {
             let res = res.and_then(|(my_info, attrib_resp_json)| -> Result<(MyConnectInfo, ConnectInfo), IndyError> {
-                let attrib_resp: serde_json::Value = serde_json::from_str(attrib_resp_json.as_str()).map_err(|err|
-                    CommonError::InvalidStructure(
-                        format!("Can't parse get ATTRIB response json {}", err.description())))?; // TODO change error type?
-                let attrib_data_json = attrib_resp["result"]["data"].as_str().ok_or(
-                    CommonError::InvalidStructure(
-                        format!("Can't parse get ATTRIB response - sub-field result.data not found: {}", attrib_resp_json)))?; // TODO
-                let attrib_data: AttribData = AttribData::from_json(attrib_data_json).map_err(|err|
-                    CommonError::InvalidStructure(
-                        format!("Can't parse get ATTRIB response data {}", err.description())))?; // TODO
-                let conn_info = ConnectInfo {
-                    endpoint: attrib_data.endpoint.ha,
-                    server_key: attrib_data.endpoint.verkey,
-                };
+                let endpoint = self.ledger_service.parse_get_attrib_endpoint(&attrib_resp_json)?
+                    .ok_or(LedgerError::NotFound(format!("Endpoint for did {} not found on the ledger", my_info.receiver_did)))?;
+                let conn_info = AgentCommandExecutor::_endpoint_to_connect_info(endpoint)?;
                 Ok((my_info, conn_info))
             });
             match res {
@@ -314,7 +461,7 @@ impl AgentCommandExecutor {
 
     fn get_connection_info_local(&self, wallet_handle: i32, sender_did: &String, receiver_did: &String)
                                  -> Result<(MyConnectInfo, Option<ConnectInfo>), IndyError> {
-        let my_did_json = self.wallet_service.get(wallet_handle, &format!("my_did::{}", sender_did))?;
+        let my_did_json = self.wallet_service.get(wallet_handle, &format!("my_did::{}", DidUtils::normalize(sender_did)))?;
         let my_did: MyDid = MyDid::from_json(&my_did_json)
             .map_err(|_| CommonError::InvalidState((format!("Invalid my did json"))))?;
         let my_connect_info = MyConnectInfo {
@@ -324,7 +471,13 @@ impl AgentCommandExecutor {
             public_key: my_did.pk,
         };
 
-        let their_did_json = self.wallet_service.get_not_expired(wallet_handle, &format!("their_did::{}", receiver_did));
+        if let Ok(endpoint_json) = self.wallet_service.get_not_expired(wallet_handle, &format!("endpoint::{}", DidUtils::normalize(receiver_did))) {
+            let endpoint = Endpoint::from_json(&endpoint_json)
+                .map_err(|_| CommonError::InvalidState((format!("Invalid endpoint json"))))?;
+            return Ok((my_connect_info, Some(AgentCommandExecutor::_endpoint_to_connect_info(endpoint)?)));
+        }
+
+        let their_did_json = self.wallet_service.get_not_expired(wallet_handle, &format!("their_did::{}", DidUtils::normalize(receiver_did)));
         let their_did_json = if let Ok(their_did_json) = their_did_json {
             their_did_json
         } else {
@@ -345,6 +498,16 @@ impl AgentCommandExecutor {
         }
     }
 
+    fn _endpoint_to_connect_info(endpoint: Endpoint) -> Result<ConnectInfo, CommonError> {
+        match endpoint {
+            Endpoint { ha: Some(ha), verkey: Some(verkey) } => Ok(ConnectInfo {
+                endpoint: ha,
+                server_key: verkey,
+            }),
+            _ => Err(CommonError::InvalidStructure(format!("Endpoint must contain both ha and verkey")))
+        }
+    }
+
     fn request_connection_info_from_ledger(&self, pool_handle: i32, wallet_handle: i32,
                                            my_conn_info: MyConnectInfo,
                                            connect_cb: AgentConnectCB, message_cb: AgentMessageCB) {
@@ -381,26 +544,30 @@ impl AgentCommandExecutor {
         }
     }
 
-    fn listen(&self, endpoint: String,
+    fn listen(&self, endpoint: String, config: Option<String>,
               listen_cb: Box<Fn(Result<i32, IndyError>) + Send>,
               connect_cb: Box<Fn(Result<(i32, i32, String, String), IndyError>) + Send>,
               message_cb: AgentMessageCB) {
-        let result = self.agent_service
-            .listen(endpoint.as_str())
-            .and_then(|cmd_id| {
+        let result = ListenerPolicy::from_config(config.as_ref().map(String::as_str))
+            .and_then(|policy| self.agent_service
+                .listen(endpoint.as_str(), config.as_ref().map(String::as_str))
+                .map(|cmd_id| (policy, cmd_id)))
+            .and_then(|(policy, cmd_id)| {
                 match self.listen_callbacks.try_borrow_mut() {
-                    Ok(cbs) => Ok((cbs, cmd_id)),
+                    Ok(cbs) => Ok((cbs, policy, cmd_id)),
                     Err(err) => Err(CommonError::InvalidState(err.description().to_string())),
                 }
             });
         match result {
             Err(err) => listen_cb(Err(From::from(err)).map_err(map_err_err!())),
-            Ok((mut cbs, handle)) => {
+            Ok((mut cbs, policy, handle)) => {
                 cbs.insert(handle, (listen_cb,
                                     Listener {
                                         on_connect: connect_cb,
                                         on_msg: message_cb,
-                                        conn_handles: HashSet::new()
+                                        conn_handles: HashSet::new(),
+                                        policy: policy,
+                                        authorization_cb: None,
                                     })); /* TODO check if map contains same key */
             }
         };
@@ -428,33 +595,106 @@ impl AgentCommandExecutor {
         }
     }
 
-    fn on_message_received(&self, connection_id: i32, res: Result<(i32, String), CommonError>) {
+    /// Message with id is passed to message id callback of connection or its listener if it is set.
+    fn on_message_received(&self, connection_id: i32, res: Result<(String, Option<String>), AgentError>) {
         let listeners = self.listeners.borrow();
         let out_connections = self.out_connections.borrow();
-        let cb = match listeners.iter().find(|&(_, listener)| listener.conn_handles.contains(&connection_id)) {
+        let listener = listeners.iter().find(|&(_, listener)| listener.conn_handles.contains(&connection_id));
+        let cb = match listener {
             Some((_, listener)) => Some(&listener.on_msg),
             None => out_connections.get(&connection_id),
         };
-        if let Some(cb) = cb {
-            cb(res.map_err(From::from));
+        let cb = match cb {
+            Some(cb) => cb,
+            None => {
+                error!("Can't handle MessageReceived cmd - callback not found for {}", connection_id);
+                return;
+            }
+        };
+
+        if let Ok((ref msg, Some(ref msg_id))) = res {
+            let message_id_cbs = self.message_id_callbacks.borrow();
+            let listener_handle = listener.map(|(&listener_handle, _)| listener_handle);
+            if let Some(message_id_cb) = _find_handle_cb(&message_id_cbs, connection_id, listener_handle) {
+                return message_id_cb(connection_id, msg.as_str(), msg_id.as_str());
+            }
+        }
+        cb(connection_id, res.map(|(msg, _)| msg).map_err(From::from));
+    }
+
+    /// Binary message is dropped if neither connection nor its listener has binary message callback.
+    fn on_binary_message_received(&self, connection_id: i32, msg: Vec<u8>) {
+        let listener_handle = self.listeners.borrow().iter()
+            .find(|&(_, listener)| listener.conn_handles.contains(&connection_id))
+            .map(|(&listener_handle, _)| listener_handle);
+        let binary_message_cbs = self.binary_message_callbacks.borrow();
+        match _find_handle_cb(&binary_message_cbs, connection_id, listener_handle) {
+            Some(binary_message_cb) => binary_message_cb(connection_id, msg.as_slice()),
+            None => warn!("Binary message on connection {} dropped: binary message callback isn't set", connection_id),
+        }
+    }
+
+    fn set_message_id_cb(&self, handle: i32, message_id_cb: Option<AgentMessageIdCB>,
+                         cb: Box<Fn(Result<(), IndyError>)>) {
+        if let Err(err) = self._check_connection_or_listener(handle) {
+            return cb(Err(err).map_err(map_err_err!()));
+        }
+        match message_id_cb {
+            Some(message_id_cb) => { self.message_id_callbacks.borrow_mut().insert(handle, message_id_cb); }
+            None => { self.message_id_callbacks.borrow_mut().remove(&handle); }
+        }
+        cb(Ok(()))
+    }
+
+    fn set_binary_message_cb(&self, handle: i32, binary_message_cb: Option<AgentBinaryMessageCB>,
+                             cb: Box<Fn(Result<(), IndyError>)>) {
+        if let Err(err) = self._check_connection_or_listener(handle) {
+            return cb(Err(err).map_err(map_err_err!()));
+        }
+        match binary_message_cb {
+            Some(binary_message_cb) => { self.binary_message_callbacks.borrow_mut().insert(handle, binary_message_cb); }
+            None => { self.binary_message_callbacks.borrow_mut().remove(&handle); }
+        }
+        cb(Ok(()))
+    }
+
+    fn _check_connection_or_listener(&self, handle: i32) -> Result<(), IndyError> {
+        let is_known = self.out_connections.borrow().contains_key(&handle)
+            || self.listeners.borrow().iter()
+            .any(|(&listener_handle, listener)| listener_handle == handle || listener.conn_handles.contains(&handle));
+        if is_known {
+            Ok(())
         } else {
-            error!("Can't handle MessageReceived cmd - callback not found for {}", connection_id);
+            Err(IndyError::CommonError(CommonError::InvalidStructure(
+                format!("Connection or listener with handle {} not found", handle))))
         }
     }
 
-    fn check_connect(&self, did: String, pk: String, listener_handle: i32, pool_handle: i32, wallet_handle: i32) {
-        trace!("check_connect >> for did {}, pk {}, listener_handle {}, pool_handle {}, wallet_handle {}", did, pk, listener_handle, pool_handle, wallet_handle);
-        if let Ok(Some(actual_pk)) = self.get_info_for_check_connect(did.clone(), wallet_handle) {
-            self.do_check_connect(listener_handle, did.as_str(), pk.as_str(), Some(actual_pk.as_str()));
+    fn check_connect(&self, did: String, receiver_did: String, pk: String, listener_handle: i32, pool_handle: i32, wallet_handle: i32) {
+        trace!("check_connect >> for did {}, receiver_did {}, pk {}, listener_handle {}, pool_handle {}, wallet_handle {}", did, receiver_did, pk, listener_handle, pool_handle, wallet_handle);
+        let (is_did_allowed, requires_role) = match self.listeners.borrow().get(&listener_handle) {
+            Some(listener) => (listener.policy.is_did_allowed(did.as_str()), listener.policy.requires_role()),
+            None => (false, false),
+        };
+        if !is_did_allowed {
+            info!("Connection from {} to {} rejected by listener {} policy", did, receiver_did, listener_handle);
+            self.agent_service.on_connect_checked(listener_handle, did.as_str(), false).unwrap();
+            return;
+        }
+
+        /* role is available only from the ledger, so wallet lookup is skipped for role based policy */
+        let local_pk = if requires_role { None } else { self.get_info_for_check_connect(did.clone(), wallet_handle).ok().and_then(|pk| pk) };
+        if let Some(actual_pk) = local_pk {
+            self.do_check_connect(listener_handle, did.as_str(), receiver_did.as_str(), pk.as_str(), Some(actual_pk.as_str()), None);
         } else {
-            self.request_check_connect_info_from_ledger(pool_handle, wallet_handle, listener_handle, did.clone(), pk.clone())
-                .unwrap_or_else(|_| self.do_check_connect(listener_handle, did.as_str(), pk.as_str(), None));
+            self.request_check_connect_info_from_ledger(pool_handle, wallet_handle, listener_handle, did.clone(), receiver_did.clone(), pk.clone())
+                .unwrap_or_else(|_| self.do_check_connect(listener_handle, did.as_str(), receiver_did.as_str(), pk.as_str(), None, None));
         }
     }
 
-    fn resume_check_connect(&self, listener_handle: i32, did: String, pk: String, res: Result<String, IndyError>) {
-        trace!("resume_check_connect >> listener {}, did {}, pk {}, res {:?}", listener_handle, did, pk, res);
-        let res = res.and_then(|get_nym_response| -> Result<String, IndyError> {
+    fn resume_check_connect(&self, listener_handle: i32, did: String, receiver_did: String, pk: String, res: Result<String, IndyError>) {
+        trace!("resume_check_connect >> listener {}, did {}, receiver_did {}, pk {}, res {:?}", listener_handle, did, receiver_did, pk, res);
+        let res = res.and_then(|get_nym_response| -> Result<(String, String), IndyError> {
             let get_nym_response: Reply<GetNymReplyResult> = Reply::from_json(&get_nym_response)
                 .map_err(map_err_trace!())
                 .map_err(|_| CommonError::InvalidState(format!("Invalid their did json")))?;
@@ -470,29 +710,67 @@ impl AgentCommandExecutor {
                     .unwrap()
                     .as_slice())
                 .unwrap().to_base58();
+            let role = ledger::role_name(gen_nym_result_data.role.as_ref().map(String::as_str))
+                .unwrap_or(policy::USER_ROLE.to_string());
 
-            Ok(pk)
+            Ok((pk, role))
         });
-        self.do_check_connect(listener_handle, did.as_str(), pk.as_str(), res.ok().as_ref().map(String::as_str));
+        match res {
+            Ok((actual_pk, role)) =>
+                self.do_check_connect(listener_handle, did.as_str(), receiver_did.as_str(), pk.as_str(), Some(actual_pk.as_str()), Some(role.as_str())),
+            Err(_) =>
+                self.do_check_connect(listener_handle, did.as_str(), receiver_did.as_str(), pk.as_str(), None, None),
+        }
     }
 
-    fn do_check_connect(&self, listener_handle: i32, did: &str, received_pk: &str, actual_pk: Option<&str>) {
+    fn do_check_connect(&self, listener_handle: i32, did: &str, receiver_did: &str, received_pk: &str, actual_pk: Option<&str>, role: Option<&str>) {
         let check_result = actual_pk.ok_or(())
             .and_then(|actual_pk| _base58_to_z85(actual_pk)
                 .map_err(map_err_trace!()).map_err(|_| ()))
             .map(|pk| pk.eq(&received_pk))
             .unwrap_or(false);
 
+        let check_result = check_result && self.authorize(listener_handle, did, receiver_did, role);
+
         self.agent_service.on_connect_checked(listener_handle, did, check_result).unwrap();
     }
 
+    /// Applies role rules of listener policy and application authorization callback to sender with verified key.
+    fn authorize(&self, listener_handle: i32, did: &str, receiver_did: &str, role: Option<&str>) -> bool {
+        let listeners = self.listeners.borrow();
+        let listener = match listeners.get(&listener_handle) {
+            Some(listener) => listener,
+            None => return false,
+        };
+        if !listener.policy.is_role_allowed(role) {
+            info!("Connection from {} with role {:?} to {} rejected by listener {} policy", did, role, receiver_did, listener_handle);
+            return false;
+        }
+        match listener.authorization_cb {
+            Some(ref authorization_cb) => authorization_cb(listener_handle, did, receiver_did, role),
+            None => true,
+        }
+    }
+
+    fn set_authorization_cb(&self, listener_handle: i32, authorization_cb: Option<AgentAuthorizationCB>,
+                            cb: Box<Fn(Result<(), IndyError>)>) {
+        let res = match self.listeners.borrow_mut().get_mut(&listener_handle) {
+            Some(listener) => {
+                listener.authorization_cb = authorization_cb;
+                Ok(())
+            }
+            None => Err(IndyError::CommonError(CommonError::InvalidStructure(format!("Listener with handle {} not found", listener_handle)))),
+        };
+        cb(res.map_err(map_err_err!()))
+    }
+
     fn get_info_for_check_connect(&self, did: String, wallet_handle: i32) -> Result<Option<String>, IndyError> {
-        let td_json = self.wallet_service.get(wallet_handle, format!("their_did::{}", did).as_str())?;
+        let td_json = self.wallet_service.get(wallet_handle, format!("their_did::{}", DidUtils::normalize(&did)).as_str())?;
         let td: TheirDid = TheirDid::from_json(td_json.as_str()).unwrap();
         Ok(Some(td.pk.unwrap()))
     }
 
-    fn request_check_connect_info_from_ledger(&self, pool_handle: i32, wallet_handle: i32, listener_handle: i32, did: String, pk: String) -> Result<(), IndyError> {
+    fn request_check_connect_info_from_ledger(&self, pool_handle: i32, wallet_handle: i32, listener_handle: i32, did: String, receiver_did: String, pk: String) -> Result<(), IndyError> {
         check_wallet_and_pool_handles_consistency(self.wallet_service.clone(), self.pool_service.clone(), wallet_handle, pool_handle)?;
         let get_nym_request = match self.ledger_service
             .build_get_nym_request(did.as_str() /* TODO receiver did */, did.as_str()) {
@@ -504,7 +782,7 @@ impl AgentCommandExecutor {
             pool_handle, get_nym_request.to_string(),
             Box::new(move |res: Result<String, IndyError>| {
                 CommandExecutor::instance().send(Command::Agent(
-                    AgentCommand::ListenerResumeCheckConnect(listener_handle, did.clone(), pk.clone(), res))).unwrap();
+                    AgentCommand::ListenerResumeCheckConnect(listener_handle, did.clone(), receiver_did.clone(), pk.clone(), res))).unwrap();
             })))).unwrap();
 
         Ok(())
@@ -550,7 +828,7 @@ impl AgentCommandExecutor {
 
     fn get_mydid_from_wallet(&self, wallet_handle: i32, did: &str) -> Result<MyDid, IndyError> {
         self.wallet_service
-            .get(wallet_handle, format!("my_did::{}", did).as_str())
+            .get(wallet_handle, format!("my_did::{}", DidUtils::normalize(did)).as_str())
             .map_err(IndyError::from)
             .and_then(|my_did_json|
                 MyDid::from_json(my_did_json.as_str())
@@ -579,14 +857,72 @@ impl AgentCommandExecutor {
         }
     }
 
-    fn on_send_ack(&self, cmd_id: i32, res: Result<(), CommonError>) {
+    fn send_bytes(&self, conn_id: i32, msg: Vec<u8>, cb: Box<Fn(Result<(), IndyError>)>) {
+        let result = self.agent_service
+            .send_bytes(conn_id, msg.as_slice())
+            .and_then(|cmd_id| {
+                match self.send_callbacks.try_borrow_mut() {
+                    Ok(cbs) => Ok((cbs, cmd_id)),
+                    Err(err) => Err(CommonError::InvalidState(err.description().to_string())),
+                }
+            });
+        match result {
+            Ok((mut cbs, cmd_id)) => { cbs.insert(cmd_id, cb); }
+            Err(err) => cb(Err(From::from(err)).map_err(map_err_err!())),
+        }
+    }
+
+    fn on_send_ack(&self, cmd_id: i32, res: Result<(), AgentError>) {
         match self.send_callbacks.borrow_mut().remove(&cmd_id) {
             Some(cb) => cb(res.map_err(From::from)),
             None => error!("Can't handle SendAck cmd - callback not found for {}", cmd_id),
         };
     }
 
+    fn send_with_ack(&self, conn_id: i32, msg_id: Option<String>, reply_to: Option<String>, msg: String,
+                     cb: Box<Fn(Result<String, IndyError>)>) {
+        let result = self.agent_service
+            .send_with_ack(conn_id, msg_id.as_ref().map(String::as_str), reply_to.as_ref().map(String::as_str), msg.as_str())
+            .and_then(|(cmd_id, msg_id)| {
+                match self.send_callbacks.try_borrow_mut() {
+                    Ok(cbs) => Ok((cbs, cmd_id, msg_id)),
+                    Err(err) => Err(CommonError::InvalidState(err.description().to_string())),
+                }
+            });
+        match result {
+            Ok((mut cbs, cmd_id, msg_id)) => {
+                /* SendAck is received after delivery acknowledgement, so report id of delivered message */
+                cbs.insert(cmd_id, Box::new(move |res: Result<(), IndyError>| cb(res.map(|()| msg_id.clone()))));
+            }
+            Err(err) => cb(Err(From::from(err)).map_err(map_err_err!())),
+        }
+    }
+
+    fn send_request(&self, conn_id: i32, msg: String, timeout_ms: u32, cb: Box<Fn(Result<String, IndyError>)>) {
+        let result = self.agent_service
+            .send_request(conn_id, msg.as_str(), timeout_ms)
+            .and_then(|cmd_id| {
+                match self.request_callbacks.try_borrow_mut() {
+                    Ok(cbs) => Ok((cbs, cmd_id)),
+                    Err(err) => Err(CommonError::InvalidState(err.description().to_string())),
+                }
+            });
+        match result {
+            Ok((mut cbs, cmd_id)) => { cbs.insert(cmd_id, cb); }
+            Err(err) => cb(Err(From::from(err)).map_err(map_err_err!())),
+        }
+    }
+
+    fn on_send_request_ack(&self, cmd_id: i32, res: Result<String, AgentError>) {
+        match self.request_callbacks.borrow_mut().remove(&cmd_id) {
+            Some(cb) => cb(res.map_err(From::from)),
+            None => error!("Can't handle SendRequestAck cmd - callback not found for {}", cmd_id),
+        };
+    }
+
     fn close_connection_or_listener(&self, handle: i32, cb: Box<Fn(Result<(), IndyError>)>, close_listener: bool) {
+        self.message_id_callbacks.borrow_mut().remove(&handle);
+        self.binary_message_callbacks.borrow_mut().remove(&handle);
         let result = self.agent_service
             .close_connection_or_listener(handle, close_listener)
             .and_then(|cmd_id| {
@@ -614,6 +950,31 @@ impl AgentCommandExecutor {
             None => error!("Can't handle CloseListenerAck cmd - not found callback for {}", cmd_id)
         };
     }
+
+    fn get_connections_info(&self, conn_id: Option<i32>, cb: Box<Fn(Result<String, IndyError>)>) {
+        let result = match conn_id {
+            Some(conn_id) => self.agent_service.get_connection_info(conn_id),
+            None => self.agent_service.list_connections(),
+        };
+        let result = result
+            .and_then(|cmd_id| {
+                match self.info_callbacks.try_borrow_mut() {
+                    Ok(cbs) => Ok((cbs, cmd_id)),
+                    Err(err) => Err(CommonError::InvalidState(err.description().to_string())),
+                }
+            });
+        match result {
+            Ok((mut cbs, cmd_id)) => { cbs.insert(cmd_id, cb); }
+            Err(err) => cb(Err(From::from(err)).map_err(map_err_err!())),
+        }
+    }
+
+    fn on_connections_info_ack(&self, cmd_id: i32, res: Result<String, CommonError>) {
+        match self.info_callbacks.borrow_mut().remove(&cmd_id) {
+            Some(cb) => cb(res.map_err(From::from)),
+            None => error!("Can't handle ConnectionsInfoAck cmd - callback not found for {}", cmd_id),
+        };
+    }
 }
 
 fn _base58_to_z85(str: &str) -> Result<String, CommonError> {
@@ -625,6 +986,12 @@ fn _base58_to_z85(str: &str) -> Result<String, CommonError> {
         })
 }
 
+/// Callback set for connection takes precedence over one set for its listener.
+fn _find_handle_cb<T>(cbs: &HashMap<i32, T>, connection_id: i32, listener_handle: Option<i32>) -> Option<&T> {
+    cbs.get(&connection_id)
+        .or_else(|| listener_handle.and_then(|listener_handle| cbs.get(&listener_handle)))
+}
+
 #[derive(Debug, Clone)]
 pub struct MyConnectInfo {
     sender_did: String,
@@ -639,16 +1006,3 @@ pub struct ConnectInfo {
     server_key: String,
     endpoint: String,
 }
-
-#[derive(Deserialize)]
-struct Endpoint {
-    verkey: String,
-    ha: String,
-}
-
-#[derive(Deserialize)]
-struct AttribData {
-    endpoint: Endpoint,
-}
-
-impl<'a> JsonDecodable<'a> for AttribData {}
@@ -12,16 +12,25 @@ use services::anoncreds::types::{
     ClaimDefinitionPrivate,
     ClaimJson,
     ClaimRequestJson,
+    RevocationPublicKey,
     RevocationRegistry,
     RevocationRegistryPrivate,
     Schema
 };
 use services::anoncreds::helpers::get_composite_id;
 use std::rc::Rc;
-use std::collections::HashMap;
+use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
 use utils::json::{JsonDecodable, JsonEncodable};
+use utils::sequence::SequenceUtils;
+use utils::workers::{catch_panic, WorkerPool};
+use utils::crypto::bn::BigNumber;
+use errors::wallet::WalletError;
 use std::cell::RefCell;
 
+use commands::{Command, CommandExecutor};
+use commands::anoncreds::AnoncredsCommand;
+
 pub enum IssuerCommand {
     CreateAndStoreClaimDefinition(
         i32, // wallet handle
@@ -30,18 +39,48 @@ pub enum IssuerCommand {
         Option<String>, // signature type
         bool,
         Box<Fn(Result<String, IndyError>) + Send>),
+    CreateAndStoreClaimDefinitionAck(
+        i32, // wallet handle
+        String, // issuer did
+        i32, // schema seq no
+        i32, // callback id
+        Option<(String, String)>, // safe primes taken from pool
+        Result<(ClaimDefinition, ClaimDefinitionPrivate), IndyError>),
     CreateAndStoreRevocationRegistry(
         i32, // wallet handle
         String, // issuer did
         i32, // schema seq no
         i32, // max claim num
         Box<Fn(Result<String, IndyError>) + Send>),
+    CreateAndStoreRevocationRegistryAck(
+        i32, // wallet handle
+        String, // issuer did
+        i32, // schema seq no
+        i32, // callback id
+        Result<(RevocationRegistry, RevocationRegistryPrivate), IndyError>),
+    GenerateSafePrimes(
+        i32, // wallet handle
+        i32, // primes count
+        Box<Fn(Result<(), IndyError>) + Send>),
+    GenerateSafePrimesAck(
+        i32, // wallet handle
+        i32, // callback id
+        Result<BigNumber, IndyError>),
+    ImportSafePrimes(
+        i32, // wallet handle
+        String, // safe primes json
+        Box<Fn(Result<(), IndyError>) + Send>),
     CreateClaim(
         i32, // wallet handle
         String, // claim req json
         String, // claim json
         Option<i32>, // user revoc index
         Box<Fn(Result<(String, String), IndyError>) + Send>),
+    CreateClaimAck(
+        i32, // wallet handle
+        Option<String>, // revocation registry id
+        i32, // callback id
+        Result<(Option<RevocationRegistry>, String), IndyError>),
     RevokeClaim(
         i32, // wallet handle
         String, // issuer did
@@ -51,19 +90,35 @@ pub enum IssuerCommand {
 }
 
 pub struct IssuerCommandExecutor {
-    pub anoncreds_service: Rc<AnoncredsService>,
+    pub anoncreds_service: Arc<AnoncredsService>,
     pub pool_service: Rc<PoolService>,
-    pub wallet_service: Rc<WalletService>
+    pub wallet_service: Rc<WalletService>,
+    worker_pool: Rc<WorkerPool>,
+    pending_callbacks: RefCell<HashMap<i32, Box<Fn(Result<String, IndyError>) + Send>>>,
+    pending_safe_primes_callbacks: RefCell<HashMap<i32, (i32, Box<Fn(Result<(), IndyError>) + Send>)>>,
+    // Safe primes used by claim definitions in progress. They stay in pool until claim definition is stored.
+    reserved_safe_primes: RefCell<HashSet<String>>,
+    pending_claim_callbacks: RefCell<HashMap<i32, Box<Fn(Result<(String, String), IndyError>) + Send>>>,
+    // Each claim updates revocation registry, so claims of one registry are created one by one.
+    // Commands for registry with claim in progress wait here until the registry is stored.
+    busy_revocation_registries: RefCell<HashMap<String, Vec<IssuerCommand>>>
 }
 
 impl IssuerCommandExecutor {
-    pub fn new(anoncreds_service: Rc<AnoncredsService>,
+    pub fn new(anoncreds_service: Arc<AnoncredsService>,
                pool_service: Rc<PoolService>,
-               wallet_service: Rc<WalletService>) -> IssuerCommandExecutor {
+               wallet_service: Rc<WalletService>,
+               worker_pool: Rc<WorkerPool>) -> IssuerCommandExecutor {
         IssuerCommandExecutor {
             anoncreds_service: anoncreds_service,
             pool_service: pool_service,
             wallet_service: wallet_service,
+            worker_pool: worker_pool,
+            pending_callbacks: RefCell::new(HashMap::new()),
+            pending_safe_primes_callbacks: RefCell::new(HashMap::new()),
+            reserved_safe_primes: RefCell::new(HashSet::new()),
+            pending_claim_callbacks: RefCell::new(HashMap::new()),
+            busy_revocation_registries: RefCell::new(HashMap::new()),
         }
     }
 
@@ -74,19 +129,43 @@ impl IssuerCommandExecutor {
                 self.create_and_store_claim_definition(wallet_handle, &issuer_did, &schema_json,
                                                        signature_type.as_ref().map(String::as_str), create_non_revoc, cb);
             }
+            IssuerCommand::CreateAndStoreClaimDefinitionAck(wallet_handle, issuer_did, schema_seq_no, cb_id, safe_primes, result) => {
+                info!(target: "issuer_command_executor", "CreateAndStoreClaimDefinitionAck command received");
+                self.create_and_store_claim_definition_ack(wallet_handle, &issuer_did, schema_seq_no, cb_id, safe_primes, result);
+            }
             IssuerCommand::CreateAndStoreRevocationRegistry(wallet_handle, issuer_did, schema_seq_no, max_claim_num, cb) => {
                 info!(target: "issuer_command_executor", "CreateAndStoreRevocationRegistryRegistry command received");
                 self.create_and_store_revocation_registry(wallet_handle, &issuer_did, schema_seq_no, max_claim_num, cb);
             }
+            IssuerCommand::CreateAndStoreRevocationRegistryAck(wallet_handle, issuer_did, schema_seq_no, cb_id, result) => {
+                info!(target: "issuer_command_executor", "CreateAndStoreRevocationRegistryAck command received");
+                self.create_and_store_revocation_registry_ack(wallet_handle, &issuer_did, schema_seq_no, cb_id, result);
+            }
+            IssuerCommand::GenerateSafePrimes(wallet_handle, count, cb) => {
+                info!(target: "issuer_command_executor", "GenerateSafePrimes command received");
+                self.generate_safe_primes(wallet_handle, count, cb);
+            }
+            IssuerCommand::GenerateSafePrimesAck(wallet_handle, cb_id, result) => {
+                info!(target: "issuer_command_executor", "GenerateSafePrimesAck command received");
+                self.generate_safe_primes_ack(wallet_handle, cb_id, result);
+            }
+            IssuerCommand::ImportSafePrimes(wallet_handle, safe_primes_json, cb) => {
+                info!(target: "issuer_command_executor", "ImportSafePrimes command received");
+                self.import_safe_primes(wallet_handle, &safe_primes_json, cb);
+            }
             IssuerCommand::CreateClaim(wallet_handle, claim_req_json, claim_json, user_revoc_index, cb) => {
                 info!(target: "issuer_command_executor", "CreateClaim command received");
-                self.create_claim(wallet_handle, &claim_req_json, &claim_json,
+                self.create_claim(wallet_handle, claim_req_json, claim_json,
                                   user_revoc_index, cb);
             }
+            IssuerCommand::CreateClaimAck(wallet_handle, revoc_reg_id, cb_id, result) => {
+                info!(target: "issuer_command_executor", "CreateClaimAck command received");
+                self.create_claim_ack(wallet_handle, revoc_reg_id, cb_id, result);
+            }
             IssuerCommand::RevokeClaim(wallet_handle, issuer_did, schema_seq_no,
                                        user_revoc_index, cb) => {
                 info!(target: "issuer_command_executor", "RevokeClaim command received");
-                self.revoke_claim(wallet_handle, &issuer_did, schema_seq_no, user_revoc_index, cb);
+                self.revoke_claim(wallet_handle, issuer_did, schema_seq_no, user_revoc_index, cb);
             }
         };
     }
@@ -98,34 +177,108 @@ impl IssuerCommandExecutor {
                                          signature_type: Option<&str>,
                                          create_non_revoc: bool,
                                          cb: Box<Fn(Result<String, IndyError>) + Send>) {
-        let result = self._create_and_store_claim_definition(wallet_handle, issuer_did, schema_json,
-                                                             signature_type, create_non_revoc);
-        cb(result)
-    }
-
-    fn _create_and_store_claim_definition(&self,
-                                          wallet_handle: i32,
-                                          issuer_did: &str,
-                                          schema_json: &str,
-                                          signature_type: Option<&str>,
-                                          create_non_revoc: bool) -> Result<String, IndyError> {
-        let schema = Schema::from_json(schema_json)
+        let schema = match Schema::from_json(schema_json)
             .map_err(map_err_trace!())
-            .map_err(|err| CommonError::InvalidStructure(format!("Invalid schema json: {}", err.to_string())))?;
+            .map_err(|err| CommonError::InvalidStructure(format!("Invalid schema json: {}", err.to_string()))) {
+            Ok(schema) => schema,
+            Err(err) => return cb(Err(IndyError::CommonError(err)))
+        };
 
-        let (claim_definition, claim_definition_private) =
-            self.anoncreds_service.issuer.generate_claim_definition(issuer_did, schema.clone(), signature_type, create_non_revoc)?;
+        let safe_primes = match self._take_safe_primes(wallet_handle) {
+            Ok(safe_primes) => safe_primes,
+            Err(err) => return cb(Err(err))
+        };
 
-        let claim_definition_json = ClaimDefinition::to_json(&claim_definition)
+        let cb_id = match self._add_pending_callback(cb) {
+            Some(cb_id) => cb_id,
+            None => return self._release_safe_primes(safe_primes.as_ref())
+        };
+
+        let primes = match safe_primes {
+            Some((ref p, ref q)) => match (BigNumber::from_dec(p), BigNumber::from_dec(q)) {
+                (Ok(p), Ok(q)) => Some((p, q)),
+                (Err(err), _) | (_, Err(err)) =>
+                    return self.create_and_store_claim_definition_ack(wallet_handle, issuer_did, schema.seq_no, cb_id,
+                                                                      safe_primes.clone(), Err(IndyError::CommonError(err)))
+            },
+            None => None
+        };
+
+        let anoncreds_service = self.anoncreds_service.clone();
+        let schema_seq_no = schema.seq_no;
+        let ack_issuer_did = issuer_did.to_string();
+        let ack_safe_primes = safe_primes.clone();
+        let signature_type = signature_type.map(String::from);
+
+        // Safe primes generation takes seconds if pool is empty, so keys are generated out of CommandExecutor thread
+        let res = self.worker_pool.execute(move || {
+            let result = catch_panic(|| anoncreds_service.issuer
+                .generate_claim_definition(&ack_issuer_did, schema, signature_type.as_ref().map(String::as_str), create_non_revoc, primes)
+                .map_err(IndyError::AnoncredsError))
+                .map_err(IndyError::CommonError)
+                .and_then(|result| result);
+
+            let res = CommandExecutor::instance()
+                .send(Command::Anoncreds(AnoncredsCommand::Issuer(IssuerCommand::CreateAndStoreClaimDefinitionAck(
+                    wallet_handle,
+                    ack_issuer_did,
+                    schema_seq_no,
+                    cb_id,
+                    ack_safe_primes,
+                    result
+                ))));
+
+            if let Err(err) = res {
+                error!("Can't send IssuerCommand::CreateAndStoreClaimDefinitionAck: {:?}", err);
+            }
+        });
+
+        if let Err(err) = res {
+            self.create_and_store_claim_definition_ack(wallet_handle, issuer_did, schema_seq_no, cb_id,
+                                                       safe_primes, Err(IndyError::CommonError(err)));
+        }
+    }
+
+    fn create_and_store_claim_definition_ack(&self,
+                                             wallet_handle: i32,
+                                             issuer_did: &str,
+                                             schema_seq_no: i32,
+                                             cb_id: i32,
+                                             safe_primes: Option<(String, String)>,
+                                             result: Result<(ClaimDefinition, ClaimDefinitionPrivate), IndyError>) {
+        let result = result
+            .and_then(|(claim_definition, claim_definition_private)|
+                self._store_claim_definition(wallet_handle, issuer_did, schema_seq_no, &claim_definition, &claim_definition_private))
+            .and_then(|claim_definition_json| match safe_primes {
+                // Used primes must never be taken again, so they leave the pool only with stored claim definition
+                Some((ref p, ref q)) => self._remove_safe_primes(wallet_handle, &[p.as_str(), q.as_str()]).map(|_| claim_definition_json),
+                None => Ok(claim_definition_json)
+            });
+
+        self._release_safe_primes(safe_primes.as_ref());
+
+        match self._remove_pending_callback(cb_id) {
+            Some(cb) => cb(result),
+            None => error!("Can't process IssuerCommand::CreateAndStoreClaimDefinitionAck for handle {} - appropriate callback not found!", cb_id)
+        }
+    }
+
+    fn _store_claim_definition(&self,
+                               wallet_handle: i32,
+                               issuer_did: &str,
+                               schema_seq_no: i32,
+                               claim_definition: &ClaimDefinition,
+                               claim_definition_private: &ClaimDefinitionPrivate) -> Result<String, IndyError> {
+        let claim_definition_json = ClaimDefinition::to_json(claim_definition)
             .map_err(map_err_trace!())
             .map_err(|err| CommonError::InvalidState(format!("Invalid claim definition json: {}", err.to_string())))?;
 
-        let claim_definition_private_json = ClaimDefinitionPrivate::to_json(&claim_definition_private)
+        let claim_definition_private_json = ClaimDefinitionPrivate::to_json(claim_definition_private)
             .map_err(map_err_trace!())
             .map_err(|err| CommonError::InvalidState(format!("Invalid claim definition private json: {}", err.to_string())))?;
 
-        self.wallet_service.set(wallet_handle, &format!("claim_definition::{}", &get_composite_id(issuer_did, schema.seq_no)), &claim_definition_json)?;
-        self.wallet_service.set(wallet_handle, &format!("claim_definition_private::{}", &get_composite_id(issuer_did, schema.seq_no)), &claim_definition_private_json)?;
+        self.wallet_service.set(wallet_handle, &format!("claim_definition::{}", &get_composite_id(issuer_did, schema_seq_no)), &claim_definition_json)?;
+        self.wallet_service.set(wallet_handle, &format!("claim_definition_private::{}", &get_composite_id(issuer_did, schema_seq_no)), &claim_definition_private_json)?;
 
         Ok(claim_definition_json)
     }
@@ -136,31 +289,82 @@ impl IssuerCommandExecutor {
                                             schema_seq_no: i32,
                                             max_claim_num: i32,
                                             cb: Box<Fn(Result<String, IndyError>) + Send>) {
-        let result = self._create_and_store_revocation_registry(wallet_handle, issuer_did, schema_seq_no, max_claim_num);
-        cb(result)
+        let pk_r = match self._get_revocation_public_key(wallet_handle, issuer_did, schema_seq_no) {
+            Ok(pk_r) => pk_r,
+            Err(err) => return cb(Err(err))
+        };
+
+        let cb_id = match self._add_pending_callback(cb) {
+            Some(cb_id) => cb_id,
+            None => return
+        };
+
+        let anoncreds_service = self.anoncreds_service.clone();
+        let issuer_did = issuer_did.to_string();
+
+        let res = self.worker_pool.execute(move || {
+            let result = catch_panic(|| anoncreds_service.issuer
+                .issue_accumulator(&pk_r, max_claim_num, &issuer_did, schema_seq_no)
+                .map_err(IndyError::AnoncredsError))
+                .map_err(IndyError::CommonError)
+                .and_then(|result| result);
+
+            let res = CommandExecutor::instance()
+                .send(Command::Anoncreds(AnoncredsCommand::Issuer(IssuerCommand::CreateAndStoreRevocationRegistryAck(
+                    wallet_handle,
+                    issuer_did,
+                    schema_seq_no,
+                    cb_id,
+                    result
+                ))));
+
+            if let Err(err) = res {
+                error!("Can't send IssuerCommand::CreateAndStoreRevocationRegistryAck: {:?}", err);
+            }
+        });
+
+        if let Err(err) = res {
+            self._fail_pending_callback(cb_id, err);
+        }
     }
 
-    fn _create_and_store_revocation_registry(&self,
-                                             wallet_handle: i32,
-                                             issuer_did: &str,
-                                             schema_seq_no: i32,
-                                             max_claim_num: i32) -> Result<String, IndyError> {
+    fn _get_revocation_public_key(&self,
+                                  wallet_handle: i32,
+                                  issuer_did: &str,
+                                  schema_seq_no: i32) -> Result<RevocationPublicKey, IndyError> {
         let claim_def_json = self.wallet_service.get(wallet_handle, &format!("claim_definition::{}", &get_composite_id(issuer_did, schema_seq_no)))?;
         let claim_def = ClaimDefinition::from_json(&claim_def_json)
             .map_err(map_err_trace!())
             .map_err(|err| CommonError::InvalidState(format!("Invalid claim definition json: {}", err.to_string())))?;
 
-        let pk_r = claim_def.data.public_key_revocation
-            .ok_or(IndyError::AnoncredsError(AnoncredsError::NotIssuedError("Revocation Public Key for this claim definition".to_string())))?;
+        claim_def.data.public_key_revocation
+            .ok_or(IndyError::AnoncredsError(AnoncredsError::NotIssuedError("Revocation Public Key for this claim definition".to_string())))
+    }
 
-        let (revocation_registry, revocation_registry_private) =
-            self.anoncreds_service.issuer.issue_accumulator(&pk_r, max_claim_num, issuer_did, schema_seq_no)?;
+    fn create_and_store_revocation_registry_ack(&self,
+                                                wallet_handle: i32,
+                                                issuer_did: &str,
+                                                schema_seq_no: i32,
+                                                cb_id: i32,
+                                                result: Result<(RevocationRegistry, RevocationRegistryPrivate), IndyError>) {
+        match self._remove_pending_callback(cb_id) {
+            Some(cb) => cb(result.and_then(|(revocation_registry, revocation_registry_private)|
+                self._store_revocation_registry(wallet_handle, issuer_did, schema_seq_no, &revocation_registry, &revocation_registry_private))),
+            None => error!("Can't process IssuerCommand::CreateAndStoreRevocationRegistryAck for handle {} - appropriate callback not found!", cb_id)
+        }
+    }
 
-        let revocation_registry_json = RevocationRegistry::to_json(&revocation_registry)
+    fn _store_revocation_registry(&self,
+                                  wallet_handle: i32,
+                                  issuer_did: &str,
+                                  schema_seq_no: i32,
+                                  revocation_registry: &RevocationRegistry,
+                                  revocation_registry_private: &RevocationRegistryPrivate) -> Result<String, IndyError> {
+        let revocation_registry_json = RevocationRegistry::to_json(revocation_registry)
             .map_err(map_err_trace!())
             .map_err(|err| CommonError::InvalidState(format!("Invalid revocation registry: {}", err.to_string())))?;
 
-        let revocation_registry_private_json = RevocationRegistryPrivate::to_json(&revocation_registry_private)
+        let revocation_registry_private_json = RevocationRegistryPrivate::to_json(revocation_registry_private)
             .map_err(map_err_trace!())
             .map_err(|err| CommonError::InvalidState(format!("Invalid revocation registry private: {}", err.to_string())))?;
 
@@ -176,21 +380,287 @@ impl IssuerCommandExecutor {
         Ok(revocation_registry_json)
     }
 
+    fn generate_safe_primes(&self,
+                            wallet_handle: i32,
+                            count: i32,
+                            cb: Box<Fn(Result<(), IndyError>) + Send>) {
+        if count <= 0 {
+            return cb(Err(IndyError::CommonError(CommonError::InvalidParam3(format!("Safe primes count must be positive: {}", count)))));
+        }
+
+        let cb_id = SequenceUtils::get_next_id();
+
+        match self.pending_safe_primes_callbacks.try_borrow_mut() {
+            Ok(mut cbs) => cbs.insert(cb_id, (count, cb)),
+            Err(err) => return cb(Err(IndyError::CommonError(CommonError::from(err))))
+        };
+
+        for _ in 0..count {
+            let anoncreds_service = self.anoncreds_service.clone();
+
+            let res = self.worker_pool.execute(move || {
+                let result = catch_panic(|| anoncreds_service.issuer.generate_safe_prime())
+                    .and_then(|result| result)
+                    .map_err(IndyError::CommonError);
+
+                let res = CommandExecutor::instance()
+                    .send(Command::Anoncreds(AnoncredsCommand::Issuer(IssuerCommand::GenerateSafePrimesAck(
+                        wallet_handle,
+                        cb_id,
+                        result
+                    ))));
+
+                if let Err(err) = res {
+                    error!("Can't send IssuerCommand::GenerateSafePrimesAck: {:?}", err);
+                }
+            });
+
+            if let Err(err) = res {
+                // Already scheduled jobs are acked to removed callback and ignored
+                return self.generate_safe_primes_ack(wallet_handle, cb_id, Err(IndyError::CommonError(err)));
+            }
+        }
+    }
+
+    fn generate_safe_primes_ack(&self,
+                                wallet_handle: i32,
+                                cb_id: i32,
+                                result: Result<BigNumber, IndyError>) {
+        let result = result.and_then(|safe_prime| self._add_safe_primes(wallet_handle, vec![safe_prime]));
+
+        let mut cbs = match self.pending_safe_primes_callbacks.try_borrow_mut() {
+            Ok(cbs) => cbs,
+            Err(err) => return error!("{:?}", err)
+        };
+
+        let finished = match (cbs.get_mut(&cb_id), result) {
+            (Some(&mut (ref mut remaining, _)), Ok(())) => {
+                *remaining -= 1;
+                if *remaining > 0 { None } else { Some(Ok(())) }
+            }
+            (Some(_), Err(err)) => Some(Err(err)),
+            // Callback was already called with error of previous job
+            (None, _) => None
+        };
+
+        if let Some(result) = finished {
+            if let Some((_, cb)) = cbs.remove(&cb_id) {
+                cb(result)
+            }
+        }
+    }
+
+    fn import_safe_primes(&self,
+                          wallet_handle: i32,
+                          safe_primes_json: &str,
+                          cb: Box<Fn(Result<(), IndyError>) + Send>) {
+        let result = self._import_safe_primes(wallet_handle, safe_primes_json);
+        cb(result)
+    }
+
+    fn _import_safe_primes(&self,
+                           wallet_handle: i32,
+                           safe_primes_json: &str) -> Result<(), IndyError> {
+        let safe_primes: Vec<String> = serde_json::from_str(safe_primes_json)
+            .map_err(map_err_trace!())
+            .map_err(|err| CommonError::InvalidStructure(format!("Invalid safe_primes_json: {}", err.to_string())))?;
+
+        let mut pool = self._get_safe_primes(wallet_handle)?;
+
+        for safe_prime in safe_primes.iter() {
+            let prime = BigNumber::from_dec(safe_prime)
+                .map_err(|err| CommonError::InvalidStructure(format!("Invalid safe prime {}: {}", safe_prime, err.to_string())))?;
+
+            if !self.anoncreds_service.issuer.check_safe_prime(&prime)? {
+                return Err(IndyError::CommonError(CommonError::InvalidStructure(format!("Not a safe prime: {}", safe_prime))));
+            }
+
+            // Canonical form is compared, so the same prime with leading zeros is a duplicate too
+            let prime = prime.to_dec()?;
+
+            if pool.contains(&prime) {
+                return Err(IndyError::CommonError(CommonError::InvalidStructure(format!("Safe prime is already in pool: {}", safe_prime))));
+            }
+
+            pool.push(prime);
+        }
+
+        self._set_safe_primes(wallet_handle, &pool)
+    }
+
+    fn _get_safe_primes(&self, wallet_handle: i32) -> Result<Vec<String>, IndyError> {
+        match self.wallet_service.get(wallet_handle, "safe_primes") {
+            Ok(safe_primes_json) => Ok(serde_json::from_str(&safe_primes_json)
+                .map_err(map_err_trace!())
+                .map_err(|err| CommonError::InvalidState(format!("Invalid safe primes pool: {}", err.to_string())))?),
+            Err(WalletError::NotFound(_)) => Ok(Vec::new()),
+            Err(err) => Err(IndyError::WalletError(err))
+        }
+    }
+
+    fn _set_safe_primes(&self, wallet_handle: i32, safe_primes: &Vec<String>) -> Result<(), IndyError> {
+        let safe_primes_json = serde_json::to_string(safe_primes)
+            .map_err(map_err_trace!())
+            .map_err(|err| CommonError::InvalidState(format!("Invalid safe primes pool: {}", err.to_string())))?;
+
+        self.wallet_service.set(wallet_handle, "safe_primes", &safe_primes_json)?;
+        Ok(())
+    }
+
+    fn _add_safe_primes(&self, wallet_handle: i32, primes: Vec<BigNumber>) -> Result<(), IndyError> {
+        let mut safe_primes = self._get_safe_primes(wallet_handle)?;
+
+        for prime in primes {
+            safe_primes.push(prime.to_dec()?);
+        }
+
+        self._set_safe_primes(wallet_handle, &safe_primes)
+    }
+
+    // Reserves two different primes not used by other claim definition in progress
+    fn _take_safe_primes(&self, wallet_handle: i32) -> Result<Option<(String, String)>, IndyError> {
+        let safe_primes = self._get_safe_primes(wallet_handle)?;
+        let mut reserved = self.reserved_safe_primes.try_borrow_mut().map_err(CommonError::from)?;
+
+        let taken = {
+            let mut available = safe_primes.iter().rev().filter(|prime| !reserved.contains(*prime));
+
+            match available.next() {
+                Some(p) => available.find(|q| *q != p).map(|q| (p.clone(), q.clone())),
+                None => None
+            }
+        };
+
+        if let Some((ref p, ref q)) = taken {
+            reserved.insert(p.clone());
+            reserved.insert(q.clone());
+        }
+
+        Ok(taken)
+    }
+
+    fn _release_safe_primes(&self, safe_primes: Option<&(String, String)>) {
+        if let Some(&(ref p, ref q)) = safe_primes {
+            match self.reserved_safe_primes.try_borrow_mut() {
+                Ok(mut reserved) => {
+                    reserved.remove(p);
+                    reserved.remove(q);
+                }
+                Err(err) => error!("{:?}", err)
+            }
+        }
+    }
+
+    fn _remove_safe_primes(&self, wallet_handle: i32, primes: &[&str]) -> Result<(), IndyError> {
+        let mut safe_primes = self._get_safe_primes(wallet_handle)?;
+        safe_primes.retain(|prime| !primes.contains(&prime.as_str()));
+        self._set_safe_primes(wallet_handle, &safe_primes)
+    }
+
+    // Callback is called with error if it can't be stored
+    fn _add_pending_callback(&self, cb: Box<Fn(Result<String, IndyError>) + Send>) -> Option<i32> {
+        let cb_id = SequenceUtils::get_next_id();
+
+        match self.pending_callbacks.try_borrow_mut() {
+            Ok(mut cbs) => {
+                cbs.insert(cb_id, cb);
+                Some(cb_id)
+            }
+            Err(err) => {
+                cb(Err(IndyError::CommonError(CommonError::from(err))));
+                None
+            }
+        }
+    }
+
+    fn _fail_pending_callback(&self, cb_id: i32, err: CommonError) {
+        match self._remove_pending_callback(cb_id) {
+            Some(cb) => cb(Err(IndyError::CommonError(err))),
+            None => error!("Can't fail callback {} - callback not found: {:?}", cb_id, err)
+        }
+    }
+
+    fn _remove_pending_callback(&self, cb_id: i32) -> Option<Box<Fn(Result<String, IndyError>) + Send>> {
+        match self.pending_callbacks.try_borrow_mut() {
+            Ok(mut cbs) => cbs.remove(&cb_id),
+            Err(err) => {
+                error!("{:?}", err);
+                None
+            }
+        }
+    }
+
     fn create_claim(&self,
                     wallet_handle: i32,
-                    claim_req_json: &str,
-                    claim_json: &str,
+                    claim_req_json: String,
+                    claim_json: String,
                     user_revoc_index: Option<i32>,
                     cb: Box<Fn(Result<(String, String), IndyError>) + Send>) {
-        let result = self._create_claim(wallet_handle, claim_req_json, claim_json, user_revoc_index);
-        cb(result)
+        let (claim_req, claim_def, claim_def_private) = match self._get_claim_definition(wallet_handle, &claim_req_json) {
+            Ok(res) => res,
+            Err(err) => return cb(Err(err))
+        };
+
+        let revoc_reg_id = claim_def.data.public_key_revocation.as_ref()
+            .map(|_| get_composite_id(&claim_req.issuer_did, claim_req.schema_seq_no));
+
+        if let Some(ref revoc_reg_id) = revoc_reg_id {
+            match self.busy_revocation_registries.try_borrow_mut() {
+                Ok(mut regs) => {
+                    if let Some(queue) = regs.get_mut(revoc_reg_id) {
+                        return queue.push(IssuerCommand::CreateClaim(wallet_handle, claim_req_json, claim_json, user_revoc_index, cb));
+                    }
+
+                    regs.insert(revoc_reg_id.clone(), Vec::new());
+                }
+                Err(err) => return cb(Err(IndyError::CommonError(CommonError::from(err))))
+            }
+        }
+
+        let cb_id = SequenceUtils::get_next_id();
+
+        match self.pending_claim_callbacks.try_borrow_mut() {
+            Ok(mut cbs) => cbs.insert(cb_id, cb),
+            Err(err) => {
+                cb(Err(IndyError::CommonError(CommonError::from(err))));
+                return self._release_revocation_registry(revoc_reg_id);
+            }
+        };
+
+        let claim_data = match self._prepare_claim_data(wallet_handle, claim_req, claim_def, claim_def_private, &claim_json) {
+            Ok(claim_data) => claim_data,
+            Err(err) => return self.create_claim_ack(wallet_handle, revoc_reg_id, cb_id, Err(err))
+        };
+
+        let anoncreds_service = self.anoncreds_service.clone();
+        let ack_revoc_reg_id = revoc_reg_id.clone();
+
+        let res = self.worker_pool.execute(move || {
+            let result = catch_panic(|| IssuerCommandExecutor::_create_claim(&anoncreds_service, claim_data, user_revoc_index))
+                .map_err(IndyError::CommonError)
+                .and_then(|result| result);
+
+            let res = CommandExecutor::instance()
+                .send(Command::Anoncreds(AnoncredsCommand::Issuer(IssuerCommand::CreateClaimAck(
+                    wallet_handle,
+                    ack_revoc_reg_id,
+                    cb_id,
+                    result
+                ))));
+
+            if let Err(err) = res {
+                error!("Can't send IssuerCommand::CreateClaimAck: {:?}", err);
+            }
+        });
+
+        if let Err(err) = res {
+            self.create_claim_ack(wallet_handle, revoc_reg_id, cb_id, Err(IndyError::CommonError(err)));
+        }
     }
 
-    fn _create_claim(&self,
-                     wallet_handle: i32,
-                     claim_req_json: &str,
-                     claim_json: &str,
-                     user_revoc_index: Option<i32>) -> Result<(String, String), IndyError> {
+    fn _get_claim_definition(&self,
+                             wallet_handle: i32,
+                             claim_req_json: &str) -> Result<(ClaimRequestJson, ClaimDefinition, ClaimDefinitionPrivate), IndyError> {
         let claim_req_json: ClaimRequestJson = ClaimRequestJson::from_json(claim_req_json)
             .map_err(map_err_trace!())
             .map_err(|err| CommonError::InvalidStructure(format!("Invalid claim_req_json: {}", err.to_string())))?;
@@ -206,66 +676,144 @@ impl IssuerCommandExecutor {
             .map_err(map_err_trace!())
             .map_err(|err| CommonError::InvalidState(format!("Invalid claim_def_private_json: {}", err.to_string())))?;
 
+        Ok((claim_req_json, claim_def, claim_def_private))
+    }
+
+    fn _prepare_claim_data(&self,
+                           wallet_handle: i32,
+                           claim_req_json: ClaimRequestJson,
+                           claim_def: ClaimDefinition,
+                           claim_def_private: ClaimDefinitionPrivate,
+                           claim_json: &str) -> Result<ClaimData, IndyError> {
         if claim_def.data.public_key_revocation.is_some() && claim_req_json.blinded_ms.ur.is_none() {
             return Err(IndyError::AnoncredsError(AnoncredsError::NotIssuedError(
                 format!("Claim_request.ur are required for this claim"))));
         }
 
-        let (revocation_registry, revocation_registry_private,
-            mut revocation_registry_json) = match claim_def.data.public_key_revocation {
+        let (revocation_registry, revocation_registry_private) = match claim_def.data.public_key_revocation {
             Some(_) => {
                 let revocation_registry_json = self.wallet_service.get(wallet_handle, &format!("revocation_registry::{}", &get_composite_id(&claim_req_json.issuer_did.clone(), claim_req_json.schema_seq_no)))?;
                 let revocation_registry_private_json = self.wallet_service.get(wallet_handle, &format!("revocation_registry_private::{}", &get_composite_id(&claim_req_json.issuer_did.clone(), claim_req_json.schema_seq_no)))?;
 
-                let revocation_registry = Some(RefCell::new(RevocationRegistry::from_json(&revocation_registry_json)
+                let revocation_registry = Some(RevocationRegistry::from_json(&revocation_registry_json)
                     .map_err(map_err_trace!())
-                    .map_err(|err| CommonError::InvalidState(format!("Invalid revocation_registry_json: {}", err.to_string())))?));
+                    .map_err(|err| CommonError::InvalidState(format!("Invalid revocation_registry_json: {}", err.to_string())))?);
 
                 let revocation_registry_private = Some(RevocationRegistryPrivate::from_json(&revocation_registry_private_json)
                     .map_err(map_err_trace!())
                     .map_err(|err| CommonError::InvalidState(format!("Invalid revocation_registry_private_json: {}", err.to_string())))?);
 
-                (revocation_registry, revocation_registry_private, revocation_registry_json)
+                (revocation_registry, revocation_registry_private)
             }
-            _ => (None, None, String::new())
+            _ => (None, None)
         };
 
         let attributes: HashMap<String, Vec<String>> = serde_json::from_str(claim_json)
             .map_err(map_err_trace!())
             .map_err(|err| CommonError::InvalidStructure(format!("Invalid claim_json: {}", err.to_string())))?;
 
-        let claims = self.anoncreds_service.issuer.create_claim(&claim_def,
-                                                                &claim_def_private,
-                                                                &revocation_registry,
-                                                                &revocation_registry_private,
-                                                                &claim_req_json.blinded_ms,
-                                                                &attributes,
-                                                                user_revoc_index)?;
+        Ok(ClaimData {
+            claim_req: claim_req_json,
+            claim_def: claim_def,
+            claim_def_private: claim_def_private,
+            revocation_registry: revocation_registry,
+            revocation_registry_private: revocation_registry_private,
+            attributes: attributes
+        })
+    }
 
-        if let Some(x) = revocation_registry {
-            revocation_registry_json = RevocationRegistry::to_json(&x.borrow())
-                .map_err(map_err_trace!())
-                .map_err(|err| CommonError::InvalidState(format!("Invalid revocation registry: {}", err.to_string())))?;
+    fn _create_claim(anoncreds_service: &AnoncredsService,
+                     claim_data: ClaimData,
+                     user_revoc_index: Option<i32>) -> Result<(Option<RevocationRegistry>, String), IndyError> {
+        let revocation_registry = claim_data.revocation_registry.map(RefCell::new);
 
-            self.wallet_service.set(wallet_handle, &format!("revocation_registry::{}", &get_composite_id(&claim_req_json.issuer_did.clone(), claim_req_json.schema_seq_no)), &revocation_registry_json)?;
-        }
+        let claims = anoncreds_service.issuer.create_claim(&claim_data.claim_def,
+                                                           &claim_data.claim_def_private,
+                                                           &revocation_registry,
+                                                           &claim_data.revocation_registry_private,
+                                                           &claim_data.claim_req.blinded_ms,
+                                                           &claim_data.attributes,
+                                                           user_revoc_index)?;
 
-        let claim_json = ClaimJson::new(attributes, claims, claim_def.schema_seq_no, claim_req_json.issuer_did);
+        let claim_json = ClaimJson::new(claim_data.attributes, claims, claim_data.claim_def.schema_seq_no, claim_data.claim_req.issuer_did);
 
         let claim_json = ClaimJson::to_json(&claim_json)
             .map_err(map_err_trace!())
             .map_err(|err| CommonError::InvalidState(format!("Invalid claim_json: {}", err.to_string())))?;
 
-        Ok((revocation_registry_json, claim_json))
+        Ok((revocation_registry.map(RefCell::into_inner), claim_json))
+    }
+
+    fn create_claim_ack(&self,
+                        wallet_handle: i32,
+                        revoc_reg_id: Option<String>,
+                        cb_id: i32,
+                        result: Result<(Option<RevocationRegistry>, String), IndyError>) {
+        let result = result.and_then(|(revocation_registry, claim_json)| {
+            let revocation_registry_json = match (revocation_registry, revoc_reg_id.as_ref()) {
+                (Some(revocation_registry), Some(revoc_reg_id)) => {
+                    let revocation_registry_json = RevocationRegistry::to_json(&revocation_registry)
+                        .map_err(map_err_trace!())
+                        .map_err(|err| CommonError::InvalidState(format!("Invalid revocation registry: {}", err.to_string())))?;
+
+                    self.wallet_service.set(wallet_handle, &format!("revocation_registry::{}", revoc_reg_id), &revocation_registry_json)?;
+                    revocation_registry_json
+                }
+                _ => String::new()
+            };
+
+            Ok((revocation_registry_json, claim_json))
+        });
+
+        let cb = match self.pending_claim_callbacks.try_borrow_mut() {
+            Ok(mut cbs) => cbs.remove(&cb_id),
+            Err(err) => {
+                error!("{:?}", err);
+                None
+            }
+        };
+
+        match cb {
+            Some(cb) => cb(result),
+            None => error!("Can't process IssuerCommand::CreateClaimAck for handle {} - appropriate callback not found!", cb_id)
+        }
+
+        self._release_revocation_registry(revoc_reg_id);
+    }
+
+    // Marks registry as free and replays commands postponed while claim was in progress
+    fn _release_revocation_registry(&self, revoc_reg_id: Option<String>) {
+        let revoc_reg_id = match revoc_reg_id {
+            Some(revoc_reg_id) => revoc_reg_id,
+            None => return
+        };
+
+        let postponed = match self.busy_revocation_registries.try_borrow_mut() {
+            Ok(mut regs) => regs.remove(&revoc_reg_id).unwrap_or(Vec::new()),
+            Err(err) => return error!("{:?}", err)
+        };
+
+        for command in postponed {
+            self.execute(command);
+        }
     }
 
     fn revoke_claim(&self,
                     wallet_handle: i32,
-                    issuer_did: &str,
+                    issuer_did: String,
                     schema_seq_no: i32,
                     user_revoc_index: i32,
                     cb: Box<Fn(Result<String, IndyError>) + Send>) {
-        let result = self._revoke_claim(wallet_handle, issuer_did, schema_seq_no, user_revoc_index);
+        match self.busy_revocation_registries.try_borrow_mut() {
+            Ok(mut regs) => {
+                if let Some(queue) = regs.get_mut(&get_composite_id(&issuer_did, schema_seq_no)) {
+                    return queue.push(IssuerCommand::RevokeClaim(wallet_handle, issuer_did, schema_seq_no, user_revoc_index, cb));
+                }
+            }
+            Err(err) => return cb(Err(IndyError::CommonError(CommonError::from(err))))
+        }
+
+        let result = self._revoke_claim(wallet_handle, &issuer_did, schema_seq_no, user_revoc_index);
         cb(result)
     }
 
@@ -300,3 +848,13 @@ impl IssuerCommandExecutor {
         Ok(revoc_reg_update_json)
     }
 }
+
+// Everything issuer needs from wallet to create claim out of CommandExecutor thread
+struct ClaimData {
+    claim_req: ClaimRequestJson,
+    claim_def: ClaimDefinition,
+    claim_def_private: ClaimDefinitionPrivate,
+    revocation_registry: Option<RevocationRegistry>,
+    revocation_registry_private: Option<RevocationRegistryPrivate>,
+    attributes: HashMap<String, Vec<String>>
+}
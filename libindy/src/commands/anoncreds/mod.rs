@@ -10,7 +10,10 @@ use services::anoncreds::AnoncredsService;
 use services::pool::PoolService;
 use services::wallet::WalletService;
 
+use utils::workers::WorkerPool;
+
 use std::rc::Rc;
+use std::sync::Arc;
 
 pub enum AnoncredsCommand {
     Issuer(IssuerCommand),
@@ -25,16 +28,17 @@ pub struct AnoncredsCommandExecutor {
 }
 
 impl AnoncredsCommandExecutor {
-    pub fn new(anoncreds_service: Rc<AnoncredsService>,
+    pub fn new(anoncreds_service: Arc<AnoncredsService>,
                pool_service: Rc<PoolService>,
-               wallet_service: Rc<WalletService>) -> AnoncredsCommandExecutor {
+               wallet_service: Rc<WalletService>,
+               worker_pool: Rc<WorkerPool>) -> AnoncredsCommandExecutor {
         AnoncredsCommandExecutor {
             issuer_command_cxecutor: IssuerCommandExecutor::new(
-                anoncreds_service.clone(), pool_service.clone(), wallet_service.clone()),
+                anoncreds_service.clone(), pool_service.clone(), wallet_service.clone(), worker_pool.clone()),
             prover_command_cxecutor: ProverCommandExecutor::new(
-                anoncreds_service.clone(), pool_service.clone(), wallet_service.clone()),
+                anoncreds_service.clone(), pool_service.clone(), wallet_service.clone(), worker_pool.clone()),
             verifier_command_cxecutor: VerifierCommandExecutor::new(
-                anoncreds_service.clone(), pool_service.clone(), wallet_service.clone()),
+                anoncreds_service.clone(), pool_service.clone(), wallet_service.clone(), worker_pool.clone()),
         }
     }
 
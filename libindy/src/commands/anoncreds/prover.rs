@@ -11,6 +11,8 @@ use services::pool::PoolService;
 use utils::json::{JsonDecodable, JsonEncodable};
 use services::wallet::WalletService;
 use std::rc::Rc;
+use std::sync::Arc;
+use utils::workers::WorkerPool;
 use services::anoncreds::helpers::get_composite_id;
 use services::anoncreds::types::{
     ClaimDefinition,
@@ -33,6 +35,7 @@ use std::collections::HashMap;
 use utils::crypto::pair::PointG2;
 use std::cell::RefCell;
 use utils::crypto::base58::Base58;
+use utils::did::DidUtils;
 
 pub enum ProverCommand {
     StoreClaimOffer(
@@ -78,19 +81,22 @@ pub enum ProverCommand {
 }
 
 pub struct ProverCommandExecutor {
-    anoncreds_service: Rc<AnoncredsService>,
+    anoncreds_service: Arc<AnoncredsService>,
     pool_service: Rc<PoolService>,
-    wallet_service: Rc<WalletService>
+    wallet_service: Rc<WalletService>,
+    worker_pool: Rc<WorkerPool>
 }
 
 impl ProverCommandExecutor {
-    pub fn new(anoncreds_service: Rc<AnoncredsService>,
+    pub fn new(anoncreds_service: Arc<AnoncredsService>,
                pool_service: Rc<PoolService>,
-               wallet_service: Rc<WalletService>) -> ProverCommandExecutor {
+               wallet_service: Rc<WalletService>,
+               worker_pool: Rc<WorkerPool>) -> ProverCommandExecutor {
         ProverCommandExecutor {
             anoncreds_service: anoncreds_service,
             pool_service: pool_service,
             wallet_service: wallet_service,
+            worker_pool: worker_pool,
         }
     }
 
@@ -149,7 +155,7 @@ impl ProverCommandExecutor {
             .map_err(map_err_trace!())
             .map_err(|err| CommonError::InvalidStructure(format!("Invalid claim_offer_json: {}", err.to_string())))?;
 
-        Base58::decode(&claim_offer.issuer_did)
+        Base58::decode(DidUtils::unqualify(&claim_offer.issuer_did))
             .map_err(map_err_trace!())
             .map_err(|err| CommonError::InvalidStructure(format!("Invalid issuer did: {}", err.to_string())))?;
 
@@ -185,7 +191,7 @@ impl ProverCommandExecutor {
         claim_offers.retain(move |claim_offer| {
             let mut condition = true;
             if let Some(ref issuer_did) = filter.issuer_did {
-                condition = condition && claim_offer.issuer_did == issuer_did.clone();
+                condition = condition && DidUtils::normalize(&claim_offer.issuer_did) == DidUtils::normalize(issuer_did);
             }
             if let Some(ref schema_seq_no) = filter.schema_seq_no {
                 condition = condition && claim_offer.schema_seq_no == schema_seq_no.clone();
@@ -256,7 +262,7 @@ impl ProverCommandExecutor {
             .map_err(|err| CommonError::InvalidStructure(format!("Invalid prover did: {}", err.to_string())))?;
 
 
-        if claim_def.issuer_did != claim_offer.issuer_did {
+        if DidUtils::normalize(&claim_def.issuer_did) != DidUtils::normalize(&claim_offer.issuer_did) {
             return Err(IndyError::CommonError(CommonError::InvalidStructure(
                 format!("ClaimOffer issuer_did {} does not correspond to ClaimDef issuer_did {:?}", claim_offer.issuer_did, claim_def.issuer_did))))
         }
@@ -391,7 +397,7 @@ impl ProverCommandExecutor {
             }
 
             if let Some(issuer_did) = filter.issuer_did.clone() {
-                condition = condition && claim_info.issuer_did == issuer_did;
+                condition = condition && DidUtils::normalize(&claim_info.issuer_did) == DidUtils::normalize(&issuer_did);
             }
 
             condition
@@ -464,18 +470,28 @@ impl ProverCommandExecutor {
                     claim_def_jsons: &str,
                     revoc_regs_jsons: &str,
                     cb: Box<Fn(Result<String, IndyError>) + Send>) {
-        let result = self._create_proof(wallet_handle, proof_req_json, requested_claims_json, schemas_jsons, master_secret_name, claim_def_jsons, revoc_regs_jsons);
-        cb(result)
+        let proof_data = match self._prepare_proof_data(wallet_handle, proof_req_json, requested_claims_json, schemas_jsons,
+                                                        master_secret_name, claim_def_jsons, revoc_regs_jsons) {
+            Ok(proof_data) => proof_data,
+            Err(err) => return cb(Err(err))
+        };
+
+        let anoncreds_service = self.anoncreds_service.clone();
+
+        // Proof doesn't need wallet anymore, so it is created and returned from worker thread
+        self.worker_pool.execute_with_cb(
+            move || ProverCommandExecutor::_create_proof(&anoncreds_service, proof_data),
+            move |result| cb(result.map_err(IndyError::CommonError).and_then(|result| result)));
     }
 
-    fn _create_proof(&self,
-                     wallet_handle: i32,
-                     proof_req_json: &str,
-                     requested_claims_json: &str,
-                     schemas_jsons: &str,
-                     master_secret_name: &str,
-                     claim_def_jsons: &str,
-                     revoc_regs_jsons: &str) -> Result<String, IndyError> {
+    fn _prepare_proof_data(&self,
+                           wallet_handle: i32,
+                           proof_req_json: &str,
+                           requested_claims_json: &str,
+                           schemas_jsons: &str,
+                           master_secret_name: &str,
+                           claim_def_jsons: &str,
+                           revoc_regs_jsons: &str) -> Result<ProofData, IndyError> {
         let proof_req: ProofRequestJson = ProofRequestJson::from_json(proof_req_json)
             .map_err(map_err_trace!())
             .map_err(|err| CommonError::InvalidStructure(format!("Invalid proof_req_json: {}", err.to_string())))?;
@@ -520,14 +536,27 @@ impl ProverCommandExecutor {
                 .map_err(|err| CommonError::InvalidState(format!("Invalid tails_json: {}", err.to_string())))?;
         }
 
-        let proof_claims = self.anoncreds_service.prover.create_proof(claims,
-                                                                      &proof_req,
-                                                                      &schemas,
-                                                                      &claim_defs,
-                                                                      &revoc_regs,
-                                                                      &requested_claims,
-                                                                      &ms,
-                                                                      &tails)?;
+        Ok(ProofData {
+            claims: claims,
+            proof_req: proof_req,
+            schemas: schemas,
+            claim_defs: claim_defs,
+            revoc_regs: revoc_regs,
+            requested_claims: requested_claims,
+            ms: ms,
+            tails: tails
+        })
+    }
+
+    fn _create_proof(anoncreds_service: &AnoncredsService, proof_data: ProofData) -> Result<String, IndyError> {
+        let proof_claims = anoncreds_service.prover.create_proof(proof_data.claims,
+                                                                 &proof_data.proof_req,
+                                                                 &proof_data.schemas,
+                                                                 &proof_data.claim_defs,
+                                                                 &proof_data.revoc_regs,
+                                                                 &proof_data.requested_claims,
+                                                                 &proof_data.ms,
+                                                                 &proof_data.tails)?;
 
         let proof_claims_json = ProofJson::to_json(&proof_claims)
             .map_err(map_err_trace!())
@@ -535,4 +564,16 @@ impl ProverCommandExecutor {
 
         Ok(proof_claims_json)
     }
-}
\ No newline at end of file
+}
+
+// Everything prover needs from wallet to create proof out of CommandExecutor thread
+struct ProofData {
+    claims: HashMap<String, ClaimJson>,
+    proof_req: ProofRequestJson,
+    schemas: HashMap<String, Schema>,
+    claim_defs: HashMap<String, ClaimDefinition>,
+    revoc_regs: HashMap<String, RevocationRegistry>,
+    requested_claims: RequestedClaimsJson,
+    ms: BigNumber,
+    tails: HashMap<i32, PointG2>
+}
@@ -15,6 +15,8 @@ use services::anoncreds::types::{
     RevocationRegistry};
 use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
+use std::sync::Arc;
+use utils::workers::WorkerPool;
 use utils::json::JsonDecodable;
 
 pub enum VerifierCommand {
@@ -28,19 +30,22 @@ pub enum VerifierCommand {
 }
 
 pub struct VerifierCommandExecutor {
-    anoncreds_service: Rc<AnoncredsService>,
+    anoncreds_service: Arc<AnoncredsService>,
     pool_service: Rc<PoolService>,
-    wallet_service: Rc<WalletService>
+    wallet_service: Rc<WalletService>,
+    worker_pool: Rc<WorkerPool>
 }
 
 impl VerifierCommandExecutor {
-    pub fn new(anoncreds_service: Rc<AnoncredsService>,
+    pub fn new(anoncreds_service: Arc<AnoncredsService>,
                pool_service: Rc<PoolService>,
-               wallet_service: Rc<WalletService>) -> VerifierCommandExecutor {
+               wallet_service: Rc<WalletService>,
+               worker_pool: Rc<WorkerPool>) -> VerifierCommandExecutor {
         VerifierCommandExecutor {
             anoncreds_service: anoncreds_service,
             pool_service: pool_service,
             wallet_service: wallet_service,
+            worker_pool: worker_pool,
         }
     }
 
@@ -50,24 +55,29 @@ impl VerifierCommandExecutor {
                                          proof_json, schemas_json,
                                          claim_defs_jsons, revoc_regs_json, cb) => {
                 info!(target: "verifier_command_executor", "VerifyProof command received");
-                self.verify_proof(&proof_request_json, &proof_json, &schemas_json,
-                                  &claim_defs_jsons, &revoc_regs_json, cb);
+                self.verify_proof(proof_request_json, proof_json, schemas_json,
+                                  claim_defs_jsons, revoc_regs_json, cb);
             }
         };
     }
 
     fn verify_proof(&self,
-                    proof_request_json: &str,
-                    proof_json: &str,
-                    schemas_json: &str,
-                    claim_defs_jsons: &str,
-                    revoc_regs_json: &str,
+                    proof_request_json: String,
+                    proof_json: String,
+                    schemas_json: String,
+                    claim_defs_jsons: String,
+                    revoc_regs_json: String,
                     cb: Box<Fn(Result<bool, IndyError>) + Send>) {
-        let result = self._verify_proof(proof_request_json, proof_json, schemas_json, claim_defs_jsons, revoc_regs_json);
-        cb(result)
+        let anoncreds_service = self.anoncreds_service.clone();
+
+        // Verification doesn't use wallet, so it is completely done in worker thread
+        self.worker_pool.execute_with_cb(
+            move || VerifierCommandExecutor::_verify_proof(&anoncreds_service, &proof_request_json, &proof_json,
+                                                           &schemas_json, &claim_defs_jsons, &revoc_regs_json),
+            move |result| cb(result.map_err(IndyError::CommonError).and_then(|result| result)));
     }
 
-    fn _verify_proof(&self,
+    fn _verify_proof(anoncreds_service: &AnoncredsService,
                      proof_request_json: &str,
                      proof_json: &str,
                      schemas_json: &str,
@@ -144,11 +154,11 @@ impl VerifierCommandExecutor {
                 format!("Requested predicates {:?} do not correspond to received {:?}", requested_predicates, received_predicates))))
         }
 
-        let result = self.anoncreds_service.verifier.verify(&proof_claims,
-                                                            &proof_req.nonce,
-                                                            &claim_defs,
-                                                            &revoc_regs,
-                                                            &schemas)?;
+        let result = anoncreds_service.verifier.verify(&proof_claims,
+                                                       &proof_req.nonce,
+                                                       &claim_defs,
+                                                       &revoc_regs,
+                                                       &schemas)?;
 
         Ok(result)
     }
@@ -14,7 +14,8 @@ use services::signus::types::MyDid;
 use services::wallet::WalletService;
 use services::ledger::LedgerService;
 
-use utils::json::JsonDecodable;
+use utils::json::{JsonEncodable, JsonDecodable};
+use utils::did::DidUtils;
 
 use super::utils::check_wallet_and_pool_handles_consistency;
 
@@ -22,6 +23,7 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::error::Error;
 use std::rc::Rc;
+use std::sync::Arc;
 
 use utils::crypto::base58::Base58;
 
@@ -103,13 +105,46 @@ pub enum LedgerCommand {
     BuildGetTxnRequest(
         String, // submitter did
         i32, // data
+        Box<Fn(Result<String, IndyError>) + Send>),
+    BuildRevocRegDefRequest(
+        String, // submitter did
+        String, // revocation registry json
+        Box<Fn(Result<String, IndyError>) + Send>),
+    BuildRevocRegEntryRequest(
+        String, // submitter did
+        String, // revocation registry json
+        Box<Fn(Result<String, IndyError>) + Send>),
+    BuildGetRevocRegDefRequest(
+        String, // submitter did
+        String, // revocation registry id
+        Box<Fn(Result<String, IndyError>) + Send>),
+    BuildGetRevocRegRequest(
+        String, // submitter did
+        String, // revocation registry id
+        i64, // timestamp
+        Box<Fn(Result<String, IndyError>) + Send>),
+    ParseGetSchemaResponse(
+        String, // get schema response json
+        Box<Fn(Result<String, IndyError>) + Send>),
+    ParseGetClaimDefResponse(
+        String, // get claim def response json
+        Box<Fn(Result<String, IndyError>) + Send>),
+    ParseGetNymResponse(
+        String, // get nym response json
+        Box<Fn(Result<String, IndyError>) + Send>),
+    ParseGetRevocRegDefResponse(
+        String, // get revoc reg def response json
+        Box<Fn(Result<String, IndyError>) + Send>),
+    ParseGetRevocRegResponse(
+        String, // revoc reg def json
+        String, // get revoc reg response json
         Box<Fn(Result<String, IndyError>) + Send>)
 }
 
 pub struct LedgerCommandExecutor {
-    anoncreds_service: Rc<AnoncredsService>,
+    anoncreds_service: Arc<AnoncredsService>,
     pool_service: Rc<PoolService>,
-    signus_service: Rc<SignusService>,
+    signus_service: Arc<SignusService>,
     wallet_service: Rc<WalletService>,
     ledger_service: Rc<LedgerService>,
 
@@ -117,9 +152,9 @@ pub struct LedgerCommandExecutor {
 }
 
 impl LedgerCommandExecutor {
-    pub fn new(anoncreds_service: Rc<AnoncredsService>,
+    pub fn new(anoncreds_service: Arc<AnoncredsService>,
                pool_service: Rc<PoolService>,
-               signus_service: Rc<SignusService>,
+               signus_service: Arc<SignusService>,
                wallet_service: Rc<WalletService>,
                ledger_service: Rc<LedgerService>) -> LedgerCommandExecutor {
         LedgerCommandExecutor {
@@ -204,6 +239,42 @@ impl LedgerCommandExecutor {
                 info!(target: "ledger_command_executor", "BuildGetTxnRequest command received");
                 self.build_get_txn_request(&submitter_did, data, cb);
             }
+            LedgerCommand::BuildRevocRegDefRequest(submitter_did, data, cb) => {
+                info!(target: "ledger_command_executor", "BuildRevocRegDefRequest command received");
+                self.build_revoc_reg_def_request(&submitter_did, &data, cb);
+            }
+            LedgerCommand::BuildRevocRegEntryRequest(submitter_did, data, cb) => {
+                info!(target: "ledger_command_executor", "BuildRevocRegEntryRequest command received");
+                self.build_revoc_reg_entry_request(&submitter_did, &data, cb);
+            }
+            LedgerCommand::BuildGetRevocRegDefRequest(submitter_did, id, cb) => {
+                info!(target: "ledger_command_executor", "BuildGetRevocRegDefRequest command received");
+                self.build_get_revoc_reg_def_request(&submitter_did, &id, cb);
+            }
+            LedgerCommand::BuildGetRevocRegRequest(submitter_did, revoc_reg_def_id, timestamp, cb) => {
+                info!(target: "ledger_command_executor", "BuildGetRevocRegRequest command received");
+                self.build_get_revoc_reg_request(&submitter_did, &revoc_reg_def_id, timestamp, cb);
+            }
+            LedgerCommand::ParseGetSchemaResponse(get_schema_response, cb) => {
+                info!(target: "ledger_command_executor", "ParseGetSchemaResponse command received");
+                cb(self.parse_get_schema_response(&get_schema_response));
+            }
+            LedgerCommand::ParseGetClaimDefResponse(get_claim_def_response, cb) => {
+                info!(target: "ledger_command_executor", "ParseGetClaimDefResponse command received");
+                cb(self.parse_get_claim_def_response(&get_claim_def_response));
+            }
+            LedgerCommand::ParseGetNymResponse(get_nym_response, cb) => {
+                info!(target: "ledger_command_executor", "ParseGetNymResponse command received");
+                cb(self.parse_get_nym_response(&get_nym_response));
+            }
+            LedgerCommand::ParseGetRevocRegDefResponse(get_revoc_reg_def_response, cb) => {
+                info!(target: "ledger_command_executor", "ParseGetRevocRegDefResponse command received");
+                cb(self.parse_get_revoc_reg_def_response(&get_revoc_reg_def_response));
+            }
+            LedgerCommand::ParseGetRevocRegResponse(revoc_reg_def_json, get_revoc_reg_response, cb) => {
+                info!(target: "ledger_command_executor", "ParseGetRevocRegResponse command received");
+                cb(self.parse_get_revoc_reg_response(&revoc_reg_def_json, &get_revoc_reg_response));
+            }
         };
     }
 
@@ -226,7 +297,7 @@ impl LedgerCommandExecutor {
                      submitter_did: &str,
                      request_json: &str,
     ) -> Result<String, IndyError> {
-        let my_did_json = self.wallet_service.get(wallet_handle, &format!("my_did::{}", submitter_did))?;
+        let my_did_json = self.wallet_service.get(wallet_handle, &format!("my_did::{}", DidUtils::normalize(submitter_did)))?;
         let my_did = MyDid::from_json(&my_did_json)
             .map_err(|err| CommonError::InvalidState(format!("Invalid my_did_json: {}", err.to_string())))?;
 
@@ -393,4 +464,82 @@ impl LedgerCommandExecutor {
                                                      data
         ).map_err(|err| IndyError::CommonError(err)))
     }
+
+    fn build_revoc_reg_def_request(&self,
+                                   submitter_did: &str,
+                                   data: &str,
+                                   cb: Box<Fn(Result<String, IndyError>) + Send>) {
+        cb(self.ledger_service.build_revoc_reg_def_request(submitter_did,
+                                                           data
+        ).map_err(|err| IndyError::CommonError(err)))
+    }
+
+    fn build_revoc_reg_entry_request(&self,
+                                     submitter_did: &str,
+                                     data: &str,
+                                     cb: Box<Fn(Result<String, IndyError>) + Send>) {
+        cb(self.ledger_service.build_revoc_reg_entry_request(submitter_did,
+                                                             data
+        ).map_err(|err| IndyError::CommonError(err)))
+    }
+
+    fn build_get_revoc_reg_def_request(&self,
+                                       submitter_did: &str,
+                                       id: &str,
+                                       cb: Box<Fn(Result<String, IndyError>) + Send>) {
+        cb(self.ledger_service.build_get_revoc_reg_def_request(submitter_did,
+                                                               id
+        ).map_err(|err| IndyError::CommonError(err)))
+    }
+
+    fn build_get_revoc_reg_request(&self,
+                                   submitter_did: &str,
+                                   revoc_reg_def_id: &str,
+                                   timestamp: i64,
+                                   cb: Box<Fn(Result<String, IndyError>) + Send>) {
+        cb(self.ledger_service.build_get_revoc_reg_request(submitter_did,
+                                                           revoc_reg_def_id,
+                                                           timestamp
+        ).map_err(|err| IndyError::CommonError(err)))
+    }
+
+    fn parse_get_schema_response(&self, get_schema_response: &str) -> Result<String, IndyError> {
+        let schema = self.ledger_service.parse_get_schema_response(get_schema_response)?;
+
+        let schema_json = schema.to_json()
+            .map_err(|err| CommonError::InvalidState(format!("Can't serialize schema: {}", err.description())))?;
+        Ok(schema_json)
+    }
+
+    fn parse_get_claim_def_response(&self, get_claim_def_response: &str) -> Result<String, IndyError> {
+        let claim_def = self.ledger_service.parse_get_claim_def_response(get_claim_def_response)?;
+
+        let claim_def_json = claim_def.to_json()
+            .map_err(|err| CommonError::InvalidState(format!("Can't serialize claim definition: {}", err.description())))?;
+        Ok(claim_def_json)
+    }
+
+    fn parse_get_nym_response(&self, get_nym_response: &str) -> Result<String, IndyError> {
+        let nym_data = self.ledger_service.parse_get_nym_response(get_nym_response)?;
+
+        let nym_json = nym_data.to_json()
+            .map_err(|err| CommonError::InvalidState(format!("Can't serialize nym data: {}", err.description())))?;
+        Ok(nym_json)
+    }
+
+    fn parse_get_revoc_reg_def_response(&self, get_revoc_reg_def_response: &str) -> Result<String, IndyError> {
+        let revoc_reg_def = self.ledger_service.parse_get_revoc_reg_def_response(get_revoc_reg_def_response)?;
+
+        let revoc_reg_def_json = revoc_reg_def.to_json()
+            .map_err(|err| CommonError::InvalidState(format!("Can't serialize revocation registry definition: {}", err.description())))?;
+        Ok(revoc_reg_def_json)
+    }
+
+    fn parse_get_revoc_reg_response(&self, revoc_reg_def_json: &str, get_revoc_reg_response: &str) -> Result<String, IndyError> {
+        let revoc_reg = self.ledger_service.parse_get_revoc_reg_response(revoc_reg_def_json, get_revoc_reg_response)?;
+
+        let revoc_reg_json = revoc_reg.to_json()
+            .map_err(|err| CommonError::InvalidState(format!("Can't serialize revocation registry: {}", err.description())))?;
+        Ok(revoc_reg_json)
+    }
 }
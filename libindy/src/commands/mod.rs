@@ -7,6 +7,7 @@ pub mod agent;
 #[allow(unused_variables)] /* FIXME */
 pub mod anoncreds;
 pub mod ledger;
+pub mod pairwise;
 pub mod pool;
 pub mod signus;
 pub mod wallet;
@@ -14,6 +15,7 @@ pub mod wallet;
 use commands::agent::{AgentCommand, AgentCommandExecutor};
 use commands::anoncreds::{AnoncredsCommand, AnoncredsCommandExecutor};
 use commands::ledger::{LedgerCommand, LedgerCommandExecutor};
+use commands::pairwise::{PairwiseCommand, PairwiseCommandExecutor};
 use commands::pool::{PoolCommand, PoolCommandExecutor};
 use commands::signus::{SignusCommand, SignusCommandExecutor};
 use commands::wallet::{WalletCommand, WalletCommandExecutor};
@@ -31,13 +33,16 @@ use std::error::Error;
 use std::sync::mpsc::{Sender, channel};
 use std::rc::Rc;
 use std::thread;
-use std::sync::{Mutex, MutexGuard};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use utils::workers::{WorkerPool, DEFAULT_WORKERS_COUNT};
 
 pub enum Command {
     Exit,
     Agent(AgentCommand),
     Anoncreds(AnoncredsCommand),
     Ledger(LedgerCommand),
+    Pairwise(PairwiseCommand),
     Pool(PoolCommand),
     Signus(SignusCommand),
     Wallet(WalletCommand)
@@ -68,17 +73,21 @@ impl CommandExecutor {
                 info!(target: "command_executor", "Worker thread started");
 
                 let agent_service = Rc::new(AgentService::new());
-                let anoncreds_service = Rc::new(AnoncredsService::new());
+                let anoncreds_service = Arc::new(AnoncredsService::new());
                 let pool_service = Rc::new(PoolService::new());
                 let wallet_service = Rc::new(WalletService::new());
-                let signus_service = Rc::new(SignusService::new());
+                let signus_service = Arc::new(SignusService::new());
                 let ledger_service = Rc::new(LedgerService::new());
 
+                // CPU-heavy commands are computed here, wallet and pool state is accessed only from this thread
+                let worker_pool = Rc::new(WorkerPool::new(DEFAULT_WORKERS_COUNT));
+
                 let agent_command_executor = AgentCommandExecutor::new(agent_service.clone(), ledger_service.clone(), pool_service.clone(), wallet_service.clone());
-                let anoncreds_command_executor = AnoncredsCommandExecutor::new(anoncreds_service.clone(), pool_service.clone(), wallet_service.clone());
+                let anoncreds_command_executor = AnoncredsCommandExecutor::new(anoncreds_service.clone(), pool_service.clone(), wallet_service.clone(), worker_pool.clone());
                 let ledger_command_executor = LedgerCommandExecutor::new(anoncreds_service.clone(), pool_service.clone(), signus_service.clone(), wallet_service.clone(), ledger_service.clone());
+                let pairwise_command_executor = PairwiseCommandExecutor::new(wallet_service.clone());
                 let pool_command_executor = PoolCommandExecutor::new(pool_service.clone());
-                let signus_command_executor = SignusCommandExecutor::new(anoncreds_service.clone(), pool_service.clone(), wallet_service.clone(), signus_service.clone(), ledger_service.clone());
+                let signus_command_executor = SignusCommandExecutor::new(anoncreds_service.clone(), pool_service.clone(), wallet_service.clone(), signus_service.clone(), ledger_service.clone(), worker_pool.clone());
                 let wallet_command_executor = WalletCommandExecutor::new(wallet_service.clone());
 
                 loop {
@@ -95,6 +104,10 @@ impl CommandExecutor {
                             info!(target: "command_executor", "LedgerCommand command received");
                             ledger_command_executor.execute(cmd);
                         }
+                        Ok(Command::Pairwise(cmd)) => {
+                            info!(target: "command_executor", "PairwiseCommand command received");
+                            pairwise_command_executor.execute(cmd);
+                        }
                         Ok(Command::Pool(cmd)) => {
                             info!(target: "command_executor", "PoolCommand command received");
                             pool_command_executor.execute(cmd);
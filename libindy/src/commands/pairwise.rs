@@ -0,0 +1,173 @@
+extern crate serde_json;
+
+use errors::common::CommonError;
+use errors::indy::IndyError;
+use services::pairwise::types::{Pairwise, PairwiseInfo};
+use services::wallet::WalletService;
+use utils::did::DidUtils;
+use utils::json::{JsonDecodable, JsonEncodable};
+
+use std::error::Error;
+use std::rc::Rc;
+
+pub enum PairwiseCommand {
+    CreatePairwise(
+        i32, // wallet handle
+        String, // their did
+        String, // my did
+        Option<String>, // metadata
+        Box<Fn(Result<(), IndyError>) + Send>),
+    ListPairwise(
+        i32, // wallet handle
+        Box<Fn(Result<String, IndyError>) + Send>),
+    GetPairwise(
+        i32, // wallet handle
+        String, // their did
+        Box<Fn(Result<String, IndyError>) + Send>),
+    SetPairwiseMetadata(
+        i32, // wallet handle
+        String, // their did
+        Option<String>, // metadata
+        Box<Fn(Result<(), IndyError>) + Send>)
+}
+
+pub struct PairwiseCommandExecutor {
+    wallet_service: Rc<WalletService>
+}
+
+impl PairwiseCommandExecutor {
+    pub fn new(wallet_service: Rc<WalletService>) -> PairwiseCommandExecutor {
+        PairwiseCommandExecutor {
+            wallet_service: wallet_service
+        }
+    }
+
+    pub fn execute(&self, command: PairwiseCommand) {
+        match command {
+            PairwiseCommand::CreatePairwise(wallet_handle, their_did, my_did, metadata, cb) => {
+                info!(target: "pairwise_command_executor", "CreatePairwise command received");
+                self.create_pairwise(wallet_handle, &their_did, &my_did, metadata.as_ref().map(String::as_str), cb);
+            }
+            PairwiseCommand::ListPairwise(wallet_handle, cb) => {
+                info!(target: "pairwise_command_executor", "ListPairwise command received");
+                self.list_pairwise(wallet_handle, cb);
+            }
+            PairwiseCommand::GetPairwise(wallet_handle, their_did, cb) => {
+                info!(target: "pairwise_command_executor", "GetPairwise command received");
+                self.get_pairwise(wallet_handle, &their_did, cb);
+            }
+            PairwiseCommand::SetPairwiseMetadata(wallet_handle, their_did, metadata, cb) => {
+                info!(target: "pairwise_command_executor", "SetPairwiseMetadata command received");
+                self.set_pairwise_metadata(wallet_handle, &their_did, metadata.as_ref().map(String::as_str), cb);
+            }
+        };
+    }
+
+    fn create_pairwise(&self,
+                       wallet_handle: i32,
+                       their_did: &str,
+                       my_did: &str,
+                       metadata: Option<&str>,
+                       cb: Box<Fn(Result<(), IndyError>) + Send>) {
+        cb(self._create_pairwise(wallet_handle, their_did, my_did, metadata));
+    }
+
+    fn _create_pairwise(&self,
+                        wallet_handle: i32,
+                        their_did: &str,
+                        my_did: &str,
+                        metadata: Option<&str>) -> Result<(), IndyError> {
+        // Both DIDs must be known to the wallet before relationship can be created
+        self.wallet_service.get(wallet_handle, &format!("my_did::{}", DidUtils::normalize(my_did)))?;
+        self.wallet_service.get(wallet_handle, &format!("their_did::{}", DidUtils::normalize(their_did)))?;
+
+        let pairwise = Pairwise::new(my_did.to_string(), their_did.to_string(), metadata.map(str::to_string));
+        self._store_pairwise(wallet_handle, &pairwise)
+    }
+
+    fn list_pairwise(&self,
+                     wallet_handle: i32,
+                     cb: Box<Fn(Result<String, IndyError>) + Send>) {
+        cb(self._list_pairwise(wallet_handle));
+    }
+
+    fn _list_pairwise(&self,
+                      wallet_handle: i32) -> Result<String, IndyError> {
+        let pairwise_list = self.wallet_service.list(wallet_handle, "pairwise::")?;
+
+        let mut result: Vec<Pairwise> = Vec::new();
+
+        for &(_, ref pairwise_json) in pairwise_list.iter() {
+            result.push(Pairwise::from_json(pairwise_json)
+                .map_err(map_err_trace!())
+                .map_err(|err|
+                    CommonError::InvalidState(format!("Invalid Pairwise json: {}", err.description())))?);
+        }
+
+        let result_json = serde_json::to_string(&result)
+            .map_err(map_err_trace!())
+            .map_err(|err|
+                CommonError::InvalidState(format!("Can't serialize list of Pairwise: {}", err.description())))?;
+
+        Ok(result_json)
+    }
+
+    fn get_pairwise(&self,
+                    wallet_handle: i32,
+                    their_did: &str,
+                    cb: Box<Fn(Result<String, IndyError>) + Send>) {
+        cb(self._get_pairwise(wallet_handle, their_did));
+    }
+
+    fn _get_pairwise(&self,
+                     wallet_handle: i32,
+                     their_did: &str) -> Result<String, IndyError> {
+        let pairwise = get_pairwise(&self.wallet_service, wallet_handle, their_did)?;
+
+        let pairwise_info_json = PairwiseInfo::new(pairwise).to_json()
+            .map_err(map_err_trace!())
+            .map_err(|err|
+                CommonError::InvalidState(format!("Can't serialize PairwiseInfo: {}", err.description())))?;
+
+        Ok(pairwise_info_json)
+    }
+
+    fn set_pairwise_metadata(&self,
+                             wallet_handle: i32,
+                             their_did: &str,
+                             metadata: Option<&str>,
+                             cb: Box<Fn(Result<(), IndyError>) + Send>) {
+        cb(self._set_pairwise_metadata(wallet_handle, their_did, metadata));
+    }
+
+    fn _set_pairwise_metadata(&self,
+                              wallet_handle: i32,
+                              their_did: &str,
+                              metadata: Option<&str>) -> Result<(), IndyError> {
+        let mut pairwise = get_pairwise(&self.wallet_service, wallet_handle, their_did)?;
+        pairwise.metadata = metadata.map(str::to_string);
+        self._store_pairwise(wallet_handle, &pairwise)
+    }
+
+    fn _store_pairwise(&self, wallet_handle: i32, pairwise: &Pairwise) -> Result<(), IndyError> {
+        let pairwise_json = pairwise.to_json()
+            .map_err(map_err_trace!())
+            .map_err(|err|
+                CommonError::InvalidState(format!("Can't serialize Pairwise: {}", err.description())))?;
+
+        self.wallet_service.set(wallet_handle, &format!("pairwise::{}", DidUtils::normalize(&pairwise.their_did)), &pairwise_json)?;
+        Ok(())
+    }
+}
+
+/// Looks up pairwise relationship stored in the wallet by DID of the other party.
+pub fn get_pairwise(wallet_service: &WalletService, wallet_handle: i32, their_did: &str) -> Result<Pairwise, IndyError> {
+    let pairwise_json = wallet_service.get(wallet_handle, &format!("pairwise::{}", DidUtils::normalize(their_did)))?;
+
+    let pairwise = Pairwise::from_json(&pairwise_json)
+        .map_err(map_err_trace!())
+        .map_err(|err|
+            CommonError::InvalidState(format!("Invalid Pairwise json: {}", err.description())))?;
+
+    Ok(pairwise)
+}
@@ -1,10 +1,15 @@
+extern crate serde_json;
+
+use api::ErrorCode;
 use utils::json::{JsonDecodable, JsonEncodable};
+use utils::did::DidUtils;
 use errors::signus::SignusError;
 use errors::common::CommonError;
 use errors::wallet::WalletError;
 use errors::indy::IndyError;
-use services::signus::types::{MyDidInfo, MyKyesInfo, MyDid, TheirDidInfo, TheirDid};
-use services::ledger::types::{Reply, GetNymResultData, GetNymReplyResult};
+use errors::ledger::LedgerError;
+use services::signus::types::{Key, MyDidInfo, MyKyesInfo, MyDid, MyDidWithMeta, PackedMessage, TheirDidInfo, TheirDid};
+use services::ledger::types::{Reply, GetNymResultData, GetNymReplyResult, Endpoint, DidDocSource};
 use services::anoncreds::AnoncredsService;
 use services::pool::PoolService;
 use services::wallet::WalletService;
@@ -13,6 +18,7 @@ use services::ledger::LedgerService;
 
 use std::error::Error;
 use std::rc::Rc;
+use std::sync::Arc;
 use std::str;
 use std::cell::RefCell;
 
@@ -20,10 +26,37 @@ use commands::ledger::LedgerCommand;
 use commands::{Command, CommandExecutor};
 use std::collections::HashMap;
 use utils::sequence::SequenceUtils;
+use utils::crypto::base58::Base58;
+use utils::crypto::mnemonic::Mnemonic;
+use utils::workers::WorkerPool;
 
 use super::utils::check_wallet_and_pool_handles_consistency;
 
 pub enum SignusCommand {
+    RegisterCryptoType(
+        String, // xtype
+        extern fn(seed_raw: *const u8, seed_len: u32,
+                  verkey_raw: *mut *const u8, verkey_len: *mut u32,
+                  signkey_raw: *mut *const u8, signkey_len: *mut u32) -> ErrorCode, // create_key
+        extern fn(signkey_raw: *const u8, signkey_len: u32,
+                  message_raw: *const u8, message_len: u32,
+                  signature_raw: *mut *const u8, signature_len: *mut u32) -> ErrorCode, // sign
+        extern fn(verkey_raw: *const u8, verkey_len: u32,
+                  message_raw: *const u8, message_len: u32,
+                  signature_raw: *const u8, signature_len: u32,
+                  valid: *mut u32) -> ErrorCode, // verify
+        extern fn(signkey_raw: *const u8, signkey_len: u32,
+                  verkey_raw: *const u8, verkey_len: u32,
+                  nonce_raw: *const u8, nonce_len: u32,
+                  message_raw: *const u8, message_len: u32,
+                  encrypted_msg_raw: *mut *const u8, encrypted_msg_len: *mut u32) -> ErrorCode, // encrypt
+        extern fn(signkey_raw: *const u8, signkey_len: u32,
+                  verkey_raw: *const u8, verkey_len: u32,
+                  nonce_raw: *const u8, nonce_len: u32,
+                  encrypted_msg_raw: *const u8, encrypted_msg_len: u32,
+                  decrypted_msg_raw: *mut *const u8, decrypted_msg_len: *mut u32) -> ErrorCode, // decrypt
+        extern fn(data_raw: *const u8, data_len: u32) -> ErrorCode, // free
+        Box<Fn(Result<(), IndyError>) + Send>),
     CreateAndStoreMyDid(
         i32, // wallet handle
         String, // did json
@@ -33,6 +66,15 @@ pub enum SignusCommand {
         String, // identity json
         String, // did
         Box<Fn(Result<(String, String), IndyError>) + Send>),
+    ReplaceKeysStart(
+        i32, // wallet handle
+        String, // identity json
+        String, // did
+        Box<Fn(Result<(String, String), IndyError>) + Send>),
+    ReplaceKeysApply(
+        i32, // wallet handle
+        String, // did
+        Box<Fn(Result<(), IndyError>) + Send>),
     StoreTheirDid(
         i32, // wallet handle
         String, // identity json
@@ -76,39 +118,179 @@ pub enum SignusCommand {
         String, // did
         Vec<u8>, // encrypted msg
         Vec<u8>, // nonce
-        Box<Fn(Result<Vec<u8>, IndyError>) + Send>)
+        Box<Fn(Result<Vec<u8>, IndyError>) + Send>),
+    SetDidMetadata(
+        i32, // wallet handle
+        String, // did
+        String, // metadata
+        Box<Fn(Result<(), IndyError>) + Send>),
+    GetDidMetadata(
+        i32, // wallet handle
+        String, // did
+        Box<Fn(Result<Option<String>, IndyError>) + Send>),
+    GetMyDidWithMeta(
+        i32, // wallet handle
+        String, // my did
+        Box<Fn(Result<String, IndyError>) + Send>),
+    ListMyDidsWithMeta(
+        i32, // wallet handle
+        Box<Fn(Result<String, IndyError>) + Send>),
+    ExportMasterSeed(
+        i32, // wallet handle
+        Box<Fn(Result<String, IndyError>) + Send>),
+    ImportMasterSeed(
+        i32, // wallet handle
+        String, // master seed
+        Box<Fn(Result<(), IndyError>) + Send>),
+    ExportMasterSeedMnemonic(
+        i32, // wallet handle
+        Box<Fn(Result<String, IndyError>) + Send>),
+    ImportMasterSeedMnemonic(
+        i32, // wallet handle
+        String, // mnemonic
+        Box<Fn(Result<(), IndyError>) + Send>),
+    SetDefaultDidMethod(
+        Option<String>, // method name
+        Box<Fn(Result<(), IndyError>) + Send>),
+    ResolveDid(
+        i32, // wallet handle
+        i32, // pool handle
+        String, // did
+        Box<Fn(Result<String, IndyError>) + Send>),
+    ResolveDidGetNymAck(
+        i32, // wallet handle
+        i32, // pool handle
+        String, // did
+        i32, // cb_id
+        Result<String, IndyError> // result
+    ),
+    ResolveDidGetAttribAck(
+        i32, // wallet handle
+        String, // did
+        GetNymResultData, // nym data
+        i32, // cb_id
+        Result<String, IndyError> // result
+    ),
+    SetEndpointForDid(
+        i32, // wallet handle
+        String, // did
+        String, // address
+        String, // transport key
+        Box<Fn(Result<(), IndyError>) + Send>),
+    GetEndpointForDid(
+        i32, // wallet handle
+        i32, // pool handle
+        String, // did
+        Box<Fn(Result<(String, String), IndyError>) + Send>),
+    GetEndpointForDidGetAttribAck(
+        i32, // wallet handle
+        String, // did
+        i32, // cb_id
+        Result<String, IndyError> // result
+    ),
+    PublishEndpoint(
+        i32, // wallet handle
+        i32, // pool handle
+        String, // submitter did
+        String, // did
+        String, // address
+        String, // transport key
+        Box<Fn(Result<String, IndyError>) + Send>),
+    VerifySignatureWithVerkey(
+        Option<String>, // did
+        String, // verkey
+        Vec<u8>, // msg
+        Vec<u8>, // signature
+        Box<Fn(Result<bool, IndyError>) + Send>),
+    CreateKey(
+        i32, // wallet handle
+        String, // key json
+        Box<Fn(Result<String, IndyError>) + Send>),
+    CryptoSign(
+        i32, // wallet handle
+        String, // my vk
+        Vec<u8>, // msg
+        Box<Fn(Result<Vec<u8>, IndyError>) + Send>),
+    CryptoVerify(
+        String, // their vk
+        Vec<u8>, // msg
+        Vec<u8>, // signature
+        Box<Fn(Result<bool, IndyError>) + Send>),
+    CryptoBox(
+        i32, // wallet handle
+        String, // my vk
+        String, // their vk
+        Vec<u8>, // msg
+        Box<Fn(Result<(Vec<u8>, Vec<u8>), IndyError>) + Send>),
+    CryptoBoxOpen(
+        i32, // wallet handle
+        String, // my vk
+        String, // their vk
+        Vec<u8>, // encrypted msg
+        Vec<u8>, // nonce
+        Box<Fn(Result<Vec<u8>, IndyError>) + Send>),
+    AnonCrypt(
+        String, // their vk
+        Vec<u8>, // msg
+        Box<Fn(Result<Vec<u8>, IndyError>) + Send>),
+    AnonDecrypt(
+        i32, // wallet handle
+        String, // my vk
+        Vec<u8>, // encrypted msg
+        Box<Fn(Result<Vec<u8>, IndyError>) + Send>),
+    PackMessage(
+        i32, // wallet handle
+        Vec<u8>, // msg
+        String, // recipient verkeys json
+        Option<String>, // sender vk
+        Box<Fn(Result<String, IndyError>) + Send>),
+    UnpackMessage(
+        i32, // wallet handle
+        String, // packed msg json
+        Box<Fn(Result<(Vec<u8>, Option<String>, String), IndyError>) + Send>)
 }
 
 pub struct SignusCommandExecutor {
-    anoncreds_service: Rc<AnoncredsService>,
+    anoncreds_service: Arc<AnoncredsService>,
     pool_service: Rc<PoolService>,
     wallet_service: Rc<WalletService>,
-    signus_service: Rc<SignusService>,
+    signus_service: Arc<SignusService>,
     ledger_service: Rc<LedgerService>,
+    worker_pool: Rc<WorkerPool>,
     verify_callbacks: RefCell<HashMap<i32, Box<Fn(Result<bool, IndyError>)>>>,
     encrypt_callbacks: RefCell<HashMap<i32, Box<Fn(Result<(Vec<u8>, Vec<u8>), IndyError>)>>>,
+    resolve_did_callbacks: RefCell<HashMap<i32, Box<Fn(Result<String, IndyError>)>>>,
+    get_endpoint_callbacks: RefCell<HashMap<i32, Box<Fn(Result<(String, String), IndyError>)>>>,
 
 }
 
 impl SignusCommandExecutor {
-    pub fn new(anoncreds_service: Rc<AnoncredsService>,
+    pub fn new(anoncreds_service: Arc<AnoncredsService>,
                pool_service: Rc<PoolService>,
                wallet_service: Rc<WalletService>,
-               signus_service: Rc<SignusService>,
-               ledger_service: Rc<LedgerService>) -> SignusCommandExecutor {
+               signus_service: Arc<SignusService>,
+               ledger_service: Rc<LedgerService>,
+               worker_pool: Rc<WorkerPool>) -> SignusCommandExecutor {
         SignusCommandExecutor {
             anoncreds_service: anoncreds_service,
             pool_service: pool_service,
             wallet_service: wallet_service,
             signus_service: signus_service,
             ledger_service: ledger_service,
+            worker_pool: worker_pool,
             verify_callbacks: RefCell::new(HashMap::new()),
             encrypt_callbacks: RefCell::new(HashMap::new()),
+            resolve_did_callbacks: RefCell::new(HashMap::new()),
+            get_endpoint_callbacks: RefCell::new(HashMap::new()),
         }
     }
 
     pub fn execute(&self, command: SignusCommand) {
         match command {
+            SignusCommand::RegisterCryptoType(xtype, create_key, sign, verify, encrypt, decrypt, free, cb) => {
+                info!(target: "signus_command_executor", "RegisterCryptoType command received");
+                self.register_type(&xtype, create_key, sign, verify, encrypt, decrypt, free, cb);
+            }
             SignusCommand::CreateAndStoreMyDid(wallet_handle, did_json, cb) => {
                 info!(target: "signus_command_executor", "CreateAndStoreMyDid command received");
                 self.create_and_store_my_did(wallet_handle, &did_json, cb);
@@ -117,6 +299,14 @@ impl SignusCommandExecutor {
                 info!(target: "signus_command_executor", "ReplaceKeys command received");
                 self.replace_keys(wallet_handle, &identity_json, &did, cb);
             }
+            SignusCommand::ReplaceKeysStart(wallet_handle, identity_json, did, cb) => {
+                info!(target: "signus_command_executor", "ReplaceKeysStart command received");
+                self.replace_keys_start(wallet_handle, &identity_json, &did, cb);
+            }
+            SignusCommand::ReplaceKeysApply(wallet_handle, did, cb) => {
+                info!(target: "signus_command_executor", "ReplaceKeysApply command received");
+                self.replace_keys_apply(wallet_handle, &did, cb);
+            }
             SignusCommand::StoreTheirDid(wallet_handle, identity_json, cb) => {
                 info!(target: "signus_command_executor", "StoreTheirDid command received");
                 self.store_their_did(wallet_handle, &identity_json, cb);
@@ -145,9 +335,143 @@ impl SignusCommandExecutor {
                 info!(target: "signus_command_executor", "Decrypt command received");
                 self.decrypt(wallet_handle, &my_did, &did, &encrypted_msg, &nonce, cb);
             }
+            SignusCommand::SetDidMetadata(wallet_handle, did, metadata, cb) => {
+                info!(target: "signus_command_executor", "SetDidMetadata command received");
+                self.set_did_metadata(wallet_handle, &did, &metadata, cb);
+            }
+            SignusCommand::GetDidMetadata(wallet_handle, did, cb) => {
+                info!(target: "signus_command_executor", "GetDidMetadata command received");
+                self.get_did_metadata(wallet_handle, &did, cb);
+            }
+            SignusCommand::GetMyDidWithMeta(wallet_handle, my_did, cb) => {
+                info!(target: "signus_command_executor", "GetMyDidWithMeta command received");
+                self.get_my_did_with_meta(wallet_handle, &my_did, cb);
+            }
+            SignusCommand::ListMyDidsWithMeta(wallet_handle, cb) => {
+                info!(target: "signus_command_executor", "ListMyDidsWithMeta command received");
+                self.list_my_dids_with_meta(wallet_handle, cb);
+            }
+            SignusCommand::ExportMasterSeed(wallet_handle, cb) => {
+                info!(target: "signus_command_executor", "ExportMasterSeed command received");
+                self.export_master_seed(wallet_handle, cb);
+            }
+            SignusCommand::ImportMasterSeed(wallet_handle, master_seed, cb) => {
+                info!(target: "signus_command_executor", "ImportMasterSeed command received");
+                self.import_master_seed(wallet_handle, &master_seed, cb);
+            }
+            SignusCommand::ExportMasterSeedMnemonic(wallet_handle, cb) => {
+                info!(target: "signus_command_executor", "ExportMasterSeedMnemonic command received");
+                self.export_master_seed_mnemonic(wallet_handle, cb);
+            }
+            SignusCommand::ImportMasterSeedMnemonic(wallet_handle, mnemonic, cb) => {
+                info!(target: "signus_command_executor", "ImportMasterSeedMnemonic command received");
+                self.import_master_seed_mnemonic(wallet_handle, &mnemonic, cb);
+            }
+            SignusCommand::SetDefaultDidMethod(method_name, cb) => {
+                info!(target: "signus_command_executor", "SetDefaultDidMethod command received");
+                self.set_default_did_method(method_name.as_ref().map(String::as_str), cb);
+            }
+            SignusCommand::ResolveDid(wallet_handle, pool_handle, did, cb) => {
+                info!(target: "signus_command_executor", "ResolveDid command received");
+                self.resolve_did(wallet_handle, pool_handle, &did, cb);
+            }
+            SignusCommand::ResolveDidGetNymAck(wallet_handle, pool_handle, did, cb_id, result) => {
+                info!(target: "signus_command_executor", "ResolveDidGetNymAck command received");
+                self.resolve_did_get_nym_ack(wallet_handle, pool_handle, &did, cb_id, result);
+            }
+            SignusCommand::ResolveDidGetAttribAck(wallet_handle, did, nym_data, cb_id, result) => {
+                info!(target: "signus_command_executor", "ResolveDidGetAttribAck command received");
+                self.resolve_did_get_attrib_ack(wallet_handle, &did, &nym_data, cb_id, result);
+            }
+            SignusCommand::SetEndpointForDid(wallet_handle, did, address, transport_key, cb) => {
+                info!(target: "signus_command_executor", "SetEndpointForDid command received");
+                self.set_endpoint_for_did(wallet_handle, &did, &address, &transport_key, cb);
+            }
+            SignusCommand::GetEndpointForDid(wallet_handle, pool_handle, did, cb) => {
+                info!(target: "signus_command_executor", "GetEndpointForDid command received");
+                self.get_endpoint_for_did(wallet_handle, pool_handle, &did, cb);
+            }
+            SignusCommand::GetEndpointForDidGetAttribAck(wallet_handle, did, cb_id, result) => {
+                info!(target: "signus_command_executor", "GetEndpointForDidGetAttribAck command received");
+                self.get_endpoint_for_did_get_attrib_ack(wallet_handle, &did, cb_id, result);
+            }
+            SignusCommand::PublishEndpoint(wallet_handle, pool_handle, submitter_did, did, address, transport_key, cb) => {
+                info!(target: "signus_command_executor", "PublishEndpoint command received");
+                self.publish_endpoint(wallet_handle, pool_handle, &submitter_did, &did, &address, &transport_key, cb);
+            }
+            SignusCommand::VerifySignatureWithVerkey(did, verkey, msg, signature, cb) => {
+                info!(target: "signus_command_executor", "VerifySignatureWithVerkey command received");
+                self.verify_signature_with_verkey(did, verkey, msg, signature, cb);
+            }
+            SignusCommand::CreateKey(wallet_handle, key_json, cb) => {
+                info!(target: "signus_command_executor", "CreateKey command received");
+                self.create_key(wallet_handle, &key_json, cb);
+            }
+            SignusCommand::CryptoSign(wallet_handle, my_vk, msg, cb) => {
+                info!(target: "signus_command_executor", "CryptoSign command received");
+                self.crypto_sign(wallet_handle, &my_vk, msg, cb);
+            }
+            SignusCommand::CryptoVerify(their_vk, msg, signature, cb) => {
+                info!(target: "signus_command_executor", "CryptoVerify command received");
+                self.crypto_verify(their_vk, msg, signature, cb);
+            }
+            SignusCommand::CryptoBox(wallet_handle, my_vk, their_vk, msg, cb) => {
+                info!(target: "signus_command_executor", "CryptoBox command received");
+                self.crypto_box(wallet_handle, &my_vk, their_vk, msg, cb);
+            }
+            SignusCommand::CryptoBoxOpen(wallet_handle, my_vk, their_vk, encrypted_msg, nonce, cb) => {
+                info!(target: "signus_command_executor", "CryptoBoxOpen command received");
+                self.crypto_box_open(wallet_handle, &my_vk, their_vk, encrypted_msg, nonce, cb);
+            }
+            SignusCommand::AnonCrypt(their_vk, msg, cb) => {
+                info!(target: "signus_command_executor", "AnonCrypt command received");
+                self.anon_crypt(their_vk, msg, cb);
+            }
+            SignusCommand::AnonDecrypt(wallet_handle, my_vk, encrypted_msg, cb) => {
+                info!(target: "signus_command_executor", "AnonDecrypt command received");
+                self.anon_decrypt(wallet_handle, &my_vk, encrypted_msg, cb);
+            }
+            SignusCommand::PackMessage(wallet_handle, msg, recipient_vks_json, sender_vk, cb) => {
+                info!(target: "signus_command_executor", "PackMessage command received");
+                self.pack_message(wallet_handle, msg, &recipient_vks_json, sender_vk.as_ref().map(String::as_str), cb);
+            }
+            SignusCommand::UnpackMessage(wallet_handle, packed_msg_json, cb) => {
+                info!(target: "signus_command_executor", "UnpackMessage command received");
+                self.unpack_message(wallet_handle, &packed_msg_json, cb);
+            }
         };
     }
 
+    fn register_type(&self,
+                     xtype: &str,
+                     create_key: extern fn(seed_raw: *const u8, seed_len: u32,
+                                           verkey_raw: *mut *const u8, verkey_len: *mut u32,
+                                           signkey_raw: *mut *const u8, signkey_len: *mut u32) -> ErrorCode,
+                     sign: extern fn(signkey_raw: *const u8, signkey_len: u32,
+                                     message_raw: *const u8, message_len: u32,
+                                     signature_raw: *mut *const u8, signature_len: *mut u32) -> ErrorCode,
+                     verify: extern fn(verkey_raw: *const u8, verkey_len: u32,
+                                       message_raw: *const u8, message_len: u32,
+                                       signature_raw: *const u8, signature_len: u32,
+                                       valid: *mut u32) -> ErrorCode,
+                     encrypt: extern fn(signkey_raw: *const u8, signkey_len: u32,
+                                        verkey_raw: *const u8, verkey_len: u32,
+                                        nonce_raw: *const u8, nonce_len: u32,
+                                        message_raw: *const u8, message_len: u32,
+                                        encrypted_msg_raw: *mut *const u8, encrypted_msg_len: *mut u32) -> ErrorCode,
+                     decrypt: extern fn(signkey_raw: *const u8, signkey_len: u32,
+                                        verkey_raw: *const u8, verkey_len: u32,
+                                        nonce_raw: *const u8, nonce_len: u32,
+                                        encrypted_msg_raw: *const u8, encrypted_msg_len: u32,
+                                        decrypted_msg_raw: *mut *const u8, decrypted_msg_len: *mut u32) -> ErrorCode,
+                     free: extern fn(data_raw: *const u8, data_len: u32) -> ErrorCode,
+                     cb: Box<Fn(Result<(), IndyError>) + Send>) {
+        cb(self
+            .signus_service
+            .register_type(xtype, create_key, sign, verify, encrypt, decrypt, free)
+            .map_err(IndyError::from));
+    }
+
     fn create_and_store_my_did(&self,
                                wallet_handle: i32,
                                my_did_info_json: &str,
@@ -162,7 +486,13 @@ impl SignusCommandExecutor {
                 CommonError::InvalidStructure(
                     format!("Invalid MyDidInfo json: {}", err.description())))?;
 
-        let my_did = self.signus_service.create_my_did(&my_did_info)?;
+        let my_did = match my_did_info.derivation_path {
+            Some(_) => {
+                let master_seed = self._get_or_create_master_seed(wallet_handle)?;
+                self.signus_service.derive_my_did(&my_did_info, &master_seed)?
+            }
+            None => self.signus_service.create_my_did(&my_did_info)?
+        };
 
         let my_did_json = MyDid::to_json(&my_did)
             .map_err(map_err_trace!())
@@ -170,7 +500,7 @@ impl SignusCommandExecutor {
                 CommonError::InvalidState(
                     format!("Can't serialize MyDid: {}", err.description())))?;
 
-        self.wallet_service.set(wallet_handle, &format!("my_did::{}", my_did.did), &my_did_json)?;
+        self.wallet_service.set(wallet_handle, &format!("my_did::{}", DidUtils::normalize(&my_did.did)), &my_did_json)?;
         Ok((my_did.did, my_did.verkey, my_did.pk))
     }
 
@@ -186,6 +516,61 @@ impl SignusCommandExecutor {
                      wallet_handle: i32,
                      keys_info_json: &str,
                      did: &str) -> Result<(String, String), IndyError> {
+        let (my_did, my_did_json) = self._create_new_keys(keys_info_json, did)?;
+
+        self.wallet_service.set(wallet_handle, &format!("my_did::{}", DidUtils::normalize(&my_did.did)), &my_did_json)?;
+
+        Ok((my_did.verkey, my_did.pk))
+    }
+
+    fn replace_keys_start(&self,
+                          wallet_handle: i32,
+                          keys_info_json: &str,
+                          did: &str,
+                          cb: Box<Fn(Result<(String, String), IndyError>) + Send>) {
+        cb(self._replace_keys_start(wallet_handle, keys_info_json, did));
+    }
+
+    fn _replace_keys_start(&self,
+                           wallet_handle: i32,
+                           keys_info_json: &str,
+                           did: &str) -> Result<(String, String), IndyError> {
+        // Current keys must exist as they keep signing until new keys are applied
+        self.wallet_service.get(wallet_handle, &format!("my_did::{}", DidUtils::normalize(did)))?;
+
+        let (my_did, my_did_json) = self._create_new_keys(keys_info_json, did)?;
+
+        self.wallet_service.set(wallet_handle, &format!("my_did_temp::{}", DidUtils::normalize(&my_did.did)), &my_did_json)?;
+
+        Ok((my_did.verkey, my_did.pk))
+    }
+
+    fn replace_keys_apply(&self,
+                          wallet_handle: i32,
+                          did: &str,
+                          cb: Box<Fn(Result<(), IndyError>) + Send>) {
+        cb(self._replace_keys_apply(wallet_handle, did));
+    }
+
+    fn _replace_keys_apply(&self,
+                           wallet_handle: i32,
+                           did: &str) -> Result<(), IndyError> {
+        self.wallet_service.get(wallet_handle, &format!("my_did::{}", DidUtils::normalize(did)))?;
+        let my_did_temp_json = self.wallet_service.get(wallet_handle, &format!("my_did_temp::{}", DidUtils::normalize(did)))?;
+
+        MyDid::from_json(&my_did_temp_json)
+            .map_err(map_err_trace!())
+            .map_err(|err|
+                CommonError::InvalidState(format!("Invalid temporary MyDid json: {}", err.description())))?;
+
+        self.wallet_service.set(wallet_handle, &format!("my_did::{}", DidUtils::normalize(did)), &my_did_temp_json)?;
+        self.wallet_service.remove(wallet_handle, &format!("my_did_temp::{}", DidUtils::normalize(did)))?;
+        Ok(())
+    }
+
+    fn _create_new_keys(&self,
+                        keys_info_json: &str,
+                        did: &str) -> Result<(MyDid, String), IndyError> {
         let keys_info: MyKyesInfo = MyKyesInfo::from_json(keys_info_json)
             .map_err(map_err_trace!())
             .map_err(|err|
@@ -195,6 +580,8 @@ impl SignusCommandExecutor {
             Some(did.to_string()),
             keys_info.seed,
             keys_info.crypto_type,
+            None,
+            None,
             None);
 
         let my_did = self.signus_service.create_my_did(&my_did_info)?;
@@ -205,9 +592,7 @@ impl SignusCommandExecutor {
                 CommonError::InvalidState(
                     format!("Can't serialize MyDid: {}", err.description())))?;
 
-        self.wallet_service.set(wallet_handle, &format!("my_did::{}", my_did.did), &my_did_json)?;
-
-        Ok((my_did.verkey, my_did.pk))
+        Ok((my_did, my_did_json))
     }
 
     fn store_their_did(&self,
@@ -233,7 +618,7 @@ impl SignusCommandExecutor {
                 CommonError::InvalidState(
                     format!("Can't serialize TheirDid: {}", err.description())))?;
 
-        self.wallet_service.set(wallet_handle, &format!("their_did::{}", their_did.did), &their_did_json)?;
+        self.wallet_service.set(wallet_handle, &format!("their_did::{}", DidUtils::normalize(&their_did.did)), &their_did_json)?;
         Ok(())
     }
 
@@ -249,7 +634,7 @@ impl SignusCommandExecutor {
              wallet_handle: i32,
              did: &str,
              msg: &[u8]) -> Result<Vec<u8>, IndyError> {
-        let my_did_json = self.wallet_service.get(wallet_handle, &format!("my_did::{}", did))?;
+        let my_did_json = self.wallet_service.get(wallet_handle, &format!("my_did::{}", DidUtils::normalize(did)))?;
         let my_did = MyDid::from_json(&my_did_json)
             .map_err(map_err_trace!())
             .map_err(|_| CommonError::InvalidState((format!("Invalid my did json"))))?;
@@ -302,7 +687,7 @@ impl SignusCommandExecutor {
             }
         };
 
-        match self.wallet_service.get_not_expired(wallet_handle, &format!("their_did::{}", did)) {
+        match self.wallet_service.get_not_expired(wallet_handle, &format!("their_did::{}", DidUtils::normalize(did))) {
             Ok(their_did_json) => {
                 let their_did = TheirDid::from_json(&their_did_json);
                 if their_did.is_err() {
@@ -367,7 +752,7 @@ impl SignusCommandExecutor {
                 CommonError::InvalidState(
                     format!("Can't serialize TheirDid: {}", err.description())))?;
 
-        self.wallet_service.set(wallet_handle, &format!("their_did::{}", their_did.did), &their_did_json)?;
+        self.wallet_service.set(wallet_handle, &format!("their_did::{}", DidUtils::normalize(&their_did.did)), &their_did_json)?;
 
         return Ok(their_did);
     }
@@ -430,7 +815,7 @@ impl SignusCommandExecutor {
         check_wallet_and_pool_handles_consistency!(self.wallet_service, self.pool_service,
                                                    wallet_handle, pool_handle, cb);
 
-        match self.wallet_service.get_not_expired(wallet_handle, &format!("their_did::{}", did)) {
+        match self.wallet_service.get_not_expired(wallet_handle, &format!("their_did::{}", DidUtils::normalize(did))) {
             Ok(their_did_json) => {
                 let their_did = TheirDid::from_json(&their_did_json);
                 if their_did.is_err() {
@@ -438,7 +823,7 @@ impl SignusCommandExecutor {
                 }
                 let their_did: TheirDid = their_did.unwrap();
 
-                let my_did_json = self.wallet_service.get(wallet_handle, &format!("my_did::{}", my_did));
+                let my_did_json = self.wallet_service.get(wallet_handle, &format!("my_did::{}", DidUtils::normalize(my_did)));
                 if my_did_json.is_err() {
                     return cb(Err(IndyError::WalletError(WalletError::NotFound(format!("My Did not found")))));
                 }
@@ -490,7 +875,7 @@ impl SignusCommandExecutor {
                             my_did: &str,
                             get_nym_response: &str,
                             msg: &[u8]) -> Result<(Vec<u8>, Vec<u8>), IndyError> {
-        let my_did_json = self.wallet_service.get(wallet_handle, &format!("my_did::{}", my_did))?;
+        let my_did_json = self.wallet_service.get(wallet_handle, &format!("my_did::{}", DidUtils::normalize(my_did)))?;
         let my_did = MyDid::from_json(&my_did_json)
             .map_err(map_err_trace!())
             .map_err(|_| CommonError::InvalidState((format!("Invalid my did json"))))?;
@@ -507,12 +892,12 @@ impl SignusCommandExecutor {
                 my_did: &str,
                 did: &str,
                 msg: &[u8]) -> Result<(Vec<u8>, Vec<u8>), IndyError> {
-        let my_did_json = self.wallet_service.get(wallet_handle, &format!("my_did::{}", my_did))?;
+        let my_did_json = self.wallet_service.get(wallet_handle, &format!("my_did::{}", DidUtils::normalize(my_did)))?;
         let my_did = MyDid::from_json(&my_did_json)
             .map_err(map_err_trace!())
             .map_err(|err| CommonError::InvalidState(err.to_string()))?;
 
-        let their_did_json = self.wallet_service.get(wallet_handle, &format!("their_did::{}", did))?;
+        let their_did_json = self.wallet_service.get(wallet_handle, &format!("their_did::{}", DidUtils::normalize(did)))?;
         let their_did = TheirDid::from_json(&their_did_json)
             .map_err(map_err_trace!())
             .map_err(|err| CommonError::InvalidState(err.to_string()))?;
@@ -538,12 +923,12 @@ impl SignusCommandExecutor {
                 did: &str,
                 encrypted_msg: &[u8],
                 nonce: &[u8]) -> Result<Vec<u8>, IndyError> {
-        let my_did_json = self.wallet_service.get(wallet_handle, &format!("my_did::{}", my_did))?;
+        let my_did_json = self.wallet_service.get(wallet_handle, &format!("my_did::{}", DidUtils::normalize(my_did)))?;
         let my_did = MyDid::from_json(&my_did_json)
             .map_err(map_err_trace!())
             .map_err(|err| CommonError::InvalidState(err.to_string()))?;
 
-        let their_did_json = self.wallet_service.get(wallet_handle, &format!("their_did::{}", did))?;
+        let their_did_json = self.wallet_service.get(wallet_handle, &format!("their_did::{}", DidUtils::normalize(did)))?;
         let their_did = TheirDid::from_json(&their_did_json)
             .map_err(map_err_trace!())
             .map_err(|err| CommonError::InvalidState(err.to_string()))?;
@@ -551,4 +936,750 @@ impl SignusCommandExecutor {
         self.signus_service.decrypt(&my_did, &their_did, encrypted_msg, nonce)
             .map_err(|err| IndyError::SignusError(err))
     }
+
+    fn set_did_metadata(&self,
+                        wallet_handle: i32,
+                        did: &str,
+                        metadata: &str,
+                        cb: Box<Fn(Result<(), IndyError>) + Send>) {
+        cb(self.wallet_service.set(wallet_handle, &format!("did_metadata::{}", DidUtils::normalize(did)), metadata)
+            .map_err(IndyError::WalletError));
+    }
+
+    fn get_did_metadata(&self,
+                        wallet_handle: i32,
+                        did: &str,
+                        cb: Box<Fn(Result<Option<String>, IndyError>) + Send>) {
+        let res = match self.wallet_service.get(wallet_handle, &format!("did_metadata::{}", DidUtils::normalize(did))) {
+            Ok(metadata) => Ok(Some(metadata)),
+            Err(WalletError::NotFound(_)) => Ok(None),
+            Err(err) => Err(IndyError::WalletError(err))
+        };
+        cb(res);
+    }
+
+    fn get_my_did_with_meta(&self,
+                            wallet_handle: i32,
+                            my_did: &str,
+                            cb: Box<Fn(Result<String, IndyError>) + Send>) {
+        cb(self._get_my_did_with_meta(wallet_handle, my_did));
+    }
+
+    fn _get_my_did_with_meta(&self,
+                             wallet_handle: i32,
+                             my_did: &str) -> Result<String, IndyError> {
+        let my_did_json = self.wallet_service.get(wallet_handle, &format!("my_did::{}", DidUtils::normalize(my_did)))?;
+
+        let my_did_with_meta = self._build_my_did_with_meta(wallet_handle, &my_did_json)?;
+
+        let my_did_with_meta_json = my_did_with_meta.to_json()
+            .map_err(map_err_trace!())
+            .map_err(|err|
+                CommonError::InvalidState(
+                    format!("Can't serialize MyDidWithMeta: {}", err.description())))?;
+
+        Ok(my_did_with_meta_json)
+    }
+
+    fn list_my_dids_with_meta(&self,
+                              wallet_handle: i32,
+                              cb: Box<Fn(Result<String, IndyError>) + Send>) {
+        cb(self._list_my_dids_with_meta(wallet_handle));
+    }
+
+    fn _list_my_dids_with_meta(&self,
+                               wallet_handle: i32) -> Result<String, IndyError> {
+        let my_dids = self.wallet_service.list(wallet_handle, "my_did::")?;
+
+        let mut my_dids_with_meta: Vec<MyDidWithMeta> = Vec::new();
+
+        for &(_, ref my_did_json) in my_dids.iter() {
+            my_dids_with_meta.push(self._build_my_did_with_meta(wallet_handle, my_did_json)?);
+        }
+
+        let my_dids_with_meta_json = serde_json::to_string(&my_dids_with_meta)
+            .map_err(map_err_trace!())
+            .map_err(|err|
+                CommonError::InvalidState(
+                    format!("Can't serialize list of MyDidWithMeta: {}", err.description())))?;
+
+        Ok(my_dids_with_meta_json)
+    }
+
+    fn _build_my_did_with_meta(&self,
+                               wallet_handle: i32,
+                               my_did_json: &str) -> Result<MyDidWithMeta, IndyError> {
+        let my_did = MyDid::from_json(my_did_json)
+            .map_err(map_err_trace!())
+            .map_err(|err|
+                CommonError::InvalidState(format!("Invalid MyDid json: {}", err.description())))?;
+
+        let metadata = match self.wallet_service.get(wallet_handle, &format!("did_metadata::{}", DidUtils::normalize(&my_did.did))) {
+            Ok(metadata) => Some(metadata),
+            Err(WalletError::NotFound(_)) => None,
+            Err(err) => return Err(IndyError::WalletError(err))
+        };
+
+        let temp_verkey = match self.wallet_service.get(wallet_handle, &format!("my_did_temp::{}", DidUtils::normalize(&my_did.did))) {
+            Ok(my_did_temp_json) => {
+                let my_did_temp = MyDid::from_json(&my_did_temp_json)
+                    .map_err(map_err_trace!())
+                    .map_err(|err|
+                        CommonError::InvalidState(format!("Invalid temporary MyDid json: {}", err.description())))?;
+
+                Some(my_did_temp.verkey)
+            }
+            Err(WalletError::NotFound(_)) => None,
+            Err(err) => return Err(IndyError::WalletError(err))
+        };
+
+        Ok(MyDidWithMeta::new(my_did, metadata, temp_verkey))
+    }
+
+    fn export_master_seed(&self,
+                          wallet_handle: i32,
+                          cb: Box<Fn(Result<String, IndyError>) + Send>) {
+        cb(self.wallet_service.get(wallet_handle, "master_seed")
+            .map_err(IndyError::WalletError));
+    }
+
+    fn import_master_seed(&self,
+                          wallet_handle: i32,
+                          master_seed: &str,
+                          cb: Box<Fn(Result<(), IndyError>) + Send>) {
+        cb(Base58::decode(master_seed)
+            .map_err(IndyError::CommonError)
+            .and_then(|master_seed| self._import_master_seed(wallet_handle, &master_seed)));
+    }
+
+    fn export_master_seed_mnemonic(&self,
+                                   wallet_handle: i32,
+                                   cb: Box<Fn(Result<String, IndyError>) + Send>) {
+        cb(self.wallet_service.get(wallet_handle, "master_seed")
+            .map_err(IndyError::WalletError)
+            .and_then(|master_seed| Ok(Mnemonic::encode(&Base58::decode(&master_seed)?)?)));
+    }
+
+    fn import_master_seed_mnemonic(&self,
+                                   wallet_handle: i32,
+                                   mnemonic: &str,
+                                   cb: Box<Fn(Result<(), IndyError>) + Send>) {
+        cb(Mnemonic::decode(mnemonic)
+            .map_err(IndyError::CommonError)
+            .and_then(|master_seed| self._import_master_seed(wallet_handle, &master_seed)));
+    }
+
+    fn _import_master_seed(&self,
+                           wallet_handle: i32,
+                           master_seed: &[u8]) -> Result<(), IndyError> {
+        self.signus_service.validate_master_seed(master_seed)?;
+
+        match self.wallet_service.get(wallet_handle, "master_seed") {
+            // Replacing master seed would make all derived DIDs unrecoverable
+            Ok(stored_master_seed) => {
+                if Base58::decode(&stored_master_seed)? != master_seed {
+                    return Err(IndyError::CommonError(
+                        CommonError::InvalidState(format!("Wallet already contains another master seed"))));
+                }
+                Ok(())
+            }
+            Err(WalletError::NotFound(_)) => {
+                self.wallet_service.set(wallet_handle, "master_seed", &Base58::encode(master_seed))?;
+                Ok(())
+            }
+            Err(err) => Err(IndyError::WalletError(err))
+        }
+    }
+
+    fn set_default_did_method(&self,
+                              method_name: Option<&str>,
+                              cb: Box<Fn(Result<(), IndyError>) + Send>) {
+        cb(self.signus_service.set_default_method_name(method_name)
+            .map_err(|err| IndyError::SignusError(err)));
+    }
+
+    fn resolve_did(&self,
+                   wallet_handle: i32,
+                   pool_handle: i32,
+                   did: &str,
+                   cb: Box<Fn(Result<String, IndyError>) + Send>) {
+        check_wallet_and_pool_handles_consistency!(self.wallet_service, self.pool_service,
+                                                   wallet_handle, pool_handle, cb);
+
+        match self.wallet_service.get_not_expired(wallet_handle, &format!("did_doc::{}", DidUtils::normalize(did))) {
+            Ok(did_doc_source_json) => cb(self._build_did_doc_from_cache(did, &did_doc_source_json)),
+            Err(WalletError::NotFound(_)) => self._resolve_did_from_ledger(wallet_handle, pool_handle, did, cb),
+            Err(err) => cb(Err(IndyError::WalletError(err)))
+        }
+    }
+
+    fn _resolve_did_from_ledger(&self,
+                                wallet_handle: i32,
+                                pool_handle: i32,
+                                did: &str,
+                                cb: Box<Fn(Result<String, IndyError>)>) {
+        let get_nym_request = match self.ledger_service.build_get_nym_request(did, did) {
+            Ok(get_nym_request) => get_nym_request,
+            Err(err) => return cb(Err(IndyError::CommonError(err)))
+        };
+
+        let cb_id: i32 = SequenceUtils::get_next_id();
+
+        match self.resolve_did_callbacks.try_borrow_mut() {
+            Ok(mut resolve_did_callbacks) => {
+                resolve_did_callbacks.insert(cb_id, cb);
+
+                let did = did.to_string();
+
+                CommandExecutor::instance()
+                    .send(Command::Ledger(LedgerCommand::SubmitRequest(
+                        pool_handle,
+                        get_nym_request,
+                        Box::new(move |result| {
+                            CommandExecutor::instance()
+                                .send(Command::Signus(SignusCommand::ResolveDidGetNymAck(
+                                    wallet_handle,
+                                    pool_handle,
+                                    did.clone(),
+                                    cb_id,
+                                    result
+                                ))).unwrap();
+                        })
+                    ))).unwrap();
+            }
+            Err(err) => cb(Err(IndyError::CommonError(CommonError::InvalidState(format!("{:?}", err)))))
+        }
+    }
+
+    fn resolve_did_get_nym_ack(&self,
+                               wallet_handle: i32,
+                               pool_handle: i32,
+                               did: &str,
+                               cb_id: i32,
+                               result: Result<String, IndyError>) {
+        let get_attrib_request = result
+            .and_then(|get_nym_response| -> Result<(GetNymResultData, String), IndyError> {
+                let nym_data = self.ledger_service.parse_get_nym_response(&get_nym_response)?;
+                let get_attrib_request = self.ledger_service.build_get_attrib_request(did, did, "endpoint")?;
+                Ok((nym_data, get_attrib_request))
+            });
+
+        match get_attrib_request {
+            Ok((nym_data, get_attrib_request)) => {
+                let did = did.to_string();
+
+                CommandExecutor::instance()
+                    .send(Command::Ledger(LedgerCommand::SubmitRequest(
+                        pool_handle,
+                        get_attrib_request,
+                        Box::new(move |result| {
+                            CommandExecutor::instance()
+                                .send(Command::Signus(SignusCommand::ResolveDidGetAttribAck(
+                                    wallet_handle,
+                                    did.clone(),
+                                    nym_data.clone(),
+                                    cb_id,
+                                    result
+                                ))).unwrap();
+                        })
+                    ))).unwrap();
+            }
+            Err(err) => self._resolve_did_complete(cb_id, Err(err))
+        }
+    }
+
+    fn resolve_did_get_attrib_ack(&self,
+                                  wallet_handle: i32,
+                                  did: &str,
+                                  nym_data: &GetNymResultData,
+                                  cb_id: i32,
+                                  result: Result<String, IndyError>) {
+        let result = result
+            .and_then(|get_attrib_response| self._resolve_did_get_attrib_ack(wallet_handle, did, nym_data, &get_attrib_response));
+
+        self._resolve_did_complete(cb_id, result);
+    }
+
+    fn _resolve_did_get_attrib_ack(&self,
+                                   wallet_handle: i32,
+                                   did: &str,
+                                   nym_data: &GetNymResultData,
+                                   get_attrib_response: &str) -> Result<String, IndyError> {
+        let endpoint = self.ledger_service.parse_get_attrib_endpoint(get_attrib_response)?;
+
+        // Ledger data is cached instead of the document, so each DID form gets a document with its own id
+        let did_doc_source = DidDocSource::new(nym_data.clone(), endpoint);
+
+        let did_doc_source_json = did_doc_source.to_json()
+            .map_err(map_err_trace!())
+            .map_err(|err|
+                CommonError::InvalidState(
+                    format!("Can't serialize DidDocSource: {}", err.description())))?;
+
+        self.wallet_service.set(wallet_handle, &format!("did_doc::{}", DidUtils::normalize(did)), &did_doc_source_json)?;
+
+        self._build_did_doc_json(did, did_doc_source)
+    }
+
+    fn _build_did_doc_from_cache(&self, did: &str, did_doc_source_json: &str) -> Result<String, IndyError> {
+        let did_doc_source = DidDocSource::from_json(did_doc_source_json)
+            .map_err(map_err_trace!())
+            .map_err(|err|
+                CommonError::InvalidState(
+                    format!("Invalid cached DidDocSource: {}", err.description())))?;
+
+        self._build_did_doc_json(did, did_doc_source)
+    }
+
+    fn _build_did_doc_json(&self, did: &str, did_doc_source: DidDocSource) -> Result<String, IndyError> {
+        let method_name = self.signus_service.default_method_name();
+        let did_doc = self.ledger_service.build_did_doc(did, method_name.as_ref().map(String::as_str),
+                                                        &did_doc_source.nym_data, did_doc_source.endpoint)?;
+
+        let did_doc_json = did_doc.to_json()
+            .map_err(map_err_trace!())
+            .map_err(|err|
+                CommonError::InvalidState(
+                    format!("Can't serialize DidDoc: {}", err.description())))?;
+
+        Ok(did_doc_json)
+    }
+
+    fn set_endpoint_for_did(&self,
+                            wallet_handle: i32,
+                            did: &str,
+                            address: &str,
+                            transport_key: &str,
+                            cb: Box<Fn(Result<(), IndyError>) + Send>) {
+        cb(self._set_endpoint_for_did(wallet_handle, did, address, transport_key));
+    }
+
+    fn _set_endpoint_for_did(&self,
+                             wallet_handle: i32,
+                             did: &str,
+                             address: &str,
+                             transport_key: &str) -> Result<(), IndyError> {
+        Base58::decode(DidUtils::unqualify(did))?;
+        Base58::decode(transport_key)?;
+
+        let endpoint_json = Endpoint::new(address.to_string(), transport_key.to_string())
+            .to_json()
+            .map_err(map_err_trace!())
+            .map_err(|err|
+                CommonError::InvalidState(
+                    format!("Can't serialize Endpoint: {}", err.description())))?;
+
+        self.wallet_service.set(wallet_handle, &format!("endpoint::{}", DidUtils::normalize(did)), &endpoint_json)?;
+        Ok(())
+    }
+
+    fn get_endpoint_for_did(&self,
+                            wallet_handle: i32,
+                            pool_handle: i32,
+                            did: &str,
+                            cb: Box<Fn(Result<(String, String), IndyError>) + Send>) {
+        check_wallet_and_pool_handles_consistency!(self.wallet_service, self.pool_service,
+                                                   wallet_handle, pool_handle, cb);
+
+        match self.wallet_service.get_not_expired(wallet_handle, &format!("endpoint::{}", DidUtils::normalize(did))) {
+            Ok(endpoint_json) => {
+                let endpoint = Endpoint::from_json(&endpoint_json)
+                    .map_err(map_err_trace!())
+                    .map_err(|_| IndyError::CommonError(CommonError::InvalidState(format!("Invalid endpoint json"))));
+                cb(endpoint.and_then(SignusCommandExecutor::_endpoint_to_pair))
+            }
+            Err(WalletError::NotFound(_)) => self._get_endpoint_for_did_from_ledger(wallet_handle, pool_handle, did, cb),
+            Err(err) => cb(Err(IndyError::WalletError(err)))
+        }
+    }
+
+    fn _get_endpoint_for_did_from_ledger(&self,
+                                         wallet_handle: i32,
+                                         pool_handle: i32,
+                                         did: &str,
+                                         cb: Box<Fn(Result<(String, String), IndyError>)>) {
+        let get_attrib_request = match self.ledger_service.build_get_attrib_request(did, did, "endpoint") {
+            Ok(get_attrib_request) => get_attrib_request,
+            Err(err) => return cb(Err(IndyError::CommonError(err)))
+        };
+
+        let cb_id: i32 = SequenceUtils::get_next_id();
+
+        match self.get_endpoint_callbacks.try_borrow_mut() {
+            Ok(mut get_endpoint_callbacks) => {
+                get_endpoint_callbacks.insert(cb_id, cb);
+
+                let did = did.to_string();
+
+                CommandExecutor::instance()
+                    .send(Command::Ledger(LedgerCommand::SubmitRequest(
+                        pool_handle,
+                        get_attrib_request,
+                        Box::new(move |result| {
+                            CommandExecutor::instance()
+                                .send(Command::Signus(SignusCommand::GetEndpointForDidGetAttribAck(
+                                    wallet_handle,
+                                    did.clone(),
+                                    cb_id,
+                                    result
+                                ))).unwrap();
+                        })
+                    ))).unwrap();
+            }
+            Err(err) => cb(Err(IndyError::CommonError(CommonError::InvalidState(format!("{:?}", err)))))
+        }
+    }
+
+    fn get_endpoint_for_did_get_attrib_ack(&self,
+                                           wallet_handle: i32,
+                                           did: &str,
+                                           cb_id: i32,
+                                           result: Result<String, IndyError>) {
+        match self.get_endpoint_callbacks.try_borrow_mut() {
+            Ok(mut cbs) => {
+                let cb = cbs.remove(&cb_id);
+
+                if cb.is_none() {
+                    return error!("Can't process Signus::GetEndpointForDidGetAttribAck for handle {} - appropriate callback not found!", cb_id);
+                }
+                let cb = cb.unwrap();
+
+                match result {
+                    Ok(get_attrib_response) =>
+                        cb(self._get_endpoint_for_did_get_attrib_ack(wallet_handle, did, &get_attrib_response)),
+                    Err(err) => cb(Err(err))
+                }
+            }
+            Err(err) => error!("{:?}", err)
+        }
+    }
+
+    fn _get_endpoint_for_did_get_attrib_ack(&self,
+                                            wallet_handle: i32,
+                                            did: &str,
+                                            get_attrib_response: &str) -> Result<(String, String), IndyError> {
+        let endpoint = self.ledger_service.parse_get_attrib_endpoint(get_attrib_response)?
+            .ok_or(LedgerError::NotFound(format!("Endpoint for did {} not found on the ledger", did)))?;
+
+        let endpoint_json = endpoint.to_json()
+            .map_err(map_err_trace!())
+            .map_err(|err|
+                CommonError::InvalidState(
+                    format!("Can't serialize Endpoint: {}", err.description())))?;
+
+        self.wallet_service.set(wallet_handle, &format!("endpoint::{}", DidUtils::normalize(did)), &endpoint_json)?;
+
+        SignusCommandExecutor::_endpoint_to_pair(endpoint)
+    }
+
+    fn _endpoint_to_pair(endpoint: Endpoint) -> Result<(String, String), IndyError> {
+        match endpoint {
+            Endpoint { ha: Some(address), verkey: Some(transport_key) } => Ok((address, transport_key)),
+            _ => Err(IndyError::CommonError(CommonError::InvalidStructure(format!("Endpoint must contain both address and transport key"))))
+        }
+    }
+
+    fn publish_endpoint(&self,
+                        wallet_handle: i32,
+                        pool_handle: i32,
+                        submitter_did: &str,
+                        did: &str,
+                        address: &str,
+                        transport_key: &str,
+                        cb: Box<Fn(Result<String, IndyError>) + Send>) {
+        let attrib_request = match self.ledger_service.build_attrib_endpoint_request(submitter_did, did, address, transport_key) {
+            Ok(attrib_request) => attrib_request,
+            Err(err) => return cb(Err(IndyError::CommonError(err)))
+        };
+
+        CommandExecutor::instance()
+            .send(Command::Ledger(LedgerCommand::SignAndSubmitRequest(
+                pool_handle,
+                wallet_handle,
+                submitter_did.to_string(),
+                attrib_request,
+                cb
+            ))).unwrap();
+    }
+
+    fn _resolve_did_complete(&self, cb_id: i32, result: Result<String, IndyError>) {
+        match self.resolve_did_callbacks.try_borrow_mut() {
+            Ok(mut cbs) => {
+                let cb = cbs.remove(&cb_id);
+
+                if cb.is_none() {
+                    return error!("Can't process Signus::ResolveDid for handle {} - appropriate callback not found!", cb_id);
+                }
+
+                cb.unwrap()(result)
+            }
+            Err(err) => error!("{:?}", err)
+        }
+    }
+
+    fn _get_or_create_master_seed(&self, wallet_handle: i32) -> Result<Vec<u8>, IndyError> {
+        match self.wallet_service.get(wallet_handle, "master_seed") {
+            Ok(master_seed) => Ok(Base58::decode(&master_seed)?),
+            Err(WalletError::NotFound(_)) => {
+                let master_seed = self.signus_service.create_master_seed();
+                self.wallet_service.set(wallet_handle, "master_seed", &Base58::encode(&master_seed))?;
+                Ok(master_seed)
+            }
+            Err(err) => Err(IndyError::WalletError(err))
+        }
+    }
+
+    fn verify_signature_with_verkey(&self,
+                                    did: Option<String>,
+                                    verkey: String,
+                                    msg: Vec<u8>,
+                                    signature: Vec<u8>,
+                                    cb: Box<Fn(Result<bool, IndyError>) + Send>) {
+        let signus_service = self.signus_service.clone();
+
+        self._compute(move || signus_service
+            .verify_with_verkey(did.as_ref().map(String::as_str), &verkey, &msg, &signature)
+            .map_err(IndyError::SignusError), cb);
+    }
+
+    fn create_key(&self,
+                  wallet_handle: i32,
+                  key_info_json: &str,
+                  cb: Box<Fn(Result<String, IndyError>) + Send>) {
+        cb(self._create_key(wallet_handle, key_info_json));
+    }
+
+    fn _create_key(&self,
+                   wallet_handle: i32,
+                   key_info_json: &str) -> Result<String, IndyError> {
+        let key_info = MyKyesInfo::from_json(key_info_json)
+            .map_err(map_err_trace!())
+            .map_err(|err|
+                CommonError::InvalidStructure(format!("Invalid KeyInfo json: {}", err.description())))?;
+
+        let key = self.signus_service.create_key(&key_info)?;
+
+        let key_json = key.to_json()
+            .map_err(map_err_trace!())
+            .map_err(|err|
+                CommonError::InvalidState(
+                    format!("Can't serialize Key: {}", err.description())))?;
+
+        self.wallet_service.set(wallet_handle, &format!("key::{}", key.verkey), &key_json)?;
+        Ok(key.verkey)
+    }
+
+    fn crypto_sign(&self,
+                   wallet_handle: i32,
+                   my_vk: &str,
+                   msg: Vec<u8>,
+                   cb: Box<Fn(Result<Vec<u8>, IndyError>) + Send>) {
+        let key = match self._get_key(wallet_handle, my_vk) {
+            Ok(key) => key,
+            Err(err) => return cb(Err(err))
+        };
+
+        let signus_service = self.signus_service.clone();
+
+        self._compute(move || signus_service.crypto_sign(&key, &msg).map_err(IndyError::SignusError), cb);
+    }
+
+    fn crypto_verify(&self,
+                     their_vk: String,
+                     msg: Vec<u8>,
+                     signature: Vec<u8>,
+                     cb: Box<Fn(Result<bool, IndyError>) + Send>) {
+        let signus_service = self.signus_service.clone();
+
+        self._compute(move || signus_service.crypto_verify(&their_vk, &msg, &signature).map_err(IndyError::SignusError), cb);
+    }
+
+    fn crypto_box(&self,
+                  wallet_handle: i32,
+                  my_vk: &str,
+                  their_vk: String,
+                  msg: Vec<u8>,
+                  cb: Box<Fn(Result<(Vec<u8>, Vec<u8>), IndyError>) + Send>) {
+        let key = match self._get_key(wallet_handle, my_vk) {
+            Ok(key) => key,
+            Err(err) => return cb(Err(err))
+        };
+
+        let signus_service = self.signus_service.clone();
+
+        self._compute(move || signus_service.crypto_box(&key, &their_vk, &msg).map_err(IndyError::SignusError), cb);
+    }
+
+    fn crypto_box_open(&self,
+                       wallet_handle: i32,
+                       my_vk: &str,
+                       their_vk: String,
+                       encrypted_msg: Vec<u8>,
+                       nonce: Vec<u8>,
+                       cb: Box<Fn(Result<Vec<u8>, IndyError>) + Send>) {
+        let key = match self._get_key(wallet_handle, my_vk) {
+            Ok(key) => key,
+            Err(err) => return cb(Err(err))
+        };
+
+        let signus_service = self.signus_service.clone();
+
+        self._compute(move || signus_service.crypto_box_open(&key, &their_vk, &encrypted_msg, &nonce).map_err(IndyError::SignusError), cb);
+    }
+
+    fn anon_crypt(&self,
+                  their_vk: String,
+                  msg: Vec<u8>,
+                  cb: Box<Fn(Result<Vec<u8>, IndyError>) + Send>) {
+        let signus_service = self.signus_service.clone();
+
+        self._compute(move || signus_service.encrypt_sealed(&their_vk, &msg).map_err(IndyError::SignusError), cb);
+    }
+
+    fn anon_decrypt(&self,
+                    wallet_handle: i32,
+                    my_vk: &str,
+                    encrypted_msg: Vec<u8>,
+                    cb: Box<Fn(Result<Vec<u8>, IndyError>) + Send>) {
+        let key = match self._get_key(wallet_handle, my_vk) {
+            Ok(key) => key,
+            Err(err) => return cb(Err(err))
+        };
+
+        let signus_service = self.signus_service.clone();
+
+        self._compute(move || signus_service.decrypt_sealed(&key, &encrypted_msg).map_err(IndyError::SignusError), cb);
+    }
+
+    fn pack_message(&self,
+                    wallet_handle: i32,
+                    msg: Vec<u8>,
+                    recipient_vks_json: &str,
+                    sender_vk: Option<&str>,
+                    cb: Box<Fn(Result<String, IndyError>) + Send>) {
+        let (recipient_vks, sender_key) = match self._prepare_pack_message(wallet_handle, recipient_vks_json, sender_vk) {
+            Ok(res) => res,
+            Err(err) => return cb(Err(err))
+        };
+
+        let signus_service = self.signus_service.clone();
+
+        self._compute(move || SignusCommandExecutor::_pack_message(&signus_service, &msg, &recipient_vks, sender_key.as_ref()), cb);
+    }
+
+    fn _prepare_pack_message(&self,
+                             wallet_handle: i32,
+                             recipient_vks_json: &str,
+                             sender_vk: Option<&str>) -> Result<(Vec<String>, Option<Key>), IndyError> {
+        let recipient_vks: Vec<String> = serde_json::from_str(recipient_vks_json)
+            .map_err(map_err_trace!())
+            .map_err(|err|
+                CommonError::InvalidStructure(format!("Invalid recipient verkeys json: {}", err.description())))?;
+
+        let sender_key = match sender_vk {
+            Some(sender_vk) => Some(self._get_key(wallet_handle, sender_vk)?),
+            None => None
+        };
+
+        Ok((recipient_vks, sender_key))
+    }
+
+    fn _pack_message(signus_service: &SignusService,
+                     msg: &[u8],
+                     recipient_vks: &[String],
+                     sender_key: Option<&Key>) -> Result<String, IndyError> {
+        let packed_msg = signus_service.pack_message(msg, recipient_vks, sender_key)?;
+
+        let packed_msg_json = packed_msg.to_json()
+            .map_err(map_err_trace!())
+            .map_err(|err|
+                CommonError::InvalidState(format!("Can't serialize packed message: {}", err.description())))?;
+        Ok(packed_msg_json)
+    }
+
+    fn unpack_message(&self,
+                      wallet_handle: i32,
+                      packed_msg_json: &str,
+                      cb: Box<Fn(Result<(Vec<u8>, Option<String>, String), IndyError>) + Send>) {
+        let (packed_msg, key) = match self._prepare_unpack_message(wallet_handle, packed_msg_json) {
+            Ok(res) => res,
+            Err(err) => return cb(Err(err))
+        };
+
+        let signus_service = self.signus_service.clone();
+
+        self._compute(move || {
+            let (msg, sender_vk) = signus_service.unpack_message(&packed_msg, &key)?;
+            Ok((msg, sender_vk, key.verkey))
+        }, cb);
+    }
+
+    fn _prepare_unpack_message(&self,
+                               wallet_handle: i32,
+                               packed_msg_json: &str) -> Result<(PackedMessage, Key), IndyError> {
+        let packed_msg = PackedMessage::from_json(packed_msg_json)
+            .map_err(map_err_trace!())
+            .map_err(|err|
+                CommonError::InvalidStructure(format!("Invalid packed message json: {}", err.description())))?;
+
+        let mut recipient_key = None;
+
+        for recipient in &packed_msg.recipients {
+            match self._get_key(wallet_handle, &recipient.verkey) {
+                Ok(key) => {
+                    recipient_key = Some(key);
+                    break;
+                }
+                Err(IndyError::WalletError(WalletError::NotFound(_))) => continue,
+                Err(err) => return Err(err)
+            }
+        }
+
+        match recipient_key {
+            Some(key) => Ok((packed_msg, key)),
+            None => Err(IndyError::WalletError(
+                WalletError::NotFound(format!("No key in wallet for any recipient of packed message"))))
+        }
+    }
+
+    // Crypto on already loaded keys doesn't need wallet, so it is computed and returned from worker thread
+    fn _compute<T, F>(&self, job: F, cb: Box<Fn(Result<T, IndyError>) + Send>)
+        where T: 'static, F: FnOnce() -> Result<T, IndyError> + Send + 'static {
+        self.worker_pool.execute_with_cb(job, move |res| {
+            cb(res.map_err(IndyError::CommonError).and_then(|res| res))
+        });
+    }
+
+    fn _get_key(&self, wallet_handle: i32, verkey: &str) -> Result<Key, IndyError> {
+        let key_json = match self.wallet_service.get(wallet_handle, &format!("key::{}", verkey)) {
+            Ok(key_json) => key_json,
+            // Keys of DIDs created by indy_create_and_store_my_did are stored only in my_did records
+            Err(WalletError::NotFound(_)) => return self._get_my_did_key(wallet_handle, verkey),
+            Err(err) => return Err(IndyError::WalletError(err))
+        };
+
+        let key = Key::from_json(&key_json)
+            .map_err(map_err_trace!())
+            .map_err(|err|
+                CommonError::InvalidState(format!("Invalid Key json: {}", err.description())))?;
+
+        Ok(key)
+    }
+
+    fn _get_my_did_key(&self, wallet_handle: i32, verkey: &str) -> Result<Key, IndyError> {
+        for (_, my_did_json) in self.wallet_service.list(wallet_handle, "my_did::")? {
+            let my_did = MyDid::from_json(&my_did_json)
+                .map_err(map_err_trace!())
+                .map_err(|err|
+                    CommonError::InvalidState(format!("Invalid MyDid json: {}", err.description())))?;
+
+            let key = self.signus_service.key_from_my_did(&my_did);
+
+            if key.verkey == verkey {
+                return Ok(key);
+            }
+        }
+
+        Err(IndyError::WalletError(WalletError::NotFound(format!("key::{}", verkey))))
+    }
 }
\ No newline at end of file
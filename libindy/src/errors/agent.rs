@@ -0,0 +1,88 @@
+use std::error;
+use std::fmt;
+
+use errors::common::CommonError;
+
+use api::ErrorCode;
+use errors::ToErrorCode;
+
+#[derive(Debug)]
+pub enum AgentError {
+    ConnectionLost(String),
+    OutboundQueueFull(String),
+    Timeout(String),
+    MessageTooLarge(String),
+    CommonError(CommonError)
+}
+
+impl fmt::Display for AgentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AgentError::ConnectionLost(ref description) => write!(f, "Connection lost: {}", description),
+            AgentError::OutboundQueueFull(ref description) => write!(f, "Outbound queue is full: {}", description),
+            AgentError::Timeout(ref description) => write!(f, "Timeout: {}", description),
+            AgentError::MessageTooLarge(ref description) => write!(f, "Message too large: {}", description),
+            AgentError::CommonError(ref err) => err.fmt(f)
+        }
+    }
+}
+
+impl error::Error for AgentError {
+    fn description(&self) -> &str {
+        match *self {
+            AgentError::ConnectionLost(ref description) => description,
+            AgentError::OutboundQueueFull(ref description) => description,
+            AgentError::Timeout(ref description) => description,
+            AgentError::MessageTooLarge(ref description) => description,
+            AgentError::CommonError(ref err) => err.description()
+        }
+    }
+
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            AgentError::ConnectionLost(ref description) => None,
+            AgentError::OutboundQueueFull(ref description) => None,
+            AgentError::Timeout(ref description) => None,
+            AgentError::MessageTooLarge(ref description) => None,
+            AgentError::CommonError(ref err) => Some(err)
+        }
+    }
+}
+
+impl ToErrorCode for AgentError {
+    fn to_error_code(&self) -> ErrorCode {
+        match *self {
+            AgentError::ConnectionLost(ref description) => ErrorCode::AgentConnectionLostError,
+            AgentError::OutboundQueueFull(ref description) => ErrorCode::AgentOutboundQueueFullError,
+            AgentError::Timeout(ref description) => ErrorCode::AgentTimeoutError,
+            AgentError::MessageTooLarge(ref description) => ErrorCode::AgentMessageTooLargeError,
+            AgentError::CommonError(ref err) => err.to_error_code()
+        }
+    }
+}
+
+impl From<CommonError> for AgentError {
+    fn from(err: CommonError) -> AgentError {
+        AgentError::CommonError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn agent_error_to_error_code_works() {
+        assert_eq!(ErrorCode::AgentConnectionLostError, AgentError::ConnectionLost("test".to_string()).to_error_code());
+        assert_eq!(ErrorCode::AgentOutboundQueueFullError, AgentError::OutboundQueueFull("test".to_string()).to_error_code());
+        assert_eq!(ErrorCode::AgentTimeoutError, AgentError::Timeout("test".to_string()).to_error_code());
+        assert_eq!(ErrorCode::AgentMessageTooLargeError, AgentError::MessageTooLarge("test".to_string()).to_error_code());
+        assert_eq!(ErrorCode::CommonInvalidState, AgentError::CommonError(CommonError::InvalidState("test".to_string())).to_error_code());
+    }
+
+    #[test]
+    fn agent_error_display_works() {
+        assert_eq!("Timeout: test", format!("{}", AgentError::Timeout("test".to_string())));
+        assert_eq!("Outbound queue is full: test", format!("{}", AgentError::OutboundQueueFull("test".to_string())));
+    }
+}
@@ -1,4 +1,6 @@
 extern crate zmq_pw as zmq;
+#[cfg(any(feature = "bn_openssl", feature = "hash_openssl", feature = "http_tls"))]
+extern crate openssl;
 
 use std::cell::{BorrowError, BorrowMutError};
 use std::error::Error;
@@ -103,6 +105,14 @@ impl From<zmq::Error> for CommonError {
     }
 }
 
+#[cfg(any(feature = "bn_openssl", feature = "hash_openssl", feature = "http_tls"))]
+impl From<openssl::error::ErrorStack> for CommonError {
+    fn from(err: openssl::error::ErrorStack) -> CommonError {
+        // TODO: FIXME: Analyze ErrorStack and split invalid structure errors from other errors
+        CommonError::InvalidStructure(err.description().to_string())
+    }
+}
+
 impl From<BorrowError> for CommonError {
     fn from(err: BorrowError) -> Self {
         CommonError::InvalidState(err.description().to_string())
@@ -1,3 +1,4 @@
+use errors::agent::AgentError;
 use errors::anoncreds::AnoncredsError;
 use errors::common::CommonError;
 use errors::ledger::LedgerError;
@@ -13,6 +14,7 @@ use std::fmt;
 
 #[derive(Debug)]
 pub enum IndyError {
+    AgentError(AgentError),
     AnoncredsError(AnoncredsError),
     CommonError(CommonError),
     LedgerError(LedgerError),
@@ -24,6 +26,7 @@ pub enum IndyError {
 impl fmt::Display for IndyError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
+            IndyError::AgentError(ref err) => err.fmt(f),
             IndyError::AnoncredsError(ref err) => err.fmt(f),
             IndyError::CommonError(ref err) => err.fmt(f),
             IndyError::LedgerError(ref err) => err.fmt(f),
@@ -37,6 +40,7 @@ impl fmt::Display for IndyError {
 impl error::Error for IndyError {
     fn description(&self) -> &str {
         match *self {
+            IndyError::AgentError(ref err) => err.description(),
             IndyError::AnoncredsError(ref err) => err.description(),
             IndyError::CommonError(ref err) => err.description(),
             IndyError::LedgerError(ref err) => err.description(),
@@ -48,6 +52,7 @@ impl error::Error for IndyError {
 
     fn cause(&self) -> Option<&error::Error> {
         match *self {
+            IndyError::AgentError(ref err) => Some(err),
             IndyError::AnoncredsError(ref err) => Some(err),
             IndyError::CommonError(ref err) => Some(err),
             IndyError::LedgerError(ref err) => Some(err),
@@ -62,6 +67,7 @@ impl ToErrorCode for IndyError {
     fn to_error_code(&self) -> ErrorCode {
         error!("Casting error to ErrorCode: {}", self);
         match *self {
+            IndyError::AgentError(ref err) => err.to_error_code(),
             IndyError::AnoncredsError(ref err) => err.to_error_code(),
             IndyError::CommonError(ref err) => err.to_error_code(),
             IndyError::LedgerError(ref err) => err.to_error_code(),
@@ -72,6 +78,12 @@ impl ToErrorCode for IndyError {
     }
 }
 
+impl From<AgentError> for IndyError {
+    fn from(err: AgentError) -> IndyError {
+        IndyError::AgentError(err)
+    }
+}
+
 impl From<AnoncredsError> for IndyError {
     fn from(err: AnoncredsError) -> IndyError {
         IndyError::AnoncredsError(err)
@@ -1,3 +1,4 @@
+pub mod agent;
 pub mod anoncreds;
 pub mod common;
 pub mod ledger;
@@ -12,6 +12,8 @@ use errors::ToErrorCode;
 #[derive(Debug)]
 pub enum SignusError {
     UnknownCryptoError(String),
+    CryptoTypeAlreadyRegistered(String),
+    PluggedCryptoError(ErrorCode),
     CommonError(CommonError)
 }
 
@@ -19,6 +21,8 @@ impl fmt::Display for SignusError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             SignusError::UnknownCryptoError(ref description) => write!(f, "Unknown crypto: {}", description),
+            SignusError::CryptoTypeAlreadyRegistered(ref description) => write!(f, "Crypto type already registered: {}", description),
+            SignusError::PluggedCryptoError(err_code) => write!(f, "Plugged crypto error: {}", err_code as i32),
             SignusError::CommonError(ref err) => err.fmt(f)
         }
     }
@@ -28,6 +32,8 @@ impl error::Error for SignusError {
     fn description(&self) -> &str {
         match *self {
             SignusError::UnknownCryptoError(ref description) => description,
+            SignusError::CryptoTypeAlreadyRegistered(ref description) => description,
+            SignusError::PluggedCryptoError(ref err_code) => "Plugged crypto error",
             SignusError::CommonError(ref err) => err.description()
         }
     }
@@ -35,6 +41,8 @@ impl error::Error for SignusError {
     fn cause(&self) -> Option<&error::Error> {
         match *self {
             SignusError::UnknownCryptoError(ref description) => None,
+            SignusError::CryptoTypeAlreadyRegistered(ref description) => None,
+            SignusError::PluggedCryptoError(ref err_code) => None,
             SignusError::CommonError(ref err) => Some(err)
         }
     }
@@ -44,6 +52,8 @@ impl ToErrorCode for SignusError {
     fn to_error_code(&self) -> ErrorCode {
         match *self {
             SignusError::UnknownCryptoError(ref description) => ErrorCode::SignusUnknownCryptoError,
+            SignusError::CryptoTypeAlreadyRegistered(ref description) => ErrorCode::SignusCryptoTypeAlreadyRegisteredError,
+            SignusError::PluggedCryptoError(err_code) => err_code,
             SignusError::CommonError(ref err) => err.to_error_code()
         }
     }
@@ -0,0 +1,862 @@
+#[cfg(feature = "http_tls")]
+extern crate openssl;
+extern crate serde_json;
+extern crate zmq_pw as zmq;
+
+#[cfg(feature = "http_tls")]
+use self::openssl::ssl::{SslConnectorBuilder, SslMethod};
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use errors::common::CommonError;
+use utils::crypto::base58::Base58;
+use utils::crypto::base64::Base64;
+use utils::crypto::ed25519::ED25519;
+use utils::json::{JsonDecodable, JsonEncodable};
+use utils::sequence::SequenceUtils;
+
+use super::{MsgDID, MsgTooLarge, DID, _create_zmq_socket_pair, _protocol_frame, _unix_timestamp};
+use super::transport::{InboundTransport, OutboundTransport};
+
+const HTTP_POLL_INTERVAL_MS: i64 = 500;
+const HTTP_LISTENER_POLL_INTERVAL_MS: i64 = 50;
+const HTTP_IO_TIMEOUT_SEC: u64 = 10;
+const MAX_HTTP_BODY_LEN: usize = 10 * 1024 * 1024;
+const MAX_HTTP_READERS: usize = 64;
+const MAX_HTTP_OUTBOX_LEN: usize = 128;
+const HTTP_ENVELOPE_TTL_SEC: u64 = 60;
+const TIMESTAMP_LEN: usize = 8;
+const BOX_MAC_LEN: usize = 16;
+const HTTP_ENVELOPE_OVERHEAD: usize = 512;
+
+const FRAME_MSG: &'static str = "MSG";
+const FRAME_ERR: &'static str = "ERR";
+const FRAME_EXIT: &'static str = "EXIT";
+
+/// Unit of data exchanged over HTTP transport.
+/// Message is encrypted by libindy with curve25519 keys of both sides,
+/// so confidentiality doesn't depend on TLS.
+/// Encrypted data starts with sending time (big-endian unix seconds), listener rejects
+/// stale envelopes and envelopes with already seen nonce.
+/// Keys are base58 encoded, nonce and message are base64url encoded to keep large messages cheap.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct HttpEnvelope {
+    sender_pk: String,
+    recipient_pk: String,
+    nonce: String,
+    msg: String,
+}
+
+impl JsonEncodable for HttpEnvelope {}
+
+impl<'a> JsonDecodable<'a> for HttpEnvelope {}
+
+impl HttpEnvelope {
+    fn seal(sender_pk: &[u8], sender_sk: &[u8], recipient_pk: &[u8], msg: &[u8]) -> Result<HttpEnvelope, CommonError> {
+        HttpEnvelope::_seal(sender_pk, sender_sk, recipient_pk, msg, _unix_timestamp())
+    }
+
+    fn _seal(sender_pk: &[u8], sender_sk: &[u8], recipient_pk: &[u8], msg: &[u8], timestamp: u64) -> Result<HttpEnvelope, CommonError> {
+        let mut data: Vec<u8> = (0..TIMESTAMP_LEN).rev().map(|i| (timestamp >> (8 * i)) as u8).collect();
+        data.extend_from_slice(msg);
+
+        let nonce = ED25519::gen_nonce();
+        let encrypted = ED25519::encrypt(sender_sk, recipient_pk, &data, &nonce)?;
+        Ok(HttpEnvelope {
+            sender_pk: Base58::encode(sender_pk),
+            recipient_pk: Base58::encode(recipient_pk),
+            nonce: Base64::encode(&nonce),
+            msg: Base64::encode(&encrypted),
+        })
+    }
+
+    /// Returns sending time and message.
+    fn open(&self, recipient_sk: &[u8]) -> Result<(u64, Vec<u8>), CommonError> {
+        let data = ED25519::decrypt(recipient_sk,
+                                    &Base58::decode(&self.sender_pk)?,
+                                    &Base64::decode(&self.msg)?,
+                                    &Base64::decode(&self.nonce)?)?;
+
+        if data.len() < TIMESTAMP_LEN {
+            return Err(CommonError::InvalidStructure("Http envelope misses timestamp".to_string()));
+        }
+
+        let timestamp = data[..TIMESTAMP_LEN].iter().fold(0u64, |timestamp, &b| (timestamp << 8) | b as u64);
+        Ok((timestamp, data[TIMESTAMP_LEN..].to_vec()))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct HttpUrl {
+    tls: bool,
+    host: String,
+    port: u16,
+    path: String,
+}
+
+impl HttpUrl {
+    fn parse(url: &str) -> Result<HttpUrl, CommonError> {
+        let (tls, rest) = if url.starts_with("https://") {
+            (true, &url["https://".len()..])
+        } else if url.starts_with("http://") {
+            (false, &url["http://".len()..])
+        } else {
+            return Err(CommonError::InvalidStructure(format!("Invalid http url: {}", url)));
+        };
+
+        let (authority, path) = match rest.find('/') {
+            Some(pos) => (&rest[..pos], &rest[pos..]),
+            None => (rest, "/"),
+        };
+
+        let (host, port) = match authority.rfind(':') {
+            Some(pos) => (&authority[..pos],
+                          authority[pos + 1..].parse::<u16>()
+                              .map_err(|_| CommonError::InvalidStructure(format!("Invalid port in http url: {}", url)))?),
+            None => (authority, if tls { 443 } else { 80 }),
+        };
+
+        if host.is_empty() {
+            return Err(CommonError::InvalidStructure(format!("Missed host in http url: {}", url)));
+        }
+
+        Ok(HttpUrl {
+            tls: tls,
+            host: host.to_string(),
+            port: port,
+            path: path.to_string(),
+        })
+    }
+
+    fn address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+}
+
+/// Client side of HTTP transport.
+///
+/// Blocking HTTP exchange is performed by dedicated thread connected with AgentWorker by PAIR socket:
+/// [MSG, msg] or [EXIT] to the thread, [MSG, msg] or [ERR, description] from the thread.
+/// As HTTP is request-response only, the thread polls remote listener for queued messages
+/// with empty message once the first message was sent.
+pub struct HttpOutbound {
+    socket: zmq::Socket,
+    worker: Option<thread::JoinHandle<()>>,
+}
+
+impl HttpOutbound {
+    pub fn new(url: &str, public_key: Vec<u8>, secret_key: Vec<u8>, server_key: Vec<u8>) -> Result<HttpOutbound, CommonError> {
+        let url = HttpUrl::parse(url)?;
+        let (send_soc, recv_soc) = _create_zmq_socket_pair(
+            format!("http_outbound_{}", SequenceUtils::get_next_id()).as_str(), true)?;
+        let worker = thread::spawn(move || {
+            _run_outbound(recv_soc, url, public_key, secret_key, server_key)
+        });
+        Ok(HttpOutbound {
+            socket: send_soc,
+            worker: Some(worker),
+        })
+    }
+}
+
+impl Drop for HttpOutbound {
+    fn drop(&mut self) {
+        trace!("HttpOutbound drop >>");
+        if let Err(err) = self.socket.send(FRAME_EXIT, zmq::DONTWAIT) {
+            warn!("Can't stop http outbound thread {}", err);
+        }
+        if let Some(worker) = self.worker.take() {
+            worker.join().unwrap_or_else(|_| error!("Http transport thread panicked"));
+        }
+        trace!("HttpOutbound drop <<");
+    }
+}
+
+impl OutboundTransport for HttpOutbound {
+    fn connect(&self, sender_did: &str, receiver_did: &str) -> Result<(), CommonError> {
+        let msg = MsgDID {
+            did: DID {
+                sender_did: sender_did.to_string(),
+                receiver_did: receiver_did.to_string(),
+            }
+        };
+        let msg = msg.to_json()
+            .map_err(|err| CommonError::InvalidState(format!("Can't serialize connect message {}", err)))?;
+        self.send(msg.as_bytes())
+    }
+
+    fn send(&self, msg: &[u8]) -> Result<(), CommonError> {
+        self.socket.send_multipart(&[FRAME_MSG.as_bytes(), msg], zmq::DONTWAIT)?;
+        Ok(())
+    }
+
+    fn poll_item(&self) -> zmq::PollItem {
+        self.socket.as_poll_item(zmq::POLLIN)
+    }
+
+    fn recv(&self) -> Result<Option<Vec<u8>>, CommonError> {
+        let frames = self.socket.recv_multipart(zmq::DONTWAIT)?;
+        let (kind, data) = _parse_frames(&frames)?;
+        if kind == FRAME_ERR {
+            Err(CommonError::IOError(io::Error::new(io::ErrorKind::Other, String::from_utf8_lossy(&data).to_string())))
+        } else {
+            Ok(Some(data))
+        }
+    }
+}
+
+fn _run_outbound(socket: zmq::Socket, url: HttpUrl, public_key: Vec<u8>, secret_key: Vec<u8>, server_key: Vec<u8>) {
+    let mut polling = false;
+    loop {
+        let timeout = if polling { HTTP_POLL_INTERVAL_MS } else { -1 };
+        let has_input = match socket.poll(zmq::POLLIN, timeout) {
+            Ok(cnt) => cnt > 0,
+            Err(err) => {
+                error!("Http outbound thread poll failed {}", err);
+                break;
+            }
+        };
+
+        let msg = if has_input {
+            let frames = match socket.recv_multipart(zmq::DONTWAIT) {
+                Ok(frames) => frames,
+                Err(err) => {
+                    error!("Http outbound thread recv failed {}", err);
+                    break;
+                }
+            };
+            if frames.len() == 1 && frames[0] == FRAME_EXIT.as_bytes() {
+                break;
+            }
+            match _parse_frames(&frames) {
+                Ok((_, msg)) => msg,
+                Err(err) => {
+                    warn!("Http outbound thread got invalid input {}", err);
+                    continue;
+                }
+            }
+        } else {
+            Vec::new()
+        };
+
+        polling = true;
+
+        match _exchange(&url, &public_key, &secret_key, &server_key, msg.as_slice()) {
+            Ok(msgs) => {
+                for msg in msgs {
+                    if let Err(err) = socket.send_multipart(&[FRAME_MSG.as_bytes(), msg.as_slice()], zmq::DONTWAIT) {
+                        error!("Http outbound thread can't forward message {}", err);
+                    }
+                }
+            }
+            Err(err) if has_input => {
+                if let Err(err) = socket.send_multipart(&[FRAME_ERR.as_bytes(), format!("{}", err).as_bytes()], zmq::DONTWAIT) {
+                    error!("Http outbound thread can't forward error {}", err);
+                }
+            }
+            Err(err) => debug!("Http outbound poll of {} failed {}", url.address(), err),
+        }
+    }
+    trace!("Http outbound thread finished");
+}
+
+/// Sends sealed message and returns opened messages queued by remote listener.
+fn _exchange(url: &HttpUrl, public_key: &[u8], secret_key: &[u8], server_key: &[u8], msg: &[u8])
+             -> Result<Vec<Vec<u8>>, CommonError> {
+    let envelope = HttpEnvelope::seal(public_key, secret_key, server_key, msg)?;
+    let body = envelope.to_json()
+        .map_err(|err| CommonError::InvalidState(format!("Can't serialize http envelope {}", err)))?;
+
+    let (status, body) = if url.tls { _post_tls(url, body.as_str())? } else {
+        let stream = _connect(url)?;
+        _post(stream, url, body.as_str())?
+    };
+
+    if status != 200 {
+        return Err(CommonError::IOError(io::Error::new(io::ErrorKind::Other,
+                                                       format!("Agent http endpoint responded {}: {}", status, body))));
+    }
+
+    let envelopes: Vec<HttpEnvelope> = serde_json::from_str(body.as_str())
+        .map_err(|err| CommonError::InvalidStructure(format!("Invalid http agent response {}", err)))?;
+
+    let server_key = Base58::encode(server_key);
+    envelopes.iter()
+        .map(|envelope| {
+            if envelope.sender_pk != server_key {
+                return Err(CommonError::InvalidStructure("Http envelope from unexpected sender".to_string()));
+            }
+            envelope.open(secret_key).map(|(_, msg)| msg)
+        })
+        .collect()
+}
+
+fn _connect(url: &HttpUrl) -> Result<TcpStream, CommonError> {
+    let stream = TcpStream::connect(url.address().as_str()).map_err(CommonError::IOError)?;
+    stream.set_read_timeout(Some(Duration::from_secs(HTTP_IO_TIMEOUT_SEC))).map_err(CommonError::IOError)?;
+    stream.set_write_timeout(Some(Duration::from_secs(HTTP_IO_TIMEOUT_SEC))).map_err(CommonError::IOError)?;
+    Ok(stream)
+}
+
+#[cfg(feature = "http_tls")]
+fn _post_tls(url: &HttpUrl, body: &str) -> Result<(u16, String), CommonError> {
+    let connector = SslConnectorBuilder::new(SslMethod::tls())?.build();
+    let stream = connector.connect(url.host.as_str(), _connect(url)?)
+        .map_err(|err| CommonError::IOError(io::Error::new(io::ErrorKind::Other, format!("TLS handshake failed {:?}", err))))?;
+    _post(stream, url, body)
+}
+
+#[cfg(not(feature = "http_tls"))]
+fn _post_tls(_url: &HttpUrl, _body: &str) -> Result<(u16, String), CommonError> {
+    Err(CommonError::InvalidState("https agent endpoints require libindy built with http_tls feature".to_string()))
+}
+
+fn _post<S: Read + Write>(mut stream: S, url: &HttpUrl, body: &str) -> Result<(u16, String), CommonError> {
+    _write_request(&mut stream, url, body).map_err(CommonError::IOError)?;
+    let (start_line, body) = _read_message(&mut stream, MAX_HTTP_BODY_LEN)?;
+    let status = start_line.split(' ').nth(1)
+        .and_then(|status| status.parse::<u16>().ok())
+        .ok_or(CommonError::InvalidStructure(format!("Invalid http status line: {}", start_line)))?;
+    Ok((status, body))
+}
+
+fn _write_request<W: Write>(stream: &mut W, url: &HttpUrl, body: &str) -> io::Result<()> {
+    write!(stream, "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+           url.path, url.host, body.len(), body)?;
+    stream.flush()
+}
+
+fn _write_response<W: Write>(stream: &mut W, status: u16, body: &str) -> io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    };
+    write!(stream, "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+           status, reason, body.len(), body)?;
+    stream.flush()
+}
+
+/// Reads HTTP request or response. Returns start line and body.
+/// Only Content-Length framing is supported.
+fn _read_message<R: Read>(stream: R, max_body_len: usize) -> Result<(String, String), CommonError> {
+    let mut reader = BufReader::new(stream);
+    let (start_line, content_length) = _read_head(&mut reader)?;
+
+    if content_length > max_body_len {
+        return Err(CommonError::InvalidStructure(format!("Http body is too large: {}", content_length)));
+    }
+
+    let body = _read_body(&mut reader, content_length)?;
+    Ok((start_line, body))
+}
+
+/// Reads start line and headers. Returns start line and Content-Length.
+fn _read_head<R: BufRead>(reader: &mut R) -> Result<(String, usize), CommonError> {
+    let mut start_line = String::new();
+    reader.read_line(&mut start_line).map_err(CommonError::IOError)?;
+    let start_line = start_line.trim_right().to_string();
+
+    let mut content_length: usize = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).map_err(CommonError::IOError)? == 0 {
+            return Err(CommonError::InvalidStructure("Unexpected end of http headers".to_string()));
+        }
+        let header = header.trim_right();
+        if header.is_empty() {
+            break;
+        }
+        if let Some(pos) = header.find(':') {
+            if header[..pos].trim().to_lowercase() == "content-length" {
+                content_length = header[pos + 1..].trim().parse::<usize>()
+                    .map_err(|_| CommonError::InvalidStructure(format!("Invalid http header: {}", header)))?;
+            }
+        }
+    }
+
+    Ok((start_line, content_length))
+}
+
+fn _read_body<R: BufRead>(reader: &mut R, content_length: usize) -> Result<String, CommonError> {
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).map_err(CommonError::IOError)?;
+    String::from_utf8(body)
+        .map_err(|_| CommonError::InvalidStructure("Invalid http body (should be valid UTF-8 string)".to_string()))
+}
+
+/// Upper bound of request body carrying envelope with message of given size.
+/// Unpadded base64 expands encrypted data to ceil(4n/3) bytes.
+fn _max_request_body_len(max_msg_size: usize) -> usize {
+    let encrypted_len = max_msg_size.saturating_add(TIMESTAMP_LEN + BOX_MAC_LEN);
+    encrypted_len.saturating_add(encrypted_len / 3 + 1).saturating_add(HTTP_ENVELOPE_OVERHEAD)
+}
+
+fn _parse_frames(frames: &[Vec<u8>]) -> Result<(String, Vec<u8>), CommonError> {
+    if frames.len() != 2 {
+        return Err(CommonError::InvalidState(format!("Unexpected frames count {}", frames.len())));
+    }
+    let kind = String::from_utf8(frames[0].clone())
+        .map_err(|_| CommonError::IOError(io::Error::from(io::ErrorKind::InvalidData)))?;
+    Ok((kind, frames[1].clone()))
+}
+
+/// Listener side of HTTP transport.
+///
+/// Dedicated thread accepts POST requests and passes [request id, body] to AgentWorker,
+/// AgentWorker answers with [request id, status, body]. Each request carries sealed envelope
+/// for one of listener identities, the response carries messages queued for the client.
+/// Client identity is z85 encoded client public key as for ZeroMQ transport.
+/// Oversized message is answered with MsgTooLarge queued before other messages for the client,
+/// request with body which can't fit max_msg_size is refused with 413 before the body is read.
+/// Requests are read by short-living threads, at most MAX_HTTP_READERS at once.
+pub struct HttpInbound {
+    socket: zmq::Socket,
+    worker: Option<thread::JoinHandle<()>>,
+    identities: Vec<(Vec<u8> /* pk */, Vec<u8> /* sk */)>,
+    outbox: RefCell<HashMap<String /* identity */, Vec<Vec<u8>>>>,
+    seen_nonces: RefCell<HashMap<String /* nonce */, u64 /* timestamp */>>,
+    max_msg_size: usize,
+}
+
+impl HttpInbound {
+    pub fn new(url: &str, max_msg_size: usize) -> Result<HttpInbound, CommonError> {
+        let url = HttpUrl::parse(url)?;
+        if url.tls {
+            return Err(CommonError::InvalidStructure(
+                "https agent listener isn't supported, listen on http behind TLS-terminating proxy".to_string()));
+        }
+
+        let listener = TcpListener::bind(url.address().as_str()).map_err(CommonError::IOError)?;
+        listener.set_nonblocking(true).map_err(CommonError::IOError)?;
+
+        let (send_soc, recv_soc) = _create_zmq_socket_pair(
+            format!("http_inbound_{}", SequenceUtils::get_next_id()).as_str(), true)?;
+        let max_body_len = _max_request_body_len(max_msg_size);
+        let worker = thread::spawn(move || {
+            _run_inbound(recv_soc, listener, max_body_len)
+        });
+        Ok(HttpInbound {
+            socket: send_soc,
+            worker: Some(worker),
+            identities: Vec::new(),
+            outbox: RefCell::new(HashMap::new()),
+            seen_nonces: RefCell::new(HashMap::new()),
+            max_msg_size: max_msg_size,
+        })
+    }
+
+    fn _handle_request(&self, body: &str) -> Result<(Option<(String, Vec<u8>)>, String), CommonError> {
+        let envelope = HttpEnvelope::from_json(body)
+            .map_err(|err| CommonError::InvalidStructure(format!("Invalid http envelope {}", err)))?;
+        let recipient_pk = Base58::decode(&envelope.recipient_pk)?;
+        let sender_pk = Base58::decode(&envelope.sender_pk)?;
+
+        let &(ref pk, ref sk) = self.identities.iter()
+            .find(|&&(ref pk, _)| pk.eq(&recipient_pk))
+            .ok_or(CommonError::InvalidStructure("UNKNOWN_RECIPIENT".to_string()))?;
+
+        let (timestamp, mut msg) = envelope.open(sk)?;
+        self._check_replay(&envelope.nonce, timestamp)?;
+        let identity = zmq::z85_encode(&sender_pk)?;
+
+        let mut queued = self.outbox.borrow_mut().remove(&identity).unwrap_or(Vec::new());
+        if msg.len() > self.max_msg_size {
+            debug!("Reject too large message from {}: {} bytes", identity, msg.len());
+            let reject = _protocol_frame(&MsgTooLarge::new(msg.len(), self.max_msg_size))?;
+            queued.insert(0, reject);
+            msg.clear();
+        }
+
+        let mut response = Vec::new();
+        for queued_msg in queued {
+            response.push(HttpEnvelope::seal(pk, sk, &sender_pk, queued_msg.as_slice())?);
+        }
+        let response = serde_json::to_string(&response)
+            .map_err(|err| CommonError::InvalidState(format!("Can't serialize http response {}", err)))?;
+
+        let incoming = if msg.is_empty() { None } else { Some((identity, msg)) };
+        Ok((incoming, response))
+    }
+
+    fn _check_replay(&self, nonce: &str, timestamp: u64) -> Result<(), CommonError> {
+        let now = _unix_timestamp();
+        if timestamp + HTTP_ENVELOPE_TTL_SEC < now || timestamp > now + HTTP_ENVELOPE_TTL_SEC {
+            return Err(CommonError::InvalidStructure("STALE_ENVELOPE".to_string()));
+        }
+
+        // Nonces older than TTL are forgotten as such envelopes are rejected by timestamp
+        let mut seen_nonces = self.seen_nonces.borrow_mut();
+        seen_nonces.retain(|_, &mut seen_timestamp| seen_timestamp + HTTP_ENVELOPE_TTL_SEC >= now);
+
+        if seen_nonces.contains_key(nonce) {
+            return Err(CommonError::InvalidStructure("REPLAYED_ENVELOPE".to_string()));
+        }
+        seen_nonces.insert(nonce.to_string(), timestamp);
+        Ok(())
+    }
+}
+
+impl Drop for HttpInbound {
+    fn drop(&mut self) {
+        trace!("HttpInbound drop >>");
+        if let Err(err) = self.socket.send(FRAME_EXIT, zmq::DONTWAIT) {
+            warn!("Can't stop http inbound thread {}", err);
+        }
+        if let Some(worker) = self.worker.take() {
+            worker.join().unwrap_or_else(|_| error!("Http transport thread panicked"));
+        }
+        trace!("HttpInbound drop <<");
+    }
+}
+
+impl InboundTransport for HttpInbound {
+    fn add_identity(&mut self, pk: &[u8], sk: &[u8]) -> Result<(), CommonError> {
+        self.identities.push((pk.to_vec(), sk.to_vec()));
+        Ok(())
+    }
+
+    fn rm_identity(&mut self, pk: &[u8]) -> Result<(), CommonError> {
+        self.identities.retain(|&(ref id_pk, _)| id_pk.as_slice() != pk);
+        Ok(())
+    }
+
+    fn poll_item(&self) -> zmq::PollItem {
+        self.socket.as_poll_item(zmq::POLLIN)
+    }
+
+    fn recv(&self) -> Result<Option<(String, Vec<u8>)>, CommonError> {
+        let frames = self.socket.recv_multipart(zmq::DONTWAIT)?;
+        let (request_id, body) = _parse_frames(&frames)?;
+        let body = String::from_utf8(body)
+            .map_err(|_| CommonError::IOError(io::Error::from(io::ErrorKind::InvalidData)))?;
+
+        let (incoming, status, response) = match self._handle_request(body.as_str()) {
+            Ok((incoming, response)) => (incoming, "200", response),
+            Err(err) => {
+                debug!("Reject client incoming data {}", err);
+                (None, "400", format!("{}", err))
+            }
+        };
+        self.socket.send_multipart(&[request_id.as_bytes(), status.as_bytes(), response.as_bytes()], zmq::DONTWAIT)?;
+        Ok(incoming)
+    }
+
+    fn send(&self, identity: &str, msg: &[u8]) -> Result<(), CommonError> {
+        let mut outbox = self.outbox.borrow_mut();
+        let queue = outbox.entry(identity.to_string()).or_insert(Vec::new());
+        if queue.len() >= MAX_HTTP_OUTBOX_LEN {
+            return Err(CommonError::InvalidState(format!("Http outbox of client {} is full", identity)));
+        }
+        queue.push(msg.to_vec());
+        Ok(())
+    }
+}
+
+/// Returns start line and body of request or http status to answer with.
+fn _read_request<R: Read>(stream: R, max_body_len: usize) -> Result<(String, String), u16> {
+    let mut reader = BufReader::new(stream);
+    let (start_line, content_length) = _read_head(&mut reader)
+        .map_err(|err| {
+            debug!("Invalid http request {}", err);
+            400
+        })?;
+
+    if content_length > max_body_len {
+        debug!("Reject too large http request: {} bytes", content_length);
+        return Err(413);
+    }
+
+    let body = _read_body(&mut reader, content_length)
+        .map_err(|err| {
+            debug!("Invalid http request {}", err);
+            400
+        })?;
+    Ok((start_line, body))
+}
+
+fn _set_reader_timeouts(stream: &TcpStream) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(Duration::from_secs(HTTP_IO_TIMEOUT_SEC)))?;
+    stream.set_write_timeout(Some(Duration::from_secs(HTTP_IO_TIMEOUT_SEC)))
+}
+
+fn _run_inbound(socket: zmq::Socket, listener: TcpListener, max_body_len: usize) {
+    let mut pending: HashMap<String /* request id */, TcpStream> = HashMap::new();
+    let (read_sender, read_receiver) = mpsc::channel::<(TcpStream, Result<(String, String), u16>)>();
+    let mut readers: usize = 0;
+    'listener_loop: loop {
+        loop {
+            let mut stream = match listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) => {
+                    warn!("Http inbound thread accept failed {}", err);
+                    break;
+                }
+            };
+
+            if readers >= MAX_HTTP_READERS {
+                debug!("Reject http connection: too many requests are being read");
+                _write_response(&mut stream, 503, "").unwrap_or_else(|err| debug!("Http response failed {}", err));
+                continue;
+            }
+
+            let read_sender = read_sender.clone();
+            let reader = thread::Builder::new().spawn(move || {
+                let res = match _set_reader_timeouts(&stream) {
+                    Ok(()) => _read_request(&mut stream, max_body_len),
+                    Err(err) => {
+                        debug!("Http inbound thread can't configure stream {}", err);
+                        Err(500)
+                    }
+                };
+                read_sender.send((stream, res)).unwrap_or_else(|_| trace!("Http inbound thread is already finished"));
+            });
+            match reader {
+                Ok(_) => readers += 1,
+                Err(err) => warn!("Http inbound thread can't start reader {}", err),
+            }
+        }
+
+        while let Ok((mut stream, res)) = read_receiver.try_recv() {
+            readers -= 1;
+            match res {
+                Ok((ref start_line, ref body)) if start_line.starts_with("POST ") => {
+                    let request_id = SequenceUtils::get_next_id().to_string();
+                    if let Err(err) = socket.send_multipart(&[request_id.as_bytes(), body.as_bytes()], zmq::DONTWAIT) {
+                        error!("Http inbound thread can't forward request {}", err);
+                        continue;
+                    }
+                    pending.insert(request_id, stream);
+                }
+                Ok(_) => {
+                    _write_response(&mut stream, 405, "").unwrap_or_else(|err| debug!("Http response failed {}", err));
+                }
+                Err(status) => {
+                    _write_response(&mut stream, status, "").unwrap_or_else(|err| debug!("Http response failed {}", err));
+                }
+            }
+        }
+
+        match socket.poll(zmq::POLLIN, HTTP_LISTENER_POLL_INTERVAL_MS) {
+            Ok(0) => continue,
+            Ok(_) => {}
+            Err(err) => {
+                error!("Http inbound thread poll failed {}", err);
+                break;
+            }
+        }
+
+        while let Ok(frames) = socket.recv_multipart(zmq::DONTWAIT) {
+            if frames.len() == 1 && frames[0] == FRAME_EXIT.as_bytes() {
+                break 'listener_loop;
+            }
+            if frames.len() != 3 {
+                warn!("Http inbound thread got unexpected frames count {}", frames.len());
+                continue;
+            }
+            let request_id = String::from_utf8_lossy(&frames[0]).to_string();
+            let status = String::from_utf8_lossy(&frames[1]).parse::<u16>().unwrap_or(500);
+            let body = String::from_utf8_lossy(&frames[2]).to_string();
+            if let Some(mut stream) = pending.remove(&request_id) {
+                _write_response(&mut stream, status, body.as_str()).unwrap_or_else(|err| debug!("Http response failed {}", err));
+            }
+        }
+    }
+    trace!("Http inbound thread finished");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use services::agent::{_frame, PROTOCOL_FRAME};
+    use std::io::Cursor;
+
+    #[test]
+    fn http_url_parse_works() {
+        assert_eq!(HttpUrl { tls: false, host: "127.0.0.1".to_string(), port: 9700, path: "/agent".to_string() },
+                   HttpUrl::parse("http://127.0.0.1:9700/agent").unwrap());
+        assert_eq!(HttpUrl { tls: true, host: "agent.example.com".to_string(), port: 443, path: "/".to_string() },
+                   HttpUrl::parse("https://agent.example.com").unwrap());
+        assert_eq!(HttpUrl { tls: false, host: "agent.example.com".to_string(), port: 80, path: "/a/b".to_string() },
+                   HttpUrl::parse("http://agent.example.com/a/b").unwrap());
+    }
+
+    #[test]
+    fn http_url_parse_works_for_invalid() {
+        assert_match!(Err(CommonError::InvalidStructure(_)), HttpUrl::parse("tcp://127.0.0.1:9700"));
+        assert_match!(Err(CommonError::InvalidStructure(_)), HttpUrl::parse("http://127.0.0.1:port"));
+        assert_match!(Err(CommonError::InvalidStructure(_)), HttpUrl::parse("http://:9700"));
+    }
+
+    #[test]
+    fn http_envelope_seal_open_works() {
+        let sender = zmq::CurveKeyPair::new().unwrap();
+        let recipient = zmq::CurveKeyPair::new().unwrap();
+
+        let envelope = HttpEnvelope::seal(&sender.public_key, &sender.secret_key, &recipient.public_key, "msg".as_bytes()).unwrap();
+        assert_eq!(envelope.sender_pk, Base58::encode(&sender.public_key));
+        assert_eq!(envelope.recipient_pk, Base58::encode(&recipient.public_key));
+        assert!(envelope.msg != "msg");
+
+        let (timestamp, msg) = envelope.open(&recipient.secret_key).unwrap();
+        assert!(timestamp + 1 >= _unix_timestamp());
+        assert_eq!(msg, "msg".as_bytes());
+        assert!(envelope.open(&sender.secret_key).is_err());
+    }
+
+    #[test]
+    fn read_message_works() {
+        let data = "POST /agent HTTP/1.1\r\nHost: 127.0.0.1\r\ncontent-length: 4\r\n\r\nbody";
+        let (start_line, body) = _read_message(Cursor::new(data.as_bytes()), MAX_HTTP_BODY_LEN).unwrap();
+        assert_eq!(start_line, "POST /agent HTTP/1.1");
+        assert_eq!(body, "body");
+    }
+
+    #[test]
+    fn read_message_works_for_too_large_body() {
+        let data = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", MAX_HTTP_BODY_LEN + 1);
+        assert_match!(Err(CommonError::InvalidStructure(_)), _read_message(Cursor::new(data.as_bytes()), MAX_HTTP_BODY_LEN));
+    }
+
+    #[test]
+    fn read_request_works_for_too_large_body() {
+        let data = "POST /agent HTTP/1.1\r\nContent-Length: 101\r\n\r\n";
+        assert_eq!(Err(413), _read_request(Cursor::new(data.as_bytes()), 100));
+
+        let data = "POST /agent HTTP/1.1\r\nContent-Length: 4\r\n\r\nbody";
+        assert_eq!(Ok(("POST /agent HTTP/1.1".to_string(), "body".to_string())), _read_request(Cursor::new(data.as_bytes()), 100));
+    }
+
+    #[test]
+    fn max_request_body_len_fits_envelope() {
+        let sender = zmq::CurveKeyPair::new().unwrap();
+        let recipient = zmq::CurveKeyPair::new().unwrap();
+        for msg_len in [0, 1, 2, 1000, 1024 * 1024].iter() {
+            let msg = vec![255; *msg_len];
+            let envelope = HttpEnvelope::seal(&sender.public_key, &sender.secret_key, &recipient.public_key, &msg).unwrap();
+            assert!(envelope.to_json().unwrap().len() <= _max_request_body_len(msg.len()));
+
+            let (_, opened_msg) = envelope.open(&recipient.secret_key).unwrap();
+            assert_eq!(msg, opened_msg);
+        }
+    }
+
+    #[test]
+    fn write_request_and_response_works() {
+        let url = HttpUrl::parse("http://127.0.0.1:9700/agent").unwrap();
+        let mut request = Vec::new();
+        _write_request(&mut request, &url, "request").unwrap();
+        let (start_line, body) = _read_message(Cursor::new(request), MAX_HTTP_BODY_LEN).unwrap();
+        assert_eq!(start_line, "POST /agent HTTP/1.1");
+        assert_eq!(body, "request");
+
+        let mut response = Vec::new();
+        _write_response(&mut response, 400, "response").unwrap();
+        let (start_line, body) = _read_message(Cursor::new(response), MAX_HTTP_BODY_LEN).unwrap();
+        assert_eq!(start_line, "HTTP/1.1 400 Bad Request");
+        assert_eq!(body, "response");
+    }
+
+    #[test]
+    fn http_inbound_handle_request_works() {
+        let server = zmq::CurveKeyPair::new().unwrap();
+        let client = zmq::CurveKeyPair::new().unwrap();
+        let mut inbound = HttpInbound::new("http://127.0.0.1:0", 100).unwrap();
+        inbound.add_identity(&server.public_key, &server.secret_key).unwrap();
+
+        let identity = zmq::z85_encode(&client.public_key).unwrap();
+        inbound.send(identity.as_str(), "queued".as_bytes()).unwrap();
+
+        let request = HttpEnvelope::seal(&client.public_key, &client.secret_key, &server.public_key, "msg".as_bytes()).unwrap();
+        let (incoming, response) = inbound._handle_request(request.to_json().unwrap().as_str()).unwrap();
+        assert_eq!(incoming, Some((identity, "msg".as_bytes().to_vec())));
+
+        let response: Vec<HttpEnvelope> = serde_json::from_str(response.as_str()).unwrap();
+        assert_eq!(response.len(), 1);
+        assert_eq!(response[0].open(&client.secret_key).unwrap().1, "queued".as_bytes());
+    }
+
+    #[test]
+    fn http_inbound_handle_request_works_for_too_large() {
+        let server = zmq::CurveKeyPair::new().unwrap();
+        let client = zmq::CurveKeyPair::new().unwrap();
+        let mut inbound = HttpInbound::new("http://127.0.0.1:0", 10).unwrap();
+        inbound.add_identity(&server.public_key, &server.secret_key).unwrap();
+
+        let request = HttpEnvelope::seal(&client.public_key, &client.secret_key, &server.public_key, &[1; 11]).unwrap();
+        let (incoming, response) = inbound._handle_request(request.to_json().unwrap().as_str()).unwrap();
+        assert_eq!(incoming, None);
+
+        let response: Vec<HttpEnvelope> = serde_json::from_str(response.as_str()).unwrap();
+        assert_eq!(response.len(), 1);
+        assert_eq!(response[0].open(&client.secret_key).unwrap().1,
+                   _frame(PROTOCOL_FRAME, r#"{"msg_too_large":{"size":11,"max_size":10}}"#.as_bytes()));
+    }
+
+    #[test]
+    fn http_inbound_handle_request_works_for_unknown_recipient() {
+        let server = zmq::CurveKeyPair::new().unwrap();
+        let client = zmq::CurveKeyPair::new().unwrap();
+        let inbound = HttpInbound::new("http://127.0.0.1:0", 100).unwrap();
+
+        let request = HttpEnvelope::seal(&client.public_key, &client.secret_key, &server.public_key, &[]).unwrap();
+        assert_match!(Err(CommonError::InvalidStructure(_)), inbound._handle_request(request.to_json().unwrap().as_str()));
+    }
+
+    #[test]
+    fn http_inbound_handle_request_works_for_replayed_envelope() {
+        let server = zmq::CurveKeyPair::new().unwrap();
+        let client = zmq::CurveKeyPair::new().unwrap();
+        let mut inbound = HttpInbound::new("http://127.0.0.1:0", 100).unwrap();
+        inbound.add_identity(&server.public_key, &server.secret_key).unwrap();
+
+        let identity = zmq::z85_encode(&client.public_key).unwrap();
+        let request = HttpEnvelope::seal(&client.public_key, &client.secret_key, &server.public_key, &[]).unwrap();
+        let request = request.to_json().unwrap();
+        inbound._handle_request(request.as_str()).unwrap();
+
+        inbound.send(identity.as_str(), "queued".as_bytes()).unwrap();
+        assert_match!(Err(CommonError::InvalidStructure(_)), inbound._handle_request(request.as_str()));
+        assert_eq!(inbound.outbox.borrow().get(&identity).map(Vec::len), Some(1));
+    }
+
+    #[test]
+    fn http_inbound_handle_request_works_for_stale_envelope() {
+        let server = zmq::CurveKeyPair::new().unwrap();
+        let client = zmq::CurveKeyPair::new().unwrap();
+        let mut inbound = HttpInbound::new("http://127.0.0.1:0", 100).unwrap();
+        inbound.add_identity(&server.public_key, &server.secret_key).unwrap();
+
+        let timestamp = _unix_timestamp() - HTTP_ENVELOPE_TTL_SEC - 1;
+        let request = HttpEnvelope::_seal(&client.public_key, &client.secret_key, &server.public_key, &[], timestamp).unwrap();
+        assert_match!(Err(CommonError::InvalidStructure(_)), inbound._handle_request(request.to_json().unwrap().as_str()));
+    }
+
+    #[test]
+    fn http_inbound_send_works_for_full_outbox() {
+        let inbound = HttpInbound::new("http://127.0.0.1:0", 100).unwrap();
+
+        for _ in 0..MAX_HTTP_OUTBOX_LEN {
+            inbound.send("identity", "msg".as_bytes()).unwrap();
+        }
+        assert_match!(Err(CommonError::InvalidState(_)), inbound.send("identity", "msg".as_bytes()));
+        inbound.send("other_identity", "msg".as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn http_inbound_new_works_for_https() {
+        assert_match!(Err(CommonError::InvalidStructure(_)), HttpInbound::new("https://127.0.0.1:0", 100));
+    }
+}
@@ -0,0 +1,2655 @@
+#![warn(unused_variables)]
+mod transport;
+mod zmq_transport;
+mod http_transport;
+pub mod policy;
+
+extern crate rust_base58;
+extern crate serde_json;
+extern crate uuid;
+extern crate zmq_pw as zmq;
+
+use self::policy::{RateLimit, RateLimiter};
+use self::rust_base58::FromBase58;
+use self::transport::{InboundTransport, OutboundTransport};
+use self::uuid::Uuid;
+use std::cmp;
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::{io, str, thread};
+
+use commands::{Command, CommandExecutor};
+use commands::agent::AgentCommand;
+use errors::agent::AgentError;
+use errors::common::CommonError;
+use utils::did::DidUtils;
+use utils::json::{JsonDecodable, JsonEncodable};
+use utils::sequence::SequenceUtils;
+
+const DID_ACK: &'static str = "DID_ACK";
+const PING: &'static str = "PING";
+const PONG: &'static str = "PONG";
+const RATE_LIMITED: &'static str = "RATE_LIMITED";
+
+/// Worker wakes up at least once per tick to send heartbeats and perform reconnects.
+const WORKER_TICK_MS: i64 = 1000;
+const HEARTBEAT_INTERVAL_SECS: u64 = 10;
+/// Connection is treated as lost if nothing (including PONG) was received during this period.
+const HEARTBEAT_TIMEOUT_SECS: u64 = 30;
+const RECONNECT_MIN_DELAY_SECS: u64 = 1;
+const RECONNECT_MAX_DELAY_SECS: u64 = 60;
+const MAX_OUTBOUND_QUEUE_LEN: usize = 100;
+const DELIVERY_ACK_TIMEOUT_SECS: u64 = 60;
+/// Frames that aren't application text start with NUL byte that can't start text message
+/// passed as C string. Next byte tells kind of the frame.
+const FRAME_MARKER: u8 = 0;
+const BINARY_FRAME: u8 = b'B';
+/// Protocol frame carries MsgEnvelope, MsgAck or MsgTooLarge json, so peer text is never taken for them.
+const PROTOCOL_FRAME: u8 = b'P';
+/// Maximum size of incoming message if not set by listener config.
+const DEFAULT_MAX_MSG_SIZE: usize = 1024 * 1024;
+
+#[derive(Serialize, Debug, PartialEq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+enum ConnectionState {
+    Connecting,
+    Connected,
+    Disconnected,
+}
+
+struct RemoteAgent {
+    transport: Box<OutboundTransport>,
+    conn_handle: i32,
+    state: ConnectionState,
+    /* info required to re-create transport on reconnect */
+    endpoint: String,
+    public_key: Vec<u8>,
+    secret_key: Vec<u8>,
+    server_key: Vec<u8>,
+    sender_did: String,
+    receiver_did: String,
+    /* true after first DID_ACK: only established connections are reconnected */
+    was_connected: bool,
+    last_activity: Instant,
+    last_ping: Instant,
+    reconnect_attempts: u32,
+    reconnect_at: Option<Instant>,
+    queue: VecDeque<Vec<u8>>,
+    opened_at: u64,
+    stats: ConnectionStats,
+}
+
+#[derive(Serialize, Debug, PartialEq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+enum ConnectionDirection {
+    Outgoing,
+    Incoming,
+}
+
+/// Heartbeats and handshake aren't counted.
+#[derive(Serialize, Debug, PartialEq, Clone, Default)]
+struct ConnectionStats {
+    messages_sent: u64,
+    messages_received: u64,
+    bytes_sent: u64,
+    bytes_received: u64,
+    reconnects: u64,
+}
+
+impl ConnectionStats {
+    fn on_sent(&mut self, len: usize) {
+        self.messages_sent += 1;
+        self.bytes_sent += len as u64;
+    }
+
+    fn on_received(&mut self, len: usize) {
+        self.messages_received += 1;
+        self.bytes_received += len as u64;
+    }
+}
+
+struct IncomingConnection {
+    sender_did: String,
+    receiver_did: String,
+    opened_at: u64,
+    stats: ConnectionStats,
+}
+
+struct AgentListener {
+    connections: Vec<(i32 /* connection_handle*/, String /* identity */)>,
+    connections_info: HashMap<i32 /* connection_handle*/, IncomingConnection>,
+    pending_connections: HashMap<String /* sender DID */, (String, String) /* (sender pk, receiver DID) */>,
+    did_resources: HashMap<String /* DID */, (i32, i32) /* pool_handle, wallet_handle */>,
+    listener_handle: i32,
+    endpoint: String,
+    transport: Box<InboundTransport>,
+    /* limits connection attempts per sender key */
+    rate_limiter: Option<RateLimiter>,
+}
+
+struct AgentWorker {
+    cmd_socket: zmq::Socket,
+    agent_connections: Vec<RemoteAgent>,
+    agent_listeners: Vec<AgentListener>,
+    /* (connection handle, message id) -> (send cmd id, deadline) */
+    pending_acks: HashMap<(i32, String), (i32, Instant)>,
+    /* (connection handle, request message id) -> (request cmd id, deadline) */
+    pending_requests: HashMap<(i32, String), (i32, Instant)>,
+}
+
+struct Agent {
+    cmd_socket: zmq::Socket,
+    worker: Option<thread::JoinHandle<()>>,
+}
+
+impl Drop for Agent {
+    fn drop(&mut self) {
+        trace!("agent drop >>");
+        self.cmd_socket.send(AgentWorkerCommand::Exit.to_json().unwrap().as_str(), zmq::DONTWAIT).unwrap(); //TODO
+        self.worker.take().unwrap().join().unwrap();
+        trace!("agent drop <<");
+    }
+}
+
+pub struct AgentService {
+    agent: Agent,
+}
+
+impl Agent {
+    pub fn new() -> Agent {
+        let (send_soc, recv_soc) = _create_zmq_socket_pair("agent", true).unwrap();
+        let mut worker = AgentWorker {
+            cmd_socket: recv_soc,
+            agent_connections: Vec::new(),
+            agent_listeners: Vec::new(),
+            pending_acks: HashMap::new(),
+            pending_requests: HashMap::new(),
+        };
+        Agent {
+            cmd_socket: send_soc,
+            worker: Some(thread::spawn(move || { worker.run() }))
+        }
+    }
+}
+
+impl AgentService {
+    pub fn new() -> AgentService {
+        AgentService { agent: Agent::new() }
+    }
+
+    pub fn connect(&self, sender_did: &str, receiver_did: &str, my_sk: &str, my_pk: &str, endpoint: &str, server_key: &str) -> Result<i32, CommonError> {
+        let conn_handle = SequenceUtils::get_next_id();
+        let connect_cmd: AgentWorkerCommand = AgentWorkerCommand::Connect(ConnectCmd {
+            sender_did: sender_did.to_string(),
+            receiver_did: receiver_did.to_string(),
+            secret_key: my_sk.to_string(),
+            public_key: my_pk.to_string(),
+            endpoint: endpoint.to_string(),
+            server_key: server_key.to_string(),
+            conn_handle: conn_handle,
+        });
+        self.agent.cmd_socket.send(connect_cmd.to_json()
+                                       .map_err(|err|
+                                           CommonError::InvalidState(format!("Can't serialize AgentWorkerCommand::Connect {}", err.description())))?
+                                       .as_str(), zmq::DONTWAIT)?;
+        Ok(conn_handle)
+    }
+
+    pub fn listen(&self, endpoint: &str, config: Option<&str>) -> Result<i32, CommonError> {
+        let config = match config {
+            Some(config) => ListenerConfig::from_json(config)
+                .map_err(|err| CommonError::InvalidStructure(format!("Invalid agent listener config {}", err.description())))?,
+            None => ListenerConfig::default(),
+        };
+        let max_msg_size = config.max_message_size.unwrap_or(DEFAULT_MAX_MSG_SIZE);
+        if max_msg_size == 0 {
+            return Err(CommonError::InvalidStructure("Agent listener max_message_size must be positive".to_string()));
+        }
+        if let Some(ref rate_limit) = config.rate_limit {
+            if rate_limit.max_attempts == 0 || rate_limit.interval_secs == 0 || rate_limit.max_total_attempts == Some(0) {
+                return Err(CommonError::InvalidStructure("Agent listener rate_limit values must be positive".to_string()));
+            }
+        }
+
+        let listen_handle = SequenceUtils::get_next_id();
+        let listen_cmd = AgentWorkerCommand::Listen(ListenCmd {
+            listen_handle: listen_handle,
+            endpoint: endpoint.to_string(),
+            max_msg_size: max_msg_size,
+            rate_limit: config.rate_limit,
+        });
+        self.agent.cmd_socket.send(listen_cmd.to_json()
+                                       .map_err(|err|
+                                           CommonError::InvalidState(format!("Can't serialize AgentWorkerCommand::Listen {}", err.description())))?
+                                       .as_str(), zmq::DONTWAIT)?;
+        Ok(listen_handle)
+    }
+
+    pub fn on_connect_checked(&self, listener_handle: i32, did: &str, is_ok: bool) -> Result<(), CommonError> {
+        let ack_connect_cmd = AgentWorkerCommand::ConnectCheck(ConnectCheckCmd {
+            listener_handle: listener_handle,
+            did: did.to_string(),
+            result: is_ok,
+        });
+        self.agent.cmd_socket.send(ack_connect_cmd.to_json()
+                                       .map_err(|err|
+                                           CommonError::InvalidState(format!("Can't serialize AgentWorkerCommand::CheckConnect {}", err.description())))?
+                                       .as_str(), zmq::DONTWAIT)?;
+        Ok(())
+    }
+
+    pub fn add_identity(&self, listener_handle: i32, did: &str,
+                        pool_handle: i32, wallet_handle: i32,
+                        sk: &str, pk: &str) -> Result<i32, CommonError> {
+        let cmd_handle = SequenceUtils::get_next_id();
+        let cmd = AgentWorkerCommand::AddIdentity(AddIdentityCmd {
+            cmd_id: cmd_handle,
+            listen_handle: listener_handle,
+            did: did.to_string(),
+            pool_handle: pool_handle,
+            wallet_handle: wallet_handle,
+            pk: pk.to_string(),
+            sk: sk.to_string(),
+        });
+        self.agent.cmd_socket.send(cmd.to_json()
+                                       .map_err(|err|
+                                           CommonError::InvalidState(format!("Can't serialize AgentWorkerCommand::AddIdentityCmd {}", err.description())))?
+                                       .as_str(), zmq::DONTWAIT)?;
+        Ok(cmd_handle)
+    }
+
+    pub fn rm_identity(&self, listener_handle: i32, did: &str, pk: &str) -> Result<i32, CommonError> {
+        let cmd_handle = SequenceUtils::get_next_id();
+        let cmd = AgentWorkerCommand::RmIdentity(RmIdentityCmd {
+            cmd_id: cmd_handle,
+            listen_handle: listener_handle,
+            did: did.to_string(),
+            pk: pk.to_string(),
+        });
+        self.agent.cmd_socket.send(cmd.to_json()
+                                       .map_err(|err|
+                                           CommonError::InvalidState(format!("Can't serialize AgentWorkerCommand::RmIdentityCmd {}", err.description())))?
+                                       .as_str(), zmq::DONTWAIT)?;
+        Ok(cmd_handle)
+    }
+
+    pub fn send(&self, conn_id: i32, msg: Option<&str>) -> Result<i32, CommonError> {
+        let send_handle = SequenceUtils::get_next_id();
+        let send_cmd = AgentWorkerCommand::Send(SendCmd {
+            cmd_id: send_handle,
+            conn_handle: conn_id,
+            msg: msg.map(str::to_string),
+            msg_id: None,
+            reply_to: None,
+        });
+        self.agent.cmd_socket.send(send_cmd.to_json()
+                                       .map_err(|err|
+                                           CommonError::InvalidState(format!("Can't serialize AgentWorkerCommand::Send {}", err.description())))?
+                                       .as_str(), zmq::DONTWAIT)?;
+        Ok(send_handle)
+    }
+
+    pub fn send_bytes(&self, conn_id: i32, msg: &[u8]) -> Result<i32, CommonError> {
+        let send_handle = SequenceUtils::get_next_id();
+        let send_cmd = AgentWorkerCommand::SendBytes(SendBytesCmd {
+            cmd_id: send_handle,
+            conn_handle: conn_id,
+            msg: Vec::new(),
+        });
+        let send_cmd = send_cmd.to_json()
+            .map_err(|err|
+                CommonError::InvalidState(format!("Can't serialize AgentWorkerCommand::SendBytes {}", err.description())))?;
+        /* payload follows command as separate frame to avoid JSON encoding of bytes */
+        self.agent.cmd_socket.send_multipart(&[send_cmd.as_bytes(), msg], zmq::DONTWAIT)?;
+        Ok(send_handle)
+    }
+
+    /// Sends message in envelope with id. Send is completed after delivery acknowledgement
+    /// from remote libindy is received. Id is generated if not provided.
+    pub fn send_with_ack(&self, conn_id: i32, msg_id: Option<&str>, reply_to: Option<&str>, msg: &str) -> Result<(i32, String), CommonError> {
+        let send_handle = SequenceUtils::get_next_id();
+        let msg_id = msg_id.map(str::to_string).unwrap_or_else(_generate_msg_id);
+        let send_cmd = AgentWorkerCommand::Send(SendCmd {
+            cmd_id: send_handle,
+            conn_handle: conn_id,
+            msg: Some(msg.to_string()),
+            msg_id: Some(msg_id.clone()),
+            reply_to: reply_to.map(str::to_string),
+        });
+        self.agent.cmd_socket.send(send_cmd.to_json()
+                                       .map_err(|err|
+                                           CommonError::InvalidState(format!("Can't serialize AgentWorkerCommand::Send {}", err.description())))?
+                                       .as_str(), zmq::DONTWAIT)?;
+        Ok((send_handle, msg_id))
+    }
+
+    pub fn send_request(&self, conn_id: i32, msg: &str, timeout_ms: u32) -> Result<i32, CommonError> {
+        let request_handle = SequenceUtils::get_next_id();
+        let request_cmd = AgentWorkerCommand::SendRequest(SendRequestCmd {
+            cmd_id: request_handle,
+            conn_handle: conn_id,
+            msg_id: _generate_msg_id(),
+            msg: msg.to_string(),
+            timeout_ms: timeout_ms,
+        });
+        self.agent.cmd_socket.send(request_cmd.to_json()
+                                       .map_err(|err|
+                                           CommonError::InvalidState(format!("Can't serialize AgentWorkerCommand::SendRequest {}", err.description())))?
+                                       .as_str(), zmq::DONTWAIT)?;
+        Ok(request_handle)
+    }
+
+    /// Requests info of all outgoing and incoming connections. Result is passed to executor as ConnectionsInfoAck.
+    pub fn list_connections(&self) -> Result<i32, CommonError> {
+        self.get_connections(None)
+    }
+
+    pub fn get_connection_info(&self, conn_id: i32) -> Result<i32, CommonError> {
+        self.get_connections(Some(conn_id))
+    }
+
+    fn get_connections(&self, conn_id: Option<i32>) -> Result<i32, CommonError> {
+        let cmd_id = SequenceUtils::get_next_id();
+        let get_cmd = AgentWorkerCommand::GetConnections(GetConnectionsCmd {
+            cmd_id: cmd_id,
+            conn_handle: conn_id,
+        });
+        self.agent.cmd_socket.send(get_cmd.to_json()
+                                       .map_err(|err|
+                                           CommonError::InvalidState(format!("Can't serialize AgentWorkerCommand::GetConnections {}", err.description())))?
+                                       .as_str(), zmq::DONTWAIT)?;
+        Ok(cmd_id)
+    }
+
+    pub fn close_connection_or_listener(&self, handle: i32, close_listener: bool)
+                                        -> Result<i32, CommonError> {
+        let close_conn_handle = SequenceUtils::get_next_id();
+        let close_cmd = AgentWorkerCommand::Close(CloseCmd {
+            cmd_id: close_conn_handle,
+            handle: handle,
+            close_listener: close_listener,
+        });
+        self.agent.cmd_socket.send(close_cmd.to_json()
+                                       .map_err(|err|
+                                           CommonError::InvalidState(format!("Can't serialize AgentWorkerCommand::Send {}", err.description())))?
+                                       .as_str(), zmq::DONTWAIT)?;
+        Ok(close_conn_handle)
+    }
+}
+
+impl AgentWorker {
+    fn run(&mut self) {
+        'agent_pool_loop: loop {
+            trace!("agent worker poll loop >>");
+            let cmds = self.poll().unwrap();
+            for cmd in cmds {
+                debug!("AgentWorker::run received cmd {:?}", cmd);
+                match cmd {
+                    AgentWorkerCommand::Connect(cmd) => self.connect(cmd).unwrap(),
+                    AgentWorkerCommand::ConnectCheck(cmd) => self.on_connect_checked(cmd.listener_handle, cmd.did, cmd.result).unwrap(),
+                    AgentWorkerCommand::Close(cmd) => self.close_connection_or_listener(cmd.cmd_id, cmd.handle, cmd.close_listener).unwrap(),
+                    AgentWorkerCommand::Listen(cmd) => self.start_listen(cmd.listen_handle, cmd.endpoint, cmd.max_msg_size, cmd.rate_limit).unwrap(),
+                    AgentWorkerCommand::AddIdentity(cmd) => self.add_identity(cmd.cmd_id, cmd.listen_handle, cmd.did, cmd.pool_handle, cmd.wallet_handle, cmd.pk, cmd.sk).unwrap(),
+                    AgentWorkerCommand::RmIdentity(cmd) => self.rm_identity(cmd.cmd_id, cmd.listen_handle, cmd.did, cmd.pk).unwrap(),
+                    AgentWorkerCommand::Response(resp) => {
+                        let msg = resp.msg.map_err(|err| CommonError::IOError(io::Error::new(io::ErrorKind::Other, err)));
+                        if let Some(cmd) = self.agent_connections[resp.agent_ind].handle_response(msg) {
+                            self.dispatch(cmd);
+                        }
+                    }
+                    AgentWorkerCommand::Request(req) => {
+                        if let Some(cmd) = self.agent_listeners[req.listener_ind].handle_request(req.identity, req.msg).unwrap() {
+                            self.dispatch(cmd);
+                        }
+                    }
+                    AgentWorkerCommand::Send(cmd) => self.send(cmd.cmd_id, cmd.conn_handle, cmd.msg, cmd.msg_id, cmd.reply_to).unwrap(),
+                    AgentWorkerCommand::SendBytes(cmd) => self.send_bytes(cmd.cmd_id, cmd.conn_handle, cmd.msg).unwrap(),
+                    AgentWorkerCommand::SendRequest(cmd) => self.send_request(cmd.cmd_id, cmd.conn_handle, cmd.msg_id, cmd.msg, cmd.timeout_ms).unwrap(),
+                    AgentWorkerCommand::GetConnections(cmd) => self.get_connections(cmd.cmd_id, cmd.conn_handle).unwrap(),
+                    AgentWorkerCommand::Exit => break 'agent_pool_loop,
+                }
+            }
+            self.check_connections();
+            trace!("agent worker poll loop <<");
+        }
+        trace!("agent poll finished");
+    }
+
+    fn connect(&mut self, cmd: ConnectCmd) -> Result<(), CommonError> {
+        let mut ra = RemoteAgent::new(&cmd)
+            .map_err(map_err_trace!("RemoteAgent::new failed"))?;
+        ra.connect().map_err(map_err_trace!("RemoteAgent::connect failed"))?;
+        self.agent_connections.push(ra);
+        Ok(())
+    }
+
+    fn check_connections(&mut self) {
+        let now = Instant::now();
+        for agent_conn in &mut self.agent_connections {
+            if let Some(cmd) = agent_conn.check_liveness(now) {
+                _send_to_executor(cmd);
+            }
+        }
+        self.check_pending(now);
+    }
+
+    fn check_pending(&mut self, now: Instant) {
+        let expired_acks: Vec<(i32, String)> = self.pending_acks.iter()
+            .filter(|&(_, &(_, deadline))| now >= deadline)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for (conn_handle, msg_id) in expired_acks {
+            if let Some((cmd_id, _)) = self.pending_acks.remove(&(conn_handle, msg_id.clone())) {
+                _send_to_executor(AgentCommand::SendAck(cmd_id, Err(AgentError::Timeout(
+                    format!("Delivery of message {} to connection {} isn't acknowledged", msg_id, conn_handle)))));
+            }
+        }
+
+        let expired_requests: Vec<(i32, String)> = self.pending_requests.iter()
+            .filter(|&(_, &(_, deadline))| now >= deadline)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for (conn_handle, msg_id) in expired_requests {
+            if let Some((cmd_id, _)) = self.pending_requests.remove(&(conn_handle, msg_id.clone())) {
+                _send_to_executor(AgentCommand::SendRequestAck(cmd_id, Err(AgentError::Timeout(
+                    format!("No response to request {} on connection {}", msg_id, conn_handle)))));
+            }
+        }
+    }
+
+    /// Protocol messages are handled by worker, other commands are passed to executor.
+    fn dispatch(&mut self, cmd: AgentCommand) {
+        match cmd {
+            AgentCommand::ProtocolMessageReceived(conn_handle, msg) => self.on_protocol_message(conn_handle, msg),
+            cmd => _send_to_executor(cmd),
+        }
+    }
+
+    fn on_protocol_message(&mut self, conn_handle: i32, msg: String) {
+        if let Ok(reject) = MsgTooLarge::from_json(msg.as_str()) {
+            return _send_to_executor(AgentCommand::MessageReceived(conn_handle, Err(AgentError::MessageTooLarge(
+                format!("Remote agent rejected message of {} bytes, maximum size is {} bytes",
+                        reject.msg_too_large.size, reject.msg_too_large.max_size)))));
+        }
+
+        if let Ok(ack) = MsgAck::from_json(msg.as_str()) {
+            match self.pending_acks.remove(&(conn_handle, ack.msg_ack.clone())) {
+                Some((cmd_id, _)) => _send_to_executor(AgentCommand::SendAck(cmd_id, Ok(()))),
+                None => debug!("Ignore acknowledgement of unknown message {} on connection {}", ack.msg_ack, conn_handle),
+            }
+            return;
+        }
+
+        let envelope = match MsgEnvelope::from_json(msg.as_str()) {
+            Ok(envelope) => envelope.msg_envelope,
+            Err(_) => {
+                warn!("Ignore unknown protocol message on connection {}: {}", conn_handle, msg);
+                return;
+            }
+        };
+
+        let ack = MsgAck { msg_ack: envelope.id.clone() };
+        let res = _protocol_frame(&ack)
+            .map_err(AgentError::CommonError)
+            .and_then(|ack| self.try_send_raw(conn_handle, ack));
+        if let Err(err) = res {
+            warn!("Can't acknowledge message {} on connection {}: {}", envelope.id, conn_handle, err);
+        }
+
+        if let Some(ref reply_to) = envelope.reply_to {
+            if let Some((cmd_id, _)) = self.pending_requests.remove(&(conn_handle, reply_to.clone())) {
+                return _send_to_executor(AgentCommand::SendRequestAck(cmd_id, Ok(envelope.content)));
+            }
+        }
+
+        _send_to_executor(AgentCommand::MessageReceived(conn_handle, Ok((envelope.content, Some(envelope.id)))));
+    }
+
+    /// Connection closed by rejected reconnect can't deliver awaited acknowledgements or responses.
+    fn on_connect_checked(&mut self, listener_handle: i32, did: String, result: bool) -> Result<(), CommonError> {
+        let closed: Vec<i32> = {
+            let listener = self.agent_listeners.iter_mut()
+                .find(|listener: &&mut AgentListener| listener.listener_handle == listener_handle)
+                .ok_or(CommonError::InvalidState(format!("Listener with handle {} not found for push client check result {}.", listener_handle, result)))?;
+            let opened: Vec<i32> = listener.connections.iter().map(|&(conn_handle, _)| conn_handle).collect();
+            listener.on_connect_checked(did, result)?;
+            opened.into_iter()
+                .filter(|conn_handle| !listener.connections.iter().any(|&(id, _)| id == *conn_handle))
+                .collect()
+        };
+        for conn_handle in closed {
+            self.fail_pending(conn_handle);
+        }
+        Ok(())
+    }
+
+    /// Fails delivery acknowledgements and responses awaited from closed connection.
+    fn fail_pending(&mut self, conn_handle: i32) {
+        let acks: Vec<String> = self.pending_acks.keys()
+            .filter(|&&(handle, _)| handle == conn_handle)
+            .map(|&(_, ref msg_id)| msg_id.clone())
+            .collect();
+        for msg_id in acks {
+            if let Some((cmd_id, _)) = self.pending_acks.remove(&(conn_handle, msg_id.clone())) {
+                _send_to_executor(AgentCommand::SendAck(cmd_id, Err(AgentError::ConnectionLost(
+                    format!("Connection {} closed before message {} was acknowledged", conn_handle, msg_id)))));
+            }
+        }
+
+        let requests: Vec<String> = self.pending_requests.keys()
+            .filter(|&&(handle, _)| handle == conn_handle)
+            .map(|&(_, ref msg_id)| msg_id.clone())
+            .collect();
+        for msg_id in requests {
+            if let Some((cmd_id, _)) = self.pending_requests.remove(&(conn_handle, msg_id.clone())) {
+                _send_to_executor(AgentCommand::SendRequestAck(cmd_id, Err(AgentError::ConnectionLost(
+                    format!("Connection {} closed before response to request {}", conn_handle, msg_id)))));
+            }
+        }
+    }
+
+    fn start_listen(&mut self, handle: i32, endpoint: String, max_msg_size: usize, rate_limit: Option<RateLimit>) -> Result<(), CommonError> {
+        let res = self.try_start_listen(handle, endpoint, max_msg_size, rate_limit);
+        let cmd = AgentCommand::ListenAck(handle, res.map(|()| (handle)));
+        CommandExecutor::instance().send(Command::Agent(cmd))
+    }
+
+    fn add_identity(&mut self, handle: i32, listener: i32, did: String, pool: i32, wallet: i32, pk: String, sk: String) -> Result<(), CommonError> {
+        let res = self.try_add_identity(listener, did, pool, wallet, pk, sk);
+        let cmd = AgentCommand::ListenerAddIdentityAck(handle, res);
+        CommandExecutor::instance().send(Command::Agent(cmd))
+    }
+
+    fn rm_identity(&mut self, handle: i32, listener: i32, did: String, pk: String) -> Result<(), CommonError> {
+        let res = self.try_rm_identity(listener, did, pk);
+        let cmd = AgentCommand::ListenerRmIdentityAck(handle, res);
+        CommandExecutor::instance().send(Command::Agent(cmd))
+    }
+
+    fn send(&mut self, cmd_id: i32, conn_handle: i32, msg: Option<String>, msg_id: Option<String>, reply_to: Option<String>)
+            -> Result<(), CommonError> {
+        let msg_id = match msg_id {
+            Some(msg_id) => msg_id,
+            None => {
+                let res = self.try_send(conn_handle, msg);
+                let cmd = AgentCommand::SendAck(cmd_id, res);
+                return CommandExecutor::instance().send(Command::Agent(cmd));
+            }
+        };
+
+        let key = (conn_handle, msg_id.clone());
+        let res = if self.pending_acks.contains_key(&key) {
+            Err(AgentError::CommonError(CommonError::InvalidStructure(
+                format!("Message with id {} already waits for acknowledgement on connection {}", msg_id, conn_handle))))
+        } else {
+            self.try_send_envelope(conn_handle, msg_id, reply_to, msg.unwrap_or(String::new()))
+        };
+
+        match res {
+            Ok(()) => {
+                /* SendAck will be sent on delivery acknowledgement or timeout */
+                self.pending_acks.insert(key, (cmd_id, Instant::now() + Duration::from_secs(DELIVERY_ACK_TIMEOUT_SECS)));
+                Ok(())
+            }
+            Err(err) => CommandExecutor::instance().send(Command::Agent(AgentCommand::SendAck(cmd_id, Err(err))))
+        }
+    }
+
+    fn send_bytes(&mut self, cmd_id: i32, conn_handle: i32, msg: Vec<u8>) -> Result<(), CommonError> {
+        let res = self.try_send_raw(conn_handle, _frame(BINARY_FRAME, msg.as_slice()));
+        CommandExecutor::instance().send(Command::Agent(AgentCommand::SendAck(cmd_id, res)))
+    }
+
+    fn send_request(&mut self, cmd_id: i32, conn_handle: i32, msg_id: String, msg: String, timeout_ms: u32)
+                    -> Result<(), CommonError> {
+        match self.try_send_envelope(conn_handle, msg_id.clone(), None, msg) {
+            Ok(()) => {
+                self.pending_requests.insert((conn_handle, msg_id),
+                                             (cmd_id, Instant::now() + Duration::from_millis(timeout_ms as u64)));
+                Ok(())
+            }
+            Err(err) => CommandExecutor::instance().send(Command::Agent(AgentCommand::SendRequestAck(cmd_id, Err(err))))
+        }
+    }
+
+    fn get_connections(&mut self, cmd_id: i32, conn_handle: Option<i32>) -> Result<(), CommonError> {
+        let res = match conn_handle {
+            Some(conn_handle) => self.connections_info().into_iter()
+                .find(|info| info.connection_handle == conn_handle)
+                .ok_or(CommonError::InvalidStructure(format!("Connection with id {} not found", conn_handle)))
+                .and_then(|info| info.to_json()
+                    .map_err(|err| CommonError::InvalidState(format!("Can't serialize ConnectionInfo {}", err.description())))),
+            None => serde_json::to_string(&self.connections_info())
+                .map_err(|err| CommonError::InvalidState(format!("Can't serialize connections info {}", err.description()))),
+        };
+        CommandExecutor::instance().send(Command::Agent(AgentCommand::ConnectionsInfoAck(cmd_id, res)))
+    }
+
+    fn connections_info(&self) -> Vec<ConnectionInfo> {
+        let mut result: Vec<ConnectionInfo> = self.agent_connections.iter()
+            .map(|remote_agent| ConnectionInfo {
+                connection_handle: remote_agent.conn_handle,
+                sender_did: remote_agent.sender_did.clone(),
+                receiver_did: remote_agent.receiver_did.clone(),
+                endpoint: remote_agent.endpoint.clone(),
+                direction: ConnectionDirection::Outgoing,
+                listener_handle: None,
+                state: remote_agent.state,
+                opened_at: remote_agent.opened_at,
+                stats: remote_agent.stats.clone(),
+            })
+            .collect();
+        for listener in &self.agent_listeners {
+            for &(conn_handle, _) in &listener.connections {
+                if let Some(conn) = listener.connections_info.get(&conn_handle) {
+                    result.push(ConnectionInfo {
+                        connection_handle: conn_handle,
+                        sender_did: conn.sender_did.clone(),
+                        receiver_did: conn.receiver_did.clone(),
+                        endpoint: listener.endpoint.clone(),
+                        direction: ConnectionDirection::Incoming,
+                        listener_handle: Some(listener.listener_handle),
+                        state: ConnectionState::Connected,
+                        opened_at: conn.opened_at,
+                        stats: conn.stats.clone(),
+                    });
+                }
+            }
+        }
+        result
+    }
+
+    fn try_add_identity(&mut self, listener_handle: i32, did: String, pool_handle: i32, wallet_handle: i32, pk: String, sk: String) -> Result<(), CommonError> {
+        let pk = pk.from_base58()
+            .map_err(|err| CommonError::InvalidState(format!("Invalid pk {}", err)))?;
+        let sk = sk.from_base58()
+            .map_err(|err| CommonError::InvalidState(format!("Invalid sk {}", err)))?;
+        for mut listener in &mut self.agent_listeners {
+            let listener: &mut AgentListener = listener;
+            if listener.listener_handle == listener_handle {
+                if listener.did_resources.contains_key(&did) {
+                    return Err(CommonError::InvalidStructure(
+                        format!("DID {} already added to listener {}", did, listener_handle)));
+                }
+                listener.transport.add_identity(pk.as_slice(), sk.as_slice())?;
+                listener.did_resources.insert(did, (pool_handle, wallet_handle));
+                return Ok(());
+            }
+        }
+        Err(CommonError::InvalidStructure(
+            format!("Listener with id {} not founded", listener_handle)))
+    }
+
+    fn try_rm_identity(&mut self, listener_handle: i32, did: String, pk: String) -> Result<(), CommonError> {
+        let pk = pk.from_base58()
+            .map_err(|err| CommonError::InvalidState(format!("Invalid pk {}", err)))?;
+        for mut listener in &mut self.agent_listeners {
+            let listener: &mut AgentListener = listener;
+            if listener.listener_handle == listener_handle {
+                if listener.did_resources.remove(&did).is_none() {
+                    return Err(CommonError::InvalidStructure(
+                        format!("DID {} missed in listener {}", did, listener_handle)));
+                }
+                listener.transport.rm_identity(pk.as_slice())?;
+                return Ok(());
+            }
+        }
+        Err(CommonError::InvalidStructure(
+            format!("Listener with id {} not founded", listener_handle)))
+    }
+
+    fn try_send(&mut self, handle: i32, msg: Option<String>) -> Result<(), AgentError> {
+        self.try_send_raw(handle, msg.unwrap_or(String::new()).into_bytes())
+    }
+
+    fn try_send_raw(&mut self, handle: i32, msg: Vec<u8>) -> Result<(), AgentError> {
+        let is_listener_connection = self.find_listener_by_conn_handle(handle).is_some();
+
+        if let Some(remote_agent) = self.agent_connections.iter_mut().find(|ac| ac.conn_handle == handle) {
+            if is_listener_connection {
+                return Err(AgentError::CommonError(CommonError::InvalidState("duplication connections".to_string()))) //TODO
+            }
+            return remote_agent.send(msg)
+        }
+        for agent_listener in &mut self.agent_listeners {
+            let identity = match agent_listener.connections.iter().find(|&&(conn_id, _)| conn_id == handle) {
+                Some(&(_, ref identity)) => identity.clone(),
+                None => continue,
+            };
+            agent_listener.transport.send(identity.as_str(), msg.as_slice())?;
+            if let Some(conn) = agent_listener.connections_info.get_mut(&handle) {
+                conn.stats.on_sent(msg.len());
+            }
+            return Ok(());
+        }
+        /* if remote_agent.is_none() && listener_with_identity.is_none() */
+        Err(AgentError::CommonError(CommonError::InvalidStructure(format!("Connection with id {} not founded", handle))))
+    }
+
+    fn try_send_envelope(&mut self, conn_handle: i32, msg_id: String, reply_to: Option<String>, content: String) -> Result<(), AgentError> {
+        let envelope = MsgEnvelope {
+            msg_envelope: Envelope {
+                id: msg_id,
+                reply_to: reply_to,
+                content: content,
+            }
+        };
+        let envelope = _protocol_frame(&envelope)?;
+        self.try_send_raw(conn_handle, envelope)
+    }
+
+    fn find_listener_by_conn_handle(&self, handle: i32) -> Option<(&AgentListener, &String)> {
+        for listener in &self.agent_listeners {
+            if let Some(&(_, ref identity)) = listener.connections.iter().find(|&&(conn_id, _)| conn_id == handle) {
+                return Some((listener, identity));
+            }
+        }
+        return None;
+    }
+
+    fn close_connection_or_listener(&mut self, cmd_handle: i32, handle: i32, close_listener: bool) -> Result<(), CommonError> {
+        let cmd = if close_listener {
+            AgentCommand::CloseListenerAck(cmd_handle, self.try_close_listener(handle))
+        } else {
+            AgentCommand::CloseConnectionAck(cmd_handle, self.try_close_connection(handle))
+        };
+
+        return CommandExecutor::instance().send(Command::Agent(cmd))
+    }
+
+    fn try_close_connection(&mut self, conn_handle: i32) -> Result<(), CommonError> {
+        /* TODO check duplicates */
+        let mut closed = match self.agent_connections.iter().position(|ac| ac.conn_handle == conn_handle) {
+            Some(i) => {
+                self.agent_connections.remove(i);
+                true
+            }
+            None => false,
+        };
+        if !closed {
+            for agent_listener in &mut self.agent_listeners {
+                if let Some(i) = agent_listener.connections.iter().position(|&(id, _)| id == conn_handle) {
+                    agent_listener.connections.remove(i);
+                    agent_listener.connections_info.remove(&conn_handle);
+                    closed = true;
+                    break;
+                }
+            }
+        }
+        if !closed {
+            return Err(CommonError::InvalidStructure(format!("Can't close agent connection {} - not found", conn_handle)));
+        }
+        self.fail_pending(conn_handle);
+        Ok(())
+    }
+
+    fn try_close_listener(&mut self, listener_handle: i32) -> Result<(), CommonError> {
+        for i in 0..self.agent_listeners.len() {
+            if self.agent_listeners[i].listener_handle == listener_handle {
+                let listener = self.agent_listeners.remove(i);
+                for &(conn_handle, _) in &listener.connections {
+                    self.fail_pending(conn_handle);
+                }
+                return Ok(())
+            }
+        }
+        return Err(CommonError::InvalidStructure(format!("Can't close agent listener {} - not found", listener_handle)))
+    }
+
+    fn try_start_listen(&mut self, handle: i32, endpoint: String, max_msg_size: usize, rate_limit: Option<RateLimit>) -> Result<(), CommonError> {
+        let listener = AgentListener::new(handle, endpoint.clone(), max_msg_size, rate_limit).map_err(map_err_trace!("AgentListener::new"))?;
+        self.agent_listeners.push(listener);
+        info!("Agent listener started at {}", endpoint);
+        Ok(())
+    }
+
+    fn poll(&self) -> Result<Vec<AgentWorkerCommand>, CommonError> {
+        let mut result = Vec::new();
+        let mut poll_items: Vec<zmq::PollItem> = Vec::new();
+        poll_items.push(self.cmd_socket.as_poll_item(zmq::POLLIN));
+        let agent_connections_cnt = self.agent_connections.len();
+        let agent_listeners_cnt = self.agent_listeners.len();
+
+        for agent_conn in &self.agent_connections {
+            poll_items.push(agent_conn.transport.poll_item());
+        }
+
+        for agent_listener in &self.agent_listeners {
+            let agent_listener: &AgentListener = agent_listener;
+            poll_items.push(agent_listener.transport.poll_item());
+        }
+
+        let has_pending = !self.pending_acks.is_empty() || !self.pending_requests.is_empty();
+        let timeout = if agent_connections_cnt > 0 || has_pending { WORKER_TICK_MS } else { -1 };
+        zmq::poll(poll_items.as_mut_slice(), timeout).map_err(map_err_trace!("agent poll failed"))?;
+
+        if poll_items[0].is_readable() {
+            let mut frames = self.cmd_socket.recv_multipart(zmq::DONTWAIT)?;
+            let payload = if frames.len() > 1 { frames.pop() } else { None };
+            let msg = String::from_utf8(frames.remove(0)).map_err(|err|
+                CommonError::InvalidState(format!("Invalid input on agent cmd socket: {:?}", err.into_bytes())))?;
+            trace!("Input on cmd socket {}", msg);
+            let cmd = AgentWorkerCommand::from_json(msg.as_str()).map_err(|err|
+                CommonError::InvalidState(format!("Invalid input on agent cmd socket: {}", err.description())))?;
+            result.push(match cmd {
+                AgentWorkerCommand::SendBytes(mut cmd) => {
+                    cmd.msg = payload.unwrap_or(Vec::new());
+                    AgentWorkerCommand::SendBytes(cmd)
+                }
+                cmd => cmd,
+            });
+        }
+
+        for i in 0..agent_connections_cnt {
+            if poll_items[1 + i].is_readable() {
+                let msg = match self.agent_connections[i].transport.recv() {
+                    Ok(Some(msg)) => Ok(msg),
+                    Ok(None) => continue,
+                    Err(err) => Err(format!("{}", err)),
+                };
+                trace!("Input on remote agent transport {}: {:?}", i, msg);
+                result.push(AgentWorkerCommand::Response(Response {
+                    agent_ind: i,
+                    msg: msg,
+                }))
+            }
+        }
+        for i in 0..agent_listeners_cnt {
+            if poll_items[1 + agent_connections_cnt + i].is_readable() {
+                if let Some((identity, msg)) = self.agent_listeners[i].transport.recv()? {
+                    trace!("Input on agent listener transport {}: identity {}, msg {}", i, identity, String::from_utf8_lossy(&msg));
+                    result.push(AgentWorkerCommand::Request(Request {
+                        listener_ind: i,
+                        identity: identity,
+                        msg: msg,
+                    }));
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+impl RemoteAgent {
+    fn new(cmd: &ConnectCmd) -> Result<RemoteAgent, CommonError> {
+        let public_key = cmd.public_key.from_base58()
+            .map_err(|err| CommonError::InvalidStructure(format!("invalid pub_key {}", err)))?;
+        let secret_key = cmd.secret_key.from_base58()
+            .map_err(|err| CommonError::InvalidStructure(format!("invalid sec_key {}", err)))?;
+        let server_key = cmd.server_key.from_base58()
+            .map_err(|err| CommonError::InvalidStructure(format!("invalid server_key {}", err)))?;
+        Ok(RemoteAgent {
+            transport: transport::create_outbound_transport(cmd.endpoint.as_str(), public_key.clone(), secret_key.clone(), server_key.clone())?,
+            conn_handle: cmd.conn_handle,
+            state: ConnectionState::Connecting,
+            endpoint: cmd.endpoint.clone(),
+            public_key: public_key,
+            secret_key: secret_key,
+            server_key: server_key,
+            sender_did: cmd.sender_did.clone(),
+            receiver_did: cmd.receiver_did.clone(),
+            was_connected: false,
+            last_activity: Instant::now(),
+            last_ping: Instant::now(),
+            reconnect_attempts: 0,
+            reconnect_at: None,
+            queue: VecDeque::new(),
+            opened_at: _unix_timestamp(),
+            stats: ConnectionStats::default(),
+        })
+    }
+
+    fn connect(&mut self) -> Result<(), CommonError> {
+        self.state = ConnectionState::Connecting;
+        self.last_activity = Instant::now();
+        self.transport.connect(self.sender_did.as_str(), self.receiver_did.as_str())
+    }
+
+    fn reconnect(&mut self) -> Result<(), CommonError> {
+        info!("Reconnecting agent connection {} to {}, attempt {}", self.conn_handle, self.endpoint, self.reconnect_attempts);
+        self.transport = transport::create_outbound_transport(self.endpoint.as_str(),
+                                                              self.public_key.clone(),
+                                                              self.secret_key.clone(),
+                                                              self.server_key.clone())?;
+        self.connect()
+    }
+
+    /// Messages to established connection are queued while it is reconnecting
+    /// and flushed after DID_ACK is received again.
+    fn send(&mut self, msg: Vec<u8>) -> Result<(), AgentError> {
+        if self.state == ConnectionState::Connected {
+            match self.send_counted(msg.as_slice()) {
+                Ok(()) => return Ok(()),
+                /* connection loss will be detected by next heartbeat */
+                Err(err) => warn!("Can't send message to agent connection {}, message will be queued: {}", self.conn_handle, err)
+            }
+        } else if !self.was_connected {
+            return self.send_counted(msg.as_slice()).map_err(AgentError::from);
+        }
+        if self.queue.len() >= MAX_OUTBOUND_QUEUE_LEN {
+            return Err(AgentError::OutboundQueueFull(
+                format!("Connection {} already has {} queued messages", self.conn_handle, self.queue.len())));
+        }
+        self.queue.push_back(msg);
+        Ok(())
+    }
+
+    fn flush_queue(&mut self) {
+        while let Some(msg) = self.queue.pop_front() {
+            if let Err(err) = self.send_counted(msg.as_slice()) {
+                warn!("Can't flush queued messages of agent connection {}: {}", self.conn_handle, err);
+                self.queue.push_front(msg);
+                break;
+            }
+        }
+    }
+
+    fn send_counted(&mut self, msg: &[u8]) -> Result<(), CommonError> {
+        self.transport.send(msg)?;
+        self.stats.on_sent(msg.len());
+        Ok(())
+    }
+
+    fn handle_response(&mut self, msg: Result<Vec<u8>, CommonError>) -> Option<AgentCommand> {
+        if msg.is_ok() {
+            self.last_activity = Instant::now();
+        }
+        match self.state {
+            ConnectionState::Connecting => {
+                let res = msg.and_then(|msg| if msg.as_slice() == DID_ACK.as_bytes() {
+                    Ok(())
+                } else {
+                    Err(CommonError::InvalidState(format!("Expect DID_ACK, receive {}", String::from_utf8_lossy(&msg))))
+                });
+                match res {
+                    Ok(()) => self.on_connected(),
+                    Err(err) => {
+                        if self.was_connected {
+                            warn!("Reconnect of agent connection {} failed: {}", self.conn_handle, err);
+                            self.on_connection_lost(format!("{}", err))
+                        } else {
+                            self.state = ConnectionState::Disconnected;
+                            Some(AgentCommand::ConnectAck(self.conn_handle, Err(err)))
+                        }
+                    }
+                }
+            }
+            ConnectionState::Connected => match msg {
+                Ok(ref msg) if msg.as_slice() == PONG.as_bytes() => None,
+                Ok(msg) => {
+                    self.stats.on_received(msg.len());
+                    _incoming_message(self.conn_handle, msg)
+                }
+                Err(err) => self.on_connection_lost(format!("{}", err)),
+            },
+            ConnectionState::Disconnected => {
+                debug!("Ignore incoming data for disconnected agent connection {}: {:?}", self.conn_handle, msg);
+                None
+            }
+        }
+    }
+
+    /// Restore isn't notified: it is visible in connection info state and stats.
+    fn on_connected(&mut self) -> Option<AgentCommand> {
+        self.state = ConnectionState::Connected;
+        self.reconnect_attempts = 0;
+        self.reconnect_at = None;
+        self.last_ping = Instant::now();
+        if self.was_connected {
+            info!("Agent connection {} restored, {} queued messages to flush", self.conn_handle, self.queue.len());
+            self.stats.reconnects += 1;
+            self.flush_queue();
+            None
+        } else {
+            self.was_connected = true;
+            Some(AgentCommand::ConnectAck(self.conn_handle, Ok(self.conn_handle)))
+        }
+    }
+
+    /// User is notified only once per loss: failed reconnect attempts are silent.
+    fn on_connection_lost(&mut self, reason: String) -> Option<AgentCommand> {
+        let prev_state = self.state;
+        self.state = ConnectionState::Disconnected;
+        self.schedule_reconnect();
+        if prev_state == ConnectionState::Connected {
+            warn!("Agent connection {} lost: {}", self.conn_handle, reason);
+            Some(AgentCommand::MessageReceived(self.conn_handle, Err(AgentError::ConnectionLost(reason))))
+        } else {
+            None
+        }
+    }
+
+    fn schedule_reconnect(&mut self) {
+        let delay = cmp::min(RECONNECT_MIN_DELAY_SECS << cmp::min(self.reconnect_attempts, 16),
+                             RECONNECT_MAX_DELAY_SECS);
+        self.reconnect_attempts += 1;
+        self.reconnect_at = Some(Instant::now() + Duration::from_secs(delay));
+    }
+
+    fn check_liveness(&mut self, now: Instant) -> Option<AgentCommand> {
+        let heartbeat_timeout = Duration::from_secs(HEARTBEAT_TIMEOUT_SECS);
+        match self.state {
+            ConnectionState::Connected => {
+                if now.duration_since(self.last_activity) > heartbeat_timeout {
+                    return self.on_connection_lost("heartbeat timeout".to_string());
+                }
+                if now.duration_since(self.last_ping) >= Duration::from_secs(HEARTBEAT_INTERVAL_SECS) {
+                    self.last_ping = now;
+                    if let Err(err) = self.transport.send(PING.as_bytes()) {
+                        return self.on_connection_lost(format!("{}", err));
+                    }
+                }
+                None
+            }
+            ConnectionState::Connecting => {
+                if self.was_connected && now.duration_since(self.last_activity) > heartbeat_timeout {
+                    warn!("Reconnect of agent connection {} timed out", self.conn_handle);
+                    return self.on_connection_lost("reconnect timeout".to_string());
+                }
+                None
+            }
+            ConnectionState::Disconnected => {
+                let reconnect_at = self.reconnect_at;
+                if reconnect_at.map(|reconnect_at| now >= reconnect_at).unwrap_or(false) {
+                    self.reconnect_at = None;
+                    if let Err(err) = self.reconnect() {
+                        warn!("Reconnect of agent connection {} failed: {}", self.conn_handle, err);
+                        self.state = ConnectionState::Disconnected;
+                        self.schedule_reconnect();
+                    }
+                }
+                None
+            }
+        }
+    }
+}
+
+impl AgentListener {
+    fn new(handle: i32, endpoint: String, max_msg_size: usize, rate_limit: Option<RateLimit>) -> Result<AgentListener, CommonError> {
+        Ok(AgentListener {
+            connections: Vec::new(),
+            connections_info: HashMap::new(),
+            pending_connections: HashMap::new(),
+            did_resources: HashMap::new(),
+            listener_handle: handle,
+            transport: transport::create_inbound_transport(endpoint.as_str(), max_msg_size)?,
+            endpoint: endpoint,
+            rate_limiter: rate_limit.map(RateLimiter::new),
+        })
+    }
+
+    /// Returns command with message received by established connection.
+    fn handle_request(&mut self, identity: String, msg: Vec<u8>) -> Result<Option<AgentCommand>, CommonError> {
+        let did_msg = str::from_utf8(msg.as_slice()).ok().and_then(|msg| MsgDID::from_json(msg).ok());
+
+        if let Some(&(conn_handle, _)) = self.connections.iter().find(|&&(_, ref id)| identity.eq(id.as_str())) {
+            if msg.as_slice() == PING.as_bytes() {
+                self.transport.send(identity.as_str(), PONG.as_bytes())?;
+                return Ok(None);
+            }
+            if did_msg.is_none() {
+                if let Some(conn) = self.connections_info.get_mut(&conn_handle) {
+                    conn.stats.on_received(msg.len());
+                }
+                return Ok(_incoming_message(conn_handle, msg));
+            }
+            /* reconnect of known client is checked as new connection: DIDs, policy and authorization may change */
+            info!("Agent connection {} reconnect requested by {}", conn_handle, identity);
+        }
+
+        let is_limited = {
+            let sender_did = did_msg.as_ref().map(|did_msg| DidUtils::normalize(did_msg.did.sender_did.as_str()));
+            self.rate_limiter.as_mut()
+                .map(|rate_limiter| !rate_limiter.check(identity.as_str(), sender_did, Instant::now()))
+                .unwrap_or(false)
+        };
+        if is_limited {
+            info!("Connection attempt from {} to agent listener rejected by rate limit", identity);
+            self.transport.send(identity.as_str(), RATE_LIMITED.as_bytes())?;
+            return Ok(None);
+        }
+
+        if let Some(did_msg) = did_msg {
+            info!("New connection to agent listener from {} with msg {}", identity, String::from_utf8_lossy(&msg));
+            self.pending_connections.insert(did_msg.did.sender_did.clone(),
+                                            (identity.clone(), did_msg.did.receiver_did.clone()));
+            let (pool, wallet) = *self.did_resources
+                .get(&did_msg.did.receiver_did)
+                .unwrap_or(&(-1, -1));
+            let cmd = AgentCommand::ListenerCheckConnect(
+                did_msg.did.sender_did, did_msg.did.receiver_did, identity, self.listener_handle, pool, wallet);
+            CommandExecutor::instance().send(Command::Agent(cmd))?;
+        } else {
+            info!("Message {} from unknown connection to agent listener from {}", String::from_utf8_lossy(&msg), identity);
+            // TODO may be notify: ListenOnConnect(self.listener_handle, Err(incorrect connection))
+            self.transport.send(identity.as_str(), "NOT_CONNECTED".as_bytes())?;
+        }
+        Ok(None)
+    }
+
+    fn on_connect_checked(&mut self, sender_did: String, is_ok: bool) -> Result<(), CommonError> {
+        let (sender_pk, receiver_did) = self.pending_connections.remove(&sender_did).unwrap();
+
+        /* only successful check proves that sender owns DID key */
+        if is_ok {
+            if let Some(ref mut rate_limiter) = self.rate_limiter {
+                rate_limiter.on_verified(DidUtils::normalize(sender_did.as_str()), Instant::now());
+            }
+        }
+
+        let restored = self.connections.iter()
+            .find(|&&(_, ref id)| sender_pk.eq(id.as_str()))
+            .map(|&(conn_handle, _)| conn_handle);
+
+        if let Some(conn_handle) = restored {
+            let is_ok = is_ok && self.connections_info.get(&conn_handle)
+                .map(|conn| conn.sender_did == sender_did && conn.receiver_did == receiver_did)
+                .unwrap_or(false);
+            if is_ok {
+                info!("Agent connection {} restored", conn_handle);
+                if let Some(conn) = self.connections_info.get_mut(&conn_handle) {
+                    conn.stats.reconnects += 1;
+                }
+                return self.transport.send(sender_pk.as_str(), DID_ACK.as_bytes());
+            }
+
+            info!("Agent connection {} closed: reconnect is rejected", conn_handle);
+            self.connections.retain(|&(id, _)| id != conn_handle);
+            self.connections_info.remove(&conn_handle);
+            let cmd = AgentCommand::MessageReceived(conn_handle, Err(AgentError::ConnectionLost(
+                format!("Reconnect of connection {} is rejected", conn_handle))));
+            CommandExecutor::instance().send(Command::Agent(cmd))?;
+            return self.transport.send(sender_pk.as_str(), "DID_NACK".as_bytes());
+        }
+
+        let conn_handle = SequenceUtils::get_next_id();
+        if is_ok {
+            self.connections.push((conn_handle, sender_pk.clone()));
+            self.connections_info.insert(conn_handle, IncomingConnection {
+                sender_did: sender_did.clone(),
+                receiver_did: receiver_did.clone(),
+                opened_at: _unix_timestamp(),
+                stats: ConnectionStats::default(),
+            });
+            let cmd = AgentCommand::ListenerOnConnect(self.listener_handle,
+                                                      Ok((self.listener_handle, conn_handle,
+                                                          sender_did.clone(), receiver_did)));
+            CommandExecutor::instance().send(Command::Agent(cmd))?;
+        }
+        let response = if is_ok { DID_ACK } else { "DID_NACK" };
+        self.transport.send(sender_pk.as_str(), response.as_bytes())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct MsgDID {
+    did: DID,
+}
+
+impl JsonEncodable for MsgDID {}
+
+impl<'a> JsonDecodable<'a> for MsgDID {}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct DID {
+    sender_did: String,
+    receiver_did: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct MsgEnvelope {
+    msg_envelope: Envelope,
+}
+
+impl JsonEncodable for MsgEnvelope {}
+
+impl<'a> JsonDecodable<'a> for MsgEnvelope {}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Envelope {
+    id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reply_to: Option<String>,
+    content: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct MsgAck {
+    msg_ack: String, /* id of acknowledged message */
+}
+
+impl JsonEncodable for MsgAck {}
+
+impl<'a> JsonDecodable<'a> for MsgAck {}
+
+/// Sent back by listener instead of passing message that exceeds maximum size.
+#[derive(Serialize, Deserialize, Debug)]
+struct MsgTooLarge {
+    msg_too_large: TooLarge,
+}
+
+impl MsgTooLarge {
+    fn new(size: usize, max_size: usize) -> MsgTooLarge {
+        MsgTooLarge {
+            msg_too_large: TooLarge {
+                size: size,
+                max_size: max_size,
+            }
+        }
+    }
+}
+
+impl JsonEncodable for MsgTooLarge {}
+
+impl<'a> JsonDecodable<'a> for MsgTooLarge {}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct TooLarge {
+    size: usize,
+    max_size: usize,
+}
+
+#[derive(Serialize, Debug)]
+struct ConnectionInfo {
+    connection_handle: i32,
+    sender_did: String,
+    receiver_did: String,
+    endpoint: String,
+    direction: ConnectionDirection,
+    listener_handle: Option<i32>,
+    state: ConnectionState,
+    opened_at: u64,
+    stats: ConnectionStats,
+}
+
+impl JsonEncodable for ConnectionInfo {}
+
+#[derive(Deserialize, Debug, Default)]
+struct ListenerConfig {
+    max_message_size: Option<usize>,
+    rate_limit: Option<RateLimit>,
+}
+
+impl<'a> JsonDecodable<'a> for ListenerConfig {}
+
+#[serde(tag = "cmd")]
+#[derive(Serialize, Deserialize, Debug)]
+enum AgentWorkerCommand {
+    Connect(ConnectCmd),
+    ConnectCheck(ConnectCheckCmd),
+    Listen(ListenCmd),
+    AddIdentity(AddIdentityCmd),
+    RmIdentity(RmIdentityCmd),
+    Response(Response),
+    Request(Request),
+    Send(SendCmd),
+    SendBytes(SendBytesCmd),
+    SendRequest(SendRequestCmd),
+    GetConnections(GetConnectionsCmd),
+    Close(CloseCmd),
+    Exit,
+}
+
+impl JsonEncodable for AgentWorkerCommand {}
+
+impl<'a> JsonDecodable<'a> for AgentWorkerCommand {}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct ConnectCmd {
+    endpoint: String,
+    sender_did: String,
+    receiver_did: String,
+    secret_key: String,
+    public_key: String,
+    server_key: String,
+    conn_handle: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct ConnectCheckCmd {
+    listener_handle: i32,
+    did: String,
+    result: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct ListenCmd {
+    listen_handle: i32,
+    endpoint: String,
+    max_msg_size: usize,
+    rate_limit: Option<RateLimit>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct AddIdentityCmd {
+    cmd_id: i32,
+    listen_handle: i32,
+    did: String,
+    pool_handle: i32,
+    wallet_handle: i32,
+    pk: String,
+    sk: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct RmIdentityCmd {
+    cmd_id: i32,
+    listen_handle: i32,
+    did: String,
+    pk: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct SendCmd {
+    cmd_id: i32,
+    conn_handle: i32,
+    msg: Option<String>,
+    msg_id: Option<String>,
+    reply_to: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct SendBytesCmd {
+    cmd_id: i32,
+    conn_handle: i32,
+    /* passed by separate frame of cmd socket */
+    #[serde(skip_serializing, skip_deserializing)]
+    msg: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct SendRequestCmd {
+    cmd_id: i32,
+    conn_handle: i32,
+    msg_id: String,
+    msg: String,
+    timeout_ms: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Response {
+    agent_ind: usize,
+    msg: Result<Vec<u8>, String /* transport error description */>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct Request {
+    listener_ind: usize,
+    identity: String,
+    msg: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct CloseCmd {
+    cmd_id: i32,
+    handle: i32,
+    close_listener: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct GetConnectionsCmd {
+    cmd_id: i32,
+    conn_handle: Option<i32>,
+}
+
+fn _send_to_executor(cmd: AgentCommand) {
+    if let Err(err) = CommandExecutor::instance().send(Command::Agent(cmd)) {
+        error!("Agent worker can't pass command to executor {}", err);
+    };
+}
+
+fn _frame(kind: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(payload.len() + 2);
+    frame.push(FRAME_MARKER);
+    frame.push(kind);
+    frame.extend_from_slice(payload);
+    frame
+}
+
+fn _protocol_frame<T: JsonEncodable>(msg: &T) -> Result<Vec<u8>, CommonError> {
+    let msg = msg.to_json()
+        .map_err(|err| CommonError::InvalidState(format!("Can't serialize protocol message {}", err.description())))?;
+    Ok(_frame(PROTOCOL_FRAME, msg.as_bytes()))
+}
+
+/// Binary message is passed to executor as is, text and protocol ones must be valid UTF-8.
+fn _incoming_message(conn_handle: i32, msg: Vec<u8>) -> Option<AgentCommand> {
+    if msg.first() != Some(&FRAME_MARKER) {
+        return match String::from_utf8(msg) {
+            Ok(msg) => Some(AgentCommand::MessageReceived(conn_handle, Ok((msg, None)))),
+            Err(_) => {
+                warn!("Ignore message on agent connection {}: should be valid UTF-8 string", conn_handle);
+                None
+            }
+        };
+    }
+    match msg.get(1) {
+        Some(&BINARY_FRAME) => Some(AgentCommand::BinaryMessageReceived(conn_handle, msg[2..].to_vec())),
+        Some(&PROTOCOL_FRAME) => match String::from_utf8(msg[2..].to_vec()) {
+            Ok(msg) => Some(AgentCommand::ProtocolMessageReceived(conn_handle, msg)),
+            Err(_) => {
+                warn!("Ignore protocol message on agent connection {}: should be valid UTF-8 string", conn_handle);
+                None
+            }
+        },
+        _ => {
+            warn!("Ignore frame of unknown kind on agent connection {}", conn_handle);
+            None
+        }
+    }
+}
+
+fn _unix_timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_secs()).unwrap_or(0)
+}
+
+fn _generate_msg_id() -> String {
+    Uuid::new_v4().to_string()
+}
+
+fn _create_zmq_socket_pair(address: &str, connect_and_bind: bool) -> Result<(zmq::Socket, zmq::Socket), zmq::Error> {
+    let ctx = zmq::Context::new();
+    let recv_soc = ctx.socket(zmq::SocketType::PAIR)?;
+    let send_soc = ctx.socket(zmq::SocketType::PAIR)?;
+    if connect_and_bind {
+        let address = format!("inproc://{}", address);
+        recv_soc.bind(&address)?;
+        send_soc.connect(&address)?;
+    }
+    Ok((send_soc, recv_soc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::mpsc::channel;
+
+    use utils::timeout::TimeoutUtils;
+
+    #[test]
+    fn agent_can_be_dropped() {
+        let (sender, receiver) = channel();
+        thread::spawn(move || {
+            #[allow(unused_variables)]
+            {
+                let agent = Agent::new();
+            }
+            sender.send(true).unwrap();
+        });
+        receiver.recv_timeout(TimeoutUtils::short_timeout()).expect("drop not finished");
+    }
+
+    mod agent_service {
+        use super::*;
+
+        #[test]
+        fn agent_service_connect_works() {
+            let (sender, receiver) = channel();
+            let (send_soc, recv_soc) = _create_zmq_socket_pair("test_connect", true).unwrap();
+            let agent = Agent {
+                cmd_socket: send_soc,
+                worker: Some(thread::spawn(move || {
+                    sender.send(recv_soc.recv_string(0).unwrap().unwrap()).unwrap();
+                    recv_soc.recv_string(0).unwrap().unwrap();
+                }))
+            };
+            let agent_service = AgentService {
+                agent: agent,
+            };
+            let conn_handle = agent_service.connect("sd", "rd", "sk", "pk", "ep", "serv").unwrap();
+            let expected_cmd = ConnectCmd {
+                server_key: "serv".to_string(),
+                public_key: "pk".to_string(),
+                secret_key: "sk".to_string(),
+                endpoint: "ep".to_string(),
+                sender_did: "sd".to_string(),
+                receiver_did: "rd".to_string(),
+                conn_handle: conn_handle,
+            };
+            let str = receiver.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+            assert_eq!(str, AgentWorkerCommand::Connect(expected_cmd).to_json().unwrap());
+        }
+
+        #[test]
+        fn agent_service_listen_works() {
+            let (sender, receiver) = channel();
+            let (send_soc, recv_soc) = _create_zmq_socket_pair("test_connect", true).unwrap();
+            let agent = Agent {
+                cmd_socket: send_soc,
+                worker: Some(thread::spawn(move || {
+                    sender.send(recv_soc.recv_string(0).unwrap().unwrap()).unwrap();
+                    recv_soc.recv_string(0).unwrap().unwrap();
+                }))
+            };
+            let agent_service = AgentService {
+                agent: agent,
+            };
+            let conn_handle = agent_service.listen("endpoint", Some(r#"{"max_message_size":1000,"rate_limit":{"max_attempts":5,"interval_secs":60}}"#)).unwrap();
+            let expected_cmd = ListenCmd {
+                listen_handle: conn_handle,
+                endpoint: "endpoint".to_string(),
+                max_msg_size: 1000,
+                rate_limit: Some(RateLimit { max_attempts: 5, interval_secs: 60, max_total_attempts: None }),
+            };
+            let str = receiver.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+            assert_eq!(str, AgentWorkerCommand::Listen(expected_cmd).to_json().unwrap());
+        }
+
+        #[test]
+        fn agent_service_listen_works_for_invalid_config() {
+            let (send_soc, recv_soc) = _create_zmq_socket_pair("test_listen_invalid_config", true).unwrap();
+            let agent = Agent {
+                cmd_socket: send_soc,
+                worker: Some(thread::spawn(move || {
+                    recv_soc.recv_string(0).unwrap().unwrap();
+                }))
+            };
+            let agent_service = AgentService {
+                agent: agent,
+            };
+            assert_match!(Err(CommonError::InvalidStructure(_)), agent_service.listen("endpoint", Some(r#"{"max_message_size":0}"#)));
+            assert_match!(Err(CommonError::InvalidStructure(_)), agent_service.listen("endpoint", Some("max_message_size")));
+            assert_match!(Err(CommonError::InvalidStructure(_)), agent_service.listen("endpoint", Some(r#"{"rate_limit":{"max_attempts":0,"interval_secs":60}}"#)));
+        }
+
+        #[test]
+        fn agent_service_add_identity_works() {
+            let (sender, receiver) = channel();
+            let (send_soc, recv_soc) = _create_zmq_socket_pair("test_connect", true).unwrap();
+            let agent = Agent {
+                cmd_socket: send_soc,
+                worker: Some(thread::spawn(move || {
+                    sender.send(recv_soc.recv_string(0).unwrap().unwrap()).unwrap();
+                    recv_soc.recv_string(0).unwrap().unwrap();
+                }))
+            };
+            let agent_service = AgentService {
+                agent: agent,
+            };
+            let listener_handle = SequenceUtils::get_next_id();
+            let wallet_handle = SequenceUtils::get_next_id();
+            let pool_handle = SequenceUtils::get_next_id();
+            let cmd_handle = agent_service.add_identity(listener_handle, "did", pool_handle, wallet_handle, "sk", "pk").unwrap();
+            let expected_cmd = AddIdentityCmd {
+                cmd_id: cmd_handle,
+                listen_handle: listener_handle,
+                pool_handle: pool_handle,
+                wallet_handle: wallet_handle,
+                did: "did".to_string(),
+                pk: "pk".to_string(),
+                sk: "sk".to_string(),
+            };
+            let str = receiver.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+            assert_eq!(str, AgentWorkerCommand::AddIdentity(expected_cmd).to_json().unwrap());
+        }
+
+        #[test]
+        fn agent_service_rm_identity_works() {
+            let (sender, receiver) = channel();
+            let (send_soc, recv_soc) = _create_zmq_socket_pair("test_connect", true).unwrap();
+            let agent = Agent {
+                cmd_socket: send_soc,
+                worker: Some(thread::spawn(move || {
+                    sender.send(recv_soc.recv_string(0).unwrap().unwrap()).unwrap();
+                    recv_soc.recv_string(0).unwrap().unwrap();
+                }))
+            };
+            let agent_service = AgentService {
+                agent: agent,
+            };
+            let listener_handle = SequenceUtils::get_next_id();
+            let cmd_handle = agent_service.rm_identity(listener_handle, "did", "pk").unwrap();
+            let expected_cmd = RmIdentityCmd {
+                cmd_id: cmd_handle,
+                listen_handle: listener_handle,
+                did: "did".to_string(),
+                pk: "pk".to_string(),
+            };
+            let str = receiver.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+            assert_eq!(str, AgentWorkerCommand::RmIdentity(expected_cmd).to_json().unwrap());
+        }
+
+        #[test]
+        fn agent_service_send_works() {
+            let (sender, receiver) = channel();
+            let (send_soc, recv_soc) = _create_zmq_socket_pair("test_send", true).unwrap();
+            let agent = Agent {
+                cmd_socket: send_soc,
+                worker: Some(thread::spawn(move || {
+                    sender.send(recv_soc.recv_string(0).unwrap().unwrap()).unwrap();
+                    recv_soc.recv_string(0).unwrap().unwrap();
+                }))
+            };
+            let agent_service = AgentService {
+                agent: agent,
+            };
+            let conn_handle = SequenceUtils::get_next_id();
+            let msg = Some("test_msg");
+            let cmd_id = agent_service.send(conn_handle, msg).unwrap();
+            let expected_cmd = SendCmd {
+                cmd_id: cmd_id,
+                conn_handle: conn_handle,
+                msg: msg.map(str::to_string),
+                msg_id: None,
+                reply_to: None,
+            };
+            let str = receiver.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+            assert_eq!(str, AgentWorkerCommand::Send(expected_cmd).to_json().unwrap());
+        }
+
+        #[test]
+        fn agent_service_get_connection_info_works() {
+            let (sender, receiver) = channel();
+            let (send_soc, recv_soc) = _create_zmq_socket_pair("test_get_connection_info", true).unwrap();
+            let agent = Agent {
+                cmd_socket: send_soc,
+                worker: Some(thread::spawn(move || {
+                    sender.send(recv_soc.recv_string(0).unwrap().unwrap()).unwrap();
+                    recv_soc.recv_string(0).unwrap().unwrap();
+                }))
+            };
+            let agent_service = AgentService {
+                agent: agent,
+            };
+            let conn_handle = SequenceUtils::get_next_id();
+            let cmd_id = agent_service.get_connection_info(conn_handle).unwrap();
+            let expected_cmd = GetConnectionsCmd {
+                cmd_id: cmd_id,
+                conn_handle: Some(conn_handle),
+            };
+            let str = receiver.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+            assert_eq!(str, AgentWorkerCommand::GetConnections(expected_cmd).to_json().unwrap());
+        }
+
+        #[test]
+        fn agent_service_send_bytes_works() {
+            let (sender, receiver) = channel();
+            let (send_soc, recv_soc) = _create_zmq_socket_pair("test_send_bytes", true).unwrap();
+            let agent = Agent {
+                cmd_socket: send_soc,
+                worker: Some(thread::spawn(move || {
+                    sender.send(recv_soc.recv_multipart(0).unwrap()).unwrap();
+                    recv_soc.recv_string(0).unwrap().unwrap();
+                }))
+            };
+            let agent_service = AgentService {
+                agent: agent,
+            };
+            let conn_handle = SequenceUtils::get_next_id();
+            let cmd_handle = agent_service.send_bytes(conn_handle, &[0, 159, 146, 150]).unwrap();
+            let frames = receiver.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+            assert_eq!(frames.len(), 2);
+            assert_eq!(String::from_utf8(frames[0].clone()).unwrap(),
+                       format!(r#"{{"cmd":"SendBytes","cmd_id":{},"conn_handle":{}}}"#, cmd_handle, conn_handle));
+            assert_eq!(frames[1], vec![0, 159, 146, 150]);
+        }
+
+        #[test]
+        fn agent_service_send_with_ack_works() {
+            let (sender, receiver) = channel();
+            let (send_soc, recv_soc) = _create_zmq_socket_pair("test_send_with_ack", true).unwrap();
+            let agent = Agent {
+                cmd_socket: send_soc,
+                worker: Some(thread::spawn(move || {
+                    sender.send(recv_soc.recv_string(0).unwrap().unwrap()).unwrap();
+                    recv_soc.recv_string(0).unwrap().unwrap();
+                }))
+            };
+            let agent_service = AgentService {
+                agent: agent,
+            };
+            let conn_handle = SequenceUtils::get_next_id();
+            let (cmd_id, msg_id) = agent_service.send_with_ack(conn_handle, Some("msg_id"), Some("request_id"), "test_msg").unwrap();
+            assert_eq!(msg_id, "msg_id");
+            let expected_cmd = SendCmd {
+                cmd_id: cmd_id,
+                conn_handle: conn_handle,
+                msg: Some("test_msg".to_string()),
+                msg_id: Some("msg_id".to_string()),
+                reply_to: Some("request_id".to_string()),
+            };
+            let str = receiver.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+            assert_eq!(str, AgentWorkerCommand::Send(expected_cmd).to_json().unwrap());
+        }
+
+        #[test]
+        fn agent_service_close_connection_or_listener_works() {
+            let (sender, receiver) = channel();
+            let (send_soc, recv_soc) = _create_zmq_socket_pair("test_close_conn", true).unwrap();
+            let agent = Agent {
+                cmd_socket: send_soc,
+                worker: Some(thread::spawn(move || {
+                    sender.send(recv_soc.recv_string(0).unwrap().unwrap()).unwrap();
+                    recv_soc.recv_string(0).unwrap().unwrap();
+                }))
+            };
+            let agent_service = AgentService {
+                agent: agent,
+            };
+            let conn_handle = SequenceUtils::get_next_id();
+            let cmd_id = agent_service.close_connection_or_listener(conn_handle, true).unwrap();
+            let expected_cmd = CloseCmd {
+                cmd_id: cmd_id,
+                handle: conn_handle,
+                close_listener: true,
+            };
+            let str = receiver.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+            assert_eq!(str, AgentWorkerCommand::Close(expected_cmd).to_json().unwrap());
+        }
+    }
+
+    mod agent_worker {
+        use super::*;
+        use super::rust_base58::ToBase58;
+        use super::zmq_transport::{ZmqInbound, ZmqOutbound};
+
+        fn _zmq_outbound(socket: zmq::Socket) -> Box<OutboundTransport> {
+            Box::new(ZmqOutbound {
+                socket: socket,
+                addr: String::new(),
+                public_key: Vec::new(),
+                secret_key: Vec::new(),
+                server_key: Vec::new(),
+            })
+        }
+
+        fn _zmq_inbound(socket: zmq::Socket) -> Box<InboundTransport> {
+            Box::new(ZmqInbound { socket: socket, max_msg_size: DEFAULT_MAX_MSG_SIZE })
+        }
+
+        fn _remote_agent(transport: Box<OutboundTransport>, conn_handle: i32) -> RemoteAgent {
+            RemoteAgent {
+                transport: transport,
+                conn_handle: conn_handle,
+                state: ConnectionState::Connecting,
+                endpoint: String::new(),
+                public_key: Vec::new(),
+                secret_key: Vec::new(),
+                server_key: Vec::new(),
+                sender_did: String::new(),
+                receiver_did: String::new(),
+                was_connected: false,
+                last_activity: Instant::now(),
+                last_ping: Instant::now(),
+                reconnect_attempts: 0,
+                reconnect_at: None,
+                queue: VecDeque::new(),
+                opened_at: 0,
+                stats: ConnectionStats::default(),
+            }
+        }
+
+        #[test]
+        fn agent_worker_connect_works() {
+            ::utils::logger::LoggerUtils::init();
+            let send_key_pair = zmq::CurveKeyPair::new().unwrap();
+            let recv_key_pair = zmq::CurveKeyPair::new().unwrap();
+            let ctx = zmq::Context::new();
+            let recv_soc = ctx.socket(zmq::SocketType::ROUTER).unwrap();
+            recv_soc.set_curve_server(true).unwrap();
+            recv_soc.add_curve_keypair([recv_key_pair.public_key, recv_key_pair.secret_key].concat().as_slice()).unwrap();
+            recv_soc.bind("tcp://127.0.0.1:*").unwrap();
+            let addr = recv_soc.get_last_endpoint().unwrap().unwrap();
+            trace!("addr {}", addr);
+
+            let mut agent_worker = AgentWorker {
+                agent_connections: Vec::new(),
+                agent_listeners: Vec::new(),
+                cmd_socket: zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap(),
+                pending_acks: HashMap::new(),
+                pending_requests: HashMap::new(),
+            };
+            let cmd = ConnectCmd {
+                endpoint: addr[6..].to_string(),
+                public_key: send_key_pair.public_key.to_base58(),
+                secret_key: send_key_pair.secret_key.to_base58(),
+                sender_did: "sd".to_string(),
+                receiver_did: "rd".to_string(),
+                server_key: recv_key_pair.public_key.to_base58(),
+                conn_handle: 0,
+            };
+
+            agent_worker.connect(cmd).unwrap();
+
+            assert_eq!(agent_worker.agent_connections.len(), 1);
+            recv_soc.recv_string(0).unwrap().unwrap(); //ignore identity
+            assert_eq!(recv_soc.recv_string(zmq::DONTWAIT).unwrap().unwrap(), r#"{"did":{"sender_did":"sd","receiver_did":"rd"}}"#);
+        }
+
+        #[test]
+        fn agent_worker_poll_works_for_cmd_socket() {
+            let (send_soc, recv_soc) = _create_zmq_socket_pair("aw_poll_cmd", true).unwrap();
+            let agent_worker = AgentWorker {
+                agent_connections: Vec::new(),
+                agent_listeners: Vec::new(),
+                cmd_socket: recv_soc,
+                pending_acks: HashMap::new(),
+                pending_requests: HashMap::new(),
+            };
+            send_soc.send(r#"{"cmd": "Exit"}"#, zmq::DONTWAIT).unwrap();
+
+            let cmds = agent_worker.poll().unwrap();
+
+            assert_eq!(cmds.len(), 1);
+            assert_match!(AgentWorkerCommand::Exit, cmds[0]);
+        }
+
+        #[test]
+        fn agent_worker_poll_works_for_agent_socket() {
+            let (send_soc, recv_soc) = _create_zmq_socket_pair("aw_poll_cmd", true).unwrap();
+            let agent_worker = AgentWorker {
+                agent_connections: vec!(_remote_agent(_zmq_outbound(recv_soc), 0)),
+                agent_listeners: Vec::new(),
+                cmd_socket: zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap(),
+                pending_acks: HashMap::new(),
+                pending_requests: HashMap::new(),
+            };
+            send_soc.send("msg", zmq::DONTWAIT).unwrap();
+
+            let mut cmds = agent_worker.poll().unwrap();
+
+            assert_eq!(cmds.len(), 1);
+            let cmd = cmds.remove(0);
+            match cmd {
+                AgentWorkerCommand::Response(resp) => {
+                    assert_eq!(resp.agent_ind, 0);
+                    assert_eq!(resp.msg.unwrap(), "msg".as_bytes());
+                }
+                _ => panic!("unexpected cmd {:?}", cmd),
+            }
+        }
+
+        #[test]
+        fn agent_worker_poll_works_for_listener() {
+            let (send_soc, recv_soc, identity) = {
+                let ctx = zmq::Context::new();
+                let server_keys = zmq::CurveKeyPair::new().unwrap();
+                let client_keys = zmq::CurveKeyPair::new().unwrap();
+                let recv_soc = ctx.socket(zmq::ROUTER).unwrap();
+                recv_soc.set_curve_publickey(&server_keys.public_key).unwrap();
+                recv_soc.set_curve_secretkey(&server_keys.secret_key).unwrap();
+                recv_soc.set_curve_server(true).unwrap();
+                recv_soc.bind("tcp://127.0.0.1:*").unwrap();
+                let send_soc = ctx.socket(zmq::DEALER).unwrap();
+                let identity = zmq::z85_encode(&client_keys.public_key).unwrap();
+                send_soc.set_identity(identity.as_bytes()).unwrap();
+                send_soc.set_curve_publickey(&client_keys.public_key).unwrap();
+                send_soc.set_curve_secretkey(&client_keys.secret_key).unwrap();
+                send_soc.set_curve_serverkey(&server_keys.public_key).unwrap();
+                send_soc.connect(recv_soc.get_last_endpoint().unwrap().unwrap().as_str()).unwrap();
+                (send_soc, recv_soc, identity)
+            };
+            let agent_worker = AgentWorker {
+                agent_listeners: vec!(AgentListener {
+                    connections: Vec::new(),
+                    pending_connections: HashMap::new(),
+                    did_resources: HashMap::new(),
+                    listener_handle: 0,
+                    rate_limiter: None,
+                    connections_info: HashMap::new(),
+                    endpoint: "endpoint".to_string(),
+                    transport: _zmq_inbound(recv_soc),
+                }),
+                agent_connections: Vec::new(),
+                cmd_socket: zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap(),
+                pending_acks: HashMap::new(),
+                pending_requests: HashMap::new(),
+            };
+            send_soc.send("msg", zmq::DONTWAIT).unwrap();
+
+            let mut cmds = agent_worker.poll().unwrap();
+
+            assert_eq!(cmds.len(), 1);
+            let cmd = cmds.remove(0);
+            match cmd {
+                AgentWorkerCommand::Request(req) => {
+                    assert_eq!(req.listener_ind, 0);
+                    assert_eq!(req.identity, identity);
+                    assert_eq!(req.msg, "msg".as_bytes());
+                }
+                _ => panic!("unexpected cmd {:?}", cmd),
+            }
+        }
+
+        #[test]
+        fn agent_worker_try_close_connection_works_for_not_found() {
+            let mut agent_worker = AgentWorker {
+                agent_connections: Vec::new(),
+                agent_listeners: Vec::new(),
+                cmd_socket: zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap(),
+                pending_acks: HashMap::new(),
+                pending_requests: HashMap::new(),
+            };
+            let conn_handle = SequenceUtils::get_next_id();
+
+            let res = agent_worker.try_close_connection(conn_handle);
+
+            assert_match!(Err(CommonError::InvalidStructure(_)), res);
+        }
+
+        #[test]
+        fn agent_worker_try_close_connection_works_for_listener() {
+            let conn_handle = SequenceUtils::get_next_id();
+            let mut agent_worker = AgentWorker {
+                agent_connections: Vec::new(),
+                agent_listeners: vec![AgentListener {
+                    transport: _zmq_inbound(zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap()),
+                    connections: vec![(conn_handle, "test_identity".to_string())],
+                    pending_connections: HashMap::new(),
+                    did_resources: HashMap::new(),
+                    listener_handle: SequenceUtils::get_next_id(),
+                    rate_limiter: None,
+                    connections_info: HashMap::new(),
+                    endpoint: "endpoint".to_string(),
+                }],
+                cmd_socket: zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap(),
+                pending_acks: HashMap::new(),
+                pending_requests: HashMap::new(),
+            };
+
+            agent_worker.try_close_connection(conn_handle).unwrap();
+        }
+
+        #[test]
+        fn agent_worker_try_close_connection_works_for_connection() {
+            let conn_handle = SequenceUtils::get_next_id();
+            let mut agent_worker = AgentWorker {
+                agent_connections: vec![_remote_agent(_zmq_outbound(zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap()), conn_handle)],
+                agent_listeners: Vec::new(),
+                cmd_socket: zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap(),
+                pending_acks: HashMap::new(),
+                pending_requests: HashMap::new(),
+            };
+
+            agent_worker.try_close_connection(conn_handle).unwrap();
+        }
+
+        #[test]
+        fn agent_worker_try_close_listener_works() {
+            let listener_handle = SequenceUtils::get_next_id();
+            let mut agent_worker = AgentWorker {
+                agent_connections: Vec::new(),
+                agent_listeners: vec![AgentListener {
+                    transport: _zmq_inbound(zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap()),
+                    connections: Vec::new(),
+                    pending_connections: HashMap::new(),
+                    did_resources: HashMap::new(),
+                    listener_handle: listener_handle,
+                    rate_limiter: None,
+                    connections_info: HashMap::new(),
+                    endpoint: "endpoint".to_string(),
+                }],
+                cmd_socket: zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap(),
+                pending_acks: HashMap::new(),
+                pending_requests: HashMap::new(),
+            };
+
+            agent_worker.try_close_listener(listener_handle).unwrap();
+            assert_eq!(agent_worker.agent_listeners.len(), 0);
+        }
+
+        #[test]
+        fn agent_worker_try_start_listen_works() {
+            let mut agent_worker = AgentWorker {
+                agent_connections: Vec::new(),
+                agent_listeners: Vec::new(),
+                cmd_socket: zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap(),
+                pending_acks: HashMap::new(),
+                pending_requests: HashMap::new(),
+            };
+
+            let server_keys = zmq::CurveKeyPair::new().unwrap();
+            let endpoint = "127.0.0.1:9700".to_string();
+            agent_worker.try_start_listen(0, endpoint.clone(), DEFAULT_MAX_MSG_SIZE, None).unwrap();
+            assert_eq!(agent_worker.agent_listeners.len(), 1);
+            agent_worker.agent_listeners[0].transport
+                .add_identity(&server_keys.public_key, &server_keys.secret_key).unwrap();
+
+            let msg = "msg";
+            let sock = zmq::Context::new().socket(zmq::SocketType::DEALER).unwrap();
+            sock.set_linger(0).unwrap();
+            let kp = zmq::CurveKeyPair::new().unwrap();
+            let identity = zmq::z85_encode(&kp.public_key).unwrap();
+            sock.set_identity(identity.as_bytes()).unwrap();
+            sock.set_curve_publickey(&kp.public_key).unwrap();
+            sock.set_curve_secretkey(&kp.secret_key).unwrap();
+            sock.set_curve_serverkey(&server_keys.public_key).unwrap();
+            sock.set_protocol_version(zmq::make_proto_version(1, 1)).unwrap();
+            sock.connect(format!("tcp://{}", endpoint).as_str()).unwrap();
+            sock.send(msg, 0).unwrap();
+
+            let mut cmds = agent_worker.poll().unwrap();
+
+            assert_eq!(cmds.len(), 1);
+            let cmd = cmds.remove(0);
+            match cmd {
+                AgentWorkerCommand::Request(req) => {
+                    assert_eq!(req.identity, identity);
+                    assert_eq!(req.msg, msg);
+                }
+                _ => panic!("unexpected cmd {:?}", cmd),
+            }
+        }
+
+        #[test]
+        fn agent_worker_try_add_identity_works_for_not_found() {
+            let mut agent_worker = AgentWorker {
+                agent_connections: Vec::new(),
+                agent_listeners: Vec::new(),
+                cmd_socket: zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap(),
+                pending_acks: HashMap::new(),
+                pending_requests: HashMap::new(),
+            };
+            let listener_handle = SequenceUtils::get_next_id();
+
+            let res = agent_worker.try_add_identity(listener_handle, String::new(), -1, -1, String::new(), String::new());
+
+            assert_match!(Err(CommonError::InvalidStructure(_)), res);
+        }
+
+        #[test]
+        fn agent_worker_try_add_identity_works() {
+            let server_kp = zmq::CurveKeyPair::new().unwrap();
+            let endpoint = "inproc://agent_worker_try_add_identity_works";
+            let client_kp = zmq::CurveKeyPair::new().unwrap();
+            let identity = zmq::z85_encode(&client_kp.public_key).unwrap();
+            let (client_soc, server_soc) = {
+                let ctx = zmq::Context::new();
+                let rs = ctx.socket(zmq::SocketType::ROUTER).unwrap();
+                let ss = ctx.socket(zmq::SocketType::DEALER).unwrap();
+                rs.set_curve_server(true).unwrap();
+                rs.bind(endpoint).unwrap();
+                ss.set_identity(identity.as_bytes()).unwrap();
+                ss.set_curve_publickey(&client_kp.public_key).unwrap();
+                ss.set_curve_secretkey(&client_kp.secret_key).unwrap();
+                ss.set_curve_serverkey(&server_kp.public_key).unwrap();
+                ss.set_protocol_version(zmq::make_proto_version(1, 1)).unwrap();
+
+                (ss, rs)
+            };
+            let conn_handle = SequenceUtils::get_next_id();
+            let listener_handle = SequenceUtils::get_next_id();
+            let mut agent_worker = AgentWorker {
+                agent_connections: Vec::new(),
+                agent_listeners: vec![AgentListener {
+                    transport: _zmq_inbound(server_soc),
+                    connections: vec![(conn_handle, "test_identity".to_string())],
+                    pending_connections: HashMap::new(),
+                    did_resources: HashMap::new(),
+                    listener_handle: listener_handle,
+                    rate_limiter: None,
+                    connections_info: HashMap::new(),
+                    endpoint: "endpoint".to_string(),
+                }],
+                cmd_socket: zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap(),
+                pending_acks: HashMap::new(),
+                pending_requests: HashMap::new(),
+            };
+
+            agent_worker.try_add_identity(listener_handle, String::new(), -1, -1, server_kp.public_key.to_base58(), server_kp.secret_key.to_base58()).unwrap();
+            client_soc.connect(endpoint).unwrap();
+            client_soc.send("test_str", zmq::DONTWAIT).unwrap();
+            let mut cmds = agent_worker.poll().unwrap();
+            assert_eq!(cmds.len(), 1);
+            match cmds.remove(0) {
+                AgentWorkerCommand::Request(req) => {
+                    assert_eq!(req.identity, identity);
+                    assert_eq!(req.msg, "test_str");
+                }
+                cmd => panic!("unexpected cmd {:?}", cmd),
+            }
+        }
+
+        #[test]
+        fn agent_worker_try_add_identity_works_for_twice() {
+            let server_kp1 = zmq::CurveKeyPair::new().unwrap();
+            let server_kp2 = zmq::CurveKeyPair::new().unwrap();
+            let endpoint = "inproc://agent_worker_try_add_identity_works";
+            let mut identities = Vec::new();
+            let (client_soc1, client_soc2, server_soc) = {
+                let ctx = zmq::Context::new();
+                let rs = ctx.socket(zmq::SocketType::ROUTER).unwrap();
+                let ss1 = ctx.socket(zmq::SocketType::DEALER).unwrap();
+                let ss2 = ctx.socket(zmq::SocketType::DEALER).unwrap();
+                rs.set_curve_server(true).unwrap();
+                rs.bind(endpoint).unwrap();
+                for &(ref ss, ref server_kp) in [(&ss1, &server_kp1), (&ss2, &server_kp2)].iter() {
+                    let client_kp = zmq::CurveKeyPair::new().unwrap();
+                    let identity = zmq::z85_encode(&client_kp.public_key).unwrap();
+                    ss.set_identity(identity.as_bytes()).unwrap();
+                    identities.push(identity);
+                    ss.set_curve_publickey(&client_kp.public_key).unwrap();
+                    ss.set_curve_secretkey(&client_kp.secret_key).unwrap();
+                    ss.set_curve_serverkey(&server_kp.public_key).unwrap();
+                    ss.set_protocol_version(zmq::make_proto_version(1, 1)).unwrap();
+                }
+
+                (ss1, ss2, rs)
+            };
+            let conn_handle = SequenceUtils::get_next_id();
+            let listener_handle = SequenceUtils::get_next_id();
+            let mut agent_worker = AgentWorker {
+                agent_connections: Vec::new(),
+                agent_listeners: vec![AgentListener {
+                    transport: _zmq_inbound(server_soc),
+                    connections: vec![(conn_handle, "test_identity".to_string())],
+                    pending_connections: HashMap::new(),
+                    did_resources: HashMap::new(),
+                    listener_handle: listener_handle,
+                    rate_limiter: None,
+                    connections_info: HashMap::new(),
+                    endpoint: "endpoint".to_string(),
+                }],
+                cmd_socket: zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap(),
+                pending_acks: HashMap::new(),
+                pending_requests: HashMap::new(),
+            };
+
+            agent_worker.try_add_identity(listener_handle, "did1".to_string(), -1, -1, server_kp1.public_key.to_base58(), server_kp1.secret_key.to_base58()).unwrap();
+            agent_worker.try_add_identity(listener_handle, "did2".to_string(), -1, -1, server_kp2.public_key.to_base58(), server_kp2.secret_key.to_base58()).unwrap();
+
+            for &(ref client_soc, i) in [(client_soc1, 0), (client_soc2, 1)].iter() {
+                let msg = format!("test_str{}", i);
+                client_soc.connect(endpoint).unwrap();
+                client_soc.send(msg.as_str(), zmq::DONTWAIT).unwrap();
+                let mut cmds = agent_worker.poll().unwrap();
+                assert_eq!(cmds.len(), 1);
+                match cmds.remove(0) {
+                    AgentWorkerCommand::Request(req) => {
+                        assert_eq!(req.identity, identities[i]);
+                        assert_eq!(req.msg, msg);
+                    }
+                    cmd => panic!("unexpected cmd {:?}", cmd),
+                }
+            }
+        }
+
+        #[test]
+        fn agent_worker_try_send_works_for_not_found() {
+            let mut agent_worker = AgentWorker {
+                agent_connections: Vec::new(),
+                agent_listeners: Vec::new(),
+                cmd_socket: zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap(),
+                pending_acks: HashMap::new(),
+                pending_requests: HashMap::new(),
+            };
+            let conn_handle = SequenceUtils::get_next_id();
+
+            let res = agent_worker.try_send(conn_handle, None);
+
+            assert_match!(Err(AgentError::CommonError(CommonError::InvalidStructure(_))), res);
+        }
+
+        #[test]
+        fn agent_worker_try_send_works_for_duplicate() {
+            let conn_handle = SequenceUtils::get_next_id();
+            let mut agent_worker = AgentWorker {
+                agent_connections: vec![_remote_agent(_zmq_outbound(zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap()), conn_handle)],
+                agent_listeners: vec![AgentListener {
+                    transport: _zmq_inbound(zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap()),
+                    connections: vec![(conn_handle, String::new())],
+                    pending_connections: HashMap::new(),
+                    did_resources: HashMap::new(),
+                    listener_handle: SequenceUtils::get_next_id(),
+                    rate_limiter: None,
+                    connections_info: HashMap::new(),
+                    endpoint: "endpoint".to_string(),
+                }],
+                cmd_socket: zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap(),
+                pending_acks: HashMap::new(),
+                pending_requests: HashMap::new(),
+            };
+
+            let res = agent_worker.try_send(conn_handle, None);
+
+            assert_match!(Err(AgentError::CommonError(CommonError::InvalidState(_))), res);
+        }
+
+        #[test]
+        fn agent_worker_try_send_works_for_listener() {
+            let (send_soc, recv_soc) = _create_zmq_socket_pair("aw_poll_cmd", true).unwrap();
+            let conn_handle = SequenceUtils::get_next_id();
+            let mut agent_worker = AgentWorker {
+                agent_connections: Vec::new(),
+                agent_listeners: vec![AgentListener {
+                    transport: _zmq_inbound(send_soc),
+                    connections: vec![(conn_handle, "test_identity".to_string())],
+                    pending_connections: HashMap::new(),
+                    did_resources: HashMap::new(),
+                    listener_handle: SequenceUtils::get_next_id(),
+                    rate_limiter: None,
+                    connections_info: HashMap::new(),
+                    endpoint: "endpoint".to_string(),
+                }],
+                cmd_socket: zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap(),
+                pending_acks: HashMap::new(),
+                pending_requests: HashMap::new(),
+            };
+
+            agent_worker.try_send(conn_handle, Some("test_str".to_string())).unwrap();
+            assert_eq!(recv_soc.recv_string(zmq::DONTWAIT).unwrap().unwrap(), "test_identity");
+            assert_eq!(recv_soc.recv_string(zmq::DONTWAIT).unwrap().unwrap(), "test_str");
+        }
+
+        #[test]
+        fn agent_worker_try_send_works_for_connection() {
+            let (send_soc, recv_soc) = _create_zmq_socket_pair("aw_poll_cmd", true).unwrap();
+            let conn_handle = SequenceUtils::get_next_id();
+            let mut agent_worker = AgentWorker {
+                agent_connections: vec![_remote_agent(_zmq_outbound(send_soc), conn_handle)],
+                agent_listeners: Vec::new(),
+                cmd_socket: zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap(),
+                pending_acks: HashMap::new(),
+                pending_requests: HashMap::new(),
+            };
+
+            agent_worker.try_send(conn_handle, Some("test_str".to_string())).unwrap();
+            assert_eq!(recv_soc.recv_string(zmq::DONTWAIT).unwrap().unwrap(), "test_str");
+        }
+
+        #[test]
+        fn agent_worker_connections_info_works() {
+            let (send_soc, recv_soc) = _create_zmq_socket_pair("aw_connections_info", true).unwrap();
+            let out_conn_handle = SequenceUtils::get_next_id();
+            let in_conn_handle = SequenceUtils::get_next_id();
+            let listener_handle = SequenceUtils::get_next_id();
+            let mut remote_agent = _remote_agent(_zmq_outbound(send_soc), out_conn_handle);
+            remote_agent.sender_did = "sender_did".to_string();
+            remote_agent.receiver_did = "receiver_did".to_string();
+            let mut connections_info = HashMap::new();
+            connections_info.insert(in_conn_handle, IncomingConnection {
+                sender_did: "remote_did".to_string(),
+                receiver_did: "my_did".to_string(),
+                opened_at: 1,
+                stats: ConnectionStats::default(),
+            });
+            let mut agent_worker = AgentWorker {
+                agent_connections: vec![remote_agent],
+                agent_listeners: vec![AgentListener {
+                    transport: _zmq_inbound(zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap()),
+                    connections: vec![(in_conn_handle, "test_identity".to_string())],
+                    connections_info: connections_info,
+                    pending_connections: HashMap::new(),
+                    did_resources: HashMap::new(),
+                    listener_handle: listener_handle,
+                    endpoint: "endpoint".to_string(),
+                    rate_limiter: None,
+                }],
+                cmd_socket: zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap(),
+                pending_acks: HashMap::new(),
+                pending_requests: HashMap::new(),
+            };
+
+            agent_worker.try_send(out_conn_handle, Some("test_str".to_string())).unwrap();
+            assert_eq!(recv_soc.recv_string(zmq::DONTWAIT).unwrap().unwrap(), "test_str");
+
+            let infos = agent_worker.connections_info();
+            assert_eq!(infos.len(), 2);
+            assert_eq!(infos[0].connection_handle, out_conn_handle);
+            assert_eq!(infos[0].direction, ConnectionDirection::Outgoing);
+            assert_eq!(infos[0].sender_did, "sender_did");
+            assert_eq!(infos[0].listener_handle, None);
+            assert_eq!(infos[0].state, ConnectionState::Connecting);
+            assert_eq!(infos[0].stats.messages_sent, 1);
+            assert_eq!(infos[0].stats.bytes_sent, 8);
+            assert_eq!(infos[1].connection_handle, in_conn_handle);
+            assert_eq!(infos[1].direction, ConnectionDirection::Incoming);
+            assert_eq!(infos[1].sender_did, "remote_did");
+            assert_eq!(infos[1].receiver_did, "my_did");
+            assert_eq!(infos[1].listener_handle, Some(listener_handle));
+            assert_eq!(infos[1].state, ConnectionState::Connected);
+
+            agent_worker.try_close_connection(in_conn_handle).unwrap();
+            assert!(agent_worker.agent_listeners[0].connections_info.is_empty());
+        }
+
+        #[test]
+        fn agent_worker_try_send_envelope_works() {
+            let (send_soc, recv_soc) = _create_zmq_socket_pair("aw_send_envelope", true).unwrap();
+            let conn_handle = SequenceUtils::get_next_id();
+            let mut agent_worker = AgentWorker {
+                agent_connections: vec![_remote_agent(_zmq_outbound(send_soc), conn_handle)],
+                agent_listeners: Vec::new(),
+                cmd_socket: zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap(),
+                pending_acks: HashMap::new(),
+                pending_requests: HashMap::new(),
+            };
+
+            agent_worker.try_send_envelope(conn_handle, "msg_id".to_string(), Some("request_id".to_string()), "test_str".to_string()).unwrap();
+            assert_eq!(recv_soc.recv_bytes(zmq::DONTWAIT).unwrap(),
+                       _frame(PROTOCOL_FRAME, r#"{"msg_envelope":{"id":"msg_id","reply_to":"request_id","content":"test_str"}}"#.as_bytes()));
+        }
+
+        #[test]
+        fn agent_worker_try_close_connection_works_for_pending() {
+            let conn_handle = SequenceUtils::get_next_id();
+            let mut agent_worker = AgentWorker {
+                agent_connections: vec![_remote_agent(_zmq_outbound(zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap()), conn_handle)],
+                agent_listeners: Vec::new(),
+                cmd_socket: zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap(),
+                pending_acks: HashMap::new(),
+                pending_requests: HashMap::new(),
+            };
+            let deadline = Instant::now() + Duration::from_secs(DELIVERY_ACK_TIMEOUT_SECS);
+            agent_worker.pending_acks.insert((conn_handle, "msg_id".to_string()), (0, deadline));
+            agent_worker.pending_requests.insert((conn_handle, "request_id".to_string()), (0, deadline));
+            agent_worker.pending_acks.insert((conn_handle + 1, "msg_id".to_string()), (0, deadline));
+
+            agent_worker.try_close_connection(conn_handle).unwrap();
+
+            assert_eq!(agent_worker.pending_acks.len(), 1);
+            assert!(agent_worker.pending_acks.contains_key(&(conn_handle + 1, "msg_id".to_string())));
+            assert!(agent_worker.pending_requests.is_empty());
+        }
+
+        #[test]
+        fn agent_worker_check_pending_works_for_not_expired() {
+            let conn_handle = SequenceUtils::get_next_id();
+            let mut agent_worker = AgentWorker {
+                agent_connections: Vec::new(),
+                agent_listeners: Vec::new(),
+                cmd_socket: zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap(),
+                pending_acks: HashMap::new(),
+                pending_requests: HashMap::new(),
+            };
+            let deadline = Instant::now() + Duration::from_secs(DELIVERY_ACK_TIMEOUT_SECS);
+            agent_worker.pending_acks.insert((conn_handle, "msg_id".to_string()), (0, deadline));
+            agent_worker.pending_requests.insert((conn_handle, "request_id".to_string()), (0, deadline));
+
+            agent_worker.check_pending(Instant::now());
+
+            assert_eq!(agent_worker.pending_acks.len(), 1);
+            assert_eq!(agent_worker.pending_requests.len(), 1);
+        }
+
+        #[test]
+        fn remote_agent_send_works_for_disconnected() {
+            let mut remote_agent = _remote_agent(_zmq_outbound(zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap()), 0);
+            remote_agent.was_connected = true;
+            remote_agent.state = ConnectionState::Disconnected;
+
+            for i in 0..MAX_OUTBOUND_QUEUE_LEN {
+                remote_agent.send(format!("msg{}", i).into_bytes()).unwrap();
+            }
+            assert_eq!(remote_agent.queue.len(), MAX_OUTBOUND_QUEUE_LEN);
+
+            let res = remote_agent.send("msg".as_bytes().to_vec());
+            assert_match!(Err(AgentError::OutboundQueueFull(_)), res);
+        }
+
+        #[test]
+        fn remote_agent_send_works_for_connected_with_failed_transport() {
+            let mut remote_agent = _remote_agent(_zmq_outbound(zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap()), 0);
+            remote_agent.was_connected = true;
+            remote_agent.state = ConnectionState::Connected;
+
+            for i in 0..MAX_OUTBOUND_QUEUE_LEN {
+                remote_agent.send(format!("msg{}", i).into_bytes()).unwrap();
+            }
+            assert_eq!(remote_agent.queue.len(), MAX_OUTBOUND_QUEUE_LEN);
+
+            let res = remote_agent.send("msg".as_bytes().to_vec());
+            assert_match!(Err(AgentError::OutboundQueueFull(_)), res);
+        }
+
+        #[test]
+        fn remote_agent_handle_response_works_for_reconnect() {
+            let (send_soc, recv_soc) = _create_zmq_socket_pair("ra_reconnect", true).unwrap();
+            let mut remote_agent = _remote_agent(_zmq_outbound(send_soc), 0);
+            remote_agent.was_connected = true;
+            remote_agent.reconnect_attempts = 3;
+            remote_agent.send("msg1".as_bytes().to_vec()).unwrap();
+            remote_agent.send("msg2".as_bytes().to_vec()).unwrap();
+
+            let cmd = remote_agent.handle_response(Ok(DID_ACK.as_bytes().to_vec()));
+
+            assert!(cmd.is_none());
+            assert_eq!(remote_agent.state, ConnectionState::Connected);
+            assert_eq!(remote_agent.reconnect_attempts, 0);
+            assert_eq!(remote_agent.stats.reconnects, 1);
+            assert!(remote_agent.queue.is_empty());
+            assert_eq!(recv_soc.recv_string(zmq::DONTWAIT).unwrap().unwrap(), "msg1");
+            assert_eq!(recv_soc.recv_string(zmq::DONTWAIT).unwrap().unwrap(), "msg2");
+        }
+
+        #[test]
+        fn remote_agent_handle_response_works_for_pong() {
+            let mut remote_agent = _remote_agent(_zmq_outbound(zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap()), 0);
+            remote_agent.state = ConnectionState::Connected;
+            remote_agent.last_activity = Instant::now() - Duration::from_secs(HEARTBEAT_INTERVAL_SECS);
+
+            let cmd = remote_agent.handle_response(Ok(PONG.as_bytes().to_vec()));
+
+            assert!(cmd.is_none());
+            assert!(remote_agent.last_activity.elapsed() < Duration::from_secs(HEARTBEAT_INTERVAL_SECS));
+        }
+
+        #[test]
+        fn remote_agent_check_liveness_works_for_ping() {
+            let (send_soc, recv_soc) = _create_zmq_socket_pair("ra_ping", true).unwrap();
+            let mut remote_agent = _remote_agent(_zmq_outbound(send_soc), 0);
+            remote_agent.state = ConnectionState::Connected;
+            remote_agent.last_ping = Instant::now() - Duration::from_secs(HEARTBEAT_INTERVAL_SECS);
+
+            let cmd = remote_agent.check_liveness(Instant::now());
+
+            assert!(cmd.is_none());
+            assert_eq!(recv_soc.recv_string(zmq::DONTWAIT).unwrap().unwrap(), PING);
+        }
+
+        #[test]
+        fn remote_agent_check_liveness_works_for_heartbeat_timeout() {
+            let mut remote_agent = _remote_agent(_zmq_outbound(zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap()), 0);
+            remote_agent.was_connected = true;
+            remote_agent.state = ConnectionState::Connected;
+            remote_agent.last_activity = Instant::now() - Duration::from_secs(HEARTBEAT_TIMEOUT_SECS + 1);
+
+            let cmd = remote_agent.check_liveness(Instant::now());
+
+            assert_match!(Some(AgentCommand::MessageReceived(0, Err(AgentError::ConnectionLost(_)))), cmd);
+            assert_eq!(remote_agent.state, ConnectionState::Disconnected);
+            assert!(remote_agent.reconnect_at.is_some());
+
+            /* failed reconnect attempts don't notify user again */
+            let cmd = remote_agent.handle_response(Err(CommonError::InvalidState("test".to_string())));
+            assert!(cmd.is_none());
+        }
+
+        #[test]
+        fn remote_agent_schedule_reconnect_works() {
+            let mut remote_agent = _remote_agent(_zmq_outbound(zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap()), 0);
+
+            remote_agent.schedule_reconnect();
+            let first_delay = remote_agent.reconnect_at.unwrap().duration_since(Instant::now());
+            assert!(first_delay <= Duration::from_secs(RECONNECT_MIN_DELAY_SECS));
+
+            remote_agent.reconnect_attempts = 100;
+            remote_agent.schedule_reconnect();
+            let delay = remote_agent.reconnect_at.unwrap().duration_since(Instant::now());
+            assert!(delay > Duration::from_secs(RECONNECT_MAX_DELAY_SECS - 1));
+            assert!(delay <= Duration::from_secs(RECONNECT_MAX_DELAY_SECS));
+        }
+
+        #[test]
+        fn agent_listener_handle_request_works_for_ping() {
+            let (send_soc, recv_soc) = _create_zmq_socket_pair("al_ping", true).unwrap();
+            let conn_handle = SequenceUtils::get_next_id();
+            let mut agent_listener = AgentListener {
+                transport: _zmq_inbound(send_soc),
+                connections: vec![(conn_handle, "test_identity".to_string())],
+                pending_connections: HashMap::new(),
+                did_resources: HashMap::new(),
+                listener_handle: SequenceUtils::get_next_id(),
+                rate_limiter: None,
+                connections_info: HashMap::new(),
+                endpoint: "endpoint".to_string(),
+            };
+
+            let cmd = agent_listener.handle_request("test_identity".to_string(), PING.as_bytes().to_vec()).unwrap();
+            assert!(cmd.is_none());
+            assert_eq!(recv_soc.recv_string(zmq::DONTWAIT).unwrap().unwrap(), "test_identity");
+            assert_eq!(recv_soc.recv_string(zmq::DONTWAIT).unwrap().unwrap(), PONG);
+        }
+
+        #[test]
+        fn agent_listener_handle_request_works_for_rate_limit() {
+            let (send_soc, recv_soc) = _create_zmq_socket_pair("al_rate_limit", true).unwrap();
+            let mut agent_listener = AgentListener {
+                transport: _zmq_inbound(send_soc),
+                connections: Vec::new(),
+                pending_connections: HashMap::new(),
+                did_resources: HashMap::new(),
+                listener_handle: SequenceUtils::get_next_id(),
+                rate_limiter: Some(RateLimiter::new(RateLimit { max_attempts: 1, interval_secs: 60, max_total_attempts: None })),
+                connections_info: HashMap::new(),
+                endpoint: "endpoint".to_string(),
+            };
+
+            let cmd = agent_listener.handle_request("test_identity".to_string(), "test_str".as_bytes().to_vec()).unwrap();
+            assert!(cmd.is_none());
+            assert_eq!(recv_soc.recv_string(zmq::DONTWAIT).unwrap().unwrap(), "test_identity");
+            assert_eq!(recv_soc.recv_string(zmq::DONTWAIT).unwrap().unwrap(), "NOT_CONNECTED");
+
+            let cmd = agent_listener.handle_request("test_identity".to_string(), "test_str".as_bytes().to_vec()).unwrap();
+            assert!(cmd.is_none());
+            assert_eq!(recv_soc.recv_string(zmq::DONTWAIT).unwrap().unwrap(), "test_identity");
+            assert_eq!(recv_soc.recv_string(zmq::DONTWAIT).unwrap().unwrap(), RATE_LIMITED);
+        }
+
+        #[test]
+        fn agent_listener_handle_request_works_for_message() {
+            let conn_handle = SequenceUtils::get_next_id();
+            let mut agent_listener = AgentListener {
+                transport: _zmq_inbound(zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap()),
+                connections: vec![(conn_handle, "test_identity".to_string())],
+                pending_connections: HashMap::new(),
+                did_resources: HashMap::new(),
+                listener_handle: SequenceUtils::get_next_id(),
+                rate_limiter: None,
+                connections_info: HashMap::new(),
+                endpoint: "endpoint".to_string(),
+            };
+            agent_listener.connections_info.insert(conn_handle, IncomingConnection {
+                sender_did: "remote_did".to_string(),
+                receiver_did: "my_did".to_string(),
+                opened_at: 1,
+                stats: ConnectionStats::default(),
+            });
+
+            let cmd = agent_listener.handle_request("test_identity".to_string(), "test_str".as_bytes().to_vec()).unwrap();
+            match cmd {
+                Some(AgentCommand::MessageReceived(handle, Ok((msg, None)))) => {
+                    assert_eq!(handle, conn_handle);
+                    assert_eq!(msg, "test_str");
+                }
+                _ => panic!("unexpected cmd"),
+            }
+            assert_eq!(agent_listener.connections_info[&conn_handle].stats.messages_received, 1);
+        }
+
+        #[test]
+        fn agent_listener_on_connect_checked_works_for_reconnect() {
+            let (send_soc, recv_soc) = _create_zmq_socket_pair("al_reconnect", true).unwrap();
+            let conn_handle = SequenceUtils::get_next_id();
+            let mut agent_listener = AgentListener {
+                transport: _zmq_inbound(send_soc),
+                connections: vec![(conn_handle, "test_identity".to_string())],
+                pending_connections: HashMap::new(),
+                did_resources: HashMap::new(),
+                listener_handle: SequenceUtils::get_next_id(),
+                rate_limiter: None,
+                connections_info: HashMap::new(),
+                endpoint: "endpoint".to_string(),
+            };
+            agent_listener.connections_info.insert(conn_handle, IncomingConnection {
+                sender_did: "remote_did".to_string(),
+                receiver_did: "my_did".to_string(),
+                opened_at: 1,
+                stats: ConnectionStats::default(),
+            });
+            agent_listener.pending_connections.insert("remote_did".to_string(),
+                                                      ("test_identity".to_string(), "my_did".to_string()));
+
+            agent_listener.on_connect_checked("remote_did".to_string(), true).unwrap();
+
+            assert_eq!(recv_soc.recv_string(zmq::DONTWAIT).unwrap().unwrap(), "test_identity");
+            assert_eq!(recv_soc.recv_string(zmq::DONTWAIT).unwrap().unwrap(), DID_ACK);
+            assert_eq!(agent_listener.connections, vec![(conn_handle, "test_identity".to_string())]);
+            assert_eq!(agent_listener.connections_info[&conn_handle].stats.reconnects, 1);
+        }
+
+        #[test]
+        fn agent_listener_handle_request_works_for_binary_message() {
+            let conn_handle = SequenceUtils::get_next_id();
+            let mut agent_listener = AgentListener {
+                transport: _zmq_inbound(zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap()),
+                connections: vec![(conn_handle, "test_identity".to_string())],
+                pending_connections: HashMap::new(),
+                did_resources: HashMap::new(),
+                listener_handle: SequenceUtils::get_next_id(),
+                rate_limiter: None,
+                connections_info: HashMap::new(),
+                endpoint: "endpoint".to_string(),
+            };
+
+            let cmd = agent_listener.handle_request("test_identity".to_string(), _frame(BINARY_FRAME, &[159, 146, 150])).unwrap();
+            match cmd {
+                Some(AgentCommand::BinaryMessageReceived(handle, msg)) => {
+                    assert_eq!(handle, conn_handle);
+                    assert_eq!(msg, vec![159, 146, 150]);
+                }
+                _ => panic!("unexpected cmd"),
+            }
+
+            /* text message must be valid UTF-8 */
+            let cmd = agent_listener.handle_request("test_identity".to_string(), vec![159, 146, 150]).unwrap();
+            assert!(cmd.is_none());
+        }
+
+        #[test]
+        fn agent_listener_handle_request_works_for_protocol_message() {
+            let conn_handle = SequenceUtils::get_next_id();
+            let mut agent_listener = AgentListener {
+                transport: _zmq_inbound(zmq::Context::new().socket(zmq::SocketType::PAIR).unwrap()),
+                connections: vec![(conn_handle, "test_identity".to_string())],
+                pending_connections: HashMap::new(),
+                did_resources: HashMap::new(),
+                listener_handle: SequenceUtils::get_next_id(),
+                rate_limiter: None,
+                connections_info: HashMap::new(),
+                endpoint: "endpoint".to_string(),
+            };
+            let ack = r#"{"msg_ack":"msg_id"}"#;
+
+            let cmd = agent_listener.handle_request("test_identity".to_string(), _frame(PROTOCOL_FRAME, ack.as_bytes())).unwrap();
+            match cmd {
+                Some(AgentCommand::ProtocolMessageReceived(handle, msg)) => {
+                    assert_eq!(handle, conn_handle);
+                    assert_eq!(msg, ack);
+                }
+                _ => panic!("unexpected cmd"),
+            }
+
+            /* peer text is passed to application even if it looks like protocol message */
+            let cmd = agent_listener.handle_request("test_identity".to_string(), ack.as_bytes().to_vec()).unwrap();
+            match cmd {
+                Some(AgentCommand::MessageReceived(handle, Ok((msg, None)))) => {
+                    assert_eq!(handle, conn_handle);
+                    assert_eq!(msg, ack);
+                }
+                _ => panic!("unexpected cmd"),
+            }
+        }
+    }
+
+    #[test]
+    fn agent_service_static_create_zmq_socket_pair_works() {
+        let msg = "msg";
+        let sockets = _create_zmq_socket_pair("test_pair", true).unwrap();
+        sockets.0.send(msg, zmq::DONTWAIT).unwrap();
+        assert_eq!(sockets.1.recv_string(zmq::DONTWAIT).unwrap().unwrap(), msg);
+    }
+}
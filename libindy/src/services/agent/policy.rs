@@ -0,0 +1,272 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::time::{Duration, Instant};
+
+use errors::common::CommonError;
+use utils::did::DidUtils;
+use utils::json::JsonDecodable;
+
+/// Role of sender DID that has NYM without role on the ledger.
+pub const USER_ROLE: &'static str = "USER";
+const KNOWN_ROLES: [&'static str; 5] = ["TRUSTEE", "STEWARD", "TRUST_ANCHOR", "TGB", USER_ROLE];
+/// Maximum number of senders tracked by rate limiter, new senders are rejected above it.
+const MAX_RATE_LIMITED_SENDERS: usize = 1024;
+/// Attempts allowed from all senders during interval if max_total_attempts isn't set.
+const DEFAULT_MAX_TOTAL_ATTEMPTS: u32 = 1024;
+
+/// Declarative connection authorization rules of listener.
+/// Deny rules take precedence over allow rules.
+#[derive(Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct ListenerPolicy {
+    pub allow_dids: Option<Vec<String>>,
+    pub deny_dids: Option<Vec<String>>,
+    pub allow_roles: Option<Vec<String>>,
+    pub deny_roles: Option<Vec<String>>,
+}
+
+impl<'a> JsonDecodable<'a> for ListenerPolicy {}
+
+impl ListenerPolicy {
+    /// Parses policy fields of listener config json. Other config fields are ignored.
+    pub fn from_config(config: Option<&str>) -> Result<ListenerPolicy, CommonError> {
+        let policy = match config {
+            Some(config) => ListenerPolicy::from_json(config)
+                .map_err(|err| CommonError::InvalidStructure(format!("Invalid agent listener config {}", err.description())))?,
+            None => ListenerPolicy::default(),
+        };
+
+        for role in policy.allow_roles.iter().chain(policy.deny_roles.iter()).flat_map(|roles| roles.iter()) {
+            if !KNOWN_ROLES.contains(&role.as_str()) {
+                return Err(CommonError::InvalidStructure(format!("Invalid role in agent listener config: {}", role)));
+            }
+        }
+
+        Ok(policy)
+    }
+
+    /// Role is known only after ledger lookup of sender NYM.
+    pub fn requires_role(&self) -> bool {
+        self.allow_roles.is_some() || self.deny_roles.is_some()
+    }
+
+    pub fn is_did_allowed(&self, did: &str) -> bool {
+        if self.deny_dids.as_ref().map(|dids| _contains_did(dids, did)).unwrap_or(false) {
+            return false;
+        }
+        self.allow_dids.as_ref().map(|dids| _contains_did(dids, did)).unwrap_or(true)
+    }
+
+    /// Unknown role (None) passes only if there are no role rules.
+    pub fn is_role_allowed(&self, role: Option<&str>) -> bool {
+        let role = match role {
+            Some(role) => role,
+            None => return !self.requires_role(),
+        };
+        if self.deny_roles.as_ref().map(|roles| roles.iter().any(|r| r == role)).unwrap_or(false) {
+            return false;
+        }
+        self.allow_roles.as_ref().map(|roles| roles.iter().any(|r| r == role)).unwrap_or(true)
+    }
+}
+
+fn _contains_did(dids: &Vec<String>, did: &str) -> bool {
+    let did = DidUtils::normalize(did);
+    dids.iter().any(|d| DidUtils::normalize(d) == did)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RateLimit {
+    pub max_attempts: u32,
+    pub interval_secs: u64,
+    pub max_total_attempts: Option<u32>,
+}
+
+/// Fixed window counter of connection attempts per sender and of accepted attempts of all senders.
+/// Sender of attempt is its authenticated transport identity together with DID it claims,
+/// so claiming somebody else's DID doesn't use up budget of the real sender.
+/// DID alone is counted only after its key is verified by connection check.
+/// New identities are cheap, the total limit bounds them.
+pub struct RateLimiter {
+    limit: RateLimit,
+    windows: HashMap<String /* sender */, (Instant /* window start */, u32 /* attempts */)>,
+    verified_windows: HashMap<String /* sender did */, (Instant /* window start */, u32 /* attempts */)>,
+    total: (Instant /* window start */, u32 /* accepted attempts */),
+}
+
+impl RateLimiter {
+    pub fn new(limit: RateLimit) -> RateLimiter {
+        RateLimiter {
+            limit: limit,
+            windows: HashMap::new(),
+            verified_windows: HashMap::new(),
+            total: (Instant::now(), 0),
+        }
+    }
+
+    /// Registers attempt of transport identity with optional sender DID. Returns false if sender,
+    /// verified attempts of DID or all senders together exceeded limit in current window
+    /// or too many senders are tracked already.
+    pub fn check(&mut self, identity: &str, did: Option<&str>, now: Instant) -> bool {
+        let interval = Duration::from_secs(self.limit.interval_secs);
+
+        if now.duration_since(self.total.0) >= interval {
+            self.total = (now, 0);
+        }
+        if self.total.1 >= self.limit.max_total_attempts.unwrap_or(DEFAULT_MAX_TOTAL_ATTEMPTS) {
+            return false;
+        }
+
+        if let Some(did) = did {
+            let is_did_limited = self.verified_windows.get(did)
+                .map(|&(start, attempts)| now.duration_since(start) < interval && attempts >= self.limit.max_attempts)
+                .unwrap_or(false);
+            if is_did_limited {
+                return false;
+            }
+        }
+
+        let sender = match did {
+            Some(did) => format!("{} {}", identity, did),
+            None => identity.to_string(),
+        };
+        let max_attempts = self.limit.max_attempts;
+        let is_accepted = match _window(&mut self.windows, sender.as_str(), now, interval) {
+            Some(window) => {
+                window.1 += 1;
+                window.1 <= max_attempts
+            }
+            None => false,
+        };
+        if is_accepted {
+            self.total.1 += 1;
+        }
+        is_accepted
+    }
+
+    /// Counts attempt of sender DID which key is verified.
+    pub fn on_verified(&mut self, did: &str, now: Instant) {
+        let interval = Duration::from_secs(self.limit.interval_secs);
+        if let Some(window) = _window(&mut self.verified_windows, did, now, interval) {
+            window.1 += 1;
+        }
+    }
+}
+
+/// Returns current window of sender, None if sender is new and too many senders are tracked.
+fn _window<'a>(windows: &'a mut HashMap<String, (Instant, u32)>, sender: &str, now: Instant, interval: Duration) -> Option<&'a mut (Instant, u32)> {
+    if !windows.contains_key(sender) {
+        windows.retain(|_, &mut (start, _)| now.duration_since(start) < interval);
+        if windows.len() >= MAX_RATE_LIMITED_SENDERS {
+            return None;
+        }
+    }
+
+    let window = windows.entry(sender.to_string()).or_insert((now, 0));
+    if now.duration_since(window.0) >= interval {
+        *window = (now, 0);
+    }
+    Some(window)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn listener_policy_from_config_works() {
+        let policy = ListenerPolicy::from_config(Some(r#"{"max_message_size":1000,"deny_dids":["did1"],"allow_roles":["STEWARD"]}"#)).unwrap();
+        assert_eq!(policy, ListenerPolicy {
+            allow_dids: None,
+            deny_dids: Some(vec!["did1".to_string()]),
+            allow_roles: Some(vec!["STEWARD".to_string()]),
+            deny_roles: None,
+        });
+        assert_eq!(ListenerPolicy::from_config(None).unwrap(), ListenerPolicy::default());
+    }
+
+    #[test]
+    fn listener_policy_from_config_works_for_unknown_role() {
+        let res = ListenerPolicy::from_config(Some(r#"{"allow_roles":["ADMIN"]}"#));
+        assert_match!(Err(CommonError::InvalidStructure(_)), res);
+    }
+
+    #[test]
+    fn listener_policy_is_did_allowed_works() {
+        let policy = ListenerPolicy {
+            allow_dids: Some(vec!["did1".to_string(), "did2".to_string()]),
+            deny_dids: Some(vec!["did:sov:did2".to_string()]),
+            allow_roles: None,
+            deny_roles: None,
+        };
+        assert!(policy.is_did_allowed("did1"));
+        assert!(policy.is_did_allowed("did:sov:did1"));
+        assert!(!policy.is_did_allowed("did2"));
+        assert!(!policy.is_did_allowed("did3"));
+        assert!(ListenerPolicy::default().is_did_allowed("did3"));
+    }
+
+    #[test]
+    fn listener_policy_is_role_allowed_works() {
+        let policy = ListenerPolicy {
+            allow_dids: None,
+            deny_dids: None,
+            allow_roles: Some(vec!["TRUSTEE".to_string(), "STEWARD".to_string()]),
+            deny_roles: Some(vec!["STEWARD".to_string()]),
+        };
+        assert!(policy.is_role_allowed(Some("TRUSTEE")));
+        assert!(!policy.is_role_allowed(Some("STEWARD")));
+        assert!(!policy.is_role_allowed(Some(USER_ROLE)));
+        assert!(!policy.is_role_allowed(None));
+        assert!(ListenerPolicy::default().is_role_allowed(None));
+    }
+
+    #[test]
+    fn rate_limiter_check_works() {
+        let mut rate_limiter = RateLimiter::new(RateLimit { max_attempts: 2, interval_secs: 10, max_total_attempts: None });
+        let now = Instant::now();
+        assert!(rate_limiter.check("sender1", None, now));
+        assert!(rate_limiter.check("sender1", None, now));
+        assert!(!rate_limiter.check("sender1", None, now));
+        assert!(rate_limiter.check("sender2", None, now));
+        assert!(rate_limiter.check("sender1", None, now + Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn rate_limiter_check_works_for_total_limit() {
+        let mut rate_limiter = RateLimiter::new(RateLimit { max_attempts: 2, interval_secs: 10, max_total_attempts: Some(3) });
+        let now = Instant::now();
+        assert!(rate_limiter.check("sender1", None, now));
+        assert!(rate_limiter.check("sender1", None, now));
+        assert!(!rate_limiter.check("sender1", None, now));
+        assert!(rate_limiter.check("sender2", None, now));
+        assert!(!rate_limiter.check("sender3", None, now));
+        assert!(rate_limiter.check("sender3", None, now + Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn rate_limiter_check_works_for_many_senders() {
+        let mut rate_limiter = RateLimiter::new(RateLimit { max_attempts: 1, interval_secs: 10, max_total_attempts: Some(u32::max_value()) });
+        let now = Instant::now();
+        for i in 0..MAX_RATE_LIMITED_SENDERS {
+            assert!(rate_limiter.check(format!("sender{}", i).as_str(), None, now));
+        }
+        assert!(!rate_limiter.check("new_sender", None, now));
+        assert_eq!(rate_limiter.windows.len(), MAX_RATE_LIMITED_SENDERS);
+
+        assert!(rate_limiter.check("new_sender", None, now + Duration::from_secs(10)));
+        assert_eq!(rate_limiter.windows.len(), 1);
+    }
+
+    #[test]
+    fn rate_limiter_check_works_for_spoofed_did() {
+        let mut rate_limiter = RateLimiter::new(RateLimit { max_attempts: 1, interval_secs: 10, max_total_attempts: None });
+        let now = Instant::now();
+        assert!(rate_limiter.check("spoofer_identity", Some("did"), now));
+        assert!(!rate_limiter.check("spoofer_identity", Some("did"), now));
+        assert!(rate_limiter.check("sender_identity", Some("did"), now));
+
+        rate_limiter.on_verified("did", now);
+        assert!(!rate_limiter.check("other_identity", Some("did"), now));
+        assert!(rate_limiter.check("other_identity", Some("did"), now + Duration::from_secs(10)));
+    }
+}
@@ -0,0 +1,100 @@
+extern crate zmq_pw as zmq;
+
+use errors::common::CommonError;
+
+use super::http_transport::{HttpInbound, HttpOutbound};
+use super::zmq_transport::{ZmqInbound, ZmqOutbound};
+
+const ZMQ_SCHEME: &'static str = "tcp://";
+const HTTP_SCHEME: &'static str = "http://";
+const HTTPS_SCHEME: &'static str = "https://";
+
+/// Client side of agent connection.
+///
+/// Transport is polled by AgentWorker together with other transports,
+/// so each implementation exposes zmq socket that becomes readable on incoming data.
+pub trait OutboundTransport {
+    fn connect(&self, sender_did: &str, receiver_did: &str) -> Result<(), CommonError>;
+
+    fn send(&self, msg: &[u8]) -> Result<(), CommonError>;
+
+    fn poll_item(&self) -> zmq::PollItem;
+
+    /// Returns received message, None if incoming data shouldn't be passed to user
+    /// or error if previously sent message can't be delivered.
+    fn recv(&self) -> Result<Option<Vec<u8>>, CommonError>;
+}
+
+/// Listener side of agent connections.
+///
+/// Clients are identified by string identity provided by the transport.
+/// Incoming data that can't be authenticated or exceeds maximum message size
+/// is rejected by the transport itself with explanation sent back to the client.
+pub trait InboundTransport {
+    fn add_identity(&mut self, pk: &[u8], sk: &[u8]) -> Result<(), CommonError>;
+
+    fn rm_identity(&mut self, pk: &[u8]) -> Result<(), CommonError>;
+
+    fn poll_item(&self) -> zmq::PollItem;
+
+    /// Returns (identity, message) for accepted incoming message.
+    fn recv(&self) -> Result<Option<(String, Vec<u8>)>, CommonError>;
+
+    fn send(&self, identity: &str, msg: &[u8]) -> Result<(), CommonError>;
+}
+
+#[derive(Debug, PartialEq)]
+enum TransportEndpoint<'a> {
+    Zmq(&'a str /* host:port */),
+    Http(&'a str /* url */),
+}
+
+impl<'a> TransportEndpoint<'a> {
+    /// Endpoints without scheme are treated as ZeroMQ ones for compatibility.
+    fn parse(endpoint: &'a str) -> Result<TransportEndpoint<'a>, CommonError> {
+        if endpoint.starts_with(HTTP_SCHEME) || endpoint.starts_with(HTTPS_SCHEME) {
+            Ok(TransportEndpoint::Http(endpoint))
+        } else if endpoint.starts_with(ZMQ_SCHEME) {
+            Ok(TransportEndpoint::Zmq(&endpoint[ZMQ_SCHEME.len()..]))
+        } else if endpoint.contains("://") {
+            Err(CommonError::InvalidStructure(format!("Unsupported agent endpoint scheme: {}", endpoint)))
+        } else {
+            Ok(TransportEndpoint::Zmq(endpoint))
+        }
+    }
+}
+
+pub fn create_outbound_transport(endpoint: &str, public_key: Vec<u8>, secret_key: Vec<u8>, server_key: Vec<u8>)
+                                 -> Result<Box<OutboundTransport>, CommonError> {
+    match TransportEndpoint::parse(endpoint)? {
+        TransportEndpoint::Zmq(address) =>
+            Ok(Box::new(ZmqOutbound::new(address, public_key, secret_key, server_key)?)),
+        TransportEndpoint::Http(url) =>
+            Ok(Box::new(HttpOutbound::new(url, public_key, secret_key, server_key)?)),
+    }
+}
+
+pub fn create_inbound_transport(endpoint: &str, max_msg_size: usize) -> Result<Box<InboundTransport>, CommonError> {
+    match TransportEndpoint::parse(endpoint)? {
+        TransportEndpoint::Zmq(address) => Ok(Box::new(ZmqInbound::new(address, max_msg_size)?)),
+        TransportEndpoint::Http(url) => Ok(Box::new(HttpInbound::new(url, max_msg_size)?)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transport_endpoint_parse_works() {
+        assert_eq!(TransportEndpoint::Zmq("127.0.0.1:9700"), TransportEndpoint::parse("127.0.0.1:9700").unwrap());
+        assert_eq!(TransportEndpoint::Zmq("127.0.0.1:9700"), TransportEndpoint::parse("tcp://127.0.0.1:9700").unwrap());
+        assert_eq!(TransportEndpoint::Http("http://127.0.0.1:9700/agent"), TransportEndpoint::parse("http://127.0.0.1:9700/agent").unwrap());
+        assert_eq!(TransportEndpoint::Http("https://agent.example.com"), TransportEndpoint::parse("https://agent.example.com").unwrap());
+    }
+
+    #[test]
+    fn transport_endpoint_parse_works_for_unsupported_scheme() {
+        assert_match!(Err(CommonError::InvalidStructure(_)), TransportEndpoint::parse("udp://127.0.0.1:9700"));
+    }
+}
@@ -0,0 +1,233 @@
+extern crate zmq_pw as zmq;
+
+use errors::common::CommonError;
+use utils::json::JsonEncodable;
+
+use super::{MsgDID, MsgTooLarge, DID, _protocol_frame};
+use super::transport::{InboundTransport, OutboundTransport};
+
+use std::cmp;
+use std::i64;
+
+/// libzmq hard limit on inbound frame size as a multiple of max_msg_size.
+const MAX_MSG_SIZE_HARD_CAP_FACTOR: usize = 4;
+
+/// ZeroMQ DEALER socket secured by CurveCP.
+pub struct ZmqOutbound {
+    pub socket: zmq::Socket,
+    pub addr: String,
+    pub public_key: Vec<u8>,
+    pub secret_key: Vec<u8>,
+    pub server_key: Vec<u8>,
+}
+
+/// ZeroMQ ROUTER socket acting as CurveCP server.
+/// Client identity is z85 encoded client public key.
+/// Message above max_msg_size and up to the hard cap (see `_max_msg_size_hard_cap`)
+/// is answered with MsgTooLarge. Frame above the hard cap is dropped by libzmq
+/// before it is buffered and the peer is disconnected without reply.
+pub struct ZmqInbound {
+    pub socket: zmq::Socket,
+    pub max_msg_size: usize,
+}
+
+impl ZmqOutbound {
+    pub fn new(addr: &str, public_key: Vec<u8>, secret_key: Vec<u8>, server_key: Vec<u8>) -> Result<ZmqOutbound, CommonError> {
+        Ok(ZmqOutbound {
+            socket: zmq::Context::new().socket(zmq::SocketType::DEALER)?,
+            addr: format!("tcp://{}", addr),
+            public_key: public_key,
+            secret_key: secret_key,
+            server_key: server_key,
+        })
+    }
+}
+
+impl OutboundTransport for ZmqOutbound {
+    fn connect(&self, sender_did: &str, receiver_did: &str) -> Result<(), CommonError> {
+        impl From<zmq::EncodeError> for CommonError {
+            fn from(err: zmq::EncodeError) -> CommonError {
+                CommonError::InvalidState(format!("Invalid data stored RemoteAgent detected while connect {:?}", err))
+            }
+        }
+        self.socket.set_identity(zmq::z85_encode(self.public_key.as_slice())?.as_bytes())
+            .map_err(map_err_trace!())?;
+        self.socket.set_curve_secretkey(self.secret_key.as_slice())
+            .map_err(map_err_trace!())?;
+        self.socket.set_curve_publickey(self.public_key.as_slice())
+            .map_err(map_err_trace!())?;
+        self.socket.set_curve_serverkey(self.server_key.as_slice())
+            .map_err(map_err_trace!())?;
+        self.socket.set_protocol_version(zmq::make_proto_version(1, 1))
+            .map_err(map_err_trace!())?;
+        self.socket.set_linger(0).map_err(map_err_trace!())?; //TODO set correct timeout
+        self.socket.connect(self.addr.as_str())
+            .map_err(map_err_trace!("ZmqOutbound::connect self.socket.connect failed"))?;
+        let msg = MsgDID {
+            did: DID {
+                sender_did: sender_did.to_string(),
+                receiver_did: receiver_did.to_string(),
+            }
+        };
+        let msg = msg.to_json().unwrap();
+        self.send(msg.as_bytes())
+    }
+
+    fn send(&self, msg: &[u8]) -> Result<(), CommonError> {
+        self.socket.send(msg, zmq::DONTWAIT).map_err(map_err_trace!())?;
+        Ok(())
+    }
+
+    fn poll_item(&self) -> zmq::PollItem {
+        self.socket.as_poll_item(zmq::POLLIN)
+    }
+
+    fn recv(&self) -> Result<Option<Vec<u8>>, CommonError> {
+        Ok(Some(self.socket.recv_bytes(zmq::DONTWAIT)?))
+    }
+}
+
+impl ZmqInbound {
+    pub fn new(addr: &str, max_msg_size: usize) -> Result<ZmqInbound, CommonError> {
+        let sock = zmq::Context::new().socket(zmq::SocketType::ROUTER).map_err(map_err_trace!())?;
+        sock.set_curve_server(true).map_err(map_err_trace!())?;
+        sock.set_maxmsgsize(ZmqInbound::_max_msg_size_hard_cap(max_msg_size)).map_err(map_err_trace!())?;
+        sock.bind(format!("tcp://{}", addr).as_str()).map_err(map_err_trace!())?;
+        Ok(ZmqInbound {
+            socket: sock,
+            max_msg_size: max_msg_size,
+        })
+    }
+
+    /// Hard cap passed to ZMQ_MAXMSGSIZE. It is above max_msg_size, so client
+    /// still gets MsgTooLarge for moderately oversized message.
+    pub fn _max_msg_size_hard_cap(max_msg_size: usize) -> i64 {
+        cmp::min(max_msg_size.saturating_mul(MAX_MSG_SIZE_HARD_CAP_FACTOR), i64::MAX as usize) as i64
+    }
+
+    /// Rejection is answered to the client: error description or framed MsgTooLarge.
+    pub fn _check_client_incoming_data(identity: Vec<u8>, pk: Option<String>, msg: Vec<u8>, max_msg_size: usize)
+                                       -> Result<(String, Vec<u8>), Vec<u8>> {
+        let identity: String = String::from_utf8(identity).map_err(|_| b"INVALID_IDENTITY(should be valid UTF-8 string)".to_vec())?;
+        let pk: String = pk.ok_or(b"MISSED_PK_IN_METADATA".to_vec())?;
+        if pk.ne(&identity) {
+            return Err(b"INVALID_IDENTITY(should be z85 encoded client pk)".to_vec());
+        }
+        if msg.len() > max_msg_size {
+            return Err(_protocol_frame(&MsgTooLarge::new(msg.len(), max_msg_size))
+                .map_err(|err| format!("MSG_TOO_LARGE({})", err).into_bytes())?);
+        }
+        Ok((identity, msg))
+    }
+}
+
+impl InboundTransport for ZmqInbound {
+    fn add_identity(&mut self, pk: &[u8], sk: &[u8]) -> Result<(), CommonError> {
+        self.socket.add_curve_keypair([pk, sk].concat().as_slice())?;
+        Ok(())
+    }
+
+    fn rm_identity(&mut self, pk: &[u8]) -> Result<(), CommonError> {
+        self.socket.remove_curve_keypair(pk)?;
+        Ok(())
+    }
+
+    fn poll_item(&self) -> zmq::PollItem {
+        self.socket.as_poll_item(zmq::POLLIN)
+    }
+
+    fn recv(&self) -> Result<Option<(String, Vec<u8>)>, CommonError> {
+        let identity = self.socket.recv_bytes(zmq::DONTWAIT)?;
+        let mut msg = self.socket.recv_msg(zmq::DONTWAIT)?;
+        let pk: Option<String> = msg.gets("__cn_client").as_ref().map(|pk| pk.to_string());
+
+        match ZmqInbound::_check_client_incoming_data(identity.clone(), pk, msg.to_vec(), self.max_msg_size) {
+            Ok((identity, msg)) => Ok(Some((identity, msg))),
+            Err(reject) => {
+                debug!("Reject client incoming data {}", String::from_utf8_lossy(&reject));
+                self.socket.send(identity, zmq::SNDMORE)?;
+                self.socket.send(reject.as_slice(), zmq::DONTWAIT)?;
+                Ok(None)
+            }
+        }
+    }
+
+    fn send(&self, identity: &str, msg: &[u8]) -> Result<(), CommonError> {
+        self.socket.send_multipart(&[identity.as_bytes(), msg], zmq::DONTWAIT)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use services::agent::{_create_zmq_socket_pair, _frame, PROTOCOL_FRAME};
+
+    #[test]
+    fn zmq_inbound_check_client_incoming_data_works() {
+        let ip = "identity-pk";
+        let i = "identity";
+        let p = "pk";
+        let test_str = "test_str";
+        let test_str_bytes = test_str.as_bytes().to_vec();
+        let binary = vec![0, 159, 146, 150];
+
+        assert_eq!((ip.to_string(), test_str_bytes.clone()), ZmqInbound::_check_client_incoming_data(ip.to_string().into_bytes(), Some(ip.to_string()), test_str_bytes, 100).unwrap());
+        assert_eq!((ip.to_string(), binary.clone()), ZmqInbound::_check_client_incoming_data(ip.to_string().into_bytes(), Some(ip.to_string()), binary, 100).unwrap());
+        assert!(ZmqInbound::_check_client_incoming_data(i.to_string().into_bytes(), Some(p.to_string()), Vec::new(), 100).unwrap_err().starts_with(b"INVALID_IDENTITY"));
+        assert!(ZmqInbound::_check_client_incoming_data(Vec::new(), None, Vec::new(), 100).unwrap_err().starts_with(b"MISSED_PK_IN_METADATA"));
+    }
+
+    #[test]
+    fn zmq_inbound_check_client_incoming_data_works_for_too_large() {
+        let ip = "identity-pk";
+
+        assert_eq!(_frame(PROTOCOL_FRAME, r#"{"msg_too_large":{"size":11,"max_size":10}}"#.as_bytes()),
+                   ZmqInbound::_check_client_incoming_data(ip.to_string().into_bytes(), Some(ip.to_string()), vec![1; 11], 10).unwrap_err());
+    }
+
+    #[test]
+    fn zmq_inbound_max_msg_size_hard_cap_works() {
+        assert_eq!(4000, ZmqInbound::_max_msg_size_hard_cap(1000));
+        assert!(ZmqInbound::_max_msg_size_hard_cap(::std::usize::MAX) > 0);
+    }
+
+    #[test]
+    fn zmq_inbound_new_sets_max_msg_size_hard_cap() {
+        let transport = ZmqInbound::new("127.0.0.1:9713", 1000).unwrap();
+        assert_eq!(4000, transport.socket.get_maxmsgsize().unwrap());
+    }
+
+    #[test]
+    fn zmq_outbound_connect_works() {
+        let dest = "test_agent_connect";
+        let addr: String = format!("inproc://{}", dest);
+        let (send_soc, recv_soc) = _create_zmq_socket_pair(dest, false).unwrap();
+        recv_soc.bind(addr.as_str()).unwrap(); //TODO enable CurveCP
+        let send_key_pair = zmq::CurveKeyPair::new().unwrap();
+        let recv_key_pair = zmq::CurveKeyPair::new().unwrap();
+        let transport = ZmqOutbound {
+            socket: send_soc,
+            addr: addr,
+            server_key: send_key_pair.public_key.to_vec(),
+            secret_key: recv_key_pair.secret_key.to_vec(),
+            public_key: recv_key_pair.public_key.to_vec(),
+        };
+        transport.connect("sd", "rd").unwrap();
+        assert_eq!(recv_soc.recv_string(zmq::DONTWAIT).unwrap().unwrap(), r#"{"did":{"sender_did":"sd","receiver_did":"rd"}}"#);
+    }
+
+    #[test]
+    fn zmq_inbound_send_works() {
+        let (send_soc, recv_soc) = _create_zmq_socket_pair("zmq_inbound_send", true).unwrap();
+        let transport = ZmqInbound {
+            socket: send_soc,
+            max_msg_size: 100,
+        };
+
+        transport.send("test_identity", "test_str".as_bytes()).unwrap();
+        assert_eq!(recv_soc.recv_string(zmq::DONTWAIT).unwrap().unwrap(), "test_identity");
+        assert_eq!(recv_soc.recv_string(zmq::DONTWAIT).unwrap().unwrap(), "test_str");
+    }
+}
@@ -1,11 +1,11 @@
 extern crate rand;
-extern crate openssl;
 
 use errors::common::CommonError;
 
 use services::anoncreds::constants::LARGE_MVECT;
 use utils::crypto::bn::BigNumber;
 use utils::crypto::pair::GroupOrderElement;
+use utils::did::DidUtils;
 use std::hash::Hash;
 use std::cmp::max;
 use std::collections::HashMap;
@@ -121,7 +121,7 @@ pub fn bignum_to_group_element(num: &BigNumber) -> Result<GroupOrderElement, Com
 }
 
 pub fn get_composite_id(issuer_did: &str, schema_seq_no: i32) -> String {
-    issuer_did.to_string() + ":" + &schema_seq_no.to_string()
+    DidUtils::normalize(issuer_did).to_string() + ":" + &schema_seq_no.to_string()
 }
 
 #[cfg(test)]
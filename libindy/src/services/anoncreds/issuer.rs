@@ -18,7 +18,8 @@ impl Issuer {
     }
 
     pub fn generate_claim_definition(&self, issuer_did: &str, schema: Schema, signature_type: Option<&str>,
-                                     create_non_revoc: bool) -> Result<(ClaimDefinition, ClaimDefinitionPrivate), AnoncredsError> {
+                                     create_non_revoc: bool, safe_primes: Option<(BigNumber, BigNumber)>)
+                                     -> Result<(ClaimDefinition, ClaimDefinitionPrivate), AnoncredsError> {
         info!(target: "anoncreds_service", "Issuer generate claim definition for Schema {:?} -> start", &schema);
 
         let signature_type = match signature_type {
@@ -26,7 +27,7 @@ impl Issuer {
             None => SignatureTypes::CL,
             _ => return Err(AnoncredsError::CommonError(CommonError::InvalidStructure(format!("Invalid Signature Type"))))
         };
-        let (pk, sk) = Issuer::_generate_keys(&schema)?;
+        let (pk, sk) = Issuer::_generate_keys(&schema, safe_primes)?;
         let (pkr, skr) = if create_non_revoc {
             Issuer::_generate_revocation_keys()?
         } else {
@@ -40,7 +41,24 @@ impl Issuer {
         Ok((claim_definition, claim_definition_private))
     }
 
-    fn _generate_keys(schema: &Schema) -> Result<(PublicKey, SecretKey), CommonError> {
+    pub fn generate_safe_prime(&self) -> Result<BigNumber, CommonError> {
+        BigNumber::generate_safe_prime(LARGE_PRIME)
+    }
+
+    pub fn check_safe_prime(&self, prime: &BigNumber) -> Result<bool, CommonError> {
+        let mut ctx = BigNumber::new_context()?;
+
+        if prime.num_bits()? != (LARGE_PRIME + 1) as i32 || !prime.is_prime(Some(&mut ctx))? {
+            return Ok(false);
+        }
+
+        let mut prime_prime = prime.sub(&BigNumber::from_u32(1)?)?;
+        prime_prime.div_word(2)?;
+
+        prime_prime.is_prime(Some(&mut ctx))
+    }
+
+    fn _generate_keys(schema: &Schema, safe_primes: Option<(BigNumber, BigNumber)>) -> Result<(PublicKey, SecretKey), CommonError> {
         info!(target: "anoncreds_service", "Issuer generate primary keys for Schema {:?} -> start", &schema);
         let mut ctx = BigNumber::new_context()?;
 
@@ -48,10 +66,16 @@ impl Issuer {
             return Err(CommonError::InvalidStructure(format!("List of attribute names is required to setup claim definition")));
         }
 
-        info!(target: "anoncreds_service", "Issuer generate_safe_prime");
-        let p = BigNumber::generate_safe_prime(LARGE_PRIME)?;
-        let q = BigNumber::generate_safe_prime(LARGE_PRIME)?;
-        info!(target: "anoncreds_service", "Issuer generate_safe_prime -> done");
+        let (p, q) = match safe_primes {
+            Some(safe_primes) => safe_primes,
+            None => {
+                info!(target: "anoncreds_service", "Issuer generate_safe_prime");
+                let p = BigNumber::generate_safe_prime(LARGE_PRIME)?;
+                let q = BigNumber::generate_safe_prime(LARGE_PRIME)?;
+                info!(target: "anoncreds_service", "Issuer generate_safe_prime -> done");
+                (p, q)
+            }
+        };
 
         let mut p_prime = p.sub(&BigNumber::from_u32(1)?)?;
         p_prime.div_word(2)?;
@@ -461,11 +485,44 @@ mod tests {
     #[test]
     fn generate_keys_works() {
         let issuer = Issuer::new();
-        let (claim_definition, claim_definition_private) = issuer.generate_claim_definition(mocks::ISSUER_DID, mocks::get_gvt_schema(), None, false).unwrap();
+        let (claim_definition, claim_definition_private) = issuer.generate_claim_definition(mocks::ISSUER_DID, mocks::get_gvt_schema(), None, false, None).unwrap();
+        assert_eq!(claim_definition, mocks::get_claim_definition());
+        assert_eq!(claim_definition_private, mocks::get_claim_definition_private());
+    }
+
+    #[test]
+    fn generate_keys_works_for_passed_safe_primes() {
+        let issuer = Issuer::new();
+        let p = BigNumber::from_dec(mocks::SAFE_PRIME_1).unwrap();
+        let q = BigNumber::from_dec(mocks::SAFE_PRIME_2).unwrap();
+
+        let (claim_definition, claim_definition_private) = issuer.generate_claim_definition(mocks::ISSUER_DID, mocks::get_gvt_schema(), None, false, Some((p, q))).unwrap();
         assert_eq!(claim_definition, mocks::get_claim_definition());
         assert_eq!(claim_definition_private, mocks::get_claim_definition_private());
     }
 
+    #[test]
+    fn check_safe_prime_works() {
+        let issuer = Issuer::new();
+        let prime = BigNumber::from_dec(mocks::SAFE_PRIME_1).unwrap();
+        assert!(issuer.check_safe_prime(&prime).unwrap());
+    }
+
+    #[test]
+    fn check_safe_prime_works_for_not_prime() {
+        let issuer = Issuer::new();
+        let mut not_prime = BigNumber::from_dec(mocks::SAFE_PRIME_1).unwrap();
+        not_prime.add_word(1).unwrap();
+        assert!(!issuer.check_safe_prime(&not_prime).unwrap());
+    }
+
+    #[test]
+    fn check_safe_prime_works_for_short_safe_prime() {
+        let issuer = Issuer::new();
+        let prime = BigNumber::from_dec("23").unwrap();
+        assert!(!issuer.check_safe_prime(&prime).unwrap());
+    }
+
     #[test]
     fn generate_v_prime_prime_works() {
         let result = BigNumber::from_dec("6620937836014079781509458870800001917950459774302786434315639456568768602266735503527631640833663968617512880802104566048179854406925811731340920442625764155409951969854303612644121780700879432308016935250101960876405664503219252820761501606507817390189252221968804450207070282033815280889897882643560437257171838117793768660731379360330750300543760457608638753190279419951706206819943151918535286779337023708838891906829360439545064730288538139152367417882097349210427894031568623898916625312124319876670702064561291393993815290033742478045530118808274555627855247830659187691067893683525651333064738899779446324124393932782261375663033826174482213348732912255948009062641783238846143256448824091556005023241191311617076266099622843011796402959351074671886795391490945230966123230485475995208322766090290573654498779155").unwrap();
@@ -479,7 +536,7 @@ mod tests {
         let signature_type = None;
         let create_non_revoc = false;
 
-        let result = issuer.generate_claim_definition(mocks::ISSUER_DID, schema, signature_type, create_non_revoc);
+        let result = issuer.generate_claim_definition(mocks::ISSUER_DID, schema, signature_type, create_non_revoc, None);
         assert!(result.is_ok());
 
         let (claim_definition, claim_definition_private) = result.unwrap();
@@ -496,7 +553,7 @@ mod tests {
         let signature_type = None;
         let create_non_revoc = true;
 
-        let result = issuer.generate_claim_definition(mocks::ISSUER_DID, schema, signature_type, create_non_revoc);
+        let result = issuer.generate_claim_definition(mocks::ISSUER_DID, schema, signature_type, create_non_revoc, None);
         assert!(result.is_ok());
 
         let (claim_definition, claim_definition_private) = result.unwrap();
@@ -514,7 +571,7 @@ mod tests {
         let signature_type = None;
         let create_non_revoc = false;
 
-        let result = issuer.generate_claim_definition(mocks::ISSUER_DID, schema, signature_type, create_non_revoc);
+        let result = issuer.generate_claim_definition(mocks::ISSUER_DID, schema, signature_type, create_non_revoc, None);
         assert!(result.is_err());
     }
 
@@ -554,7 +611,7 @@ mod tests {
         let prover = Prover::new();
 
         let (claim_definition, claim_definition_private) = issuer.generate_claim_definition(
-            mocks::ISSUER_DID, mocks::get_gvt_schema(), None, true).unwrap();
+            mocks::ISSUER_DID, mocks::get_gvt_schema(), None, true, None).unwrap();
 
         let (revocation_registry, revocation_registry_private) = issuer.issue_accumulator(
             &claim_definition.clone().unwrap().data.public_key_revocation.clone().unwrap(),
@@ -602,6 +659,8 @@ pub mod mocks {
     use services::anoncreds::types::SchemaData;
 
     pub const ISSUER_DID: &'static str = "NcYxiDXkpYi6ov5FcYDi1e";
+    pub const SAFE_PRIME_1: &'static str = "298425477551432359319017298068281828134535746771300905126443720735756534287270383542467183175737460443806952398210045827718115111810885752229119677470711305345901926067944629292942471551423868488963517954094239606951758940767987427212463600313901180668176172283994206392965011112962119159458674722785709556623";
+    pub const SAFE_PRIME_2: &'static str = "349439472409627363409079679396576405429143382066548851902530725651404099527826072557284473504836555030716422598885728830708978735185002598855838705189350963485807782488853612278899610618871178161422072823370241137803351529313449299429833947650792943989791761947876766043905469793223225806020517891749130932603";
 
     pub fn get_claim_definition() -> ClaimDefinition {
         let mut r: HashMap<String, BigNumber> = HashMap::new();
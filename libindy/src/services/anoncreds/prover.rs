@@ -7,11 +7,16 @@ use services::anoncreds::verifier::Verifier;
 use services::anoncreds::issuer::Issuer;
 use utils::crypto::bn::BigNumber;
 use utils::crypto::pair::{GroupOrderElement, PointG1, PointG2, Pair};
+use utils::did::DidUtils;
 use std::collections::{HashMap, HashSet};
 use std::cell::RefCell;
 use services::anoncreds::types::{AttributeInfo, ClaimInfo, RequestedClaimsJson, ProofRequestJson};
 use std::iter::FromIterator;
 
+extern crate rayon;
+
+use self::rayon::prelude::*;
+
 pub struct Prover {}
 
 impl Prover {
@@ -147,7 +152,7 @@ impl Prover {
                     .filter(|claim|
                         claim.attrs.contains_key(&attribute_info.name) &&
                             if attribute_info.schema_seq_no.is_some() { claim.schema_seq_no == attribute_info.schema_seq_no.unwrap() } else { true } &&
-                            if attribute_info.issuer_did.is_some() { claim.issuer_did == attribute_info.issuer_did.clone().unwrap() } else { true })
+                            if attribute_info.issuer_did.is_some() { DidUtils::normalize(&claim.issuer_did) == DidUtils::normalize(attribute_info.issuer_did.as_ref().unwrap()) } else { true })
                     .collect();
 
             found_attributes.insert(uuid, claims_for_attribute);
@@ -160,7 +165,7 @@ impl Prover {
                 if let Some(attribute_value) = claim.attrs.get(&predicate.attr_name) {
                     if Prover::_attribute_satisfy_predicate(&predicate, attribute_value)? &&
                         if predicate.schema_seq_no.is_some() { claim.schema_seq_no == predicate.schema_seq_no.unwrap() } else { true } &&
-                        if predicate.issuer_did.is_some() { claim.issuer_did == predicate.issuer_did.clone().unwrap() } else { true } {
+                        if predicate.issuer_did.is_some() { DidUtils::normalize(&claim.issuer_did) == DidUtils::normalize(predicate.issuer_did.as_ref().unwrap()) } else { true } {
                         claims_for_predicate.push(claim.clone());
                     }
                 }
@@ -291,44 +296,32 @@ impl Prover {
 
         let m1_tilde = BigNumber::rand(LARGE_M2_TILDE)?;
 
-        let mut init_proofs: HashMap<String, InitProof> = HashMap::new();
+        // Claims are independent of each other until c_h is calculated, so init proofs are built
+        // on the worker pool. Collecting keeps the order of proof_claims, so c_list and tau_list
+        // are the same as for sequential execution.
+        let init_proofs: Vec<(String, ProofClaims, InitProof)> = proof_claims
+            .into_iter()
+            .collect::<Vec<(String, ProofClaims)>>()
+            .into_par_iter()
+            .map(|(proof_claim_uuid, proof_claim)| -> Result<(String, ProofClaims, InitProof), AnoncredsError> {
+                let init_proof = Prover::_init_claim_proof(&proof_claim, &m1_tilde, tails)?;
+                Ok((proof_claim_uuid, proof_claim, init_proof))
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?;
+
         let mut c_list: Vec<Vec<u8>> = Vec::new();
         let mut tau_list: Vec<Vec<u8>> = Vec::new();
 
-        for (proof_claim_uuid, proof_claim) in &proof_claims {
-            let mut non_revoc_init_proof = None;
-            let mut m2_tilde: Option<BigNumber> = None;
-
-            if let Some(ref non_revocation_claim) = proof_claim.claim_json.signature.non_revocation_claim.clone() {
-                let proof = Prover::_init_non_revocation_proof(non_revocation_claim,
-                                                               &proof_claim.revocation_registry.clone()
-                                                                   .ok_or(CommonError::InvalidStructure("Revocation registry not found".to_string()))?
-                                                                   .accumulator,
-                                                               &proof_claim.claim_definition.data.public_key_revocation.clone()
-                                                                   .ok_or(CommonError::InvalidStructure("Field public_key_revocation not found".to_string()))?,
-                                                               tails)?;
-
-                c_list.extend_from_slice(&proof.as_c_list()?);
-                tau_list.extend_from_slice(&proof.as_tau_list()?);
-                m2_tilde = Some(group_element_to_bignum(&proof.tau_list_params.m2)?);
-                non_revoc_init_proof = Some(proof);
+        for &(_, _, ref init_proof) in init_proofs.iter() {
+            if let Some(ref non_revoc_init_proof) = init_proof.non_revoc_init_proof {
+                c_list.extend_from_slice(&non_revoc_init_proof.as_c_list()?);
+                tau_list.extend_from_slice(&non_revoc_init_proof.as_tau_list()?);
             }
 
-            let primary_init_proof = Prover::_init_proof(&proof_claim.claim_definition.data.public_key,
-                                                         &proof_claim.schema,
-                                                         &proof_claim.claim_json.signature.primary_claim,
-                                                         &proof_claim.claim_json.claim,
-                                                         &proof_claim.revealed_attrs,
-                                                         &proof_claim.predicates,
-                                                         &m1_tilde,
-                                                         m2_tilde)?;
-
-            c_list.extend_from_slice(&primary_init_proof.as_c_list()?);
-            tau_list.extend_from_slice(&primary_init_proof.as_tau_list()?);
-
-            let init_proof = InitProof::new(primary_init_proof, non_revoc_init_proof);
-
-            init_proofs.insert(proof_claim_uuid.clone(), init_proof);
+            c_list.extend_from_slice(&init_proof.primary_init_proof.as_c_list()?);
+            tau_list.extend_from_slice(&init_proof.primary_init_proof.as_tau_list()?);
         }
 
         let mut values: Vec<Vec<u8>> = Vec::new();
@@ -338,36 +331,42 @@ impl Prover {
 
         let c_h = get_hash_as_int(&mut values)?;
 
-        let mut proofs: HashMap<String, ClaimProof> = HashMap::new();
-        let mut attributes: HashMap<String, HashMap<String, Vec<String>>> = HashMap::new();
+        let claim_proofs: Vec<(String, ClaimProof, HashMap<String, Vec<String>>)> = init_proofs
+            .into_par_iter()
+            .map(|(proof_claim_uuid, proof_claim, init_proof)| -> Result<(String, ClaimProof, HashMap<String, Vec<String>>), AnoncredsError> {
+                let mut non_revoc_proof: Option<NonRevocProof> = None;
+                if let Some(ref non_revoc_init_proof) = init_proof.non_revoc_init_proof {
+                    non_revoc_proof = Some(Prover::_finalize_non_revocation_proof(&non_revoc_init_proof,
+                                                                                  &c_h)?);
+                }
 
-        for (proof_claim_uuid, init_proof) in init_proofs.iter() {
-            let proof_claim = proof_claims.get(proof_claim_uuid)
-                .ok_or(CommonError::InvalidStructure(format!("Claim not found")))?;
+                let primary_proof = Prover::_finalize_proof(&ms,
+                                                            &init_proof.primary_init_proof,
+                                                            &c_h,
+                                                            &proof_claim.claim_json.claim,
+                                                            &proof_claim.revealed_attrs)?;
 
-            let mut non_revoc_proof: Option<NonRevocProof> = None;
-            if let Some(ref non_revoc_init_proof) = init_proof.non_revoc_init_proof {
-                non_revoc_proof = Some(Prover::_finalize_non_revocation_proof(&non_revoc_init_proof,
-                                                                              &c_h)?);
-            }
+                let proof = Proof {
+                    primary_proof: primary_proof,
+                    non_revoc_proof: non_revoc_proof
+                };
 
-            let primary_proof = Prover::_finalize_proof(&ms,
-                                                        &init_proof.primary_init_proof,
-                                                        &c_h,
-                                                        &proof_claim.claim_json.claim,
-                                                        &proof_claim.revealed_attrs)?;
+                let claim_proof = ClaimProof::new(proof,
+                                                  proof_claim.claim_json.schema_seq_no,
+                                                  proof_claim.claim_json.issuer_did.clone());
 
-            let proof = Proof {
-                primary_proof: primary_proof,
-                non_revoc_proof: non_revoc_proof
-            };
+                Ok((proof_claim_uuid, claim_proof, proof_claim.claim_json.claim))
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?;
 
-            let claim_proof = ClaimProof::new(proof,
-                                              proof_claim.claim_json.schema_seq_no,
-                                              proof_claim.claim_json.issuer_did.clone());
+        let mut proofs: HashMap<String, ClaimProof> = HashMap::new();
+        let mut attributes: HashMap<String, HashMap<String, Vec<String>>> = HashMap::new();
 
+        for (proof_claim_uuid, claim_proof, claim) in claim_proofs {
             proofs.insert(proof_claim_uuid.clone(), claim_proof);
-            attributes.insert(proof_claim_uuid.clone(), proof_claim.claim_json.claim.clone());
+            attributes.insert(proof_claim_uuid, claim);
         }
 
         let aggregated_proof = AggregatedProof::new(c_h, c_list);
@@ -384,19 +383,48 @@ impl Prover {
         Ok(ProofJson::new(proofs, aggregated_proof, requested_proof))
     }
 
+    fn _init_claim_proof(proof_claim: &ProofClaims, m1_tilde: &BigNumber, tails: &HashMap<i32, PointG2>)
+                         -> Result<InitProof, AnoncredsError> {
+        let mut non_revoc_init_proof = None;
+        let mut m2_tilde: Option<BigNumber> = None;
+
+        if let Some(ref non_revocation_claim) = proof_claim.claim_json.signature.non_revocation_claim {
+            let proof = Prover::_init_non_revocation_proof(non_revocation_claim,
+                                                           &proof_claim.revocation_registry.as_ref()
+                                                               .ok_or(CommonError::InvalidStructure("Revocation registry not found".to_string()))?
+                                                               .accumulator,
+                                                           &proof_claim.claim_definition.data.public_key_revocation.as_ref()
+                                                               .ok_or(CommonError::InvalidStructure("Field public_key_revocation not found".to_string()))?,
+                                                           tails)?;
+
+            m2_tilde = Some(group_element_to_bignum(&proof.tau_list_params.m2)?);
+            non_revoc_init_proof = Some(proof);
+        }
+
+        let primary_init_proof = Prover::_init_proof(&proof_claim.claim_definition.data.public_key,
+                                                     &proof_claim.schema,
+                                                     &proof_claim.claim_json.signature.primary_claim,
+                                                     &proof_claim.claim_json.claim,
+                                                     &proof_claim.revealed_attrs,
+                                                     &proof_claim.predicates,
+                                                     m1_tilde,
+                                                     m2_tilde)?;
+
+        Ok(InitProof::new(primary_init_proof, non_revoc_init_proof))
+    }
+
     fn _init_proof(pk: &PublicKey, schema: &Schema, c1: &PrimaryClaim, attributes: &HashMap<String, Vec<String>>,
                    revealed_attrs: &Vec<String>, predicates: &Vec<Predicate>, m1_t: &BigNumber,
                    m2_t: Option<BigNumber>) -> Result<PrimaryInitProof, CommonError> {
         info!(target: "anoncreds_service", "Prover init primary proof -> start");
         let eq_proof = Prover::_init_eq_proof(&pk, schema, c1, revealed_attrs, m1_t, m2_t)?;
 
-
-        let mut ge_proofs: Vec<PrimaryPredicateGEInitProof> = Vec::new();
-
-        for predicate in predicates.iter() {
-            let ge_proof = Prover::_init_ge_proof(&pk, &eq_proof.mtilde, attributes, predicate)?;
-            ge_proofs.push(ge_proof);
-        }
+        let ge_proofs: Vec<PrimaryPredicateGEInitProof> = predicates
+            .par_iter()
+            .map(|predicate| Prover::_init_ge_proof(&pk, &eq_proof.mtilde, attributes, predicate))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?;
 
         info!(target: "anoncreds_service", "Prover init primary proof -> done");
         Ok(PrimaryInitProof::new(eq_proof, ge_proofs))
@@ -527,20 +555,31 @@ impl Prover {
         let mut t: HashMap<String, BigNumber> = HashMap::new();
         let mut c_list: Vec<BigNumber> = Vec::new();
 
-        for i in 0..ITERATION {
-            let cur_u = u.get(&i.to_string())
-                .ok_or(CommonError::InvalidStructure(format!("Value by key '{}' not found in u1", i)))?;
+        let iterations: Vec<(BigNumber, BigNumber)> = (0..ITERATION)
+            .into_par_iter()
+            .map(|i| -> Result<(BigNumber, BigNumber), CommonError> {
+                let mut ctx = BigNumber::new_context()?;
+
+                let cur_u = u.get(&i.to_string())
+                    .ok_or(CommonError::InvalidStructure(format!("Value by key '{}' not found in u1", i)))?;
 
-            let cur_r = BigNumber::rand(LARGE_VPRIME)?;
+                let cur_r = BigNumber::rand(LARGE_VPRIME)?;
 
-            let cut_t = pk.z
-                .mod_exp(&cur_u, &pk.n, Some(&mut ctx))?
-                .mul(
-                    &pk.s.mod_exp(&cur_r, &pk.n, Some(&mut ctx))?,
-                    Some(&mut ctx)
-                )?
-                .modulus(&pk.n, Some(&mut ctx))?;
+                let cut_t = pk.z
+                    .mod_exp(&cur_u, &pk.n, Some(&mut ctx))?
+                    .mul(
+                        &pk.s.mod_exp(&cur_r, &pk.n, Some(&mut ctx))?,
+                        Some(&mut ctx)
+                    )?
+                    .modulus(&pk.n, Some(&mut ctx))?;
 
+                Ok((cur_r, cut_t))
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (i, (cur_r, cut_t)) in iterations.into_iter().enumerate() {
             r.insert(i.to_string(), cur_r);
             t.insert(i.to_string(), cut_t.clone()?);
             c_list.push(cut_t)
@@ -910,6 +949,32 @@ mod tests {
         assert!(res.is_ok());
     }
 
+    #[test]
+    fn init_proof_works_for_several_predicates() {
+        let pk = issuer::mocks::get_pk();
+        let claim = mocks::get_gvt_primary_claim();
+        let revealed_attrs = mocks::get_revealed_attrs();
+        let m1_t = BigNumber::from_dec("21544287380986891419162473617242441136231665555467324140952028776483657408525689082249184862870856267009773225408151321864247533184196094757877079561221602250888815228824796823045594410522810417051146366939126434027952941761214129885206419097498982142646746254256892181011609282364766769899756219988071473111").unwrap();
+        let predicates = vec![
+            Predicate::new("height".to_string(), PredicateType::GE, 150, None, None),
+            mocks::get_gvt_predicate()
+        ];
+        let encoded_attributes = issuer::mocks::get_gvt_attributes();
+        let schema = issuer::mocks::get_gvt_schema();
+
+        let init_proof = Prover::_init_proof(&pk, &schema, &claim, &encoded_attributes, &revealed_attrs, &predicates, &m1_t, None).unwrap();
+
+        assert_eq!(2, init_proof.ge_proofs.len());
+
+        for (ge_proof, predicate) in init_proof.ge_proofs.iter().zip(predicates.iter()) {
+            assert_eq!(predicate, &ge_proof.predicate);
+
+            let sequential_ge_proof = Prover::_init_ge_proof(&pk, &init_proof.eq_proof.mtilde, &encoded_attributes, predicate).unwrap();
+            assert_eq!(sequential_ge_proof.c_list, ge_proof.c_list);
+            assert_eq!(sequential_ge_proof.tau_list, ge_proof.tau_list);
+        }
+    }
+
     #[test]
     fn finalize_proof_works() {
         let proof = mocks::get_primary_init_proof();
@@ -1008,7 +1073,7 @@ mod tests {
         let prover = Prover::new();
 
         let (claim_definition, claim_definition_private) = issuer.generate_claim_definition(
-            issuer::mocks::ISSUER_DID, issuer::mocks::get_gvt_schema(), None, true).unwrap();
+            issuer::mocks::ISSUER_DID, issuer::mocks::get_gvt_schema(), None, true, None).unwrap();
 
         let (revocation_registry, revocation_registry_private) = issuer.issue_accumulator(
             &claim_definition.clone().unwrap().data.public_key_revocation.clone().unwrap(),
@@ -7,6 +7,10 @@ use errors::common::CommonError;
 use utils::crypto::pair::{Pair, PointG1, PointG2};
 use services::anoncreds::issuer::Issuer;
 
+extern crate rayon;
+
+use self::rayon::prelude::*;
+
 pub struct Verifier {}
 
 impl Verifier {
@@ -26,34 +30,51 @@ impl Verifier {
                   schemas: &HashMap<String, Schema>) -> Result<bool, CommonError> {
         info!(target: "anoncreds_service", "Verifier verify proof -> start");
 
+        // Every claim proof is verified on the worker pool. Per-claim tau lists are joined
+        // in the iteration order of proof.proofs, as the prover did.
+        let claim_tau_lists: Vec<Vec<Vec<u8>>> = proof.proofs
+            .iter()
+            .collect::<Vec<(&String, &ClaimProof)>>()
+            .into_par_iter()
+            .map(|(proof_uuid, proof_item)| -> Result<Vec<Vec<u8>>, CommonError> {
+                let mut tau_list: Vec<Vec<u8>> = Vec::new();
+
+                let claim_definition = claim_defs.get(proof_uuid)
+                    .ok_or(CommonError::InvalidStructure(format!("Claim definition is not found")))?;
+                let schema = schemas.get(proof_uuid)
+                    .ok_or(CommonError::InvalidStructure(format!("Schema is not found")))?;
+
+                if let (Some(non_revocation_proof), Some(pkr), Some(revoc_reg)) = (proof_item.proof.non_revoc_proof.as_ref(),
+                                                                                   claim_definition.data.public_key_revocation.as_ref(),
+                                                                                   revoc_regs.get(proof_uuid)) {
+
+                    tau_list.extend_from_slice(
+                        &Verifier::_verify_non_revocation_proof(
+                            pkr,
+                            &revoc_reg.accumulator,
+                            &revoc_reg.acc_pk,
+                            &proof.aggregated_proof.c_hash,
+                            non_revocation_proof)?.as_slice()?
+                    );
+                };
+
+                tau_list.append_vec(
+                    &Verifier::_verify_primary_proof(&claim_definition.data.public_key,
+                                                     &proof.aggregated_proof.c_hash,
+                                                     &proof_item.proof.primary_proof,
+                                                     &schema)?
+                )?;
+
+                Ok(tau_list)
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?;
+
         let mut tau_list: Vec<Vec<u8>> = Vec::new();
 
-        for (proof_uuid, proof_item) in &proof.proofs {
-            let claim_definition = claim_defs.get(proof_uuid)
-                .ok_or(CommonError::InvalidStructure(format!("Claim definition is not found")))?;
-            let schema = schemas.get(proof_uuid)
-                .ok_or(CommonError::InvalidStructure(format!("Schema is not found")))?;
-
-            if let (Some(ref non_revocation_proof), Some(ref pkr), Some(ref revoc_reg)) = (proof_item.proof.non_revoc_proof.clone(),
-                                                                                           claim_definition.data.public_key_revocation.clone(),
-                                                                                           revoc_regs.get(proof_uuid)) {
-
-                tau_list.extend_from_slice(
-                    &Verifier::_verify_non_revocation_proof(
-                        pkr,
-                        &revoc_reg.accumulator,
-                        &revoc_reg.acc_pk,
-                        &proof.aggregated_proof.c_hash,
-                        &non_revocation_proof)?.as_slice()?
-                );
-            };
-
-            tau_list.append_vec(
-                &Verifier::_verify_primary_proof(&claim_definition.data.public_key,
-                                                 &proof.aggregated_proof.c_hash,
-                                                 &proof_item.proof.primary_proof,
-                                                 &schema)?
-            )?;
+        for claim_tau_list in claim_tau_lists {
+            tau_list.extend(claim_tau_list);
         }
 
         let mut values: Vec<Vec<u8>> = Vec::new();
@@ -75,8 +96,15 @@ impl Verifier {
 
         let mut t_hat: Vec<BigNumber> = Verifier::_verify_equality(pk, &primary_proof.eq_proof, c_hash, schema)?;
 
-        for ge_proof in primary_proof.ge_proofs.iter() {
-            t_hat.append(&mut Verifier::_verify_ge_predicate(pk, ge_proof, c_hash)?)
+        let ge_t_hats: Vec<Vec<BigNumber>> = primary_proof.ge_proofs
+            .par_iter()
+            .map(|ge_proof| Verifier::_verify_ge_predicate(pk, ge_proof, c_hash))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for mut ge_t_hat in ge_t_hats {
+            t_hat.append(&mut ge_t_hat)
         }
 
         info!(target: "anoncreds_service", "Verifier verify primary proof -> done");
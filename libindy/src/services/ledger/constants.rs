@@ -8,6 +8,10 @@ pub const GET_NYM: &'static str = "105";
 pub const GET_TXN: &'static str = "3";
 pub const GET_SCHEMA: &'static str = "107";
 pub const GET_CLAIM_DEF: &'static str = "108";
+pub const REVOC_REG_DEF: &'static str = "113";
+pub const REVOC_REG_ENTRY: &'static str = "114";
+pub const GET_REVOC_REG_DEF: &'static str = "115";
+pub const GET_REVOC_REG: &'static str = "116";
 pub const GET_DDO: &'static str = "120";//TODO change number
 
 pub const STEWARD: &str = "2";
@@ -15,3 +19,8 @@ pub const TRUSTEE: &str = "0";
 pub const TRUST_ANCHOR: &str = "101";
 pub const TGB: &str = "100";
 pub const ROLE_REMOVE: &str = "";
+
+pub const DID_DOC_CONTEXT: &str = "https://w3id.org/did/v1";
+pub const DID_DOC_KEY_TYPE: &str = "Ed25519VerificationKey2018";
+pub const DID_DOC_AUTHENTICATION_TYPE: &str = "Ed25519SignatureAuthentication2018";
+pub const DID_DOC_SERVICE_TYPE: &str = "IndyAgent";
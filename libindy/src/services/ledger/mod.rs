@@ -1,4 +1,5 @@
 extern crate time;
+extern crate serde_json;
 
 pub mod merkletree;
 pub mod types;
@@ -20,11 +21,36 @@ use self::types::{
     GetDdoOperation,
     NodeOperation,
     NodeOperationData,
-    GetTxnOperation
+    GetTxnOperation,
+    RevocRegDefOperation,
+    RevocRegDefValue,
+    RevocRegDefData,
+    RevocRegEntryOperation,
+    GetRevocRegDefOperation,
+    GetRevocRegOperation,
+    GetRevocRegDefReplyResult,
+    GetRevocRegReplyResult,
+    GetNymResultData,
+    GetSchemaReplyResult,
+    GetSchemaResultData,
+    GetClaimDefReplyResult,
+    Reply,
+    EndpointAttribData,
+    Endpoint,
+    DidDoc
 };
+use std::error::Error;
+
 use errors::common::CommonError;
+use errors::ledger::LedgerError;
+use services::anoncreds::helpers::get_composite_id;
+use services::anoncreds::types::{Schema, SchemaData, ClaimDefinition, ClaimDefinitionData, RevocationRegistry};
 use utils::json::{JsonEncodable, JsonDecodable};
 use utils::crypto::base58::Base58;
+use utils::did::DidUtils;
+use utils::crypto::verkey_builder::build_full_verkey;
+
+const DEFAULT_DID_METHOD: &'static str = "sov";
 
 trait LedgerSerializer {
     fn serialize(&self) -> String;
@@ -40,7 +66,9 @@ impl LedgerService {
     pub fn build_nym_request(&self, identifier: &str, dest: &str, verkey: Option<&str>,
                              alias: Option<&str>, role: Option<&str>) -> Result<String, CommonError> {
         //TODO: check identifier, dest, verkey
+        let identifier = DidUtils::unqualify(identifier);
         Base58::decode(&identifier)?;
+        let dest = DidUtils::unqualify(dest);
         Base58::decode(&dest)?;
 
         let req_id = LedgerService::get_req_id();
@@ -73,7 +101,9 @@ impl LedgerService {
     }
 
     pub fn build_get_nym_request(&self, identifier: &str, dest: &str) -> Result<String, CommonError> {
+        let identifier = DidUtils::unqualify(identifier);
         Base58::decode(&identifier)?;
+        let dest = DidUtils::unqualify(dest);
         Base58::decode(&dest)?;
 
         let req_id = LedgerService::get_req_id();
@@ -87,7 +117,9 @@ impl LedgerService {
     }
 
     pub fn build_get_ddo_request(&self, identifier: &str, dest: &str) -> Result<String, CommonError> {
+        let identifier = DidUtils::unqualify(identifier);
         Base58::decode(&identifier)?;
+        let dest = DidUtils::unqualify(dest);
         Base58::decode(&dest)?;
 
         let req_id = LedgerService::get_req_id();
@@ -102,7 +134,9 @@ impl LedgerService {
 
     pub fn build_attrib_request(&self, identifier: &str, dest: &str, hash: Option<&str>,
                                 raw: Option<&str>, enc: Option<&str>) -> Result<String, CommonError> {
+        let identifier = DidUtils::unqualify(identifier);
         Base58::decode(&identifier)?;
+        let dest = DidUtils::unqualify(dest);
         Base58::decode(&dest)?;
 
         if raw.is_none() && hash.is_none() && enc.is_none() {
@@ -122,7 +156,9 @@ impl LedgerService {
     }
 
     pub fn build_get_attrib_request(&self, identifier: &str, dest: &str, raw: &str) -> Result<String, CommonError> {
+        let identifier = DidUtils::unqualify(identifier);
         Base58::decode(&identifier)?;
+        let dest = DidUtils::unqualify(dest);
         Base58::decode(&dest)?;
 
         let req_id = LedgerService::get_req_id();
@@ -136,7 +172,18 @@ impl LedgerService {
         Ok(request_json)
     }
 
+    pub fn build_attrib_endpoint_request(&self, identifier: &str, dest: &str, address: &str, transport_key: &str) -> Result<String, CommonError> {
+        Base58::decode(transport_key)?;
+
+        let raw = EndpointAttribData { endpoint: Endpoint::new(address.to_string(), transport_key.to_string()) }
+            .to_json()
+            .map_err(|err| CommonError::InvalidState(format!("Invalid endpoint attrib json: {}", err.to_string())))?;
+
+        self.build_attrib_request(identifier, dest, None, Some(&raw), None)
+    }
+
     pub fn build_schema_request(&self, identifier: &str, data: &str) -> Result<String, CommonError> {
+        let identifier = DidUtils::unqualify(identifier);
         Base58::decode(&identifier)?;
 
         let req_id = LedgerService::get_req_id();
@@ -152,7 +199,9 @@ impl LedgerService {
     }
 
     pub fn build_get_schema_request(&self, identifier: &str, dest: &str, data: &str) -> Result<String, CommonError> {
+        let identifier = DidUtils::unqualify(identifier);
         Base58::decode(&identifier)?;
+        let dest = DidUtils::unqualify(dest);
         Base58::decode(&dest)?;
 
         let req_id = LedgerService::get_req_id();
@@ -168,6 +217,7 @@ impl LedgerService {
     }
 
     pub fn build_claim_def_request(&self, identifier: &str, _ref: i32, signature_type: &str, data: &str) -> Result<String, CommonError> {
+        let identifier = DidUtils::unqualify(identifier);
         Base58::decode(&identifier)?;
 
         let req_id = LedgerService::get_req_id();
@@ -184,7 +234,9 @@ impl LedgerService {
     }
 
     pub fn build_get_claim_def_request(&self, identifier: &str, _ref: i32, signature_type: &str, origin: &str) -> Result<String, CommonError> {
+        let identifier = DidUtils::unqualify(identifier);
         Base58::decode(&identifier)?;
+        let origin = DidUtils::unqualify(origin);
         Base58::decode(&origin)?;
 
         let req_id = LedgerService::get_req_id();
@@ -200,7 +252,9 @@ impl LedgerService {
     }
 
     pub fn build_node_request(&self, identifier: &str, dest: &str, data: &str) -> Result<String, CommonError> {
+        let identifier = DidUtils::unqualify(identifier);
         Base58::decode(&identifier)?;
+        let dest = DidUtils::unqualify(dest);
         Base58::decode(&dest)?;
 
         let req_id = LedgerService::get_req_id();
@@ -216,6 +270,7 @@ impl LedgerService {
     }
 
     pub fn build_get_txn_request(&self, identifier: &str, data: i32) -> Result<String, CommonError> {
+        let identifier = DidUtils::unqualify(identifier);
         Base58::decode(&identifier)?;
 
         let req_id = LedgerService::get_req_id();
@@ -229,14 +284,242 @@ impl LedgerService {
         Ok(request_json)
     }
 
+    /// Builds REVOC_REG_DEF request from revocation registry json created by issuer.
+    pub fn build_revoc_reg_def_request(&self, identifier: &str, data: &str) -> Result<String, CommonError> {
+        let identifier = DidUtils::unqualify(identifier);
+        Base58::decode(&identifier)?;
+
+        let revoc_reg = LedgerService::_parse_revoc_reg(data)?;
+        let issuer_did = DidUtils::unqualify(&revoc_reg.issuer_did).to_string();
+
+        let req_id = LedgerService::get_req_id();
+        let value = RevocRegDefValue::new(issuer_did.clone(), revoc_reg.accumulator.max_claim_num, revoc_reg.acc_pk);
+        let operation = RevocRegDefOperation::new(get_composite_id(&issuer_did, revoc_reg.schema_seq_no),
+                                                  revoc_reg.schema_seq_no,
+                                                  value);
+        let request = Request::new(req_id,
+                                   identifier.to_string(),
+                                   operation);
+        let request_json = Request::to_json(&request)
+            .map_err(|err| CommonError::InvalidState(format!("Invalid revoc_reg_def request json: {}", err.to_string())))?;
+        Ok(request_json)
+    }
+
+    /// Builds REVOC_REG_ENTRY request that publishes accumulator of revocation registry update
+    /// (result of claim issuance or revocation).
+    pub fn build_revoc_reg_entry_request(&self, identifier: &str, data: &str) -> Result<String, CommonError> {
+        let identifier = DidUtils::unqualify(identifier);
+        Base58::decode(&identifier)?;
+
+        let revoc_reg = LedgerService::_parse_revoc_reg(data)?;
+        let revoc_reg_def_id = get_composite_id(DidUtils::unqualify(&revoc_reg.issuer_did), revoc_reg.schema_seq_no);
+
+        let req_id = LedgerService::get_req_id();
+        let operation = RevocRegEntryOperation::new(revoc_reg_def_id, revoc_reg.accumulator);
+        let request = Request::new(req_id,
+                                   identifier.to_string(),
+                                   operation);
+        let request_json = Request::to_json(&request)
+            .map_err(|err| CommonError::InvalidState(format!("Invalid revoc_reg_entry request json: {}", err.to_string())))?;
+        Ok(request_json)
+    }
+
+    pub fn build_get_revoc_reg_def_request(&self, identifier: &str, id: &str) -> Result<String, CommonError> {
+        let identifier = DidUtils::unqualify(identifier);
+        Base58::decode(&identifier)?;
+        LedgerService::_check_revoc_reg_def_id(id)?;
+
+        let req_id = LedgerService::get_req_id();
+        let operation = GetRevocRegDefOperation::new(id.to_string());
+        let request = Request::new(req_id,
+                                   identifier.to_string(),
+                                   operation);
+        let request_json = Request::to_json(&request)
+            .map_err(|err| CommonError::InvalidState(format!("Invalid get_revoc_reg_def request json: {}", err.to_string())))?;
+        Ok(request_json)
+    }
+
+    pub fn build_get_revoc_reg_request(&self, identifier: &str, revoc_reg_def_id: &str, timestamp: i64) -> Result<String, CommonError> {
+        let identifier = DidUtils::unqualify(identifier);
+        Base58::decode(&identifier)?;
+        LedgerService::_check_revoc_reg_def_id(revoc_reg_def_id)?;
+
+        let req_id = LedgerService::get_req_id();
+        let operation = GetRevocRegOperation::new(revoc_reg_def_id.to_string(), timestamp);
+        let request = Request::new(req_id,
+                                   identifier.to_string(),
+                                   operation);
+        let request_json = Request::to_json(&request)
+            .map_err(|err| CommonError::InvalidState(format!("Invalid get_revoc_reg request json: {}", err.to_string())))?;
+        Ok(request_json)
+    }
+
+    pub fn parse_get_nym_response(&self, get_nym_response: &str) -> Result<GetNymResultData, LedgerError> {
+        let data = LedgerService::_parse_reply_data(get_nym_response, "GET_NYM")?
+            .ok_or(LedgerError::NotFound("NYM not found on the ledger".to_string()))?;
+
+        GetNymResultData::from_json(&data)
+            .map_err(map_err_trace!())
+            .map_err(|err| CommonError::InvalidState(format!("Invalid GET_NYM response data json: {}", err.description())))
+            .map_err(LedgerError::from)
+    }
+
+    /// Converts GET_SCHEMA reply to Schema used by anoncreds: attr_names of ledger data become keys.
+    pub fn parse_get_schema_response(&self, get_schema_response: &str) -> Result<Schema, LedgerError> {
+        let reply: Reply<GetSchemaReplyResult> = Reply::from_json(get_schema_response)
+            .map_err(map_err_trace!())
+            .map_err(|err| CommonError::InvalidStructure(format!("Invalid GET_SCHEMA response json: {}", err.description())))?;
+
+        let (seq_no, data) = match (reply.result.seq_no, reply.result.data) {
+            (Some(seq_no), Some(data)) => (seq_no, data),
+            _ => return Err(LedgerError::NotFound("Schema not found on the ledger".to_string()))
+        };
+
+        let data: GetSchemaResultData = serde_json::from_value(data)
+            .map_err(map_err_trace!())
+            .map_err(|err| CommonError::InvalidStructure(format!("Invalid GET_SCHEMA response data json: {}", err.description())))?;
+        Ok(Schema::new(seq_no, SchemaData::new(data.name, data.version, data.attr_names)))
+    }
+
+    /// Converts GET_CLAIM_DEF reply to ClaimDefinition used by anoncreds.
+    pub fn parse_get_claim_def_response(&self, get_claim_def_response: &str) -> Result<ClaimDefinition, LedgerError> {
+        let reply: Reply<GetClaimDefReplyResult> = Reply::from_json(get_claim_def_response)
+            .map_err(map_err_trace!())
+            .map_err(|err| CommonError::InvalidStructure(format!("Invalid GET_CLAIM_DEF response json: {}", err.description())))?;
+        let result = reply.result;
+
+        match (result.seq_no, result.data) {
+            (Some(_), Some(data)) => Ok(ClaimDefinition::new(result._ref,
+                                                             result.origin,
+                                                             result.signature_type,
+                                                             ClaimDefinitionData::new(data.primary, data.revocation))),
+            _ => Err(LedgerError::NotFound("Claim definition not found on the ledger".to_string()))
+        }
+    }
+
+    pub fn parse_get_revoc_reg_def_response(&self, get_revoc_reg_def_response: &str) -> Result<RevocRegDefData, LedgerError> {
+        let reply: Reply<GetRevocRegDefReplyResult> = Reply::from_json(get_revoc_reg_def_response)
+            .map_err(map_err_trace!())
+            .map_err(|err| CommonError::InvalidStructure(format!("Invalid GET_REVOC_REG_DEF response json: {}", err.description())))?;
+
+        match (reply.result.seq_no, reply.result.data) {
+            (Some(_), Some(data)) => Ok(data),
+            _ => Err(LedgerError::NotFound("Revocation registry definition not found on the ledger".to_string()))
+        }
+    }
+
+    /// Combines accumulator of GET_REVOC_REG reply with its definition to
+    /// revocation registry used by prover and verifier.
+    pub fn parse_get_revoc_reg_response(&self, revoc_reg_def_json: &str, get_revoc_reg_response: &str) -> Result<RevocationRegistry, LedgerError> {
+        let revoc_reg_def = RevocRegDefData::from_json(revoc_reg_def_json)
+            .map_err(map_err_trace!())
+            .map_err(|err| CommonError::InvalidStructure(format!("Invalid revoc_reg_def json: {}", err.description())))?;
+
+        let reply: Reply<GetRevocRegReplyResult> = Reply::from_json(get_revoc_reg_response)
+            .map_err(map_err_trace!())
+            .map_err(|err| CommonError::InvalidStructure(format!("Invalid GET_REVOC_REG response json: {}", err.description())))?;
+
+        let entry = match (reply.result.seq_no, reply.result.data) {
+            (Some(_), Some(data)) => data,
+            _ => return Err(LedgerError::NotFound("Revocation registry entry not found on the ledger".to_string()))
+        };
+
+        if entry.revoc_reg_def_id != revoc_reg_def.id {
+            return Err(LedgerError::CommonError(CommonError::InvalidStructure(
+                format!("GET_REVOC_REG response is for revocation registry {}, but definition {} is given", entry.revoc_reg_def_id, revoc_reg_def.id))));
+        }
+
+        if entry.txn_time > reply.result.timestamp {
+            return Err(LedgerError::CommonError(CommonError::InvalidStructure(
+                format!("GET_REVOC_REG response entry is written at {}, after requested timestamp {}", entry.txn_time, reply.result.timestamp))));
+        }
+
+        Ok(RevocationRegistry::new(entry.value,
+                                   revoc_reg_def.value.acc_pk,
+                                   revoc_reg_def.value.issuer_did,
+                                   revoc_reg_def._ref))
+    }
+
+    pub fn parse_get_attrib_endpoint(&self, get_attrib_response: &str) -> Result<Option<Endpoint>, LedgerError> {
+        match LedgerService::_parse_reply_data(get_attrib_response, "GET_ATTR")? {
+            Some(data) => {
+                let attrib_data = EndpointAttribData::from_json(&data)
+                    .map_err(map_err_trace!())
+                    .map_err(|err| CommonError::InvalidState(format!("Invalid GET_ATTR endpoint data json: {}", err.description())))?;
+                Ok(Some(attrib_data.endpoint))
+            }
+            None => Ok(None)
+        }
+    }
+
+    pub fn build_did_doc(&self, did: &str, method_name: Option<&str>,
+                         nym_data: &GetNymResultData, endpoint: Option<Endpoint>) -> Result<DidDoc, LedgerError> {
+        let verkey = build_full_verkey(&nym_data.dest, &nym_data.verkey)
+            .map_err(|_| CommonError::InvalidState(format!("Invalid verkey in NYM for did: {}", did)))?;
+        let verkey = Base58::encode(&verkey);
+
+        let role = role_name(nym_data.role.as_ref().map(String::as_str));
+
+        let method_name = DidUtils::method(did)
+            .or(method_name)
+            .unwrap_or(DEFAULT_DID_METHOD);
+        let id = DidUtils::qualify(DidUtils::unqualify(did), method_name);
+
+        Ok(DidDoc::new(id, verkey, role, endpoint))
+    }
+
+    fn _parse_reply_data(response: &str, txn_name: &str) -> Result<Option<String>, LedgerError> {
+        let response: serde_json::Value = serde_json::from_str(response)
+            .map_err(map_err_trace!())
+            .map_err(|err| CommonError::InvalidStructure(format!("Invalid {} response json: {}", txn_name, err.description())))?;
+
+        match response["result"]["data"] {
+            serde_json::Value::Null => Ok(None),
+            serde_json::Value::String(ref data) => Ok(Some(data.clone())),
+            _ => Err(LedgerError::CommonError(
+                CommonError::InvalidStructure(format!("Invalid {} response: data field is missed or malformed", txn_name))))
+        }
+    }
+
+    fn _parse_revoc_reg(data: &str) -> Result<RevocationRegistry, CommonError> {
+        RevocationRegistry::from_json(data)
+            .map_err(|err| CommonError::InvalidStructure(format!("Invalid revocation registry json: {}", err.to_string())))
+    }
+
+    /// Revocation registry is identified by issuer did and schema seq_no: "<issuer_did>:<schema_seq_no>".
+    /// Issuer did may be fully qualified, so seq_no is split off by the last colon.
+    fn _check_revoc_reg_def_id(id: &str) -> Result<(), CommonError> {
+        let mut parts = id.rsplitn(2, ':');
+        let seq_no = parts.next().unwrap_or("");
+        let issuer_did = parts.next().unwrap_or("");
+        if issuer_did.is_empty() || seq_no.parse::<i32>().is_err() {
+            return Err(CommonError::InvalidStructure(format!("Invalid revocation registry id: {}", id)));
+        }
+        Ok(())
+    }
+
     fn get_req_id() -> u64 {
         time::get_time().sec as u64 * (1e9 as u64) + time::get_time().nsec as u64
     }
 }
 
+/// Converts role code of NYM transaction to its name. NYM without role (missed or empty) has no name.
+pub fn role_name(role: Option<&str>) -> Option<String> {
+    match role {
+        Some(constants::TRUSTEE) => Some("TRUSTEE".to_string()),
+        Some(constants::STEWARD) => Some("STEWARD".to_string()),
+        Some(constants::TRUST_ANCHOR) => Some("TRUST_ANCHOR".to_string()),
+        Some(constants::TGB) => Some("TGB".to_string()),
+        Some(role) if !role.is_empty() => Some(role.to_string()),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use services::anoncreds::prover::mocks::get_accumulator;
+    use services::anoncreds::verifier::mocks::get_accum_publick_key;
 
     #[test]
     fn build_nym_request_works_for_only_required_fields() {
@@ -449,4 +732,248 @@ mod tests {
         let get_txn_request = get_txn_request.unwrap();
         assert!(get_txn_request.contains(expected_result));
     }
+
+    #[test]
+    fn build_get_revoc_reg_def_request_works() {
+        let ledger_service = LedgerService::new();
+
+        let expected_result = r#""identifier":"identifier","operation":{"type":"115","id":"V4SGRU86Z58d6TV7PBUe6f:1"}"#;
+
+        let request = ledger_service.build_get_revoc_reg_def_request("identifier", "V4SGRU86Z58d6TV7PBUe6f:1").unwrap();
+        assert!(request.contains(expected_result));
+    }
+
+    #[test]
+    fn build_get_revoc_reg_request_works() {
+        let ledger_service = LedgerService::new();
+
+        let expected_result = r#""identifier":"identifier","operation":{"type":"116","revocRegDefId":"V4SGRU86Z58d6TV7PBUe6f:1","timestamp":1500000000}"#;
+
+        let request = ledger_service.build_get_revoc_reg_request("identifier", "V4SGRU86Z58d6TV7PBUe6f:1", 1500000000).unwrap();
+        assert!(request.contains(expected_result));
+    }
+
+    #[test]
+    fn build_get_revoc_reg_request_works_for_invalid_id() {
+        let ledger_service = LedgerService::new();
+
+        let res = ledger_service.build_get_revoc_reg_request("identifier", "V4SGRU86Z58d6TV7PBUe6f", 1500000000);
+        assert_match!(Err(CommonError::InvalidStructure(_)), res);
+    }
+
+    #[test]
+    fn build_revoc_reg_entry_request_works_for_invalid_data() {
+        let ledger_service = LedgerService::new();
+
+        let res = ledger_service.build_revoc_reg_entry_request("identifier", r#"{"issuer_did":"V4SGRU86Z58d6TV7PBUe6f","schema_seq_no":1}"#);
+        assert_match!(Err(CommonError::InvalidStructure(_)), res);
+    }
+
+    #[test]
+    fn build_get_revoc_reg_request_works_for_qualified_issuer_did() {
+        let ledger_service = LedgerService::new();
+
+        let request = ledger_service.build_get_revoc_reg_request("identifier", "did:sov:V4SGRU86Z58d6TV7PBUe6f:1", 1500000000).unwrap();
+        assert!(request.contains(r#""revocRegDefId":"did:sov:V4SGRU86Z58d6TV7PBUe6f:1""#));
+
+        let res = ledger_service.build_get_revoc_reg_request("identifier", "did:sov:V4SGRU86Z58d6TV7PBUe6f", 1500000000);
+        assert_match!(Err(CommonError::InvalidStructure(_)), res);
+    }
+
+    #[test]
+    fn parse_get_revoc_reg_def_response_works_for_not_found() {
+        let ledger_service = LedgerService::new();
+        let response = r#"{"op":"REPLY","result":{"identifier":"V4SGRU86Z58d6TV7PBUe6f","reqId":1,"seqNo":null,"type":"115","id":"V4SGRU86Z58d6TV7PBUe6f:1","data":null}}"#;
+
+        let res = ledger_service.parse_get_revoc_reg_def_response(response);
+        assert_match!(Err(LedgerError::NotFound(_)), res);
+    }
+
+    fn _revoc_reg_def_json() -> String {
+        RevocRegDefData {
+            id: "V4SGRU86Z58d6TV7PBUe6f:1".to_string(),
+            _ref: 1,
+            value: RevocRegDefValue::new("V4SGRU86Z58d6TV7PBUe6f".to_string(), 5, get_accum_publick_key())
+        }.to_json().unwrap()
+    }
+
+    fn _get_revoc_reg_response(timestamp: i64, txn_time: i64) -> String {
+        format!(r#"{{"op":"REPLY","result":{{"identifier":"V4SGRU86Z58d6TV7PBUe6f","reqId":1,"seqNo":12,"type":"116","revocRegDefId":"V4SGRU86Z58d6TV7PBUe6f:1","timestamp":{},"data":{{"revocRegDefId":"V4SGRU86Z58d6TV7PBUe6f:1","value":{},"txnTime":{}}}}}}}"#,
+                timestamp, get_accumulator().to_json().unwrap(), txn_time)
+    }
+
+    #[test]
+    fn parse_get_revoc_reg_response_works() {
+        let ledger_service = LedgerService::new();
+
+        let revoc_reg = ledger_service.parse_get_revoc_reg_response(&_revoc_reg_def_json(), &_get_revoc_reg_response(1500000000, 1400000000)).unwrap();
+        assert_eq!("V4SGRU86Z58d6TV7PBUe6f", revoc_reg.issuer_did);
+        assert_eq!(1, revoc_reg.schema_seq_no);
+        assert_eq!(5, revoc_reg.accumulator.max_claim_num);
+    }
+
+    #[test]
+    fn parse_get_revoc_reg_response_works_for_entry_after_timestamp() {
+        let ledger_service = LedgerService::new();
+
+        let res = ledger_service.parse_get_revoc_reg_response(&_revoc_reg_def_json(), &_get_revoc_reg_response(1400000000, 1500000000));
+        assert_match!(Err(LedgerError::CommonError(CommonError::InvalidStructure(_))), res);
+    }
+
+    #[test]
+    fn parse_get_nym_response_works() {
+        let ledger_service = LedgerService::new();
+        let response = r#"{"op":"REPLY","result":{"identifier":"V4SGRU86Z58d6TV7PBUe6f","reqId":1,"type":"105","dest":"V4SGRU86Z58d6TV7PBUe6f","data":"{\"identifier\":\"V4SGRU86Z58d6TV7PBUe6f\",\"dest\":\"V4SGRU86Z58d6TV7PBUe6f\",\"role\":\"0\",\"verkey\":\"~CoRER63DVYnWZtK8uAzNbx\"}"}}"#;
+
+        let nym_data = ledger_service.parse_get_nym_response(response).unwrap();
+        assert_eq!("V4SGRU86Z58d6TV7PBUe6f", nym_data.dest);
+        assert_eq!(Some("0".to_string()), nym_data.role);
+        assert_eq!(Some("~CoRER63DVYnWZtK8uAzNbx".to_string()), nym_data.verkey);
+    }
+
+    #[test]
+    fn parse_get_nym_response_works_for_not_found() {
+        let ledger_service = LedgerService::new();
+        let response = r#"{"op":"REPLY","result":{"identifier":"V4SGRU86Z58d6TV7PBUe6f","reqId":1,"type":"105","dest":"V4SGRU86Z58d6TV7PBUe6f","data":null}}"#;
+
+        let res = ledger_service.parse_get_nym_response(response);
+        assert_match!(Err(LedgerError::NotFound(_)), res);
+    }
+
+    #[test]
+    fn parse_get_schema_response_works() {
+        let ledger_service = LedgerService::new();
+        let response = r#"{"op":"REPLY","result":{"identifier":"V4SGRU86Z58d6TV7PBUe6f","reqId":1,"seqNo":10,"type":"107","dest":"V4SGRU86Z58d6TV7PBUe6f","data":{"name":"gvt","version":"1.0","attr_names":["name","age"],"origin":"V4SGRU86Z58d6TV7PBUe6f"}}}"#;
+
+        let schema = ledger_service.parse_get_schema_response(response).unwrap();
+        assert_eq!(10, schema.seq_no);
+        assert_eq!("gvt", schema.data.name);
+        assert_eq!("1.0", schema.data.version);
+        assert!(schema.data.keys.contains("name"));
+        assert!(schema.data.keys.contains("age"));
+    }
+
+    #[test]
+    fn parse_get_schema_response_works_for_not_found() {
+        let ledger_service = LedgerService::new();
+        let response = r#"{"op":"REPLY","result":{"identifier":"V4SGRU86Z58d6TV7PBUe6f","reqId":1,"seqNo":null,"type":"107","dest":"V4SGRU86Z58d6TV7PBUe6f","data":{"name":"gvt","version":"1.0"}}}"#;
+
+        let res = ledger_service.parse_get_schema_response(response);
+        assert_match!(Err(LedgerError::NotFound(_)), res);
+    }
+
+    #[test]
+    fn parse_get_schema_response_works_for_missed_attr_names() {
+        let ledger_service = LedgerService::new();
+        let response = r#"{"op":"REPLY","result":{"identifier":"V4SGRU86Z58d6TV7PBUe6f","reqId":1,"seqNo":10,"type":"107","dest":"V4SGRU86Z58d6TV7PBUe6f","data":{"name":"gvt","version":"1.0"}}}"#;
+
+        let res = ledger_service.parse_get_schema_response(response);
+        assert_match!(Err(LedgerError::CommonError(CommonError::InvalidStructure(_))), res);
+    }
+
+    #[test]
+    fn parse_get_claim_def_response_works() {
+        let ledger_service = LedgerService::new();
+        let response = r#"{"op":"REPLY","result":{"identifier":"V4SGRU86Z58d6TV7PBUe6f","reqId":1,"seqNo":11,"type":"108","ref":10,"signature_type":"CL","origin":"V4SGRU86Z58d6TV7PBUe6f","data":{"primary":{"n":"1","s":"2","rms":"3","r":{"name":"4"},"rctxt":"5","z":"6"},"revocation":{}}}}"#;
+
+        let claim_def = ledger_service.parse_get_claim_def_response(response).unwrap();
+        assert_eq!(10, claim_def.schema_seq_no);
+        assert_eq!("V4SGRU86Z58d6TV7PBUe6f", claim_def.issuer_did);
+        assert_eq!(None, claim_def.data.public_key_revocation);
+        assert!(claim_def.to_json().unwrap().contains(r#""ref":10,"origin":"V4SGRU86Z58d6TV7PBUe6f","signature_type":"CL""#));
+    }
+
+    #[test]
+    fn parse_get_claim_def_response_works_for_not_found() {
+        let ledger_service = LedgerService::new();
+        let response = r#"{"op":"REPLY","result":{"identifier":"V4SGRU86Z58d6TV7PBUe6f","reqId":1,"seqNo":null,"type":"108","ref":10,"signature_type":"CL","origin":"V4SGRU86Z58d6TV7PBUe6f","data":null}}"#;
+
+        let res = ledger_service.parse_get_claim_def_response(response);
+        assert_match!(Err(LedgerError::NotFound(_)), res);
+    }
+
+    #[test]
+    fn parse_get_attrib_endpoint_works() {
+        let ledger_service = LedgerService::new();
+        let response = r#"{"op":"REPLY","result":{"identifier":"V4SGRU86Z58d6TV7PBUe6f","reqId":1,"type":"104","dest":"V4SGRU86Z58d6TV7PBUe6f","raw":"endpoint","data":"{\"endpoint\":{\"ha\":\"127.0.0.1:9700\",\"verkey\":\"Bwsw5CT9cvyX7WcvMPwrwwVWqjJoZjtvpUBcA9PDg1rg\"}}"}}"#;
+
+        let endpoint = ledger_service.parse_get_attrib_endpoint(response).unwrap().unwrap();
+        assert_eq!(Some("127.0.0.1:9700".to_string()), endpoint.ha);
+        assert_eq!(Some("Bwsw5CT9cvyX7WcvMPwrwwVWqjJoZjtvpUBcA9PDg1rg".to_string()), endpoint.verkey);
+    }
+
+    #[test]
+    fn parse_get_attrib_endpoint_works_for_missed_attrib() {
+        let ledger_service = LedgerService::new();
+        let response = r#"{"op":"REPLY","result":{"identifier":"V4SGRU86Z58d6TV7PBUe6f","reqId":1,"type":"104","dest":"V4SGRU86Z58d6TV7PBUe6f","raw":"endpoint","data":null}}"#;
+
+        assert_eq!(None, ledger_service.parse_get_attrib_endpoint(response).unwrap());
+    }
+
+    #[test]
+    fn role_name_works() {
+        assert_eq!(Some("TRUSTEE".to_string()), role_name(Some(constants::TRUSTEE)));
+        assert_eq!(Some("STEWARD".to_string()), role_name(Some(constants::STEWARD)));
+        assert_eq!(Some("42".to_string()), role_name(Some("42")));
+        assert_eq!(None, role_name(Some("")));
+        assert_eq!(None, role_name(None));
+    }
+
+    #[test]
+    fn build_did_doc_works() {
+        let ledger_service = LedgerService::new();
+        let nym_data = GetNymResultData {
+            identifier: Some("V4SGRU86Z58d6TV7PBUe6f".to_string()),
+            dest: "V4SGRU86Z58d6TV7PBUe6f".to_string(),
+            role: Some("0".to_string()),
+            verkey: Some("GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL".to_string())
+        };
+        let endpoint = Endpoint {
+            ha: Some("127.0.0.1:9700".to_string()),
+            verkey: Some("Bwsw5CT9cvyX7WcvMPwrwwVWqjJoZjtvpUBcA9PDg1rg".to_string())
+        };
+
+        let did_doc = ledger_service.build_did_doc("V4SGRU86Z58d6TV7PBUe6f", None, &nym_data, Some(endpoint)).unwrap();
+        assert_eq!("did:sov:V4SGRU86Z58d6TV7PBUe6f", did_doc.id);
+        assert_eq!(Some("TRUSTEE".to_string()), did_doc.role);
+        assert_eq!("GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL", did_doc.public_key[0].public_key_base58);
+        assert_eq!("did:sov:V4SGRU86Z58d6TV7PBUe6f#key-1", did_doc.authentication[0].public_key);
+        assert_eq!("127.0.0.1:9700", did_doc.service[0].service_endpoint);
+    }
+
+    #[test]
+    fn build_did_doc_works_for_qualified_did_without_endpoint() {
+        let ledger_service = LedgerService::new();
+        let nym_data = GetNymResultData {
+            identifier: Some("V4SGRU86Z58d6TV7PBUe6f".to_string()),
+            dest: "V4SGRU86Z58d6TV7PBUe6f".to_string(),
+            role: None,
+            verkey: None
+        };
+
+        let did_doc = ledger_service.build_did_doc("did:peer:V4SGRU86Z58d6TV7PBUe6f", Some("sov"), &nym_data, None).unwrap();
+        assert_eq!("did:peer:V4SGRU86Z58d6TV7PBUe6f", did_doc.id);
+        assert_eq!(None, did_doc.role);
+        assert!(did_doc.service.is_empty());
+    }
+
+    #[test]
+    fn build_attrib_endpoint_request_works() {
+        let ledger_service = LedgerService::new();
+        let identifier = "identifier";
+        let dest = "dest";
+
+        let expected_result = r#""identifier":"identifier","operation":{"type":"100","dest":"dest","raw":"{\"endpoint\":{\"ha\":\"127.0.0.1:9700\",\"verkey\":\"Bwsw5CT9cvyX7WcvMPwrwwVWqjJoZjtvpUBcA9PDg1rg\"}}"}"#;
+
+        let attrib_request = ledger_service.build_attrib_endpoint_request(identifier, dest, "127.0.0.1:9700", "Bwsw5CT9cvyX7WcvMPwrwwVWqjJoZjtvpUBcA9PDg1rg").unwrap();
+        assert!(attrib_request.contains(expected_result));
+    }
+
+    #[test]
+    fn build_attrib_endpoint_request_works_for_invalid_transport_key() {
+        let ledger_service = LedgerService::new();
+
+        let res = ledger_service.build_attrib_endpoint_request("identifier", "dest", "127.0.0.1:9700", "invalid_base58_0OIl");
+        assert_match!(Err(CommonError::InvalidStructure(_)), res);
+    }
 }
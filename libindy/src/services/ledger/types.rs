@@ -1,6 +1,14 @@
 extern crate serde_json;
 
-use services::anoncreds::types::{PublicKey, RevocationPublicKey};
+use std::collections::HashSet;
+
+use services::anoncreds::types::{
+    Accumulator,
+    AccumulatorPublicKey,
+    PublicKey,
+    RevocationPublicKey,
+    SignatureTypes
+};
 use utils::json::{JsonEncodable, JsonDecodable};
 use services::ledger::constants::{
     NODE,
@@ -13,7 +21,15 @@ use services::ledger::constants::{
     GET_SCHEMA,
     CLAIM_DEF,
     GET_CLAIM_DEF,
-    GET_TXN
+    GET_TXN,
+    REVOC_REG_DEF,
+    REVOC_REG_ENTRY,
+    GET_REVOC_REG_DEF,
+    GET_REVOC_REG,
+    DID_DOC_CONTEXT,
+    DID_DOC_KEY_TYPE,
+    DID_DOC_AUTHENTICATION_TYPE,
+    DID_DOC_SERVICE_TYPE
 };
 
 #[derive(Serialize, PartialEq, Debug)]
@@ -193,15 +209,26 @@ impl GetSchemaOperation {
 
 impl JsonEncodable for GetSchemaOperation {}
 
-#[derive(Deserialize, PartialEq, Debug)]
+#[derive(Deserialize, Eq, PartialEq, Debug)]
 #[serde(rename_all = "camelCase")]
+pub struct GetSchemaReplyResult {
+    pub identifier: String,
+    pub req_id: u64,
+    pub seq_no: Option<i32>,
+    #[serde(rename = "type")]
+    pub _type: String,
+    /* not found reply carries data without attr_names, so data is parsed only if seqNo is set */
+    pub data: Option<serde_json::Value>,
+    pub dest: Option<String>
+}
+
+impl<'a> JsonDecodable<'a> for GetSchemaReplyResult {}
+
+#[derive(Deserialize, Eq, PartialEq, Debug)]
 pub struct GetSchemaResultData {
-    pub attr_names: Vec<String>,
+    pub attr_names: HashSet<String>,
     pub name: String,
-    pub origin: String,
-    pub seq_no: String,
-    #[serde(rename = "type")]
-    pub _type: Option<String>,
+    pub origin: Option<String>,
     pub version: String
 }
 
@@ -250,7 +277,7 @@ impl JsonEncodable for ClaimDefOperation {}
 #[derive(Serialize, PartialEq, Debug, Deserialize)]
 pub struct ClaimDefOperationData {
     pub primary: PublicKey,
-    #[serde(serialize_with = "empty_map_instead_of_null")] //FIXME
+    #[serde(serialize_with = "empty_map_instead_of_null", deserialize_with = "null_instead_of_empty_map", default)] //FIXME
     pub revocation: Option<RevocationPublicKey>
 }
 
@@ -265,8 +292,9 @@ impl ClaimDefOperationData {
 
 //FIXME workaround for ledger: serialize required dictionary as empty instead of using null
 extern crate serde;
-use self::serde::Serializer;
+use self::serde::{Serializer, Deserializer, Deserialize};
 use self::serde::ser::SerializeMap;
+use self::serde::de::Error;
 fn empty_map_instead_of_null<S>(x: &Option<RevocationPublicKey>, s: S) -> Result<S::Ok, S::Error>
     where S: Serializer {
     if let &Some(ref x) = x {
@@ -275,6 +303,15 @@ fn empty_map_instead_of_null<S>(x: &Option<RevocationPublicKey>, s: S) -> Result
         s.serialize_map(None)?.end()
     }
 }
+
+fn null_instead_of_empty_map<'de, D>(d: D) -> Result<Option<RevocationPublicKey>, D::Error>
+    where D: Deserializer<'de> {
+    match serde_json::Value::deserialize(d)? {
+        serde_json::Value::Null => Ok(None),
+        serde_json::Value::Object(ref map) if map.is_empty() => Ok(None),
+        value => serde_json::from_value(value).map(Some).map_err(D::Error::custom)
+    }
+}
 //FIXME
 
 impl JsonEncodable for ClaimDefOperationData {}
@@ -393,6 +430,107 @@ impl GetTxnOperation {
 
 impl JsonEncodable for GetTxnOperation {}
 
+#[derive(Serialize, Debug)]
+pub struct RevocRegDefOperation {
+    #[serde(rename = "type")]
+    pub _type: String,
+    pub id: String,
+    #[serde(rename = "ref")]
+    pub _ref: i32,
+    pub value: RevocRegDefValue
+}
+
+impl RevocRegDefOperation {
+    pub fn new(id: String, _ref: i32, value: RevocRegDefValue) -> RevocRegDefOperation {
+        RevocRegDefOperation {
+            _type: REVOC_REG_DEF.to_string(),
+            id: id,
+            _ref: _ref,
+            value: value
+        }
+    }
+}
+
+impl JsonEncodable for RevocRegDefOperation {}
+
+/// Static part of revocation registry. Accumulator itself is published by REVOC_REG_ENTRY.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RevocRegDefValue {
+    pub issuer_did: String,
+    pub max_claim_num: i32,
+    pub acc_pk: AccumulatorPublicKey
+}
+
+impl RevocRegDefValue {
+    pub fn new(issuer_did: String, max_claim_num: i32, acc_pk: AccumulatorPublicKey) -> RevocRegDefValue {
+        RevocRegDefValue {
+            issuer_did: issuer_did,
+            max_claim_num: max_claim_num,
+            acc_pk: acc_pk
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RevocRegEntryOperation {
+    #[serde(rename = "type")]
+    pub _type: String,
+    pub revoc_reg_def_id: String,
+    pub value: Accumulator
+}
+
+impl RevocRegEntryOperation {
+    pub fn new(revoc_reg_def_id: String, value: Accumulator) -> RevocRegEntryOperation {
+        RevocRegEntryOperation {
+            _type: REVOC_REG_ENTRY.to_string(),
+            revoc_reg_def_id: revoc_reg_def_id,
+            value: value
+        }
+    }
+}
+
+impl JsonEncodable for RevocRegEntryOperation {}
+
+#[derive(Serialize, PartialEq, Debug)]
+pub struct GetRevocRegDefOperation {
+    #[serde(rename = "type")]
+    pub _type: String,
+    pub id: String
+}
+
+impl GetRevocRegDefOperation {
+    pub fn new(id: String) -> GetRevocRegDefOperation {
+        GetRevocRegDefOperation {
+            _type: GET_REVOC_REG_DEF.to_string(),
+            id: id
+        }
+    }
+}
+
+impl JsonEncodable for GetRevocRegDefOperation {}
+
+#[derive(Serialize, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetRevocRegOperation {
+    #[serde(rename = "type")]
+    pub _type: String,
+    pub revoc_reg_def_id: String,
+    pub timestamp: i64
+}
+
+impl GetRevocRegOperation {
+    pub fn new(revoc_reg_def_id: String, timestamp: i64) -> GetRevocRegOperation {
+        GetRevocRegOperation {
+            _type: GET_REVOC_REG.to_string(),
+            revoc_reg_def_id: revoc_reg_def_id,
+            timestamp: timestamp
+        }
+    }
+}
+
+impl JsonEncodable for GetRevocRegOperation {}
+
 #[derive(Deserialize, Eq, PartialEq, Debug)]
 pub struct Reply<T> {
     pub op: String,
@@ -401,6 +539,73 @@ pub struct Reply<T> {
 
 impl<'a, T: JsonDecodable<'a>> JsonDecodable<'a> for Reply<T> {}
 
+#[derive(Deserialize, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetClaimDefReplyResult {
+    pub identifier: String,
+    pub req_id: u64,
+    pub seq_no: Option<i32>,
+    #[serde(rename = "type")]
+    pub _type: String,
+    pub data: Option<ClaimDefOperationData>,
+    pub origin: String,
+    pub signature_type: SignatureTypes,
+    #[serde(rename = "ref")]
+    pub _ref: i32
+}
+
+impl<'a> JsonDecodable<'a> for GetClaimDefReplyResult {}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetRevocRegDefReplyResult {
+    pub identifier: String,
+    pub req_id: u64,
+    pub seq_no: Option<i32>,
+    #[serde(rename = "type")]
+    pub _type: String,
+    pub id: String,
+    pub data: Option<RevocRegDefData>
+}
+
+impl<'a> JsonDecodable<'a> for GetRevocRegDefReplyResult {}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RevocRegDefData {
+    pub id: String,
+    #[serde(rename = "ref")]
+    pub _ref: i32,
+    pub value: RevocRegDefValue
+}
+
+impl JsonEncodable for RevocRegDefData {}
+
+impl<'a> JsonDecodable<'a> for RevocRegDefData {}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetRevocRegReplyResult {
+    pub identifier: String,
+    pub req_id: u64,
+    pub seq_no: Option<i32>,
+    #[serde(rename = "type")]
+    pub _type: String,
+    pub revoc_reg_def_id: String,
+    pub timestamp: i64,
+    pub data: Option<RevocRegEntryData>
+}
+
+impl<'a> JsonDecodable<'a> for GetRevocRegReplyResult {}
+
+/// Latest REVOC_REG_ENTRY written before requested timestamp.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RevocRegEntryData {
+    pub revoc_reg_def_id: String,
+    pub value: Accumulator,
+    pub txn_time: i64
+}
+
 #[derive(Deserialize, Eq, PartialEq, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct GetNymReplyResult {
@@ -414,7 +619,7 @@ pub struct GetNymReplyResult {
 
 impl<'a> JsonDecodable<'a> for GetNymReplyResult {}
 
-#[derive(Deserialize, Eq, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct GetNymResultData {
     pub identifier: Option<String>,
@@ -423,4 +628,137 @@ pub struct GetNymResultData {
     pub verkey: Option<String>
 }
 
+impl JsonEncodable for GetNymResultData {}
+
 impl<'a> JsonDecodable<'a> for GetNymResultData {}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug)]
+pub struct EndpointAttribData {
+    pub endpoint: Endpoint
+}
+
+impl JsonEncodable for EndpointAttribData {}
+
+impl<'a> JsonDecodable<'a> for EndpointAttribData {}
+
+#[derive(Serialize, Deserialize, Eq, PartialEq, Debug)]
+pub struct Endpoint {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ha: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verkey: Option<String>
+}
+
+impl Endpoint {
+    pub fn new(ha: String, verkey: String) -> Endpoint {
+        Endpoint {
+            ha: Some(ha),
+            verkey: Some(verkey)
+        }
+    }
+}
+
+impl JsonEncodable for Endpoint {}
+
+impl<'a> JsonDecodable<'a> for Endpoint {}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DidDocSource {
+    pub nym_data: GetNymResultData,
+    pub endpoint: Option<Endpoint>
+}
+
+impl DidDocSource {
+    pub fn new(nym_data: GetNymResultData, endpoint: Option<Endpoint>) -> DidDocSource {
+        DidDocSource {
+            nym_data: nym_data,
+            endpoint: endpoint
+        }
+    }
+}
+
+impl JsonEncodable for DidDocSource {}
+
+impl<'a> JsonDecodable<'a> for DidDocSource {}
+
+#[derive(Serialize, Debug)]
+pub struct DidDoc {
+    #[serde(rename = "@context")]
+    pub context: String,
+    pub id: String,
+    #[serde(rename = "publicKey")]
+    pub public_key: Vec<DidDocPublicKey>,
+    pub authentication: Vec<DidDocAuthentication>,
+    pub service: Vec<DidDocService>,
+    pub role: Option<String>
+}
+
+impl DidDoc {
+    pub fn new(id: String, verkey: String, role: Option<String>, endpoint: Option<Endpoint>) -> DidDoc {
+        let key_id = format!("{}#key-1", id);
+
+        let service = match endpoint {
+            Some(Endpoint { ha: Some(ha), verkey: endpoint_verkey }) => vec![
+                DidDocService {
+                    id: format!("{}#agent", id),
+                    _type: DID_DOC_SERVICE_TYPE.to_string(),
+                    service_endpoint: ha,
+                    recipient_keys: endpoint_verkey.into_iter().collect()
+                }
+            ],
+            _ => Vec::new()
+        };
+
+        DidDoc {
+            context: DID_DOC_CONTEXT.to_string(),
+            public_key: vec![
+                DidDocPublicKey {
+                    id: key_id.clone(),
+                    _type: DID_DOC_KEY_TYPE.to_string(),
+                    controller: id.clone(),
+                    public_key_base58: verkey
+                }
+            ],
+            authentication: vec![
+                DidDocAuthentication {
+                    _type: DID_DOC_AUTHENTICATION_TYPE.to_string(),
+                    public_key: key_id
+                }
+            ],
+            service: service,
+            role: role,
+            id: id
+        }
+    }
+}
+
+impl JsonEncodable for DidDoc {}
+
+#[derive(Serialize, Debug)]
+pub struct DidDocPublicKey {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub _type: String,
+    pub controller: String,
+    #[serde(rename = "publicKeyBase58")]
+    pub public_key_base58: String
+}
+
+#[derive(Serialize, Debug)]
+pub struct DidDocAuthentication {
+    #[serde(rename = "type")]
+    pub _type: String,
+    #[serde(rename = "publicKey")]
+    pub public_key: String
+}
+
+#[derive(Serialize, Debug)]
+pub struct DidDocService {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub _type: String,
+    #[serde(rename = "serviceEndpoint")]
+    pub service_endpoint: String,
+    #[serde(rename = "recipientKeys")]
+    pub recipient_keys: Vec<String>
+}
@@ -3,4 +3,5 @@ pub mod anoncreds;
 pub mod pool;
 pub mod wallet;
 pub mod ledger;
+pub mod pairwise;
 pub mod signus;
\ No newline at end of file
@@ -0,0 +1,39 @@
+use utils::json::{JsonEncodable, JsonDecodable};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Pairwise {
+    pub my_did: String,
+    pub their_did: String,
+    pub metadata: Option<String>,
+}
+
+impl Pairwise {
+    pub fn new(my_did: String, their_did: String, metadata: Option<String>) -> Pairwise {
+        Pairwise {
+            my_did: my_did,
+            their_did: their_did,
+            metadata: metadata,
+        }
+    }
+}
+
+impl JsonEncodable for Pairwise {}
+
+impl<'a> JsonDecodable<'a> for Pairwise {}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PairwiseInfo {
+    pub my_did: String,
+    pub metadata: Option<String>,
+}
+
+impl PairwiseInfo {
+    pub fn new(pairwise: Pairwise) -> PairwiseInfo {
+        PairwiseInfo {
+            my_did: pairwise.my_did,
+            metadata: pairwise.metadata,
+        }
+    }
+}
+
+impl JsonEncodable for PairwiseInfo {}
@@ -1082,8 +1082,6 @@ mod tests {
     }
 
     mod nodes_emulator {
-        extern crate sodiumoxide;
-
         use services::pool::rust_base58::ToBase58;
         use std::thread;
         use super::*;
@@ -1091,9 +1089,9 @@ mod tests {
         pub static POLL_TIMEOUT: i64 = 1000; /* in ms */
 
         pub fn start() -> (GenTransaction, thread::JoinHandle<Vec<String>>) {
-            let (vk, sk) = sodiumoxide::crypto::sign::ed25519::gen_keypair();
-            let pkc = ED25519::vk_to_curve25519(&Vec::from(&vk.0 as &[u8])).expect("Invalid pkc");
-            let skc = ED25519::sk_to_curve25519(&Vec::from(&sk.0 as &[u8])).expect("Invalid skc");
+            let (vk, sk) = ED25519::create_key_pair_for_signature(None).expect("Invalid keys");
+            let pkc = ED25519::vk_to_curve25519(&vk).expect("Invalid pkc");
+            let skc = ED25519::sk_to_curve25519(&sk).expect("Invalid skc");
             let ctx = zmq::Context::new();
             let s: zmq::Socket = ctx.socket(zmq::SocketType::ROUTER).unwrap();
             let gt = GenTransaction {
@@ -1108,7 +1106,7 @@ mod tests {
                 },
                 txn_id: None,
                 txn_type: "0".to_string(),
-                dest: (&vk.0 as &[u8]).to_base58(),
+                dest: vk.to_base58(),
             };
             let addr = format!("tcp://{}:{}", gt.data.client_ip, gt.data.client_port);
             s.set_curve_publickey(pkc.as_slice()).expect("set public key");
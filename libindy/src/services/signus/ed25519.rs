@@ -1,7 +1,10 @@
 use super::CryptoType;
 use utils::crypto::ed25519::ED25519;
 use errors::common::CommonError;
+use errors::signus::SignusError;
 
+const PUBLIC_KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 24;
 
 pub struct ED25519Signus {}
 
@@ -12,35 +15,62 @@ impl ED25519Signus {
 }
 
 impl CryptoType for ED25519Signus {
-    fn encrypt(&self, private_key: &[u8], public_key: &[u8], doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, CommonError> {
-        ED25519::encrypt(private_key, public_key, doc, nonce)
+    fn encrypt(&self, private_key: &[u8], public_key: &[u8], doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, SignusError> {
+        Ok(ED25519::encrypt(private_key, public_key, doc, nonce)?)
     }
 
-    fn decrypt(&self, private_key: &[u8], public_key: &[u8], doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, CommonError> {
-        ED25519::decrypt(private_key, public_key, doc, nonce)
+    fn decrypt(&self, private_key: &[u8], public_key: &[u8], doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, SignusError> {
+        Ok(ED25519::decrypt(private_key, public_key, doc, nonce)?)
+    }
+
+    fn encrypt_sealed(&self, public_key: &[u8], doc: &[u8]) -> Result<Vec<u8>, SignusError> {
+        // Sealed message layout: ephemeral public key || nonce || box
+        let (ephemeral_vk, ephemeral_sk) = ED25519::create_key_pair_for_signature(None)?;
+        let ephemeral_public_key = ED25519::vk_to_curve25519(&ephemeral_vk)?;
+        let ephemeral_private_key = ED25519::sk_to_curve25519(&ephemeral_sk)?;
+
+        let nonce = ED25519::gen_nonce();
+
+        let encrypted_doc = ED25519::encrypt(&ephemeral_private_key, public_key, doc, &nonce)?;
+
+        let mut sealed_doc = ephemeral_public_key;
+        sealed_doc.extend_from_slice(&nonce);
+        sealed_doc.extend_from_slice(&encrypted_doc);
+        Ok(sealed_doc)
+    }
+
+    fn decrypt_sealed(&self, private_key: &[u8], doc: &[u8]) -> Result<Vec<u8>, SignusError> {
+        if doc.len() < PUBLIC_KEY_LEN + NONCE_LEN {
+            return Err(SignusError::CommonError(CommonError::InvalidStructure(format!("Invalid sealed message"))));
+        }
+
+        let (ephemeral_public_key, doc) = doc.split_at(PUBLIC_KEY_LEN);
+        let (nonce, encrypted_doc) = doc.split_at(NONCE_LEN);
+
+        Ok(ED25519::decrypt(private_key, ephemeral_public_key, encrypted_doc, nonce)?)
     }
 
     fn gen_nonce(&self) -> Vec<u8> {
         ED25519::gen_nonce()
     }
 
-    fn create_key_pair_for_signature(&self, seed: Option<&[u8]>) -> Result<(Vec<u8>, Vec<u8>), CommonError> {
-        ED25519::create_key_pair_for_signature(seed)
+    fn create_key_pair_for_signature(&self, seed: Option<&[u8]>) -> Result<(Vec<u8>, Vec<u8>), SignusError> {
+        Ok(ED25519::create_key_pair_for_signature(seed)?)
     }
 
-    fn sign(&self, private_key: &[u8], doc: &[u8]) -> Result<Vec<u8>, CommonError> {
-        ED25519::sign(private_key, doc)
+    fn sign(&self, private_key: &[u8], doc: &[u8]) -> Result<Vec<u8>, SignusError> {
+        Ok(ED25519::sign(private_key, doc)?)
     }
 
-    fn verify(&self, public_key: &[u8], doc: &[u8], signature: &[u8]) -> Result<bool, CommonError> {
-        ED25519::verify(public_key, doc, signature)
+    fn verify(&self, public_key: &[u8], doc: &[u8], signature: &[u8]) -> Result<bool, SignusError> {
+        Ok(ED25519::verify(public_key, doc, signature)?)
     }
 
-    fn verkey_to_public_key(&self, vk: &[u8]) -> Result<Vec<u8>, CommonError> {
-        ED25519::vk_to_curve25519(vk)
+    fn verkey_to_public_key(&self, vk: &[u8]) -> Result<Vec<u8>, SignusError> {
+        Ok(ED25519::vk_to_curve25519(vk)?)
     }
 
-    fn signkey_to_private_key(&self, sk: &[u8]) -> Result<Vec<u8>, CommonError> {
-        ED25519::sk_to_curve25519(sk)
+    fn signkey_to_private_key(&self, sk: &[u8]) -> Result<Vec<u8>, SignusError> {
+        Ok(ED25519::sk_to_curve25519(sk)?)
     }
 }
\ No newline at end of file
@@ -1,72 +1,233 @@
+extern crate rand;
+
 mod ed25519;
+mod plugged;
 pub mod types;
 
 use self::ed25519::ED25519Signus;
+use self::plugged::PluggedCryptoType;
 use self::types::{
+    Key,
     MyDidInfo,
+    MyKyesInfo,
     MyDid,
+    PackedMessage,
+    PackedRecipient,
     TheirDidInfo,
     TheirDid
 };
 use utils::crypto::base58::Base58;
+use utils::crypto::base64::Base64;
+use utils::crypto::hash::Hash;
+use utils::crypto::verkey_builder::build_full_verkey;
+use utils::crypto::xsalsa20::XSalsa20;
+use utils::did::DidUtils;
 
+use api::ErrorCode;
 use errors::common::CommonError;
 use errors::signus::SignusError;
 
+use self::rand::{OsRng, Rng};
+
 use std::collections::HashMap;
 use std::str;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 const DEFAULT_CRYPTO_TYPE: &'static str = "ed25519";
-
-trait CryptoType {
-    fn encrypt(&self, private_key: &[u8], public_key: &[u8], doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, CommonError>;
-    fn decrypt(&self, private_key: &[u8], public_key: &[u8], doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, CommonError>;
+const MASTER_SEED_LEN: usize = 32;
+const DERIVATION_TAG: &'static [u8] = b"indy-did-derivation";
+const PACKED_MESSAGE_VERSION: &'static str = "1.0";
+const PACKED_MESSAGE_AUTHCRYPT: &'static str = "Authcrypt";
+const PACKED_MESSAGE_ANONCRYPT: &'static str = "Anoncrypt";
+const PACKED_MESSAGE_KEY_LEN: usize = 32;
+const PACKED_MESSAGE_NONCE_LEN: usize = 24;
+
+trait CryptoType: Send + Sync {
+    fn encrypt(&self, private_key: &[u8], public_key: &[u8], doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, SignusError>;
+    fn decrypt(&self, private_key: &[u8], public_key: &[u8], doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, SignusError>;
+    fn encrypt_sealed(&self, public_key: &[u8], doc: &[u8]) -> Result<Vec<u8>, SignusError>;
+    fn decrypt_sealed(&self, private_key: &[u8], doc: &[u8]) -> Result<Vec<u8>, SignusError>;
     fn gen_nonce(&self) -> Vec<u8>;
-    fn create_key_pair_for_signature(&self, seed: Option<&[u8]>) -> Result<(Vec<u8>, Vec<u8>), CommonError>;
-    fn sign(&self, private_key: &[u8], doc: &[u8]) -> Result<Vec<u8>, CommonError>;
-    fn verify(&self, public_key: &[u8], doc: &[u8], signature: &[u8]) -> Result<bool, CommonError>;
-    fn verkey_to_public_key(&self, vk: &[u8]) -> Result<Vec<u8>, CommonError>;
-    fn signkey_to_private_key(&self, sk: &[u8]) -> Result<Vec<u8>, CommonError>;
+    fn create_key_pair_for_signature(&self, seed: Option<&[u8]>) -> Result<(Vec<u8>, Vec<u8>), SignusError>;
+    fn sign(&self, private_key: &[u8], doc: &[u8]) -> Result<Vec<u8>, SignusError>;
+    fn verify(&self, public_key: &[u8], doc: &[u8], signature: &[u8]) -> Result<bool, SignusError>;
+    fn verkey_to_public_key(&self, vk: &[u8]) -> Result<Vec<u8>, SignusError>;
+    fn signkey_to_private_key(&self, sk: &[u8]) -> Result<Vec<u8>, SignusError>;
 }
 
+/// Service is shared with worker threads, so crypto operations on already loaded keys
+/// can be computed out of CommandExecutor thread.
 pub struct SignusService {
-    crypto_types: HashMap<&'static str, Box<CryptoType>>
+    crypto_types: RwLock<HashMap<String, Arc<CryptoType>>>,
+    default_method_name: RwLock<Option<String>>
 }
 
 impl SignusService {
     pub fn new() -> SignusService {
-        let mut crypto_types: HashMap<&str, Box<CryptoType>> = HashMap::new();
-        crypto_types.insert(DEFAULT_CRYPTO_TYPE, Box::new(ED25519Signus::new()));
+        let mut crypto_types: HashMap<String, Arc<CryptoType>> = HashMap::new();
+        crypto_types.insert(DEFAULT_CRYPTO_TYPE.to_string(), Arc::new(ED25519Signus::new()));
 
         SignusService {
-            crypto_types: crypto_types
+            crypto_types: RwLock::new(crypto_types),
+            default_method_name: RwLock::new(None)
         }
     }
 
+    pub fn set_default_method_name(&self, method_name: Option<&str>) -> Result<(), SignusError> {
+        if let Some(method_name) = method_name {
+            DidUtils::validate_method(method_name)?;
+        }
+
+        *self.default_method_name.write().unwrap_or_else(|err| err.into_inner()) = method_name.map(String::from);
+        Ok(())
+    }
+
+    pub fn default_method_name(&self) -> Option<String> {
+        self.default_method_name.read().unwrap_or_else(|err| err.into_inner()).clone()
+    }
+
+    pub fn register_type(&self,
+                         xtype: &str,
+                         create_key: extern fn(seed_raw: *const u8, seed_len: u32,
+                                               verkey_raw: *mut *const u8, verkey_len: *mut u32,
+                                               signkey_raw: *mut *const u8, signkey_len: *mut u32) -> ErrorCode,
+                         sign: extern fn(signkey_raw: *const u8, signkey_len: u32,
+                                         message_raw: *const u8, message_len: u32,
+                                         signature_raw: *mut *const u8, signature_len: *mut u32) -> ErrorCode,
+                         verify: extern fn(verkey_raw: *const u8, verkey_len: u32,
+                                           message_raw: *const u8, message_len: u32,
+                                           signature_raw: *const u8, signature_len: u32,
+                                           valid: *mut u32) -> ErrorCode,
+                         encrypt: extern fn(signkey_raw: *const u8, signkey_len: u32,
+                                            verkey_raw: *const u8, verkey_len: u32,
+                                            nonce_raw: *const u8, nonce_len: u32,
+                                            message_raw: *const u8, message_len: u32,
+                                            encrypted_msg_raw: *mut *const u8, encrypted_msg_len: *mut u32) -> ErrorCode,
+                         decrypt: extern fn(signkey_raw: *const u8, signkey_len: u32,
+                                            verkey_raw: *const u8, verkey_len: u32,
+                                            nonce_raw: *const u8, nonce_len: u32,
+                                            encrypted_msg_raw: *const u8, encrypted_msg_len: u32,
+                                            decrypted_msg_raw: *mut *const u8, decrypted_msg_len: *mut u32) -> ErrorCode,
+                         free: extern fn(data_raw: *const u8, data_len: u32) -> ErrorCode) -> Result<(), SignusError> {
+        let mut crypto_types = self._crypto_types_mut();
+
+        if crypto_types.contains_key(xtype) {
+            return Err(SignusError::CryptoTypeAlreadyRegistered(xtype.to_string()))
+        }
+
+        crypto_types.insert(xtype.to_string(),
+                            Arc::new(
+                                PluggedCryptoType::new(create_key, sign, verify, encrypt, decrypt, free)));
+        Ok(())
+    }
+
     pub fn create_my_did(&self, my_did_info: &MyDidInfo) -> Result<MyDid, SignusError> {
+        if my_did_info.derivation_path.is_some() {
+            return Err(SignusError::CommonError(
+                CommonError::InvalidStructure(format!("Master seed is required to create DID by derivation path"))));
+        }
+
+        let seed = my_did_info.seed.as_ref().map(String::as_bytes);
+        self._create_my_did(my_did_info, seed)
+    }
+
+    pub fn derive_my_did(&self, my_did_info: &MyDidInfo, master_seed: &[u8]) -> Result<MyDid, SignusError> {
+        if my_did_info.seed.is_some() {
+            return Err(SignusError::CommonError(
+                CommonError::InvalidStructure(format!("Seed and derivation path can't be used together"))));
+        }
+
+        let derivation_path = my_did_info.derivation_path.as_ref()
+            .ok_or(SignusError::CommonError(
+                CommonError::InvalidStructure(format!("MyDidInfo doesn't contain derivation path"))))?;
+
+        let seed = SignusService::_derive_seed(master_seed, derivation_path)?;
+        self._create_my_did(my_did_info, Some(&seed))
+    }
+
+    pub fn create_master_seed(&self) -> Vec<u8> {
+        let mut master_seed = vec![0u8; MASTER_SEED_LEN];
+        OsRng::new().unwrap().fill_bytes(&mut master_seed);
+        master_seed
+    }
+
+    pub fn validate_master_seed(&self, master_seed: &[u8]) -> Result<(), SignusError> {
+        if master_seed.len() != MASTER_SEED_LEN {
+            return Err(SignusError::CommonError(
+                CommonError::InvalidStructure(format!("Invalid master seed length: {}", master_seed.len()))));
+        }
+        Ok(())
+    }
+
+    // Each path segment is a u32 index. Child seed is HMAC-SHA256 of the index keyed by the parent seed,
+    // so any DID of the tree can be restored from the master seed and the path only.
+    fn _derive_seed(master_seed: &[u8], derivation_path: &str) -> Result<Vec<u8>, SignusError> {
+        let mut seed = master_seed.to_vec();
+
+        for segment in derivation_path.split('/') {
+            let index = segment.parse::<u32>()
+                .map_err(|err| CommonError::InvalidStructure(
+                    format!("Invalid derivation path {}: {}", derivation_path, err)))?;
+
+            let mut data = DERIVATION_TAG.to_vec();
+            data.extend_from_slice(&[(index >> 24) as u8, (index >> 16) as u8, (index >> 8) as u8, index as u8]);
+
+            seed = Hash::hmac(&seed, &data)?;
+        }
+
+        Ok(seed)
+    }
+
+    fn _create_my_did(&self, my_did_info: &MyDidInfo, seed: Option<&[u8]>) -> Result<MyDid, SignusError> {
         let xtype = my_did_info.crypto_type.clone().unwrap_or(DEFAULT_CRYPTO_TYPE.to_string());
 
-        if !self.crypto_types.contains_key(&xtype.as_str()) {
+        let crypto_types = self._crypto_types();
+
+        if !crypto_types.contains_key(xtype.as_str()) {
             return Err(
                 SignusError::UnknownCryptoError(
                     format!("MyDidInfo info contains unknown crypto: {}", xtype)));
         }
 
-        let signus = self.crypto_types.get(&xtype.as_str()).unwrap();
+        let signus = crypto_types.get(xtype.as_str()).unwrap();
 
-        let seed = my_did_info.seed.as_ref().map(String::as_bytes);
         let (ver_key, sign_key) = signus.create_key_pair_for_signature(seed)?;
 
         let public_key = signus.verkey_to_public_key(&ver_key)?;
         let secret_key = signus.signkey_to_private_key(&sign_key)?;
 
+        // Passed DID keeps its own form, new DIDs are qualified with explicit or default method if any
+        let method_name = match (&my_did_info.method_name, &my_did_info.did) {
+            (&Some(ref method_name), _) => Some(method_name.clone()),
+            (&None, &Some(ref did)) => DidUtils::method(did).map(String::from),
+            (&None, &None) => self.default_method_name.read().unwrap_or_else(|err| err.into_inner()).clone()
+        };
+
+        if let Some(ref method_name) = method_name {
+            DidUtils::validate_method(method_name)?;
+        }
+
+        if let (&Some(ref method_name), &Some(ref did)) = (&my_did_info.method_name, &my_did_info.did) {
+            if DidUtils::method(did).map(|did_method_name| did_method_name != method_name).unwrap_or(false) {
+                return Err(SignusError::CommonError(
+                    CommonError::InvalidStructure(format!("DID {} doesn't match method name: {}", did, method_name))));
+            }
+        }
+
         let did = match my_did_info.did {
-            Some(ref did) => Base58::decode(did)?,
+            Some(ref did) => Base58::decode(DidUtils::unqualify(did))?,
             _ if my_did_info.cid == Some(true) => ver_key.clone(),
+            _ if ver_key.len() < 16 => return Err(SignusError::CommonError(
+                CommonError::InvalidStructure(format!("Verkey is too short to derive DID: {}", ver_key.len())))),
             _ => ver_key[0..16].to_vec()
         };
 
-        let my_did = MyDid::new(Base58::encode(&did),
+        let did = match method_name {
+            Some(ref method_name) => DidUtils::qualify(&Base58::encode(&did), method_name),
+            None => Base58::encode(&did)
+        };
+
+        let my_did = MyDid::new(did,
                                 xtype.clone(),
                                 Base58::encode(&public_key),
                                 Base58::encode(&secret_key),
@@ -79,16 +240,18 @@ impl SignusService {
     pub fn create_their_did(&self, their_did_info: &TheirDidInfo) -> Result<TheirDid, SignusError> {
         let xtype = their_did_info.crypto_type.clone().unwrap_or(DEFAULT_CRYPTO_TYPE.to_string());
 
-        if !self.crypto_types.contains_key(&xtype.as_str()) {
+        let crypto_types = self._crypto_types();
+
+        if !crypto_types.contains_key(xtype.as_str()) {
             return Err(
                 SignusError::UnknownCryptoError(
                     format!("TheirDidInfo info contains unknown crypto: {}", xtype)));
         }
 
-        let signus = self.crypto_types.get(&xtype.as_str()).unwrap();
+        let signus = crypto_types.get(xtype.as_str()).unwrap();
 
         // Check did is correct Base58
-        Base58::decode(&their_did_info.did)?;
+        Base58::decode(DidUtils::unqualify(&their_did_info.did))?;
 
         let (verkey, pk) = match their_did_info.verkey {
             Some(ref verkey) => (
@@ -106,13 +269,15 @@ impl SignusService {
     }
 
     pub fn sign(&self, my_did: &MyDid, doc: &[u8]) -> Result<Vec<u8>, SignusError> {
-        if !self.crypto_types.contains_key(&my_did.crypto_type.as_str()) {
+        let crypto_types = self._crypto_types();
+
+        if !crypto_types.contains_key(my_did.crypto_type.as_str()) {
             return Err(
                 SignusError::UnknownCryptoError(
                     format!("Trying to sign message with unknown crypto: {}", my_did.crypto_type)));
         }
 
-        let signus = self.crypto_types.get(&my_did.crypto_type.as_str()).unwrap();
+        let signus = crypto_types.get(my_did.crypto_type.as_str()).unwrap();
 
         let sign_key = Base58::decode(&my_did.signkey)?;
 
@@ -122,11 +287,13 @@ impl SignusService {
     }
 
     pub fn verify(&self, their_did: &TheirDid, msg: &[u8], signature: &[u8]) -> Result<bool, SignusError> {
-        if !self.crypto_types.contains_key(their_did.crypto_type.as_str()) {
+        let crypto_types = self._crypto_types();
+
+        if !crypto_types.contains_key(their_did.crypto_type.as_str()) {
             return Err(SignusError::UnknownCryptoError(format!("Trying to verify message with unknown crypto: {}", their_did.crypto_type)));
         }
 
-        let signus = self.crypto_types.get(their_did.crypto_type.as_str()).unwrap();
+        let signus = crypto_types.get(their_did.crypto_type.as_str()).unwrap();
 
         let verkey = match their_did.verkey {
             Some(ref verkey) => Base58::decode(&verkey)?,
@@ -137,11 +304,13 @@ impl SignusService {
     }
 
     pub fn encrypt(&self, my_did: &MyDid, their_did: &TheirDid, doc: &[u8]) -> Result<(Vec<u8>, Vec<u8>), SignusError> {
-        if !self.crypto_types.contains_key(&my_did.crypto_type.as_str()) {
+        let crypto_types = self._crypto_types();
+
+        if !crypto_types.contains_key(my_did.crypto_type.as_str()) {
             return Err(SignusError::UnknownCryptoError(format!("Trying to encrypt message with unknown crypto: {}", my_did.crypto_type)));
         }
 
-        let signus = self.crypto_types.get(&my_did.crypto_type.as_str()).unwrap();
+        let signus = crypto_types.get(my_did.crypto_type.as_str()).unwrap();
 
         if their_did.pk.is_none() {
             return Err(SignusError::CommonError(CommonError::InvalidStructure(format!("TheirDid doesn't contain pk: {}", their_did.did))));
@@ -159,11 +328,13 @@ impl SignusService {
     }
 
     pub fn decrypt(&self, my_did: &MyDid, their_did: &TheirDid, doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, SignusError> {
-        if !self.crypto_types.contains_key(&my_did.crypto_type.as_str()) {
+        let crypto_types = self._crypto_types();
+
+        if !crypto_types.contains_key(my_did.crypto_type.as_str()) {
             return Err(SignusError::UnknownCryptoError(format!("MyDid crypto is unknown: {}, {}", my_did.did, my_did.crypto_type)));
         }
 
-        let signus = self.crypto_types.get(&my_did.crypto_type.as_str()).unwrap();
+        let signus = crypto_types.get(my_did.crypto_type.as_str()).unwrap();
 
         if their_did.pk.is_none() {
             return Err(SignusError::CommonError(
@@ -179,17 +350,377 @@ impl SignusService {
 
         Ok(decrypted_doc)
     }
+
+    pub fn create_key(&self, key_info: &MyKyesInfo) -> Result<Key, SignusError> {
+        let xtype = key_info.crypto_type.clone().unwrap_or(DEFAULT_CRYPTO_TYPE.to_string());
+
+        let crypto_types = self._crypto_types();
+
+        if !crypto_types.contains_key(xtype.as_str()) {
+            return Err(
+                SignusError::UnknownCryptoError(
+                    format!("KeyInfo contains unknown crypto: {}", xtype)));
+        }
+
+        let signus = crypto_types.get(xtype.as_str()).unwrap();
+
+        let seed = key_info.seed.as_ref().map(String::as_bytes);
+        let (ver_key, sign_key) = signus.create_key_pair_for_signature(seed)?;
+
+        Ok(Key::new(SignusService::_build_verkey(&Base58::encode(&ver_key), &xtype), Base58::encode(&sign_key), xtype))
+    }
+
+    /// Key of my DID in form used by crypto API.
+    pub fn key_from_my_did(&self, my_did: &MyDid) -> Key {
+        Key::new(SignusService::_build_verkey(&my_did.verkey, &my_did.crypto_type), my_did.signkey.clone(), my_did.crypto_type.clone())
+    }
+
+    pub fn crypto_sign(&self, my_key: &Key, doc: &[u8]) -> Result<Vec<u8>, SignusError> {
+        let signus = self._get_crypto_type(&my_key.crypto_type)?;
+
+        let sign_key = Base58::decode(&my_key.signkey)?;
+
+        Ok(signus.sign(&sign_key, doc)?)
+    }
+
+    pub fn crypto_verify(&self, their_vk: &str, doc: &[u8], signature: &[u8]) -> Result<bool, SignusError> {
+        let (verkey, xtype) = SignusService::_split_verkey(their_vk);
+        let signus = self._get_crypto_type(xtype)?;
+
+        let verkey = Base58::decode(verkey)?;
+
+        Ok(signus.verify(&verkey, doc, signature)?)
+    }
+
+    pub fn verify_with_verkey(&self, did: Option<&str>, their_vk: &str, doc: &[u8], signature: &[u8]) -> Result<bool, SignusError> {
+        let (verkey, xtype) = SignusService::_split_verkey(their_vk);
+        let signus = self._get_crypto_type(xtype)?;
+
+        // Abbreviated verkey contains only the part of verkey that isn't present in DID
+        let verkey = if verkey.starts_with("~") {
+            let did = did.ok_or(
+                CommonError::InvalidStructure(format!("DID is required to resolve abbreviated verkey: {}", verkey)))?;
+
+            build_full_verkey(&did.to_string(), &Some(verkey.to_string()))
+                .map_err(|err| CommonError::InvalidStructure(format!("Invalid abbreviated verkey: {:?}", err)))?
+        } else {
+            Base58::decode(verkey)?
+        };
+
+        Ok(signus.verify(&verkey, doc, signature)?)
+    }
+
+    pub fn crypto_box(&self, my_key: &Key, their_vk: &str, doc: &[u8]) -> Result<(Vec<u8>, Vec<u8>), SignusError> {
+        let signus = self._get_crypto_type(&my_key.crypto_type)?;
+
+        let (secret_key, public_key) = self._get_box_keys(my_key, their_vk)?;
+
+        let nonce = signus.gen_nonce();
+
+        let encrypted_doc = signus.encrypt(&secret_key, &public_key, doc, &nonce)?;
+        Ok((encrypted_doc, nonce))
+    }
+
+    pub fn crypto_box_open(&self, my_key: &Key, their_vk: &str, doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, SignusError> {
+        let signus = self._get_crypto_type(&my_key.crypto_type)?;
+
+        let (secret_key, public_key) = self._get_box_keys(my_key, their_vk)?;
+
+        Ok(signus.decrypt(&secret_key, &public_key, doc, nonce)?)
+    }
+
+    pub fn encrypt_sealed(&self, their_vk: &str, doc: &[u8]) -> Result<Vec<u8>, SignusError> {
+        let (their_vk, their_xtype) = SignusService::_split_verkey(their_vk);
+        let signus = self._get_crypto_type(their_xtype)?;
+
+        let public_key = signus.verkey_to_public_key(&Base58::decode(their_vk)?)?;
+
+        Ok(signus.encrypt_sealed(&public_key, doc)?)
+    }
+
+    pub fn decrypt_sealed(&self, my_key: &Key, doc: &[u8]) -> Result<Vec<u8>, SignusError> {
+        let signus = self._get_crypto_type(&my_key.crypto_type)?;
+
+        let secret_key = signus.signkey_to_private_key(&Base58::decode(&my_key.signkey)?)?;
+
+        Ok(signus.decrypt_sealed(&secret_key, doc)?)
+    }
+
+    /// Encrypts message once with random content key and wraps content key for each recipient.
+    /// With sender key content key is authenticated by crypto_box and sender verkey
+    /// is sealed for each recipient, without sender key sealed box is used.
+    /// Binary fields are base64 encoded as ciphertext can be as large as the whole message.
+    pub fn pack_message(&self, msg: &[u8], recipient_vks: &[String], sender_key: Option<&Key>) -> Result<PackedMessage, SignusError> {
+        if recipient_vks.is_empty() {
+            return Err(SignusError::CommonError(
+                CommonError::InvalidStructure("Packed message requires at least one recipient".to_string())));
+        }
+
+        let xsalsa20 = XSalsa20::new();
+        let content_key = xsalsa20.create_key();
+        let nonce = xsalsa20.gen_nonce();
+        let ciphertext = xsalsa20.encrypt(&content_key, &nonce, msg);
+
+        let mut recipients = Vec::new();
+        for recipient_vk in recipient_vks {
+            let recipient = match sender_key {
+                Some(sender_key) => {
+                    let (encrypted_key, key_nonce) = self.crypto_box(sender_key, recipient_vk, &content_key)?;
+                    let sender = self.encrypt_sealed(recipient_vk, sender_key.verkey.as_bytes())?;
+                    PackedRecipient {
+                        verkey: recipient_vk.clone(),
+                        encrypted_key: Base64::encode(&encrypted_key),
+                        sender: Some(Base64::encode(&sender)),
+                        nonce: Some(Base64::encode(&key_nonce)),
+                    }
+                }
+                None => PackedRecipient {
+                    verkey: recipient_vk.clone(),
+                    encrypted_key: Base64::encode(&self.encrypt_sealed(recipient_vk, &content_key)?),
+                    sender: None,
+                    nonce: None,
+                }
+            };
+            recipients.push(recipient);
+        }
+
+        Ok(PackedMessage {
+            version: PACKED_MESSAGE_VERSION.to_string(),
+            alg: if sender_key.is_some() { PACKED_MESSAGE_AUTHCRYPT } else { PACKED_MESSAGE_ANONCRYPT }.to_string(),
+            recipients: recipients,
+            nonce: Base64::encode(&nonce),
+            ciphertext: Base64::encode(&ciphertext),
+        })
+    }
+
+    /// Returns decrypted message and sender verkey for authenticated message.
+    pub fn unpack_message(&self, packed_msg: &PackedMessage, my_key: &Key) -> Result<(Vec<u8>, Option<String>), SignusError> {
+        if packed_msg.version != PACKED_MESSAGE_VERSION {
+            return Err(SignusError::CommonError(
+                CommonError::InvalidStructure(format!("Unsupported packed message version: {}", packed_msg.version))));
+        }
+
+        let recipient = packed_msg.recipients.iter()
+            .find(|recipient| recipient.verkey == my_key.verkey)
+            .ok_or(SignusError::CommonError(
+                CommonError::InvalidStructure(format!("Packed message isn't addressed to {}", my_key.verkey))))?;
+
+        let (content_key, sender_vk) = match packed_msg.alg.as_str() {
+            PACKED_MESSAGE_AUTHCRYPT => {
+                let (sender, nonce) = match (recipient.sender.as_ref(), recipient.nonce.as_ref()) {
+                    (Some(sender), Some(nonce)) => (sender, nonce),
+                    _ => return Err(SignusError::CommonError(
+                        CommonError::InvalidStructure("Authcrypt recipient requires sender and nonce".to_string())))
+                };
+                let sender_vk = self.decrypt_sealed(my_key, &Base64::decode(sender)?)?;
+                let sender_vk = String::from_utf8(sender_vk)
+                    .map_err(|_| CommonError::InvalidStructure("Invalid sender verkey in packed message".to_string()))?;
+                let nonce = SignusService::_decode_fixed_len(nonce, PACKED_MESSAGE_NONCE_LEN, "recipient nonce")?;
+                let content_key = self.crypto_box_open(my_key, &sender_vk,
+                                                       &Base64::decode(&recipient.encrypted_key)?,
+                                                       &nonce)?;
+                (content_key, Some(sender_vk))
+            }
+            PACKED_MESSAGE_ANONCRYPT => {
+                (self.decrypt_sealed(my_key, &Base64::decode(&recipient.encrypted_key)?)?, None)
+            }
+            alg => return Err(SignusError::CommonError(
+                CommonError::InvalidStructure(format!("Unsupported packed message alg: {}", alg))))
+        };
+
+        if content_key.len() != PACKED_MESSAGE_KEY_LEN {
+            return Err(SignusError::CommonError(
+                CommonError::InvalidStructure(format!("Invalid content key length in packed message: {}", content_key.len()))));
+        }
+
+        let nonce = SignusService::_decode_fixed_len(&packed_msg.nonce, PACKED_MESSAGE_NONCE_LEN, "nonce")?;
+
+        let msg = XSalsa20::new().decrypt(&content_key,
+                                          &nonce,
+                                          &Base64::decode(&packed_msg.ciphertext)?)?;
+        Ok((msg, sender_vk))
+    }
+
+    fn _decode_fixed_len(value: &str, len: usize, name: &str) -> Result<Vec<u8>, SignusError> {
+        let value = Base64::decode(value)?;
+        if value.len() != len {
+            return Err(SignusError::CommonError(
+                CommonError::InvalidStructure(format!("Invalid {} length in packed message: {}", name, value.len()))));
+        }
+        Ok(value)
+    }
+
+    fn _get_box_keys(&self, my_key: &Key, their_vk: &str) -> Result<(Vec<u8>, Vec<u8>), SignusError> {
+        let (their_vk, their_xtype) = SignusService::_split_verkey(their_vk);
+
+        if their_xtype != my_key.crypto_type {
+            return Err(SignusError::UnknownCryptoError(
+                format!("Keys have different crypto types: {} and {}", my_key.crypto_type, their_xtype)));
+        }
+
+        let signus = self._get_crypto_type(&my_key.crypto_type)?;
+
+        let secret_key = signus.signkey_to_private_key(&Base58::decode(&my_key.signkey)?)?;
+        let public_key = signus.verkey_to_public_key(&Base58::decode(their_vk)?)?;
+
+        Ok((secret_key, public_key))
+    }
+
+    fn _get_crypto_type(&self, xtype: &str) -> Result<Arc<CryptoType>, SignusError> {
+        self._crypto_types()
+            .get(xtype)
+            .cloned()
+            .ok_or(SignusError::UnknownCryptoError(format!("Trying to use key with unknown crypto: {}", xtype)))
+    }
+
+    // Crypto types are only ever inserted, so map behind a poisoned lock is still consistent
+    fn _crypto_types(&self) -> RwLockReadGuard<HashMap<String, Arc<CryptoType>>> {
+        self.crypto_types.read().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn _crypto_types_mut(&self) -> RwLockWriteGuard<HashMap<String, Arc<CryptoType>>> {
+        self.crypto_types.write().unwrap_or_else(|err| err.into_inner())
+    }
+
+    // Verkey of not default crypto type carries type as suffix: <verkey>:<crypto_type>
+    fn _build_verkey(verkey: &str, xtype: &str) -> String {
+        if xtype == DEFAULT_CRYPTO_TYPE {
+            verkey.to_string()
+        } else {
+            format!("{}:{}", verkey, xtype)
+        }
+    }
+
+    fn _split_verkey(verkey: &str) -> (&str, &str) {
+        match verkey.find(':') {
+            Some(pos) => (&verkey[..pos], &verkey[pos + 1..]),
+            None => (verkey, DEFAULT_CRYPTO_TYPE)
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use services::signus::types::MyDidInfo;
+    use utils::dummy_crypto::DummyCrypto;
+
+    fn _register_dummy_type(service: &SignusService) -> Result<(), SignusError> {
+        service.register_type("dummy",
+                              DummyCrypto::create_key,
+                              DummyCrypto::sign,
+                              DummyCrypto::verify,
+                              DummyCrypto::encrypt,
+                              DummyCrypto::decrypt,
+                              DummyCrypto::free)
+    }
+
+    #[test]
+    fn register_type_works() {
+        let service = SignusService::new();
+        _register_dummy_type(&service).unwrap();
+    }
+
+    #[test]
+    fn register_type_works_for_twice() {
+        let service = SignusService::new();
+        _register_dummy_type(&service).unwrap();
+
+        let res = _register_dummy_type(&service);
+        assert_match!(Err(SignusError::CryptoTypeAlreadyRegistered(_)), res);
+    }
+
+    #[test]
+    fn register_type_works_for_default_type() {
+        let service = SignusService::new();
+
+        let res = service.register_type(DEFAULT_CRYPTO_TYPE,
+                                        DummyCrypto::create_key,
+                                        DummyCrypto::sign,
+                                        DummyCrypto::verify,
+                                        DummyCrypto::encrypt,
+                                        DummyCrypto::decrypt,
+                                        DummyCrypto::free);
+        assert_match!(Err(SignusError::CryptoTypeAlreadyRegistered(_)), res);
+    }
+
+    #[test]
+    fn create_my_did_works_for_plugged_type() {
+        let service = SignusService::new();
+        _register_dummy_type(&service).unwrap();
+
+        let did_info = MyDidInfo::new(None, Some("00000000000000000000000000000My1".to_string()), Some("dummy".to_string()), None, None, None);
+        let my_did = service.create_my_did(&did_info).unwrap();
+
+        assert_eq!("dummy", my_did.crypto_type);
+        assert_eq!(my_did.verkey, my_did.signkey);
+    }
+
+    #[test]
+    fn crypto_sign_verify_works_for_plugged_type() {
+        let service = SignusService::new();
+        _register_dummy_type(&service).unwrap();
+
+        let msg = "some message".as_bytes();
+
+        let key = service.create_key(&MyKyesInfo::new(None, Some("dummy".to_string()))).unwrap();
+        assert!(key.verkey.ends_with(":dummy"));
+
+        let signature = service.crypto_sign(&key, msg).unwrap();
+
+        assert!(service.crypto_verify(&key.verkey, msg, &signature).unwrap());
+        assert!(!service.crypto_verify(&key.verkey, "other message".as_bytes(), &signature).unwrap());
+    }
+
+    extern "C" fn _verify_as_c_unsigned_int(_: *const u8, _: u32,
+                                            _: *const u8, _: u32,
+                                            _: *const u8, _: u32,
+                                            valid: *mut u32) -> ErrorCode {
+        // C plugin writes the whole indy_bool_t; low byte is zero to catch 1-byte reads
+        unsafe { *valid = 0x0100 };
+        ErrorCode::Success
+    }
+
+    #[test]
+    fn crypto_verify_works_for_plugged_type_writing_c_unsigned_int() {
+        let service = SignusService::new();
+        service.register_type("dummy_c_bool",
+                              DummyCrypto::create_key,
+                              DummyCrypto::sign,
+                              _verify_as_c_unsigned_int,
+                              DummyCrypto::encrypt,
+                              DummyCrypto::decrypt,
+                              DummyCrypto::free).unwrap();
+
+        let msg = "some message".as_bytes();
+
+        let key = service.create_key(&MyKyesInfo::new(None, Some("dummy_c_bool".to_string()))).unwrap();
+        let signature = service.crypto_sign(&key, msg).unwrap();
+
+        assert!(service.crypto_verify(&key.verkey, msg, &signature).unwrap());
+    }
+
+    #[test]
+    fn crypto_box_open_works_for_plugged_type() {
+        let service = SignusService::new();
+        _register_dummy_type(&service).unwrap();
+
+        let msg = "some message".as_bytes();
+
+        let my_key = service.create_key(&MyKyesInfo::new(None, Some("dummy".to_string()))).unwrap();
+        let their_key = service.create_key(&MyKyesInfo::new(None, Some("dummy".to_string()))).unwrap();
+
+        let (encrypted_message, nonce) = service.crypto_box(&my_key, &their_key.verkey, msg).unwrap();
+
+        let decrypted_message = service.crypto_box_open(&their_key, &my_key.verkey, &encrypted_message, &nonce).unwrap();
+
+        assert_eq!(msg.to_vec(), decrypted_message);
+    }
 
     #[test]
     fn create_my_did_with_works_for_empty_info() {
         let service = SignusService::new();
-        let did_info = MyDidInfo::new(None, None, None, None);
+        let did_info = MyDidInfo::new(None, None, None, None, None, None);
         service.create_my_did(&did_info).unwrap();
     }
 
@@ -198,7 +729,7 @@ mod tests {
         let service = SignusService::new();
 
         let did = Some("Dbf2fjCbsiq2kfns".to_string());
-        let did_info = MyDidInfo::new(did.clone(), None, None, None);
+        let did_info = MyDidInfo::new(did.clone(), None, None, None, None, None);
 
         let my_did = service.create_my_did(&did_info).unwrap();
 
@@ -212,7 +743,7 @@ mod tests {
         let did = Some("Dbf2fjCbsiq2kfns".to_string());
         let crypto_type = Some("type".to_string());
 
-        let did_info = MyDidInfo::new(did.clone(), None, crypto_type, None);
+        let did_info = MyDidInfo::new(did.clone(), None, crypto_type, None, None, None);
 
         assert!(service.create_my_did(&did_info).is_err());
     }
@@ -224,8 +755,8 @@ mod tests {
         let did = Some("Dbf2fjCbsiq2kfns".to_string());
         let seed = Some("DJASbewkdUY3265HJFDSbds278sdDSnA".to_string());
 
-        let did_info_with_seed = MyDidInfo::new(did.clone(), seed, None, None);
-        let did_info_without_seed = MyDidInfo::new(did.clone(), None, None, None);
+        let did_info_with_seed = MyDidInfo::new(did.clone(), seed, None, None, None, None);
+        let did_info_without_seed = MyDidInfo::new(did.clone(), None, None, None, None, None);
 
         let res_with_seed = service.create_my_did(&did_info_with_seed).unwrap();
         let res_without_seed = service.create_my_did(&did_info_without_seed).unwrap();
@@ -233,11 +764,137 @@ mod tests {
         assert_ne!(res_with_seed.verkey, res_without_seed.verkey)
     }
 
+    #[test]
+    fn create_my_did_works_for_method_name() {
+        let service = SignusService::new();
+
+        let did_info = MyDidInfo::new(None, None, None, None, None, Some("sov".to_string()));
+
+        let my_did = service.create_my_did(&did_info).unwrap();
+
+        assert!(my_did.did.starts_with("did:sov:"));
+    }
+
+    #[test]
+    fn create_my_did_works_for_default_method_name() {
+        let service = SignusService::new();
+
+        service.set_default_method_name(Some("sov")).unwrap();
+
+        let did_info = MyDidInfo::new(None, None, None, None, None, None);
+        let my_did = service.create_my_did(&did_info).unwrap();
+        assert!(my_did.did.starts_with("did:sov:"));
+
+        service.set_default_method_name(None).unwrap();
+
+        let my_did = service.create_my_did(&did_info).unwrap();
+        assert!(!my_did.did.starts_with("did:"));
+    }
+
+    #[test]
+    fn create_my_did_works_for_passed_qualified_did() {
+        let service = SignusService::new();
+
+        let did = Some("did:sov:Dbf2fjCbsiq2kfns".to_string());
+        let did_info = MyDidInfo::new(did.clone(), None, None, None, None, None);
+
+        let my_did = service.create_my_did(&did_info).unwrap();
+
+        assert_eq!(did.unwrap(), my_did.did);
+    }
+
+    #[test]
+    fn create_my_did_not_works_for_method_name_mismatch() {
+        let service = SignusService::new();
+
+        let did_info = MyDidInfo::new(Some("did:sov:Dbf2fjCbsiq2kfns".to_string()), None, None, None, None, Some("peer".to_string()));
+
+        assert_match!(Err(SignusError::CommonError(CommonError::InvalidStructure(_))), service.create_my_did(&did_info));
+    }
+
+    #[test]
+    fn create_my_did_not_works_for_invalid_method_name() {
+        let service = SignusService::new();
+
+        let did_info = MyDidInfo::new(None, None, None, None, None, Some("Invalid:Method".to_string()));
+
+        assert!(service.create_my_did(&did_info).is_err());
+    }
+
+    #[test]
+    fn set_default_method_name_not_works_for_invalid_method_name() {
+        let service = SignusService::new();
+
+        assert!(service.set_default_method_name(Some("")).is_err());
+    }
+
+    #[test]
+    fn derive_my_did_works() {
+        let service = SignusService::new();
+
+        let master_seed = service.create_master_seed();
+
+        let did_info = MyDidInfo::new(None, None, None, None, Some("0/1".to_string()), None);
+
+        let my_did = service.derive_my_did(&did_info, &master_seed).unwrap();
+        let same_my_did = service.derive_my_did(&did_info, &master_seed).unwrap();
+
+        assert_eq!(my_did.did, same_my_did.did);
+        assert_eq!(my_did.verkey, same_my_did.verkey);
+        assert_eq!(my_did.signkey, same_my_did.signkey);
+    }
+
+    #[test]
+    fn derive_my_did_works_for_different_paths() {
+        let service = SignusService::new();
+
+        let master_seed = service.create_master_seed();
+
+        let did_info_1 = MyDidInfo::new(None, None, None, None, Some("0/1".to_string()), None);
+        let did_info_2 = MyDidInfo::new(None, None, None, None, Some("0/2".to_string()), None);
+
+        let my_did_1 = service.derive_my_did(&did_info_1, &master_seed).unwrap();
+        let my_did_2 = service.derive_my_did(&did_info_2, &master_seed).unwrap();
+
+        assert_ne!(my_did_1.verkey, my_did_2.verkey);
+    }
+
+    #[test]
+    fn derive_my_did_not_works_for_invalid_path() {
+        let service = SignusService::new();
+
+        let master_seed = service.create_master_seed();
+
+        let did_info = MyDidInfo::new(None, None, None, None, Some("0/a".to_string()), None);
+
+        assert!(service.derive_my_did(&did_info, &master_seed).is_err());
+    }
+
+    #[test]
+    fn derive_my_did_not_works_for_seed() {
+        let service = SignusService::new();
+
+        let master_seed = service.create_master_seed();
+
+        let did_info = MyDidInfo::new(None, Some("DJASbewkdUY3265HJFDSbds278sdDSnA".to_string()), None, None, Some("0".to_string()), None);
+
+        assert!(service.derive_my_did(&did_info, &master_seed).is_err());
+    }
+
+    #[test]
+    fn create_my_did_not_works_for_derivation_path() {
+        let service = SignusService::new();
+
+        let did_info = MyDidInfo::new(None, None, None, None, Some("0".to_string()), None);
+
+        assert!(service.create_my_did(&did_info).is_err());
+    }
+
     #[test]
     fn sign_works() {
         let service = SignusService::new();
 
-        let did_info = MyDidInfo::new(None, None, None, None);
+        let did_info = MyDidInfo::new(None, None, None, None, None, None);
 
         let message = r#"{
             "reqId":1495034346617224651,
@@ -280,7 +937,7 @@ mod tests {
     fn sign_verify_works() {
         let service = SignusService::new();
 
-        let did_info = MyDidInfo::new(None, None, None, None);
+        let did_info = MyDidInfo::new(None, None, None, None, None, None);
 
         let message = r#"{
             "reqId":1495034346617224651,
@@ -311,7 +968,7 @@ mod tests {
     fn verify_not_works_for_invalid_verkey() {
         let service = SignusService::new();
 
-        let did_info = MyDidInfo::new(None, None, None, None);
+        let did_info = MyDidInfo::new(None, None, None, None, None, None);
 
         let message = r#"{
             "reqId":1495034346617224651,
@@ -344,7 +1001,7 @@ mod tests {
 
         let msg = "some message";
 
-        let did_info = MyDidInfo::new(None, None, None, None);
+        let did_info = MyDidInfo::new(None, None, None, None, None, None);
 
         let my_did = service.create_my_did(&did_info).unwrap();
 
@@ -367,7 +1024,7 @@ mod tests {
 
         let msg = "some message";
 
-        let did_info = MyDidInfo::new(None, None, None, None);
+        let did_info = MyDidInfo::new(None, None, None, None, None, None);
 
         let my_did = service.create_my_did(&did_info).unwrap();
 
@@ -400,4 +1057,243 @@ mod tests {
 
         assert_eq!(msg.as_bytes().to_vec(), decrypted_message);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn create_key_works_for_seed() {
+        let service = SignusService::new();
+
+        let key_info = MyKyesInfo::new(Some("00000000000000000000000000000My1".to_string()), None);
+
+        let key = service.create_key(&key_info).unwrap();
+        assert_eq!("GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa", key.verkey);
+        assert_eq!(DEFAULT_CRYPTO_TYPE, key.crypto_type);
+    }
+
+    #[test]
+    fn create_key_works_for_unknown_crypto_type() {
+        let service = SignusService::new();
+
+        let key_info = MyKyesInfo::new(None, Some("type".to_string()));
+
+        assert!(service.create_key(&key_info).is_err());
+    }
+
+    #[test]
+    fn crypto_sign_verify_works() {
+        let service = SignusService::new();
+
+        let msg = "some message".as_bytes();
+
+        let key = service.create_key(&MyKyesInfo::new(None, None)).unwrap();
+
+        let signature = service.crypto_sign(&key, msg).unwrap();
+
+        assert!(service.crypto_verify(&key.verkey, msg, &signature).unwrap());
+        assert!(service.crypto_verify(&format!("{}:{}", key.verkey, DEFAULT_CRYPTO_TYPE), msg, &signature).unwrap());
+    }
+
+    #[test]
+    fn crypto_verify_works_for_unknown_crypto_type() {
+        let service = SignusService::new();
+
+        let msg = "some message".as_bytes();
+
+        let key = service.create_key(&MyKyesInfo::new(None, None)).unwrap();
+
+        let signature = service.crypto_sign(&key, msg).unwrap();
+
+        assert!(service.crypto_verify(&format!("{}:type", key.verkey), msg, &signature).is_err());
+    }
+
+    #[test]
+    fn crypto_box_open_works() {
+        let service = SignusService::new();
+
+        let msg = "some message".as_bytes();
+
+        let my_key = service.create_key(&MyKyesInfo::new(None, None)).unwrap();
+        let their_key = service.create_key(&MyKyesInfo::new(None, None)).unwrap();
+
+        let (encrypted_message, nonce) = service.crypto_box(&my_key, &their_key.verkey, msg).unwrap();
+
+        let decrypted_message = service.crypto_box_open(&their_key, &my_key.verkey, &encrypted_message, &nonce).unwrap();
+
+        assert_eq!(msg.to_vec(), decrypted_message);
+    }
+
+    #[test]
+    fn verify_with_verkey_works() {
+        let service = SignusService::new();
+
+        let msg = "some message".as_bytes();
+
+        let key = service.create_key(&MyKyesInfo::new(None, None)).unwrap();
+        let signature = service.crypto_sign(&key, msg).unwrap();
+
+        assert!(service.verify_with_verkey(None, &key.verkey, msg, &signature).unwrap());
+    }
+
+    #[test]
+    fn verify_with_verkey_works_for_abbreviated_verkey() {
+        let service = SignusService::new();
+
+        let msg = "some message".as_bytes();
+
+        let my_did = service.create_my_did(&MyDidInfo::new(None, None, None, None, None, None)).unwrap();
+        let signature = service.sign(&my_did, msg).unwrap();
+
+        let abbreviated_verkey = format!("~{}", Base58::encode(&Base58::decode(&my_did.verkey).unwrap()[16..]));
+
+        assert!(service.verify_with_verkey(Some(&my_did.did), &abbreviated_verkey, msg, &signature).unwrap());
+    }
+
+    #[test]
+    fn verify_with_verkey_works_for_abbreviated_verkey_without_did() {
+        let service = SignusService::new();
+
+        let res = service.verify_with_verkey(None, "~NcYxiDXkpYi6ov5FcYDi1e", "some message".as_bytes(), &[0; 64]);
+        assert_match!(Err(SignusError::CommonError(CommonError::InvalidStructure(_))), res);
+    }
+
+    #[test]
+    fn decrypt_sealed_works() {
+        let service = SignusService::new();
+
+        let msg = "some message".as_bytes();
+
+        let my_key = service.create_key(&MyKyesInfo::new(None, None)).unwrap();
+
+        let encrypted_message = service.encrypt_sealed(&my_key.verkey, msg).unwrap();
+
+        let decrypted_message = service.decrypt_sealed(&my_key, &encrypted_message).unwrap();
+
+        assert_eq!(msg.to_vec(), decrypted_message);
+    }
+
+    #[test]
+    fn decrypt_sealed_works_for_other_key() {
+        let service = SignusService::new();
+
+        let msg = "some message".as_bytes();
+
+        let my_key = service.create_key(&MyKyesInfo::new(None, None)).unwrap();
+        let other_key = service.create_key(&MyKyesInfo::new(None, None)).unwrap();
+
+        let encrypted_message = service.encrypt_sealed(&my_key.verkey, msg).unwrap();
+
+        let res = service.decrypt_sealed(&other_key, &encrypted_message);
+        assert_match!(Err(SignusError::CommonError(CommonError::InvalidStructure(_))), res);
+    }
+
+    #[test]
+    fn decrypt_sealed_works_for_short_message() {
+        let service = SignusService::new();
+
+        let my_key = service.create_key(&MyKyesInfo::new(None, None)).unwrap();
+
+        let res = service.decrypt_sealed(&my_key, "short".as_bytes());
+        assert_match!(Err(SignusError::CommonError(CommonError::InvalidStructure(_))), res);
+    }
+
+    #[test]
+    fn pack_unpack_message_works_for_authcrypt() {
+        let service = SignusService::new();
+
+        let msg = "some message".as_bytes();
+
+        let sender_key = service.create_key(&MyKyesInfo::new(None, None)).unwrap();
+        let recipient_key1 = service.create_key(&MyKyesInfo::new(None, None)).unwrap();
+        let recipient_key2 = service.create_key(&MyKyesInfo::new(None, None)).unwrap();
+
+        let packed_msg = service.pack_message(msg, &[recipient_key1.verkey.clone(), recipient_key2.verkey.clone()], Some(&sender_key)).unwrap();
+        assert_eq!(PACKED_MESSAGE_AUTHCRYPT, packed_msg.alg);
+        assert_eq!(2, packed_msg.recipients.len());
+
+        for recipient_key in [recipient_key1, recipient_key2].iter() {
+            let (unpacked_msg, sender_vk) = service.unpack_message(&packed_msg, recipient_key).unwrap();
+            assert_eq!(msg.to_vec(), unpacked_msg);
+            assert_eq!(Some(sender_key.verkey.clone()), sender_vk);
+        }
+    }
+
+    #[test]
+    fn pack_unpack_message_works_for_anoncrypt() {
+        let service = SignusService::new();
+
+        let msg = "some message".as_bytes();
+
+        let recipient_key = service.create_key(&MyKyesInfo::new(None, None)).unwrap();
+
+        let packed_msg = service.pack_message(msg, &[recipient_key.verkey.clone()], None).unwrap();
+        assert_eq!(PACKED_MESSAGE_ANONCRYPT, packed_msg.alg);
+        assert!(packed_msg.recipients[0].sender.is_none());
+
+        let (unpacked_msg, sender_vk) = service.unpack_message(&packed_msg, &recipient_key).unwrap();
+        assert_eq!(msg.to_vec(), unpacked_msg);
+        assert_eq!(None, sender_vk);
+    }
+
+    #[test]
+    fn pack_unpack_message_works_for_large_message() {
+        let service = SignusService::new();
+
+        let msg = vec![42u8; 1024 * 1024];
+
+        let sender_key = service.create_key(&MyKyesInfo::new(None, None)).unwrap();
+        let recipient_key = service.create_key(&MyKyesInfo::new(None, None)).unwrap();
+
+        let packed_msg = service.pack_message(&msg, &[recipient_key.verkey.clone()], Some(&sender_key)).unwrap();
+
+        let (unpacked_msg, sender_vk) = service.unpack_message(&packed_msg, &recipient_key).unwrap();
+        assert_eq!(msg, unpacked_msg);
+        assert_eq!(Some(sender_key.verkey.clone()), sender_vk);
+    }
+
+    #[test]
+    fn pack_message_works_for_empty_recipients() {
+        let service = SignusService::new();
+
+        let res = service.pack_message("some message".as_bytes(), &[], None);
+        assert_match!(Err(SignusError::CommonError(CommonError::InvalidStructure(_))), res);
+    }
+
+    #[test]
+    fn unpack_message_works_for_other_key() {
+        let service = SignusService::new();
+
+        let recipient_key = service.create_key(&MyKyesInfo::new(None, None)).unwrap();
+        let other_key = service.create_key(&MyKyesInfo::new(None, None)).unwrap();
+
+        let packed_msg = service.pack_message("some message".as_bytes(), &[recipient_key.verkey.clone()], None).unwrap();
+
+        let res = service.unpack_message(&packed_msg, &other_key);
+        assert_match!(Err(SignusError::CommonError(CommonError::InvalidStructure(_))), res);
+    }
+
+    #[test]
+    fn unpack_message_works_for_truncated_nonce() {
+        let service = SignusService::new();
+
+        let recipient_key = service.create_key(&MyKyesInfo::new(None, None)).unwrap();
+
+        let mut packed_msg = service.pack_message("some message".as_bytes(), &[recipient_key.verkey.clone()], None).unwrap();
+        packed_msg.nonce = Base64::encode(&Base64::decode(&packed_msg.nonce).unwrap()[..PACKED_MESSAGE_NONCE_LEN - 1]);
+
+        let res = service.unpack_message(&packed_msg, &recipient_key);
+        assert_match!(Err(SignusError::CommonError(CommonError::InvalidStructure(_))), res);
+    }
+
+    #[test]
+    fn unpack_message_works_for_short_content_key() {
+        let service = SignusService::new();
+
+        let recipient_key = service.create_key(&MyKyesInfo::new(None, None)).unwrap();
+
+        let mut packed_msg = service.pack_message("some message".as_bytes(), &[recipient_key.verkey.clone()], None).unwrap();
+        let short_key = service.encrypt_sealed(&recipient_key.verkey, &[1; PACKED_MESSAGE_KEY_LEN - 1]).unwrap();
+        packed_msg.recipients[0].encrypted_key = Base64::encode(&short_key);
+
+        let res = service.unpack_message(&packed_msg, &recipient_key);
+        assert_match!(Err(SignusError::CommonError(CommonError::InvalidStructure(_))), res);
+    }
+}
@@ -0,0 +1,204 @@
+extern crate rand;
+
+use super::CryptoType;
+
+use api::ErrorCode;
+use errors::common::CommonError;
+use errors::signus::SignusError;
+
+use self::rand::{OsRng, Rng};
+
+use std::ptr;
+use std::slice;
+
+const NONCE_LEN: usize = 24;
+
+pub struct PluggedCryptoType {
+    create_key_handler: extern fn(seed_raw: *const u8, seed_len: u32,
+                                  verkey_raw: *mut *const u8, verkey_len: *mut u32,
+                                  signkey_raw: *mut *const u8, signkey_len: *mut u32) -> ErrorCode,
+    sign_handler: extern fn(signkey_raw: *const u8, signkey_len: u32,
+                            message_raw: *const u8, message_len: u32,
+                            signature_raw: *mut *const u8, signature_len: *mut u32) -> ErrorCode,
+    verify_handler: extern fn(verkey_raw: *const u8, verkey_len: u32,
+                              message_raw: *const u8, message_len: u32,
+                              signature_raw: *const u8, signature_len: u32,
+                              valid: *mut u32) -> ErrorCode,
+    encrypt_handler: extern fn(signkey_raw: *const u8, signkey_len: u32,
+                               verkey_raw: *const u8, verkey_len: u32,
+                               nonce_raw: *const u8, nonce_len: u32,
+                               message_raw: *const u8, message_len: u32,
+                               encrypted_msg_raw: *mut *const u8, encrypted_msg_len: *mut u32) -> ErrorCode,
+    decrypt_handler: extern fn(signkey_raw: *const u8, signkey_len: u32,
+                               verkey_raw: *const u8, verkey_len: u32,
+                               nonce_raw: *const u8, nonce_len: u32,
+                               encrypted_msg_raw: *const u8, encrypted_msg_len: u32,
+                               decrypted_msg_raw: *mut *const u8, decrypted_msg_len: *mut u32) -> ErrorCode,
+    free_handler: extern fn(data_raw: *const u8, data_len: u32) -> ErrorCode
+}
+
+impl PluggedCryptoType {
+    pub fn new(create_key_handler: extern fn(seed_raw: *const u8, seed_len: u32,
+                                             verkey_raw: *mut *const u8, verkey_len: *mut u32,
+                                             signkey_raw: *mut *const u8, signkey_len: *mut u32) -> ErrorCode,
+               sign_handler: extern fn(signkey_raw: *const u8, signkey_len: u32,
+                                       message_raw: *const u8, message_len: u32,
+                                       signature_raw: *mut *const u8, signature_len: *mut u32) -> ErrorCode,
+               verify_handler: extern fn(verkey_raw: *const u8, verkey_len: u32,
+                                         message_raw: *const u8, message_len: u32,
+                                         signature_raw: *const u8, signature_len: u32,
+                                         valid: *mut u32) -> ErrorCode,
+               encrypt_handler: extern fn(signkey_raw: *const u8, signkey_len: u32,
+                                          verkey_raw: *const u8, verkey_len: u32,
+                                          nonce_raw: *const u8, nonce_len: u32,
+                                          message_raw: *const u8, message_len: u32,
+                                          encrypted_msg_raw: *mut *const u8, encrypted_msg_len: *mut u32) -> ErrorCode,
+               decrypt_handler: extern fn(signkey_raw: *const u8, signkey_len: u32,
+                                          verkey_raw: *const u8, verkey_len: u32,
+                                          nonce_raw: *const u8, nonce_len: u32,
+                                          encrypted_msg_raw: *const u8, encrypted_msg_len: u32,
+                                          decrypted_msg_raw: *mut *const u8, decrypted_msg_len: *mut u32) -> ErrorCode,
+               free_handler: extern fn(data_raw: *const u8, data_len: u32) -> ErrorCode) -> PluggedCryptoType {
+        PluggedCryptoType {
+            create_key_handler: create_key_handler,
+            sign_handler: sign_handler,
+            verify_handler: verify_handler,
+            encrypt_handler: encrypt_handler,
+            decrypt_handler: decrypt_handler,
+            free_handler: free_handler
+        }
+    }
+
+    fn _take_data(&self, data_raw: *const u8, data_len: u32) -> Result<Vec<u8>, SignusError> {
+        if data_raw.is_null() {
+            return Ok(Vec::new());
+        }
+
+        let data = unsafe { slice::from_raw_parts(data_raw, data_len as usize).to_vec() };
+
+        let err = (self.free_handler)(data_raw, data_len);
+
+        if err != ErrorCode::Success {
+            return Err(SignusError::PluggedCryptoError(err));
+        }
+
+        Ok(data)
+    }
+}
+
+impl CryptoType for PluggedCryptoType {
+    fn encrypt(&self, private_key: &[u8], public_key: &[u8], doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, SignusError> {
+        let mut encrypted_doc_raw: *const u8 = ptr::null();
+        let mut encrypted_doc_len: u32 = 0;
+
+        let err = (self.encrypt_handler)(private_key.as_ptr(), private_key.len() as u32,
+                                         public_key.as_ptr(), public_key.len() as u32,
+                                         nonce.as_ptr(), nonce.len() as u32,
+                                         doc.as_ptr(), doc.len() as u32,
+                                         &mut encrypted_doc_raw, &mut encrypted_doc_len);
+
+        if err != ErrorCode::Success {
+            return Err(SignusError::PluggedCryptoError(err));
+        }
+
+        self._take_data(encrypted_doc_raw, encrypted_doc_len)
+    }
+
+    fn decrypt(&self, private_key: &[u8], public_key: &[u8], doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, SignusError> {
+        let mut decrypted_doc_raw: *const u8 = ptr::null();
+        let mut decrypted_doc_len: u32 = 0;
+
+        let err = (self.decrypt_handler)(private_key.as_ptr(), private_key.len() as u32,
+                                         public_key.as_ptr(), public_key.len() as u32,
+                                         nonce.as_ptr(), nonce.len() as u32,
+                                         doc.as_ptr(), doc.len() as u32,
+                                         &mut decrypted_doc_raw, &mut decrypted_doc_len);
+
+        if err != ErrorCode::Success {
+            return Err(SignusError::PluggedCryptoError(err));
+        }
+
+        self._take_data(decrypted_doc_raw, decrypted_doc_len)
+    }
+
+    fn encrypt_sealed(&self, _public_key: &[u8], _doc: &[u8]) -> Result<Vec<u8>, SignusError> {
+        Err(SignusError::CommonError(
+            CommonError::InvalidState(format!("Anonymous encryption isn't supported by plugged crypto types"))))
+    }
+
+    fn decrypt_sealed(&self, _private_key: &[u8], _doc: &[u8]) -> Result<Vec<u8>, SignusError> {
+        Err(SignusError::CommonError(
+            CommonError::InvalidState(format!("Anonymous encryption isn't supported by plugged crypto types"))))
+    }
+
+    fn gen_nonce(&self) -> Vec<u8> {
+        let mut nonce = vec![0u8; NONCE_LEN];
+        OsRng::new().unwrap().fill_bytes(&mut nonce);
+        nonce
+    }
+
+    fn create_key_pair_for_signature(&self, seed: Option<&[u8]>) -> Result<(Vec<u8>, Vec<u8>), SignusError> {
+        let (seed_raw, seed_len) = match seed {
+            Some(seed) => (seed.as_ptr(), seed.len() as u32),
+            None => (ptr::null(), 0)
+        };
+
+        let mut verkey_raw: *const u8 = ptr::null();
+        let mut verkey_len: u32 = 0;
+        let mut signkey_raw: *const u8 = ptr::null();
+        let mut signkey_len: u32 = 0;
+
+        let err = (self.create_key_handler)(seed_raw, seed_len,
+                                            &mut verkey_raw, &mut verkey_len,
+                                            &mut signkey_raw, &mut signkey_len);
+
+        if err != ErrorCode::Success {
+            return Err(SignusError::PluggedCryptoError(err));
+        }
+
+        let verkey = self._take_data(verkey_raw, verkey_len)?;
+        let signkey = self._take_data(signkey_raw, signkey_len)?;
+
+        Ok((verkey, signkey))
+    }
+
+    fn sign(&self, private_key: &[u8], doc: &[u8]) -> Result<Vec<u8>, SignusError> {
+        let mut signature_raw: *const u8 = ptr::null();
+        let mut signature_len: u32 = 0;
+
+        let err = (self.sign_handler)(private_key.as_ptr(), private_key.len() as u32,
+                                      doc.as_ptr(), doc.len() as u32,
+                                      &mut signature_raw, &mut signature_len);
+
+        if err != ErrorCode::Success {
+            return Err(SignusError::PluggedCryptoError(err));
+        }
+
+        self._take_data(signature_raw, signature_len)
+    }
+
+    fn verify(&self, public_key: &[u8], doc: &[u8], signature: &[u8]) -> Result<bool, SignusError> {
+        // valid is indy_bool_t (C unsigned int) on the C side
+        let mut valid: u32 = 0;
+
+        let err = (self.verify_handler)(public_key.as_ptr(), public_key.len() as u32,
+                                        doc.as_ptr(), doc.len() as u32,
+                                        signature.as_ptr(), signature.len() as u32,
+                                        &mut valid);
+
+        if err != ErrorCode::Success {
+            return Err(SignusError::PluggedCryptoError(err));
+        }
+
+        Ok(valid != 0)
+    }
+
+    // Plugged types receive keys exactly as they were returned by create_key handler
+    fn verkey_to_public_key(&self, vk: &[u8]) -> Result<Vec<u8>, SignusError> {
+        Ok(vk.to_vec())
+    }
+
+    fn signkey_to_private_key(&self, sk: &[u8]) -> Result<Vec<u8>, SignusError> {
+        Ok(sk.to_vec())
+    }
+}
@@ -5,17 +5,22 @@ pub struct MyDidInfo {
     pub did: Option<String>,
     pub seed: Option<String>,
     pub crypto_type: Option<String>,
-    pub cid: Option<bool>
+    pub cid: Option<bool>,
+    pub derivation_path: Option<String>,
+    pub method_name: Option<String>
 }
 
 impl MyDidInfo {
     pub fn new(did: Option<String>, seed: Option<String>,
-               crypto_type: Option<String>, cid: Option<bool>) -> MyDidInfo {
+               crypto_type: Option<String>, cid: Option<bool>,
+               derivation_path: Option<String>, method_name: Option<String>) -> MyDidInfo {
         MyDidInfo {
             did: did,
             seed: seed,
             crypto_type: crypto_type,
-            cid: cid
+            cid: cid,
+            derivation_path: derivation_path,
+            method_name: method_name
         }
     }
 }
@@ -70,6 +75,50 @@ impl JsonEncodable for MyDid {}
 
 impl<'a> JsonDecodable<'a> for MyDid {}
 
+#[derive(Serialize, Debug)]
+pub struct MyDidWithMeta {
+    pub did: String,
+    pub verkey: String,
+    pub crypto_type: String,
+    pub metadata: Option<String>,
+    pub temp_verkey: Option<String>
+}
+
+impl MyDidWithMeta {
+    pub fn new(my_did: MyDid, metadata: Option<String>, temp_verkey: Option<String>) -> MyDidWithMeta {
+        MyDidWithMeta {
+            did: my_did.did,
+            verkey: my_did.verkey,
+            crypto_type: my_did.crypto_type,
+            metadata: metadata,
+            temp_verkey: temp_verkey
+        }
+    }
+}
+
+impl JsonEncodable for MyDidWithMeta {}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Key {
+    pub verkey: String,
+    pub signkey: String,
+    pub crypto_type: String
+}
+
+impl Key {
+    pub fn new(verkey: String, signkey: String, crypto_type: String) -> Key {
+        Key {
+            verkey: verkey,
+            signkey: signkey,
+            crypto_type: crypto_type
+        }
+    }
+}
+
+impl JsonEncodable for Key {}
+
+impl<'a> JsonDecodable<'a> for Key {}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TheirDidInfo {
     pub did: String,
@@ -116,4 +165,27 @@ impl TheirDid {
 
 impl JsonEncodable for TheirDid {}
 
-impl<'a> JsonDecodable<'a> for TheirDid {}
\ No newline at end of file
+impl<'a> JsonDecodable<'a> for TheirDid {}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PackedMessage {
+    pub version: String,
+    pub alg: String,
+    pub recipients: Vec<PackedRecipient>,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+impl JsonEncodable for PackedMessage {}
+
+impl<'a> JsonDecodable<'a> for PackedMessage {}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PackedRecipient {
+    pub verkey: String,
+    pub encrypted_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sender: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+}
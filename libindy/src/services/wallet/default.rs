@@ -82,6 +82,17 @@ impl Wallet for DefaultWallet {
         Ok(record.value)
     }
 
+    fn remove(&self, key: &str) -> Result<(), WalletError> {
+        let removed = _open_connection(self.name.as_str())?
+            .execute("DELETE FROM wallet WHERE key = ?1", &[&key.to_string()])?;
+
+        if removed == 0 {
+            return Err(WalletError::NotFound(key.to_string()));
+        }
+
+        Ok(())
+    }
+
     fn list(&self, key_prefix: &str) -> Result<Vec<(String, String)>, WalletError> {
         let connection = _open_connection(self.name.as_str())?;
         let mut stmt = connection.prepare("SELECT key, value, time_created FROM wallet WHERE key like ?1 order by key")?;
@@ -315,6 +326,23 @@ mod tests {
         TestUtils::cleanup_indy_home();
     }
 
+    #[test]
+    fn default_wallet_remove_works() {
+        TestUtils::cleanup_indy_home();
+
+        let wallet_type = DefaultWalletType::new();
+        wallet_type.create("wallet1", None, None).unwrap();
+        let wallet = wallet_type.open("wallet1", "pool1", None, None, None).unwrap();
+
+        wallet.set("key1", "value1").unwrap();
+        wallet.remove("key1").unwrap();
+
+        assert_match!(Err(WalletError::NotFound(_)), wallet.get("key1"));
+        assert_match!(Err(WalletError::NotFound(_)), wallet.remove("key1"));
+
+        TestUtils::cleanup_indy_home();
+    }
+
     #[test]
     fn default_wallet_set_get_works_for_update() {
         TestUtils::cleanup_indy_home();
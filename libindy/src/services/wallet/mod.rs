@@ -24,6 +24,7 @@ use self::libc::c_char;
 pub trait Wallet {
     fn set(&self, key: &str, value: &str) -> Result<(), WalletError>;
     fn get(&self, key: &str) -> Result<String, WalletError>;
+    fn remove(&self, key: &str) -> Result<(), WalletError>;
     fn list(&self, key_prefix: &str) -> Result<Vec<(String, String)>, WalletError>;
     fn get_not_expired(&self, key: &str) -> Result<String, WalletError>;
     fn close(&self) -> Result<(), WalletError>;
@@ -253,6 +254,13 @@ impl WalletService {
         }
     }
 
+    pub fn remove(&self, handle: i32, key: &str) -> Result<(), WalletError> {
+        match self.wallets.borrow().get(&handle) {
+            Some(wallet) => wallet.remove(key),
+            None => Err(WalletError::InvalidHandle(handle.to_string()))
+        }
+    }
+
     pub fn list(&self, handle: i32, key_prefix: &str) -> Result<Vec<(String, String)>, WalletError> {
         match self.wallets.borrow().get(&handle) {
             Some(wallet) => wallet.list(key_prefix),
@@ -573,6 +581,41 @@ mod tests {
         InmemWallet::cleanup();
     }
 
+    #[test]
+    fn wallet_service_remove_works_for_plugged() {
+        TestUtils::cleanup_indy_home();
+        InmemWallet::cleanup();
+
+        let wallet_service = WalletService::new();
+
+        wallet_service
+            .register_type(
+                "inmem",
+                InmemWallet::create,
+                InmemWallet::open,
+                InmemWallet::set,
+                InmemWallet::get,
+                InmemWallet::get_not_expied,
+                InmemWallet::list,
+                InmemWallet::close,
+                InmemWallet::delete,
+                InmemWallet::free
+            )
+            .unwrap();
+
+        wallet_service.create("pool1", Some("inmem"), "wallet1", None, None).unwrap();
+        let wallet_handle = wallet_service.open("wallet1", None, None).unwrap();
+
+        wallet_service.set(wallet_handle, "key1", "value1").unwrap();
+        wallet_service.remove(wallet_handle, "key1").unwrap();
+
+        assert_match!(Err(WalletError::NotFound(_)), wallet_service.get(wallet_handle, "key1"));
+        assert_eq!(0, wallet_service.list(wallet_handle, "key").unwrap().len());
+
+        TestUtils::cleanup_indy_home();
+        InmemWallet::cleanup();
+    }
+
     #[test]
     fn wallet_service_set_get_works_for_reopen() {
         TestUtils::cleanup_indy_home();
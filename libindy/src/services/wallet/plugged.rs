@@ -119,9 +119,19 @@ impl Wallet for PluggedWallet {
             return Err(WalletError::PluggedWallerError(err));
         }
 
+        if result.is_empty() {
+            return Err(WalletError::NotFound(key.to_str()?.to_string()));
+        }
+
         Ok(result)
     }
 
+    // Plugged wallet API has no records deletion, so removed record is overwritten with empty value
+    fn remove(&self, key: &str) -> Result<(), WalletError> {
+        self.get(key)?;
+        self.set(key, "")
+    }
+
     fn list(&self, key_prefix: &str) -> Result<Vec<(String, String)>, WalletError> {
         let key_prefix = CString::new(key_prefix)?;
         let mut values_json_ptr: *const c_char = ptr::null_mut();
@@ -147,6 +157,7 @@ impl Wallet for PluggedWallet {
         let result = PluggedWalletJSONValues::from_json(values_json.as_str())?
             .values
             .iter()
+            .filter(|value| !value.value.is_empty())
             .map(|value| (value.key.clone(), value.value.clone()))
             .collect();
 
@@ -175,6 +186,10 @@ impl Wallet for PluggedWallet {
             return Err(WalletError::PluggedWallerError(err));
         }
 
+        if result.is_empty() {
+            return Err(WalletError::NotFound(key.to_str()?.to_string()));
+        }
+
         Ok(result)
     }
 
@@ -0,0 +1,43 @@
+extern crate base64;
+
+use errors::common::CommonError;
+
+// Unlike Base58 it encodes in linear time, so it is used for large binary payloads
+pub struct Base64 {}
+
+impl Base64 {
+    pub fn encode(doc: &[u8]) -> String {
+        base64::encode_config(doc, base64::URL_SAFE_NO_PAD)
+    }
+
+    pub fn decode(doc: &str) -> Result<Vec<u8>, CommonError> {
+        base64::decode_config(doc, base64::URL_SAFE_NO_PAD)
+            .map_err(|err| CommonError::InvalidStructure(format!("{}", err)))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_works() {
+        let result = Base64::encode(&[251, 255, 1]);
+        assert_eq!("-_8B", &result, "Got unexpected data");
+    }
+
+    #[test]
+    fn decode_works() {
+        let result = Base64::decode("-_8B");
+
+        assert!(result.is_ok(), "Got error");
+        assert_eq!(&[251, 255, 1], &result.unwrap()[..], "Get unexpected data");
+    }
+
+    #[test]
+    fn decode_works_for_invalid_data() {
+        let result = Base64::decode("+/8B");
+        assert!(result.is_err());
+    }
+}
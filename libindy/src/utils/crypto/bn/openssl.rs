@@ -8,11 +8,9 @@ use self::int_traits::IntTraits;
 
 use self::openssl::bn::{BigNum, BigNumRef, BigNumContext};
 use self::openssl::hash::{hash2, MessageDigest, Hasher};
-use self::openssl::error::ErrorStack;
 use self::serde::ser::{Serialize, Serializer, Error as SError};
 use self::serde::de::{Deserialize, Deserializer, Visitor, Error as DError};
 
-use std::error::Error;
 use std::fmt;
 use std::cmp::Ord;
 use std::cmp::Ordering;
@@ -408,13 +406,6 @@ impl<'a> Deserialize<'a> for BigNumber {
     }
 }
 
-impl From<ErrorStack> for CommonError {
-    fn from(err: ErrorStack) -> CommonError {
-        // TODO: FIXME: Analyze ErrorStack and split invalid structure errors from other errors
-        CommonError::InvalidStructure(err.description().to_string())
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,638 @@
+use errors::common::CommonError;
+
+extern crate num_bigint;
+extern crate num_integer;
+extern crate num_traits;
+extern crate rand;
+extern crate serde;
+extern crate sha2;
+
+use self::num_bigint::{BigInt, BigUint, RandBigInt, Sign, ToBigInt};
+use self::num_integer::Integer;
+use self::num_traits::{One, Signed, ToPrimitive, Zero};
+use self::rand::OsRng;
+use self::sha2::{Digest, Sha256};
+use self::serde::ser::{Serialize, Serializer, Error as SError};
+use self::serde::de::{Deserialize, Deserializer, Visitor, Error as DError};
+
+use std::fmt;
+use std::cmp::Ord;
+use std::cmp::Ordering;
+use services::anoncreds::helpers::BytesView;
+
+#[cfg(test)]
+use services::anoncreds::constants::{
+    LARGE_ALPHATILDE,
+    LARGE_NONCE,
+    LARGE_ETILDE,
+    LARGE_UTILDE,
+    LARGE_RTILDE,
+    LARGE_MASTER_SECRET,
+    LARGE_PRIME,
+    LARGE_VPRIME,
+    LARGE_VPRIME_PRIME,
+    LARGE_VTILDE
+};
+
+const SMALL_PRIMES: [u32; 54] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+    101, 103, 107, 109, 113, 127, 131, 137, 139, 149, 151, 157, 163, 167, 173, 179, 181, 191, 193,
+    197, 199, 211, 223, 227, 229, 233, 239, 241, 251
+];
+
+/// Pure Rust backend keeps no scratch state between operations,
+/// context exists only to keep the same API as OpenSSL backend.
+pub struct BigNumberContext {}
+
+#[derive(Debug)]
+pub struct BigNumber {
+    bn: BigInt
+}
+
+impl BigNumber {
+    pub fn new_context() -> Result<BigNumberContext, CommonError> {
+        Ok(BigNumberContext {})
+    }
+
+    pub fn new() -> Result<BigNumber, CommonError> {
+        Ok(BigNumber {
+            bn: BigInt::zero()
+        })
+    }
+
+    pub fn generate_prime(size: usize) -> Result<BigNumber, CommonError> {
+        let mut rng = BigNumber::_rng()?;
+
+        loop {
+            let candidate = BigNumber::_rand_odd_with_top_bit(&mut rng, size);
+
+            if BigNumber::_is_prime(&candidate, &mut rng, BigNumber::_prime_checks(&candidate)) {
+                return Ok(BigNumber { bn: candidate });
+            }
+        }
+    }
+
+    #[cfg(not(test))]
+    pub fn generate_safe_prime(size: usize) -> Result<BigNumber, CommonError> {
+        let mut rng = BigNumber::_rng()?;
+
+        // Result is safe prime p = 2q + 1 of (size + 1) bits as OpenSSL backend returns
+        loop {
+            let q = BigNumber::_rand_odd_with_top_bit(&mut rng, size);
+
+            if BigNumber::_has_small_factor(&q) {
+                continue;
+            }
+
+            let p: BigInt = (&q << 1) + BigInt::one();
+
+            if BigNumber::_has_small_factor(&p) {
+                continue;
+            }
+
+            if BigNumber::_is_prime(&q, &mut rng, BigNumber::_prime_checks(&q)) &&
+                BigNumber::_is_prime(&p, &mut rng, BigNumber::_prime_checks(&p)) {
+                return Ok(BigNumber { bn: p });
+            }
+        }
+    }
+
+    #[cfg(test)]
+    pub fn generate_safe_prime(size: usize) -> Result<BigNumber, CommonError> {
+        match size {
+            LARGE_PRIME => Ok(BigNumber::from_dec("298425477551432359319017298068281828134535746771300905126443720735756534287270383542467183175737460443806952398210045827718115111810885752229119677470711305345901926067944629292942471551423868488963517954094239606951758940767987427212463600313901180668176172283994206392965011112962119159458674722785709556623")?),
+            _ => {
+                debug!("Uncovered case: {}", size);
+                Ok(BigNumber::new()?)
+            }
+        }
+    }
+
+    pub fn generate_prime_in_range(start: &BigNumber, end: &BigNumber) -> Result<BigNumber, CommonError> {
+        let mut prime;
+        let mut iteration = 0;
+        let mut bn_ctx = BigNumber::new_context()?;
+        let sub = end.sub(start)?;
+
+        loop {
+            prime = sub.rand_range()?;
+            prime = prime.add(start)?;
+
+            if prime.is_prime(Some(&mut bn_ctx))? {
+                debug!("Found prime in {} iteration", iteration);
+                break;
+            }
+            iteration += 1;
+        }
+
+        Ok(prime)
+    }
+
+    pub fn is_prime(&self, _ctx: Option<&mut BigNumberContext>) -> Result<bool, CommonError> {
+        let mut rng = BigNumber::_rng()?;
+        Ok(BigNumber::_is_prime(&self.bn, &mut rng, BigNumber::_prime_checks(&self.bn)))
+    }
+
+    #[cfg(not(test))]
+    pub fn rand(size: usize) -> Result<BigNumber, CommonError> {
+        let mut rng = BigNumber::_rng()?;
+        Ok(BigNumber {
+            bn: rng.gen_biguint(size).to_bigint().unwrap()
+        })
+    }
+
+    #[cfg(test)]
+    pub fn rand(size: usize) -> Result<BigNumber, CommonError> {
+        match size {
+            LARGE_NONCE => Ok(BigNumber::from_dec("526193306511429638192053")?),
+            LARGE_MASTER_SECRET => Ok(BigNumber::from_dec("21578029250517794450984707538122537192839006240802068037273983354680998203845")?),
+            LARGE_ETILDE => Ok(BigNumber::from_dec("162083298053730499878539835193560156486733663622707027216327685550780519347628838870322946818623352681120371349972731968874009673965057322")?),
+            LARGE_UTILDE => Ok(BigNumber::from_dec("6461691768834933403326572830814516653957231030793837560544354737855803497655300429843454445497126567767486684087006218691084619904526729989680526652503377438786587511370042964338")?),
+            LARGE_RTILDE => Ok(BigNumber::from_dec("7575191721496255329790454166600075461811327744716122725414003704363002865687003988444075479817517968742651133011723131465916075452356777073568785406106174349810313776328792235352103470770562831584011847")?),
+            LARGE_PRIME => Ok(BigNumber::from_dec("67940925789970108743024738273926421512152745397724199848594503731042154269417576665420030681245389493783225644817826683796657351721363490290016166310023506339911751676800452438014771736117676826911321621579680668201191205819012441197794443970687648330757835198888257781967404396196813475280544039772512800509")?),
+            LARGE_VPRIME => Ok(BigNumber::from_dec("1921424195886158938744777125021406748763985122590553448255822306242766229793715475428833504725487921105078008192433858897449555181018215580757557939320974389877538474522876366787859030586130885280724299566241892352485632499791646228580480458657305087762181033556428779333220803819945703716249441372790689501824842594015722727389764537806761583087605402039968357991056253519683582539703803574767702877615632257021995763302779502949501243649740921598491994352181379637769188829653918416991301420900374928589100515793950374255826572066003334385555085983157359122061582085202490537551988700484875690854200826784921400257387622318582276996322436")?),
+            LARGE_VPRIME_PRIME => Ok(BigNumber::from_dec("6620937836014079781509458870800001917950459774302786434315639456568768602266735503527631640833663968617512880802104566048179854406925811731340920442625764155409951969854303612644121780700879432308016935250101960876405664503219252820761501606507817390189252221968804450207070282033815280889897882643560437257171838117793768660731379360330750300543760457608638753190279419951706206819943151918535286779337023708838891906829360439545064730288538139152367417882097349210427894031568623898916625312124319876670702064561291393993815290033742478045530118808274555627855247830659187691067893683525651333064738899779446324124393932782261375663033826174482213348732912255948009062641783238846143256448824091556005023241191311617076266099622843011796402959351074671886795391490945230966123230485475995208322766090290573654498779155")?),
+            LARGE_VTILDE => Ok(BigNumber::from_dec("241132863422049783305938184561371219250127488499746090592218003869595412171810997360214885239402274273939963489505434726467041932541499422544431299362364797699330176612923593931231233163363211565697860685967381420219969754969010598350387336530924879073366177641099382257720898488467175132844984811431059686249020737675861448309521855120928434488546976081485578773933300425198911646071284164884533755653094354378714645351464093907890440922615599556866061098147921890790915215227463991346847803620736586839786386846961213073783437136210912924729098636427160258710930323242639624389905049896225019051952864864612421360643655700799102439682797806477476049234033513929028472955119936073490401848509891547105031112859155855833089675654686301183778056755431562224990888545742379494795601542482680006851305864539769704029428620446639445284011289708313620219638324467338840766574612783533920114892847440641473989502440960354573501")?),
+            LARGE_ALPHATILDE => Ok(BigNumber::from_dec("15019832071918025992746443764672619814038193111378331515587108416842661492145380306078894142589602719572721868876278167686578705125701790763532708415180504799241968357487349133908918935916667492626745934151420791943681376124817051308074507483664691464171654649868050938558535412658082031636255658721308264295197092495486870266555635348911182100181878388728256154149188718706253259396012667950509304959158288841789791483411208523521415447630365867367726300467842829858413745535144815825801952910447948288047749122728907853947789264574578039991615261320141035427325207080621563365816477359968627596441227854436137047681372373555472236147836722255880181214889123172703767379416198854131024048095499109158532300492176958443747616386425935907770015072924926418668194296922541290395990933578000312885508514814484100785527174742772860178035596639")?),
+            _ => {
+                debug!("Uncovered case: {}", size);
+                Ok(BigNumber::new()?)
+            }
+        }
+    }
+
+    pub fn rand_range(&self) -> Result<BigNumber, CommonError> {
+        if !self.bn.is_positive() {
+            return Err(CommonError::InvalidStructure(format!("Invalid range: {}", self.bn)));
+        }
+
+        let mut rng = BigNumber::_rng()?;
+        Ok(BigNumber {
+            bn: rng.gen_bigint_range(&BigInt::zero(), &self.bn)
+        })
+    }
+
+    pub fn num_bits(&self) -> Result<i32, CommonError> {
+        Ok(self.bn.bits() as i32)
+    }
+
+    pub fn is_bit_set(&self, n: i32) -> Result<bool, CommonError> {
+        Ok((self.bn.abs() >> n as usize).is_odd())
+    }
+
+    pub fn set_bit(&mut self, n: i32) -> Result<&mut BigNumber, CommonError> {
+        if !self.is_bit_set(n)? {
+            let bit = BigInt::one() << n as usize;
+            self.bn = if self.bn.is_negative() { &self.bn - bit } else { &self.bn + bit };
+        }
+        Ok(self)
+    }
+
+    pub fn from_u32(n: usize) -> Result<BigNumber, CommonError> {
+        Ok(BigNumber {
+            bn: BigInt::from(n as u32)
+        })
+    }
+
+    pub fn from_dec(dec: &str) -> Result<BigNumber, CommonError> {
+        BigNumber::_from_str_radix(dec, 10)
+    }
+
+    pub fn from_hex(hex: &str) -> Result<BigNumber, CommonError> {
+        BigNumber::_from_str_radix(hex, 16)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<BigNumber, CommonError> {
+        Ok(BigNumber {
+            bn: BigInt::from_bytes_be(Sign::Plus, bytes)
+        })
+    }
+
+    pub fn to_dec(&self) -> Result<String, CommonError> {
+        Ok(self.bn.to_str_radix(10))
+    }
+
+    pub fn to_hex(&self) -> Result<String, CommonError> {
+        Ok(self.bn.to_str_radix(16).to_uppercase())
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, CommonError> {
+        // OpenSSL backend returns magnitude only and empty vector for zero
+        if self.bn.is_zero() {
+            return Ok(Vec::new());
+        }
+        Ok(self.bn.to_bytes_be().1)
+    }
+
+    pub fn hash(data: &[u8]) -> Result<Vec<u8>, CommonError> {
+        let mut sha256 = Sha256::default();
+        sha256.input(data);
+        Ok(sha256.result().to_vec())
+    }
+
+    pub fn add(&self, a: &BigNumber) -> Result<BigNumber, CommonError> {
+        Ok(BigNumber {
+            bn: &self.bn + &a.bn
+        })
+    }
+
+    pub fn sub(&self, a: &BigNumber) -> Result<BigNumber, CommonError> {
+        Ok(BigNumber {
+            bn: &self.bn - &a.bn
+        })
+    }
+
+    pub fn sqr(&self, _ctx: Option<&mut BigNumberContext>) -> Result<BigNumber, CommonError> {
+        Ok(BigNumber {
+            bn: &self.bn * &self.bn
+        })
+    }
+
+    pub fn mul(&self, a: &BigNumber, _ctx: Option<&mut BigNumberContext>) -> Result<BigNumber, CommonError> {
+        Ok(BigNumber {
+            bn: &self.bn * &a.bn
+        })
+    }
+
+    pub fn div(&self, a: &BigNumber, _ctx: Option<&mut BigNumberContext>) -> Result<BigNumber, CommonError> {
+        if a.bn.is_zero() {
+            return Err(CommonError::InvalidStructure("Division by zero".to_string()));
+        }
+
+        Ok(BigNumber {
+            bn: &self.bn / &a.bn
+        })
+    }
+
+    pub fn add_word(&mut self, w: u32) -> Result<&mut BigNumber, CommonError> {
+        self.bn = &self.bn + BigInt::from(w);
+        Ok(self)
+    }
+
+    pub fn sub_word(&mut self, w: u32) -> Result<&mut BigNumber, CommonError> {
+        self.bn = &self.bn - BigInt::from(w);
+        Ok(self)
+    }
+
+    pub fn mul_word(&mut self, w: u32) -> Result<&mut BigNumber, CommonError> {
+        self.bn = &self.bn * BigInt::from(w);
+        Ok(self)
+    }
+
+    pub fn div_word(&mut self, w: u32) -> Result<&mut BigNumber, CommonError> {
+        if w == 0 {
+            return Err(CommonError::InvalidStructure("Division by zero".to_string()));
+        }
+
+        self.bn = &self.bn / BigInt::from(w);
+        Ok(self)
+    }
+
+    pub fn mod_exp(&self, a: &BigNumber, b: &BigNumber, _ctx: Option<&mut BigNumberContext>) -> Result<BigNumber, CommonError> {
+        if b.bn.is_zero() {
+            return Err(CommonError::InvalidStructure("Division by zero".to_string()));
+        }
+
+        // Like OpenSSL only magnitude of exponent is used
+        Ok(BigNumber {
+            bn: BigNumber::_mod_exp(&self.bn, &a.bn.abs(), &b.bn.abs())
+        })
+    }
+
+    pub fn modulus(&self, a: &BigNumber, _ctx: Option<&mut BigNumberContext>) -> Result<BigNumber, CommonError> {
+        if a.bn.is_zero() {
+            return Err(CommonError::InvalidStructure("Division by zero".to_string()));
+        }
+
+        Ok(BigNumber {
+            bn: BigNumber::_nnmod(&self.bn, &a.bn.abs())
+        })
+    }
+
+    pub fn exp(&self, a: &BigNumber, _ctx: Option<&mut BigNumberContext>) -> Result<BigNumber, CommonError> {
+        let mut exp = a.bn.to_usize()
+            .ok_or(CommonError::InvalidStructure(format!("Invalid exponent: {}", a.bn)))?;
+
+        let mut base = self.bn.clone();
+        let mut result = BigInt::one();
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = &result * &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+
+        Ok(BigNumber {
+            bn: result
+        })
+    }
+
+    pub fn inverse(&self, n: &BigNumber, _ctx: Option<&mut BigNumberContext>) -> Result<BigNumber, CommonError> {
+        let n = n.bn.abs();
+
+        if n.is_zero() {
+            return Err(CommonError::InvalidStructure("Division by zero".to_string()));
+        }
+
+        let (mut r0, mut r1) = (n.clone(), BigNumber::_nnmod(&self.bn, &n));
+        let (mut t0, mut t1) = (BigInt::zero(), BigInt::one());
+
+        while !r1.is_zero() {
+            let q = &r0 / &r1;
+
+            let r2 = &r0 - &q * &r1;
+            r0 = r1;
+            r1 = r2;
+
+            let t2 = &t0 - &q * &t1;
+            t0 = t1;
+            t1 = t2;
+        }
+
+        if r0 != BigInt::one() {
+            return Err(CommonError::InvalidStructure(format!("No inverse for {} modulo {}", self.bn, n)));
+        }
+
+        Ok(BigNumber {
+            bn: BigNumber::_nnmod(&t0, &n)
+        })
+    }
+
+    pub fn mod_div(&self, b: &BigNumber, p: &BigNumber) -> Result<BigNumber, CommonError> {
+        //(a*  (1/b mod p) mod p)
+
+        let mut context = BigNumber::new_context()?;
+
+        let res = b
+            .inverse(p, Some(&mut context))?
+            .mul(&self, Some(&mut context))?
+            .modulus(&p, Some(&mut context))?;
+        Ok(res)
+    }
+
+    pub fn clone(&self) -> Result<BigNumber, CommonError> {
+        Ok(BigNumber {
+            bn: self.bn.clone()
+        })
+    }
+
+    pub fn hash_array(nums: &Vec<Vec<u8>>) -> Result<Vec<u8>, CommonError> {
+        let mut sha256 = Sha256::default();
+
+        for num in nums.iter() {
+            let index =
+                num.iter()
+                    .position(|&value| value != 0)
+                    .unwrap_or(num.len());
+
+            sha256.input(&num[index..]);
+        }
+
+        Ok(sha256.result().to_vec())
+    }
+
+    fn _rng() -> Result<OsRng, CommonError> {
+        OsRng::new()
+            .map_err(|err| CommonError::InvalidState(format!("Can't initialize random generator: {}", err)))
+    }
+
+    fn _from_str_radix(s: &str, radix: u32) -> Result<BigNumber, CommonError> {
+        let (sign, digits) = if s.starts_with('-') { (Sign::Minus, &s[1..]) } else { (Sign::Plus, s) };
+
+        let bn = BigInt::parse_bytes(digits.as_bytes(), radix)
+            .ok_or(CommonError::InvalidStructure(format!("Invalid number: {}", s)))?;
+
+        Ok(BigNumber {
+            bn: if sign == Sign::Minus { -bn } else { bn }
+        })
+    }
+
+    fn _nnmod(a: &BigInt, m: &BigInt) -> BigInt {
+        let r = a % m;
+        if r.is_negative() { r + m } else { r }
+    }
+
+    /// Uses num-bigint modpow (Montgomery multiplication for odd modulus).
+    /// It isn't constant time, so timing of operations with secret exponents
+    /// (master secret, blinding factors) may leak them.
+    fn _mod_exp(base: &BigInt, exp: &BigInt, modulus: &BigInt) -> BigInt {
+        if *modulus == BigInt::one() {
+            return BigInt::zero();
+        }
+
+        BigNumber::_nnmod(base, modulus).modpow(exp, modulus)
+    }
+
+    fn _rand_odd_with_top_bit(rng: &mut OsRng, size: usize) -> BigInt {
+        let mut candidate = rng.gen_biguint(size);
+        candidate = candidate | (BigUint::one() << (size - 1)) | BigUint::one();
+        candidate.to_bigint().unwrap()
+    }
+
+    fn _has_small_factor(n: &BigInt) -> bool {
+        SMALL_PRIMES.iter()
+            .map(|&p| BigInt::from(p))
+            .any(|p| n != &p && (n % &p).is_zero())
+    }
+
+    fn _prime_checks(n: &BigInt) -> usize {
+        // Same number of Miller-Rabin rounds as OpenSSL backend uses
+        let prime_len = n.to_str_radix(10).len();
+        (prime_len as f64).log2() as usize
+    }
+
+    fn _is_prime(n: &BigInt, rng: &mut OsRng, checks: usize) -> bool {
+        let two = BigInt::from(2u32);
+
+        if n < &two {
+            return false;
+        }
+
+        for &p in SMALL_PRIMES.iter() {
+            let p = BigInt::from(p);
+            if n == &p {
+                return true;
+            }
+            if (n % &p).is_zero() {
+                return false;
+            }
+        }
+
+        let n_minus_one = n - BigInt::one();
+        let mut d = n_minus_one.clone();
+        let mut s = 0;
+
+        while d.is_even() {
+            d = d >> 1;
+            s += 1;
+        }
+
+        'witness: for _ in 0..checks.max(1) {
+            let a = rng.gen_bigint_range(&two, &n_minus_one);
+            let mut x = BigNumber::_mod_exp(&a, &d, n);
+
+            if x == BigInt::one() || x == n_minus_one {
+                continue;
+            }
+
+            for _ in 1..s {
+                x = (&x * &x) % n;
+                if x == n_minus_one {
+                    continue 'witness;
+                }
+            }
+
+            return false;
+        }
+
+        true
+    }
+}
+
+impl Ord for BigNumber {
+    fn cmp(&self, other: &BigNumber) -> Ordering {
+        // OpenSSL backend compares absolute values
+        self.bn.abs().cmp(&other.bn.abs())
+    }
+}
+
+impl Eq for BigNumber {}
+
+impl PartialOrd for BigNumber {
+    fn partial_cmp(&self, other: &BigNumber) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for BigNumber {
+    fn eq(&self, other: &BigNumber) -> bool {
+        self.bn == other.bn
+    }
+}
+
+impl BytesView for BigNumber {
+    fn to_bytes(&self) -> Result<Vec<u8>, CommonError> {
+        Ok(self.to_bytes()?)
+    }
+}
+
+impl Serialize for BigNumber {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        serializer.serialize_newtype_struct("BigNumber", &self.to_dec().map_err(SError::custom)?)
+    }
+}
+
+impl<'a> Deserialize<'a> for BigNumber {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'a> {
+        struct BigNumberVisitor;
+
+        impl<'a> Visitor<'a> for BigNumberVisitor {
+            type Value = BigNumber;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("expected BigNumber")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<BigNumber, E>
+                where E: DError
+            {
+                Ok(BigNumber::from_dec(value).map_err(DError::custom)?)
+            }
+        }
+
+        deserializer.deserialize_str(BigNumberVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::logger::LoggerUtils;
+    use services::anoncreds::constants::{
+        LARGE_UTILDE,
+        LARGE_RTILDE,
+    };
+
+    extern crate serde_json;
+
+    #[test]
+    fn generate_prime_in_range_works() {
+        LoggerUtils::init();
+
+        let start = BigNumber::rand(LARGE_UTILDE).unwrap();
+        let end = BigNumber::rand(LARGE_RTILDE).unwrap();
+        let random_prime = BigNumber::generate_prime_in_range(&start, &end).unwrap();
+        assert!(start < random_prime);
+        assert!(end > random_prime);
+    }
+
+    #[test]
+    fn is_prime_works() {
+        let safe_prime = BigNumber::generate_safe_prime(LARGE_PRIME).unwrap();
+        assert!(safe_prime.is_prime(None).unwrap());
+        assert!(BigNumber::from_dec("23").unwrap().is_prime(None).unwrap());
+        assert!(!BigNumber::from_dec("561").unwrap().is_prime(None).unwrap());
+    }
+
+    #[test]
+    fn mod_exp_works() {
+        let base = BigNumber::from_dec("4").unwrap();
+        let exp = BigNumber::from_dec("13").unwrap();
+        let modulus = BigNumber::from_dec("497").unwrap();
+        assert_eq!("445", base.mod_exp(&exp, &modulus, None).unwrap().to_dec().unwrap());
+    }
+
+    #[test]
+    fn inverse_works() {
+        let a = BigNumber::from_dec("3").unwrap();
+        let n = BigNumber::from_dec("11").unwrap();
+        assert_eq!("4", a.inverse(&n, None).unwrap().to_dec().unwrap());
+    }
+
+    #[test]
+    fn inverse_works_for_not_coprime() {
+        let a = BigNumber::from_dec("4").unwrap();
+        let n = BigNumber::from_dec("8").unwrap();
+        assert!(a.inverse(&n, None).is_err());
+    }
+
+    #[test]
+    fn modulus_works_for_negative() {
+        let a = BigNumber::from_dec("-7").unwrap();
+        let n = BigNumber::from_dec("5").unwrap();
+        assert_eq!("3", a.modulus(&n, None).unwrap().to_dec().unwrap());
+    }
+
+    #[test]
+    fn hash_works() {
+        assert_eq!("E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855",
+                   BigNumber::from_bytes(&BigNumber::hash(&[]).unwrap()).unwrap().to_hex().unwrap());
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Test {
+        field: BigNumber
+    }
+
+    #[test]
+    fn serialize_works() {
+        let s = Test { field: BigNumber::from_dec("1").unwrap() };
+        let serialized = serde_json::to_string(&s);
+
+        assert!(serialized.is_ok());
+        assert_eq!("{\"field\":\"1\"}", serialized.unwrap());
+    }
+
+    #[test]
+    fn deserialize_works() {
+        let s = "{\"field\":\"1\"}";
+        let bn: Result<Test, _> = serde_json::from_str(&s);
+
+        assert!(bn.is_ok());
+        assert_eq!("1", bn.unwrap().field.to_dec().unwrap());
+    }
+}
@@ -0,0 +1,190 @@
+extern crate crypto;
+extern crate rand;
+
+use errors::common::CommonError;
+
+use self::crypto::curve25519::{curve25519, Fe};
+use self::crypto::digest::Digest;
+use self::crypto::ed25519;
+use self::crypto::salsa20::hsalsa20;
+use self::crypto::sha2::Sha512;
+use self::rand::{OsRng, Rng};
+use utils::crypto::xsalsa20::XSalsa20;
+
+pub struct ED25519 {}
+
+impl ED25519 {
+    pub fn encrypt(private_key: &[u8], public_key: &[u8], doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, CommonError> {
+        if nonce.len() != 24 {
+            return Err(CommonError::InvalidStructure(format!("Invalid nonce")))
+        }
+
+        let key = ED25519::_box_beforenm(private_key, public_key)?;
+        Ok(XSalsa20::new().encrypt(&key, nonce, doc))
+    }
+
+    pub fn decrypt(private_key: &[u8], public_key: &[u8], doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, CommonError> {
+        if nonce.len() != 24 {
+            return Err(CommonError::InvalidStructure(format!("Invalid nonce")))
+        }
+
+        let key = ED25519::_box_beforenm(private_key, public_key)?;
+        XSalsa20::new().decrypt(&key, nonce, doc)
+    }
+
+    pub fn gen_nonce() -> Vec<u8> {
+        ED25519::_random_bytes(24)
+    }
+
+    pub fn create_key_pair_for_signature(seed: Option<&[u8]>) -> Result<(Vec<u8>, Vec<u8>), CommonError> {
+        if seed.is_some() && seed.unwrap().len() != 32 {
+            return Err(CommonError::InvalidStructure(format!("Invalid seed")));
+        }
+
+        // Secret key is seed || public key as in libsodium
+        let (private_key, public_key) = ed25519::keypair(seed.unwrap_or(&ED25519::_random_bytes(32)[..]));
+
+        Ok((public_key[..].to_vec(), private_key[..].to_vec()))
+    }
+
+    pub fn sign(private_key: &[u8], doc: &[u8]) -> Result<Vec<u8>, CommonError> {
+        if private_key.len() != 64 {
+            return Err(CommonError::InvalidStructure(format!("Invalid sign key")));
+        }
+
+        Ok(ed25519::signature(doc, private_key)[..].to_vec())
+    }
+
+    pub fn verify(public_key: &[u8], doc: &[u8], sign: &[u8]) -> Result<bool, CommonError> {
+        if sign.len() != 64 {
+            return Err(CommonError::InvalidStructure(format!("Invalid signature")));
+        }
+
+        if public_key.len() != 32 {
+            return Err(CommonError::InvalidStructure(format!("Invalid verkey")));
+        }
+
+        Ok(ed25519::verify(doc, public_key, sign))
+    }
+
+    pub fn sk_to_curve25519(sk: &[u8]) -> Result<Vec<u8>, CommonError> {
+        if sk.len() != 64 {
+            return Err(CommonError::InvalidStructure(format!("Invalid signkey")));
+        }
+
+        let mut hash: [u8; 64] = [0; 64];
+        let mut sha512 = Sha512::new();
+        sha512.input(&sk[..32]);
+        sha512.result(&mut hash);
+
+        hash[0] &= 248;
+        hash[31] &= 127;
+        hash[31] |= 64;
+
+        Ok(hash[..32].to_vec())
+    }
+
+    pub fn vk_to_curve25519(pk: &[u8]) -> Result<Vec<u8>, CommonError> {
+        if pk.len() != 32 {
+            return Err(CommonError::InvalidStructure(format!("Invalid verkey")));
+        }
+
+        // Montgomery u = (1 + y) / (1 - y)
+        let mut one_bytes: [u8; 32] = [0; 32];
+        one_bytes[0] = 1;
+        let one = Fe::from_bytes(&one_bytes);
+        let y = Fe::from_bytes(pk);
+
+        let u = (one + y) * (one - y).invert();
+
+        Ok(u.to_bytes().to_vec())
+    }
+
+    fn _box_beforenm(private_key: &[u8], public_key: &[u8]) -> Result<[u8; 32], CommonError> {
+        if private_key.len() != 32 || public_key.len() != 32 {
+            return Err(CommonError::InvalidStructure(format!("Invalid key")));
+        }
+
+        let shared_secret = curve25519(private_key, public_key);
+
+        let mut key: [u8; 32] = [0; 32];
+        hsalsa20(&shared_secret, &[0u8; 16], &mut key);
+        Ok(key)
+    }
+
+    fn _random_bytes(len: usize) -> Vec<u8> {
+        let mut bytes = vec![0u8; len];
+        OsRng::new().unwrap().fill_bytes(&mut bytes);
+        bytes
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_decrypt_works() {
+        let text = ED25519::_random_bytes(16);
+        let nonce = ED25519::gen_nonce();
+        let seed = ED25519::_random_bytes(32);
+
+        let (alice_ver_key, alice_sign_key) = ED25519::create_key_pair_for_signature(Some(&seed)).unwrap();
+        let alice_pk = ED25519::vk_to_curve25519(&alice_ver_key).unwrap();
+        let alice_sk = ED25519::sk_to_curve25519(&alice_sign_key).unwrap();
+
+        let (bob_ver_key, bob_sign_key) = ED25519::create_key_pair_for_signature(Some(&seed)).unwrap();
+        let bob_pk = ED25519::vk_to_curve25519(&bob_ver_key).unwrap();
+        let bob_sk = ED25519::sk_to_curve25519(&bob_sign_key).unwrap();
+
+        let bob_encrypted_text = ED25519::encrypt(&bob_sk, &alice_pk, &text, &nonce).unwrap();
+        let bob_decrypt_result = ED25519::decrypt(&alice_sk, &bob_pk, &bob_encrypted_text, &nonce);
+        assert!(bob_decrypt_result.is_ok());
+        assert_eq!(text, bob_decrypt_result.unwrap());
+
+        let alice_encrypted_text = ED25519::encrypt(&alice_sk, &bob_pk, &text, &nonce).unwrap();
+        let alice_decrypted_text = ED25519::decrypt(&bob_sk, &alice_pk, &alice_encrypted_text, &nonce);
+        assert!(alice_decrypted_text.is_ok());
+        assert_eq!(text, alice_decrypted_text.unwrap());
+    }
+
+    #[test]
+    fn signin_verify_works() {
+        let seed = ED25519::_random_bytes(32);
+        let text = ED25519::_random_bytes(16);
+
+        let (public_key, secret_key) = ED25519::create_key_pair_for_signature(Some(&seed)).unwrap();
+        let alice_signed_text = ED25519::sign(&secret_key, &text).unwrap();
+        let verified = ED25519::verify(&public_key, &text, &alice_signed_text).unwrap();
+
+        assert!(verified);
+    }
+
+    #[test]
+    fn sign_works_for_rfc8032_vector() {
+        let seed = vec!(157, 97, 177, 157, 239, 253, 90, 96, 186, 132, 74, 244, 146, 236, 44, 196, 68, 73, 197, 105, 123, 50, 105, 25, 112, 59, 172, 3, 28, 174, 127, 96);
+        let expected_public_key = vec!(215, 90, 152, 1, 130, 177, 10, 183, 213, 75, 254, 211, 201, 100, 7, 58, 14, 225, 114, 243, 218, 166, 35, 37, 175, 2, 26, 104, 247, 7, 81, 26);
+        let expected_signature = vec!(229, 86, 67, 0, 195, 96, 172, 114, 144, 134, 226, 204, 128, 110, 130, 138, 132, 135, 127, 30, 184, 229, 217, 116, 216, 115, 224, 101, 34, 73, 1, 85, 95, 184, 130, 21, 144, 163, 59, 172, 198, 30, 57, 112, 28, 249, 180, 107, 210, 91, 245, 240, 89, 91, 190, 36, 101, 81, 65, 67, 142, 122, 16, 11);
+
+        let (public_key, secret_key) = ED25519::create_key_pair_for_signature(Some(&seed)).unwrap();
+        assert_eq!(expected_public_key, public_key);
+        assert_eq!(expected_signature, ED25519::sign(&secret_key, &[]).unwrap());
+    }
+
+    #[test]
+    fn pk_to_curve25519_works() {
+        let pk = vec!(236, 191, 114, 144, 108, 87, 211, 244, 148, 23, 20, 175, 122, 6, 159, 254, 85, 99, 145, 152, 178, 133, 230, 236, 192, 69, 35, 136, 141, 194, 243, 134);
+        let pkc_test = ED25519::vk_to_curve25519(&pk).unwrap();
+        let pkc_exp = vec!(8, 45, 124, 147, 248, 201, 112, 171, 11, 51, 29, 248, 34, 127, 197, 241, 60, 158, 84, 47, 4, 176, 238, 166, 110, 39, 207, 58, 127, 110, 76, 42);
+        assert_eq!(pkc_exp, pkc_test);
+    }
+
+    #[test]
+    fn sk_to_curve25519_works() {
+        let sk = vec!(78, 67, 205, 99, 150, 131, 75, 110, 56, 154, 76, 61, 27, 142, 36, 141, 44, 223, 122, 199, 14, 230, 12, 163, 4, 255, 94, 230, 21, 242, 97, 200, 236, 191, 114, 144, 108, 87, 211, 244, 148, 23, 20, 175, 122, 6, 159, 254, 85, 99, 145, 152, 178, 133, 230, 236, 192, 69, 35, 136, 141, 194, 243, 134);
+        let skc_test = ED25519::sk_to_curve25519(&sk).unwrap();
+        let skc_exp = vec!(144, 112, 64, 101, 69, 167, 61, 44, 220, 148, 58, 187, 108, 73, 11, 247, 130, 161, 158, 40, 100, 1, 40, 27, 76, 148, 209, 240, 195, 35, 153, 121);
+        assert_eq!(skc_exp, skc_test);
+    }
+}
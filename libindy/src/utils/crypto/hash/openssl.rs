@@ -2,6 +2,8 @@ extern crate openssl;
 
 use errors::common::CommonError;
 use self::openssl::hash::{hash2, MessageDigest, Hasher, DigestBytes};
+use self::openssl::pkey::PKey;
+use self::openssl::sign::Signer;
 
 pub const HASH_OUTPUT_LEN: usize = 32;
 
@@ -48,6 +50,12 @@ impl Hash {
         Ok(Digest::new(ctx.finish2()?))
     }
 
+    pub fn hmac(key: &[u8], data: &[u8]) -> Result<Vec<u8>, CommonError> {
+        let key = PKey::hmac(key)?;
+        let mut signer = Signer::new(MessageDigest::sha256(), &key)?;
+        signer.update(data)?;
+        Ok(signer.finish()?)
+    }
 }
 
 /// The type of values stored in a `MerkleTree` must implement
@@ -0,0 +1,141 @@
+extern crate sha2;
+
+use errors::common::CommonError;
+use self::sha2::{Digest as Sha2Digest, Sha256};
+
+use std::mem;
+
+pub const HASH_OUTPUT_LEN: usize = 32;
+const HASH_BLOCK_LEN: usize = 64;
+
+pub struct Digest {
+    data: Vec<u8>
+}
+
+impl Digest {
+    fn new(data: Vec<u8>) -> Digest {
+        Digest {
+            data: data
+        }
+    }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.data.clone()
+    }
+}
+
+impl AsRef<[u8]> for Digest {
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+/// Sha256 context with the same interface as OpenSSL Hasher used by OpenSSL backend.
+pub struct Hasher {
+    sha256: Sha256
+}
+
+impl Hasher {
+    pub fn update(&mut self, data: &[u8]) -> Result<(), CommonError> {
+        self.sha256.input(data);
+        Ok(())
+    }
+
+    /// Returns digest and resets context.
+    pub fn finish2(&mut self) -> Result<Digest, CommonError> {
+        let sha256 = mem::replace(&mut self.sha256, Sha256::default());
+        Ok(Digest::new(sha256.result().to_vec()))
+    }
+}
+
+pub struct Hash {}
+
+impl Hash {
+    pub fn new_context() -> Result<Hasher, CommonError> {
+        Ok(Hasher { sha256: Sha256::default() })
+    }
+
+    pub fn hash_empty() -> Result<Digest, CommonError> {
+        Hash::new_context()?.finish2()
+    }
+
+    pub fn hash_leaf<T>(leaf: &T) -> Result<Digest, CommonError> where T: Hashable {
+        let mut ctx = Hash::new_context()?;
+        ctx.update(&[0x00])?;
+        leaf.update_context(&mut ctx)?;
+        ctx.finish2()
+    }
+
+    pub fn hash_nodes<T>(left: &T, right: &T) -> Result<Digest, CommonError> where T: Hashable {
+        let mut ctx = Hash::new_context()?;
+        ctx.update(&[0x01])?;
+        left.update_context(&mut ctx)?;
+        right.update_context(&mut ctx)?;
+        ctx.finish2()
+    }
+
+    /// HMAC-SHA256 as defined by RFC 2104.
+    pub fn hmac(key: &[u8], data: &[u8]) -> Result<Vec<u8>, CommonError> {
+        let mut block_key = if key.len() > HASH_BLOCK_LEN {
+            let mut ctx = Hash::new_context()?;
+            ctx.update(key)?;
+            ctx.finish2()?.to_vec()
+        } else {
+            key.to_vec()
+        };
+        block_key.resize(HASH_BLOCK_LEN, 0);
+
+        let mut inner = Hash::new_context()?;
+        inner.update(&block_key.iter().map(|b| b ^ 0x36).collect::<Vec<u8>>())?;
+        inner.update(data)?;
+
+        let mut outer = Hash::new_context()?;
+        outer.update(&block_key.iter().map(|b| b ^ 0x5c).collect::<Vec<u8>>())?;
+        outer.update(inner.finish2()?.as_ref())?;
+        Ok(outer.finish2()?.to_vec())
+    }
+}
+
+/// The type of values stored in a `MerkleTree` must implement
+/// this trait, in order for them to be able to be fed
+/// to a `Hasher` when computing the hash of a leaf.
+///
+/// A default instance for types that already implements
+/// `AsRef<[u8]>` is provided.
+pub trait Hashable {
+
+    /// Update the given `context` with `self`.
+    fn update_context(&self, context: &mut Hasher) -> Result<(), CommonError>;
+
+}
+
+impl <T: AsRef<[u8]>> Hashable for T {
+
+    fn update_context(&self, context: &mut Hasher) -> Result<(), CommonError> {
+        context.update(self.as_ref())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_empty_works() {
+        assert_eq!(Hash::hash_empty().unwrap().to_vec(),
+                   vec![0xe3, 0xb0, 0xc4, 0x42, 0x98, 0xfc, 0x1c, 0x14, 0x9a, 0xfb, 0xf4, 0xc8, 0x99, 0x6f, 0xb9, 0x24,
+                        0x27, 0xae, 0x41, 0xe4, 0x64, 0x9b, 0x93, 0x4c, 0xa4, 0x95, 0x99, 0x1b, 0x78, 0x52, 0xb8, 0x55]);
+    }
+
+    #[test]
+    fn hmac_works() {
+        // RFC 4231 test cases 2 and 6
+        assert_eq!(Hash::hmac(b"Jefe", b"what do ya want for nothing?").unwrap(),
+                   vec![0x5b, 0xdc, 0xc1, 0x46, 0xbf, 0x60, 0x75, 0x4e, 0x6a, 0x04, 0x24, 0x26, 0x08, 0x95, 0x75, 0xc7,
+                        0x5a, 0x00, 0x3f, 0x08, 0x9d, 0x27, 0x39, 0x83, 0x9d, 0xec, 0x58, 0xb9, 0x64, 0xec, 0x38, 0x43]);
+        assert_eq!(Hash::hmac(&[0xaa; 131], b"Test Using Larger Than Block-Size Key - Hash Key First").unwrap(),
+                   vec![0x60, 0xe4, 0x31, 0x59, 0x1e, 0xe0, 0xb6, 0x7f, 0x0d, 0x8a, 0x26, 0xaa, 0xcb, 0xf5, 0xb7, 0x7f,
+                        0x8e, 0x0b, 0xc6, 0x21, 0x37, 0x28, 0xc5, 0x14, 0x05, 0x46, 0x04, 0x0f, 0x0e, 0xe3, 0x7f, 0x54]);
+    }
+}
@@ -0,0 +1,115 @@
+use errors::common::CommonError;
+use utils::crypto::hash::Hash;
+
+const WORDS: &'static str = include_str!("english.txt");
+const BITS_PER_WORD: usize = 11;
+
+/// BIP39 mnemonic encoding of entropy with english word list.
+pub struct Mnemonic {}
+
+impl Mnemonic {
+    pub fn encode(entropy: &[u8]) -> Result<String, CommonError> {
+        if entropy.len() % 4 != 0 || entropy.len() < 16 || entropy.len() > 32 {
+            return Err(CommonError::InvalidStructure(format!("Invalid mnemonic entropy length: {}", entropy.len())));
+        }
+
+        // Checksum is first entropy_bits / 32 bits of sha256 of entropy, so it fits into one byte
+        let mut data = entropy.to_vec();
+        data.push(Mnemonic::_checksum(entropy)?);
+
+        let words: Vec<&str> = WORDS.lines().collect();
+        let words_count = (entropy.len() * 8 + entropy.len() / 4) / BITS_PER_WORD;
+
+        let mnemonic: Vec<&str> = (0..words_count)
+            .map(|i| words[Mnemonic::_bits(&data, i * BITS_PER_WORD, BITS_PER_WORD)])
+            .collect();
+        Ok(mnemonic.join(" "))
+    }
+
+    pub fn decode(mnemonic: &str) -> Result<Vec<u8>, CommonError> {
+        let words: Vec<&str> = WORDS.lines().collect();
+
+        let indexes = mnemonic.split_whitespace()
+            .map(|word| words.iter().position(|w| *w == word)
+                .ok_or(CommonError::InvalidStructure(format!("Unknown mnemonic word: {}", word))))
+            .collect::<Result<Vec<usize>, CommonError>>()?;
+
+        if indexes.len() % 3 != 0 || indexes.len() < 12 || indexes.len() > 24 {
+            return Err(CommonError::InvalidStructure(format!("Invalid mnemonic words count: {}", indexes.len())));
+        }
+
+        let bits_count = indexes.len() * BITS_PER_WORD;
+        let checksum_len = bits_count / 33;
+        let entropy_len = (bits_count - checksum_len) / 8;
+
+        let mut data = vec![0u8; (bits_count + 7) / 8];
+        for (i, index) in indexes.iter().enumerate() {
+            for j in 0..BITS_PER_WORD {
+                if (index >> (BITS_PER_WORD - 1 - j)) & 1 == 1 {
+                    let bit = i * BITS_PER_WORD + j;
+                    data[bit / 8] |= 1 << (7 - bit % 8);
+                }
+            }
+        }
+
+        let entropy = data[..entropy_len].to_vec();
+        let checksum = Mnemonic::_bits(&data, entropy_len * 8, checksum_len);
+
+        if checksum != (Mnemonic::_checksum(&entropy)? >> (8 - checksum_len)) as usize {
+            return Err(CommonError::InvalidStructure("Invalid mnemonic checksum".to_string()));
+        }
+
+        Ok(entropy)
+    }
+
+    fn _checksum(entropy: &[u8]) -> Result<u8, CommonError> {
+        let mut ctx = Hash::new_context()?;
+        ctx.update(entropy)?;
+        Ok(ctx.finish2()?.to_vec()[0])
+    }
+
+    // Big-endian value of len bits of data starting from bit start
+    fn _bits(data: &[u8], start: usize, len: usize) -> usize {
+        (start..start + len)
+            .fold(0, |value, bit| (value << 1) | ((data[bit / 8] >> (7 - bit % 8)) & 1) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // BIP39 reference test vectors
+    const ZERO_MNEMONIC: &'static str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art";
+    const LEGAL_MNEMONIC: &'static str = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+
+    #[test]
+    fn encode_works() {
+        assert_eq!(ZERO_MNEMONIC, Mnemonic::encode(&[0; 32]).unwrap());
+        assert_eq!(LEGAL_MNEMONIC, Mnemonic::encode(&[0x7f; 16]).unwrap());
+    }
+
+    #[test]
+    fn encode_works_for_invalid_length() {
+        let res = Mnemonic::encode(&[0; 31]);
+        assert_match!(Err(CommonError::InvalidStructure(_)), res);
+    }
+
+    #[test]
+    fn decode_works() {
+        assert_eq!(vec![0; 32], Mnemonic::decode(ZERO_MNEMONIC).unwrap());
+        assert_eq!(vec![0x7f; 16], Mnemonic::decode(LEGAL_MNEMONIC).unwrap());
+    }
+
+    #[test]
+    fn decode_works_for_invalid_checksum() {
+        let res = Mnemonic::decode(&ZERO_MNEMONIC.replace(" art", " zoo"));
+        assert_match!(Err(CommonError::InvalidStructure(_)), res);
+    }
+
+    #[test]
+    fn decode_works_for_unknown_word() {
+        let res = Mnemonic::decode(&ZERO_MNEMONIC.replace(" art", " indy"));
+        assert_match!(Err(CommonError::InvalidStructure(_)), res);
+    }
+}
@@ -2,14 +2,26 @@
 #[path = "bn/openssl.rs"]
 pub mod bn;
 
+#[cfg(all(feature = "bn_rust", not(feature = "bn_openssl")))]
+#[path = "bn/rust.rs"]
+pub mod bn;
+
 #[cfg(feature = "ed25519_sodium")]
 #[path = "ed25519/sodium.rs"]
 pub mod ed25519;
 
+#[cfg(all(feature = "ed25519_rust_crypto", not(feature = "ed25519_sodium")))]
+#[path = "ed25519/rust_crypto.rs"]
+pub mod ed25519;
+
 #[cfg(feature = "base58_rust_base58")]
 #[path = "base58/rust_base58.rs"]
 pub mod base58;
 
+#[cfg(feature = "base64_rust_base64")]
+#[path = "base64/rust_base64.rs"]
+pub mod base64;
+
 #[cfg(feature = "pair_amcl")]
 #[path = "pair/amcl.rs"]
 pub mod pair;
@@ -18,10 +30,168 @@ pub mod pair;
 #[path = "xsalsa20/sodium.rs"]
 pub mod xsalsa20;
 
+#[cfg(all(feature = "xsalsa20_rust_crypto", not(feature = "xsalsa20_sodium")))]
+#[path = "xsalsa20/rust_crypto.rs"]
+pub mod xsalsa20;
+
 #[cfg(feature = "hash_openssl")]
 #[path = "hash/openssl.rs"]
 pub mod hash;
 
+#[cfg(all(feature = "hash_rust", not(feature = "hash_openssl")))]
+#[path = "hash/rust.rs"]
+pub mod hash;
+
 pub mod signature_serializer;
 
-pub mod verkey_builder;
\ No newline at end of file
+pub mod verkey_builder;
+
+pub mod mnemonic;
+
+// Both backends are built together only to check that they are interoperable
+#[cfg(all(test, feature = "ed25519_sodium", feature = "ed25519_rust_crypto"))]
+#[path = "ed25519/rust_crypto.rs"]
+mod ed25519_rust_crypto;
+
+#[cfg(all(test, feature = "xsalsa20_sodium", feature = "xsalsa20_rust_crypto"))]
+#[path = "xsalsa20/rust_crypto.rs"]
+mod xsalsa20_rust_crypto;
+
+#[cfg(all(test, feature = "bn_openssl", feature = "bn_rust"))]
+#[path = "bn/rust.rs"]
+mod bn_rust;
+
+#[cfg(all(test, feature = "hash_openssl", feature = "hash_rust"))]
+#[path = "hash/rust.rs"]
+mod hash_rust;
+
+#[cfg(all(test, feature = "bn_openssl", feature = "bn_rust", feature = "hash_openssl", feature = "hash_rust"))]
+mod bn_and_hash_interoperability_tests {
+    use super::bn::BigNumber as OpenSslBigNumber;
+    use super::bn_rust::BigNumber as RustBigNumber;
+    use super::hash::Hash as OpenSslHash;
+    use super::hash_rust::Hash as RustHash;
+
+    const BASE: &'static str = "123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890";
+    const EXP: &'static str = "987654321098765432109876543210987654321098765432109876543210";
+    const ODD_MODULUS: &'static str = "298425477551432359319017298068281828134535746771300905126443720735756534287270383542467183175737460443806952398210045827718115111810885752229119677470711305345901926067944629292942471551423868488963517954094239606951758940767987427212463600313901180668176172283994206392965011112962119159458674722785709556623";
+    const EVEN_MODULUS: &'static str = "298425477551432359319017298068281828134535746771300905126443720735756534287270383542467183175737460443806952398210045827718115111810885752229119677470711305345901926067944629292942471551423868488963517954094239606951758940767987427212463600313901180668176172283994206392965011112962119159458674722785709556624";
+
+    fn _openssl_mod_exp(modulus: &str) -> String {
+        OpenSslBigNumber::from_dec(BASE).unwrap()
+            .mod_exp(&OpenSslBigNumber::from_dec(EXP).unwrap(), &OpenSslBigNumber::from_dec(modulus).unwrap(), None).unwrap()
+            .to_dec().unwrap()
+    }
+
+    fn _rust_mod_exp(modulus: &str) -> String {
+        RustBigNumber::from_dec(BASE).unwrap()
+            .mod_exp(&RustBigNumber::from_dec(EXP).unwrap(), &RustBigNumber::from_dec(modulus).unwrap(), None).unwrap()
+            .to_dec().unwrap()
+    }
+
+    #[test]
+    fn bn_mod_exp_results_are_same() {
+        assert_eq!(_openssl_mod_exp(ODD_MODULUS), _rust_mod_exp(ODD_MODULUS));
+        assert_eq!(_openssl_mod_exp(EVEN_MODULUS), _rust_mod_exp(EVEN_MODULUS));
+    }
+
+    #[test]
+    fn bn_inverse_results_are_same() {
+        let openssl_inverse = OpenSslBigNumber::from_dec(BASE).unwrap()
+            .inverse(&OpenSslBigNumber::from_dec(ODD_MODULUS).unwrap(), None).unwrap();
+        let rust_inverse = RustBigNumber::from_dec(BASE).unwrap()
+            .inverse(&RustBigNumber::from_dec(ODD_MODULUS).unwrap(), None).unwrap();
+        assert_eq!(openssl_inverse.to_dec().unwrap(), rust_inverse.to_dec().unwrap());
+    }
+
+    #[test]
+    fn bn_bytes_and_hashes_are_same() {
+        let openssl_bytes = OpenSslBigNumber::from_dec(ODD_MODULUS).unwrap().to_bytes().unwrap();
+        let rust_bytes = RustBigNumber::from_dec(ODD_MODULUS).unwrap().to_bytes().unwrap();
+        assert_eq!(openssl_bytes, rust_bytes);
+
+        let nums = vec![openssl_bytes, vec![1, 2, 3]];
+        assert_eq!(OpenSslBigNumber::hash_array(&nums).unwrap(), RustBigNumber::hash_array(&nums).unwrap());
+        assert_eq!(OpenSslBigNumber::hash(&nums[0]).unwrap(), RustBigNumber::hash(&nums[0]).unwrap());
+    }
+
+    #[test]
+    fn hashes_are_same() {
+        assert_eq!(OpenSslHash::hash_empty().unwrap().to_vec(), RustHash::hash_empty().unwrap().to_vec());
+        assert_eq!(OpenSslHash::hash_leaf(&"leaf").unwrap().to_vec(), RustHash::hash_leaf(&"leaf").unwrap().to_vec());
+        assert_eq!(OpenSslHash::hash_nodes(&"left", &"right").unwrap().to_vec(),
+                   RustHash::hash_nodes(&"left", &"right").unwrap().to_vec());
+        assert_eq!(OpenSslHash::hmac(b"key", b"data").unwrap(), RustHash::hmac(b"key", b"data").unwrap());
+    }
+}
+
+#[cfg(all(test, feature = "ed25519_sodium", feature = "ed25519_rust_crypto",
+feature = "xsalsa20_sodium", feature = "xsalsa20_rust_crypto"))]
+mod interoperability_tests {
+    use super::ed25519::ED25519 as SodiumED25519;
+    use super::ed25519_rust_crypto::ED25519 as RustED25519;
+    use super::xsalsa20::XSalsa20 as SodiumXSalsa20;
+    use super::xsalsa20_rust_crypto::XSalsa20 as RustXSalsa20;
+
+    const SEED: &'static [u8; 32] = b"00000000000000000000000000000My1";
+    const TEXT: &'static [u8] = b"Some text to check interoperability";
+
+    #[test]
+    fn ed25519_keys_from_seed_are_same() {
+        let sodium_keys = SodiumED25519::create_key_pair_for_signature(Some(SEED)).unwrap();
+        let rust_keys = RustED25519::create_key_pair_for_signature(Some(SEED)).unwrap();
+        assert_eq!(sodium_keys, rust_keys);
+
+        let (vk, sk) = sodium_keys;
+        assert_eq!(SodiumED25519::vk_to_curve25519(&vk).unwrap(), RustED25519::vk_to_curve25519(&vk).unwrap());
+        assert_eq!(SodiumED25519::sk_to_curve25519(&sk).unwrap(), RustED25519::sk_to_curve25519(&sk).unwrap());
+    }
+
+    #[test]
+    fn ed25519_signatures_are_same() {
+        let (vk, sk) = SodiumED25519::create_key_pair_for_signature(Some(SEED)).unwrap();
+
+        let sodium_signature = SodiumED25519::sign(&sk, TEXT).unwrap();
+        let rust_signature = RustED25519::sign(&sk, TEXT).unwrap();
+        assert_eq!(sodium_signature, rust_signature);
+
+        assert!(RustED25519::verify(&vk, TEXT, &sodium_signature).unwrap());
+        assert!(SodiumED25519::verify(&vk, TEXT, &rust_signature).unwrap());
+    }
+
+    #[test]
+    fn ed25519_boxes_are_same() {
+        let (alice_vk, alice_sk) = SodiumED25519::create_key_pair_for_signature(Some(SEED)).unwrap();
+        let (bob_vk, bob_sk) = SodiumED25519::create_key_pair_for_signature(None).unwrap();
+
+        let alice_sk = SodiumED25519::sk_to_curve25519(&alice_sk).unwrap();
+        let alice_pk = SodiumED25519::vk_to_curve25519(&alice_vk).unwrap();
+        let bob_sk = SodiumED25519::sk_to_curve25519(&bob_sk).unwrap();
+        let bob_pk = SodiumED25519::vk_to_curve25519(&bob_vk).unwrap();
+
+        let nonce = SodiumED25519::gen_nonce();
+
+        let sodium_box = SodiumED25519::encrypt(&alice_sk, &bob_pk, TEXT, &nonce).unwrap();
+        let rust_box = RustED25519::encrypt(&alice_sk, &bob_pk, TEXT, &nonce).unwrap();
+        assert_eq!(sodium_box, rust_box);
+
+        assert_eq!(TEXT, &RustED25519::decrypt(&bob_sk, &alice_pk, &sodium_box, &nonce).unwrap()[..]);
+        assert_eq!(TEXT, &SodiumED25519::decrypt(&bob_sk, &alice_pk, &rust_box, &nonce).unwrap()[..]);
+    }
+
+    #[test]
+    fn xsalsa20_secret_boxes_are_same() {
+        let sodium_xsalsa20 = SodiumXSalsa20::new();
+        let rust_xsalsa20 = RustXSalsa20::new();
+
+        let key = sodium_xsalsa20.create_key();
+        let nonce = sodium_xsalsa20.gen_nonce();
+
+        let sodium_box = sodium_xsalsa20.encrypt(&key, &nonce, TEXT);
+        let rust_box = rust_xsalsa20.encrypt(&key, &nonce, TEXT);
+        assert_eq!(sodium_box, rust_box);
+
+        assert_eq!(TEXT, &rust_xsalsa20.decrypt(&key, &nonce, &sodium_box).unwrap()[..]);
+        assert_eq!(TEXT, &sodium_xsalsa20.decrypt(&key, &nonce, &rust_box).unwrap()[..]);
+    }
+}
@@ -2,11 +2,13 @@ extern crate rust_base58;
 
 use self::rust_base58::base58::{FromBase58, FromBase58Error};
 
+use utils::did::DidUtils;
+
 pub fn build_full_verkey(dest: &String, verkey: &Option<String>)
                          -> Result<Vec<u8>, FromBase58Error> {
     if let &Some(ref verkey) = verkey {
         if verkey.starts_with("~") {
-            let mut result = dest.from_base58()?;
+            let mut result = DidUtils::unqualify(dest).from_base58()?;
             let mut end = verkey[1..].from_base58()?;
             result.append(&mut end);
             return Ok(result);
@@ -14,5 +16,5 @@ pub fn build_full_verkey(dest: &String, verkey: &Option<String>)
             return verkey.from_base58();
         }
     }
-    dest.from_base58()
+    DidUtils::unqualify(dest).from_base58()
 }
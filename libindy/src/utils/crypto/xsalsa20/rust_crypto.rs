@@ -0,0 +1,116 @@
+extern crate crypto;
+extern crate rand;
+
+use errors::common::CommonError;
+
+use self::crypto::mac::Mac;
+use self::crypto::poly1305::Poly1305;
+use self::crypto::salsa20::Salsa20;
+use self::crypto::symmetriccipher::SynchronousStreamCipher;
+use self::crypto::util::fixed_time_eq;
+use self::rand::{OsRng, Rng};
+
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 24;
+const MAC_LEN: usize = 16;
+
+pub struct XSalsa20 {}
+
+impl XSalsa20 {
+    pub fn new() -> XSalsa20 {
+        XSalsa20 {}
+    }
+
+    pub fn create_key(&self) -> Vec<u8> {
+        XSalsa20::_random_bytes(KEY_LEN)
+    }
+
+    pub fn gen_nonce(&self) -> Vec<u8> {
+        XSalsa20::_random_bytes(NONCE_LEN)
+    }
+
+    /// Same layout as libsodium crypto_secretbox_easy: mac || ciphertext
+    pub fn encrypt(&self, key: &[u8], nonce: &[u8], doc: &[u8]) -> Vec<u8> {
+        let (mut cipher, mut mac) = XSalsa20::_init(key, nonce);
+
+        let mut encrypted = vec![0u8; MAC_LEN + doc.len()];
+        cipher.process(doc, &mut encrypted[MAC_LEN..]);
+
+        mac.input(&encrypted[MAC_LEN..]);
+        mac.raw_result(&mut encrypted[..MAC_LEN]);
+
+        encrypted
+    }
+
+    pub fn decrypt(&self, key: &[u8], nonce: &[u8], doc: &[u8]) -> Result<Vec<u8>, CommonError> {
+        if doc.len() < MAC_LEN {
+            return Err(CommonError::InvalidStructure("Unable to decrypt data".to_string()));
+        }
+
+        let (mut cipher, mut mac) = XSalsa20::_init(key, nonce);
+
+        let mut expected_mac = [0u8; MAC_LEN];
+        mac.input(&doc[MAC_LEN..]);
+        mac.raw_result(&mut expected_mac);
+
+        if !fixed_time_eq(&expected_mac, &doc[..MAC_LEN]) {
+            return Err(CommonError::InvalidStructure("Unable to decrypt data".to_string()));
+        }
+
+        let mut decrypted = vec![0u8; doc.len() - MAC_LEN];
+        cipher.process(&doc[MAC_LEN..], &mut decrypted);
+
+        Ok(decrypted)
+    }
+
+    fn _init(key: &[u8], nonce: &[u8]) -> (Salsa20, Poly1305) {
+        let mut cipher = Salsa20::new_xsalsa20(key, nonce);
+
+        // First 32 bytes of key stream are used as one-time poly1305 key
+        let mut mac_key = [0u8; 32];
+        cipher.process(&[0u8; 32], &mut mac_key);
+
+        (cipher, Poly1305::new(&mac_key))
+    }
+
+    fn _random_bytes(len: usize) -> Vec<u8> {
+        let mut bytes = vec![0u8; len];
+        OsRng::new().unwrap().fill_bytes(&mut bytes);
+        bytes
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_decrypt_works() {
+        let xsalsa20 = XSalsa20::new();
+
+        let nonce = xsalsa20.gen_nonce();
+        let key = xsalsa20.create_key();
+        let data = XSalsa20::_random_bytes(16);
+
+        let encrypted_data = xsalsa20.encrypt(&key, &nonce, &data);
+        let decrypt_result = xsalsa20.decrypt(&key, &nonce, &encrypted_data);
+
+        assert!(decrypt_result.is_ok());
+        assert_eq!(data, decrypt_result.unwrap());
+    }
+
+    #[test]
+    fn decrypt_works_for_modified_data() {
+        let xsalsa20 = XSalsa20::new();
+
+        let nonce = xsalsa20.gen_nonce();
+        let key = xsalsa20.create_key();
+        let data = XSalsa20::_random_bytes(16);
+
+        let mut encrypted_data = xsalsa20.encrypt(&key, &nonce, &data);
+        encrypted_data[MAC_LEN] ^= 1;
+
+        assert!(xsalsa20.decrypt(&key, &nonce, &encrypted_data).is_err());
+    }
+}
@@ -0,0 +1,110 @@
+use errors::common::CommonError;
+
+// Method-qualified DID has form did:<method>:<method specific id>
+const DID_PREFIX: &'static str = "did:";
+
+// Bare DIDs are DIDs of the ledger method
+pub const LEDGER_METHOD_NAME: &'static str = "sov";
+
+pub struct DidUtils {}
+
+impl DidUtils {
+    pub fn qualify(did: &str, method_name: &str) -> String {
+        if DidUtils::is_qualified(did) {
+            return did.to_string();
+        }
+        format!("{}{}:{}", DID_PREFIX, method_name, did)
+    }
+
+    pub fn unqualify(did: &str) -> &str {
+        match DidUtils::_split(did) {
+            Some((_, id)) => id,
+            None => did
+        }
+    }
+
+    // Form used in wallet keys and DID comparison: ledger method DIDs are stored bare,
+    // DIDs of other methods keep their prefix so they can't clash with ledger DIDs
+    pub fn normalize(did: &str) -> &str {
+        match DidUtils::_split(did) {
+            Some((LEDGER_METHOD_NAME, id)) => id,
+            _ => did
+        }
+    }
+
+    pub fn method(did: &str) -> Option<&str> {
+        DidUtils::_split(did).map(|(method_name, _)| method_name)
+    }
+
+    pub fn is_qualified(did: &str) -> bool {
+        DidUtils::_split(did).is_some()
+    }
+
+    pub fn validate_method(method_name: &str) -> Result<(), CommonError> {
+        let is_method_char = |c: char| match c {
+            'a'...'z' | '0'...'9' => true,
+            _ => false
+        };
+
+        if method_name.is_empty() || !method_name.chars().all(is_method_char) {
+            return Err(CommonError::InvalidStructure(format!("Invalid DID method name: {}", method_name)));
+        }
+        Ok(())
+    }
+
+    fn _split(did: &str) -> Option<(&str, &str)> {
+        if !did.starts_with(DID_PREFIX) {
+            return None;
+        }
+
+        let mut parts = did[DID_PREFIX.len()..].splitn(2, ':');
+
+        match (parts.next(), parts.next()) {
+            (Some(method_name), Some(id)) if !method_name.is_empty() && !id.is_empty() => Some((method_name, id)),
+            _ => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn qualify_works() {
+        assert_eq!("did:sov:NcYxiDXkpYi6ov5FcYDi1e", DidUtils::qualify("NcYxiDXkpYi6ov5FcYDi1e", "sov"));
+    }
+
+    #[test]
+    fn qualify_works_for_qualified_did() {
+        assert_eq!("did:sov:NcYxiDXkpYi6ov5FcYDi1e", DidUtils::qualify("did:sov:NcYxiDXkpYi6ov5FcYDi1e", "peer"));
+    }
+
+    #[test]
+    fn unqualify_works() {
+        assert_eq!("NcYxiDXkpYi6ov5FcYDi1e", DidUtils::unqualify("did:sov:NcYxiDXkpYi6ov5FcYDi1e"));
+        assert_eq!("NcYxiDXkpYi6ov5FcYDi1e", DidUtils::unqualify("NcYxiDXkpYi6ov5FcYDi1e"));
+    }
+
+    #[test]
+    fn normalize_works() {
+        assert_eq!("NcYxiDXkpYi6ov5FcYDi1e", DidUtils::normalize("did:sov:NcYxiDXkpYi6ov5FcYDi1e"));
+        assert_eq!("NcYxiDXkpYi6ov5FcYDi1e", DidUtils::normalize("NcYxiDXkpYi6ov5FcYDi1e"));
+        assert_eq!("did:peer:NcYxiDXkpYi6ov5FcYDi1e", DidUtils::normalize("did:peer:NcYxiDXkpYi6ov5FcYDi1e"));
+    }
+
+    #[test]
+    fn method_works() {
+        assert_eq!(Some("sov"), DidUtils::method("did:sov:NcYxiDXkpYi6ov5FcYDi1e"));
+        assert_eq!(None, DidUtils::method("NcYxiDXkpYi6ov5FcYDi1e"));
+        assert_eq!(None, DidUtils::method("did::NcYxiDXkpYi6ov5FcYDi1e"));
+    }
+
+    #[test]
+    fn validate_method_works() {
+        assert!(DidUtils::validate_method("sov").is_ok());
+        assert!(DidUtils::validate_method("").is_err());
+        assert!(DidUtils::validate_method("Sov").is_err());
+        assert!(DidUtils::validate_method("s:v").is_err());
+    }
+}
@@ -0,0 +1,113 @@
+extern crate rand;
+
+use api::ErrorCode;
+
+use self::rand::{OsRng, Rng};
+
+use std::slice;
+
+const KEY_LEN: usize = 32;
+
+// Plugged crypto type used to check custom crypto types registration.
+// Verkey and signkey are the same bytes, so it must never be used for real data.
+pub struct DummyCrypto {}
+
+impl DummyCrypto {
+    pub extern "C" fn create_key(seed_raw: *const u8, seed_len: u32,
+                                 verkey_raw: *mut *const u8, verkey_len: *mut u32,
+                                 signkey_raw: *mut *const u8, signkey_len: *mut u32) -> ErrorCode {
+        let key = if seed_raw.is_null() {
+            let mut key = vec![0u8; KEY_LEN];
+            OsRng::new().unwrap().fill_bytes(&mut key);
+            key
+        } else {
+            unsafe { slice::from_raw_parts(seed_raw, seed_len as usize).to_vec() }
+        };
+
+        if key.is_empty() {
+            return ErrorCode::CommonInvalidStructure;
+        }
+
+        unsafe {
+            DummyCrypto::_put_data(key.clone(), verkey_raw, verkey_len);
+            DummyCrypto::_put_data(key, signkey_raw, signkey_len);
+        }
+        ErrorCode::Success
+    }
+
+    pub extern "C" fn sign(signkey_raw: *const u8, signkey_len: u32,
+                           message_raw: *const u8, message_len: u32,
+                           signature_raw: *mut *const u8, signature_len: *mut u32) -> ErrorCode {
+        let signkey = unsafe { slice::from_raw_parts(signkey_raw, signkey_len as usize) };
+        let message = unsafe { slice::from_raw_parts(message_raw, message_len as usize) };
+
+        unsafe { DummyCrypto::_put_data(DummyCrypto::_signature(signkey, message), signature_raw, signature_len); }
+        ErrorCode::Success
+    }
+
+    pub extern "C" fn verify(verkey_raw: *const u8, verkey_len: u32,
+                             message_raw: *const u8, message_len: u32,
+                             signature_raw: *const u8, signature_len: u32,
+                             valid: *mut u32) -> ErrorCode {
+        let verkey = unsafe { slice::from_raw_parts(verkey_raw, verkey_len as usize) };
+        let message = unsafe { slice::from_raw_parts(message_raw, message_len as usize) };
+        let signature = unsafe { slice::from_raw_parts(signature_raw, signature_len as usize) };
+
+        unsafe { *valid = (DummyCrypto::_signature(verkey, message) == signature) as u32 };
+        ErrorCode::Success
+    }
+
+    pub extern "C" fn encrypt(_: *const u8, _: u32,
+                              verkey_raw: *const u8, verkey_len: u32,
+                              nonce_raw: *const u8, nonce_len: u32,
+                              message_raw: *const u8, message_len: u32,
+                              encrypted_msg_raw: *mut *const u8, encrypted_msg_len: *mut u32) -> ErrorCode {
+        let verkey = unsafe { slice::from_raw_parts(verkey_raw, verkey_len as usize) };
+        let nonce = unsafe { slice::from_raw_parts(nonce_raw, nonce_len as usize) };
+        let message = unsafe { slice::from_raw_parts(message_raw, message_len as usize) };
+
+        unsafe { DummyCrypto::_put_data(DummyCrypto::_xor(verkey, nonce, message), encrypted_msg_raw, encrypted_msg_len); }
+        ErrorCode::Success
+    }
+
+    pub extern "C" fn decrypt(signkey_raw: *const u8, signkey_len: u32,
+                              _: *const u8, _: u32,
+                              nonce_raw: *const u8, nonce_len: u32,
+                              encrypted_msg_raw: *const u8, encrypted_msg_len: u32,
+                              decrypted_msg_raw: *mut *const u8, decrypted_msg_len: *mut u32) -> ErrorCode {
+        let signkey = unsafe { slice::from_raw_parts(signkey_raw, signkey_len as usize) };
+        let nonce = unsafe { slice::from_raw_parts(nonce_raw, nonce_len as usize) };
+        let encrypted_msg = unsafe { slice::from_raw_parts(encrypted_msg_raw, encrypted_msg_len as usize) };
+
+        unsafe { DummyCrypto::_put_data(DummyCrypto::_xor(signkey, nonce, encrypted_msg), decrypted_msg_raw, decrypted_msg_len); }
+        ErrorCode::Success
+    }
+
+    pub extern "C" fn free(data_raw: *const u8, data_len: u32) -> ErrorCode {
+        unsafe { Box::from_raw(slice::from_raw_parts_mut(data_raw as *mut u8, data_len as usize)); }
+        ErrorCode::Success
+    }
+
+    fn _signature(key: &[u8], message: &[u8]) -> Vec<u8> {
+        let mut signature: Vec<u8> = (0..KEY_LEN).map(|i| key[i % key.len()]).collect();
+
+        for (i, byte) in message.iter().enumerate() {
+            signature[i % KEY_LEN] ^= *byte;
+        }
+
+        signature
+    }
+
+    fn _xor(key: &[u8], nonce: &[u8], data: &[u8]) -> Vec<u8> {
+        data.iter()
+            .enumerate()
+            .map(|(i, byte)| byte ^ key[i % key.len()] ^ nonce[i % nonce.len()])
+            .collect()
+    }
+
+    unsafe fn _put_data(data: Vec<u8>, data_raw: *mut *const u8, data_len: *mut u32) {
+        let data = data.into_boxed_slice();
+        *data_len = data.len() as u32;
+        *data_raw = Box::into_raw(data) as *const u8;
+    }
+}
@@ -12,11 +12,16 @@ pub mod ccallback;
 pub mod byte_array;
 
 pub mod crypto;
+
+pub mod did;
 #[macro_use]
 pub mod logger;
 
 pub mod inmem_wallet;
 
+#[cfg(test)]
+pub mod dummy_crypto;
+
 #[macro_use]
 pub mod result;
 
@@ -26,3 +31,5 @@ pub mod sequence;
 pub mod test;
 
 pub mod timeout;
+
+pub mod workers;
@@ -0,0 +1,207 @@
+use errors::common::CommonError;
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+
+pub const DEFAULT_WORKERS_COUNT: usize = 4;
+
+trait Job: Send {
+    fn run(self: Box<Self>);
+}
+
+impl<F: FnOnce() + Send> Job for F {
+    fn run(self: Box<Self>) {
+        (*self)()
+    }
+}
+
+/// Fixed size pool of threads for CPU-heavy jobs.
+/// Jobs must not touch services owned by CommandExecutor thread. If a result
+/// must be stored in wallet it is returned back to CommandExecutor as *Ack command.
+/// A panicking job is logged and does not take its worker down.
+pub struct WorkerPool {
+    sender: Option<Sender<Box<Job>>>,
+    workers: Vec<thread::JoinHandle<()>>
+}
+
+impl WorkerPool {
+    pub fn new(workers_count: usize) -> WorkerPool {
+        let (sender, receiver) = channel::<Box<Job>>();
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let workers = (0..workers_count)
+            .map(|id| WorkerPool::_spawn_worker(id, receiver.clone()))
+            .collect();
+
+        WorkerPool {
+            sender: Some(sender),
+            workers: workers
+        }
+    }
+
+    pub fn execute<F>(&self, job: F) -> Result<(), CommonError> where F: FnOnce() + Send + 'static {
+        match self.sender {
+            Some(ref sender) => sender.send(Box::new(job))
+                .map_err(|_| CommonError::InvalidState("Worker pool is stopped".to_string())),
+            None => Err(CommonError::InvalidState("Worker pool is stopped".to_string()))
+        }
+    }
+
+    /// Runs job on worker thread and passes its result to cb on the same thread.
+    /// If job can't be scheduled cb is called immediately with the scheduling error,
+    /// so caller always gets exactly one callback.
+    pub fn execute_with_cb<T, J, C>(&self, job: J, cb: C)
+        where J: FnOnce() -> T + Send + 'static, C: FnOnce(Result<T, CommonError>) + Send + 'static {
+        let cb = Arc::new(Mutex::new(Some(cb)));
+        let job_cb = cb.clone();
+
+        let res = self.execute(move || {
+            let result = catch_panic(job);
+
+            if let Some(cb) = job_cb.lock().ok().and_then(|mut cb| cb.take()) {
+                cb(result)
+            }
+        });
+
+        // Rejected job is already dropped here, so callback is taken only once
+        if let Err(err) = res {
+            if let Some(cb) = cb.lock().ok().and_then(|mut cb| cb.take()) {
+                cb(Err(err))
+            }
+        }
+    }
+
+    fn _spawn_worker(id: usize, receiver: Arc<Mutex<Receiver<Box<Job>>>>) -> thread::JoinHandle<()> {
+        thread::spawn(move || {
+            info!(target: "worker_pool", "Worker {} started", id);
+
+            loop {
+                let job = match receiver.lock() {
+                    Ok(receiver) => receiver.recv(),
+                    Err(_) => break
+                };
+
+                match job {
+                    Ok(job) => {
+                        if panic::catch_unwind(AssertUnwindSafe(|| job.run())).is_err() {
+                            error!(target: "worker_pool", "Worker {} job panicked", id);
+                        }
+                    }
+                    Err(_) => break
+                }
+            }
+
+            info!(target: "worker_pool", "Worker {} finished", id);
+        })
+    }
+}
+
+impl Drop for WorkerPool {
+    fn drop(&mut self) {
+        // Closing channel stops workers after all queued jobs are finished
+        self.sender.take();
+
+        for worker in self.workers.drain(..) {
+            if worker.join().is_err() {
+                error!(target: "worker_pool", "Can't join worker thread");
+            }
+        }
+    }
+}
+
+/// Runs job body turning its panic into error, so caller of the job still gets a result.
+pub fn catch_panic<T, F>(f: F) -> Result<T, CommonError> where F: FnOnce() -> T {
+    panic::catch_unwind(AssertUnwindSafe(f))
+        .map_err(|_| CommonError::InvalidState("Worker job panicked".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::mpsc::channel;
+    use std::time::Duration;
+
+    #[test]
+    fn worker_pool_executes_jobs() {
+        let pool = WorkerPool::new(2);
+        let (sender, receiver) = channel();
+
+        for i in 0..10 {
+            let sender = sender.clone();
+            pool.execute(move || sender.send(i).unwrap()).unwrap();
+        }
+
+        let mut results: Vec<i32> = (0..10)
+            .map(|_| receiver.recv_timeout(Duration::from_secs(1)).unwrap())
+            .collect();
+        results.sort();
+
+        assert_eq!((0..10).collect::<Vec<i32>>(), results);
+    }
+
+    #[test]
+    fn worker_pool_finishes_queued_jobs_on_drop() {
+        let (sender, receiver) = channel();
+
+        {
+            let pool = WorkerPool::new(1);
+
+            for i in 0..3 {
+                let sender = sender.clone();
+                pool.execute(move || sender.send(i).unwrap()).unwrap();
+            }
+        }
+
+        assert_eq!(vec![0, 1, 2], receiver.try_iter().collect::<Vec<i32>>());
+    }
+
+    #[test]
+    fn catch_panic_works() {
+        assert_eq!(1, catch_panic(|| 1).unwrap());
+        assert_match!(Err(CommonError::InvalidState(_)), catch_panic(|| -> i32 { panic!("job panic") }));
+    }
+
+    #[test]
+    fn worker_pool_execute_with_cb_works() {
+        let pool = WorkerPool::new(1);
+        let (sender, receiver) = channel();
+
+        pool.execute_with_cb(|| 1, move |res| sender.send(res.unwrap()).unwrap());
+
+        assert_eq!(1, receiver.recv_timeout(Duration::from_secs(1)).unwrap());
+    }
+
+    #[test]
+    fn worker_pool_execute_with_cb_works_for_panicking_job() {
+        let pool = WorkerPool::new(1);
+        let (sender, receiver) = channel();
+
+        pool.execute_with_cb(|| -> i32 { panic!("job panic") }, move |res| sender.send(res).unwrap());
+
+        assert_match!(Err(CommonError::InvalidState(_)), receiver.recv_timeout(Duration::from_secs(1)).unwrap());
+    }
+
+    #[test]
+    fn worker_pool_execute_with_cb_works_for_stopped_pool() {
+        let pool = WorkerPool { sender: None, workers: Vec::new() };
+        let (sender, receiver) = channel();
+
+        pool.execute_with_cb(|| 1, move |res| sender.send(res).unwrap());
+
+        assert_match!(Err(CommonError::InvalidState(_)), receiver.try_recv().unwrap());
+    }
+
+    #[test]
+    fn worker_pool_survives_panicking_job() {
+        let pool = WorkerPool::new(1);
+        let (sender, receiver) = channel();
+
+        pool.execute(|| panic!("job panic")).unwrap();
+        pool.execute(move || sender.send(1).unwrap()).unwrap();
+
+        assert_eq!(1, receiver.recv_timeout(Duration::from_secs(1)).unwrap());
+    }
+}
@@ -10,6 +10,8 @@ extern crate log;
 extern crate rust_base58;
 #[macro_use]
 extern crate serde_derive;
+#[macro_use]
+extern crate serde_json;
 extern crate zmq_pw as zmq;
 
 use std::sync::mpsc::channel;
@@ -22,6 +24,7 @@ mod utils;
 
 use utils::agent::AgentUtils;
 use utils::ledger::LedgerUtils;
+use utils::pairwise::PairwiseUtils;
 use utils::pool::PoolUtils;
 use utils::signus::SignusUtils;
 use utils::test::TestUtils;
@@ -35,6 +38,7 @@ static ENDPOINT: &'static str = "127.0.0.1:9700";
 static TRUSTEE_SEED: &'static str = "000000000000000000000000Trustee1";
 static CLIENT_MESSAGE: &'static str = "msg_from_client";
 static SERVER_MESSAGE: &'static str = "msg_from_server";
+static BINARY_MESSAGE: [u8; 4] = [0, 159, 146, 150];
 
 mod high_cases {
     use super::*;
@@ -136,6 +140,31 @@ mod high_cases {
         }
     }
 
+    mod indy_agent_connect_pairwise {
+        use super::*;
+
+        #[test]
+        fn indy_agent_connect_pairwise_works() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (did, ver_key, pub_key): (String, String, String) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            let listener_handle = AgentUtils::listen(ENDPOINT, None, None).unwrap();
+            AgentUtils::add_identity(listener_handle, -1, wallet_handle, did.as_str()).unwrap();
+
+            SignusUtils::store_their_did_from_parts(wallet_handle, did.as_str(), pub_key.as_str(), ver_key.as_str(), ENDPOINT).unwrap();
+            PairwiseUtils::create_pairwise(wallet_handle, did.as_str(), did.as_str(), None).unwrap();
+
+            AgentUtils::connect_pairwise(0, wallet_handle, did.as_str()).unwrap();
+
+            AgentUtils::close_listener(listener_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
     mod indy_agent_listen {
         use super::*;
 
@@ -385,6 +414,35 @@ mod high_cases {
         }
     }
 
+    mod indy_agent_set_authorization_cb {
+        use super::*;
+
+        #[test]
+        fn indy_agent_set_authorization_cb_works() {
+            TestUtils::cleanup_storage();
+
+            let (wait_auth_send, wait_auth_recv) = channel();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (did, ver_key, pub_key): (String, String, String) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            SignusUtils::store_their_did_from_parts(wallet_handle, did.as_str(), pub_key.as_str(), ver_key.as_str(), ENDPOINT).unwrap();
+            let listener_handle = AgentUtils::listen(ENDPOINT, None, None).unwrap();
+            AgentUtils::add_identity(listener_handle, -1, wallet_handle, did.as_str()).unwrap();
+            AgentUtils::set_authorization_cb(listener_handle, Some(Box::new(move |sender_did, receiver_did, role| {
+                wait_auth_send.send((sender_did, receiver_did, role)).unwrap();
+                true
+            }))).unwrap();
+
+            AgentUtils::connect(0, wallet_handle, did.as_str(), did.as_str(), None).unwrap();
+            assert_eq!(wait_auth_recv.recv_timeout(TimeoutUtils::short_timeout()).unwrap(), (did.clone(), did.clone(), None));
+
+            AgentUtils::close_listener(listener_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
     mod indy_agent_send {
         use super::*;
 
@@ -426,6 +484,309 @@ mod high_cases {
         }
     }
 
+    mod indy_agent_send_bytes {
+        use super::*;
+
+        #[test]
+        fn indy_agent_send_bytes_works() {
+            TestUtils::cleanup_storage();
+
+            let (wait_conn_send, wait_conn_recv) = channel();
+            let (wait_msg_from_cli_send, wait_msg_from_cli_recv) = channel();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (did, ver_key, pub_key): (String, String, String) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            SignusUtils::store_their_did_from_parts(wallet_handle, did.as_str(), pub_key.as_str(), ver_key.as_str(), ENDPOINT).unwrap();
+            let listener_handle = AgentUtils::listen_with_config(ENDPOINT, None,
+                                                                 Some(Box::new(move |_, conn_handle| {
+                                                                     wait_conn_send.send(conn_handle).unwrap();
+                                                                 })),
+                                                                 None,
+                                                                 Some(Box::new(move |_, msg| {
+                                                                     wait_msg_from_cli_send.send(msg).unwrap();
+                                                                 }))).unwrap();
+            AgentUtils::add_identity(listener_handle, -1, wallet_handle, did.as_str()).unwrap();
+            let cli_to_srv_connect_id = AgentUtils::connect(0, wallet_handle, did.as_str(), did.as_str(), None).unwrap();
+            wait_conn_recv.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+
+            AgentUtils::send_bytes(cli_to_srv_connect_id, &BINARY_MESSAGE).unwrap();
+            assert_eq!(wait_msg_from_cli_recv.recv_timeout(TimeoutUtils::short_timeout()).unwrap(), BINARY_MESSAGE.to_vec());
+
+            AgentUtils::close_listener(listener_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_agent_send_bytes_works_without_binary_message_cb() {
+            TestUtils::cleanup_storage();
+
+            let (wait_conn_send, wait_conn_recv) = channel();
+            let (wait_msg_from_cli_send, wait_msg_from_cli_recv) = channel::<String>();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (did, ver_key, pub_key): (String, String, String) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            SignusUtils::store_their_did_from_parts(wallet_handle, did.as_str(), pub_key.as_str(), ver_key.as_str(), ENDPOINT).unwrap();
+            let listener_handle = AgentUtils::listen(ENDPOINT,
+                                                     Some(Box::new(move |_, conn_handle| {
+                                                         wait_conn_send.send(conn_handle).unwrap();
+                                                     })),
+                                                     Some(Box::new(move |_, msg| {
+                                                         wait_msg_from_cli_send.send(msg).unwrap();
+                                                     }))).unwrap();
+            AgentUtils::add_identity(listener_handle, -1, wallet_handle, did.as_str()).unwrap();
+            let cli_to_srv_connect_id = AgentUtils::connect(0, wallet_handle, did.as_str(), did.as_str(), None).unwrap();
+            wait_conn_recv.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+
+            AgentUtils::send_bytes(cli_to_srv_connect_id, &BINARY_MESSAGE).unwrap();
+            assert_eq!(wait_msg_from_cli_recv.recv_timeout(TimeoutUtils::short_timeout()).unwrap_err(), RecvTimeoutError::Timeout);
+
+            AgentUtils::close_listener(listener_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod indy_agent_send_with_ack {
+        use super::*;
+
+        #[test]
+        fn indy_agent_send_with_ack_works() {
+            TestUtils::cleanup_storage();
+
+            let (wait_msg_from_cli_send, wait_msg_from_cli_recv) = channel();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (did, ver_key, pub_key): (String, String, String) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            SignusUtils::store_their_did_from_parts(wallet_handle, did.as_str(), pub_key.as_str(), ver_key.as_str(), ENDPOINT).unwrap();
+            let listener_handle = AgentUtils::listen_with_message_id_cb(ENDPOINT,
+                                                                        None,
+                                                                        Box::new(move |_, msg, msg_id| {
+                                                                            wait_msg_from_cli_send.send((msg, msg_id)).unwrap();
+                                                                        })).unwrap();
+            AgentUtils::add_identity(listener_handle, -1, wallet_handle, did.as_str()).unwrap();
+            let conn_handle = AgentUtils::connect(0, wallet_handle, did.as_str(), did.as_str(), None).unwrap();
+
+            let msg_id = AgentUtils::send_with_ack(conn_handle, Some("msg_1"), CLIENT_MESSAGE).unwrap();
+            assert_eq!(msg_id, "msg_1");
+            assert_eq!(wait_msg_from_cli_recv.recv_timeout(TimeoutUtils::short_timeout()).unwrap(),
+                       (CLIENT_MESSAGE.to_string(), "msg_1".to_string()));
+
+            AgentUtils::close_listener(listener_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_agent_send_with_ack_works_for_generated_id() {
+            TestUtils::cleanup_storage();
+
+            let (wait_msg_from_cli_send, wait_msg_from_cli_recv) = channel();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (did, ver_key, pub_key): (String, String, String) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            SignusUtils::store_their_did_from_parts(wallet_handle, did.as_str(), pub_key.as_str(), ver_key.as_str(), ENDPOINT).unwrap();
+            let listener_handle = AgentUtils::listen_with_message_id_cb(ENDPOINT,
+                                                                        None,
+                                                                        Box::new(move |_, _, msg_id| {
+                                                                            wait_msg_from_cli_send.send(msg_id).unwrap();
+                                                                        })).unwrap();
+            AgentUtils::add_identity(listener_handle, -1, wallet_handle, did.as_str()).unwrap();
+            let conn_handle = AgentUtils::connect(0, wallet_handle, did.as_str(), did.as_str(), None).unwrap();
+
+            let msg_id = AgentUtils::send_with_ack(conn_handle, None, CLIENT_MESSAGE).unwrap();
+            assert!(!msg_id.is_empty());
+            assert_eq!(wait_msg_from_cli_recv.recv_timeout(TimeoutUtils::short_timeout()).unwrap(), msg_id);
+
+            AgentUtils::close_listener(listener_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_agent_send_with_ack_works_without_message_id_cb() {
+            TestUtils::cleanup_storage();
+
+            let (wait_msg_from_cli_send, wait_msg_from_cli_recv) = channel();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (did, ver_key, pub_key): (String, String, String) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            SignusUtils::store_their_did_from_parts(wallet_handle, did.as_str(), pub_key.as_str(), ver_key.as_str(), ENDPOINT).unwrap();
+            let listener_handle = AgentUtils::listen(ENDPOINT,
+                                                     None,
+                                                     Some(Box::new(move |_, msg| {
+                                                         wait_msg_from_cli_send.send(msg).unwrap();
+                                                     }))).unwrap();
+            AgentUtils::add_identity(listener_handle, -1, wallet_handle, did.as_str()).unwrap();
+            let conn_handle = AgentUtils::connect(0, wallet_handle, did.as_str(), did.as_str(), None).unwrap();
+
+            AgentUtils::send_with_ack(conn_handle, None, CLIENT_MESSAGE).unwrap();
+            assert_eq!(wait_msg_from_cli_recv.recv_timeout(TimeoutUtils::short_timeout()).unwrap(), CLIENT_MESSAGE);
+
+            AgentUtils::close_listener(listener_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_agent_send_with_ack_works_for_protocol_like_text() {
+            TestUtils::cleanup_storage();
+
+            let (wait_msg_from_cli_send, wait_msg_from_cli_recv) = channel();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (did, ver_key, pub_key): (String, String, String) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            SignusUtils::store_their_did_from_parts(wallet_handle, did.as_str(), pub_key.as_str(), ver_key.as_str(), ENDPOINT).unwrap();
+            let listener_handle = AgentUtils::listen(ENDPOINT,
+                                                     None,
+                                                     Some(Box::new(move |_, msg| {
+                                                         wait_msg_from_cli_send.send(msg).unwrap();
+                                                     }))).unwrap();
+            AgentUtils::add_identity(listener_handle, -1, wallet_handle, did.as_str()).unwrap();
+            let conn_handle = AgentUtils::connect(0, wallet_handle, did.as_str(), did.as_str(), None).unwrap();
+
+            let msg = r#"{"msg_ack":"msg_1"}"#;
+            AgentUtils::send(conn_handle, msg).unwrap();
+            assert_eq!(wait_msg_from_cli_recv.recv_timeout(TimeoutUtils::short_timeout()).unwrap(), msg);
+
+            AgentUtils::close_listener(listener_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod indy_agent_request {
+        use super::*;
+
+        #[test]
+        fn indy_agent_request_works() {
+            TestUtils::cleanup_storage();
+
+            let (wait_request_send, wait_request_recv) = channel();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (did, ver_key, pub_key): (String, String, String) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            SignusUtils::store_their_did_from_parts(wallet_handle, did.as_str(), pub_key.as_str(), ver_key.as_str(), ENDPOINT).unwrap();
+            let listener_handle = AgentUtils::listen_with_message_id_cb(ENDPOINT,
+                                                                        None,
+                                                                        Box::new(move |conn_handle, msg, msg_id| {
+                                                                            wait_request_send.send((conn_handle, msg, msg_id)).unwrap();
+                                                                        })).unwrap();
+            AgentUtils::add_identity(listener_handle, -1, wallet_handle, did.as_str()).unwrap();
+            let conn_handle = AgentUtils::connect(0, wallet_handle, did.as_str(), did.as_str(), None).unwrap();
+
+            let responder = thread::spawn(move || {
+                let (srv_conn_handle, msg, msg_id) = wait_request_recv.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+                assert_eq!(msg, CLIENT_MESSAGE);
+                AgentUtils::reply(srv_conn_handle, msg_id.as_str(), SERVER_MESSAGE).unwrap();
+            });
+
+            let response = AgentUtils::request(conn_handle, CLIENT_MESSAGE, 5000).unwrap();
+            assert_eq!(response, SERVER_MESSAGE);
+            responder.join().unwrap();
+
+            AgentUtils::close_listener(listener_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod indy_agent_get_connection_info {
+        use super::*;
+
+        #[test]
+        fn indy_agent_get_connection_info_works() {
+            TestUtils::cleanup_storage();
+
+            let (wait_conn_send, wait_conn_recv) = channel();
+            let (wait_msg_from_cli_send, wait_msg_from_cli_recv) = channel();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (did, ver_key, pub_key): (String, String, String) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            SignusUtils::store_their_did_from_parts(wallet_handle, did.as_str(), pub_key.as_str(), ver_key.as_str(), ENDPOINT).unwrap();
+            let listener_handle = AgentUtils::listen(ENDPOINT,
+                                                     Some(Box::new(move |_, conn_handle| {
+                                                         wait_conn_send.send(conn_handle).unwrap();
+                                                     })),
+                                                     Some(Box::new(move |_, msg| {
+                                                         wait_msg_from_cli_send.send(msg).unwrap();
+                                                     }))).unwrap();
+            AgentUtils::add_identity(listener_handle, -1, wallet_handle, did.as_str()).unwrap();
+            let cli_to_srv_connect_id = AgentUtils::connect(0, wallet_handle, did.as_str(), did.as_str(), None).unwrap();
+            let srv_to_cli_connect_id = wait_conn_recv.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+
+            AgentUtils::send(cli_to_srv_connect_id, CLIENT_MESSAGE).unwrap();
+            wait_msg_from_cli_recv.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+
+            let info_json = AgentUtils::get_connection_info(cli_to_srv_connect_id).unwrap();
+            let info: serde_json::Value = serde_json::from_str(&info_json).unwrap();
+            assert_eq!(info["connection_handle"], json!(cli_to_srv_connect_id));
+            assert_eq!(info["sender_did"], json!(did));
+            assert_eq!(info["receiver_did"], json!(did));
+            assert_eq!(info["direction"], json!("outgoing"));
+            assert_eq!(info["listener_handle"], serde_json::Value::Null);
+            assert_eq!(info["state"], json!("connected"));
+            assert_eq!(info["stats"]["messages_sent"], json!(1));
+
+            let info_json = AgentUtils::get_connection_info(srv_to_cli_connect_id).unwrap();
+            let info: serde_json::Value = serde_json::from_str(&info_json).unwrap();
+            assert_eq!(info["direction"], json!("incoming"));
+            assert_eq!(info["listener_handle"], json!(listener_handle));
+            assert_eq!(info["stats"]["messages_received"], json!(1));
+
+            AgentUtils::close_listener(listener_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod indy_agent_list_connections {
+        use super::*;
+
+        #[test]
+        fn indy_agent_list_connections_works() {
+            TestUtils::cleanup_storage();
+
+            let (wait_conn_send, wait_conn_recv) = channel();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (did, ver_key, pub_key): (String, String, String) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            SignusUtils::store_their_did_from_parts(wallet_handle, did.as_str(), pub_key.as_str(), ver_key.as_str(), ENDPOINT).unwrap();
+            let listener_handle = AgentUtils::listen(ENDPOINT,
+                                                     Some(Box::new(move |_, conn_handle| {
+                                                         wait_conn_send.send(conn_handle).unwrap();
+                                                     })),
+                                                     None).unwrap();
+            AgentUtils::add_identity(listener_handle, -1, wallet_handle, did.as_str()).unwrap();
+            let cli_to_srv_connect_id = AgentUtils::connect(0, wallet_handle, did.as_str(), did.as_str(), None).unwrap();
+            let srv_to_cli_connect_id = wait_conn_recv.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+
+            let connections_json = AgentUtils::list_connections().unwrap();
+            let connections: Vec<serde_json::Value> = serde_json::from_str(&connections_json).unwrap();
+            let handles: Vec<i64> = connections.iter().map(|info| info["connection_handle"].as_i64().unwrap()).collect();
+            assert!(handles.contains(&(cli_to_srv_connect_id as i64)));
+            assert!(handles.contains(&(srv_to_cli_connect_id as i64)));
+
+            AgentUtils::close_connection(cli_to_srv_connect_id).unwrap();
+            let connections_json = AgentUtils::list_connections().unwrap();
+            let connections: Vec<serde_json::Value> = serde_json::from_str(&connections_json).unwrap();
+            assert!(!connections.iter().any(|info| info["connection_handle"] == json!(cli_to_srv_connect_id)));
+
+            AgentUtils::close_listener(listener_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
     mod indy_agent_close_connection {
         use super::*;
 
@@ -878,6 +1239,25 @@ mod medium_cases {
         }
     }
 
+    mod indy_agent_connect_pairwise {
+        use super::*;
+
+        #[test]
+        fn indy_agent_connect_pairwise_works_for_unknown_pairwise() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            let res = AgentUtils::connect_pairwise(0, wallet_handle, did.as_str());
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletNotFoundError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
     mod indy_agent_listen {
         use super::*;
 
@@ -905,6 +1285,84 @@ mod medium_cases {
             TestUtils::cleanup_storage();
         }
 
+        #[test]
+        fn indy_agent_listen_works_for_invalid_config() {
+            TestUtils::cleanup_storage();
+
+            let res = AgentUtils::listen_with_config(ENDPOINT, Some(r#"{"max_message_size":0}"#), None, None, None);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_agent_listen_works_for_reject_denied_sender() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (did, ver_key, pub_key): (String, String, String) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            SignusUtils::store_their_did_from_parts(wallet_handle, did.as_str(), pub_key.as_str(), ver_key.as_str(), ENDPOINT).unwrap();
+            let config = format!(r#"{{"deny_dids":["{}"]}}"#, did);
+            let listener_handle = AgentUtils::listen_with_config(ENDPOINT, Some(config.as_str()), None, None, None).unwrap();
+            AgentUtils::add_identity(listener_handle, -1, wallet_handle, did.as_str()).unwrap();
+
+            let res = AgentUtils::connect(0, wallet_handle, did.as_str(), did.as_str(), None);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidState);
+
+            AgentUtils::close_listener(listener_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_agent_listen_works_for_invalid_role_in_config() {
+            TestUtils::cleanup_storage();
+
+            let res = AgentUtils::listen_with_config(ENDPOINT, Some(r#"{"allow_roles":["ADMIN"]}"#), None, None, None);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_agent_listen_works_for_reject_too_large_message() {
+            TestUtils::cleanup_storage();
+
+            let (wait_err_send, wait_err_recv) = channel();
+            let (wait_msg_from_cli_send, wait_msg_from_cli_recv) = channel::<String>();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (did, ver_key, pub_key): (String, String, String) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            SignusUtils::store_their_did_from_parts(wallet_handle, did.as_str(), pub_key.as_str(), ver_key.as_str(), ENDPOINT).unwrap();
+            let listener_handle = AgentUtils::listen_with_config(ENDPOINT, Some(r#"{"max_message_size":1000}"#), None,
+                                                                 Some(Box::new(move |_, msg| {
+                                                                     wait_msg_from_cli_send.send(msg).unwrap();
+                                                                 })),
+                                                                 None).unwrap();
+            AgentUtils::add_identity(listener_handle, -1, wallet_handle, did.as_str()).unwrap();
+            let cli_to_srv_connect_id = AgentUtils::connect_with_err_cb(0, wallet_handle, did.as_str(), did.as_str(), None,
+                                                                        Some(Box::new(move |_, err| {
+                                                                            wait_err_send.send(err).unwrap();
+                                                                        }))).unwrap();
+
+            let large_message = "a".repeat(2000);
+            AgentUtils::send(cli_to_srv_connect_id, large_message.as_str()).unwrap();
+            assert_eq!(wait_err_recv.recv_timeout(TimeoutUtils::short_timeout()).unwrap(), ErrorCode::AgentMessageTooLargeError);
+            assert_eq!(wait_msg_from_cli_recv.recv_timeout(TimeoutUtils::short_timeout()).unwrap_err(), RecvTimeoutError::Timeout);
+
+            let far_too_large_message = "a".repeat(100000);
+            AgentUtils::send(cli_to_srv_connect_id, far_too_large_message.as_str()).unwrap();
+            assert_eq!(wait_err_recv.recv_timeout(TimeoutUtils::short_timeout()).unwrap(), ErrorCode::AgentMessageTooLargeError);
+            assert_eq!(wait_msg_from_cli_recv.recv_timeout(TimeoutUtils::short_timeout()).unwrap_err(), RecvTimeoutError::Timeout);
+
+            AgentUtils::close_listener(listener_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
         #[test]
         fn indy_agent_listen_works_for_reject_unknow_sender() {
             TestUtils::cleanup_storage();
@@ -1216,6 +1674,48 @@ mod medium_cases {
         }
     }
 
+    mod indy_agent_set_authorization_cb {
+        use super::*;
+
+        #[test]
+        fn indy_agent_set_authorization_cb_works_for_reject() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (did, ver_key, pub_key): (String, String, String) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            SignusUtils::store_their_did_from_parts(wallet_handle, did.as_str(), pub_key.as_str(), ver_key.as_str(), ENDPOINT).unwrap();
+            let listener_handle = AgentUtils::listen(ENDPOINT, None, None).unwrap();
+            AgentUtils::add_identity(listener_handle, -1, wallet_handle, did.as_str()).unwrap();
+            AgentUtils::set_authorization_cb(listener_handle, Some(Box::new(|_, _, _| false))).unwrap();
+
+            let res = AgentUtils::connect(0, wallet_handle, did.as_str(), did.as_str(), None);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidState);
+
+            AgentUtils::set_authorization_cb(listener_handle, None).unwrap();
+            AgentUtils::connect(0, wallet_handle, did.as_str(), did.as_str(), None).unwrap();
+
+            AgentUtils::close_listener(listener_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_agent_set_authorization_cb_works_for_invalid_listener_handle() {
+            TestUtils::cleanup_storage();
+
+            let listener_handle = AgentUtils::listen(ENDPOINT, None, None).unwrap();
+
+            let res = AgentUtils::set_authorization_cb(listener_handle + 100, Some(Box::new(|_, _, _| true)));
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            AgentUtils::close_listener(listener_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
     mod indy_agent_send {
         use super::*;
 
@@ -1357,6 +1857,169 @@ mod medium_cases {
         }
     }
 
+    mod indy_agent_send_bytes {
+        use super::*;
+
+        #[test]
+        fn indy_agent_send_bytes_works_for_invalid_connection_handle() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (did, ver_key, pub_key) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            SignusUtils::store_their_did_from_parts(wallet_handle, did.as_str(), pub_key.as_str(),
+                                                    ver_key.as_str(), ENDPOINT).unwrap();
+
+            let listener_handle = AgentUtils::listen(ENDPOINT, None, None).unwrap();
+            AgentUtils::add_identity(listener_handle, 0, wallet_handle, did.as_str()).unwrap();
+
+            let connection_handle = AgentUtils::connect(0, wallet_handle, did.as_str(),
+                                                        did.as_str(), None).unwrap();
+            let invalid_connection_handle = connection_handle + 100;
+
+            let res = AgentUtils::send_bytes(invalid_connection_handle, &BINARY_MESSAGE);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            AgentUtils::close_listener(listener_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod indy_agent_send_with_ack {
+        use super::*;
+
+        #[test]
+        fn indy_agent_send_with_ack_works_for_invalid_connection_handle() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (did, ver_key, pub_key) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            SignusUtils::store_their_did_from_parts(wallet_handle, did.as_str(), pub_key.as_str(),
+                                                    ver_key.as_str(), ENDPOINT).unwrap();
+
+            let listener_handle = AgentUtils::listen(ENDPOINT, None, None).unwrap();
+            AgentUtils::add_identity(listener_handle, 0, wallet_handle, did.as_str()).unwrap();
+
+            let connection_handle = AgentUtils::connect(0, wallet_handle, did.as_str(),
+                                                        did.as_str(), None).unwrap();
+
+            let res = AgentUtils::send_with_ack(connection_handle + 100, None, CLIENT_MESSAGE);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            AgentUtils::close_listener(listener_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod indy_agent_request {
+        use super::*;
+
+        #[test]
+        fn indy_agent_request_works_for_timeout() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (did, ver_key, pub_key) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            SignusUtils::store_their_did_from_parts(wallet_handle, did.as_str(), pub_key.as_str(),
+                                                    ver_key.as_str(), ENDPOINT).unwrap();
+
+            let listener_handle = AgentUtils::listen(ENDPOINT, None, None).unwrap();
+            AgentUtils::add_identity(listener_handle, 0, wallet_handle, did.as_str()).unwrap();
+
+            let connection_handle = AgentUtils::connect(0, wallet_handle, did.as_str(),
+                                                        did.as_str(), None).unwrap();
+
+            let res = AgentUtils::request(connection_handle, CLIENT_MESSAGE, 100);
+            assert_eq!(res.unwrap_err(), ErrorCode::AgentTimeoutError);
+
+            AgentUtils::close_listener(listener_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_agent_request_works_for_zero_timeout() {
+            let res = AgentUtils::request(-1, CLIENT_MESSAGE, 0);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidParam4);
+        }
+
+        #[test]
+        fn indy_agent_request_works_for_closed_connection() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (did, ver_key, pub_key) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            SignusUtils::store_their_did_from_parts(wallet_handle, did.as_str(), pub_key.as_str(),
+                                                    ver_key.as_str(), ENDPOINT).unwrap();
+
+            let listener_handle = AgentUtils::listen(ENDPOINT, None, None).unwrap();
+            AgentUtils::add_identity(listener_handle, 0, wallet_handle, did.as_str()).unwrap();
+
+            let connection_handle = AgentUtils::connect(0, wallet_handle, did.as_str(),
+                                                        did.as_str(), None).unwrap();
+
+            let closer = thread::spawn(move || {
+                thread::sleep(TimeoutUtils::short_timeout() / 10);
+                AgentUtils::close_connection(connection_handle).unwrap();
+            });
+
+            let res = AgentUtils::request(connection_handle, CLIENT_MESSAGE, 60000);
+            assert_eq!(res.unwrap_err(), ErrorCode::AgentConnectionLostError);
+            closer.join().unwrap();
+
+            AgentUtils::close_listener(listener_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod indy_agent_set_message_id_cb {
+        use super::*;
+
+        #[test]
+        fn indy_agent_set_message_id_cb_works_for_unknown_handle() {
+            let res = AgentUtils::set_message_id_cb(-1, None);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+        }
+    }
+
+    mod indy_agent_set_binary_message_cb {
+        use super::*;
+
+        #[test]
+        fn indy_agent_set_binary_message_cb_works_for_unknown_handle() {
+            let res = AgentUtils::set_binary_message_cb(-1, None);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+        }
+    }
+
+    mod indy_agent_get_connection_info {
+        use super::*;
+
+        #[test]
+        fn indy_agent_get_connection_info_works_for_invalid_connection_handle() {
+            TestUtils::cleanup_storage();
+
+            let res = AgentUtils::get_connection_info(-1);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
     mod indy_agent_close_connection {
         use super::*;
 
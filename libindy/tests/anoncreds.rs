@@ -69,6 +69,70 @@ mod high_cases {
         }
     }
 
+    mod issuer_generate_safe_primes {
+        use super::*;
+
+        #[test]
+        fn issuer_generate_safe_primes_works() {
+            AnoncredsUtils::init_common_wallet();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet("pool1", None).unwrap();
+
+            AnoncredsUtils::issuer_generate_safe_primes(wallet_handle, 1).unwrap();
+
+            let safe_prime = AnoncredsUtils::get_safe_primes()[0];
+            AnoncredsUtils::issuer_import_safe_primes(wallet_handle, &format!(r#"["{}"]"#, safe_prime)).unwrap();
+
+            let schema = AnoncredsUtils::get_gvt_schema_json(1);
+            let claim_def_json = AnoncredsUtils::issuer_create_claim_definition(wallet_handle, &ISSUER_DID, &schema, None, false).unwrap();
+
+            let claim_def: ClaimDefinition = serde_json::from_str(&claim_def_json).unwrap();
+            assert!(claim_def.data.public_key.n.len() > 0);
+
+            // Generated and imported primes are both used, so imported one can be imported again
+            AnoncredsUtils::issuer_import_safe_primes(wallet_handle, &format!(r#"["{}"]"#, safe_prime)).unwrap();
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
+    mod issuer_import_safe_primes {
+        use super::*;
+
+        #[test]
+        fn issuer_import_safe_primes_works() {
+            AnoncredsUtils::init_common_wallet();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet("pool1", None).unwrap();
+
+            AnoncredsUtils::issuer_import_safe_primes(wallet_handle, &AnoncredsUtils::get_safe_primes_json()).unwrap();
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn issuer_create_claim_definition_works_for_imported_safe_primes() {
+            AnoncredsUtils::init_common_wallet();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet("pool1", None).unwrap();
+
+            let safe_primes = AnoncredsUtils::get_safe_primes();
+            let safe_primes_json = format!(r#"["{}","{}","{}"]"#, safe_primes[0], safe_primes[1], safe_primes[2]);
+            AnoncredsUtils::issuer_import_safe_primes(wallet_handle, &safe_primes_json).unwrap();
+
+            let schema = AnoncredsUtils::get_gvt_schema_json(1);
+            AnoncredsUtils::issuer_create_claim_definition(wallet_handle, &ISSUER_DID, &schema, None, false).unwrap();
+
+            // Exactly two last imported primes are consumed, the first one is still in pool
+            let res = AnoncredsUtils::issuer_import_safe_primes(wallet_handle, &format!(r#"["{}"]"#, safe_primes[0]));
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            AnoncredsUtils::issuer_import_safe_primes(wallet_handle, &format!(r#"["{}","{}"]"#, safe_primes[1], safe_primes[2])).unwrap();
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
     mod prover_store_claim_offer {
         use super::*;
 
@@ -837,6 +901,63 @@ mod medium_cases {
         }
     }
 
+    mod issuer_generate_safe_primes {
+        use super::*;
+
+        #[test]
+        fn issuer_generate_safe_primes_works_for_invalid_count() {
+            let (wallet_handle, _) = AnoncredsUtils::init_common_wallet();
+
+            let res = AnoncredsUtils::issuer_generate_safe_primes(wallet_handle, 0);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidParam3);
+        }
+    }
+
+    mod issuer_import_safe_primes {
+        use super::*;
+
+        #[test]
+        fn issuer_import_safe_primes_works_for_not_safe_prime() {
+            let (wallet_handle, _) = AnoncredsUtils::init_common_wallet();
+
+            let res = AnoncredsUtils::issuer_import_safe_primes(wallet_handle, r#"["23"]"#);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+        }
+
+        #[test]
+        fn issuer_import_safe_primes_works_for_duplicated_prime() {
+            let (wallet_handle, _) = AnoncredsUtils::init_common_wallet();
+
+            let safe_prime = AnoncredsUtils::get_safe_primes()[0];
+
+            let res = AnoncredsUtils::issuer_import_safe_primes(wallet_handle, &format!(r#"["{}","{}"]"#, safe_prime, safe_prime));
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+        }
+
+        #[test]
+        fn issuer_import_safe_primes_works_for_already_imported_prime() {
+            AnoncredsUtils::init_common_wallet();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet("pool1", None).unwrap();
+
+            let safe_prime_json = format!(r#"["{}"]"#, AnoncredsUtils::get_safe_primes()[0]);
+            AnoncredsUtils::issuer_import_safe_primes(wallet_handle, &safe_prime_json).unwrap();
+
+            let res = AnoncredsUtils::issuer_import_safe_primes(wallet_handle, &safe_prime_json);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn issuer_import_safe_primes_works_for_invalid_json() {
+            let (wallet_handle, _) = AnoncredsUtils::init_common_wallet();
+
+            let res = AnoncredsUtils::issuer_import_safe_primes(wallet_handle, r#"{"prime":"23"}"#);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+        }
+    }
+
     mod prover_store_claim_offer {
         use super::*;
 
@@ -289,6 +289,25 @@ mod high_cases {
             assert!(nym_request.contains(&expected_result));
         }
 
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_build_nym_requests_works_for_qualified_dids() {
+            let identifier = "Th7MpTaRZVRYnPiabds81Y";
+            let dest = "FYmoFw55GeQH7SRFa37dkx1d2dZ3zUF8ckg7wmL7ofN4";
+
+            let expected_result = format!(
+                "\"identifier\":\"{}\",\
+                \"operation\":{{\
+                    \"type\":\"1\",\
+                    \"dest\":\"{}\"\
+                }}", identifier, dest);
+
+            let nym_request = LedgerUtils::build_nym_request(&format!("did:sov:{}", identifier),
+                                                             &format!("did:sov:{}", dest), None, None, None).unwrap();
+
+            assert!(nym_request.contains(&expected_result));
+        }
+
         #[test]
         #[cfg(feature = "local_nodes_pool")]
         fn indy_build_nym_requests_works_with_option_fields() {
@@ -417,6 +436,17 @@ mod high_cases {
 
             TestUtils::cleanup_storage();
         }
+
+        #[test]
+        fn indy_parse_get_nym_response_works() {
+            let get_nym_response = r#"{"op":"REPLY","result":{"identifier":"V4SGRU86Z58d6TV7PBUe6f","reqId":1,"type":"105","dest":"V4SGRU86Z58d6TV7PBUe6f","data":"{\"identifier\":\"V4SGRU86Z58d6TV7PBUe6f\",\"dest\":\"V4SGRU86Z58d6TV7PBUe6f\",\"role\":\"0\",\"verkey\":\"~CoRER63DVYnWZtK8uAzNbx\"}"}}"#;
+
+            let nym_json = LedgerUtils::parse_get_nym_response(get_nym_response).unwrap();
+            let nym: serde_json::Value = serde_json::from_str(&nym_json).unwrap();
+            assert_eq!("V4SGRU86Z58d6TV7PBUe6f", nym["dest"].as_str().unwrap());
+            assert_eq!("0", nym["role"].as_str().unwrap());
+            assert_eq!("~CoRER63DVYnWZtK8uAzNbx", nym["verkey"].as_str().unwrap());
+        }
     }
 
     mod attrib_requests {
@@ -623,6 +653,21 @@ mod high_cases {
 
             TestUtils::cleanup_storage();
         }
+
+        #[test]
+        fn indy_parse_get_schema_response_works() {
+            let get_schema_response = r#"{"op":"REPLY","result":{"identifier":"V4SGRU86Z58d6TV7PBUe6f","reqId":1,"seqNo":10,"type":"107","dest":"V4SGRU86Z58d6TV7PBUe6f","data":{"name":"gvt","version":"1.0","attr_names":["name","age"],"origin":"V4SGRU86Z58d6TV7PBUe6f"}}}"#;
+
+            let schema_json = LedgerUtils::parse_get_schema_response(get_schema_response).unwrap();
+            let schema: serde_json::Value = serde_json::from_str(&schema_json).unwrap();
+            assert_eq!(10, schema["seqNo"].as_i64().unwrap());
+            assert_eq!("gvt", schema["data"]["name"].as_str().unwrap());
+            assert_eq!("1.0", schema["data"]["version"].as_str().unwrap());
+
+            let keys: HashSet<String> = serde_json::from_value(schema["data"]["keys"].clone()).unwrap();
+            let expected_keys: HashSet<String> = ["name".to_string(), "age".to_string()].iter().cloned().collect();
+            assert_eq!(expected_keys, keys);
+        }
     }
 
     mod node_request {
@@ -827,6 +872,80 @@ mod high_cases {
             info!("get_claim_def_response {}", get_claim_def_response);
             let _: Reply<GetClaimDefReplyResult> = serde_json::from_str(&get_claim_def_response).unwrap();
 
+            let claim_def_json = LedgerUtils::parse_get_claim_def_response(&get_claim_def_response).unwrap();
+            let parsed_claim_def: ClaimDefinition = serde_json::from_str(&claim_def_json).unwrap();
+            assert_eq!(claim_def_data.public_key, parsed_claim_def.data.public_key);
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_parse_get_claim_def_response_works() {
+            let claim_def: ClaimDefinition = serde_json::from_str(&AnoncredsUtils::get_gvt_claim_def()).unwrap();
+            let get_claim_def_response = format!(r#"{{"op":"REPLY","result":{{"identifier":"{}","reqId":1,"seqNo":2,"type":"108","ref":{},"signature_type":"{}","origin":"{}","data":{}}}}}"#,
+                                                 claim_def.issuer_did, claim_def.schema_seq_no, claim_def.signature_type,
+                                                 claim_def.issuer_did, serde_json::to_string(&claim_def.data).unwrap());
+
+            let claim_def_json = LedgerUtils::parse_get_claim_def_response(&get_claim_def_response).unwrap();
+            let parsed_claim_def: ClaimDefinition = serde_json::from_str(&claim_def_json).unwrap();
+            assert_eq!(claim_def, parsed_claim_def);
+        }
+    }
+
+    mod revoc_reg_requests {
+        use super::*;
+
+        const ISSUER_DID: &'static str = "NcYxiDXkpYi6ov5FcYDi1e";
+
+        #[test]
+        fn indy_build_get_revoc_reg_request_works() {
+            let revoc_reg_def_id = "NcYxiDXkpYi6ov5FcYDi1e:1";
+            let timestamp = 1500000000;
+
+            let expected_result = format!(
+                "\"identifier\":\"{}\",\
+                \"operation\":{{\
+                    \"type\":\"116\",\
+                    \"revocRegDefId\":\"{}\",\
+                    \"timestamp\":{}\
+                }}", ISSUER_DID, revoc_reg_def_id, timestamp);
+
+            let get_revoc_reg_request = LedgerUtils::build_get_revoc_reg_request(ISSUER_DID, revoc_reg_def_id, timestamp).unwrap();
+            assert!(get_revoc_reg_request.contains(&expected_result));
+        }
+
+        #[test]
+        fn indy_revoc_reg_requests_works() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet("pool1", None).unwrap();
+
+            let schema_seq_no = 1;
+            let schema = AnoncredsUtils::get_gvt_schema_json(schema_seq_no);
+            AnoncredsUtils::issuer_create_claim_definition(wallet_handle, ISSUER_DID, &schema, None, true).unwrap();
+            let revoc_reg_json = AnoncredsUtils::indy_issuer_create_and_store_revoc_reg(wallet_handle, ISSUER_DID, schema_seq_no, 5).unwrap();
+            let revoc_reg_def_id = AnoncredsUtils::get_composite_id(ISSUER_DID, schema_seq_no);
+
+            let revoc_reg_def_request = LedgerUtils::build_revoc_reg_def_request(ISSUER_DID, &revoc_reg_json).unwrap();
+            let revoc_reg_def_request: serde_json::Value = serde_json::from_str(&revoc_reg_def_request).unwrap();
+            assert_eq!(revoc_reg_def_id, revoc_reg_def_request["operation"]["id"].as_str().unwrap());
+
+            let get_revoc_reg_def_response = format!(r#"{{"op":"REPLY","result":{{"identifier":"{}","reqId":1,"seqNo":3,"type":"115","id":"{}","data":{}}}}}"#,
+                                                     ISSUER_DID, revoc_reg_def_id, revoc_reg_def_request["operation"]);
+            let revoc_reg_def_json = LedgerUtils::parse_get_revoc_reg_def_response(&get_revoc_reg_def_response).unwrap();
+
+            let revoc_reg_entry_request = LedgerUtils::build_revoc_reg_entry_request(ISSUER_DID, &revoc_reg_json).unwrap();
+            let revoc_reg_entry_request: serde_json::Value = serde_json::from_str(&revoc_reg_entry_request).unwrap();
+            assert_eq!(revoc_reg_def_id, revoc_reg_entry_request["operation"]["revocRegDefId"].as_str().unwrap());
+
+            let get_revoc_reg_response = format!(r#"{{"op":"REPLY","result":{{"identifier":"{}","reqId":2,"seqNo":4,"type":"116","revocRegDefId":"{}","timestamp":1500000000,"data":{{"revocRegDefId":"{}","value":{},"txnTime":1400000000}}}}}}"#,
+                                                 ISSUER_DID, revoc_reg_def_id, revoc_reg_def_id, revoc_reg_entry_request["operation"]["value"]);
+            let ledger_revoc_reg_json = LedgerUtils::parse_get_revoc_reg_response(&revoc_reg_def_json, &get_revoc_reg_response).unwrap();
+
+            let revoc_reg: serde_json::Value = serde_json::from_str(&revoc_reg_json).unwrap();
+            let ledger_revoc_reg: serde_json::Value = serde_json::from_str(&ledger_revoc_reg_json).unwrap();
+            assert_eq!(revoc_reg, ledger_revoc_reg);
+
             TestUtils::cleanup_storage();
         }
     }
@@ -1160,6 +1279,14 @@ mod medium_cases {
             let res = LedgerUtils::build_get_nym_request(identifier, dest);
             assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
         }
+
+        #[test]
+        fn indy_parse_get_nym_response_works_for_not_found() {
+            let get_nym_response = r#"{"op":"REPLY","result":{"identifier":"V4SGRU86Z58d6TV7PBUe6f","reqId":1,"type":"105","dest":"V4SGRU86Z58d6TV7PBUe6f","data":null}}"#;
+
+            let res = LedgerUtils::parse_get_nym_response(get_nym_response);
+            assert_eq!(res.unwrap_err(), ErrorCode::LedgerNotFound);
+        }
     }
 
     mod attrib_requests {
@@ -1332,6 +1459,14 @@ mod medium_cases {
 
             TestUtils::cleanup_storage();
         }
+
+        #[test]
+        fn indy_parse_get_schema_response_works_for_not_found() {
+            let get_schema_response = r#"{"op":"REPLY","result":{"identifier":"V4SGRU86Z58d6TV7PBUe6f","reqId":1,"seqNo":null,"type":"107","dest":"V4SGRU86Z58d6TV7PBUe6f","data":null}}"#;
+
+            let res = LedgerUtils::parse_get_schema_response(get_schema_response);
+            assert_eq!(res.unwrap_err(), ErrorCode::LedgerNotFound);
+        }
     }
 
     mod node_requests {
@@ -1428,5 +1563,39 @@ mod medium_cases {
             assert!(res.is_err());
             assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
         }
+
+        #[test]
+        fn indy_parse_get_claim_def_response_works_for_invalid_response() {
+            let get_claim_def_response = r#"{"op":"REPLY","result":{"identifier":"V4SGRU86Z58d6TV7PBUe6f","reqId":1,"seqNo":2,"type":"108","ref":1,"signature_type":"CL","origin":"V4SGRU86Z58d6TV7PBUe6f","data":{"primary":{"n":"1"}}}}"#;
+
+            let res = LedgerUtils::parse_get_claim_def_response(get_claim_def_response);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+        }
+    }
+
+    mod revoc_reg_requests {
+        use super::*;
+
+        #[test]
+        fn indy_build_get_revoc_reg_request_works_for_invalid_id() {
+            let res = LedgerUtils::build_get_revoc_reg_request("NcYxiDXkpYi6ov5FcYDi1e", "NcYxiDXkpYi6ov5FcYDi1e", 1500000000);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+        }
+
+        #[test]
+        fn indy_build_revoc_reg_entry_request_works_for_invalid_data() {
+            let data = r#"{"issuer_did":"NcYxiDXkpYi6ov5FcYDi1e","schema_seq_no":1}"#;
+
+            let res = LedgerUtils::build_revoc_reg_entry_request("NcYxiDXkpYi6ov5FcYDi1e", data);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+        }
+
+        #[test]
+        fn indy_parse_get_revoc_reg_def_response_works_for_not_found() {
+            let get_revoc_reg_def_response = r#"{"op":"REPLY","result":{"identifier":"NcYxiDXkpYi6ov5FcYDi1e","reqId":1,"seqNo":null,"type":"115","id":"NcYxiDXkpYi6ov5FcYDi1e:1","data":null}}"#;
+
+            let res = LedgerUtils::parse_get_revoc_reg_def_response(get_revoc_reg_def_response);
+            assert_eq!(res.unwrap_err(), ErrorCode::LedgerNotFound);
+        }
     }
 }
@@ -0,0 +1,310 @@
+extern crate indy;
+
+// Workaround to share some utils code based on indy sdk types between tests and indy sdk
+use indy::api as api;
+
+extern crate rust_base58;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_json;
+#[macro_use]
+extern crate lazy_static;
+#[macro_use]
+extern crate log;
+
+#[macro_use]
+mod utils;
+
+use utils::wallet::WalletUtils;
+use utils::signus::SignusUtils;
+use utils::pairwise::PairwiseUtils;
+use utils::test::TestUtils;
+
+use indy::api::ErrorCode;
+
+pub const POOL: &'static str = "pool_1";
+pub const MY1_SEED: &'static str = "00000000000000000000000000000My1";
+pub const MY2_SEED: &'static str = "00000000000000000000000000000My2";
+pub const ENDPOINT: &'static str = "127.0.0.1:9700";
+pub const METADATA: &'static str = "some metadata";
+
+fn _create_dids(wallet_handle: i32) -> (String, String) {
+    let (my_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(MY1_SEED)).unwrap();
+    let (their_did, their_verkey, their_pk) = SignusUtils::create_and_store_my_did(wallet_handle, Some(MY2_SEED)).unwrap();
+    SignusUtils::store_their_did_from_parts(wallet_handle, &their_did, &their_pk, &their_verkey, ENDPOINT).unwrap();
+    (my_did, their_did)
+}
+
+mod high_cases {
+    use super::*;
+
+    mod create_pairwise {
+        use super::*;
+
+        #[test]
+        fn indy_create_pairwise_works() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (my_did, their_did) = _create_dids(wallet_handle);
+
+            PairwiseUtils::create_pairwise(wallet_handle, &their_did, &my_did, Some(METADATA)).unwrap();
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_create_pairwise_works_for_empty_metadata() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (my_did, their_did) = _create_dids(wallet_handle);
+
+            PairwiseUtils::create_pairwise(wallet_handle, &their_did, &my_did, None).unwrap();
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod list_pairwise {
+        use super::*;
+
+        #[test]
+        fn indy_list_pairwise_works() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (my_did, their_did) = _create_dids(wallet_handle);
+
+            PairwiseUtils::create_pairwise(wallet_handle, &their_did, &my_did, Some(METADATA)).unwrap();
+
+            let list_pairwise_json = PairwiseUtils::list_pairwise(wallet_handle).unwrap();
+            let list_pairwise: Vec<serde_json::Value> = serde_json::from_str(&list_pairwise_json).unwrap();
+
+            assert_eq!(1, list_pairwise.len());
+            assert_eq!(my_did, list_pairwise[0]["my_did"].as_str().unwrap());
+            assert_eq!(their_did, list_pairwise[0]["their_did"].as_str().unwrap());
+            assert_eq!(METADATA, list_pairwise[0]["metadata"].as_str().unwrap());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_list_pairwise_works_for_empty_result() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let list_pairwise_json = PairwiseUtils::list_pairwise(wallet_handle).unwrap();
+            assert_eq!("[]", list_pairwise_json);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod get_pairwise {
+        use super::*;
+
+        #[test]
+        fn indy_get_pairwise_works() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (my_did, their_did) = _create_dids(wallet_handle);
+
+            PairwiseUtils::create_pairwise(wallet_handle, &their_did, &my_did, Some(METADATA)).unwrap();
+
+            let pairwise_info_json = PairwiseUtils::get_pairwise(wallet_handle, &their_did).unwrap();
+            assert_eq!(format!(r#"{{"my_did":"{}","metadata":"{}"}}"#, my_did, METADATA), pairwise_info_json);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_get_pairwise_works_after_wallet_reopen() {
+            TestUtils::cleanup_storage();
+
+            let wallet_name = "indy_get_pairwise_works_after_wallet_reopen";
+            WalletUtils::create_wallet(POOL, wallet_name, None, None).unwrap();
+            let wallet_handle = WalletUtils::open_wallet(wallet_name, None).unwrap();
+            let (my_did, their_did) = _create_dids(wallet_handle);
+
+            PairwiseUtils::create_pairwise(wallet_handle, &their_did, &my_did, None).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            let wallet_handle = WalletUtils::open_wallet(wallet_name, None).unwrap();
+
+            let pairwise_info_json = PairwiseUtils::get_pairwise(wallet_handle, &their_did).unwrap();
+            assert_eq!(format!(r#"{{"my_did":"{}","metadata":null}}"#, my_did), pairwise_info_json);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_get_pairwise_works_for_same_id_in_different_methods() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (my_did, their_did) = _create_dids(wallet_handle);
+            let (_, their_verkey, their_pk) = SignusUtils::create_and_store_my_did(wallet_handle, Some(MY2_SEED)).unwrap();
+            let their_peer_did = format!("did:peer:{}", their_did);
+            SignusUtils::store_their_did_from_parts(wallet_handle, &their_peer_did, &their_pk, &their_verkey, ENDPOINT).unwrap();
+
+            PairwiseUtils::create_pairwise(wallet_handle, &their_did, &my_did, Some(METADATA)).unwrap();
+            PairwiseUtils::create_pairwise(wallet_handle, &their_peer_did, &my_did, None).unwrap();
+
+            let pairwise_info_json = PairwiseUtils::get_pairwise(wallet_handle, &their_did).unwrap();
+            assert_eq!(format!(r#"{{"my_did":"{}","metadata":"{}"}}"#, my_did, METADATA), pairwise_info_json);
+
+            let pairwise_info_json = PairwiseUtils::get_pairwise(wallet_handle, &their_peer_did).unwrap();
+            assert_eq!(format!(r#"{{"my_did":"{}","metadata":null}}"#, my_did), pairwise_info_json);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod set_pairwise_metadata {
+        use super::*;
+
+        #[test]
+        fn indy_set_pairwise_metadata_works() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (my_did, their_did) = _create_dids(wallet_handle);
+
+            PairwiseUtils::create_pairwise(wallet_handle, &their_did, &my_did, None).unwrap();
+            PairwiseUtils::set_pairwise_metadata(wallet_handle, &their_did, Some(METADATA)).unwrap();
+
+            let pairwise_info_json = PairwiseUtils::get_pairwise(wallet_handle, &their_did).unwrap();
+            assert_eq!(format!(r#"{{"my_did":"{}","metadata":"{}"}}"#, my_did, METADATA), pairwise_info_json);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_set_pairwise_metadata_works_for_reset() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (my_did, their_did) = _create_dids(wallet_handle);
+
+            PairwiseUtils::create_pairwise(wallet_handle, &their_did, &my_did, Some(METADATA)).unwrap();
+            PairwiseUtils::set_pairwise_metadata(wallet_handle, &their_did, None).unwrap();
+
+            let pairwise_info_json = PairwiseUtils::get_pairwise(wallet_handle, &their_did).unwrap();
+            assert_eq!(format!(r#"{{"my_did":"{}","metadata":null}}"#, my_did), pairwise_info_json);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+}
+
+mod medium_cases {
+    use super::*;
+
+    mod create_pairwise {
+        use super::*;
+
+        #[test]
+        fn indy_create_pairwise_works_for_not_found_my_did() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (_, their_did) = _create_dids(wallet_handle);
+
+            let res = PairwiseUtils::create_pairwise(wallet_handle, &their_did, "VsKV7grR1BUE29mG2Fm2kX", None);
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletNotFoundError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_create_pairwise_works_for_not_found_their_did() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (my_did, _) = _create_dids(wallet_handle);
+
+            let res = PairwiseUtils::create_pairwise(wallet_handle, "VsKV7grR1BUE29mG2Fm2kX", &my_did, None);
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletNotFoundError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_create_pairwise_works_for_invalid_wallet_handle() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (my_did, their_did) = _create_dids(wallet_handle);
+
+            let res = PairwiseUtils::create_pairwise(wallet_handle + 1, &their_did, &my_did, None);
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletInvalidHandle);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod get_pairwise {
+        use super::*;
+
+        #[test]
+        fn indy_get_pairwise_works_for_not_created_pairwise() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (_, their_did) = _create_dids(wallet_handle);
+
+            let res = PairwiseUtils::get_pairwise(wallet_handle, &their_did);
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletNotFoundError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod set_pairwise_metadata {
+        use super::*;
+
+        #[test]
+        fn indy_set_pairwise_metadata_works_for_not_created_pairwise() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+            let (_, their_did) = _create_dids(wallet_handle);
+
+            let res = PairwiseUtils::set_pairwise_metadata(wallet_handle, &their_did, Some(METADATA));
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletNotFoundError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+}
@@ -32,11 +32,44 @@ pub const NONCE: &'static [u8; 24] = &[242, 246, 53, 153, 106, 37, 185, 65, 212,
 pub const SIGNATURE: &'static [u8; 64] = &[169, 215, 8, 225, 7, 107, 110, 9, 193, 162, 202, 214, 162, 66, 238, 211, 63, 209, 12, 196, 8, 211, 55, 27, 120, 94, 204, 147, 53, 104, 103, 61, 60, 249, 237, 127, 103, 46, 220, 223, 10, 95, 75, 53, 245, 210, 241, 151, 191, 41, 48, 30, 9, 16, 78, 252, 157, 206, 210, 145, 125, 133, 109, 11];
 pub const TRUSTEE_SEED: &'static str = "000000000000000000000000Trustee1";
 pub const MY1_SEED: &'static str = "00000000000000000000000000000My1";
+pub const MASTER_SEED: &'static str = "GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa";
+pub const ENDPOINT: &'static str = "127.0.0.1:9700";
+pub const TRANSPORT_KEY: &'static str = "Bwsw5CT9cvyX7WcvMPwrwwVWqjJoZjtvpUBcA9PDg1rg";
 
 
 mod high_cases {
     use super::*;
 
+    mod register_crypto_type {
+        use super::*;
+
+        #[test]
+        fn indy_register_crypto_type_works() {
+            SignusUtils::register_crypto_type("dummy", false).unwrap();
+        }
+
+        #[test]
+        fn indy_register_crypto_type_works_for_create_my_did_and_sign() {
+            TestUtils::cleanup_storage();
+
+            SignusUtils::register_crypto_type("dummy", false).unwrap();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let my_did_json = format!(r#"{{"seed":"{}", "crypto_type":"dummy"}}"#, MY1_SEED);
+            let (my_did, my_verkey, _) = SignusUtils::create_my_did(wallet_handle, &my_did_json).unwrap();
+
+            let signature = SignusUtils::sign(wallet_handle, &my_did, MESSAGE.as_bytes()).unwrap();
+
+            let valid = SignusUtils::crypto_verify(&format!("{}:dummy", my_verkey), MESSAGE.as_bytes(), &signature).unwrap();
+            assert!(valid);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
     mod create_my_did {
         use super::*;
         use rust_base58::FromBase58;
@@ -134,6 +167,111 @@ mod high_cases {
 
             TestUtils::cleanup_storage();
         }
+
+        #[test]
+        fn indy_create_my_did_works_for_derivation_path() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, my_verkey, _) = SignusUtils::create_my_did(wallet_handle, r#"{"derivation_path":"0/1"}"#).unwrap();
+
+            assert_eq!(my_did.from_base58().unwrap().len(), 16);
+            assert_eq!(my_verkey.from_base58().unwrap().len(), 32);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_create_my_did_works_for_derivation_path_from_imported_master_seed() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            SignusUtils::import_master_seed(wallet_handle, MASTER_SEED).unwrap();
+
+            let (my_did_1, my_verkey_1, _) = SignusUtils::create_my_did(wallet_handle, r#"{"derivation_path":"0/1"}"#).unwrap();
+            let (my_did_2, my_verkey_2, _) = SignusUtils::create_my_did(wallet_handle, r#"{"derivation_path":"0/2"}"#).unwrap();
+            assert_ne!(my_did_1, my_did_2);
+            assert_ne!(my_verkey_1, my_verkey_2);
+
+            let restored_wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            SignusUtils::import_master_seed(restored_wallet_handle, MASTER_SEED).unwrap();
+
+            let (restored_my_did, restored_my_verkey, _) = SignusUtils::create_my_did(restored_wallet_handle, r#"{"derivation_path":"0/1"}"#).unwrap();
+            assert_eq!(my_did_1, restored_my_did);
+            assert_eq!(my_verkey_1, restored_my_verkey);
+
+            WalletUtils::close_wallet(restored_wallet_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_create_my_did_works_for_method_name() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, my_verkey, _) = SignusUtils::create_my_did(wallet_handle, r#"{"seed":"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa","method_name":"sov"}"#).unwrap();
+
+            assert_eq!(my_did, "did:sov:NcYxiDXkpYi6ov5FcYDi1e");
+            assert_eq!(my_verkey, "CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW");
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_create_my_did_works_for_qualified_did_used_as_bare() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, _, _) = SignusUtils::create_my_did(wallet_handle, &format!(r#"{{"seed":"{}","method_name":"sov"}}"#, MY1_SEED)).unwrap();
+
+            let signature = SignusUtils::sign(wallet_handle, &my_did, MESSAGE.as_bytes()).unwrap();
+            assert_eq!(SIGNATURE.to_vec(), signature);
+
+            let bare_did = my_did.trim_left_matches("did:sov:");
+
+            let signature = SignusUtils::sign(wallet_handle, bare_did, MESSAGE.as_bytes()).unwrap();
+            assert_eq!(SIGNATURE.to_vec(), signature);
+
+            let did_with_meta_json = SignusUtils::get_my_did_with_meta(wallet_handle, bare_did).unwrap();
+            let did_with_meta: serde_json::Value = serde_json::from_str(&did_with_meta_json).unwrap();
+            assert_eq!(my_did, did_with_meta["did"].as_str().unwrap());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+        #[test]
+        fn indy_create_my_did_works_for_same_id_in_different_methods() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (sov_did, _, _) = SignusUtils::create_my_did(wallet_handle, &format!(r#"{{"seed":"{}","method_name":"sov"}}"#, MY1_SEED)).unwrap();
+            let peer_did = sov_did.replace("did:sov:", "did:peer:");
+
+            SignusUtils::create_my_did(wallet_handle, &format!(r#"{{"did":"{}"}}"#, peer_did)).unwrap();
+
+            SignusUtils::set_did_metadata(wallet_handle, &sov_did, "sov metadata").unwrap();
+            SignusUtils::set_did_metadata(wallet_handle, &peer_did, "peer metadata").unwrap();
+
+            assert_eq!(Some("sov metadata".to_string()), SignusUtils::get_did_metadata(wallet_handle, &sov_did).unwrap());
+            assert_eq!(Some("peer metadata".to_string()), SignusUtils::get_did_metadata(wallet_handle, &peer_did).unwrap());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
     }
 
     mod replace_keys {
@@ -205,6 +343,102 @@ mod high_cases {
         }
     }
 
+    mod replace_keys_start {
+        use super::*;
+
+        #[test]
+        fn indy_replace_keys_start_works() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, my_verkey, _) = SignusUtils::create_my_did(wallet_handle, "{}").unwrap();
+
+            let (new_verkey, _) = SignusUtils::replace_keys_start(wallet_handle, &my_did, "{}").unwrap();
+            assert_ne!(my_verkey, new_verkey);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_replace_keys_start_works_for_old_key_still_signing() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(MY1_SEED)).unwrap();
+
+            SignusUtils::replace_keys_start(wallet_handle, &my_did, "{}").unwrap();
+
+            let signature = SignusUtils::sign(wallet_handle, &my_did, MESSAGE.as_bytes()).unwrap();
+            assert_eq!(SIGNATURE.to_vec(), signature);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod replace_keys_apply {
+        use super::*;
+
+        #[test]
+        fn indy_replace_keys_apply_works() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(MY1_SEED)).unwrap();
+
+            SignusUtils::replace_keys_start(wallet_handle, &my_did, "{}").unwrap();
+            SignusUtils::replace_keys_apply(wallet_handle, &my_did).unwrap();
+
+            let signature = SignusUtils::sign(wallet_handle, &my_did, MESSAGE.as_bytes()).unwrap();
+            assert_ne!(SIGNATURE.to_vec(), signature);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_replace_keys_apply_works_without_calling_replace_start() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, _, _) = SignusUtils::create_my_did(wallet_handle, "{}").unwrap();
+
+            let res = SignusUtils::replace_keys_apply(wallet_handle, &my_did);
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletNotFoundError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_replace_keys_apply_works_for_twice() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, _, _) = SignusUtils::create_my_did(wallet_handle, "{}").unwrap();
+
+            SignusUtils::replace_keys_start(wallet_handle, &my_did, "{}").unwrap();
+            SignusUtils::replace_keys_apply(wallet_handle, &my_did).unwrap();
+
+            let res = SignusUtils::replace_keys_apply(wallet_handle, &my_did);
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletNotFoundError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
     mod store_their_did {
         use super::*;
 
@@ -488,6 +722,40 @@ mod high_cases {
         }
     }
 
+    mod verify_with_verkey {
+        use super::*;
+        use rust_base58::{FromBase58, ToBase58};
+
+        #[test]
+        fn indy_verify_with_verkey_works() {
+            let valid = SignusUtils::verify_with_verkey(None, "GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa", MESSAGE.as_bytes(), SIGNATURE).unwrap();
+            assert!(valid);
+        }
+
+        #[test]
+        fn indy_verify_with_verkey_works_for_abbreviated_verkey() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, my_verkey, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(MY1_SEED)).unwrap();
+            let abbreviated_verkey = format!("~{}", my_verkey.from_base58().unwrap()[16..].to_base58());
+
+            let valid = SignusUtils::verify_with_verkey(Some(&my_did), &abbreviated_verkey, MESSAGE.as_bytes(), SIGNATURE).unwrap();
+            assert!(valid);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_verify_with_verkey_works_for_other_signer() {
+            let valid = SignusUtils::verify_with_verkey(None, "CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW", MESSAGE.as_bytes(), SIGNATURE).unwrap();
+            assert!(!valid);
+        }
+    }
+
     mod encrypt {
         use super::*;
 
@@ -727,22 +995,19 @@ mod high_cases {
             TestUtils::cleanup_storage();
         }
     }
-}
-
-mod medium_cases {
-    use super::*;
 
-    mod create_my_did {
+    mod create_key {
         use super::*;
+        use rust_base58::FromBase58;
 
         #[test]
-        fn indy_create_my_did_works_for_invalid_crypto_type() {
+        fn indy_create_key_works_for_seed() {
             TestUtils::cleanup_storage();
 
             let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
 
-            let res = SignusUtils::create_my_did(wallet_handle, r#"{"crypto_type":"type"}"#);
-            assert_eq!(res.unwrap_err(), ErrorCode::SignusUnknownCryptoError);
+            let verkey = SignusUtils::create_key(wallet_handle, Some(MY1_SEED)).unwrap();
+            assert_eq!(verkey, "GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa");
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
 
@@ -750,27 +1015,33 @@ mod medium_cases {
         }
 
         #[test]
-        fn indy_create_my_did_works_for_invalid_seed() {
+        fn indy_create_key_works_without_seed() {
             TestUtils::cleanup_storage();
 
             let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
 
-            let res = SignusUtils::create_my_did(wallet_handle, r#"{"seed":"seed"}"#);
-            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+            let verkey = SignusUtils::create_key(wallet_handle, None).unwrap();
+            assert_eq!(verkey.from_base58().unwrap().len(), 32);
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
 
             TestUtils::cleanup_storage();
         }
+    }
+
+    mod crypto_sign {
+        use super::*;
 
         #[test]
-        fn indy_create_my_did_works_for_invalid_did() {
+        fn indy_crypto_sign_works() {
             TestUtils::cleanup_storage();
 
             let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
 
-            let res = SignusUtils::create_my_did(wallet_handle, r#"{"did":"invalid_base58_did"}"#);
-            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+            let my_vk = SignusUtils::create_key(wallet_handle, Some(MY1_SEED)).unwrap();
+
+            let signature = SignusUtils::crypto_sign(wallet_handle, &my_vk, MESSAGE.as_bytes()).unwrap();
+            assert_eq!(SIGNATURE.to_vec(), signature);
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
 
@@ -778,83 +1049,115 @@ mod medium_cases {
         }
 
         #[test]
-        fn indy_create_my_did_works_for_invalid_json() {
+        fn indy_crypto_sign_works_for_my_did_verkey() {
             TestUtils::cleanup_storage();
 
             let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
 
-            let res = SignusUtils::create_my_did(wallet_handle, r#"{"seed":123}"#);
-            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+            let (_, my_vk, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(MY1_SEED)).unwrap();
+
+            let signature = SignusUtils::crypto_sign(wallet_handle, &my_vk, MESSAGE.as_bytes()).unwrap();
+            assert_eq!(SIGNATURE.to_vec(), signature);
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
 
             TestUtils::cleanup_storage();
         }
-    }
-
-    mod replace_keys {
-        use super::*;
 
         #[test]
-        fn indy_replace_keys_works_for_not_exists_did() {
+        fn indy_crypto_sign_works_for_unknown_key() {
             TestUtils::cleanup_storage();
 
             let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
 
-            //TODO may be we must return WalletNotFound in case if key not exists in wallet
-            SignusUtils::replace_keys(wallet_handle, "8wZcEriaNLNKtteJvx7f8i", "{}").unwrap();
+            let res = SignusUtils::crypto_sign(wallet_handle, "GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa", MESSAGE.as_bytes());
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletNotFoundError);
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
 
             TestUtils::cleanup_storage();
         }
+    }
+
+    mod crypto_verify {
+        use super::*;
 
         #[test]
-        fn indy_replace_keys_works_for_correct_crypto_type() {
+        fn indy_crypto_verify_works() {
+            let valid = SignusUtils::crypto_verify("GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa", MESSAGE.as_bytes(), SIGNATURE).unwrap();
+            assert!(valid);
+        }
+
+        #[test]
+        fn indy_crypto_verify_works_for_other_signer() {
             TestUtils::cleanup_storage();
 
             let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
 
-            let (my_did, my_verkey, _) = SignusUtils::create_my_did(wallet_handle, "{}").unwrap();
+            let their_vk = SignusUtils::create_key(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
 
-            let (new_verkey, _) = SignusUtils::replace_keys(wallet_handle, &my_did, r#"{"crypto_type":"ed25519"}"#).unwrap();
-            assert!(my_verkey != new_verkey);
+            let valid = SignusUtils::crypto_verify(&their_vk, MESSAGE.as_bytes(), SIGNATURE).unwrap();
+            assert!(!valid);
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
 
             TestUtils::cleanup_storage();
         }
+    }
+
+    mod crypto_box {
+        use super::*;
 
         #[test]
-        fn indy_replace_keys_works_for_invalid_crypto_type() {
+        fn indy_crypto_box_works() {
             TestUtils::cleanup_storage();
 
             let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
 
-            let (my_did, _, _) = SignusUtils::create_my_did(wallet_handle, "{}").unwrap();
+            let my_vk = SignusUtils::create_key(wallet_handle, Some(MY1_SEED)).unwrap();
+            let their_vk = SignusUtils::create_key(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
 
-            let res = SignusUtils::replace_keys(wallet_handle, &my_did, r#"{"crypto_type":"type"}"#);
-            assert_eq!(res.unwrap_err(), ErrorCode::SignusUnknownCryptoError);
+            let (encrypted_message, nonce) = SignusUtils::crypto_box(wallet_handle, &my_vk, &their_vk, MESSAGE.as_bytes()).unwrap();
+
+            let decrypted_message = SignusUtils::crypto_box_open(wallet_handle, &their_vk, &my_vk, &encrypted_message, &nonce).unwrap();
+            assert_eq!(MESSAGE.as_bytes().to_vec(), decrypted_message);
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
 
             TestUtils::cleanup_storage();
         }
-    }
 
-    mod store_their_did {
+        #[test]
+        fn indy_crypto_box_works_for_unknown_key() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let their_vk = SignusUtils::create_key(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+
+            let res = SignusUtils::crypto_box(wallet_handle, "GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa", &their_vk, MESSAGE.as_bytes());
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletNotFoundError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod crypto_box_open {
         use super::*;
 
         #[test]
-        fn indy_store_their_did_works_for_invalid_crypto_type() {
+        fn indy_crypto_box_open_works() {
             TestUtils::cleanup_storage();
 
             let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
 
-            let identity_json = r#"{"did":"8wZcEriaNLNKtteJvx7f8i", "crypto_type":"type"}"#;
+            let my_vk = SignusUtils::create_key(wallet_handle, Some(MY1_SEED)).unwrap();
+            let their_vk = SignusUtils::create_key(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
 
-            let res = SignusUtils::store_their_did(wallet_handle, identity_json);
-            assert_eq!(res.unwrap_err(), ErrorCode::SignusUnknownCryptoError);
+            let decrypted_message = SignusUtils::crypto_box_open(wallet_handle, &my_vk, &their_vk, ENCRYPTED_MESSAGE, NONCE).unwrap();
+            assert_eq!(MESSAGE.as_bytes().to_vec(), decrypted_message);
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
 
@@ -862,211 +1165,337 @@ mod medium_cases {
         }
 
         #[test]
-        fn indy_store_their_did_works_for_invalid_did() {
+        fn indy_crypto_box_open_works_for_other_coder() {
             TestUtils::cleanup_storage();
 
             let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
 
-            let identity_json = r#"{"did":"invalid_base58_string"}"#;
+            let my_vk = SignusUtils::create_key(wallet_handle, Some(MY1_SEED)).unwrap();
+            let other_vk = SignusUtils::create_key(wallet_handle, None).unwrap();
 
-            let res = SignusUtils::store_their_did(wallet_handle, identity_json);
+            let res = SignusUtils::crypto_box_open(wallet_handle, &my_vk, &other_vk, ENCRYPTED_MESSAGE, NONCE);
             assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
 
             TestUtils::cleanup_storage();
         }
+    }
+
+    mod anon_crypt {
+        use super::*;
 
         #[test]
-        fn indy_store_their_did_works_for_invalid_verkey() {
+        fn indy_anon_crypt_works() {
             TestUtils::cleanup_storage();
 
             let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
 
-            let identity_json = r#"{"did":"did", "verkey":"verkey"}"#;
+            let my_vk = SignusUtils::create_key(wallet_handle, Some(MY1_SEED)).unwrap();
 
-            let res = SignusUtils::store_their_did(wallet_handle, identity_json);
+            let encrypted_message = SignusUtils::anon_crypt(&my_vk, MESSAGE.as_bytes()).unwrap();
+            assert!(encrypted_message.len() > MESSAGE.len());
+
+            let decrypted_message = SignusUtils::anon_decrypt(wallet_handle, &my_vk, &encrypted_message).unwrap();
+            assert_eq!(MESSAGE.as_bytes().to_vec(), decrypted_message);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_anon_crypt_works_without_wallet() {
+            let encrypted_message = SignusUtils::anon_crypt("GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa", MESSAGE.as_bytes()).unwrap();
+            let other_encrypted_message = SignusUtils::anon_crypt("GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa", MESSAGE.as_bytes()).unwrap();
+
+            assert_ne!(encrypted_message, other_encrypted_message);
+        }
+    }
+
+    mod anon_decrypt {
+        use super::*;
+
+        #[test]
+        fn indy_anon_decrypt_works_for_other_key() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let my_vk = SignusUtils::create_key(wallet_handle, Some(MY1_SEED)).unwrap();
+            let other_vk = SignusUtils::create_key(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+
+            let encrypted_message = SignusUtils::anon_crypt(&my_vk, MESSAGE.as_bytes()).unwrap();
+
+            let res = SignusUtils::anon_decrypt(wallet_handle, &other_vk, &encrypted_message);
             assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
 
             TestUtils::cleanup_storage();
         }
+
+        #[test]
+        fn indy_anon_decrypt_works_for_unknown_key() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let encrypted_message = SignusUtils::anon_crypt("GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa", MESSAGE.as_bytes()).unwrap();
+
+            let res = SignusUtils::anon_decrypt(wallet_handle, "GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa", &encrypted_message);
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletNotFoundError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
     }
 
-    mod verify {
+    mod pack_message {
         use super::*;
 
         #[test]
-        fn indy_verify_works_for_invalid_signature_len() {
+        fn indy_pack_message_works_for_authcrypt() {
             TestUtils::cleanup_storage();
 
-            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
             let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
 
-            let (did, verkey, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
-            let identity_json = format!(r#"{{"did":"{}", "verkey":"{}"}}"#, did, verkey);
+            let sender_vk = SignusUtils::create_key(wallet_handle, Some(MY1_SEED)).unwrap();
+            let recipient_vk = SignusUtils::create_key(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            let receiver_keys = format!(r#"["{}"]"#, recipient_vk);
 
-            SignusUtils::store_their_did(wallet_handle, &identity_json).unwrap();
+            let packed_message = SignusUtils::pack_message(wallet_handle, MESSAGE.as_bytes(), &receiver_keys, Some(&sender_vk)).unwrap();
+            assert!(packed_message.contains(r#""alg":"Authcrypt""#));
+            assert!(!packed_message.contains(MESSAGE));
 
-            let signature: Vec<u8> = vec![20, 191, 100, 213, 101, 12, 197, 198, 203, 49, 89, 220, 205, 192, 224, 221, 97, 77, 220, 190];
+            WalletUtils::close_wallet(wallet_handle).unwrap();
 
-            let res = SignusUtils::verify(wallet_handle, pool_handle, &did, MESSAGE.as_bytes(), &signature);
-            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_pack_message_works_for_anoncrypt() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let recipient_vk = SignusUtils::create_key(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            let receiver_keys = format!(r#"["{}"]"#, recipient_vk);
+
+            let packed_message = SignusUtils::pack_message(wallet_handle, MESSAGE.as_bytes(), &receiver_keys, None).unwrap();
+            assert!(packed_message.contains(r#""alg":"Anoncrypt""#));
 
-            PoolUtils::close(pool_handle).unwrap();
             WalletUtils::close_wallet(wallet_handle).unwrap();
 
             TestUtils::cleanup_storage();
         }
 
         #[test]
-        fn indy_verify_works_for_get_nym_from_ledger_with_incompatible_wallet() {
+        fn indy_pack_message_works_for_unknown_sender_key() {
             TestUtils::cleanup_storage();
 
-            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
-            let wallet_handle = WalletUtils::create_and_open_wallet("other_pool_name", None).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
 
-            let (my_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(MY1_SEED)).unwrap();
+            let recipient_vk = SignusUtils::create_key(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            let receiver_keys = format!(r#"["{}"]"#, recipient_vk);
 
-            let identity_json = format!(r#"{{"did":"{}"}}"#, my_did);
-            SignusUtils::store_their_did(wallet_handle, &identity_json).unwrap();
+            let res = SignusUtils::pack_message(wallet_handle, MESSAGE.as_bytes(), &receiver_keys, Some(TRANSPORT_KEY));
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletNotFoundError);
 
-            let res = SignusUtils::verify(wallet_handle, pool_handle, &my_did, MESSAGE.as_bytes(), SIGNATURE);
-            assert_eq!(res.unwrap_err(), ErrorCode::WalletIncompatiblePoolError);
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod unpack_message {
+        use super::*;
+
+        #[test]
+        fn indy_unpack_message_works_for_authcrypt() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let sender_vk = SignusUtils::create_key(wallet_handle, Some(MY1_SEED)).unwrap();
+            let recipient_vk = SignusUtils::create_key(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            let receiver_keys = format!(r#"["{}"]"#, recipient_vk);
+
+            let packed_message = SignusUtils::pack_message(wallet_handle, MESSAGE.as_bytes(), &receiver_keys, Some(&sender_vk)).unwrap();
+
+            let (message, unpacked_sender_vk, unpacked_recipient_vk) = SignusUtils::unpack_message(wallet_handle, &packed_message).unwrap();
+            assert_eq!(MESSAGE.as_bytes().to_vec(), message);
+            assert_eq!(Some(sender_vk), unpacked_sender_vk);
+            assert_eq!(recipient_vk, unpacked_recipient_vk);
 
-            PoolUtils::close(pool_handle).unwrap();
             WalletUtils::close_wallet(wallet_handle).unwrap();
 
             TestUtils::cleanup_storage();
         }
 
         #[test]
-        fn indy_verify_works_for_get_ledger_not_found_nym() {
+        fn indy_unpack_message_works_for_anoncrypt() {
             TestUtils::cleanup_storage();
 
-            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
             let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
 
-            let (my_did, _, _) = SignusUtils::create_my_did(wallet_handle, r#"{"seed":"0000000000000000000000000000Fake"}"#).unwrap();
+            let recipient_vk = SignusUtils::create_key(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            let receiver_keys = format!(r#"["{}"]"#, recipient_vk);
 
-            let identity_json = format!(r#"{{"did":"{}"}}"#, my_did);
-            SignusUtils::store_their_did(wallet_handle, &identity_json).unwrap();
+            let packed_message = SignusUtils::pack_message(wallet_handle, MESSAGE.as_bytes(), &receiver_keys, None).unwrap();
 
-            let res = SignusUtils::verify(wallet_handle, pool_handle, &my_did, MESSAGE.as_bytes(), SIGNATURE);
-            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidState); //TODO maybe we need add LedgerNotFound error
+            let (message, unpacked_sender_vk, unpacked_recipient_vk) = SignusUtils::unpack_message(wallet_handle, &packed_message).unwrap();
+            assert_eq!(MESSAGE.as_bytes().to_vec(), message);
+            assert_eq!(None, unpacked_sender_vk);
+            assert_eq!(recipient_vk, unpacked_recipient_vk);
 
-            PoolUtils::close(pool_handle).unwrap();
             WalletUtils::close_wallet(wallet_handle).unwrap();
 
             TestUtils::cleanup_storage();
         }
 
         #[test]
-        fn indy_verify_works_for_get_nym_from_ledger() {
+        fn indy_unpack_message_works_for_several_recipients() {
             TestUtils::cleanup_storage();
 
-            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
             let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
 
-            let (trustee_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
-            let (my_did, my_verkey, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(MY1_SEED)).unwrap();
+            let sender_vk = SignusUtils::create_key(wallet_handle, Some(MY1_SEED)).unwrap();
+            let recipient_vk = SignusUtils::create_key(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            let receiver_keys = format!(r#"["{}", "{}"]"#, TRANSPORT_KEY, recipient_vk);
 
-            let nym_request = LedgerUtils::build_nym_request(&trustee_did.clone(), &my_did.clone(), Some(&my_verkey), None, None).unwrap();
-            LedgerUtils::sign_and_submit_request(pool_handle, wallet_handle, &trustee_did, &nym_request).unwrap();
+            let packed_message = SignusUtils::pack_message(wallet_handle, MESSAGE.as_bytes(), &receiver_keys, Some(&sender_vk)).unwrap();
 
-            let valid = SignusUtils::verify(wallet_handle, pool_handle, &my_did, MESSAGE.as_bytes(), SIGNATURE).unwrap();
-            assert!(valid);
+            let (message, _, unpacked_recipient_vk) = SignusUtils::unpack_message(wallet_handle, &packed_message).unwrap();
+            assert_eq!(MESSAGE.as_bytes().to_vec(), message);
+            assert_eq!(recipient_vk, unpacked_recipient_vk);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_unpack_message_works_for_unknown_recipients() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let receiver_keys = format!(r#"["{}"]"#, TRANSPORT_KEY);
+
+            let packed_message = SignusUtils::pack_message(wallet_handle, MESSAGE.as_bytes(), &receiver_keys, None).unwrap();
+
+            let res = SignusUtils::unpack_message(wallet_handle, &packed_message);
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletNotFoundError);
 
-            PoolUtils::close(pool_handle).unwrap();
             WalletUtils::close_wallet(wallet_handle).unwrap();
 
             TestUtils::cleanup_storage();
         }
     }
 
-    mod encrypt {
+    mod set_did_metadata {
         use super::*;
 
         #[test]
-        fn indy_encrypt_works_for_unknow_my_did() {
+        fn indy_set_did_metadata_works() {
             TestUtils::cleanup_storage();
 
-            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
             let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
 
-            let (their_did, their_verkey, _) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+            let (my_did, _, _) = SignusUtils::create_my_did(wallet_handle, "{}").unwrap();
 
-            let identity_json = format!(r#"{{"did":"{}", "verkey":"{}"}}"#, their_did, their_verkey);
-            SignusUtils::store_their_did(wallet_handle, &identity_json).unwrap();
+            SignusUtils::set_did_metadata(wallet_handle, &my_did, "my metadata").unwrap();
 
-            let res = SignusUtils::encrypt(wallet_handle, pool_handle, "unknowDid", &their_did, MESSAGE.as_bytes());
-            assert_eq!(res.unwrap_err(), ErrorCode::WalletNotFoundError);
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_set_did_metadata_works_for_replace() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, _, _) = SignusUtils::create_my_did(wallet_handle, "{}").unwrap();
+
+            SignusUtils::set_did_metadata(wallet_handle, &my_did, "my metadata").unwrap();
+            SignusUtils::set_did_metadata(wallet_handle, &my_did, "new metadata").unwrap();
+
+            let metadata = SignusUtils::get_did_metadata(wallet_handle, &my_did).unwrap();
+            assert_eq!(Some("new metadata".to_string()), metadata);
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
-            PoolUtils::close(pool_handle).unwrap();
 
             TestUtils::cleanup_storage();
         }
+    }
+
+    mod get_did_metadata {
+        use super::*;
 
         #[test]
-        fn indy_encrypt_works_for_get_nym_from_ledger_with_incompatible_pool() {
+        fn indy_get_did_metadata_works() {
             TestUtils::cleanup_storage();
 
-            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
-            let wallet_handle = WalletUtils::create_and_open_wallet("other_pool", None).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
 
-            let (my_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
-            let (their_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+            let (my_did, _, _) = SignusUtils::create_my_did(wallet_handle, "{}").unwrap();
 
-            let identity_json = format!(r#"{{"did":"{}"}}"#, their_did);
-            SignusUtils::store_their_did(wallet_handle, &identity_json).unwrap();
+            SignusUtils::set_did_metadata(wallet_handle, &my_did, "my metadata").unwrap();
 
-            let res = SignusUtils::encrypt(wallet_handle, pool_handle, &my_did, &their_did, MESSAGE.as_bytes());
-            assert_eq!(res.unwrap_err(), ErrorCode::WalletIncompatiblePoolError);
+            let metadata = SignusUtils::get_did_metadata(wallet_handle, &my_did).unwrap();
+            assert_eq!(Some("my metadata".to_string()), metadata);
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
-            PoolUtils::close(pool_handle).unwrap();
 
             TestUtils::cleanup_storage();
         }
 
         #[test]
-        fn indy_encrypt_works_for_not_found_nym() {
+        fn indy_get_did_metadata_works_for_their_did() {
             TestUtils::cleanup_storage();
 
-            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
             let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
 
-            let (my_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
-            let (their_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+            let their_did = "8wZcEriaNLNKtteJvx7f8i";
+            let identity_json = format!(r#"{{"did":"{}"}}"#, their_did);
+            SignusUtils::store_their_did(wallet_handle, &identity_json).unwrap();
 
-            let res = SignusUtils::encrypt(wallet_handle, pool_handle, &my_did, &their_did, MESSAGE.as_bytes());
-            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidState);
+            SignusUtils::set_did_metadata(wallet_handle, their_did, "their metadata").unwrap();
+
+            let metadata = SignusUtils::get_did_metadata(wallet_handle, their_did).unwrap();
+            assert_eq!(Some("their metadata".to_string()), metadata);
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
-            PoolUtils::close(pool_handle).unwrap();
 
             TestUtils::cleanup_storage();
         }
     }
 
-    mod decrypt {
+    mod get_my_did_with_meta {
         use super::*;
 
         #[test]
-        fn indy_decrypt_works_for_unknown_my_did() {
+        fn indy_get_my_did_with_meta_works() {
             TestUtils::cleanup_storage();
 
             let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
 
-            let (their_did, their_verkey, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            let (my_did, my_verkey, _) = SignusUtils::create_my_did(wallet_handle, "{}").unwrap();
 
-            let identity_json = format!(r#"{{"did":"{}", "verkey":"{}"}}"#, their_did, their_verkey);
-            SignusUtils::store_their_did(wallet_handle, &identity_json).unwrap();
+            SignusUtils::set_did_metadata(wallet_handle, &my_did, "my metadata").unwrap();
 
-            let res = SignusUtils::decrypt(wallet_handle, "unknowDid", &their_did, ENCRYPTED_MESSAGE, NONCE);
-            assert_eq!(res.unwrap_err(), ErrorCode::WalletNotFoundError);
+            let did_with_meta_json = SignusUtils::get_my_did_with_meta(wallet_handle, &my_did).unwrap();
+            let did_with_meta: serde_json::Value = serde_json::from_str(&did_with_meta_json).unwrap();
+
+            assert_eq!(my_did, did_with_meta["did"].as_str().unwrap());
+            assert_eq!(my_verkey, did_with_meta["verkey"].as_str().unwrap());
+            assert_eq!("ed25519", did_with_meta["crypto_type"].as_str().unwrap());
+            assert_eq!("my metadata", did_with_meta["metadata"].as_str().unwrap());
+            assert!(did_with_meta["temp_verkey"].is_null());
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
 
@@ -1074,16 +1503,17 @@ mod medium_cases {
         }
 
         #[test]
-        fn indy_decrypt_works_for_unknown_coder_did() {
+        fn indy_get_my_did_with_meta_works_for_no_metadata() {
             TestUtils::cleanup_storage();
 
             let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
 
-            let (my_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(MY1_SEED)).unwrap();
-            let (their_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            let (my_did, _, _) = SignusUtils::create_my_did(wallet_handle, "{}").unwrap();
 
-            let res = SignusUtils::decrypt(wallet_handle, &my_did, &their_did, ENCRYPTED_MESSAGE, NONCE);
-            assert_eq!(res.unwrap_err(), ErrorCode::WalletNotFoundError);
+            let did_with_meta_json = SignusUtils::get_my_did_with_meta(wallet_handle, &my_did).unwrap();
+            let did_with_meta: serde_json::Value = serde_json::from_str(&did_with_meta_json).unwrap();
+
+            assert!(did_with_meta["metadata"].is_null());
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
 
@@ -1091,42 +1521,1190 @@ mod medium_cases {
         }
 
         #[test]
-        fn indy_decrypt_works_for_saved_coder_nym_without_pk() {
+        fn indy_get_my_did_with_meta_works_for_pending_replace_keys() {
             TestUtils::cleanup_storage();
 
             let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
 
-            let (my_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(MY1_SEED)).unwrap();
-            let (their_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            let (my_did, my_verkey, _) = SignusUtils::create_my_did(wallet_handle, "{}").unwrap();
 
-            let identity_json = format!(r#"{{"did":"{}"}}"#, their_did);
-            SignusUtils::store_their_did(wallet_handle, &identity_json).unwrap();
+            let (new_verkey, _) = SignusUtils::replace_keys_start(wallet_handle, &my_did, "{}").unwrap();
 
-            let res = SignusUtils::decrypt(wallet_handle, &my_did, &their_did, ENCRYPTED_MESSAGE, NONCE);
-            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+            let did_with_meta_json = SignusUtils::get_my_did_with_meta(wallet_handle, &my_did).unwrap();
+            let did_with_meta: serde_json::Value = serde_json::from_str(&did_with_meta_json).unwrap();
+
+            assert_eq!(my_verkey, did_with_meta["verkey"].as_str().unwrap());
+            assert_eq!(new_verkey, did_with_meta["temp_verkey"].as_str().unwrap());
+
+            SignusUtils::replace_keys_apply(wallet_handle, &my_did).unwrap();
+
+            let did_with_meta_json = SignusUtils::get_my_did_with_meta(wallet_handle, &my_did).unwrap();
+            let did_with_meta: serde_json::Value = serde_json::from_str(&did_with_meta_json).unwrap();
+
+            assert_eq!(new_verkey, did_with_meta["verkey"].as_str().unwrap());
+            assert!(did_with_meta["temp_verkey"].is_null());
 
             WalletUtils::close_wallet(wallet_handle).unwrap();
 
             TestUtils::cleanup_storage();
         }
+    }
+
+    mod list_my_dids_with_meta {
+        use super::*;
 
         #[test]
-        fn indy_decrypt_works_for_invalid_nonce_len() {
+        fn indy_list_my_dids_with_meta_works() {
             TestUtils::cleanup_storage();
 
             let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
 
-            let (my_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(MY1_SEED)).unwrap();
-            let (their_did, their_verkey, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            let (my_did_1, _, _) = SignusUtils::create_my_did(wallet_handle, "{}").unwrap();
+            let (my_did_2, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(MY1_SEED)).unwrap();
 
-            let identity_json = format!(r#"{{"did":"{}", "verkey":"{}"}}"#, their_did, their_verkey);
-            SignusUtils::store_their_did(wallet_handle, &identity_json).unwrap();
+            SignusUtils::set_did_metadata(wallet_handle, &my_did_1, "my metadata").unwrap();
 
-            let nonce = vec![24, 99, 107, 70, 58, 6, 252, 149, 225];
+            let dids_json = SignusUtils::list_my_dids_with_meta(wallet_handle).unwrap();
+            let dids: Vec<serde_json::Value> = serde_json::from_str(&dids_json).unwrap();
+            assert_eq!(2, dids.len());
 
-            let res = SignusUtils::decrypt(wallet_handle, &my_did, &their_did, ENCRYPTED_MESSAGE, &nonce);
-            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+            let did_1 = dids.iter().find(|did| did["did"].as_str().unwrap() == my_did_1).unwrap();
+            assert_eq!("my metadata", did_1["metadata"].as_str().unwrap());
+
+            let did_2 = dids.iter().find(|did| did["did"].as_str().unwrap() == my_did_2).unwrap();
+            assert!(did_2["metadata"].is_null());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_list_my_dids_with_meta_works_for_empty_wallet() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let dids_json = SignusUtils::list_my_dids_with_meta(wallet_handle).unwrap();
+            let dids: Vec<serde_json::Value> = serde_json::from_str(&dids_json).unwrap();
+            assert!(dids.is_empty());
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod export_master_seed {
+        use super::*;
+        use rust_base58::FromBase58;
+
+        #[test]
+        fn indy_export_master_seed_works() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, _, _) = SignusUtils::create_my_did(wallet_handle, r#"{"derivation_path":"0/1"}"#).unwrap();
+
+            let master_seed = SignusUtils::export_master_seed(wallet_handle).unwrap();
+            assert_eq!(master_seed.from_base58().unwrap().len(), 32);
+
+            let same_master_seed = SignusUtils::export_master_seed(wallet_handle).unwrap();
+            assert_eq!(master_seed, same_master_seed);
 
+            let restored_wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            SignusUtils::import_master_seed(restored_wallet_handle, &master_seed).unwrap();
+
+            let (restored_my_did, _, _) = SignusUtils::create_my_did(restored_wallet_handle, r#"{"derivation_path":"0/1"}"#).unwrap();
+            assert_eq!(my_did, restored_my_did);
+
+            WalletUtils::close_wallet(restored_wallet_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_export_master_seed_works_for_wallet_without_master_seed() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = SignusUtils::export_master_seed(wallet_handle);
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletNotFoundError);
+
+            SignusUtils::import_master_seed(wallet_handle, MASTER_SEED).unwrap();
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_export_master_seed_works_for_imported_master_seed() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            SignusUtils::import_master_seed(wallet_handle, MASTER_SEED).unwrap();
+
+            let master_seed = SignusUtils::export_master_seed(wallet_handle).unwrap();
+            assert_eq!(MASTER_SEED, master_seed);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod import_master_seed {
+        use super::*;
+
+        #[test]
+        fn indy_import_master_seed_works() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            SignusUtils::import_master_seed(wallet_handle, MASTER_SEED).unwrap();
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_import_master_seed_works_for_same_master_seed() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            SignusUtils::import_master_seed(wallet_handle, MASTER_SEED).unwrap();
+            SignusUtils::import_master_seed(wallet_handle, MASTER_SEED).unwrap();
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod export_master_seed_mnemonic {
+        use super::*;
+
+        #[test]
+        fn indy_export_master_seed_mnemonic_works() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            SignusUtils::import_master_seed(wallet_handle, MASTER_SEED).unwrap();
+
+            let mnemonic = SignusUtils::export_master_seed_mnemonic(wallet_handle).unwrap();
+            assert_eq!(mnemonic.split(' ').count(), 24);
+
+            let restored_wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            SignusUtils::import_master_seed_mnemonic(restored_wallet_handle, &mnemonic).unwrap();
+
+            let master_seed = SignusUtils::export_master_seed(restored_wallet_handle).unwrap();
+            assert_eq!(MASTER_SEED, master_seed);
+
+            WalletUtils::close_wallet(restored_wallet_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod set_default_did_method {
+        use super::*;
+
+        #[test]
+        fn indy_set_default_did_method_works_for_reset() {
+            SignusUtils::set_default_did_method(None).unwrap();
+        }
+    }
+
+    mod resolve_did {
+        use super::*;
+
+        #[test]
+        fn indy_resolve_did_works_for_trustee() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (trustee_did, trustee_verkey, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+
+            let did_doc = SignusUtils::resolve_did(wallet_handle, pool_handle, &trustee_did).unwrap();
+            assert!(did_doc.contains(&format!(r#""id":"did:sov:{}""#, trustee_did)));
+            assert!(did_doc.contains(&format!(r#""publicKeyBase58":"{}""#, trustee_verkey)));
+            assert!(did_doc.contains(r#""role":"TRUSTEE""#));
+
+            PoolUtils::close(pool_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_resolve_did_works_for_qualified_did() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (trustee_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            let qualified_did = format!("did:peer:{}", trustee_did);
+
+            let did_doc = SignusUtils::resolve_did(wallet_handle, pool_handle, &qualified_did).unwrap();
+            assert!(did_doc.contains(&format!(r#""id":"{}""#, qualified_did)));
+
+            PoolUtils::close(pool_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_resolve_did_works_for_cached_did_in_other_form() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (trustee_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            let qualified_did = format!("did:sov:{}", trustee_did);
+
+            let did_doc = SignusUtils::resolve_did(wallet_handle, pool_handle, &qualified_did).unwrap();
+            assert!(did_doc.contains(&format!(r#""id":"{}""#, qualified_did)));
+
+            let did_doc = SignusUtils::resolve_did(wallet_handle, pool_handle, &trustee_did).unwrap();
+            assert!(did_doc.contains(&format!(r#""id":"did:sov:{}""#, trustee_did)));
+            assert!(did_doc.contains(&format!(r#""controller":"did:sov:{}""#, trustee_did)));
+
+            PoolUtils::close(pool_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod set_endpoint_for_did {
+        use super::*;
+
+        #[test]
+        fn indy_set_endpoint_for_did_works() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            SignusUtils::set_endpoint_for_did(wallet_handle, &did, ENDPOINT, TRANSPORT_KEY).unwrap();
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod get_endpoint_for_did {
+        use super::*;
+
+        #[test]
+        fn indy_get_endpoint_for_did_works_for_endpoint_set_in_wallet() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            SignusUtils::set_endpoint_for_did(wallet_handle, &did, ENDPOINT, TRANSPORT_KEY).unwrap();
+
+            let (address, transport_key) = SignusUtils::get_endpoint_for_did(wallet_handle, pool_handle, &did).unwrap();
+            assert_eq!(ENDPOINT, address);
+            assert_eq!(TRANSPORT_KEY, transport_key);
+
+            PoolUtils::close(pool_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_get_endpoint_for_did_works_for_published_endpoint() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (trustee_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            let (my_did, my_verkey, _) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            let nym_request = LedgerUtils::build_nym_request(&trustee_did, &my_did, Some(&my_verkey), None, None).unwrap();
+            LedgerUtils::sign_and_submit_request(pool_handle, wallet_handle, &trustee_did, &nym_request).unwrap();
+
+            SignusUtils::publish_endpoint(wallet_handle, pool_handle, &my_did, &my_did, ENDPOINT, TRANSPORT_KEY).unwrap();
+
+            let (address, transport_key) = SignusUtils::get_endpoint_for_did(wallet_handle, pool_handle, &my_did).unwrap();
+            assert_eq!(ENDPOINT, address);
+            assert_eq!(TRANSPORT_KEY, transport_key);
+
+            PoolUtils::close(pool_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+}
+
+mod medium_cases {
+    use super::*;
+
+    mod register_crypto_type {
+        use super::*;
+
+        #[test]
+        fn indy_register_crypto_type_does_not_work_twice_with_same_name() {
+            SignusUtils::register_crypto_type("dummy", false).unwrap();
+
+            let res = SignusUtils::register_crypto_type("dummy", true);
+            assert_eq!(res.unwrap_err(), ErrorCode::SignusCryptoTypeAlreadyRegisteredError);
+        }
+
+        #[test]
+        fn indy_register_crypto_type_does_not_work_for_default_type() {
+            let res = SignusUtils::register_crypto_type("ed25519", true);
+            assert_eq!(res.unwrap_err(), ErrorCode::SignusCryptoTypeAlreadyRegisteredError);
+        }
+    }
+
+    mod create_my_did {
+        use super::*;
+
+        #[test]
+        fn indy_create_my_did_works_for_invalid_crypto_type() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = SignusUtils::create_my_did(wallet_handle, r#"{"crypto_type":"type"}"#);
+            assert_eq!(res.unwrap_err(), ErrorCode::SignusUnknownCryptoError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_create_my_did_works_for_invalid_seed() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = SignusUtils::create_my_did(wallet_handle, r#"{"seed":"seed"}"#);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_create_my_did_works_for_invalid_did() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = SignusUtils::create_my_did(wallet_handle, r#"{"did":"invalid_base58_did"}"#);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_create_my_did_works_for_method_name_mismatch() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = SignusUtils::create_my_did(wallet_handle, r#"{"did":"did:sov:8wZcEriaNLNKtteJvx7f8i","method_name":"peer"}"#);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_create_my_did_works_for_invalid_json() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = SignusUtils::create_my_did(wallet_handle, r#"{"seed":123}"#);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_create_my_did_works_for_seed_and_derivation_path() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = SignusUtils::create_my_did(wallet_handle, r#"{"seed":"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa","derivation_path":"0"}"#);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_create_my_did_works_for_invalid_derivation_path() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = SignusUtils::create_my_did(wallet_handle, r#"{"derivation_path":"0/invalid"}"#);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_create_my_did_works_for_invalid_method_name() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = SignusUtils::create_my_did(wallet_handle, r#"{"method_name":"Invalid:Method"}"#);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod replace_keys {
+        use super::*;
+
+        #[test]
+        fn indy_replace_keys_works_for_not_exists_did() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            //TODO may be we must return WalletNotFound in case if key not exists in wallet
+            SignusUtils::replace_keys(wallet_handle, "8wZcEriaNLNKtteJvx7f8i", "{}").unwrap();
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_replace_keys_works_for_correct_crypto_type() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, my_verkey, _) = SignusUtils::create_my_did(wallet_handle, "{}").unwrap();
+
+            let (new_verkey, _) = SignusUtils::replace_keys(wallet_handle, &my_did, r#"{"crypto_type":"ed25519"}"#).unwrap();
+            assert!(my_verkey != new_verkey);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_replace_keys_works_for_invalid_crypto_type() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, _, _) = SignusUtils::create_my_did(wallet_handle, "{}").unwrap();
+
+            let res = SignusUtils::replace_keys(wallet_handle, &my_did, r#"{"crypto_type":"type"}"#);
+            assert_eq!(res.unwrap_err(), ErrorCode::SignusUnknownCryptoError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod replace_keys_start {
+        use super::*;
+
+        #[test]
+        fn indy_replace_keys_start_works_for_not_exists_did() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = SignusUtils::replace_keys_start(wallet_handle, "8wZcEriaNLNKtteJvx7f8i", "{}");
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletNotFoundError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod store_their_did {
+        use super::*;
+
+        #[test]
+        fn indy_store_their_did_works_for_invalid_crypto_type() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let identity_json = r#"{"did":"8wZcEriaNLNKtteJvx7f8i", "crypto_type":"type"}"#;
+
+            let res = SignusUtils::store_their_did(wallet_handle, identity_json);
+            assert_eq!(res.unwrap_err(), ErrorCode::SignusUnknownCryptoError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_store_their_did_works_for_invalid_did() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let identity_json = r#"{"did":"invalid_base58_string"}"#;
+
+            let res = SignusUtils::store_their_did(wallet_handle, identity_json);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_store_their_did_works_for_invalid_verkey() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let identity_json = r#"{"did":"did", "verkey":"verkey"}"#;
+
+            let res = SignusUtils::store_their_did(wallet_handle, identity_json);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod verify {
+        use super::*;
+
+        #[test]
+        fn indy_verify_works_for_invalid_signature_len() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (did, verkey, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            let identity_json = format!(r#"{{"did":"{}", "verkey":"{}"}}"#, did, verkey);
+
+            SignusUtils::store_their_did(wallet_handle, &identity_json).unwrap();
+
+            let signature: Vec<u8> = vec![20, 191, 100, 213, 101, 12, 197, 198, 203, 49, 89, 220, 205, 192, 224, 221, 97, 77, 220, 190];
+
+            let res = SignusUtils::verify(wallet_handle, pool_handle, &did, MESSAGE.as_bytes(), &signature);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            PoolUtils::close(pool_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_verify_works_for_get_nym_from_ledger_with_incompatible_wallet() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet("other_pool_name", None).unwrap();
+
+            let (my_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(MY1_SEED)).unwrap();
+
+            let identity_json = format!(r#"{{"did":"{}"}}"#, my_did);
+            SignusUtils::store_their_did(wallet_handle, &identity_json).unwrap();
+
+            let res = SignusUtils::verify(wallet_handle, pool_handle, &my_did, MESSAGE.as_bytes(), SIGNATURE);
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletIncompatiblePoolError);
+
+            PoolUtils::close(pool_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_verify_works_for_get_ledger_not_found_nym() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, _, _) = SignusUtils::create_my_did(wallet_handle, r#"{"seed":"0000000000000000000000000000Fake"}"#).unwrap();
+
+            let identity_json = format!(r#"{{"did":"{}"}}"#, my_did);
+            SignusUtils::store_their_did(wallet_handle, &identity_json).unwrap();
+
+            let res = SignusUtils::verify(wallet_handle, pool_handle, &my_did, MESSAGE.as_bytes(), SIGNATURE);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidState); //TODO maybe we need add LedgerNotFound error
+
+            PoolUtils::close(pool_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_verify_works_for_get_nym_from_ledger() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (trustee_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            let (my_did, my_verkey, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(MY1_SEED)).unwrap();
+
+            let nym_request = LedgerUtils::build_nym_request(&trustee_did.clone(), &my_did.clone(), Some(&my_verkey), None, None).unwrap();
+            LedgerUtils::sign_and_submit_request(pool_handle, wallet_handle, &trustee_did, &nym_request).unwrap();
+
+            let valid = SignusUtils::verify(wallet_handle, pool_handle, &my_did, MESSAGE.as_bytes(), SIGNATURE).unwrap();
+            assert!(valid);
+
+            PoolUtils::close(pool_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod encrypt {
+        use super::*;
+
+        #[test]
+        fn indy_encrypt_works_for_unknow_my_did() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (their_did, their_verkey, _) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            let identity_json = format!(r#"{{"did":"{}", "verkey":"{}"}}"#, their_did, their_verkey);
+            SignusUtils::store_their_did(wallet_handle, &identity_json).unwrap();
+
+            let res = SignusUtils::encrypt(wallet_handle, pool_handle, "unknowDid", &their_did, MESSAGE.as_bytes());
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletNotFoundError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            PoolUtils::close(pool_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_encrypt_works_for_get_nym_from_ledger_with_incompatible_pool() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet("other_pool", None).unwrap();
+
+            let (my_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+            let (their_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            let identity_json = format!(r#"{{"did":"{}"}}"#, their_did);
+            SignusUtils::store_their_did(wallet_handle, &identity_json).unwrap();
+
+            let res = SignusUtils::encrypt(wallet_handle, pool_handle, &my_did, &their_did, MESSAGE.as_bytes());
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletIncompatiblePoolError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            PoolUtils::close(pool_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_encrypt_works_for_not_found_nym() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+            let (their_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            let res = SignusUtils::encrypt(wallet_handle, pool_handle, &my_did, &their_did, MESSAGE.as_bytes());
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidState);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+            PoolUtils::close(pool_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod decrypt {
+        use super::*;
+
+        #[test]
+        fn indy_decrypt_works_for_unknown_my_did() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (their_did, their_verkey, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+
+            let identity_json = format!(r#"{{"did":"{}", "verkey":"{}"}}"#, their_did, their_verkey);
+            SignusUtils::store_their_did(wallet_handle, &identity_json).unwrap();
+
+            let res = SignusUtils::decrypt(wallet_handle, "unknowDid", &their_did, ENCRYPTED_MESSAGE, NONCE);
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletNotFoundError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_decrypt_works_for_unknown_coder_did() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(MY1_SEED)).unwrap();
+            let (their_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+
+            let res = SignusUtils::decrypt(wallet_handle, &my_did, &their_did, ENCRYPTED_MESSAGE, NONCE);
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletNotFoundError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_decrypt_works_for_saved_coder_nym_without_pk() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(MY1_SEED)).unwrap();
+            let (their_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+
+            let identity_json = format!(r#"{{"did":"{}"}}"#, their_did);
+            SignusUtils::store_their_did(wallet_handle, &identity_json).unwrap();
+
+            let res = SignusUtils::decrypt(wallet_handle, &my_did, &their_did, ENCRYPTED_MESSAGE, NONCE);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_decrypt_works_for_invalid_nonce_len() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(MY1_SEED)).unwrap();
+            let (their_did, their_verkey, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+
+            let identity_json = format!(r#"{{"did":"{}", "verkey":"{}"}}"#, their_did, their_verkey);
+            SignusUtils::store_their_did(wallet_handle, &identity_json).unwrap();
+
+            let nonce = vec![24, 99, 107, 70, 58, 6, 252, 149, 225];
+
+            let res = SignusUtils::decrypt(wallet_handle, &my_did, &their_did, ENCRYPTED_MESSAGE, &nonce);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod create_key {
+        use super::*;
+
+        #[test]
+        fn indy_create_key_works_for_invalid_seed() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = SignusUtils::create_key(wallet_handle, Some("seed"));
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod crypto_verify {
+        use super::*;
+
+        #[test]
+        fn indy_crypto_verify_works_for_unknown_crypto_type() {
+            let res = SignusUtils::crypto_verify("GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa:type", MESSAGE.as_bytes(), SIGNATURE);
+            assert_eq!(res.unwrap_err(), ErrorCode::SignusUnknownCryptoError);
+        }
+    }
+
+    mod verify_with_verkey {
+        use super::*;
+
+        #[test]
+        fn indy_verify_with_verkey_works_for_abbreviated_verkey_without_did() {
+            let res = SignusUtils::verify_with_verkey(None, "~NcYxiDXkpYi6ov5FcYDi1e", MESSAGE.as_bytes(), SIGNATURE);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+        }
+
+        #[test]
+        fn indy_verify_with_verkey_works_for_invalid_verkey() {
+            let res = SignusUtils::verify_with_verkey(None, "invalid_base58_verkey", MESSAGE.as_bytes(), SIGNATURE);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+        }
+    }
+
+    mod anon_decrypt {
+        use super::*;
+
+        #[test]
+        fn indy_anon_decrypt_works_for_invalid_message() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let my_vk = SignusUtils::create_key(wallet_handle, Some(MY1_SEED)).unwrap();
+
+            let res = SignusUtils::anon_decrypt(wallet_handle, &my_vk, "unencrypted message".as_bytes());
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod pack_message {
+        use super::*;
+
+        #[test]
+        fn indy_pack_message_works_for_invalid_receiver_keys() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = SignusUtils::pack_message(wallet_handle, MESSAGE.as_bytes(), "not a json", None);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            let res = SignusUtils::pack_message(wallet_handle, MESSAGE.as_bytes(), "[]", None);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod unpack_message {
+        use super::*;
+
+        #[test]
+        fn indy_unpack_message_works_for_invalid_packed_message() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = SignusUtils::unpack_message(wallet_handle, r#"{"version":"1.0"}"#);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod get_did_metadata {
+        use super::*;
+
+        #[test]
+        fn indy_get_did_metadata_works_for_no_metadata() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, _, _) = SignusUtils::create_my_did(wallet_handle, "{}").unwrap();
+
+            let metadata = SignusUtils::get_did_metadata(wallet_handle, &my_did).unwrap();
+            assert_eq!(None, metadata);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_get_did_metadata_works_for_invalid_handle() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (my_did, _, _) = SignusUtils::create_my_did(wallet_handle, "{}").unwrap();
+
+            SignusUtils::set_did_metadata(wallet_handle, &my_did, "my metadata").unwrap();
+
+            let res = SignusUtils::get_did_metadata(wallet_handle + 1, &my_did);
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletInvalidHandle);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod get_my_did_with_meta {
+        use super::*;
+
+        #[test]
+        fn indy_get_my_did_with_meta_works_for_unknown_did() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = SignusUtils::get_my_did_with_meta(wallet_handle, "unknowndid");
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletNotFoundError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod list_my_dids_with_meta {
+        use super::*;
+
+        #[test]
+        fn indy_list_my_dids_with_meta_works_for_invalid_handle() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = SignusUtils::list_my_dids_with_meta(wallet_handle + 1);
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletInvalidHandle);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod import_master_seed {
+        use super::*;
+
+        #[test]
+        fn indy_import_master_seed_works_for_another_master_seed() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            SignusUtils::create_my_did(wallet_handle, r#"{"derivation_path":"0/1"}"#).unwrap();
+
+            let res = SignusUtils::import_master_seed(wallet_handle, MASTER_SEED);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidState);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_import_master_seed_works_for_invalid_length() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = SignusUtils::import_master_seed(wallet_handle, "NcYxiDXkpYi6ov5FcYDi1e");
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_import_master_seed_works_for_invalid_base58() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = SignusUtils::import_master_seed(wallet_handle, "invalid_base58_master_seed");
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod export_master_seed_mnemonic {
+        use super::*;
+
+        #[test]
+        fn indy_export_master_seed_mnemonic_works_for_wallet_without_master_seed() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let res = SignusUtils::export_master_seed_mnemonic(wallet_handle);
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletNotFoundError);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod import_master_seed_mnemonic {
+        use super::*;
+
+        #[test]
+        fn indy_import_master_seed_mnemonic_works_for_invalid_checksum() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let mnemonic = format!("{}zoo", "abandon ".repeat(23));
+            let res = SignusUtils::import_master_seed_mnemonic(wallet_handle, &mnemonic);
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod set_default_did_method {
+        use super::*;
+
+        #[test]
+        fn indy_set_default_did_method_works_for_invalid_method_name() {
+            let res = SignusUtils::set_default_did_method(Some("Invalid:Method"));
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+        }
+    }
+
+    mod resolve_did {
+        use super::*;
+
+        #[test]
+        fn indy_resolve_did_works_for_unknown_did() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            let res = SignusUtils::resolve_did(wallet_handle, pool_handle, &did);
+            assert_eq!(res.unwrap_err(), ErrorCode::LedgerNotFound);
+
+            PoolUtils::close(pool_handle).unwrap();
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+
+        #[test]
+        fn indy_resolve_did_works_for_incompatible_wallet() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet("other_pool_name", None).unwrap();
+
+            let (trustee_did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+
+            let res = SignusUtils::resolve_did(wallet_handle, pool_handle, &trustee_did);
+            assert_eq!(res.unwrap_err(), ErrorCode::WalletIncompatiblePoolError);
+
+            PoolUtils::close(pool_handle).unwrap();
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod set_endpoint_for_did {
+        use super::*;
+
+        #[test]
+        fn indy_set_endpoint_for_did_works_for_invalid_transport_key() {
+            TestUtils::cleanup_storage();
+
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            let res = SignusUtils::set_endpoint_for_did(wallet_handle, &did, ENDPOINT, "invalid_base58_0OIl");
+            assert_eq!(res.unwrap_err(), ErrorCode::CommonInvalidStructure);
+
+            WalletUtils::close_wallet(wallet_handle).unwrap();
+
+            TestUtils::cleanup_storage();
+        }
+    }
+
+    mod get_endpoint_for_did {
+        use super::*;
+
+        #[test]
+        fn indy_get_endpoint_for_did_works_for_unknown_did() {
+            TestUtils::cleanup_storage();
+
+            let pool_handle = PoolUtils::create_and_open_pool_ledger(POOL).unwrap();
+            let wallet_handle = WalletUtils::create_and_open_wallet(POOL, None).unwrap();
+
+            let (did, _, _) = SignusUtils::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            let res = SignusUtils::get_endpoint_for_did(wallet_handle, pool_handle, &did);
+            assert_eq!(res.unwrap_err(), ErrorCode::LedgerNotFound);
+
+            PoolUtils::close(pool_handle).unwrap();
             WalletUtils::close_wallet(wallet_handle).unwrap();
 
             TestUtils::cleanup_storage();
@@ -1,14 +1,30 @@
+extern crate libc;
+
 use std::sync::mpsc::{channel};
 use std::ffi::{CString};
+use std::ptr::null;
+
+use self::libc::c_char;
 
 use indy::api::agent::{
     indy_agent_add_identity,
     indy_agent_close_connection,
     indy_agent_close_listener,
     indy_agent_connect,
+    indy_agent_connect_pairwise,
+    indy_agent_get_connection_info,
+    indy_agent_list_connections,
     indy_agent_listen,
+    indy_agent_listen_with_config,
     indy_agent_remove_identity,
+    indy_agent_reply,
+    indy_agent_request,
     indy_agent_send,
+    indy_agent_send_bytes,
+    indy_agent_send_with_ack,
+    indy_agent_set_authorization_cb,
+    indy_agent_set_binary_message_cb,
+    indy_agent_set_message_id_cb,
 };
 use indy::api::ErrorCode;
 
@@ -21,12 +37,22 @@ pub struct AgentUtils {}
 impl AgentUtils {
     pub fn connect(pool_handle: i32, wallet_handle: i32, sender_did: &str, receiver_did: &str,
                    on_msg: Option<Box<Fn(i32, String) + Send>>) -> Result<i32, ErrorCode> {
+        AgentUtils::connect_with_err_cb(pool_handle, wallet_handle, sender_did, receiver_did, on_msg, None)
+    }
+
+    pub fn connect_with_err_cb(pool_handle: i32, wallet_handle: i32, sender_did: &str, receiver_did: &str,
+                               on_msg: Option<Box<Fn(i32, String) + Send>>,
+                               on_err: Option<Box<Fn(i32, ErrorCode) + Send>>) -> Result<i32, ErrorCode> {
         let (sender, receiver) = channel();
         let closure = Box::new(move |err, connection_handle| { sender.send((err, connection_handle)).unwrap(); });
         let (cmd_connect, cb) = CallbackUtils::closure_to_agent_connect_cb(closure);
         let (cb_id, msg_cb) = CallbackUtils::closure_to_agent_message_cb(Box::new(move |conn_handle, err, msg| {
             info!("On connection {} received (with error {:?}) agent message (SRV->CLI): {}", conn_handle, err, msg);
-            if let Some(ref on_msg) = on_msg {
+            if err != ErrorCode::Success {
+                if let Some(ref on_err) = on_err {
+                    on_err(conn_handle, err);
+                }
+            } else if let Some(ref on_msg) = on_msg {
                 on_msg(conn_handle, msg);
             }
         })); //TODO make as parameter?
@@ -48,6 +74,30 @@ impl AgentUtils {
         Ok(conn_handle)
     }
 
+    pub fn connect_pairwise(pool_handle: i32, wallet_handle: i32, their_did: &str) -> Result<i32, ErrorCode> {
+        let (sender, receiver) = channel();
+        let closure = Box::new(move |err, connection_handle| { sender.send((err, connection_handle)).unwrap(); });
+        let (cmd_connect, cb) = CallbackUtils::closure_to_agent_connect_cb(closure);
+        let (cb_id, msg_cb) = CallbackUtils::closure_to_agent_message_cb(Box::new(move |conn_handle, err, msg| {
+            info!("On connection {} received (with error {:?}) agent message (SRV->CLI): {}", conn_handle, err, msg);
+        }));
+
+        let err = indy_agent_connect_pairwise(cmd_connect, pool_handle, wallet_handle,
+                                              CString::new(their_did).unwrap().as_ptr(),
+                                              cb, msg_cb);
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, conn_handle) = receiver.recv_timeout(TimeoutUtils::medium_timeout()).unwrap();
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+        CallbackUtils::closure_map_ids(cb_id, conn_handle);
+
+        Ok(conn_handle)
+    }
+
     pub fn connect_hang_up_expected(pool_handle: i32, wallet_handle: i32, sender_did: &str, receiver_did: &str) -> Result<(ErrorCode, i32), RecvTimeoutError> {
         let (sender, receiver) = channel();
         let closure = Box::new(move |err, connection_handle| { sender.send((err, connection_handle)).unwrap(); });
@@ -67,6 +117,29 @@ impl AgentUtils {
     pub fn listen(endpoint: &str,
                   on_connect: Option<Box<Fn(i32, i32) + Send>>,
                   on_msg: Option<Box<Fn(i32, String) + Send>>) -> Result<i32, ErrorCode> {
+        AgentUtils::listen_with_config(endpoint, None, on_connect, on_msg, None)
+    }
+
+    pub fn listen_with_config(endpoint: &str,
+                              config: Option<&str>,
+                              on_connect: Option<Box<Fn(i32, i32) + Send>>,
+                              on_msg: Option<Box<Fn(i32, String) + Send>>,
+                              on_binary_msg: Option<Box<Fn(i32, Vec<u8>) + Send>>) -> Result<i32, ErrorCode> {
+        AgentUtils::_listen(endpoint, config, on_connect, on_msg, on_binary_msg, None)
+    }
+
+    pub fn listen_with_message_id_cb(endpoint: &str,
+                                     on_connect: Option<Box<Fn(i32, i32) + Send>>,
+                                     on_msg_with_id: Box<Fn(i32, String, String) + Send>) -> Result<i32, ErrorCode> {
+        AgentUtils::_listen(endpoint, None, on_connect, None, None, Some(on_msg_with_id))
+    }
+
+    fn _listen(endpoint: &str,
+               config: Option<&str>,
+               on_connect: Option<Box<Fn(i32, i32) + Send>>,
+               on_msg: Option<Box<Fn(i32, String) + Send>>,
+               on_binary_msg: Option<Box<Fn(i32, Vec<u8>) + Send>>,
+               on_msg_with_id: Option<Box<Fn(i32, String, String) + Send>>) -> Result<i32, ErrorCode> {
         let (sender, receiver) = channel();
         let on_msg = Box::new(move |conn_handle, err, msg| {
             info!("On connection {} received (with error {:?}) agent message (CLI->SRV): {}", conn_handle, err, msg);
@@ -88,7 +161,12 @@ impl AgentUtils {
         let cb = Box::new(move |err, listener_handle| sender.send((err, listener_handle)).unwrap());
         let (cmd_id, cb) = CallbackUtils::closure_to_agent_listen_cb(cb);
 
-        let res = indy_agent_listen(cmd_id, CString::new(endpoint).unwrap().as_ptr(), cb, on_connect, on_msg);
+        let res = match config {
+            Some(config) => indy_agent_listen_with_config(cmd_id, CString::new(endpoint).unwrap().as_ptr(),
+                                                          CString::new(config).unwrap().as_ptr(),
+                                                          cb, on_connect, on_msg),
+            None => indy_agent_listen(cmd_id, CString::new(endpoint).unwrap().as_ptr(), cb, on_connect, on_msg),
+        };
 
         if res != ErrorCode::Success {
             return Err(res);
@@ -100,6 +178,22 @@ impl AgentUtils {
             return Err(res);
         }
 
+        if let Some(on_msg_with_id) = on_msg_with_id {
+            let message_id_cb = CallbackUtils::closure_to_agent_message_id_cb(on_msg_cb_id, Box::new(move |conn_handle, msg, msg_id| {
+                info!("On connection {} received agent message (CLI->SRV): {}, id {}", conn_handle, msg, msg_id);
+                on_msg_with_id(conn_handle, msg, msg_id);
+            }));
+            AgentUtils::set_message_id_cb(listener_handle, message_id_cb)?;
+        }
+
+        if let Some(on_binary_msg) = on_binary_msg {
+            let binary_message_cb = CallbackUtils::closure_to_agent_binary_message_cb(on_msg_cb_id, Box::new(move |conn_handle, msg| {
+                info!("On connection {} received binary agent message (CLI->SRV): {:?}", conn_handle, msg);
+                on_binary_msg(conn_handle, msg);
+            }));
+            AgentUtils::set_binary_message_cb(listener_handle, binary_message_cb)?;
+        }
+
         Ok(listener_handle)
     }
 
@@ -141,6 +235,71 @@ impl AgentUtils {
         Ok(())
     }
 
+    pub fn set_authorization_cb(listener_handle: i32,
+                                authorization_cb: Option<Box<Fn(String, String, Option<String>) -> bool + Send>>) -> Result<(), ErrorCode> {
+        let (sender, receiver) = channel();
+        let (cmd_id, cb) = CallbackUtils::closure_to_agent_set_authorization_cb(
+            Box::new(move |err_code| sender.send(err_code).unwrap())
+        );
+        let authorization_cb = authorization_cb.and_then(|authorization_cb|
+            CallbackUtils::closure_to_agent_authorization_cb(listener_handle, Box::new(move |sender_did, receiver_did, role| {
+                info!("Authorize connection to listener {} from {} to {}, role {:?}", listener_handle, sender_did, receiver_did, role);
+                authorization_cb(sender_did, receiver_did, role)
+            })));
+
+        let res = indy_agent_set_authorization_cb(cmd_id, listener_handle, authorization_cb, cb);
+        if res != ErrorCode::Success {
+            return Err(res);
+        }
+
+        let res = receiver.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+        if res != ErrorCode::Success {
+            return Err(res);
+        }
+
+        Ok(())
+    }
+
+    pub fn set_message_id_cb(handle: i32,
+                             message_id_cb: Option<extern fn(connection_handle: i32, msg: *const c_char, msg_id: *const c_char)>) -> Result<(), ErrorCode> {
+        let (sender, receiver) = channel();
+        let (cmd_id, cb) = CallbackUtils::closure_to_agent_set_message_id_cb(
+            Box::new(move |err_code| sender.send(err_code).unwrap())
+        );
+
+        let res = indy_agent_set_message_id_cb(cmd_id, handle, message_id_cb, cb);
+        if res != ErrorCode::Success {
+            return Err(res);
+        }
+
+        let res = receiver.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+        if res != ErrorCode::Success {
+            return Err(res);
+        }
+
+        Ok(())
+    }
+
+    pub fn set_binary_message_cb(handle: i32,
+                                 binary_message_cb: Option<extern fn(connection_handle: i32, message_raw: *const u8, message_len: u32)>) -> Result<(), ErrorCode> {
+        let (sender, receiver) = channel();
+        let (cmd_id, cb) = CallbackUtils::closure_to_agent_set_binary_message_cb(
+            Box::new(move |err_code| sender.send(err_code).unwrap())
+        );
+
+        let res = indy_agent_set_binary_message_cb(cmd_id, handle, binary_message_cb, cb);
+        if res != ErrorCode::Success {
+            return Err(res);
+        }
+
+        let res = receiver.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+        if res != ErrorCode::Success {
+            return Err(res);
+        }
+
+        Ok(())
+    }
+
     pub fn send(conn_handle: i32, msg: &str) -> Result<(), ErrorCode> {
         let (send_sender, send_receiver) = channel();
         let (send_cmd_id, send_cb) = CallbackUtils::closure_to_agent_send_cb(
@@ -160,6 +319,125 @@ impl AgentUtils {
         Ok(())
     }
 
+    pub fn send_bytes(conn_handle: i32, msg: &[u8]) -> Result<(), ErrorCode> {
+        let (send_sender, send_receiver) = channel();
+        let (send_cmd_id, send_cb) = CallbackUtils::closure_to_agent_send_cb(
+            Box::new(move |err_code| send_sender.send(err_code).unwrap())
+        );
+
+        let res = indy_agent_send_bytes(send_cmd_id, conn_handle, msg.as_ptr(), msg.len() as u32, send_cb);
+        if res != ErrorCode::Success {
+            return Err(res);
+        }
+
+        let res = send_receiver.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+        if res != ErrorCode::Success {
+            return Err(res)
+        }
+
+        Ok(())
+    }
+
+    pub fn send_with_ack(conn_handle: i32, msg_id: Option<&str>, msg: &str) -> Result<String, ErrorCode> {
+        let (sender, receiver) = channel();
+        let (cmd_id, cb) = CallbackUtils::closure_to_agent_send_with_ack_cb(
+            Box::new(move |err_code, msg_id| sender.send((err_code, msg_id)).unwrap())
+        );
+
+        let msg_id = msg_id.map(|id| CString::new(id).unwrap());
+        let res = indy_agent_send_with_ack(cmd_id, conn_handle,
+                                           msg_id.as_ref().map(|id| id.as_ptr()).unwrap_or(null()),
+                                           CString::new(msg).unwrap().as_ptr(), cb);
+        if res != ErrorCode::Success {
+            return Err(res);
+        }
+
+        let (res, msg_id) = receiver.recv_timeout(TimeoutUtils::medium_timeout()).unwrap();
+        if res != ErrorCode::Success {
+            return Err(res)
+        }
+
+        Ok(msg_id)
+    }
+
+    pub fn request(conn_handle: i32, msg: &str, timeout_ms: u32) -> Result<String, ErrorCode> {
+        let (sender, receiver) = channel();
+        let (cmd_id, cb) = CallbackUtils::closure_to_agent_request_cb(
+            Box::new(move |err_code, response| sender.send((err_code, response)).unwrap())
+        );
+
+        let res = indy_agent_request(cmd_id, conn_handle, CString::new(msg).unwrap().as_ptr(), timeout_ms, cb);
+        if res != ErrorCode::Success {
+            return Err(res);
+        }
+
+        let (res, response) = receiver.recv_timeout(TimeoutUtils::medium_timeout()).unwrap();
+        if res != ErrorCode::Success {
+            return Err(res)
+        }
+
+        Ok(response)
+    }
+
+    pub fn reply(conn_handle: i32, request_id: &str, msg: &str) -> Result<(), ErrorCode> {
+        let (sender, receiver) = channel();
+        let (cmd_id, cb) = CallbackUtils::closure_to_agent_reply_cb(
+            Box::new(move |err_code| sender.send(err_code).unwrap())
+        );
+
+        let res = indy_agent_reply(cmd_id, conn_handle,
+                                   CString::new(request_id).unwrap().as_ptr(),
+                                   CString::new(msg).unwrap().as_ptr(), cb);
+        if res != ErrorCode::Success {
+            return Err(res);
+        }
+
+        let res = receiver.recv_timeout(TimeoutUtils::medium_timeout()).unwrap();
+        if res != ErrorCode::Success {
+            return Err(res)
+        }
+
+        Ok(())
+    }
+
+    pub fn list_connections() -> Result<String, ErrorCode> {
+        let (sender, receiver) = channel();
+        let (cmd_id, cb) = CallbackUtils::closure_to_agent_connections_info_cb(
+            Box::new(move |err, connections_json| sender.send((err, connections_json)).unwrap())
+        );
+
+        let res = indy_agent_list_connections(cmd_id, cb);
+        if res != ErrorCode::Success {
+            return Err(res);
+        }
+
+        let (res, connections_json) = receiver.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+        if res != ErrorCode::Success {
+            return Err(res);
+        }
+
+        Ok(connections_json)
+    }
+
+    pub fn get_connection_info(conn_handle: i32) -> Result<String, ErrorCode> {
+        let (sender, receiver) = channel();
+        let (cmd_id, cb) = CallbackUtils::closure_to_agent_connections_info_cb(
+            Box::new(move |err, connection_info_json| sender.send((err, connection_info_json)).unwrap())
+        );
+
+        let res = indy_agent_get_connection_info(cmd_id, conn_handle, cb);
+        if res != ErrorCode::Success {
+            return Err(res);
+        }
+
+        let (res, connection_info_json) = receiver.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+        if res != ErrorCode::Success {
+            return Err(res);
+        }
+
+        Ok(connection_info_json)
+    }
+
     pub fn close_connection(conn_handle: i32) -> Result<(), ErrorCode> {
         let (sender, receiver) = channel();
         let (cmd_id, cb) = CallbackUtils::closure_to_agent_close_cb(Box::new(move |res| {
@@ -442,6 +442,75 @@ impl AnoncredsUtils {
         Ok(revoc_reg_update_json)
     }
 
+    pub fn issuer_generate_safe_primes(wallet_handle: i32, count: i32) -> Result<(), ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err| {
+            sender.send(err).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_issuer_generate_safe_primes_cb(cb);
+
+        let err = indy_issuer_generate_safe_primes(command_handle,
+                                                   wallet_handle,
+                                                   count,
+                                                   cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let err = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    pub fn issuer_import_safe_primes(wallet_handle: i32, safe_primes_json: &str) -> Result<(), ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err| {
+            sender.send(err).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_issuer_import_safe_primes_cb(cb);
+
+        let safe_primes_json = CString::new(safe_primes_json).unwrap();
+
+        let err = indy_issuer_import_safe_primes(command_handle,
+                                                 wallet_handle,
+                                                 safe_primes_json.as_ptr(),
+                                                 cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let err = receiver.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    pub fn get_safe_primes() -> Vec<&'static str> {
+        vec![
+            "298425477551432359319017298068281828134535746771300905126443720735756534287270383542467183175737460443806952398210045827718115111810885752229119677470711305345901926067944629292942471551423868488963517954094239606951758940767987427212463600313901180668176172283994206392965011112962119159458674722785709556623",
+            "349439472409627363409079679396576405429143382066548851902530725651404099527826072557284473504836555030716422598885728830708978735185002598855838705189350963485807782488853612278899610618871178161422072823370241137803351529313449299429833947650792943989791761947876766043905469793223225806020517891749130932603",
+            "316938716248866522366479172267545581464678304265876939947682385941199313696555456782990365726357744959891044195780509734303868540344868226034459836206614527780613726738037363915948464137713744894705160053228334053940292842067189906368220024022749278331238031150167314635218508789898308261347862085359109062623",
+            "319562736340198405972401240971345668526062577562452091192183466339636891791366704760631929184548915189026270975120172058039803507901010971235190077043364444099508511401147748132336863682281208507140699114803419074284365955312278403225510356130390602499874890635196864340146564721444152212880511592886505020199"
+        ]
+    }
+
+    pub fn get_safe_primes_json() -> String {
+        format!(r#"["{}"]"#, AnoncredsUtils::get_safe_primes().join(r#"",""#))
+    }
+
     pub fn get_composite_id(issuer_did: &str, schema_seq_no: i32) -> String {
         issuer_did.to_string() + ":" + &schema_seq_no.to_string()
     }
@@ -250,6 +250,46 @@ impl CallbackUtils {
         (command_handle, Some(closure_to_prover_create_master_secret_callback))
     }
 
+    pub fn closure_to_issuer_generate_safe_primes_cb(closure: Box<FnMut(ErrorCode) + Send>) -> (i32,
+                                                                          Option<extern fn(command_handle: i32,
+                                                                                           err: ErrorCode)>) {
+        lazy_static! {
+            static ref ISSUER_GENERATE_SAFE_PRIMES_CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode) + Send>>> = Default::default();
+        }
+
+        extern "C" fn closure_to_issuer_generate_safe_primes_callback(command_handle: i32, err: ErrorCode) {
+            let mut callbacks = ISSUER_GENERATE_SAFE_PRIMES_CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            cb(err)
+        }
+
+        let mut callbacks = ISSUER_GENERATE_SAFE_PRIMES_CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(closure_to_issuer_generate_safe_primes_callback))
+    }
+
+    pub fn closure_to_issuer_import_safe_primes_cb(closure: Box<FnMut(ErrorCode) + Send>) -> (i32,
+                                                                          Option<extern fn(command_handle: i32,
+                                                                                           err: ErrorCode)>) {
+        lazy_static! {
+            static ref ISSUER_IMPORT_SAFE_PRIMES_CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode) + Send>>> = Default::default();
+        }
+
+        extern "C" fn closure_to_issuer_import_safe_primes_callback(command_handle: i32, err: ErrorCode) {
+            let mut callbacks = ISSUER_IMPORT_SAFE_PRIMES_CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            cb(err)
+        }
+
+        let mut callbacks = ISSUER_IMPORT_SAFE_PRIMES_CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(closure_to_issuer_import_safe_primes_callback))
+    }
+
     pub fn closure_to_prover_create_claim_req_cb(closure: Box<FnMut(ErrorCode, String) + Send>) -> (i32,
                                                                                                     Option<extern fn(command_handle: i32,
                                                                                                                      err: ErrorCode,
@@ -576,11 +616,54 @@ impl CallbackUtils {
         (cb_id, Some(agent_message_callback))
     }
 
+    /// Closure is registered under id of message cb, so connections mapped by closure_map_ids are served.
+    pub fn closure_to_agent_message_id_cb(cb_id: i32, closure: Box<FnMut(i32, String, String) + Send>)
+                                          -> Option<extern fn(connection_handle: i32, msg: *const c_char, msg_id: *const c_char)> {
+        lazy_static! {
+            static ref CALLBACKS: Mutex<HashMap<i32, Box<FnMut(i32, String, String) + Send>>> = Default::default();
+        }
+
+        extern "C" fn agent_message_id_callback(conn_handle: i32, msg: *const c_char, msg_id: *const c_char) {
+            info!("CallbackUtils::agent_message_id_callback");
+            let mut callbacks = CALLBACKS.lock().unwrap();
+            let msg = unsafe { CStr::from_ptr(msg).to_str().unwrap().to_string() };
+            let msg_id = unsafe { CStr::from_ptr(msg_id).to_str().unwrap().to_string() };
+            let cb_id: i32 = *CLOSURE_CB_MAP.lock().unwrap().get(&conn_handle).unwrap();
+            callbacks.get_mut(&cb_id).unwrap()(conn_handle, msg, msg_id);
+        }
+
+        let mut callbacks = CALLBACKS.lock().unwrap();
+        callbacks.insert(cb_id, closure);
+
+        Some(agent_message_id_callback)
+    }
+
     pub fn closure_map_ids(cb_id: i32, param_id: i32) {
         let mut map = CLOSURE_CB_MAP.lock().unwrap();
         map.insert(param_id, cb_id);
     }
 
+    /// Closure is registered under id of message cb, so connections mapped by closure_map_ids are served.
+    pub fn closure_to_agent_binary_message_cb(cb_id: i32, closure: Box<FnMut(i32, Vec<u8>) + Send>)
+                                              -> Option<extern fn(connection_handle: i32, message_raw: *const u8, message_len: u32)> {
+        lazy_static! {
+            static ref CALLBACKS: Mutex<HashMap<i32, Box<FnMut(i32, Vec<u8>) + Send>>> = Default::default();
+        }
+
+        extern "C" fn agent_binary_message_callback(conn_handle: i32, message_raw: *const u8, message_len: u32) {
+            info!("CallbackUtils::agent_binary_message_callback");
+            let mut callbacks = CALLBACKS.lock().unwrap();
+            let message = unsafe { slice::from_raw_parts(message_raw, message_len as usize) }.to_vec();
+            let cb_id: i32 = *CLOSURE_CB_MAP.lock().unwrap().get(&conn_handle).unwrap();
+            callbacks.get_mut(&cb_id).unwrap()(conn_handle, message);
+        }
+
+        let mut callbacks = CALLBACKS.lock().unwrap();
+        callbacks.insert(cb_id, closure);
+
+        Some(agent_binary_message_callback)
+    }
+
     pub fn closure_to_agent_listen_cb(closure: Box<FnMut(ErrorCode, i32) + Send>)
                                       -> (i32,
                                           Option<extern fn(command_handle: i32, err: ErrorCode,
@@ -671,6 +754,110 @@ impl CallbackUtils {
         (command_handle, Some(callback))
     }
 
+    pub fn closure_to_agent_authorization_cb(listener_handle: i32, closure: Box<FnMut(String, String, Option<String>) -> bool + Send>)
+                                             -> Option<extern fn(listener_handle: i32, sender_did: *const c_char,
+                                                                 receiver_did: *const c_char, role: *const c_char) -> bool> {
+        lazy_static! {
+            static ref CALLBACKS: Mutex<HashMap<i32, Box<FnMut(String, String, Option<String>) -> bool + Send>>> = Default::default();
+        }
+
+        extern "C" fn agent_authorization_callback(listener_handle: i32, sender_did: *const c_char,
+                                                   receiver_did: *const c_char, role: *const c_char) -> bool {
+            let mut callbacks = CALLBACKS.lock().unwrap();
+            let sender_did = unsafe { CStr::from_ptr(sender_did).to_str().unwrap().to_string() };
+            let receiver_did = unsafe { CStr::from_ptr(receiver_did).to_str().unwrap().to_string() };
+            let role = if role.is_null() { None } else { Some(unsafe { CStr::from_ptr(role).to_str().unwrap().to_string() }) };
+            callbacks.get_mut(&listener_handle).unwrap()(sender_did, receiver_did, role)
+        }
+
+        let mut callbacks = CALLBACKS.lock().unwrap();
+        callbacks.insert(listener_handle, closure);
+
+        Some(agent_authorization_callback)
+    }
+
+    pub fn closure_to_agent_set_authorization_cb(closure: Box<FnMut(ErrorCode) + Send>) -> (i32,
+                                                                                            Option<extern fn(command_handle: i32,
+                                                                                                             err: ErrorCode)>) {
+        lazy_static! {
+            static ref CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode) + Send>>> = Default::default();
+        }
+
+        extern "C" fn callback(command_handle: i32, err: ErrorCode) {
+            let mut callbacks = CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            cb(err)
+        }
+
+        let mut callbacks = CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(callback))
+    }
+
+    pub fn closure_to_agent_set_message_id_cb(closure: Box<FnMut(ErrorCode) + Send>) -> (i32,
+                                                                                         Option<extern fn(command_handle: i32,
+                                                                                                          err: ErrorCode)>) {
+        lazy_static! {
+            static ref CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode) + Send>>> = Default::default();
+        }
+
+        extern "C" fn callback(command_handle: i32, err: ErrorCode) {
+            let mut callbacks = CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            cb(err)
+        }
+
+        let mut callbacks = CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(callback))
+    }
+
+    pub fn closure_to_agent_set_binary_message_cb(closure: Box<FnMut(ErrorCode) + Send>) -> (i32,
+                                                                                             Option<extern fn(command_handle: i32,
+                                                                                                              err: ErrorCode)>) {
+        lazy_static! {
+            static ref CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode) + Send>>> = Default::default();
+        }
+
+        extern "C" fn callback(command_handle: i32, err: ErrorCode) {
+            let mut callbacks = CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            cb(err)
+        }
+
+        let mut callbacks = CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(callback))
+    }
+
+    pub fn closure_to_agent_connections_info_cb(closure: Box<FnMut(ErrorCode, String) + Send>) -> (i32,
+                                                                                                 Option<extern fn(command_handle: i32,
+                                                                                                                  err: ErrorCode,
+                                                                                                                  info_json: *const c_char)>) {
+        lazy_static! {
+            static ref CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode, String) + Send>>> = Default::default();
+        }
+
+        extern "C" fn callback(command_handle: i32, err: ErrorCode, info_json: *const c_char) {
+            let mut callbacks = CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            let info_json = unsafe { CStr::from_ptr(info_json).to_str().unwrap().to_string() };
+            cb(err, info_json)
+        }
+
+        let mut callbacks = CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(callback))
+    }
+
     pub fn closure_to_agent_send_cb(closure: Box<FnMut(ErrorCode) + Send>) -> (i32,
                                                                                Option<extern fn(command_handle: i32,
                                                                                                 err: ErrorCode)>) {
@@ -691,6 +878,70 @@ impl CallbackUtils {
         (command_handle, Some(callback))
     }
 
+    pub fn closure_to_agent_send_with_ack_cb(closure: Box<FnMut(ErrorCode, String) + Send>) -> (i32,
+                                                                                                Option<extern fn(command_handle: i32,
+                                                                                                                 err: ErrorCode,
+                                                                                                                 message_id: *const c_char)>) {
+        lazy_static! {
+            static ref AGENT_SEND_WITH_ACK_CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode, String) + Send>>> = Default::default();
+        }
+
+        extern "C" fn agent_send_with_ack_callback(command_handle: i32, err: ErrorCode, message_id: *const c_char) {
+            let mut callbacks = AGENT_SEND_WITH_ACK_CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            let message_id = unsafe { CStr::from_ptr(message_id).to_str().unwrap().to_string() };
+            cb(err, message_id)
+        }
+
+        let mut callbacks = AGENT_SEND_WITH_ACK_CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(agent_send_with_ack_callback))
+    }
+
+    pub fn closure_to_agent_request_cb(closure: Box<FnMut(ErrorCode, String) + Send>) -> (i32,
+                                                                                          Option<extern fn(command_handle: i32,
+                                                                                                           err: ErrorCode,
+                                                                                                           response: *const c_char)>) {
+        lazy_static! {
+            static ref AGENT_REQUEST_CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode, String) + Send>>> = Default::default();
+        }
+
+        extern "C" fn agent_request_callback(command_handle: i32, err: ErrorCode, response: *const c_char) {
+            let mut callbacks = AGENT_REQUEST_CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            let response = unsafe { CStr::from_ptr(response).to_str().unwrap().to_string() };
+            cb(err, response)
+        }
+
+        let mut callbacks = AGENT_REQUEST_CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(agent_request_callback))
+    }
+
+    pub fn closure_to_agent_reply_cb(closure: Box<FnMut(ErrorCode) + Send>) -> (i32,
+                                                                                Option<extern fn(command_handle: i32,
+                                                                                                 err: ErrorCode)>) {
+        lazy_static! {
+            static ref AGENT_REPLY_CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode) + Send>>> = Default::default();
+        }
+
+        extern "C" fn agent_reply_callback(command_handle: i32, err: ErrorCode) {
+            let mut callbacks = AGENT_REPLY_CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            cb(err)
+        }
+
+        let mut callbacks = AGENT_REPLY_CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(agent_reply_callback))
+    }
+
     pub fn closure_to_agent_close_cb(closure: Box<FnMut(ErrorCode) + Send>) -> (i32,
                                                                                 Option<extern fn(command_handle: i32,
                                                                                                  err: ErrorCode)>) {
@@ -822,6 +1073,461 @@ impl CallbackUtils {
         (command_handle, Some(replace_keys_callback))
     }
 
+    pub fn closure_to_replace_keys_apply_cb(closure: Box<FnMut(ErrorCode) + Send>) -> (i32,
+                                                                                   Option<extern fn(command_handle: i32,
+                                                                                                    err: ErrorCode)>) {
+        lazy_static! {
+            static ref REPLACE_KEYS_APPLY_CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode) + Send>>> = Default::default();
+        }
+
+        extern "C" fn replace_keys_apply_callback(command_handle: i32, err: ErrorCode) {
+            let mut callbacks = REPLACE_KEYS_APPLY_CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            cb(err)
+        }
+
+        let mut callbacks = REPLACE_KEYS_APPLY_CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(replace_keys_apply_callback))
+    }
+
+    pub fn closure_to_register_crypto_type_cb(closure: Box<FnMut(ErrorCode) + Send>) -> (i32,
+                                                                                         Option<extern fn(command_handle: i32,
+                                                                                                          err: ErrorCode)>) {
+        lazy_static! {
+            static ref REGISTER_CRYPTO_TYPE_CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode) + Send>>> = Default::default();
+        }
+
+        extern "C" fn register_crypto_type_callback(command_handle: i32, err: ErrorCode) {
+            let mut callbacks = REGISTER_CRYPTO_TYPE_CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            cb(err)
+        }
+
+        let mut callbacks = REGISTER_CRYPTO_TYPE_CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(register_crypto_type_callback))
+    }
+
+    pub fn closure_to_create_key_cb(closure: Box<FnMut(ErrorCode, String) + Send>) -> (i32,
+                                                                                      Option<extern fn(command_handle: i32,
+                                                                                                       err: ErrorCode,
+                                                                                                       verkey: *const c_char)>) {
+        lazy_static! {
+            static ref CREATE_KEY_CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode, String) + Send>>> = Default::default();
+        }
+
+        extern "C" fn create_key_callback(command_handle: i32, err: ErrorCode, verkey: *const c_char) {
+            let mut callbacks = CREATE_KEY_CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            let verkey = unsafe { CStr::from_ptr(verkey).to_str().unwrap().to_string() };
+            cb(err, verkey)
+        }
+
+        let mut callbacks = CREATE_KEY_CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(create_key_callback))
+    }
+
+    pub fn closure_to_anon_crypt_cb(closure: Box<FnMut(ErrorCode, Vec<u8>) + Send>) -> (i32,
+                                                                                       Option<extern fn(command_handle: i32,
+                                                                                                        err: ErrorCode,
+                                                                                                        encrypted_msg_raw: *const u8,
+                                                                                                        encrypted_msg_len: u32)>) {
+        lazy_static! {
+            static ref ANON_CRYPT_CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode, Vec<u8>) + Send>>> = Default::default();
+        }
+
+        extern "C" fn anon_crypt_callback(command_handle: i32, err: ErrorCode, encrypted_msg_raw: *const u8, encrypted_msg_len: u32) {
+            let mut callbacks = ANON_CRYPT_CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            let encrypted_msg = unsafe { slice::from_raw_parts(encrypted_msg_raw, encrypted_msg_len as usize) };
+            cb(err, encrypted_msg.to_vec())
+        }
+
+        let mut callbacks = ANON_CRYPT_CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(anon_crypt_callback))
+    }
+
+    pub fn closure_to_set_did_metadata_cb(closure: Box<FnMut(ErrorCode) + Send>) -> (i32,
+                                                                                   Option<extern fn(command_handle: i32,
+                                                                                                    err: ErrorCode)>) {
+        lazy_static! {
+            static ref SET_DID_METADATA_CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode) + Send>>> = Default::default();
+        }
+
+        extern "C" fn set_did_metadata_callback(command_handle: i32, err: ErrorCode) {
+            let mut callbacks = SET_DID_METADATA_CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            cb(err)
+        }
+
+        let mut callbacks = SET_DID_METADATA_CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(set_did_metadata_callback))
+    }
+
+    pub fn closure_to_get_did_metadata_cb(closure: Box<FnMut(ErrorCode, Option<String>) + Send>) -> (i32,
+                                                                                             Option<extern fn(command_handle: i32,
+                                                                                                              err: ErrorCode,
+                                                                                                              metadata: *const c_char)>) {
+        lazy_static! {
+            static ref GET_DID_METADATA_CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode, Option<String>) + Send>>> = Default::default();
+        }
+
+        extern "C" fn get_did_metadata_callback(command_handle: i32, err: ErrorCode, metadata: *const c_char) {
+            let mut callbacks = GET_DID_METADATA_CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            let metadata = if metadata.is_null() { None } else { Some(unsafe { CStr::from_ptr(metadata).to_str().unwrap().to_string() }) };
+            cb(err, metadata)
+        }
+
+        let mut callbacks = GET_DID_METADATA_CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(get_did_metadata_callback))
+    }
+
+    pub fn closure_to_get_my_did_with_meta_cb(closure: Box<FnMut(ErrorCode, String) + Send>) -> (i32,
+                                                                                                 Option<extern fn(command_handle: i32,
+                                                                                                                  err: ErrorCode,
+                                                                                                                  did_with_meta: *const c_char)>) {
+        lazy_static! {
+            static ref GET_MY_DID_WITH_META_CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode, String) + Send>>> = Default::default();
+        }
+
+        extern "C" fn get_my_did_with_meta_callback(command_handle: i32, err: ErrorCode, did_with_meta: *const c_char) {
+            let mut callbacks = GET_MY_DID_WITH_META_CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            let did_with_meta = unsafe { CStr::from_ptr(did_with_meta).to_str().unwrap().to_string() };
+            cb(err, did_with_meta)
+        }
+
+        let mut callbacks = GET_MY_DID_WITH_META_CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(get_my_did_with_meta_callback))
+    }
+
+    pub fn closure_to_list_my_dids_with_meta_cb(closure: Box<FnMut(ErrorCode, String) + Send>) -> (i32,
+                                                                                                   Option<extern fn(command_handle: i32,
+                                                                                                                    err: ErrorCode,
+                                                                                                                    dids: *const c_char)>) {
+        lazy_static! {
+            static ref LIST_MY_DIDS_WITH_META_CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode, String) + Send>>> = Default::default();
+        }
+
+        extern "C" fn list_my_dids_with_meta_callback(command_handle: i32, err: ErrorCode, dids: *const c_char) {
+            let mut callbacks = LIST_MY_DIDS_WITH_META_CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            let dids = unsafe { CStr::from_ptr(dids).to_str().unwrap().to_string() };
+            cb(err, dids)
+        }
+
+        let mut callbacks = LIST_MY_DIDS_WITH_META_CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(list_my_dids_with_meta_callback))
+    }
+
+    pub fn closure_to_export_master_seed_cb(closure: Box<FnMut(ErrorCode, String) + Send>) -> (i32,
+                                                                                               Option<extern fn(command_handle: i32,
+                                                                                                                err: ErrorCode,
+                                                                                                                master_seed: *const c_char)>) {
+        lazy_static! {
+            static ref EXPORT_MASTER_SEED_CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode, String) + Send>>> = Default::default();
+        }
+
+        extern "C" fn export_master_seed_callback(command_handle: i32, err: ErrorCode, master_seed: *const c_char) {
+            let mut callbacks = EXPORT_MASTER_SEED_CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            let master_seed = unsafe { CStr::from_ptr(master_seed).to_str().unwrap().to_string() };
+            cb(err, master_seed)
+        }
+
+        let mut callbacks = EXPORT_MASTER_SEED_CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(export_master_seed_callback))
+    }
+
+    pub fn closure_to_import_master_seed_cb(closure: Box<FnMut(ErrorCode) + Send>) -> (i32,
+                                                                                       Option<extern fn(command_handle: i32,
+                                                                                                        err: ErrorCode)>) {
+        lazy_static! {
+            static ref IMPORT_MASTER_SEED_CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode) + Send>>> = Default::default();
+        }
+
+        extern "C" fn import_master_seed_callback(command_handle: i32, err: ErrorCode) {
+            let mut callbacks = IMPORT_MASTER_SEED_CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            cb(err)
+        }
+
+        let mut callbacks = IMPORT_MASTER_SEED_CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(import_master_seed_callback))
+    }
+
+    pub fn closure_to_set_default_did_method_cb(closure: Box<FnMut(ErrorCode) + Send>) -> (i32,
+                                                                                           Option<extern fn(command_handle: i32,
+                                                                                                            err: ErrorCode)>) {
+        lazy_static! {
+            static ref SET_DEFAULT_DID_METHOD_CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode) + Send>>> = Default::default();
+        }
+
+        extern "C" fn set_default_did_method_callback(command_handle: i32, err: ErrorCode) {
+            let mut callbacks = SET_DEFAULT_DID_METHOD_CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            cb(err)
+        }
+
+        let mut callbacks = SET_DEFAULT_DID_METHOD_CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(set_default_did_method_callback))
+    }
+
+    pub fn closure_to_resolve_did_cb(closure: Box<FnMut(ErrorCode, String) + Send>) -> (i32,
+                                                                                        Option<extern fn(command_handle: i32,
+                                                                                                         err: ErrorCode,
+                                                                                                         did_doc: *const c_char)>) {
+        lazy_static! {
+            static ref RESOLVE_DID_CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode, String) + Send>>> = Default::default();
+        }
+
+        extern "C" fn resolve_did_callback(command_handle: i32, err: ErrorCode, did_doc: *const c_char) {
+            let mut callbacks = RESOLVE_DID_CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            let did_doc = unsafe { CStr::from_ptr(did_doc).to_str().unwrap().to_string() };
+            cb(err, did_doc)
+        }
+
+        let mut callbacks = RESOLVE_DID_CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(resolve_did_callback))
+    }
+
+    pub fn closure_to_set_endpoint_for_did_cb(closure: Box<FnMut(ErrorCode) + Send>) -> (i32,
+                                                                                         Option<extern fn(command_handle: i32,
+                                                                                                          err: ErrorCode)>) {
+        lazy_static! {
+            static ref SET_ENDPOINT_FOR_DID_CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode) + Send>>> = Default::default();
+        }
+
+        extern "C" fn set_endpoint_for_did_callback(command_handle: i32, err: ErrorCode) {
+            let mut callbacks = SET_ENDPOINT_FOR_DID_CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            cb(err)
+        }
+
+        let mut callbacks = SET_ENDPOINT_FOR_DID_CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(set_endpoint_for_did_callback))
+    }
+
+    pub fn closure_to_get_endpoint_for_did_cb(closure: Box<FnMut(ErrorCode, String, String) + Send>) -> (i32,
+                                                                                                         Option<extern fn(command_handle: i32,
+                                                                                                                          err: ErrorCode,
+                                                                                                                          address: *const c_char,
+                                                                                                                          transport_key: *const c_char)>) {
+        lazy_static! {
+            static ref GET_ENDPOINT_FOR_DID_CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode, String, String) + Send>>> = Default::default();
+        }
+
+        extern "C" fn get_endpoint_for_did_callback(command_handle: i32, err: ErrorCode, address: *const c_char, transport_key: *const c_char) {
+            let mut callbacks = GET_ENDPOINT_FOR_DID_CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            let address = unsafe { CStr::from_ptr(address).to_str().unwrap().to_string() };
+            let transport_key = unsafe { CStr::from_ptr(transport_key).to_str().unwrap().to_string() };
+            cb(err, address, transport_key)
+        }
+
+        let mut callbacks = GET_ENDPOINT_FOR_DID_CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(get_endpoint_for_did_callback))
+    }
+
+    pub fn closure_to_publish_endpoint_cb(closure: Box<FnMut(ErrorCode, String) + Send>) -> (i32,
+                                                                                             Option<extern fn(command_handle: i32,
+                                                                                                              err: ErrorCode,
+                                                                                                              request_result_json: *const c_char)>) {
+        lazy_static! {
+            static ref PUBLISH_ENDPOINT_CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode, String) + Send>>> = Default::default();
+        }
+
+        extern "C" fn publish_endpoint_callback(command_handle: i32, err: ErrorCode, request_result_json: *const c_char) {
+            let mut callbacks = PUBLISH_ENDPOINT_CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            let request_result_json = unsafe { CStr::from_ptr(request_result_json).to_str().unwrap().to_string() };
+            cb(err, request_result_json)
+        }
+
+        let mut callbacks = PUBLISH_ENDPOINT_CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(publish_endpoint_callback))
+    }
+
+    pub fn closure_to_pack_message_cb(closure: Box<FnMut(ErrorCode, String) + Send>) -> (i32,
+                                                                                         Option<extern fn(command_handle: i32,
+                                                                                                          err: ErrorCode,
+                                                                                                          packed_message: *const c_char)>) {
+        lazy_static! {
+            static ref PACK_MESSAGE_CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode, String) + Send>>> = Default::default();
+        }
+
+        extern "C" fn pack_message_callback(command_handle: i32, err: ErrorCode, packed_message: *const c_char) {
+            let mut callbacks = PACK_MESSAGE_CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            let packed_message = unsafe { CStr::from_ptr(packed_message).to_str().unwrap().to_string() };
+            cb(err, packed_message)
+        }
+
+        let mut callbacks = PACK_MESSAGE_CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(pack_message_callback))
+    }
+
+    pub fn closure_to_unpack_message_cb(closure: Box<FnMut(ErrorCode, Vec<u8>, Option<String>, String) + Send>) -> (i32,
+                                                                                                                     Option<extern fn(command_handle: i32,
+                                                                                                                                      err: ErrorCode,
+                                                                                                                                      message_raw: *const u8, message_len: u32,
+                                                                                                                                      sender_vk: *const c_char,
+                                                                                                                                      recipient_vk: *const c_char)>) {
+        lazy_static! {
+            static ref UNPACK_MESSAGE_CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode, Vec<u8>, Option<String>, String) + Send>>> = Default::default();
+        }
+
+        extern "C" fn unpack_message_callback(command_handle: i32, err: ErrorCode,
+                                              message_raw: *const u8, message_len: u32,
+                                              sender_vk: *const c_char, recipient_vk: *const c_char) {
+            let mut callbacks = UNPACK_MESSAGE_CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            let message = unsafe { slice::from_raw_parts(message_raw, message_len as usize) };
+            let sender_vk = if sender_vk.is_null() { None } else { Some(unsafe { CStr::from_ptr(sender_vk).to_str().unwrap().to_string() }) };
+            let recipient_vk = unsafe { CStr::from_ptr(recipient_vk).to_str().unwrap().to_string() };
+            cb(err, message.to_vec(), sender_vk, recipient_vk)
+        }
+
+        let mut callbacks = UNPACK_MESSAGE_CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(unpack_message_callback))
+    }
+
+    pub fn closure_to_create_pairwise_cb(closure: Box<FnMut(ErrorCode) + Send>) -> (i32,
+                                                                                    Option<extern fn(command_handle: i32,
+                                                                                                     err: ErrorCode)>) {
+        lazy_static! {
+            static ref CREATE_PAIRWISE_CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode) + Send>>> = Default::default();
+        }
+
+        extern "C" fn create_pairwise_callback(command_handle: i32, err: ErrorCode) {
+            let mut callbacks = CREATE_PAIRWISE_CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            cb(err)
+        }
+
+        let mut callbacks = CREATE_PAIRWISE_CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(create_pairwise_callback))
+    }
+
+    pub fn closure_to_list_pairwise_cb(closure: Box<FnMut(ErrorCode, String) + Send>) -> (i32,
+                                                                                          Option<extern fn(command_handle: i32,
+                                                                                                           err: ErrorCode,
+                                                                                                           list_pairwise: *const c_char)>) {
+        lazy_static! {
+            static ref LIST_PAIRWISE_CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode, String) + Send>>> = Default::default();
+        }
+
+        extern "C" fn list_pairwise_callback(command_handle: i32, err: ErrorCode, list_pairwise: *const c_char) {
+            let mut callbacks = LIST_PAIRWISE_CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            let list_pairwise = unsafe { CStr::from_ptr(list_pairwise).to_str().unwrap().to_string() };
+            cb(err, list_pairwise)
+        }
+
+        let mut callbacks = LIST_PAIRWISE_CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(list_pairwise_callback))
+    }
+
+    pub fn closure_to_get_pairwise_cb(closure: Box<FnMut(ErrorCode, String) + Send>) -> (i32,
+                                                                                         Option<extern fn(command_handle: i32,
+                                                                                                          err: ErrorCode,
+                                                                                                          pairwise_info_json: *const c_char)>) {
+        lazy_static! {
+            static ref GET_PAIRWISE_CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode, String) + Send>>> = Default::default();
+        }
+
+        extern "C" fn get_pairwise_callback(command_handle: i32, err: ErrorCode, pairwise_info_json: *const c_char) {
+            let mut callbacks = GET_PAIRWISE_CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            let pairwise_info_json = unsafe { CStr::from_ptr(pairwise_info_json).to_str().unwrap().to_string() };
+            cb(err, pairwise_info_json)
+        }
+
+        let mut callbacks = GET_PAIRWISE_CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(get_pairwise_callback))
+    }
+
+    pub fn closure_to_set_pairwise_metadata_cb(closure: Box<FnMut(ErrorCode) + Send>) -> (i32,
+                                                                                          Option<extern fn(command_handle: i32,
+                                                                                                           err: ErrorCode)>) {
+        lazy_static! {
+            static ref SET_PAIRWISE_METADATA_CALLBACKS: Mutex<HashMap<i32, Box<FnMut(ErrorCode) + Send>>> = Default::default();
+        }
+
+        extern "C" fn set_pairwise_metadata_callback(command_handle: i32, err: ErrorCode) {
+            let mut callbacks = SET_PAIRWISE_METADATA_CALLBACKS.lock().unwrap();
+            let mut cb = callbacks.remove(&command_handle).unwrap();
+            cb(err)
+        }
+
+        let mut callbacks = SET_PAIRWISE_METADATA_CALLBACKS.lock().unwrap();
+        let command_handle = (COMMAND_HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst) + 1) as i32;
+        callbacks.insert(command_handle, closure);
+
+        (command_handle, Some(set_pairwise_metadata_callback))
+    }
+
     pub fn closure_to_encrypt_cb(closure: Box<FnMut(ErrorCode, Vec<u8>, Vec<u8>) + Send>) -> (i32,
                                                                                               Option<extern fn(command_handle: i32,
                                                                                                                err: ErrorCode,
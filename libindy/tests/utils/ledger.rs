@@ -15,7 +15,16 @@ use indy::api::ledger::{
     indy_build_get_claim_def_txn,
     indy_build_node_request,
     indy_build_nym_request,
-    indy_build_get_txn_request
+    indy_build_get_txn_request,
+    indy_build_revoc_reg_def_request,
+    indy_build_revoc_reg_entry_request,
+    indy_build_get_revoc_reg_def_request,
+    indy_build_get_revoc_reg_request,
+    indy_parse_get_schema_response,
+    indy_parse_get_claim_def_response,
+    indy_parse_get_nym_response,
+    indy_parse_get_revoc_reg_def_response,
+    indy_parse_get_revoc_reg_response
 };
 
 use utils::callback::CallbackUtils;
@@ -488,4 +497,276 @@ impl LedgerUtils {
 
         Ok(request_json)
     }
+
+    pub fn build_revoc_reg_def_request(submitter_did: &str, data: &str) -> Result<String, ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, request_json| {
+            sender.send((err, request_json)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_build_request_cb(cb);
+
+        let submitter_did = CString::new(submitter_did).unwrap();
+        let data = CString::new(data).unwrap();
+
+        let err =
+            indy_build_revoc_reg_def_request(command_handle,
+                                             submitter_did.as_ptr(),
+                                             data.as_ptr(),
+                                             cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, request_json) = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(request_json)
+    }
+
+    pub fn build_revoc_reg_entry_request(submitter_did: &str, data: &str) -> Result<String, ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, request_json| {
+            sender.send((err, request_json)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_build_request_cb(cb);
+
+        let submitter_did = CString::new(submitter_did).unwrap();
+        let data = CString::new(data).unwrap();
+
+        let err =
+            indy_build_revoc_reg_entry_request(command_handle,
+                                               submitter_did.as_ptr(),
+                                               data.as_ptr(),
+                                               cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, request_json) = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(request_json)
+    }
+
+    pub fn build_get_revoc_reg_def_request(submitter_did: &str, id: &str) -> Result<String, ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, request_json| {
+            sender.send((err, request_json)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_build_request_cb(cb);
+
+        let submitter_did = CString::new(submitter_did).unwrap();
+        let id = CString::new(id).unwrap();
+
+        let err =
+            indy_build_get_revoc_reg_def_request(command_handle,
+                                                 submitter_did.as_ptr(),
+                                                 id.as_ptr(),
+                                                 cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, request_json) = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(request_json)
+    }
+
+    pub fn build_get_revoc_reg_request(submitter_did: &str, revoc_reg_def_id: &str, timestamp: i64) -> Result<String, ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, request_json| {
+            sender.send((err, request_json)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_build_request_cb(cb);
+
+        let submitter_did = CString::new(submitter_did).unwrap();
+        let revoc_reg_def_id = CString::new(revoc_reg_def_id).unwrap();
+
+        let err =
+            indy_build_get_revoc_reg_request(command_handle,
+                                             submitter_did.as_ptr(),
+                                             revoc_reg_def_id.as_ptr(),
+                                             timestamp,
+                                             cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, request_json) = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(request_json)
+    }
+
+    pub fn parse_get_schema_response(get_schema_response: &str) -> Result<String, ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, schema_json| {
+            sender.send((err, schema_json)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_build_request_cb(cb);
+
+        let get_schema_response = CString::new(get_schema_response).unwrap();
+
+        let err =
+            indy_parse_get_schema_response(command_handle,
+                                           get_schema_response.as_ptr(),
+                                           cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, schema_json) = receiver.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(schema_json)
+    }
+
+    pub fn parse_get_claim_def_response(get_claim_def_response: &str) -> Result<String, ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, claim_def_json| {
+            sender.send((err, claim_def_json)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_build_request_cb(cb);
+
+        let get_claim_def_response = CString::new(get_claim_def_response).unwrap();
+
+        let err =
+            indy_parse_get_claim_def_response(command_handle,
+                                              get_claim_def_response.as_ptr(),
+                                              cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, claim_def_json) = receiver.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(claim_def_json)
+    }
+
+    pub fn parse_get_nym_response(get_nym_response: &str) -> Result<String, ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, nym_json| {
+            sender.send((err, nym_json)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_build_request_cb(cb);
+
+        let get_nym_response = CString::new(get_nym_response).unwrap();
+
+        let err =
+            indy_parse_get_nym_response(command_handle,
+                                        get_nym_response.as_ptr(),
+                                        cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, nym_json) = receiver.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(nym_json)
+    }
+
+    pub fn parse_get_revoc_reg_def_response(get_revoc_reg_def_response: &str) -> Result<String, ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, revoc_reg_def_json| {
+            sender.send((err, revoc_reg_def_json)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_build_request_cb(cb);
+
+        let get_revoc_reg_def_response = CString::new(get_revoc_reg_def_response).unwrap();
+
+        let err =
+            indy_parse_get_revoc_reg_def_response(command_handle,
+                                                  get_revoc_reg_def_response.as_ptr(),
+                                                  cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, revoc_reg_def_json) = receiver.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(revoc_reg_def_json)
+    }
+
+    pub fn parse_get_revoc_reg_response(revoc_reg_def_json: &str, get_revoc_reg_response: &str) -> Result<String, ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, revoc_reg_json| {
+            sender.send((err, revoc_reg_json)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_build_request_cb(cb);
+
+        let revoc_reg_def_json = CString::new(revoc_reg_def_json).unwrap();
+        let get_revoc_reg_response = CString::new(get_revoc_reg_response).unwrap();
+
+        let err =
+            indy_parse_get_revoc_reg_response(command_handle,
+                                              revoc_reg_def_json.as_ptr(),
+                                              get_revoc_reg_response.as_ptr(),
+                                              cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, revoc_reg_json) = receiver.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(revoc_reg_json)
+    }
 }
@@ -5,6 +5,7 @@ pub mod callback;
 #[path = "../../src/utils/environment.rs"]
 pub mod environment;
 
+pub mod pairwise;
 pub mod pool;
 pub mod signus;
 pub mod wallet;
@@ -33,3 +34,6 @@ pub mod cstring;
 
 #[path = "../../src/utils/inmem_wallet.rs"]
 pub mod inmem_wallet;
+
+#[path = "../../src/utils/dummy_crypto.rs"]
+pub mod dummy_crypto;
@@ -0,0 +1,141 @@
+use indy::api::ErrorCode;
+use indy::api::pairwise::{
+    indy_create_pairwise,
+    indy_list_pairwise,
+    indy_get_pairwise,
+    indy_set_pairwise_metadata
+};
+
+use utils::callback::CallbackUtils;
+use utils::timeout::TimeoutUtils;
+
+use std::ffi::CString;
+use std::ptr::null;
+use std::sync::mpsc::channel;
+
+pub struct PairwiseUtils {}
+
+impl PairwiseUtils {
+    pub fn create_pairwise(wallet_handle: i32, their_did: &str, my_did: &str, metadata: Option<&str>) -> Result<(), ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err| {
+            sender.send(err).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_create_pairwise_cb(cb);
+
+        let their_did = CString::new(their_did).unwrap();
+        let my_did = CString::new(my_did).unwrap();
+        let metadata_str = metadata.map(|s| CString::new(s).unwrap());
+
+        let err =
+            indy_create_pairwise(command_handle,
+                                 wallet_handle,
+                                 their_did.as_ptr(),
+                                 my_did.as_ptr(),
+                                 metadata_str.as_ref().map(|s| s.as_ptr()).unwrap_or(null()),
+                                 cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let err = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    pub fn list_pairwise(wallet_handle: i32) -> Result<String, ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, list_pairwise| {
+            sender.send((err, list_pairwise)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_list_pairwise_cb(cb);
+
+        let err =
+            indy_list_pairwise(command_handle,
+                               wallet_handle,
+                               cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, list_pairwise) = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(list_pairwise)
+    }
+
+    pub fn get_pairwise(wallet_handle: i32, their_did: &str) -> Result<String, ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, pairwise_info_json| {
+            sender.send((err, pairwise_info_json)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_get_pairwise_cb(cb);
+
+        let their_did = CString::new(their_did).unwrap();
+
+        let err =
+            indy_get_pairwise(command_handle,
+                              wallet_handle,
+                              their_did.as_ptr(),
+                              cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, pairwise_info_json) = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(pairwise_info_json)
+    }
+
+    pub fn set_pairwise_metadata(wallet_handle: i32, their_did: &str, metadata: Option<&str>) -> Result<(), ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err| {
+            sender.send(err).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_set_pairwise_metadata_cb(cb);
+
+        let their_did = CString::new(their_did).unwrap();
+        let metadata_str = metadata.map(|s| CString::new(s).unwrap());
+
+        let err =
+            indy_set_pairwise_metadata(command_handle,
+                                       wallet_handle,
+                                       their_did.as_ptr(),
+                                       metadata_str.as_ref().map(|s| s.as_ptr()).unwrap_or(null()),
+                                       cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let err = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(())
+    }
+}
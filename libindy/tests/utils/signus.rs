@@ -1,20 +1,50 @@
 extern crate libc;
 
 use std::sync::mpsc::channel;
+use std::collections::HashSet;
 use std::ffi::CString;
+use std::ptr::null;
+use std::sync::Mutex;
 
 use indy::api::signus::{
+    indy_register_crypto_type,
     indy_sign,
     indy_create_and_store_my_did,
     indy_store_their_did,
     indy_replace_keys,
+    indy_replace_keys_start,
+    indy_replace_keys_apply,
     indy_verify_signature,
+    indy_verify_signature_with_verkey,
     indy_encrypt,
-    indy_decrypt
+    indy_decrypt,
+    indy_create_key,
+    indy_crypto_sign,
+    indy_crypto_verify,
+    indy_crypto_box,
+    indy_crypto_box_open,
+    indy_crypto_anon_crypt,
+    indy_crypto_anon_decrypt,
+    indy_set_did_metadata,
+    indy_get_did_metadata,
+    indy_get_my_did_with_meta,
+    indy_list_my_dids_with_meta,
+    indy_export_master_seed,
+    indy_import_master_seed,
+    indy_export_master_seed_mnemonic,
+    indy_import_master_seed_mnemonic,
+    indy_set_default_did_method,
+    indy_resolve_did,
+    indy_set_endpoint_for_did,
+    indy_get_endpoint_for_did,
+    indy_publish_endpoint,
+    indy_pack_message,
+    indy_unpack_message
 };
 use indy::api::ErrorCode;
 
 use utils::callback::CallbackUtils;
+use utils::dummy_crypto::DummyCrypto;
 use utils::timeout::TimeoutUtils;
 
 pub struct SignusUtils {}
@@ -202,6 +232,68 @@ impl SignusUtils {
         Ok((my_verkey, my_pk))
     }
 
+    pub fn replace_keys_start(wallet_handle: i32, did: &str, identity_json: &str) -> Result<(String, String), ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, verkey, public_key| {
+            sender.send((err, verkey, public_key)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_replace_keys_cb(cb);
+
+        let did = CString::new(did).unwrap();
+        let identity_json = CString::new(identity_json).unwrap();
+
+        let err =
+            indy_replace_keys_start(command_handle,
+                                    wallet_handle,
+                                    did.as_ptr(),
+                                    identity_json.as_ptr(),
+                                    cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, my_verkey, my_pk) = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok((my_verkey, my_pk))
+    }
+
+    pub fn replace_keys_apply(wallet_handle: i32, did: &str) -> Result<(), ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err| {
+            sender.send(err).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_replace_keys_apply_cb(cb);
+
+        let did = CString::new(did).unwrap();
+
+        let err =
+            indy_replace_keys_apply(command_handle,
+                                    wallet_handle,
+                                    did.as_ptr(),
+                                    cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let err = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
     pub fn verify(wallet_handle: i32, pool_handle: i32, did: &str, msg: &[u8], signature: &[u8]) -> Result<bool, ErrorCode> {
         let (sender, receiver) = channel();
 
@@ -237,6 +329,41 @@ impl SignusUtils {
         Ok(valid)
     }
 
+    pub fn verify_with_verkey(did: Option<&str>, verkey: &str, msg: &[u8], signature: &[u8]) -> Result<bool, ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, valid| {
+            sender.send((err, valid)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_verify_signature_cb(cb);
+
+        let did_str = did.map(|did| CString::new(did).unwrap());
+        let verkey = CString::new(verkey).unwrap();
+
+        let err =
+            indy_verify_signature_with_verkey(command_handle,
+                                              did_str.as_ref().map(|did| did.as_ptr()).unwrap_or(null()),
+                                              verkey.as_ptr(),
+                                              msg.as_ptr() as *const u8,
+                                              msg.len() as u32,
+                                              signature.as_ptr() as *const u8,
+                                              signature.len() as u32,
+                                              cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, valid) = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(valid)
+    }
+
     pub fn encrypt(wallet_handle: i32, pool_handle: i32, my_did: &str, did: &str, msg: &[u8]) -> Result<(Vec<u8>, Vec<u8>), ErrorCode> {
         let (sender, receiver) = channel();
 
@@ -307,4 +434,741 @@ impl SignusUtils {
 
         Ok(decrypted_msg)
     }
-}
\ No newline at end of file
+
+    pub fn create_key(wallet_handle: i32, seed: Option<&str>) -> Result<String, ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, verkey| {
+            sender.send((err, verkey)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_create_key_cb(cb);
+
+        let key_json = seed.map_or("{}".to_string(), |seed| format!("{{\"seed\":\"{}\"}}", seed));
+        let key_json = CString::new(key_json).unwrap();
+
+        let err =
+            indy_create_key(command_handle,
+                            wallet_handle,
+                            key_json.as_ptr(),
+                            cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, verkey) = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(verkey)
+    }
+
+    pub fn crypto_sign(wallet_handle: i32, my_vk: &str, msg: &[u8]) -> Result<Vec<u8>, ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, signature| {
+            sender.send((err, signature)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_sign_cb(cb);
+
+        let my_vk = CString::new(my_vk).unwrap();
+
+        let err =
+            indy_crypto_sign(command_handle,
+                             wallet_handle,
+                             my_vk.as_ptr(),
+                             msg.as_ptr() as *const u8,
+                             msg.len() as u32,
+                             cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, signature) = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(signature)
+    }
+
+    pub fn crypto_verify(their_vk: &str, msg: &[u8], signature: &[u8]) -> Result<bool, ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, valid| {
+            sender.send((err, valid)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_verify_signature_cb(cb);
+
+        let their_vk = CString::new(their_vk).unwrap();
+
+        let err =
+            indy_crypto_verify(command_handle,
+                               their_vk.as_ptr(),
+                               msg.as_ptr() as *const u8,
+                               msg.len() as u32,
+                               signature.as_ptr() as *const u8,
+                               signature.len() as u32,
+                               cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, valid) = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(valid)
+    }
+
+    pub fn crypto_box(wallet_handle: i32, my_vk: &str, their_vk: &str, msg: &[u8]) -> Result<(Vec<u8>, Vec<u8>), ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, encrypted_msg, nonce| {
+            sender.send((err, encrypted_msg, nonce)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_encrypt_cb(cb);
+
+        let my_vk = CString::new(my_vk).unwrap();
+        let their_vk = CString::new(their_vk).unwrap();
+
+        let err =
+            indy_crypto_box(command_handle,
+                            wallet_handle,
+                            my_vk.as_ptr(),
+                            their_vk.as_ptr(),
+                            msg.as_ptr() as *const u8,
+                            msg.len() as u32,
+                            cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, encrypted_msg, nonce) = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok((encrypted_msg, nonce))
+    }
+
+    pub fn crypto_box_open(wallet_handle: i32, my_vk: &str, their_vk: &str, encrypted_msg: &[u8], nonce: &[u8]) -> Result<Vec<u8>, ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, decrypted_msg| {
+            sender.send((err, decrypted_msg)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_decrypt_cb(cb);
+
+        let my_vk = CString::new(my_vk).unwrap();
+        let their_vk = CString::new(their_vk).unwrap();
+
+        let err =
+            indy_crypto_box_open(command_handle,
+                                 wallet_handle,
+                                 my_vk.as_ptr(),
+                                 their_vk.as_ptr(),
+                                 encrypted_msg.as_ptr() as *const u8,
+                                 encrypted_msg.len() as u32,
+                                 nonce.as_ptr() as *const u8,
+                                 nonce.len() as u32,
+                                 cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, decrypted_msg) = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(decrypted_msg)
+    }
+
+    pub fn anon_crypt(their_vk: &str, msg: &[u8]) -> Result<Vec<u8>, ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, encrypted_msg| {
+            sender.send((err, encrypted_msg)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_anon_crypt_cb(cb);
+
+        let their_vk = CString::new(their_vk).unwrap();
+
+        let err =
+            indy_crypto_anon_crypt(command_handle,
+                                   their_vk.as_ptr(),
+                                   msg.as_ptr() as *const u8,
+                                   msg.len() as u32,
+                                   cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, encrypted_msg) = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(encrypted_msg)
+    }
+
+    pub fn anon_decrypt(wallet_handle: i32, my_vk: &str, encrypted_msg: &[u8]) -> Result<Vec<u8>, ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, decrypted_msg| {
+            sender.send((err, decrypted_msg)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_decrypt_cb(cb);
+
+        let my_vk = CString::new(my_vk).unwrap();
+
+        let err =
+            indy_crypto_anon_decrypt(command_handle,
+                                     wallet_handle,
+                                     my_vk.as_ptr(),
+                                     encrypted_msg.as_ptr() as *const u8,
+                                     encrypted_msg.len() as u32,
+                                     cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, decrypted_msg) = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(decrypted_msg)
+    }
+
+    pub fn register_crypto_type(xtype: &str, force_create: bool) -> Result<(), ErrorCode> {
+        lazy_static! {
+            static ref REGISERED_CRYPTO_TYPES: Mutex<HashSet<String>> = Default::default();
+        }
+
+        let mut crypto_types = REGISERED_CRYPTO_TYPES.lock().unwrap();
+
+        if crypto_types.contains(xtype) & !force_create {
+            // as registering of plugged crypto type is global for library
+            return Ok(())
+        }
+
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err| {
+            sender.send(err).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_register_crypto_type_cb(cb);
+
+        let xxtype = CString::new(xtype).unwrap();
+
+        let err = indy_register_crypto_type(
+            command_handle,
+            xxtype.as_ptr(),
+            Some(DummyCrypto::create_key),
+            Some(DummyCrypto::sign),
+            Some(DummyCrypto::verify),
+            Some(DummyCrypto::encrypt),
+            Some(DummyCrypto::decrypt),
+            Some(DummyCrypto::free),
+            cb
+        );
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let err = receiver.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        crypto_types.insert(xtype.to_string());
+        Ok(())
+    }
+
+    pub fn set_did_metadata(wallet_handle: i32, did: &str, metadata: &str) -> Result<(), ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err| {
+            sender.send(err).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_set_did_metadata_cb(cb);
+
+        let did = CString::new(did).unwrap();
+        let metadata = CString::new(metadata).unwrap();
+
+        let err =
+            indy_set_did_metadata(command_handle,
+                                  wallet_handle,
+                                  did.as_ptr(),
+                                  metadata.as_ptr(),
+                                  cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let err = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    pub fn get_did_metadata(wallet_handle: i32, did: &str) -> Result<Option<String>, ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, metadata| {
+            sender.send((err, metadata)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_get_did_metadata_cb(cb);
+
+        let did = CString::new(did).unwrap();
+
+        let err =
+            indy_get_did_metadata(command_handle,
+                                  wallet_handle,
+                                  did.as_ptr(),
+                                  cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, metadata) = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(metadata)
+    }
+
+    pub fn get_my_did_with_meta(wallet_handle: i32, my_did: &str) -> Result<String, ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, did_with_meta| {
+            sender.send((err, did_with_meta)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_get_my_did_with_meta_cb(cb);
+
+        let my_did = CString::new(my_did).unwrap();
+
+        let err =
+            indy_get_my_did_with_meta(command_handle,
+                                      wallet_handle,
+                                      my_did.as_ptr(),
+                                      cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, did_with_meta) = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(did_with_meta)
+    }
+
+    pub fn list_my_dids_with_meta(wallet_handle: i32) -> Result<String, ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, dids| {
+            sender.send((err, dids)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_list_my_dids_with_meta_cb(cb);
+
+        let err =
+            indy_list_my_dids_with_meta(command_handle,
+                                        wallet_handle,
+                                        cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, dids) = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(dids)
+    }
+
+    pub fn export_master_seed(wallet_handle: i32) -> Result<String, ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, master_seed| {
+            sender.send((err, master_seed)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_export_master_seed_cb(cb);
+
+        let err =
+            indy_export_master_seed(command_handle,
+                                    wallet_handle,
+                                    cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, master_seed) = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(master_seed)
+    }
+
+    pub fn import_master_seed(wallet_handle: i32, master_seed: &str) -> Result<(), ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err| {
+            sender.send(err).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_import_master_seed_cb(cb);
+
+        let master_seed = CString::new(master_seed).unwrap();
+
+        let err =
+            indy_import_master_seed(command_handle,
+                                    wallet_handle,
+                                    master_seed.as_ptr(),
+                                    cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let err = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    pub fn export_master_seed_mnemonic(wallet_handle: i32) -> Result<String, ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, mnemonic| {
+            sender.send((err, mnemonic)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_export_master_seed_cb(cb);
+
+        let err =
+            indy_export_master_seed_mnemonic(command_handle,
+                                             wallet_handle,
+                                             cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, mnemonic) = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(mnemonic)
+    }
+
+    pub fn import_master_seed_mnemonic(wallet_handle: i32, mnemonic: &str) -> Result<(), ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err| {
+            sender.send(err).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_import_master_seed_cb(cb);
+
+        let mnemonic = CString::new(mnemonic).unwrap();
+
+        let err =
+            indy_import_master_seed_mnemonic(command_handle,
+                                             wallet_handle,
+                                             mnemonic.as_ptr(),
+                                             cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let err = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    pub fn set_default_did_method(method_name: Option<&str>) -> Result<(), ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err| {
+            sender.send(err).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_set_default_did_method_cb(cb);
+
+        let method_name_str = method_name.map(|method_name| CString::new(method_name).unwrap());
+
+        let err =
+            indy_set_default_did_method(command_handle,
+                                        method_name_str.as_ref().map(|method_name| method_name.as_ptr()).unwrap_or(null()),
+                                        cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let err = receiver.recv_timeout(TimeoutUtils::short_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    pub fn resolve_did(wallet_handle: i32, pool_handle: i32, did: &str) -> Result<String, ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, did_doc| {
+            sender.send((err, did_doc)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_resolve_did_cb(cb);
+
+        let did = CString::new(did).unwrap();
+
+        let err =
+            indy_resolve_did(command_handle,
+                             wallet_handle,
+                             pool_handle,
+                             did.as_ptr(),
+                             cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, did_doc) = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(did_doc)
+    }
+
+    pub fn set_endpoint_for_did(wallet_handle: i32, did: &str, address: &str, transport_key: &str) -> Result<(), ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err| {
+            sender.send(err).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_set_endpoint_for_did_cb(cb);
+
+        let did = CString::new(did).unwrap();
+        let address = CString::new(address).unwrap();
+        let transport_key = CString::new(transport_key).unwrap();
+
+        let err =
+            indy_set_endpoint_for_did(command_handle,
+                                      wallet_handle,
+                                      did.as_ptr(),
+                                      address.as_ptr(),
+                                      transport_key.as_ptr(),
+                                      cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let err = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    pub fn get_endpoint_for_did(wallet_handle: i32, pool_handle: i32, did: &str) -> Result<(String, String), ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, address, transport_key| {
+            sender.send((err, address, transport_key)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_get_endpoint_for_did_cb(cb);
+
+        let did = CString::new(did).unwrap();
+
+        let err =
+            indy_get_endpoint_for_did(command_handle,
+                                      wallet_handle,
+                                      pool_handle,
+                                      did.as_ptr(),
+                                      cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, address, transport_key) = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok((address, transport_key))
+    }
+
+    pub fn publish_endpoint(wallet_handle: i32, pool_handle: i32, submitter_did: &str, did: &str,
+                            address: &str, transport_key: &str) -> Result<String, ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, request_result_json| {
+            sender.send((err, request_result_json)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_publish_endpoint_cb(cb);
+
+        let submitter_did = CString::new(submitter_did).unwrap();
+        let did = CString::new(did).unwrap();
+        let address = CString::new(address).unwrap();
+        let transport_key = CString::new(transport_key).unwrap();
+
+        let err =
+            indy_publish_endpoint(command_handle,
+                                  wallet_handle,
+                                  pool_handle,
+                                  submitter_did.as_ptr(),
+                                  did.as_ptr(),
+                                  address.as_ptr(),
+                                  transport_key.as_ptr(),
+                                  cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, request_result_json) = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(request_result_json)
+    }
+
+    pub fn pack_message(wallet_handle: i32, message: &[u8], receiver_keys: &str, sender_vk: Option<&str>) -> Result<String, ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, packed_message| {
+            sender.send((err, packed_message)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_pack_message_cb(cb);
+
+        let receiver_keys = CString::new(receiver_keys).unwrap();
+        let sender_vk_str = sender_vk.map(|s| CString::new(s).unwrap());
+
+        let err =
+            indy_pack_message(command_handle,
+                              wallet_handle,
+                              message.as_ptr() as *const u8,
+                              message.len() as u32,
+                              receiver_keys.as_ptr(),
+                              sender_vk_str.as_ref().map(|s| s.as_ptr()).unwrap_or(null()),
+                              cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, packed_message) = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok(packed_message)
+    }
+
+    pub fn unpack_message(wallet_handle: i32, packed_message: &str) -> Result<(Vec<u8>, Option<String>, String), ErrorCode> {
+        let (sender, receiver) = channel();
+
+        let cb = Box::new(move |err, message, sender_vk, recipient_vk| {
+            sender.send((err, message, sender_vk, recipient_vk)).unwrap();
+        });
+
+        let (command_handle, cb) = CallbackUtils::closure_to_unpack_message_cb(cb);
+
+        let packed_message = CString::new(packed_message).unwrap();
+
+        let err =
+            indy_unpack_message(command_handle,
+                                wallet_handle,
+                                packed_message.as_ptr(),
+                                cb);
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        let (err, message, sender_vk, recipient_vk) = receiver.recv_timeout(TimeoutUtils::long_timeout()).unwrap();
+
+        if err != ErrorCode::Success {
+            return Err(err);
+        }
+
+        Ok((message, sender_vk, recipient_vk))
+    }
+}